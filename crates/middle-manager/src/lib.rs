@@ -0,0 +1,177 @@
+//! Embeddable Middle Manager memory graph.
+//!
+//! [`MiddleManager`] connects to the backend named in a [`Config`] and
+//! exposes the core operations directly, without running the MCP server:
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use middle_manager::{Config, MiddleManager};
+//!
+//! let mm = MiddleManager::connect(Config::load(&["config/local.toml"])?).await?;
+//! let tasks = mm.list_tasks(Some("my-project")).await?;
+//! # Ok(())
+//! # }
+//! ```
+#![warn(clippy::all)]
+
+use std::path::Path;
+use std::sync::Arc;
+
+use mm_core::operations::memory::{
+    CreateEntitiesCommand, CreateEntitiesOutcome, CreateTasksCommand, FindEntitiesByLabelsCommand,
+    FindEntitiesByLabelsResult, GetEntityCommand, GetProjectContextCommand, ListTasksCommand,
+    ProjectContext, ProjectFilter, TaskInput, TaskProperties, create_entities, create_tasks,
+    find_entities_by_labels, get_entity, get_project_context, list_tasks,
+};
+use mm_memory::{LabelMatchMode, MemoryEntity, MemoryResult, MemoryService};
+use mm_server::backend::create_git_service;
+
+pub use mm_core::{CoreError, CoreResult, Ports, operations};
+pub use mm_memory::{self as memory, MemoryConfig};
+pub use mm_server::Config;
+pub use mm_server::backend::{BackendError, BackendKind, GitBackend, MemoryBackend};
+
+/// Result of the facade's operations
+pub type Result<T> = CoreResult<T, BackendError>;
+
+/// Handle to a memory graph
+pub struct MiddleManager {
+    ports: Ports<MemoryBackend, GitBackend>,
+}
+
+impl MiddleManager {
+    /// Connect to the backend selected in `config`
+    pub async fn connect(config: Config) -> MemoryResult<Self, BackendError> {
+        let repository = MemoryBackend::connect(&config, &config.memory).await?;
+        let memory_service = MemoryService::new(repository, config.memory);
+        Ok(Self {
+            ports: Ports::new(Arc::new(memory_service), Arc::new(create_git_service())),
+        })
+    }
+
+    /// Load configuration files the way `mm-cli` does and connect
+    pub async fn from_config_files<P: AsRef<Path>>(paths: &[P]) -> anyhow::Result<Self> {
+        let (_, ports) = mm_server::create_ports_from_config(paths).await?;
+        Ok(Self { ports })
+    }
+
+    /// Services backing this handle, for operations without a shortcut here
+    pub fn ports(&self) -> &Ports<MemoryBackend, GitBackend> {
+        &self.ports
+    }
+
+    /// Create entities using the vocabulary of `project_name`, or the default project
+    pub async fn create_entities(
+        &self,
+        entities: Vec<MemoryEntity>,
+        project_name: Option<&str>,
+    ) -> Result<CreateEntitiesOutcome> {
+        let command = CreateEntitiesCommand {
+            entities,
+            confidence: None,
+            source: None,
+            project_name: project_name.map(str::to_string),
+        };
+        create_entities(&self.ports, command).await
+    }
+
+    /// Entity named `name`, if any
+    pub async fn get_entity(&self, name: &str) -> Result<Option<MemoryEntity>> {
+        let command = GetEntityCommand {
+            name: name.to_string(),
+        };
+        get_entity(&self.ports, command).await
+    }
+
+    /// Entities carrying any (or all, per `match_mode`) of `labels`
+    pub async fn find_entities_by_labels(
+        &self,
+        labels: &[&str],
+        match_mode: LabelMatchMode,
+    ) -> Result<Vec<MemoryEntity>> {
+        let command = FindEntitiesByLabelsCommand {
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            match_mode,
+            required_label: None,
+            min_confidence: None,
+            limit: None,
+            cursor: None,
+            properties: Vec::new(),
+            sort_by: Vec::new(),
+            exclude_labels: Vec::new(),
+        };
+        let FindEntitiesByLabelsResult { entities, .. } =
+            find_entities_by_labels(&self.ports, command).await?;
+        Ok(entities)
+    }
+
+    /// Create tasks in `project_name`, or the default project
+    pub async fn create_tasks(
+        &self,
+        tasks: Vec<TaskInput>,
+        project_name: Option<&str>,
+    ) -> Result<()> {
+        let command = CreateTasksCommand {
+            tasks,
+            project_name: project_name.map(str::to_string),
+            calibrate_estimates: false,
+        };
+        create_tasks(&self.ports, command).await
+    }
+
+    /// All tasks of `project_name`, or the default project
+    pub async fn list_tasks(
+        &self,
+        project_name: Option<&str>,
+    ) -> Result<Vec<MemoryEntity<TaskProperties>>> {
+        let command = ListTasksCommand {
+            project_name: project_name.map(str::to_string),
+            labels: Vec::new(),
+            status: None,
+            priority: None,
+            due_before: None,
+            due_after: None,
+        };
+        Ok(list_tasks(&self.ports, command).await?.tasks)
+    }
+
+    /// Context of the project found by `filter`
+    pub async fn project_context(&self, filter: ProjectFilter) -> Result<ProjectContext> {
+        let command = GetProjectContextCommand {
+            filter,
+            exclude_labels: Vec::new(),
+        };
+        Ok(get_project_context(&self.ports, command).await?.context)
+    }
+}
+
+#[cfg(all(test, feature = "inmem"))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_embedded_round_trip() {
+        let config = Config {
+            backend: BackendKind::Inmem,
+            ..Config::default()
+        };
+        let mm = MiddleManager::connect(config).await.unwrap();
+
+        let entity = MemoryEntity {
+            name: "embedded:example".to_string(),
+            labels: vec!["Feature".to_string()],
+            observations: vec!["created without MCP".to_string()],
+            ..Default::default()
+        };
+        mm.create_entities(vec![entity], None).await.unwrap();
+
+        let found = mm.get_entity("embedded:example").await.unwrap().unwrap();
+        assert_eq!(found.observations, vec!["created without MCP".to_string()]);
+
+        let by_label = mm
+            .find_entities_by_labels(&["Feature"], LabelMatchMode::Any)
+            .await
+            .unwrap();
+        assert_eq!(by_label.len(), 1);
+    }
+}
@@ -1,3 +1,8 @@
+use mm_core::mm_memory::{AuditEntry, MemoryConfig};
+use mm_core::operations::memory::{
+    BiasFactor, CriticalPathReport, DuplicateCandidate, EstimateCalibration, ForgetProposal,
+    TaskGraph, TaskReport, TaskStatus, UnknownTerm, UnusedTerm, VocabularyLint,
+};
 use tabled::{Table, Tabled};
 
 #[derive(Tabled)]
@@ -65,15 +70,290 @@ pub fn format_task_detail(task: &serde_json::Value) -> String {
     if let Some(updated) = task["properties"]["updated_at"].as_str() {
         out.push_str(&format!("Updated: {}\n", updated));
     }
-    if let Some(obs) = task["observations"].as_array() {
-        if !obs.is_empty() {
-            out.push_str("Observations:\n");
-            for o in obs {
-                if let Some(s) = o.as_str() {
-                    out.push_str(&format!("  - {}\n", s));
-                }
+    if let Some(obs) = task["observations"].as_array()
+        && !obs.is_empty()
+    {
+        out.push_str("Observations:\n");
+        for o in obs {
+            if let Some(s) = o.as_str() {
+                out.push_str(&format!("  - {}\n", s));
             }
         }
     }
     out
 }
+
+/// Fill colour of a task node in DOT output
+fn status_color(status: &TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Todo => "white",
+        TaskStatus::InProgress => "lightblue",
+        TaskStatus::Blocked => "orange",
+        TaskStatus::Done => "palegreen",
+        TaskStatus::Cancelled => "lightgray",
+    }
+}
+
+/// Quote `s` as a DOT identifier
+fn dot_id(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Render a task dependency graph in Graphviz DOT, edges pointing from a
+/// task to the task it depends on
+pub fn format_task_graph_dot(graph: &TaskGraph) -> String {
+    let mut out = String::from("digraph tasks {\n    node [shape=box, style=filled];\n");
+    for task in &graph.tasks {
+        out.push_str(&format!(
+            "    {} [fillcolor={}, tooltip={}];\n",
+            dot_id(&task.name),
+            status_color(&task.status),
+            dot_id(task.status.as_ref())
+        ));
+    }
+    for dependency in &graph.dependencies {
+        out.push_str(&format!(
+            "    {} -> {};\n",
+            dot_id(&dependency.task),
+            dot_id(&dependency.depends_on)
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[derive(Tabled)]
+struct ScheduleRow<'a> {
+    #[tabled(rename = "Task")]
+    name: &'a str,
+    #[tabled(rename = "Hours")]
+    duration: f64,
+    #[tabled(rename = "Start")]
+    earliest_start: f64,
+    #[tabled(rename = "Finish")]
+    earliest_finish: f64,
+    #[tabled(rename = "Slack")]
+    slack: f64,
+    #[tabled(rename = "Critical")]
+    critical: &'static str,
+}
+
+pub fn format_critical_path(report: &CriticalPathReport) -> String {
+    let rows: Vec<ScheduleRow> = report
+        .tasks
+        .iter()
+        .map(|t| ScheduleRow {
+            name: &t.name,
+            duration: t.duration_hours,
+            earliest_start: t.earliest_start,
+            earliest_finish: t.earliest_finish,
+            slack: t.slack,
+            critical: if report.path.contains(&t.name) {
+                "*"
+            } else {
+                ""
+            },
+        })
+        .collect();
+
+    let mut out = format!(
+        "Critical path ({}h): {}\n",
+        report.total_hours,
+        report.path.join(" -> ")
+    );
+    out.push_str(&Table::new(rows).to_string());
+    out.push('\n');
+    if !report.unestimated.is_empty() {
+        out.push_str(&format!(
+            "Unestimated tasks: {}\n",
+            report.unestimated.join(", ")
+        ));
+    }
+    out
+}
+
+pub fn format_task_report(report: &TaskReport) -> String {
+    let counts: Vec<String> = report
+        .status_counts
+        .iter()
+        .map(|(status, count)| format!("{status}: {count}"))
+        .collect();
+    let mut out = format!("Tasks in {}: {}\n", report.project_name, counts.join(", "));
+    if !report.in_progress.is_empty() {
+        out.push_str(&format!("In progress: {}\n", report.in_progress.join(", ")));
+    }
+    if report.violations.is_empty() {
+        out.push_str("WIP limits: ok\n");
+    } else {
+        out.push_str("WIP limits exceeded:\n");
+        for violation in &report.violations {
+            out.push_str(&format!("  {violation}: {}\n", violation.tasks.join(", ")));
+        }
+    }
+    out
+}
+
+pub fn format_estimate_calibration(calibration: &EstimateCalibration) -> String {
+    let Some(overall) = &calibration.overall else {
+        return "No finished tasks with estimated and actual hours\n".to_string();
+    };
+    let line = |name: &str, bias: &BiasFactor| {
+        format!("{name}: x{:.2} ({} tasks)\n", bias.factor, bias.samples)
+    };
+    let mut out = line("Overall", overall);
+    for (title, factors) in [
+        ("By task type", &calibration.by_task_type),
+        ("By assignee", &calibration.by_assignee),
+    ] {
+        if factors.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("{title}:\n"));
+        for (name, bias) in factors {
+            out.push_str(&format!("  {}", line(name, bias)));
+        }
+    }
+    out
+}
+
+#[derive(Tabled)]
+struct ForgetProposalRow<'a> {
+    #[tabled(rename = "Proposal")]
+    name: &'a str,
+    #[tabled(rename = "Forget")]
+    forget: String,
+    #[tabled(rename = "Reason")]
+    reason: &'a str,
+    #[tabled(rename = "Proposed By")]
+    proposed_by: &'a str,
+    #[tabled(rename = "Proposed At")]
+    proposed_at: String,
+}
+
+pub fn format_forget_proposals_table(proposals: &[ForgetProposal]) -> String {
+    let rows: Vec<ForgetProposalRow> = proposals
+        .iter()
+        .map(|p| ForgetProposalRow {
+            name: &p.name,
+            forget: if p.forgets_entity() {
+                format!("entity {}", p.target)
+            } else {
+                format!("{}: {}", p.target, p.observations.join("; "))
+            },
+            reason: p.reason.as_deref().unwrap_or(""),
+            proposed_by: &p.proposed_by,
+            proposed_at: p.proposed_at.format("%Y-%m-%d %H:%M").to_string(),
+        })
+        .collect();
+
+    Table::new(rows).to_string()
+}
+
+#[derive(Tabled)]
+struct DuplicateRow<'a> {
+    #[tabled(rename = "Keep")]
+    target: &'a str,
+    #[tabled(rename = "Merge")]
+    source: &'a str,
+    #[tabled(rename = "Score")]
+    score: String,
+    #[tabled(rename = "Name")]
+    name_score: String,
+    #[tabled(rename = "Shared Relationships")]
+    shared_relationships: usize,
+}
+
+pub fn format_duplicates_table(candidates: &[DuplicateCandidate]) -> String {
+    let rows: Vec<DuplicateRow> = candidates
+        .iter()
+        .map(|c| DuplicateRow {
+            target: &c.target,
+            source: &c.source,
+            score: format!("{:.2}", c.score),
+            name_score: format!("{:.2}", c.name_score),
+            shared_relationships: c.shared_relationships,
+        })
+        .collect();
+
+    Table::new(rows).to_string()
+}
+
+#[derive(Tabled)]
+struct AuditRow<'a> {
+    #[tabled(rename = "Time")]
+    timestamp: String,
+    #[tabled(rename = "Actor")]
+    actor: &'a str,
+    #[tabled(rename = "Operation")]
+    operation: &'a str,
+    #[tabled(rename = "Entities")]
+    names: String,
+    #[tabled(rename = "Correlation Id")]
+    correlation_id: &'a str,
+}
+
+pub fn format_audit_log(entries: &[AuditEntry]) -> String {
+    let rows: Vec<AuditRow> = entries
+        .iter()
+        .map(|e| AuditRow {
+            timestamp: e.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            actor: &e.actor,
+            operation: &e.operation,
+            names: e.names.join(", "),
+            correlation_id: e.correlation_id.as_deref().unwrap_or(""),
+        })
+        .collect();
+
+    Table::new(rows).to_string()
+}
+
+#[derive(Tabled)]
+struct VocabularyRow<'a> {
+    #[tabled(rename = "Kind")]
+    kind: &'static str,
+    #[tabled(rename = "Name")]
+    name: &'a str,
+    #[tabled(rename = "Issue")]
+    issue: String,
+}
+
+fn unknown_rows<'a>(kind: &'static str, terms: &'a [UnknownTerm]) -> Vec<VocabularyRow<'a>> {
+    terms
+        .iter()
+        .map(|t| VocabularyRow {
+            kind,
+            name: &t.name,
+            issue: format!("not in config, used {} time(s)", t.count),
+        })
+        .collect()
+}
+
+fn unused_rows<'a>(kind: &'static str, terms: &'a [UnusedTerm]) -> Vec<VocabularyRow<'a>> {
+    terms
+        .iter()
+        .map(|t| VocabularyRow {
+            kind,
+            name: &t.name,
+            issue: match &t.project {
+                Some(project) => format!("unused, configured for project {project}"),
+                None => "unused, configured globally".to_string(),
+            },
+        })
+        .collect()
+}
+
+pub fn format_vocabulary_lint(lint: &VocabularyLint, config: &MemoryConfig) -> String {
+    if lint.is_clean() {
+        return "Vocabulary matches the graph\n".to_string();
+    }
+    let mut rows = unknown_rows("label", &lint.unknown_labels);
+    rows.extend(unused_rows("label", &lint.unused_labels));
+    rows.extend(unknown_rows("relationship", &lint.unknown_relationships));
+    rows.extend(unused_rows("relationship", &lint.unused_relationships));
+
+    format!(
+        "{}\n\nSuggested config:\n\n{}",
+        Table::new(rows),
+        lint.suggested_config(config)
+    )
+}
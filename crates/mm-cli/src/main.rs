@@ -1,16 +1,40 @@
 #![warn(clippy::all)]
+use chrono::NaiveDate;
 use clap::{Parser, Subcommand, ValueEnum};
-use std::fs::{File, OpenOptions};
-use std::io;
-use std::path::PathBuf;
+use rolling_file::{BasicRollingFileAppender, RollingConditionBasic};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use tracing::{Level, instrument};
 use tracing_subscriber::fmt::writer::MakeWriterExt;
 use tracing_subscriber::{EnvFilter, Registry, fmt, prelude::*};
 
-use mm_cli::{format_task_detail, format_tasks_table};
+use mm_cli::{
+    format_audit_log, format_critical_path, format_duplicates_table, format_estimate_calibration,
+    format_forget_proposals_table, format_task_detail, format_task_graph_dot, format_task_report,
+    format_tasks_table, format_vocabulary_lint,
+};
+use mm_core::CoreError;
+use mm_core::mm_memory::MemoryValue;
+use mm_core::mm_memory::date::parse_date;
+use mm_core::mm_memory::merge::PropertyConflict;
+use mm_core::operations::memory::{
+    BackupArchive, ConflictPolicy, CopyGraphCommand, CreateBackupCommand, CriticalPathCommand,
+    EstimateCalibrationCommand, ExpireMemoriesCommand, ExportFormat, ExportGraphCommand,
+    FindDuplicatesCommand, GetAuditLogCommand, ImportOptions, LintVocabularyCommand,
+    ListForgetProposalsCommand, MergeEntitiesCommand, PurgeDeletedCommand,
+    ResolveForgetProposalCommand, RestoreBackupCommand, ReviewDecision, TaskGraphCommand,
+    TaskReportCommand, copy_graph, create_backup, critical_path, estimate_calibration,
+    expire_memories, export_graph, find_duplicates, get_audit_log, import_conflicts,
+    lint_vocabulary, list_forget_proposals, merge_conflicts, merge_entities, purge_deleted,
+    resolve_forget_proposal, restore_backup, task_graph, task_report,
+};
 use mm_server as mm_server_lib;
 use mm_server_lib::mcp::{GetTaskTool, ListTasksTool};
-use mm_server_lib::{ToolsCommand, create_ports_from_config};
+use mm_server_lib::{
+    Config, LogRotation, LoggingConfig, ToolsCommand, create_ports, create_ports_from_config,
+};
 
 /// Middle Manager CLI
 #[derive(Parser, Debug)]
@@ -20,27 +44,45 @@ struct Args {
     #[arg(short, long, value_enum, default_value_t = LogLevel::Info)]
     log_level: LogLevel,
 
-    /// Path to log file (required if log level is specified)
+    /// Where logs are written
+    #[arg(long, value_enum, default_value_t = LogTarget::File)]
+    log_target: LogTarget,
+
+    /// Format of the log file; stderr is always human-readable
+    #[arg(long, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+
+    /// Path to log file (required when logging to a file)
     #[arg(
         short = 'f',
         long,
         value_name = "FILE",
-        required_if_eq_any([
-            ("log_level", "error"),
-            ("log_level", "warn"),
-            ("log_level", "info"),
-            ("log_level", "debug"),
-            ("log_level", "trace"),
-        ])
+        required_if_eq_any([("log_target", "file"), ("log_target", "both")])
     )]
     logfile: Option<PathBuf>,
 
-    /// Rotate logs (clear log file if it exists)
+    /// Rotate logs (start a new log file on startup, keeping the previous one)
     #[arg(short = 'r', long, default_value_t = true)]
     rotate_logs: bool,
 
-    /// Path to config file (can be specified multiple times)
-    #[arg(short, long, value_name = "FILE", required = true, action = clap::ArgAction::Append)]
+    /// Time-based log rotation [default: `logging.rotation` from config, or daily]
+    #[arg(long, value_enum, value_name = "WHEN")]
+    log_rotation: Option<LogRotation>,
+
+    /// Rotate the log file once it grows past this many megabytes
+    /// [default: `logging.max_size_mb` from config, or 10]
+    #[arg(long, value_name = "MB")]
+    log_max_size_mb: Option<u64>,
+
+    /// Number of rotated log files to keep
+    /// [default: `logging.max_files` from config, or 5]
+    #[arg(long, value_name = "COUNT")]
+    log_max_files: Option<usize>,
+
+    /// Path to config file (can be specified multiple times) [default:
+    /// `.middle-manager.toml` in the current directory and its git root, on
+    /// top of `~/.config/middle-manager/config.toml`]
+    #[arg(short, long, value_name = "FILE", action = clap::ArgAction::Append)]
     config: Vec<PathBuf>,
 
     #[command(subcommand)]
@@ -57,6 +99,68 @@ enum LogLevel {
     Trace,
 }
 
+/// Destination of log output
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum LogTarget {
+    /// The file given by `--logfile`
+    File,
+    /// Standard error, leaving standard output to the MCP transport
+    Stderr,
+    /// Both the log file and standard error
+    Both,
+}
+
+/// Format of the log file
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum LogFormat {
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// Output format for `tasks graph`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum GraphFormat {
+    Dot,
+    Json,
+}
+
+/// Output format for `export`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum ExportFileFormat {
+    Json,
+    Cypher,
+    Graphml,
+}
+
+/// Conflict policy for `restore` and `copy`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum RestoreConflict {
+    Skip,
+    Overwrite,
+    Merge,
+}
+
+impl From<RestoreConflict> for ConflictPolicy {
+    fn from(policy: RestoreConflict) -> Self {
+        match policy {
+            RestoreConflict::Skip => ConflictPolicy::Skip,
+            RestoreConflict::Overwrite => ConflictPolicy::Overwrite,
+            RestoreConflict::Merge => ConflictPolicy::Merge,
+        }
+    }
+}
+
+impl From<ExportFileFormat> for ExportFormat {
+    fn from(format: ExportFileFormat) -> Self {
+        match format {
+            ExportFileFormat::Json => ExportFormat::Json,
+            ExportFileFormat::Cypher => ExportFormat::Cypher,
+            ExportFileFormat::Graphml => ExportFormat::Graphml,
+        }
+    }
+}
+
 #[derive(Subcommand, Debug)]
 enum Command {
     /// Start the MCP server
@@ -67,6 +171,102 @@ enum Command {
     Config(ConfigSubcommand),
     /// Task management commands
     Tasks(TasksSubcommand),
+    /// Graph schema commands
+    Schema(SchemaSubcommand),
+    /// Review memory that agents proposed to forget
+    Review(ReviewSubcommand),
+    /// Memory housekeeping jobs
+    Maintenance(MaintenanceSubcommand),
+    /// Inspect and tidy stored memory
+    Memory(MemorySubcommand),
+    /// Show recorded changes to the graph, newest first
+    Audit {
+        /// Only changes made by this agent
+        #[arg(long)]
+        actor: Option<String>,
+        /// Only changes made by this operation, e.g. update_entity
+        #[arg(long)]
+        operation: Option<String>,
+        /// Only changes affecting this entity
+        #[arg(long)]
+        name: Option<String>,
+        /// Only changes on or after this date
+        #[arg(long, value_parser = parse_date)]
+        since: Option<NaiveDate>,
+        /// Maximum number of changes to show
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Output results in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+    /// Export the memory graph in a stable order suitable for diffing
+    Export {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ExportFileFormat::Json)]
+        format: ExportFileFormat,
+        /// Only export entities with any of these labels
+        #[arg(long, value_delimiter = ',', num_args = 1..)]
+        labels: Vec<String>,
+        /// File to write; stdout when omitted
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Write a backup archive of the whole memory graph
+    Backup {
+        /// File to write; stdout when omitted
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Verify a backup archive and restore it into the configured backend
+    Restore {
+        /// Archive written by `backup`
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+        /// Only restore entities with any of these labels
+        #[arg(long, value_delimiter = ',', num_args = 1..)]
+        labels: Vec<String>,
+        /// Only restore this project and the entities reachable from it
+        #[arg(long)]
+        project: Option<String>,
+        /// What to do with entities that already exist
+        #[arg(long, value_enum, default_value_t = RestoreConflict::Skip)]
+        on_conflict: RestoreConflict,
+        /// With `--on-conflict merge`, choose the value of each conflicting
+        /// property instead of keeping the stored one
+        #[arg(long)]
+        interactive: bool,
+    },
+    /// Fold duplicate entities into one and delete them
+    Merge {
+        /// Entity that is kept
+        target: String,
+        /// Duplicates folded into the target
+        #[arg(required = true)]
+        sources: Vec<String>,
+        /// Choose the value of each conflicting property instead of keeping
+        /// the target's
+        #[arg(long)]
+        interactive: bool,
+    },
+    /// Copy memory from one configured backend profile to another
+    Copy {
+        /// Profile to read from
+        #[arg(long, value_name = "PROFILE")]
+        from: String,
+        /// Profile to write to
+        #[arg(long, value_name = "PROFILE")]
+        to: String,
+        /// Only copy entities with any of these labels
+        #[arg(long, value_delimiter = ',', num_args = 1..)]
+        labels: Vec<String>,
+        /// Only copy this project and the entities reachable from it
+        #[arg(long)]
+        project: Option<String>,
+        /// What to do with entities that already exist in the target
+        #[arg(long, value_enum, default_value_t = RestoreConflict::Skip)]
+        on_conflict: RestoreConflict,
+    },
 }
 
 #[derive(Parser, Debug)]
@@ -89,6 +289,96 @@ enum ConfigSubcommandType {
         #[arg(long)]
         show: bool,
     },
+    /// Compare allowed labels and relationships with those used in the graph
+    Lint {
+        /// Output results in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Parser, Debug)]
+struct SchemaSubcommand {
+    #[command(subcommand)]
+    command: SchemaSubcommandType,
+}
+
+#[derive(Subcommand, Debug)]
+enum SchemaSubcommandType {
+    /// Create indexes for the configured `indexed_properties`
+    Init,
+}
+
+#[derive(Parser, Debug)]
+struct ReviewSubcommand {
+    #[command(subcommand)]
+    command: ReviewSubcommandType,
+}
+
+#[derive(Subcommand, Debug)]
+enum ReviewSubcommandType {
+    /// List pending forget proposals
+    List {
+        /// Output results in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+    /// Approve a proposal and forget the memory it targets
+    Approve {
+        /// Name of the proposal
+        name: String,
+    },
+    /// Reject a proposal and keep the memory
+    Reject {
+        /// Name of the proposal
+        name: String,
+    },
+}
+
+#[derive(Parser, Debug)]
+struct MaintenanceSubcommand {
+    #[command(subcommand)]
+    command: MaintenanceSubcommandType,
+}
+
+#[derive(Subcommand, Debug)]
+enum MaintenanceSubcommandType {
+    /// Delete or archive entities past their label's time to live
+    Expire {
+        /// List expired entities without changing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Permanently delete soft-deleted entities
+    Purge {
+        /// Entities to purge; the whole trash when omitted
+        names: Vec<String>,
+    },
+}
+
+#[derive(Parser, Debug)]
+struct MemorySubcommand {
+    #[command(subcommand)]
+    command: MemorySubcommandType,
+}
+
+#[derive(Subcommand, Debug)]
+enum MemorySubcommandType {
+    /// Find likely duplicate entities and merge each into the one with more content
+    Dedupe {
+        /// Only compare entities with any of these labels
+        #[arg(long, value_delimiter = ',', num_args = 1..)]
+        labels: Vec<String>,
+        /// Lowest score between 0 and 1 treated as a duplicate [default: 0.8]
+        #[arg(long)]
+        min_score: Option<f64>,
+        /// List the candidates without merging them
+        #[arg(long)]
+        dry_run: bool,
+        /// Output results in JSON format
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -125,6 +415,13 @@ enum TasksSubcommandType {
         /// Labels to filter by
         #[arg(long, value_delimiter = ',', num_args = 1..)]
         labels: Vec<String>,
+        /// Only list tasks due before this date, e.g. 2025-07-01, tomorrow
+        /// or "next friday"
+        #[arg(long, value_parser = parse_date)]
+        due_before: Option<NaiveDate>,
+        /// Only list tasks due on or after this date
+        #[arg(long, value_parser = parse_date)]
+        due_after: Option<NaiveDate>,
         /// Output results in JSON format
         #[arg(long)]
         json: bool,
@@ -137,6 +434,42 @@ enum TasksSubcommandType {
         #[arg(long)]
         json: bool,
     },
+    /// Print the task dependency graph of a project
+    Graph {
+        /// Project whose tasks to include
+        #[arg(long)]
+        project: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+    },
+    /// Show the critical path and slack of a project's tasks
+    CriticalPath {
+        /// Project whose tasks to schedule
+        #[arg(long)]
+        project: Option<String>,
+        /// Output result in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+    /// Summarize a project's tasks and report exceeded WIP limits
+    Report {
+        /// Project to report on
+        #[arg(long)]
+        project: Option<String>,
+        /// Output result in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+    /// Compare estimated and actual hours of a project's finished tasks
+    Calibration {
+        /// Project to calibrate against
+        #[arg(long)]
+        project: Option<String>,
+        /// Output result in JSON format
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 impl From<LogLevel> for Level {
@@ -151,16 +484,30 @@ impl From<LogLevel> for Level {
     }
 }
 
-/// Create a file writer for logging
-#[instrument(fields(path = ?path, rotate))]
-fn create_file_writer(path: &PathBuf, rotate: bool) -> io::Result<File> {
-    if rotate {
-        // Create or truncate the file
-        File::create(path)
-    } else {
-        // Open for appending
-        OpenOptions::new().create(true).append(true).open(path)
+/// Create a file writer for logging that rotates as configured in `logging`
+///
+/// With `rotate` set, an existing non-empty log is rolled over so each run
+/// starts a new file.
+#[instrument(skip(logging), fields(path = ?path, rotate))]
+fn create_file_writer(
+    path: &Path,
+    rotate: bool,
+    logging: &LoggingConfig,
+) -> io::Result<BasicRollingFileAppender> {
+    let mut condition = match logging.rotation {
+        LogRotation::Never => RollingConditionBasic::new(),
+        LogRotation::Hourly => RollingConditionBasic::new().hourly(),
+        LogRotation::Daily => RollingConditionBasic::new().daily(),
+    };
+    if let Some(max_size_mb) = logging.max_size_mb {
+        condition = condition.max_size(max_size_mb.saturating_mul(1024 * 1024));
+    }
+
+    let mut appender = BasicRollingFileAppender::new(path, condition, logging.max_files)?;
+    if rotate && path.metadata().is_ok_and(|m| m.len() > 0) {
+        appender.rollover()?;
     }
+    Ok(appender)
 }
 
 #[instrument(skip(paths))]
@@ -184,31 +531,397 @@ async fn run_config_validate<P: AsRef<std::path::Path>>(
     }
 }
 
+#[instrument(skip(paths))]
+async fn run_config_lint<P: AsRef<std::path::Path>>(paths: &[P], json: bool) -> anyhow::Result<()> {
+    let (config, ports) = create_ports_from_config(paths).await?;
+    let lint = lint_vocabulary(&ports, LintVocabularyCommand::default())
+        .await
+        .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&lint)?);
+    } else {
+        print!("{}", format_vocabulary_lint(&lint, &config.memory));
+    }
+    Ok(())
+}
+
+#[instrument(skip(paths))]
+async fn run_schema_init<P: AsRef<std::path::Path>>(paths: &[P]) -> anyhow::Result<()> {
+    let (_, ports) = create_ports_from_config(paths).await?;
+    let indexes = ports
+        .memory_service
+        .init_schema()
+        .await
+        .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+    if indexes.is_empty() {
+        println!("No indexed properties configured");
+    }
+    for (label, property) in indexes {
+        println!("Index ready: {label}.{property}");
+    }
+    Ok(())
+}
+
+#[instrument(skip(paths))]
+async fn run_review<P: AsRef<std::path::Path>>(
+    paths: &[P],
+    command: ReviewSubcommandType,
+) -> anyhow::Result<()> {
+    let (_, ports) = create_ports_from_config(paths).await?;
+    let (name, decision) = match command {
+        ReviewSubcommandType::List { json } => {
+            let proposals = list_forget_proposals(&ports, ListForgetProposalsCommand::default())
+                .await
+                .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&proposals)?);
+            } else if proposals.is_empty() {
+                println!("No pending proposals");
+            } else {
+                print!("{}", format_forget_proposals_table(&proposals));
+            }
+            return Ok(());
+        }
+        ReviewSubcommandType::Approve { name } => (name, ReviewDecision::Approve),
+        ReviewSubcommandType::Reject { name } => (name, ReviewDecision::Reject),
+    };
+    let proposal = resolve_forget_proposal(&ports, ResolveForgetProposalCommand { name, decision })
+        .await
+        .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+    match decision {
+        ReviewDecision::Approve if proposal.forgets_entity() => {
+            println!("Forgot entity {}", proposal.target)
+        }
+        ReviewDecision::Approve => println!(
+            "Forgot {} observation(s) on {}",
+            proposal.observations.len(),
+            proposal.target
+        ),
+        ReviewDecision::Reject => println!("Kept {}", proposal.target),
+    }
+    Ok(())
+}
+
+#[instrument(skip(paths))]
+async fn run_expire<P: AsRef<std::path::Path>>(paths: &[P], dry_run: bool) -> anyhow::Result<()> {
+    let (_, ports) = create_ports_from_config(paths).await?;
+    let result = expire_memories(&ports, ExpireMemoriesCommand { dry_run })
+        .await
+        .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+    let (deleted, archived) = if dry_run {
+        ("Would delete", "Would archive")
+    } else {
+        ("Deleted", "Archived")
+    };
+    for name in &result.deleted {
+        println!("{deleted} {name}");
+    }
+    for name in &result.archived {
+        println!("{archived} {name}");
+    }
+    if result.deleted.is_empty() && result.archived.is_empty() {
+        println!("No expired entities");
+    }
+    Ok(())
+}
+
+#[instrument(skip(paths))]
+async fn run_purge<P: AsRef<std::path::Path>>(
+    paths: &[P],
+    names: Vec<String>,
+) -> anyhow::Result<()> {
+    let (_, ports) = create_ports_from_config(paths).await?;
+    let purged = purge_deleted(&ports, PurgeDeletedCommand { names })
+        .await
+        .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+    for name in &purged {
+        println!("Purged {name}");
+    }
+    if purged.is_empty() {
+        println!("Trash is empty");
+    }
+    Ok(())
+}
+
+#[instrument(skip(paths))]
+async fn run_audit<P: AsRef<std::path::Path>>(
+    paths: &[P],
+    command: GetAuditLogCommand,
+    json: bool,
+) -> anyhow::Result<()> {
+    let (_, ports) = create_ports_from_config(paths).await?;
+    let entries = get_audit_log(&ports, command)
+        .await
+        .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+    } else if entries.is_empty() {
+        println!("No recorded changes");
+    } else {
+        print!("{}", format_audit_log(&entries));
+    }
+    Ok(())
+}
+
+#[instrument(skip(paths))]
+async fn run_export<P: AsRef<std::path::Path>>(
+    paths: &[P],
+    format: ExportFileFormat,
+    labels: Vec<String>,
+    output: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let (_, ports) = create_ports_from_config(paths).await?;
+    let export = export_graph(&ports, ExportGraphCommand { labels })
+        .await
+        .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+    let text = export.render(format.into())?;
+    match output {
+        Some(path) => std::fs::write(path, text)?,
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
+#[instrument(skip(paths))]
+async fn run_backup<P: AsRef<std::path::Path>>(
+    paths: &[P],
+    output: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let (_, ports) = create_ports_from_config(paths).await?;
+    let command = CreateBackupCommand {
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    let archive = create_backup(&ports, command)
+        .await
+        .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+    let text = archive.to_json()?;
+    match output {
+        Some(path) => std::fs::write(path, text)?,
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
+#[instrument(skip(paths))]
+async fn run_restore<P: AsRef<std::path::Path>>(
+    paths: &[P],
+    input: PathBuf,
+    labels: Vec<String>,
+    project: Option<String>,
+    on_conflict: RestoreConflict,
+    interactive: bool,
+) -> anyhow::Result<()> {
+    // Invalid archives are reported with their mismatches, one per line
+    let report = |e: CoreError<_>| match e {
+        CoreError::InvalidBackup(_) => anyhow::anyhow!(e.to_string()),
+        e => anyhow::anyhow!(format!("{e:?}")),
+    };
+    let text = std::fs::read_to_string(&input)?;
+    let archive = BackupArchive::from_json(&text).map_err(report)?;
+    let (_, ports) = create_ports_from_config(paths).await?;
+    let mut options = ImportOptions {
+        labels,
+        project,
+        on_conflict: on_conflict.into(),
+        resolutions: HashMap::new(),
+    };
+    // Archives failing verification are reported by the restore instead
+    if interactive && on_conflict == RestoreConflict::Merge && archive.verify().is_empty() {
+        let conflicts = import_conflicts(&ports, archive.graph.clone(), &options)
+            .await
+            .map_err(report)?;
+        for (name, conflicts) in conflicts {
+            let resolutions = prompt_resolutions(&name, &conflicts)?;
+            options.resolutions.insert(name, resolutions);
+        }
+    }
+    let command = RestoreBackupCommand { archive, options };
+    let summary = restore_backup(&ports, command).await.map_err(report)?;
+    println!(
+        "Created {} entities, updated {}, skipped {}; added {} relationships",
+        summary.created, summary.updated, summary.skipped, summary.relationships
+    );
+    Ok(())
+}
+
+#[instrument(skip(paths))]
+async fn run_dedupe<P: AsRef<std::path::Path>>(
+    paths: &[P],
+    command: FindDuplicatesCommand,
+    dry_run: bool,
+    json: bool,
+) -> anyhow::Result<()> {
+    let (_, ports) = create_ports_from_config(paths).await?;
+    let result = find_duplicates(&ports, command)
+        .await
+        .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&result.candidates)?);
+    } else if result.candidates.is_empty() {
+        println!("No duplicates found");
+    } else {
+        print!("{}", format_duplicates_table(&result.candidates));
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    // An entity merged away can neither be kept nor merged again
+    let mut merged = HashSet::new();
+    for candidate in result.candidates {
+        if merged.contains(&candidate.target) || merged.contains(&candidate.source) {
+            continue;
+        }
+        let command = MergeEntitiesCommand {
+            target: candidate.target.clone(),
+            sources: vec![candidate.source.clone()],
+            resolutions: HashMap::new(),
+        };
+        merge_entities(&ports, command)
+            .await
+            .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+        eprintln!("Merged {} into {}", candidate.source, candidate.target);
+        merged.insert(candidate.source);
+    }
+    Ok(())
+}
+
+/// Ask which value to keep for each of `conflicts` on `entity`
+///
+/// An empty answer keeps the first value, which is what a non-interactive
+/// merge keeps.
+fn prompt_resolutions(
+    entity: &str,
+    conflicts: &[PropertyConflict],
+) -> io::Result<HashMap<String, MemoryValue>> {
+    let mut resolutions = HashMap::new();
+    let mut lines = io::stdin().lock().lines();
+    for conflict in conflicts {
+        println!("{entity}: conflicting values for `{}`", conflict.property);
+        for (i, candidate) in conflict.values.iter().enumerate() {
+            let value = serde_json::to_string(&candidate.value)?;
+            println!("  {}) {value} ({})", i + 1, candidate.from);
+        }
+        let chosen = loop {
+            print!("Keep [1-{}, default 1]: ", conflict.values.len());
+            io::stdout().flush()?;
+            let Some(line) = lines.next().transpose()? else {
+                break 0;
+            };
+            match line.trim() {
+                "" => break 0,
+                answer => match answer.parse::<usize>() {
+                    Ok(n) if (1..=conflict.values.len()).contains(&n) => break n - 1,
+                    _ => println!("Enter a number between 1 and {}", conflict.values.len()),
+                },
+            }
+        };
+        resolutions.insert(
+            conflict.property.clone(),
+            conflict.values[chosen].value.clone(),
+        );
+    }
+    Ok(resolutions)
+}
+
+#[instrument(skip(paths))]
+async fn run_merge<P: AsRef<std::path::Path>>(
+    paths: &[P],
+    mut command: MergeEntitiesCommand,
+    interactive: bool,
+) -> anyhow::Result<()> {
+    let (_, ports) = create_ports_from_config(paths).await?;
+    if interactive {
+        let conflicts = merge_conflicts(&ports, &command)
+            .await
+            .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+        command.resolutions = prompt_resolutions(&command.target, &conflicts)?;
+    }
+    let merged = merge_entities(&ports, command)
+        .await
+        .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+    println!("Merged into {}", merged.name);
+    Ok(())
+}
+
+#[instrument(skip(paths))]
+async fn run_copy<P: AsRef<std::path::Path>>(
+    paths: &[P],
+    from: String,
+    to: String,
+    options: ImportOptions,
+) -> anyhow::Result<()> {
+    let cwd = std::env::current_dir()?;
+    let config = Config::load(paths)
+        .and_then(|config| config.with_project_overrides(Config::workspace_root(&cwd)))
+        .map_err(|e| anyhow::anyhow!("Failed to load configuration: {e}"))?;
+    let source = create_ports(&config.with_profile(&from)?).await?;
+    let target = create_ports(&config.with_profile(&to)?).await?;
+    let progress = |done: usize, total: usize| eprint!("\rCopied {done}/{total} entities");
+    let summary = copy_graph(&source, &target, CopyGraphCommand { options }, progress)
+        .await
+        .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+    eprintln!();
+    println!(
+        "Created {} entities, updated {}, skipped {}; added {} relationships",
+        summary.created, summary.updated, summary.skipped, summary.relationships
+    );
+    Ok(())
+}
+
 #[instrument(skip(args))]
 async fn run(args: Args) -> anyhow::Result<()> {
+    // Config files are discovered when none are given
+    let config_paths: Vec<PathBuf> = if args.config.is_empty() {
+        Config::discover(&std::env::current_dir()?)
+    } else {
+        args.config
+    };
+
     // Initialize tracing
     let level: Level = args.log_level.into();
     let filter = EnvFilter::from_default_env().add_directive(level.into());
 
     // Set up logging
     let subscriber = Registry::default().with(filter);
+    let mut layers = Vec::new();
 
-    if let Some(logfile_path) = &args.logfile {
-        // Create log file writer
-        let file = create_file_writer(logfile_path, args.rotate_logs)?;
+    let to_file = matches!(args.log_target, LogTarget::File | LogTarget::Both);
+    if let Some(logfile_path) = args.logfile.as_ref().filter(|_| to_file) {
+        // Rotation settings come from the config file unless overridden by
+        // flags; a config that fails to load is reported by the command later
+        let mut logging = Config::load(&config_paths)
+            .map(|config| config.logging)
+            .unwrap_or_default();
+        if let Some(rotation) = args.log_rotation {
+            logging.rotation = rotation;
+        }
+        if let Some(max_size_mb) = args.log_max_size_mb {
+            logging.max_size_mb = Some(max_size_mb);
+        }
+        if let Some(max_files) = args.log_max_files {
+            logging.max_files = max_files;
+        }
 
-        // Set up file logging only (no console)
-        let file_layer = fmt::layer().with_writer(file.with_max_level(level));
+        // Create log file writer
+        let file = create_file_writer(logfile_path, args.rotate_logs, &logging)?;
+        let file_layer = fmt::layer().with_writer(Mutex::new(file).with_max_level(level));
+        layers.push(match args.log_format {
+            LogFormat::Text => file_layer.boxed(),
+            LogFormat::Json => file_layer.json().boxed(),
+        });
+    }
 
-        // Register only the file layer
-        subscriber.with(file_layer).init();
-    } else {
-        // No logging output at all if no logfile is specified
-        subscriber.init();
+    if matches!(args.log_target, LogTarget::Stderr | LogTarget::Both) {
+        layers.push(
+            fmt::layer()
+                .with_writer(io::stderr.with_max_level(level))
+                .boxed(),
+        );
     }
 
-    // Use the specified config paths directly
-    let config_paths: Vec<PathBuf> = args.config;
+    // Logging to a file without `--logfile` produces no output at all
+    subscriber.with(layers).init();
 
     match args.command.unwrap_or(Command::Server) {
         Command::Server => mm_server_lib::run_server(&config_paths).await?,
@@ -247,18 +960,117 @@ async fn run(args: Args) -> anyhow::Result<()> {
             ConfigSubcommandType::Validate { show } => {
                 run_config_validate(&config_paths, show).await?;
             }
+            ConfigSubcommandType::Lint { json } => run_config_lint(&config_paths, json).await?,
         },
+        Command::Schema(schema_subcommand) => match schema_subcommand.command {
+            SchemaSubcommandType::Init => run_schema_init(&config_paths).await?,
+        },
+        Command::Review(review_subcommand) => {
+            run_review(&config_paths, review_subcommand.command).await?
+        }
+        Command::Maintenance(maintenance_subcommand) => match maintenance_subcommand.command {
+            MaintenanceSubcommandType::Expire { dry_run } => {
+                run_expire(&config_paths, dry_run).await?
+            }
+            MaintenanceSubcommandType::Purge { names } => run_purge(&config_paths, names).await?,
+        },
+        Command::Memory(memory_subcommand) => match memory_subcommand.command {
+            MemorySubcommandType::Dedupe {
+                labels,
+                min_score,
+                dry_run,
+                json,
+            } => {
+                let command = FindDuplicatesCommand { labels, min_score };
+                run_dedupe(&config_paths, command, dry_run, json).await?
+            }
+        },
+        Command::Audit {
+            actor,
+            operation,
+            name,
+            since,
+            limit,
+            json,
+        } => {
+            let command = GetAuditLogCommand {
+                actor,
+                operation,
+                name,
+                since: since
+                    .and_then(|d| d.and_hms_opt(0, 0, 0))
+                    .map(|t| t.and_utc()),
+                limit,
+            };
+            run_audit(&config_paths, command, json).await?
+        }
+        Command::Export {
+            format,
+            labels,
+            output,
+        } => run_export(&config_paths, format, labels, output).await?,
+        Command::Backup { output } => run_backup(&config_paths, output).await?,
+        Command::Restore {
+            input,
+            labels,
+            project,
+            on_conflict,
+            interactive,
+        } => {
+            run_restore(
+                &config_paths,
+                input,
+                labels,
+                project,
+                on_conflict,
+                interactive,
+            )
+            .await?
+        }
+        Command::Merge {
+            target,
+            sources,
+            interactive,
+        } => {
+            let command = MergeEntitiesCommand {
+                target,
+                sources,
+                resolutions: HashMap::new(),
+            };
+            run_merge(&config_paths, command, interactive).await?
+        }
+        Command::Copy {
+            from,
+            to,
+            labels,
+            project,
+            on_conflict,
+        } => {
+            let options = ImportOptions {
+                labels,
+                project,
+                on_conflict: on_conflict.into(),
+                resolutions: HashMap::new(),
+            };
+            run_copy(&config_paths, from, to, options).await?
+        }
         Command::Tasks(tasks_subcommand) => {
             let (_, ports) = create_ports_from_config(&config_paths).await?;
             match tasks_subcommand.command {
                 TasksSubcommandType::List {
                     project,
                     labels,
+                    due_before,
+                    due_after,
                     json,
                 } => {
                     let tool = ListTasksTool {
                         project_name: project,
                         labels,
+                        status: None,
+                        priority: None,
+                        due_before,
+                        due_after,
                     };
                     let result = tool
                         .call_tool(&ports)
@@ -297,6 +1109,69 @@ async fn run(args: Args) -> anyhow::Result<()> {
                         print!("{}", format_task_detail(&task));
                     }
                 }
+                TasksSubcommandType::Graph { project, format } => {
+                    let graph = task_graph(
+                        &ports,
+                        TaskGraphCommand {
+                            project_name: project,
+                        },
+                    )
+                    .await
+                    .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+                    match format {
+                        GraphFormat::Dot => print!("{}", format_task_graph_dot(&graph)),
+                        GraphFormat::Json => {
+                            println!("{}", serde_json::to_string_pretty(&graph)?)
+                        }
+                    }
+                }
+                TasksSubcommandType::CriticalPath { project, json } => {
+                    let report = critical_path(
+                        &ports,
+                        CriticalPathCommand {
+                            project_name: project,
+                        },
+                    )
+                    .await
+                    .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+                    if json {
+                        println!("{}", serde_json::to_string_pretty(&report)?);
+                    } else if report.tasks.is_empty() {
+                        println!("No tasks found");
+                    } else {
+                        print!("{}", format_critical_path(&report));
+                    }
+                }
+                TasksSubcommandType::Report { project, json } => {
+                    let report = task_report(
+                        &ports,
+                        TaskReportCommand {
+                            project_name: project,
+                        },
+                    )
+                    .await
+                    .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+                    if json {
+                        println!("{}", serde_json::to_string_pretty(&report)?);
+                    } else {
+                        print!("{}", format_task_report(&report));
+                    }
+                }
+                TasksSubcommandType::Calibration { project, json } => {
+                    let calibration = estimate_calibration(
+                        &ports,
+                        EstimateCalibrationCommand {
+                            project_name: project,
+                        },
+                    )
+                    .await
+                    .map_err(|e| anyhow::anyhow!(format!("{e:?}")))?;
+                    if json {
+                        println!("{}", serde_json::to_string_pretty(&calibration)?);
+                    } else {
+                        print!("{}", format_estimate_calibration(&calibration));
+                    }
+                }
             }
         }
     }
@@ -0,0 +1,33 @@
+use mm_cli::format_vocabulary_lint;
+use mm_core::mm_memory::MemoryConfig;
+use mm_core::operations::memory::{UnknownTerm, UnusedTerm, VocabularyLint};
+
+#[test]
+fn test_format_vocabulary_lint() {
+    let config = MemoryConfig {
+        allowed_labels: ["Stale".to_string()].into(),
+        ..MemoryConfig::default()
+    };
+    let lint = VocabularyLint {
+        unknown_labels: vec![UnknownTerm {
+            name: "Milestone".to_string(),
+            count: 2,
+        }],
+        unused_labels: vec![UnusedTerm {
+            name: "Stale".to_string(),
+            project: None,
+        }],
+        ..Default::default()
+    };
+    let output = format_vocabulary_lint(&lint, &config);
+    assert!(output.contains("Milestone"));
+    assert!(output.contains("used 2 time(s)"));
+    assert!(output.contains("unused, configured globally"));
+    assert!(output.contains("allowed_labels = [\"Milestone\"]"));
+}
+
+#[test]
+fn test_format_vocabulary_lint_clean() {
+    let output = format_vocabulary_lint(&VocabularyLint::default(), &MemoryConfig::default());
+    assert_eq!(output, "Vocabulary matches the graph\n");
+}
@@ -34,3 +34,111 @@ fn test_format_task_detail() {
     assert!(out.contains("Memory, Task"));
     assert!(out.contains("Test"));
 }
+
+#[test]
+fn test_format_task_graph_dot() {
+    use mm_cli::format_task_graph_dot;
+    use mm_core::operations::memory::{Priority, TaskDependency, TaskGraph, TaskNode, TaskStatus};
+
+    let graph = TaskGraph {
+        tasks: vec![
+            TaskNode {
+                name: "task:a".to_string(),
+                status: TaskStatus::Done,
+                priority: Priority::Low,
+                estimate_hours: None,
+            },
+            TaskNode {
+                name: "task:\"b\"".to_string(),
+                status: TaskStatus::Blocked,
+                priority: Priority::High,
+                estimate_hours: None,
+            },
+        ],
+        dependencies: vec![TaskDependency {
+            task: "task:\"b\"".to_string(),
+            depends_on: "task:a".to_string(),
+        }],
+    };
+    let output = format_task_graph_dot(&graph);
+    assert!(output.starts_with("digraph tasks {\n"));
+    assert!(output.contains("\"task:a\" [fillcolor=palegreen, tooltip=\"done\"];"));
+    assert!(output.contains("\"task:\\\"b\\\"\" -> \"task:a\";"));
+    assert!(output.ends_with("}\n"));
+}
+
+#[test]
+fn test_format_critical_path() {
+    use mm_cli::format_critical_path;
+    use mm_core::operations::memory::{CriticalPathReport, TaskSchedule};
+
+    let schedule = |name: &str, start: f64, hours: f64, slack: f64| TaskSchedule {
+        name: name.to_string(),
+        duration_hours: hours,
+        earliest_start: start,
+        earliest_finish: start + hours,
+        latest_start: start + slack,
+        latest_finish: start + hours + slack,
+        slack,
+    };
+    let report = CriticalPathReport {
+        total_hours: 3.0,
+        path: vec!["task:a".to_string(), "task:b".to_string()],
+        tasks: vec![
+            schedule("task:a", 0.0, 1.0, 0.0),
+            schedule("task:b", 1.0, 2.0, 0.0),
+            schedule("task:c", 1.0, 1.0, 1.0),
+        ],
+        unestimated: vec!["task:c".to_string()],
+    };
+    let output = format_critical_path(&report);
+    assert!(output.starts_with("Critical path (3h): task:a -> task:b\n"));
+    assert!(output.contains("task:c"));
+    assert!(output.ends_with("Unestimated tasks: task:c\n"));
+}
+
+#[test]
+fn test_format_task_report() {
+    use mm_cli::format_task_report;
+    use mm_core::operations::memory::{TaskReport, WipScope, WipViolation};
+    use std::collections::BTreeMap;
+
+    let report = TaskReport {
+        project_name: "project:x".to_string(),
+        status_counts: BTreeMap::from([("in_progress".to_string(), 2), ("todo".to_string(), 1)]),
+        in_progress: vec!["task:a".to_string(), "task:b".to_string()],
+        violations: vec![WipViolation {
+            scope: WipScope::Project("project:x".to_string()),
+            limit: 1,
+            tasks: vec!["task:a".to_string(), "task:b".to_string()],
+        }],
+    };
+    let output = format_task_report(&report);
+    assert!(output.starts_with("Tasks in project:x: in_progress: 2, todo: 1\n"));
+    assert!(
+        output
+            .contains("  project 'project:x' has 2 tasks in progress (limit 1): task:a, task:b\n")
+    );
+}
+
+#[test]
+fn test_format_estimate_calibration() {
+    use mm_cli::format_estimate_calibration;
+    use mm_core::operations::memory::{BiasFactor, EstimateCalibration};
+    use std::collections::BTreeMap;
+
+    let bias = |samples, factor| BiasFactor { samples, factor };
+    let calibration = EstimateCalibration {
+        overall: Some(bias(3, 1.5)),
+        by_task_type: BTreeMap::from([("bug".to_string(), bias(3, 1.5))]),
+        by_assignee: BTreeMap::new(),
+    };
+    assert_eq!(
+        format_estimate_calibration(&calibration),
+        "Overall: x1.50 (3 tasks)\nBy task type:\n  bug: x1.50 (3 tasks)\n"
+    );
+    assert_eq!(
+        format_estimate_calibration(&EstimateCalibration::default()),
+        "No finished tasks with estimated and actual hours\n"
+    );
+}
@@ -19,13 +19,32 @@ where
     #[error("Validation error")]
     Validation(#[from] mm_memory::ValidationError),
 
-    /// Multiple validation errors grouped by name
+    /// Validation errors of individual items in a batch
     #[error("Batch validation error")]
-    BatchValidation(Vec<(String, mm_memory::ValidationError)>),
+    BatchValidation(Vec<mm_memory::BatchItemError>),
 
     /// Error when a project name is required but not provided
     #[error("No project specified")]
     MissingProject,
+
+    /// Backup archive whose contents do not match its manifest
+    #[error(
+        "Invalid backup archive:{}",
+        crate::operations::memory::backup::format_mismatches(.0)
+    )]
+    InvalidBackup(Vec<crate::operations::memory::backup::ManifestMismatch>),
+
+    /// Error resolving a path against the workspace roots
+    #[error("Invalid workspace path")]
+    WorkspacePath(#[from] crate::workspace::WorkspacePathError),
+
+    /// Error reading a file referenced by a tool
+    #[error("Failed to read file '{}'", path.display())]
+    FileRead {
+        path: std::path::PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
 }
 
 /// Result type for mm-core
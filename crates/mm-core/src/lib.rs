@@ -10,12 +10,14 @@ pub mod error;
 pub mod operations;
 mod ports;
 mod root;
+pub mod warning;
+pub mod workspace;
 
 pub use error::{CoreError, CoreResult};
 pub use ports::Ports;
 pub use root::{Root, RootCollection};
-
-pub(crate) use operations::memory::validate_name;
+pub use warning::{HasWarnings, Warning};
+pub use workspace::{WorkspacePathError, resolve_workspace_path};
 
 // Re-export the mm-memory crate for easy access to memory types and services
 pub use mm_memory;
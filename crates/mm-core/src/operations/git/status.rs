@@ -2,11 +2,13 @@ use std::path::PathBuf;
 
 use crate::error::{CoreError, CoreResult};
 use crate::ports::Ports;
+use crate::workspace::resolve_workspace_path;
 use mm_git::{GitRepository, GitStatus};
 use mm_memory::MemoryRepository;
 
 #[derive(Debug, Clone)]
 pub struct GetGitStatusCommand {
+    /// Repository path, absolute or relative to the workspace root
     pub path: PathBuf,
 }
 
@@ -21,10 +23,12 @@ where
     G: GitRepository + Send + Sync,
     G::Error: std::error::Error + Send + Sync + 'static,
 {
+    let path = resolve_workspace_path(&*ports.roots.read().await, &command.path)?;
+
     // Use the git_service from ports to get the status
     ports
         .git_service
-        .get_status(&command.path)
+        .get_status(&path)
         .await
         .map_err(CoreError::Git)
 }
@@ -95,4 +99,43 @@ mod tests {
             "Expected Git error"
         );
     }
+
+    #[tokio::test]
+    async fn test_get_git_status_resolves_relative_path() {
+        let mut git_repo = MockGitRepository::new();
+        git_repo
+            .expect_get_status()
+            .withf(|p| p == std::path::Path::new("/work/repo/crates/mm-core"))
+            .returning(|_| {
+                Ok(GitStatus {
+                    branch: "main".to_string(),
+                    is_dirty: false,
+                    ahead_by: 0,
+                    behind_by: 0,
+                    changed_files: vec![],
+                })
+            });
+
+        let roots = crate::RootCollection::from_roots(vec![crate::Root::new(
+            None,
+            "file:///work/repo".to_string(),
+        )]);
+        let ports = Ports::noop().with(|ports| {
+            ports.git_service = Arc::new(mm_git::GitService::new(git_repo));
+            ports.roots = Arc::new(tokio::sync::RwLock::new(roots));
+        });
+
+        let command = GetGitStatusCommand {
+            path: PathBuf::from("crates/mm-core"),
+        };
+        assert!(get_git_status(&ports, command).await.is_ok());
+
+        let command = GetGitStatusCommand {
+            path: PathBuf::from("../elsewhere"),
+        };
+        assert!(matches!(
+            get_git_status(&ports, command).await,
+            Err(CoreError::WorkspacePath(_))
+        ));
+    }
 }
@@ -0,0 +1,112 @@
+use super::observation_metadata::{ObservationMetadata, record_in_update};
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{EntityUpdate, MemoryRepository, ObservationSource, ObservationsUpdate};
+use tracing::instrument;
+
+/// Command to add observations to an entity
+#[derive(Debug, Clone)]
+pub struct AddObservationsCommand {
+    pub name: String,
+    pub observations: Vec<String>,
+    /// Confidence recorded for the added observations
+    pub confidence: Option<f64>,
+    /// Source cited for the added observations
+    pub source: Option<ObservationSource>,
+}
+
+pub type AddObservationsResult<E> = CoreResult<(), E>;
+
+/// Add observations to an entity, ignoring ones it already has
+#[instrument(skip(ports), fields(name = %command.name, observations_count = command.observations.len()))]
+pub async fn add_observations<M, G>(
+    ports: &Ports<M, G>,
+    command: AddObservationsCommand,
+) -> AddObservationsResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.name);
+
+    let mut update = EntityUpdate {
+        observations: Some(ObservationsUpdate {
+            add: Some(command.observations),
+            remove: None,
+            set: None,
+        }),
+        ..Default::default()
+    };
+    let metadata = ObservationMetadata {
+        confidence: command.confidence,
+        source: command.source,
+    };
+    record_in_update(ports, &command.name, &mut update, &metadata).await?;
+    ports
+        .memory_service
+        .update_entity(&command.name, &update)
+        .await
+        .map_err(CoreError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::confidence::observation_confidence;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_add_observations_with_confidence() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|_| Ok(None));
+        mock.expect_update_entity()
+            .withf(|name, update| {
+                name == "e"
+                    && update
+                        .observations
+                        .as_ref()
+                        .and_then(|o| o.add.clone())
+                        .is_some_and(|add| add == ["a", "b"])
+                    && update
+                        .properties
+                        .as_ref()
+                        .and_then(|p| p.add.as_ref())
+                        .is_some_and(|add| {
+                            observation_confidence(add, "a") == 0.5
+                                && observation_confidence(add, "b") == 0.5
+                        })
+            })
+            .returning(|_, _| Ok(()));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let command = AddObservationsCommand {
+            name: "e".into(),
+            observations: vec!["a".into(), "b".into()],
+            confidence: Some(0.5),
+            source: None,
+        };
+        add_observations(&ports, command).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_add_observations_invalid_confidence() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_update_entity().never();
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let command = AddObservationsCommand {
+            name: "e".into(),
+            observations: vec!["a".into()],
+            confidence: Some(2.0),
+            source: None,
+        };
+        let res = add_observations(&ports, command).await;
+        assert!(matches!(res, Err(CoreError::Validation(_))));
+    }
+}
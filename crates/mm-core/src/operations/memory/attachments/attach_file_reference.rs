@@ -0,0 +1,227 @@
+use chrono::Utc;
+use mm_git::GitRepository;
+use mm_memory::labels::FILE_LABEL;
+use mm_memory::{
+    MemoryEntity, MemoryError, MemoryRelationship, MemoryRepository, PropertiesUpdate,
+    RelationshipUpdate,
+};
+use std::path::PathBuf;
+use tracing::instrument;
+
+use super::super::common::handle_batch_result;
+use super::types::{ATTACHMENT_RELATIONSHIP, FileAttachment, content_hash, file_entity_name};
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use crate::workspace::resolve_workspace_path;
+
+#[derive(Debug, Clone)]
+pub struct AttachFileReferenceCommand {
+    /// Entity the file is attached to
+    pub entity_name: String,
+    /// File path, absolute or relative to the workspace root
+    pub path: PathBuf,
+}
+
+pub type AttachFileReferenceResult<E> = CoreResult<FileAttachment, E>;
+
+/// Record a snapshot of a file on an entity
+///
+/// The file is represented by a `File` entity named `file:<path>` and linked
+/// from the entity with a `references` relationship carrying the path, root
+/// and content hash. Attaching the same file again refreshes the snapshot.
+#[instrument(skip(ports), fields(entity_name = %command.entity_name))]
+pub async fn attach_file_reference<M, G>(
+    ports: &Ports<M, G>,
+    command: AttachFileReferenceCommand,
+) -> AttachFileReferenceResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.entity_name);
+
+    let (path, root) = {
+        let roots = ports.roots.read().await;
+        let path = resolve_workspace_path(&roots, &command.path)?;
+        let root = roots
+            .roots()
+            .iter()
+            .find(|r| r.path().is_some_and(|p| path.starts_with(p)))
+            .map(|r| r.uri.clone());
+        (path, root)
+    };
+
+    let contents = tokio::fs::read(&path)
+        .await
+        .map_err(|source| CoreError::FileRead {
+            path: path.clone(),
+            source,
+        })?;
+
+    if ports
+        .memory_service
+        .find_entity_by_name(&command.entity_name)
+        .await?
+        .is_none()
+    {
+        return Err(MemoryError::entity_not_found(command.entity_name).into());
+    }
+
+    let file_name = file_entity_name(&path);
+    if ports
+        .memory_service
+        .find_entity_by_name(&file_name)
+        .await?
+        .is_none()
+    {
+        let file = MemoryEntity {
+            name: file_name.clone(),
+            labels: vec![FILE_LABEL.to_string()],
+            ..Default::default()
+        };
+        handle_batch_result("entities", || {
+            ports
+                .memory_service
+                .create_entities(std::slice::from_ref(&file))
+        })
+        .await?;
+    }
+
+    let attachment = FileAttachment {
+        path: path.display().to_string(),
+        root,
+        content_hash: content_hash(&contents),
+        attached_at: Utc::now(),
+    };
+
+    let existing = ports
+        .memory_service
+        .find_relationships(
+            Some(command.entity_name.clone()),
+            Some(file_name.clone()),
+            Some(ATTACHMENT_RELATIONSHIP.to_string()),
+        )
+        .await?;
+    if existing.is_empty() {
+        let rel = MemoryRelationship {
+            from: command.entity_name.clone(),
+            to: file_name,
+            name: ATTACHMENT_RELATIONSHIP.to_string(),
+            properties: attachment.clone().into(),
+        };
+        handle_batch_result("relationships", || {
+            ports
+                .memory_service
+                .create_relationships(std::slice::from_ref(&rel))
+        })
+        .await?;
+    } else {
+        let update = RelationshipUpdate {
+            properties: Some(PropertiesUpdate {
+                add: None,
+                remove: None,
+                set: Some(attachment.clone().into()),
+            }),
+        };
+        ports
+            .memory_service
+            .update_relationship(
+                &command.entity_name,
+                &file_name,
+                ATTACHMENT_RELATIONSHIP,
+                &update,
+            )
+            .await?;
+    }
+
+    Ok(attachment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Root, RootCollection};
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use mockall::predicate::*;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    fn entity(name: &str) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_attach_file_reference_creates_file_and_relationship() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("design.md"), b"design").unwrap();
+        let root_dir = dir.path().to_path_buf();
+        let file_name = file_entity_name(&root_dir.join("design.md"));
+
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name()
+            .with(eq("decision:1"))
+            .returning(|n| Ok(Some(entity(n))));
+        let expected_file = file_name.clone();
+        mock.expect_find_entity_by_name()
+            .withf(move |n| n == expected_file)
+            .returning(|_| Ok(None));
+        let expected_file = file_name.clone();
+        mock.expect_create_entities()
+            .withf(move |e| e.len() == 1 && e[0].name == expected_file)
+            .returning(|_| Ok(()));
+        mock.expect_find_relationships()
+            .returning(|_, _, _| Ok(vec![]));
+        let expected_hash = content_hash(b"design");
+        mock.expect_create_relationships()
+            .withf(move |rels| {
+                rels.len() == 1
+                    && rels[0].name == ATTACHMENT_RELATIONSHIP
+                    && FileAttachment::from_properties(&rels[0].properties)
+                        .is_some_and(|a| a.content_hash == expected_hash)
+            })
+            .returning(|_| Ok(()));
+
+        let root_uri = url::Url::from_file_path(&root_dir).unwrap().to_string();
+        let roots = RootCollection::from_roots(vec![Root::new(None, root_uri.clone())]);
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| {
+            p.memory_service = Arc::new(service);
+            p.roots = Arc::new(RwLock::new(roots));
+        });
+
+        let attachment = attach_file_reference(
+            &ports,
+            AttachFileReferenceCommand {
+                entity_name: "decision:1".to_string(),
+                path: PathBuf::from("design.md"),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(attachment.root, Some(root_uri));
+        assert_eq!(attachment.content_hash, content_hash(b"design"));
+    }
+
+    #[tokio::test]
+    async fn test_attach_file_reference_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().never();
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let result = attach_file_reference(
+            &ports,
+            AttachFileReferenceCommand {
+                entity_name: "decision:1".to_string(),
+                path: dir.path().join("missing.md"),
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(CoreError::FileRead { .. })));
+    }
+}
@@ -0,0 +1,12 @@
+pub mod types;
+
+mod attach_file_reference;
+mod verify_attachments;
+
+pub use attach_file_reference::{
+    AttachFileReferenceCommand, AttachFileReferenceResult, attach_file_reference,
+};
+pub use types::{AttachmentState, AttachmentStatus, FileAttachment};
+pub use verify_attachments::{
+    VerifyAttachments, VerifyAttachmentsCommand, VerifyAttachmentsResult, verify_attachments,
+};
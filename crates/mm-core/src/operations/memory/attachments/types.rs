@@ -0,0 +1,135 @@
+use chrono::{DateTime, Utc};
+use mm_memory::MemoryValue;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Relationship type linking an entity to an attached file
+pub const ATTACHMENT_RELATIONSHIP: &str = "references";
+
+/// Name of the `File` entity representing `path`
+pub fn file_entity_name(path: &Path) -> String {
+    format!("file:{}", path.display())
+}
+
+/// Snapshot of a file recorded on an entity
+///
+/// Stored as the properties of the [`ATTACHMENT_RELATIONSHIP`] relationship
+/// between the entity and the `File` entity.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct FileAttachment {
+    /// Absolute path of the file
+    pub path: String,
+    /// URI of the workspace root containing the file, if known
+    pub root: Option<String>,
+    /// Hex-encoded SHA-256 of the file contents when it was attached
+    pub content_hash: String,
+    /// When the file was attached
+    #[schemars(with = "String")]
+    pub attached_at: DateTime<Utc>,
+}
+
+impl FileAttachment {
+    /// Read an attachment back from relationship properties
+    ///
+    /// Returns `None` for `references` relationships that do not carry a file
+    /// snapshot.
+    pub fn from_properties(props: &HashMap<String, MemoryValue>) -> Option<Self> {
+        let string = |key: &str| match props.get(key) {
+            Some(MemoryValue::String(s)) => Some(s.clone()),
+            _ => None,
+        };
+        let attached_at = match props.get("attached_at") {
+            Some(MemoryValue::DateTime(dt)) => dt.with_timezone(&Utc),
+            Some(MemoryValue::String(s)) => DateTime::parse_from_rfc3339(s).ok()?.into(),
+            _ => return None,
+        };
+        Some(Self {
+            path: string("path")?,
+            root: string("root"),
+            content_hash: string("content_hash")?,
+            attached_at,
+        })
+    }
+}
+
+impl From<FileAttachment> for HashMap<String, MemoryValue> {
+    fn from(attachment: FileAttachment) -> Self {
+        let mut map = HashMap::new();
+        map.insert("path".to_string(), MemoryValue::String(attachment.path));
+        if let Some(root) = attachment.root {
+            map.insert("root".to_string(), MemoryValue::String(root));
+        }
+        map.insert(
+            "content_hash".to_string(),
+            MemoryValue::String(attachment.content_hash),
+        );
+        map.insert(
+            "attached_at".to_string(),
+            MemoryValue::DateTime(attachment.attached_at.into()),
+        );
+        map
+    }
+}
+
+/// Outcome of checking an attachment against the file on disk
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentState {
+    /// The file still has the recorded hash
+    Unchanged,
+    /// The file contents differ from the recorded hash
+    Changed,
+    /// The file can no longer be read
+    Missing,
+}
+
+/// Verification result for a single attachment
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct AttachmentStatus {
+    /// The recorded attachment
+    pub attachment: FileAttachment,
+    /// Hash of the file as it is now, if it could be read
+    pub current_hash: Option<String>,
+    /// Whether the file still matches the snapshot
+    pub state: AttachmentState,
+}
+
+/// Hex-encoded SHA-256 of `contents`
+pub fn content_hash(contents: &[u8]) -> String {
+    Sha256::digest(contents)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(
+            content_hash(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_attachment_round_trip() {
+        let attachment = FileAttachment {
+            path: "/work/repo/docs/design.md".to_string(),
+            root: Some("file:///work/repo".to_string()),
+            content_hash: content_hash(b"design"),
+            attached_at: Utc::now(),
+        };
+        let props: HashMap<String, MemoryValue> = attachment.clone().into();
+        let restored = FileAttachment::from_properties(&props).unwrap();
+        assert_eq!(restored.path, attachment.path);
+        assert_eq!(restored.root, attachment.root);
+        assert_eq!(restored.content_hash, attachment.content_hash);
+        assert!(FileAttachment::from_properties(&HashMap::new()).is_none());
+    }
+}
@@ -0,0 +1,152 @@
+use mm_git::GitRepository;
+use mm_memory::MemoryRepository;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use super::types::{
+    ATTACHMENT_RELATIONSHIP, AttachmentState, AttachmentStatus, FileAttachment, content_hash,
+};
+use crate::error::CoreResult;
+use crate::ports::Ports;
+
+#[derive(Debug, Clone)]
+pub struct VerifyAttachmentsCommand {
+    /// Entity whose attachments are checked
+    pub entity_name: String,
+}
+
+/// Result of verifying the attachments of an entity
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct VerifyAttachments {
+    /// Status of every file attached to the entity
+    pub attachments: Vec<AttachmentStatus>,
+    /// Whether any attachment changed or went missing
+    pub stale: bool,
+}
+
+pub type VerifyAttachmentsResult<E> = CoreResult<VerifyAttachments, E>;
+
+/// Re-hash the files attached to an entity and flag those that changed
+#[instrument(skip(ports), fields(entity_name = %command.entity_name))]
+pub async fn verify_attachments<M, G>(
+    ports: &Ports<M, G>,
+    command: VerifyAttachmentsCommand,
+) -> VerifyAttachmentsResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.entity_name);
+
+    let relationships = ports
+        .memory_service
+        .find_relationships(
+            Some(command.entity_name),
+            None,
+            Some(ATTACHMENT_RELATIONSHIP.to_string()),
+        )
+        .await?;
+
+    let mut attachments = Vec::new();
+    for attachment in relationships
+        .iter()
+        .filter_map(|r| FileAttachment::from_properties(&r.properties))
+    {
+        let current_hash = tokio::fs::read(&attachment.path)
+            .await
+            .ok()
+            .map(|contents| content_hash(&contents));
+        let state = match &current_hash {
+            None => AttachmentState::Missing,
+            Some(hash) if *hash == attachment.content_hash => AttachmentState::Unchanged,
+            Some(_) => AttachmentState::Changed,
+        };
+        attachments.push(AttachmentStatus {
+            attachment,
+            current_hash,
+            state,
+        });
+    }
+
+    let stale = attachments
+        .iter()
+        .any(|a| a.state != AttachmentState::Unchanged);
+    Ok(VerifyAttachments { attachments, stale })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use mm_memory::{MemoryConfig, MemoryRelationship, MemoryService, MockMemoryRepository};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn attachment_rel(path: &std::path::Path, contents: &[u8]) -> MemoryRelationship {
+        let attachment = FileAttachment {
+            path: path.display().to_string(),
+            root: None,
+            content_hash: content_hash(contents),
+            attached_at: Utc::now(),
+        };
+        MemoryRelationship {
+            from: "decision:1".to_string(),
+            to: format!("file:{}", path.display()),
+            name: ATTACHMENT_RELATIONSHIP.to_string(),
+            properties: attachment.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_attachments_flags_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let same = dir.path().join("same.md");
+        let changed = dir.path().join("changed.md");
+        std::fs::write(&same, b"same").unwrap();
+        std::fs::write(&changed, b"new").unwrap();
+
+        let rels = vec![
+            attachment_rel(&same, b"same"),
+            attachment_rel(&changed, b"old"),
+            attachment_rel(&dir.path().join("gone.md"), b"gone"),
+            MemoryRelationship {
+                from: "decision:1".to_string(),
+                to: "other".to_string(),
+                name: ATTACHMENT_RELATIONSHIP.to_string(),
+                properties: HashMap::default(),
+            },
+        ];
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_relationships()
+            .withf(|from, to, name| {
+                from.as_deref() == Some("decision:1")
+                    && to.is_none()
+                    && name.as_deref() == Some(ATTACHMENT_RELATIONSHIP)
+            })
+            .returning(move |_, _, _| Ok(rels.clone()));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let result = verify_attachments(
+            &ports,
+            VerifyAttachmentsCommand {
+                entity_name: "decision:1".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let states: Vec<_> = result.attachments.iter().map(|a| a.state.clone()).collect();
+        assert_eq!(
+            states,
+            [
+                AttachmentState::Unchanged,
+                AttachmentState::Changed,
+                AttachmentState::Missing
+            ]
+        );
+        assert!(result.stale);
+    }
+}
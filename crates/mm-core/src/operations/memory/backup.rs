@@ -0,0 +1,404 @@
+//! Backup archives of the memory graph.
+//!
+//! An archive is a [`GraphExport`] together with a [`BackupManifest`]
+//! describing it. Restoring checks the manifest first, so a truncated, edited
+//! or too new archive is rejected with a list of every mismatch before
+//! anything is written. Restores go through [`import_graph`], so they can be
+//! limited to part of the graph.
+
+use super::attachments::types::content_hash;
+use super::export::{ExportGraphCommand, GraphExport, export_graph, sort_keys};
+use super::import::{ImportOptions, ImportSummary, import_graph};
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use chrono::{DateTime, Utc};
+use mm_git::GitRepository;
+use mm_memory::MemoryRepository;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use tracing::instrument;
+
+/// Archive layout written by this version; older layouts are still restored
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// Description of an archive's contents used to verify it on restore
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct BackupManifest {
+    /// Archive layout version, see [`BACKUP_FORMAT_VERSION`]
+    pub format_version: u32,
+    /// Version of the tool that wrote the archive
+    pub tool_version: String,
+    #[schemars(with = "String")]
+    pub created_at: DateTime<Utc>,
+    pub entity_count: usize,
+    pub relationship_count: usize,
+    /// SHA-256 of the entities carrying each label
+    pub label_hashes: BTreeMap<String, String>,
+    /// SHA-256 of all relationships
+    pub relationships_hash: String,
+}
+
+/// Backup of the memory graph
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct BackupArchive {
+    pub manifest: BackupManifest,
+    pub graph: GraphExport,
+}
+
+/// Difference between an archive's manifest and its contents
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ManifestMismatch {
+    /// Archive was written by a newer, unknown layout
+    UnsupportedVersion {
+        found: u32,
+        supported: u32,
+    },
+    EntityCount {
+        expected: usize,
+        found: usize,
+    },
+    RelationshipCount {
+        expected: usize,
+        found: usize,
+    },
+    /// Entities with `label` differ from what was backed up; a `None` hash
+    /// means the label is missing on that side
+    LabelHash {
+        label: String,
+        expected: Option<String>,
+        found: Option<String>,
+    },
+    RelationshipsHash {
+        expected: String,
+        found: String,
+    },
+}
+
+impl fmt::Display for ManifestMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion { found, supported } => write!(
+                f,
+                "archive format version {found} is newer than the supported version {supported}"
+            ),
+            Self::EntityCount { expected, found } => {
+                write!(f, "expected {expected} entities, found {found}")
+            }
+            Self::RelationshipCount { expected, found } => {
+                write!(f, "expected {expected} relationships, found {found}")
+            }
+            Self::LabelHash {
+                label,
+                expected: None,
+                ..
+            } => write!(f, "label '{label}' is not in the manifest"),
+            Self::LabelHash {
+                label, found: None, ..
+            } => write!(f, "no entities with label '{label}'"),
+            Self::LabelHash { label, .. } => {
+                write!(f, "entities with label '{label}' do not match their hash")
+            }
+            Self::RelationshipsHash { .. } => {
+                write!(f, "relationships do not match their hash")
+            }
+        }
+    }
+}
+
+/// Write every mismatch on its own line
+pub(crate) fn format_mismatches(mismatches: &[ManifestMismatch]) -> String {
+    mismatches.iter().map(|m| format!("\n  - {m}")).collect()
+}
+
+/// Hash of the canonical JSON form of `value`
+fn canonical_hash<T: Serialize>(value: &T) -> String {
+    let value = sort_keys(serde_json::to_value(value).unwrap_or_default());
+    content_hash(value.to_string().as_bytes())
+}
+
+fn label_hashes(graph: &GraphExport) -> BTreeMap<String, String> {
+    let mut by_label: BTreeMap<&str, Vec<_>> = BTreeMap::new();
+    for entity in &graph.entities {
+        for label in &entity.labels {
+            by_label.entry(label).or_default().push(entity);
+        }
+    }
+    by_label
+        .into_iter()
+        .map(|(label, entities)| (label.to_string(), canonical_hash(&entities)))
+        .collect()
+}
+
+impl BackupArchive {
+    /// Wrap `graph` with a manifest describing it
+    pub fn new(graph: GraphExport, tool_version: impl Into<String>) -> Self {
+        let manifest = BackupManifest {
+            format_version: BACKUP_FORMAT_VERSION,
+            tool_version: tool_version.into(),
+            created_at: Utc::now(),
+            entity_count: graph.entities.len(),
+            relationship_count: graph.relationships.len(),
+            label_hashes: label_hashes(&graph),
+            relationships_hash: canonical_hash(&graph.relationships),
+        };
+        Self { manifest, graph }
+    }
+
+    /// Compare the manifest with the contents; empty when the archive is intact
+    pub fn verify(&self) -> Vec<ManifestMismatch> {
+        let manifest = &self.manifest;
+        if manifest.format_version > BACKUP_FORMAT_VERSION {
+            return vec![ManifestMismatch::UnsupportedVersion {
+                found: manifest.format_version,
+                supported: BACKUP_FORMAT_VERSION,
+            }];
+        }
+        let mut mismatches = Vec::new();
+        if manifest.entity_count != self.graph.entities.len() {
+            mismatches.push(ManifestMismatch::EntityCount {
+                expected: manifest.entity_count,
+                found: self.graph.entities.len(),
+            });
+        }
+        if manifest.relationship_count != self.graph.relationships.len() {
+            mismatches.push(ManifestMismatch::RelationshipCount {
+                expected: manifest.relationship_count,
+                found: self.graph.relationships.len(),
+            });
+        }
+
+        let mut found = label_hashes(&self.graph);
+        for (label, expected) in &manifest.label_hashes {
+            let actual = found.remove(label);
+            if actual.as_ref() != Some(expected) {
+                mismatches.push(ManifestMismatch::LabelHash {
+                    label: label.clone(),
+                    expected: Some(expected.clone()),
+                    found: actual,
+                });
+            }
+        }
+        mismatches.extend(
+            found
+                .into_iter()
+                .map(|(label, hash)| ManifestMismatch::LabelHash {
+                    label,
+                    expected: None,
+                    found: Some(hash),
+                }),
+        );
+
+        let relationships_hash = canonical_hash(&self.graph.relationships);
+        if manifest.relationships_hash != relationships_hash {
+            mismatches.push(ManifestMismatch::RelationshipsHash {
+                expected: manifest.relationships_hash.clone(),
+                found: relationships_hash,
+            });
+        }
+        mismatches
+    }
+
+    /// Pretty-printed JSON with sorted keys
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        let value = sort_keys(serde_json::to_value(self)?);
+        let mut out = serde_json::to_string_pretty(&value)?;
+        out.push('\n');
+        Ok(out)
+    }
+
+    /// Parse an archive, checking its format version before its layout
+    ///
+    /// An archive from a newer version is reported as
+    /// [`ManifestMismatch::UnsupportedVersion`] rather than as a parse error.
+    pub fn from_json<E>(text: &str) -> CoreResult<Self, E>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let value: serde_json::Value = serde_json::from_str(text)?;
+        let version = value
+            .pointer("/manifest/format_version")
+            .and_then(serde_json::Value::as_u64);
+        if let Some(found) = version.filter(|v| *v > u64::from(BACKUP_FORMAT_VERSION)) {
+            return Err(CoreError::InvalidBackup(vec![
+                ManifestMismatch::UnsupportedVersion {
+                    found: u32::try_from(found).unwrap_or(u32::MAX),
+                    supported: BACKUP_FORMAT_VERSION,
+                },
+            ]));
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+/// Command for backing up the whole memory graph
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct CreateBackupCommand {
+    /// Version recorded in the manifest
+    pub tool_version: String,
+}
+
+pub type CreateBackupResult<E> = CoreResult<BackupArchive, E>;
+
+/// Export every entity and relationship into an archive
+#[instrument(skip(ports), err)]
+pub async fn create_backup<M, G>(
+    ports: &Ports<M, G>,
+    command: CreateBackupCommand,
+) -> CreateBackupResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let graph = export_graph(ports, ExportGraphCommand::default()).await?;
+    Ok(BackupArchive::new(graph, command.tool_version))
+}
+
+/// Command for restoring an archive
+#[derive(Debug, Clone)]
+pub struct RestoreBackupCommand {
+    pub archive: BackupArchive,
+    pub options: ImportOptions,
+}
+
+impl RestoreBackupCommand {
+    /// Restore the whole archive, skipping entities that already exist
+    pub fn new(archive: BackupArchive) -> Self {
+        Self {
+            archive,
+            options: ImportOptions::default(),
+        }
+    }
+}
+
+pub type RestoreBackupResult<E> = CoreResult<ImportSummary, E>;
+
+/// Verify an archive and restore the selected part of it
+///
+/// Fails with [`CoreError::InvalidBackup`] before writing anything when the
+/// manifest does not match the contents; see [`import_graph`] for the rest.
+#[instrument(skip(ports, command), fields(project = ?command.options.project, on_conflict = ?command.options.on_conflict), err)]
+pub async fn restore_backup<M, G>(
+    ports: &Ports<M, G>,
+    command: RestoreBackupCommand,
+) -> RestoreBackupResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let archive = command.archive;
+    let mismatches = archive.verify();
+    if !mismatches.is_empty() {
+        return Err(CoreError::InvalidBackup(mismatches));
+    }
+    import_graph(ports, archive.graph, &command.options, |_, _| {}).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::export::{ExportedEntity, ExportedRelationship};
+    use super::*;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    type TestError = std::convert::Infallible;
+
+    fn archive() -> BackupArchive {
+        let entity = |name: &str, labels: &[&str]| ExportedEntity {
+            name: name.to_string(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            observations: vec![format!("about {name}")],
+            properties: BTreeMap::new(),
+        };
+        let graph = GraphExport {
+            entities: vec![
+                entity("a:project:one", &["Memory", "Project"]),
+                entity("a:task:one", &["Memory", "Task"]),
+            ],
+            relationships: vec![ExportedRelationship {
+                from: "a:project:one".to_string(),
+                to: "a:task:one".to_string(),
+                name: "contains".to_string(),
+                properties: BTreeMap::new(),
+            }],
+        };
+        BackupArchive::new(graph, "0.1.0")
+    }
+
+    #[test]
+    fn test_intact_archive_round_trips() {
+        let archive = archive();
+        assert!(archive.verify().is_empty());
+        assert_eq!(
+            archive.manifest.label_hashes.keys().collect::<Vec<_>>(),
+            ["Memory", "Project", "Task"]
+        );
+
+        let parsed = BackupArchive::from_json::<TestError>(&archive.to_json().unwrap()).unwrap();
+        assert_eq!(parsed, archive);
+    }
+
+    #[test]
+    fn test_verify_reports_each_mismatch() {
+        let mut archive = archive();
+        archive.graph.entities[1].observations.push("edited".into());
+        archive.graph.relationships.clear();
+
+        let mismatches = archive.verify();
+        assert!(mismatches.contains(&ManifestMismatch::RelationshipCount {
+            expected: 1,
+            found: 0
+        }));
+        let labels: Vec<&str> = mismatches
+            .iter()
+            .filter_map(|m| match m {
+                ManifestMismatch::LabelHash { label, .. } => Some(label.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(labels, ["Memory", "Task"]);
+        assert!(matches!(
+            mismatches.last(),
+            Some(ManifestMismatch::RelationshipsHash { .. })
+        ));
+    }
+
+    #[test]
+    fn test_newer_format_is_rejected_before_parsing() {
+        let text = r#"{"manifest": {"format_version": 99}, "graph": "unknown layout"}"#;
+        let err = BackupArchive::from_json::<TestError>(text).unwrap_err();
+        match err {
+            CoreError::InvalidBackup(mismatches) => assert_eq!(
+                mismatches,
+                [ManifestMismatch::UnsupportedVersion {
+                    found: 99,
+                    supported: BACKUP_FORMAT_VERSION
+                }]
+            ),
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_corrupted_archive_without_writing() {
+        let mut archive = archive();
+        archive.graph.entities.pop();
+
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_create_graph().never();
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let err = restore_backup(&ports, RestoreBackupCommand::new(archive))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CoreError::InvalidBackup(_)));
+        assert!(err.to_string().contains("expected 2 entities, found 1"));
+    }
+}
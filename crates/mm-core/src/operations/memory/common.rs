@@ -6,20 +6,28 @@ macro_rules! validate_name {
             ));
         }
     };
+    // Names of new entities must also follow the naming policy
+    ($name:expr, $ports:expr) => {
+        validate_name!($name);
+        $ports
+            .memory_service
+            .check_entity_name(&$name)
+            .map_err($crate::error::CoreError::Validation)?;
+    };
 }
 
 use crate::error::{CoreError, CoreResult};
-use mm_memory::{MemoryError, ValidationError};
+use mm_memory::{BatchItemError, MemoryError};
 
 /// Handle the result of a batch memory service call.
 ///
 /// This utility executes a future returned by `MemoryService` methods that
-/// produce a list of `(name, ValidationError)` tuples on success. If the list
-/// is empty the operation succeeded, otherwise a `CoreError::BatchValidation`
-/// is returned.
-pub async fn handle_batch_result<F, Fut, E>(fut: F) -> CoreResult<(), E>
+/// produce a list of [`BatchItemError`]s on success. If the list is empty the
+/// operation succeeded, otherwise a `CoreError::BatchValidation` is returned
+/// with each path prefixed by `field`, the request field holding the batch.
+pub async fn handle_batch_result<F, Fut, E>(field: &str, fut: F) -> CoreResult<(), E>
 where
-    Fut: std::future::Future<Output = Result<Vec<(String, ValidationError)>, MemoryError<E>>>,
+    Fut: std::future::Future<Output = Result<Vec<BatchItemError>, MemoryError<E>>>,
     F: FnOnce() -> Fut,
     E: std::error::Error + Send + Sync + 'static,
 {
@@ -27,7 +35,9 @@ where
     if errors.is_empty() {
         Ok(())
     } else {
-        Err(CoreError::BatchValidation(errors))
+        Err(CoreError::BatchValidation(
+            errors.into_iter().map(|e| e.in_field(field)).collect(),
+        ))
     }
 }
 
@@ -0,0 +1,86 @@
+use super::export::{ExportGraphCommand, export_graph};
+use super::import::{ImportOptions, ImportSummary, import_graph};
+use crate::error::CoreResult;
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::MemoryRepository;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+/// Command for copying part of one memory backend into another
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct CopyGraphCommand {
+    #[serde(flatten)]
+    pub options: ImportOptions,
+}
+
+pub type CopyGraphResult<E> = CoreResult<ImportSummary, E>;
+
+/// Export the selected subgraph from `from` and import it into `to`
+///
+/// `progress` is called with the number of entities written so far and the
+/// number selected.
+#[instrument(skip(from, to, progress), err)]
+pub async fn copy_graph<M, G>(
+    from: &Ports<M, G>,
+    to: &Ports<M, G>,
+    command: CopyGraphCommand,
+    progress: impl Fn(usize, usize) + Send + Sync,
+) -> CopyGraphResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    // A project subtree may pass through entities without the wanted labels,
+    // so only narrow the export when no project is given.
+    let export = ExportGraphCommand {
+        labels: match command.options.project {
+            Some(_) => Vec::new(),
+            None => command.options.labels.clone(),
+        },
+    };
+    let graph = export_graph(from, export).await?;
+    import_graph(to, graph, &command.options, progress).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_copy_writes_exported_entities_to_target() {
+        let mut source = MockMemoryRepository::new();
+        source
+            .expect_find_entities_by_labels()
+            .returning(|_, _, _| {
+                Ok(vec![MemoryEntity {
+                    name: "a:project:one".to_string(),
+                    labels: vec!["Project".to_string()],
+                    ..Default::default()
+                }])
+            });
+        let mut target = MockMemoryRepository::new();
+        target.expect_find_entity_by_name().returning(|_| Ok(None));
+        target
+            .expect_create_graph()
+            .withf(|entities, _| entities.len() == 1 && entities[0].name == "a:project:one")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let from = Ports::noop().with(|p| {
+            p.memory_service = Arc::new(MemoryService::new(source, MemoryConfig::default()))
+        });
+        let to = Ports::noop().with(|p| {
+            p.memory_service = Arc::new(MemoryService::new(target, MemoryConfig::default()))
+        });
+        let summary = copy_graph(&from, &to, CopyGraphCommand::default(), |_, _| {})
+            .await
+            .unwrap();
+        assert_eq!(summary.created, 1);
+    }
+}
@@ -1,19 +1,44 @@
 use super::common::handle_batch_result;
+use super::observation_metadata::ObservationMetadata;
 use crate::error::CoreResult;
 use crate::ports::Ports;
+use crate::warning::{HasWarnings, Warning};
 use mm_git::GitRepository;
 use mm_memory::MemoryEntity;
 use mm_memory::MemoryRepository;
+use mm_memory::ObservationSource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
 /// Command to create new entities
 #[derive(Debug, Clone)]
 pub struct CreateEntitiesCommand {
     pub entities: Vec<MemoryEntity>,
+    /// Confidence recorded for every observation of the entities
+    pub confidence: Option<f64>,
+    /// Source cited for every observation of the entities
+    pub source: Option<ObservationSource>,
+    /// Project whose vocabulary applies; the default project when unset
+    pub project_name: Option<String>,
+}
+
+/// Outcome of creating entities
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct CreateEntitiesOutcome {
+    /// Defaults applied to the created entities
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+impl HasWarnings for CreateEntitiesOutcome {
+    fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
 }
 
 /// Result type for the create_entities operation
-pub type CreateEntitiesResult<E> = CoreResult<(), E>;
+pub type CreateEntitiesResult<E> = CoreResult<CreateEntitiesOutcome, E>;
 
 /// Create a new entity
 ///
@@ -24,7 +49,7 @@ pub type CreateEntitiesResult<E> = CoreResult<(), E>;
 ///
 /// # Returns
 ///
-/// Ok(()) if the entity was created successfully, or an error
+/// The warnings raised while creating the entities, or an error
 #[instrument(skip(ports), fields(entities_count = command.entities.len()))]
 pub async fn create_entities<M, G>(
     ports: &Ports<M, G>,
@@ -36,14 +61,46 @@ where
     M::Error: std::error::Error + Send + Sync + 'static,
     G::Error: std::error::Error + Send + Sync + 'static,
 {
-    handle_batch_result(|| ports.memory_service.create_entities(&command.entities)).await
+    let metadata = ObservationMetadata {
+        confidence: command.confidence,
+        source: command.source,
+    };
+    let mut entities = command.entities;
+    if !metadata.is_empty() {
+        for entity in &mut entities {
+            metadata.validate(&entity.name)?;
+            let recorded = metadata.properties(&entity.properties, &entity.observations);
+            entity.properties.extend(recorded);
+        }
+    }
+    handle_batch_result("entities", || {
+        ports
+            .memory_service
+            .create_project_entities(command.project_name.as_deref(), &entities)
+    })
+    .await?;
+
+    let default_label = ports.memory_service.memory_config().default_label.as_ref();
+    let warnings = default_label
+        .into_iter()
+        .flat_map(|label| {
+            entities
+                .iter()
+                .filter(|e| e.labels.is_empty())
+                .map(|e| Warning::DefaultLabelApplied {
+                    entity: e.name.clone(),
+                    label: label.clone(),
+                })
+        })
+        .collect();
+    Ok(CreateEntitiesOutcome { warnings })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::error::CoreError;
-    use mm_memory::ValidationErrorKind;
+    use mm_memory::{BatchItemError, ValidationErrorKind};
     use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
     use std::sync::Arc;
 
@@ -69,11 +126,14 @@ mod tests {
         });
 
         let command = CreateEntitiesCommand {
+            project_name: None,
             entities: vec![MemoryEntity {
                 name: "test:entity".to_string(),
                 labels: vec!["Test".to_string()],
                 ..Default::default()
             }],
+            confidence: None,
+            source: None,
         };
 
         let result = create_entities(&ports, command).await;
@@ -99,17 +159,20 @@ mod tests {
         });
 
         let command = CreateEntitiesCommand {
+            project_name: None,
             entities: vec![MemoryEntity {
                 name: "".to_string(),
                 labels: vec!["Test".to_string()],
                 ..Default::default()
             }],
+            confidence: None,
+            source: None,
         };
 
         let result = create_entities(&ports, command).await;
         assert!(matches!(
             result,
-            Err(CoreError::BatchValidation(ref errs)) if errs.iter().any(|(n, e)| n.is_empty() && e.0.contains(&ValidationErrorKind::EmptyEntityName))
+            Err(CoreError::BatchValidation(ref errs)) if errs.iter().any(|BatchItemError { name: n, error: e, .. }| n.is_empty() && e.0.contains(&ValidationErrorKind::EmptyEntityName))
         ));
     }
 
@@ -137,11 +200,14 @@ mod tests {
         });
 
         let command = CreateEntitiesCommand {
+            project_name: None,
             entities: vec![MemoryEntity {
                 name: "test:entity".to_string(),
                 labels: vec!["Test".to_string()],
                 ..Default::default()
             }],
+            confidence: None,
+            source: None,
         };
 
         let result = create_entities(&ports, command).await;
@@ -168,6 +234,7 @@ mod tests {
         });
 
         let command = CreateEntitiesCommand {
+            project_name: None,
             entities: vec![
                 MemoryEntity {
                     name: "".to_string(),
@@ -178,24 +245,123 @@ mod tests {
                     ..Default::default()
                 },
             ],
+            confidence: None,
+            source: None,
         };
 
         let result = create_entities(&ports, command).await;
 
         if let Err(CoreError::BatchValidation(errs)) = result {
             assert_eq!(errs.len(), 2);
-            assert!(errs.iter().any(|(n, e)| {
-                n.is_empty()
-                    && e.0.contains(&ValidationErrorKind::EmptyEntityName)
-                    && e.0.contains(&ValidationErrorKind::NoLabels("".to_string()))
-            }));
-            assert!(errs.iter().any(|(n, e)| {
-                n == "valid:entity"
-                    && e.0
-                        .contains(&ValidationErrorKind::NoLabels("valid:entity".to_string()))
-            }));
+            assert!(errs.iter().any(
+                |BatchItemError {
+                     name: n, error: e, ..
+                 }| {
+                    n.is_empty()
+                        && e.0.contains(&ValidationErrorKind::EmptyEntityName)
+                        && e.0.contains(&ValidationErrorKind::NoLabels("".to_string()))
+                }
+            ));
+            assert!(errs.iter().any(
+                |BatchItemError {
+                     name: n, error: e, ..
+                 }| {
+                    n == "valid:entity"
+                        && e.0
+                            .contains(&ValidationErrorKind::NoLabels("valid:entity".to_string()))
+                }
+            ));
+            let paths: Vec<_> = errs.iter().map(|e| (e.index, e.path.as_str())).collect();
+            assert_eq!(paths, [(0, "entities[0]"), (1, "entities[1]")]);
         } else {
             unreachable!("Expected batch validation error");
         }
     }
+
+    #[tokio::test]
+    async fn test_create_entities_warns_about_default_label() {
+        let mut mock_repo = MockMemoryRepository::new();
+        mock_repo
+            .expect_create_entities()
+            .withf(|entities| {
+                entities
+                    .iter()
+                    .all(|e| e.labels.contains(&"Memory".to_string()))
+            })
+            .returning(|_| Ok(()));
+
+        let service = MemoryService::new(
+            mock_repo,
+            MemoryConfig {
+                default_label: Some("Memory".to_string()),
+                allow_default_labels: true,
+                ..MemoryConfig::default()
+            },
+        );
+        let ports = Ports::noop().with(|p| {
+            p.memory_service = Arc::new(service);
+        });
+
+        let command = CreateEntitiesCommand {
+            confidence: None,
+            source: None,
+            project_name: None,
+            entities: vec![
+                MemoryEntity {
+                    name: "note:bare".to_string(),
+                    ..Default::default()
+                },
+                MemoryEntity {
+                    name: "note:labeled".to_string(),
+                    labels: vec!["Memory".to_string()],
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let outcome = create_entities(&ports, command).await.unwrap();
+        assert_eq!(
+            outcome.warnings,
+            [Warning::DefaultLabelApplied {
+                entity: "note:bare".to_string(),
+                label: "Memory".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_entities_records_confidence() {
+        let mut mock_repo = MockMemoryRepository::new();
+        mock_repo
+            .expect_create_entities()
+            .withf(|entities| {
+                mm_memory::confidence::observation_confidence(&entities[0].properties, "guess")
+                    == 0.4
+            })
+            .returning(|_| Ok(()));
+        let service = MemoryService::new(
+            mock_repo,
+            MemoryConfig {
+                allow_default_labels: false,
+                ..MemoryConfig::default()
+            },
+        );
+        let ports = Ports::noop().with(|p| {
+            p.memory_service = Arc::new(service);
+        });
+
+        let command = CreateEntitiesCommand {
+            entities: vec![MemoryEntity {
+                name: "test:entity".to_string(),
+                labels: vec!["Test".to_string()],
+                observations: vec!["guess".to_string()],
+                ..Default::default()
+            }],
+            confidence: Some(0.4),
+            source: None,
+            project_name: None,
+        };
+
+        create_entities(&ports, command).await.unwrap();
+    }
 }
@@ -0,0 +1,174 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{
+    MemoryEntity, MemoryRelationship, MemoryRepository, MemoryValue, ValidationError,
+    ValidationErrorKind,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::instrument;
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct CreateFromTemplateCommand {
+    /// Name of the template
+    pub template: String,
+    /// Name of the new entity
+    pub name: String,
+    /// Project whose vocabulary and `{{project}}` variable apply; the
+    /// default project when unset
+    #[serde(default)]
+    pub project_name: Option<String>,
+    /// Observations added to the template's
+    #[serde(default)]
+    pub observations: Vec<String>,
+    /// Properties overriding the template's defaults
+    #[serde(default)]
+    pub properties: HashMap<String, MemoryValue>,
+}
+
+/// What was created from a template
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct CreateFromTemplateResult {
+    pub entity: MemoryEntity,
+    pub relationships: Vec<MemoryRelationship>,
+}
+
+pub type CreateFromTemplateResultType<E> = CoreResult<CreateFromTemplateResult, E>;
+
+/// Create an entity and its relationships shaped by a named template
+#[instrument(skip(ports), fields(template = %command.template, name = %command.name))]
+pub async fn create_from_template<M, G>(
+    ports: &Ports<M, G>,
+    command: CreateFromTemplateCommand,
+) -> CreateFromTemplateResultType<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.name, ports);
+
+    let service = &ports.memory_service;
+    let template = service
+        .find_template(&command.template)
+        .await
+        .map_err(CoreError::from)?
+        .ok_or_else(|| {
+            ValidationError::from(ValidationErrorKind::UnknownTemplate(
+                command.template.clone(),
+            ))
+        })?;
+
+    let project = command.project_name.as_deref();
+    let vars = service.memory_config().template_vars(project);
+    let (mut entity, relationships) = template.instantiate(&command.name, &vars);
+    entity.observations.extend(command.observations);
+    entity.properties.extend(command.properties);
+
+    let errors = service
+        .create_project_graph_typed(project, std::slice::from_ref(&entity), &relationships)
+        .await
+        .map_err(CoreError::from)?;
+    if !errors.is_empty() {
+        let entity_errors = errors.entities.into_iter().map(|e| e.in_field("entity"));
+        let relationship_errors = errors
+            .relationships
+            .into_iter()
+            .map(|e| e.in_field("relationships"));
+        return Err(CoreError::BatchValidation(
+            entity_errors.chain(relationship_errors).collect(),
+        ));
+    }
+
+    Ok(CreateFromTemplateResult {
+        entity,
+        relationships,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{
+        EntityTemplate, MemoryConfig, MemoryService, MockMemoryRepository, TemplateRelationship,
+    };
+    use std::sync::Arc;
+
+    fn config() -> MemoryConfig {
+        let template = EntityTemplate {
+            labels: vec!["Technology".to_string()],
+            properties: HashMap::from([
+                ("status".to_string(), MemoryValue::String("trial".into())),
+                ("kind".to_string(), MemoryValue::String("library".into())),
+            ]),
+            relationships: vec![TemplateRelationship {
+                name: "relates_to".to_string(),
+                to: "{{project}}".to_string(),
+            }],
+            ..Default::default()
+        };
+        MemoryConfig {
+            templates: HashMap::from([("technology".to_string(), template)]),
+            ..MemoryConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_from_template_applies_overrides() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_create_graph()
+            .withf(|entities, relationships| {
+                entities.len() == 1
+                    && entities[0].properties["status"] == MemoryValue::String("adopted".into())
+                    && relationships.len() == 1
+                    && relationships[0].to == "mm"
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let service = MemoryService::new(mock, config());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let command = CreateFromTemplateCommand {
+            template: "technology".to_string(),
+            name: "tech:tokio".to_string(),
+            project_name: Some("mm".to_string()),
+            observations: vec!["Async runtime".to_string()],
+            properties: HashMap::from([(
+                "status".to_string(),
+                MemoryValue::String("adopted".into()),
+            )]),
+        };
+
+        let result = create_from_template(&ports, command).await.unwrap();
+        assert_eq!(result.entity.labels, ["Technology"]);
+        assert_eq!(
+            result.entity.properties["kind"],
+            MemoryValue::String("library".into())
+        );
+        assert_eq!(result.entity.observations, ["Async runtime"]);
+    }
+
+    #[tokio::test]
+    async fn test_create_from_template_unknown_template() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|_| Ok(None));
+        let service = MemoryService::new(mock, config());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let command = CreateFromTemplateCommand {
+            template: "adr".to_string(),
+            name: "adr:1".to_string(),
+            project_name: None,
+            observations: Vec::new(),
+            properties: HashMap::new(),
+        };
+
+        let result = create_from_template(&ports, command).await;
+        assert!(matches!(
+            result,
+            Err(CoreError::Validation(ValidationError(kinds)))
+                if kinds == [ValidationErrorKind::UnknownTemplate("adr".to_string())]
+        ));
+    }
+}
@@ -8,6 +8,8 @@ use tracing::instrument;
 #[derive(Debug, Clone)]
 pub struct CreateRelationshipsCommand {
     pub relationships: Vec<MemoryRelationship>,
+    /// Project whose vocabulary applies; the default project when unset
+    pub project_name: Option<String>,
 }
 
 pub type CreateRelationshipsResult<E> = CoreResult<(), E>;
@@ -23,10 +25,10 @@ where
     M::Error: std::error::Error + Send + Sync + 'static,
     G::Error: std::error::Error + Send + Sync + 'static,
 {
-    handle_batch_result(|| {
+    handle_batch_result("relationships", || {
         ports
             .memory_service
-            .create_relationships(&command.relationships)
+            .create_project_relationships(command.project_name.as_deref(), &command.relationships)
     })
     .await
 }
@@ -35,7 +37,7 @@ where
 mod tests {
     use super::*;
     use crate::error::CoreError;
-    use mm_memory::ValidationErrorKind;
+    use mm_memory::{BatchItemError, ValidationErrorKind};
     use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
     use std::collections::HashMap;
     use std::sync::Arc;
@@ -53,6 +55,7 @@ mod tests {
         });
 
         let command = CreateRelationshipsCommand {
+            project_name: None,
             relationships: vec![MemoryRelationship {
                 from: "a".to_string(),
                 to: "b".to_string(),
@@ -75,6 +78,7 @@ mod tests {
         });
 
         let command = CreateRelationshipsCommand {
+            project_name: None,
             relationships: vec![MemoryRelationship {
                 from: "a".to_string(),
                 to: "b".to_string(),
@@ -86,7 +90,7 @@ mod tests {
         let result = create_relationships(&ports, command).await;
         assert!(matches!(
             result,
-            Err(CoreError::BatchValidation(ref errs)) if errs.iter().any(|(n, e)| n.is_empty() && e.0.contains(&ValidationErrorKind::UnknownRelationship("".to_string())))
+            Err(CoreError::BatchValidation(ref errs)) if errs.iter().any(|BatchItemError { name: n, error: e, .. }| n.is_empty() && e.0.contains(&ValidationErrorKind::UnknownRelationship("".to_string())))
         ));
     }
 
@@ -100,6 +104,7 @@ mod tests {
         });
 
         let command = CreateRelationshipsCommand {
+            project_name: None,
             relationships: vec![MemoryRelationship {
                 from: "a".to_string(),
                 to: "b".to_string(),
@@ -111,7 +116,7 @@ mod tests {
         let result = create_relationships(&ports, command).await;
         assert!(matches!(
             result,
-            Err(CoreError::BatchValidation(ref errs)) if errs.iter().any(|(n, e)| n == "InvalidFormat" && e.0.contains(&ValidationErrorKind::InvalidRelationshipFormat("InvalidFormat".to_string())))
+            Err(CoreError::BatchValidation(ref errs)) if errs.iter().any(|BatchItemError { name: n, error: e, .. }| n == "InvalidFormat" && e.0.contains(&ValidationErrorKind::InvalidRelationshipFormat("InvalidFormat".to_string())))
         ));
     }
 
@@ -125,6 +130,7 @@ mod tests {
         });
 
         let command = CreateRelationshipsCommand {
+            project_name: None,
             relationships: vec![MemoryRelationship {
                 from: "a".to_string(),
                 to: "b".to_string(),
@@ -136,7 +142,7 @@ mod tests {
         let result = create_relationships(&ports, command).await;
         assert!(matches!(
             result,
-            Err(CoreError::BatchValidation(ref errs)) if errs.iter().any(|(n, e)| n == "custom_rel" && e.0.contains(&ValidationErrorKind::UnknownRelationship("custom_rel".to_string())))
+            Err(CoreError::BatchValidation(ref errs)) if errs.iter().any(|BatchItemError { name: n, error: e, .. }| n == "custom_rel" && e.0.contains(&ValidationErrorKind::UnknownRelationship("custom_rel".to_string())))
         ));
     }
 
@@ -151,6 +157,7 @@ mod tests {
         });
 
         let command = CreateRelationshipsCommand {
+            project_name: None,
             relationships: vec![
                 MemoryRelationship {
                     from: "a".to_string(),
@@ -171,20 +178,61 @@ mod tests {
 
         if let Err(CoreError::BatchValidation(errs)) = result {
             assert_eq!(errs.len(), 2);
-            assert!(errs.iter().any(|(n, e)| {
-                n == "Invalid"
-                    && e.0
-                        .contains(&ValidationErrorKind::InvalidRelationshipFormat(
-                            "Invalid".to_string(),
-                        ))
-            }));
-            assert!(errs.iter().any(|(n, e)| {
-                n.is_empty()
-                    && e.0
-                        .contains(&ValidationErrorKind::UnknownRelationship("".to_string()))
-            }));
+            assert!(errs.iter().any(
+                |BatchItemError {
+                     name: n, error: e, ..
+                 }| {
+                    n == "Invalid"
+                        && e.0
+                            .contains(&ValidationErrorKind::InvalidRelationshipFormat(
+                                "Invalid".to_string(),
+                            ))
+                }
+            ));
+            assert!(errs.iter().any(
+                |BatchItemError {
+                     name: n, error: e, ..
+                 }| {
+                    n.is_empty()
+                        && e.0
+                            .contains(&ValidationErrorKind::UnknownRelationship("".to_string()))
+                }
+            ));
         } else {
             unreachable!("Expected batch validation error");
         }
     }
+
+    #[tokio::test]
+    async fn test_create_relationships_project_vocabulary() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_create_relationships()
+            .times(1)
+            .returning(|_| Ok(()));
+        let config = MemoryConfig {
+            projects: HashMap::from([(
+                "alpha".to_string(),
+                mm_memory::ProjectVocabulary {
+                    allowed_relationships: ["measures".to_string()].into(),
+                    ..Default::default()
+                },
+            )]),
+            ..MemoryConfig::default()
+        };
+        let service = MemoryService::new(mock, config);
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let command = |project: &str| CreateRelationshipsCommand {
+            project_name: Some(project.to_string()),
+            relationships: vec![MemoryRelationship {
+                from: "a".to_string(),
+                to: "b".to_string(),
+                name: "measures".to_string(),
+                properties: HashMap::default(),
+            }],
+        };
+
+        assert!(create_relationships(&ports, command("alpha")).await.is_ok());
+        let result = create_relationships(&ports, command("beta")).await;
+        assert!(matches!(result, Err(CoreError::BatchValidation(_))));
+    }
 }
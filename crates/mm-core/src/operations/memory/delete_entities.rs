@@ -23,5 +23,8 @@ where
     M::Error: std::error::Error + Send + Sync + 'static,
     G::Error: std::error::Error + Send + Sync + 'static,
 {
-    handle_batch_result(|| ports.memory_service.delete_entities(&command.names)).await
+    handle_batch_result("names", || {
+        ports.memory_service.delete_entities(&command.names)
+    })
+    .await
 }
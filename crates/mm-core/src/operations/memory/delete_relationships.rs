@@ -8,6 +8,8 @@ use tracing::instrument;
 #[derive(Debug, Clone)]
 pub struct DeleteRelationshipsCommand {
     pub relationships: Vec<RelationshipRef>,
+    /// Delete reserved relationships as well
+    pub force: bool,
 }
 
 pub type DeleteRelationshipsResult<E> = CoreResult<(), E>;
@@ -23,10 +25,10 @@ where
     M::Error: std::error::Error + Send + Sync + 'static,
     G::Error: std::error::Error + Send + Sync + 'static,
 {
-    handle_batch_result(|| {
+    handle_batch_result("relationships", || {
         ports
             .memory_service
-            .delete_relationships(&command.relationships)
+            .delete_relationships(&command.relationships, command.force)
     })
     .await
 }
@@ -0,0 +1,204 @@
+use chrono::Utc;
+use futures::TryStreamExt;
+use mm_git::GitRepository;
+use mm_memory::expiry::ARCHIVED_AT_PROPERTY;
+use mm_memory::labels::ARCHIVED_LABEL;
+use mm_memory::{
+    EntityUpdate, ExpiryAction, LabelMatchMode, LabelsUpdate, MemoryRepository, MemoryValue,
+    PropertiesUpdate,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tracing::instrument;
+
+use super::common::handle_batch_result;
+use crate::error::CoreResult;
+use crate::ports::Ports;
+
+#[derive(Debug, Clone, Default)]
+pub struct ExpireMemoriesCommand {
+    /// Report expired entities without changing them
+    pub dry_run: bool,
+}
+
+/// Entities handled by an expiry run, sorted by name
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ExpireMemoriesResult {
+    pub deleted: Vec<String>,
+    pub archived: Vec<String>,
+}
+
+pub type ExpireMemoriesResultType<E> = CoreResult<ExpireMemoriesResult, E>;
+
+/// Delete or archive entities whose label expiry policy has lapsed
+///
+/// Policies come from the `expiry` section of the memory configuration.
+#[instrument(skip(ports))]
+pub async fn expire_memories<M, G>(
+    ports: &Ports<M, G>,
+    command: ExpireMemoriesCommand,
+) -> ExpireMemoriesResultType<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+{
+    let config = ports.memory_service.memory_config();
+    let labels: Vec<String> = config.expiry.keys().cloned().collect();
+    if labels.is_empty() {
+        return Ok(ExpireMemoriesResult::default());
+    }
+
+    // Only the names of expired entities are kept, so large graphs are
+    // never held in memory at once
+    let now = Utc::now();
+    let mut entities = ports
+        .memory_service
+        .stream_entities_by_labels(&labels, LabelMatchMode::Any, None)
+        .await?;
+
+    let mut expired = BTreeMap::new();
+    while let Some(entity) = entities.try_next().await? {
+        if let Some(policy) = config.expiry_policy(&entity.labels)
+            && policy.is_expired(&entity, now)
+        {
+            expired.insert(entity.name, policy.action);
+        }
+    }
+
+    let mut result = ExpireMemoriesResult::default();
+    for (name, action) in expired {
+        match action {
+            ExpiryAction::Delete => result.deleted.push(name),
+            ExpiryAction::Archive => result.archived.push(name),
+        }
+    }
+    if command.dry_run {
+        return Ok(result);
+    }
+
+    if !result.deleted.is_empty() {
+        handle_batch_result("deleted", || {
+            ports.memory_service.delete_entities(&result.deleted)
+        })
+        .await?;
+    }
+
+    let archive = EntityUpdate {
+        labels: Some(LabelsUpdate {
+            add: Some(vec![ARCHIVED_LABEL.to_string()]),
+            remove: None,
+        }),
+        properties: Some(PropertiesUpdate {
+            add: Some(HashMap::from([(
+                ARCHIVED_AT_PROPERTY.to_string(),
+                MemoryValue::DateTime(now.into()),
+            )])),
+            remove: None,
+            set: None,
+        }),
+        observations: None,
+        expected_revision: None,
+        force: false,
+    };
+    for name in &result.archived {
+        ports.memory_service.update_entity(name, &archive).await?;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+    use futures::{StreamExt, stream};
+    use mm_memory::expiry::CREATED_AT_PROPERTY;
+    use mm_memory::{
+        ExpiryPolicy, MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository,
+    };
+    use mockall::predicate::*;
+    use std::sync::Arc;
+
+    fn entity(name: &str, label: &str, age: TimeDelta) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            labels: vec![label.to_string()],
+            properties: HashMap::from([(
+                CREATED_AT_PROPERTY.to_string(),
+                MemoryValue::DateTime((Utc::now() - age).into()),
+            )]),
+            ..Default::default()
+        }
+    }
+
+    fn config() -> MemoryConfig {
+        let policy = |ttl: &str, action| ExpiryPolicy {
+            ttl: ttl.parse().unwrap(),
+            action,
+        };
+        MemoryConfig {
+            expiry: HashMap::from([
+                (
+                    "GitStatusSnapshot".to_string(),
+                    policy("1d", ExpiryAction::Delete),
+                ),
+                ("Temporal".to_string(), policy("1w", ExpiryAction::Archive)),
+            ]),
+            ..MemoryConfig::default()
+        }
+    }
+
+    fn mock_with_entities() -> MockMemoryRepository {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_stream_entities_by_labels()
+            .returning(|_, _, _| {
+                let entities = vec![
+                    entity("snapshot:old", "GitStatusSnapshot", TimeDelta::days(2)),
+                    entity("snapshot:new", "GitStatusSnapshot", TimeDelta::hours(1)),
+                    entity("event:old", "Temporal", TimeDelta::weeks(2)),
+                ];
+                Ok(stream::iter(entities.into_iter().map(Ok)).boxed())
+            });
+        mock
+    }
+
+    #[tokio::test]
+    async fn test_expire_memories_applies_policies() {
+        let mut mock = mock_with_entities();
+        mock.expect_delete_entities()
+            .with(eq(vec!["snapshot:old".to_string()]))
+            .times(1)
+            .returning(|_| Ok(()));
+        mock.expect_update_entity()
+            .withf(|name, update| {
+                name == "event:old"
+                    && update.labels.as_ref().and_then(|l| l.add.clone())
+                        == Some(vec![ARCHIVED_LABEL.to_string()])
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let service = MemoryService::new(mock, config());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let result = expire_memories(&ports, ExpireMemoriesCommand::default())
+            .await
+            .unwrap();
+        assert_eq!(result.deleted, ["snapshot:old"]);
+        assert_eq!(result.archived, ["event:old"]);
+    }
+
+    #[tokio::test]
+    async fn test_expire_memories_dry_run() {
+        let mut mock = mock_with_entities();
+        mock.expect_delete_entities().never();
+        mock.expect_update_entity().never();
+        let service = MemoryService::new(mock, config());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let result = expire_memories(&ports, ExpireMemoriesCommand { dry_run: true })
+            .await
+            .unwrap();
+        assert_eq!(result.deleted.len() + result.archived.len(), 2);
+    }
+}
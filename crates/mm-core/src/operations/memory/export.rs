@@ -0,0 +1,445 @@
+//! Graph export with stable output.
+//!
+//! Entities are ordered by name, relationships by source, type and target,
+//! labels are sorted and property maps are written with sorted keys, so
+//! exporting an unchanged graph twice yields identical files that can be
+//! committed and diffed.
+
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{LabelMatchMode, MemoryEntity, MemoryRelationship, MemoryRepository, MemoryValue};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+use tracing::instrument;
+
+/// File format of an export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Pretty-printed JSON of [`GraphExport`]
+    #[default]
+    Json,
+    /// `CREATE` statements that rebuild the graph
+    Cypher,
+    /// GraphML document
+    Graphml,
+}
+
+/// Command for exporting the memory graph
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct ExportGraphCommand {
+    /// Only export entities with any of these labels; everything when empty
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+/// Entity as written to an export
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ExportedEntity {
+    pub name: String,
+    pub labels: Vec<String>,
+    pub observations: Vec<String>,
+    #[serde(default)]
+    pub properties: BTreeMap<String, MemoryValue>,
+}
+
+/// Relationship as written to an export
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ExportedRelationship {
+    pub from: String,
+    pub to: String,
+    pub name: String,
+    #[serde(default)]
+    pub properties: BTreeMap<String, MemoryValue>,
+}
+
+/// Exported subgraph in canonical order
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct GraphExport {
+    pub entities: Vec<ExportedEntity>,
+    /// Relationships between exported entities
+    pub relationships: Vec<ExportedRelationship>,
+}
+
+impl From<ExportedEntity> for MemoryEntity {
+    fn from(entity: ExportedEntity) -> Self {
+        MemoryEntity {
+            name: entity.name,
+            labels: entity.labels,
+            observations: entity.observations,
+            properties: entity.properties.into_iter().collect(),
+            relationships: Vec::new(),
+            revision: 0,
+        }
+    }
+}
+
+impl From<ExportedRelationship> for MemoryRelationship {
+    fn from(rel: ExportedRelationship) -> Self {
+        MemoryRelationship {
+            from: rel.from,
+            to: rel.to,
+            name: rel.name,
+            properties: rel.properties.into_iter().collect(),
+        }
+    }
+}
+
+pub type ExportGraphResult<E> = CoreResult<GraphExport, E>;
+
+/// Export entities and the relationships between them
+#[instrument(skip(ports), err)]
+pub async fn export_graph<M, G>(
+    ports: &Ports<M, G>,
+    command: ExportGraphCommand,
+) -> ExportGraphResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let entities = ports
+        .memory_service
+        .find_entities_by_labels(&command.labels, LabelMatchMode::Any, None)
+        .await
+        .map_err(CoreError::from)?;
+    Ok(GraphExport::from_entities(entities))
+}
+
+impl GraphExport {
+    /// Build a canonical export from loaded entities
+    ///
+    /// Relationships are taken from the entities and kept once each, and only
+    /// when both ends are among the entities.
+    pub fn from_entities(entities: Vec<MemoryEntity>) -> Self {
+        let names: BTreeSet<String> = entities.iter().map(|e| e.name.clone()).collect();
+        let mut relationships = BTreeMap::new();
+        let mut exported = Vec::with_capacity(entities.len());
+        for entity in entities {
+            for rel in entity.relationships {
+                if names.contains(&rel.from) && names.contains(&rel.to) {
+                    relationships
+                        .entry((rel.from.clone(), rel.name.clone(), rel.to.clone()))
+                        .or_insert_with(|| ExportedRelationship {
+                            from: rel.from,
+                            to: rel.to,
+                            name: rel.name,
+                            properties: sorted(rel.properties),
+                        });
+                }
+            }
+            let mut labels = entity.labels;
+            labels.sort_unstable();
+            labels.dedup();
+            exported.push(ExportedEntity {
+                name: entity.name,
+                labels,
+                observations: entity.observations,
+                properties: sorted(entity.properties),
+            });
+        }
+        exported.sort_by(|a, b| a.name.cmp(&b.name));
+        exported.dedup_by(|a, b| a.name == b.name);
+        Self {
+            entities: exported,
+            relationships: relationships.into_values().collect(),
+        }
+    }
+
+    /// Render the export in `format`; the output ends with a newline
+    pub fn render(&self, format: ExportFormat) -> Result<String, serde_json::Error> {
+        match format {
+            ExportFormat::Json => self.to_json(),
+            ExportFormat::Cypher => Ok(self.to_cypher()),
+            ExportFormat::Graphml => self.to_graphml(),
+        }
+    }
+
+    fn to_json(&self) -> Result<String, serde_json::Error> {
+        let value = sort_keys(serde_json::to_value(self)?);
+        let mut out = serde_json::to_string_pretty(&value)?;
+        out.push('\n');
+        Ok(out)
+    }
+
+    fn to_cypher(&self) -> String {
+        let mut out = String::new();
+        for entity in &self.entities {
+            let labels: String = entity
+                .labels
+                .iter()
+                .map(|l| format!(":{}", quote_identifier(l)))
+                .collect();
+            let mut props = vec![
+                format!("name: {}", string_literal(&entity.name)),
+                format!(
+                    "observations: [{}]",
+                    entity
+                        .observations
+                        .iter()
+                        .map(|o| string_literal(o))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ];
+            props.extend(property_literals(&entity.properties));
+            let _ = writeln!(out, "CREATE (n{labels} {{{}}});", props.join(", "));
+        }
+        for rel in &self.relationships {
+            let props = property_literals(&rel.properties);
+            let props = if props.is_empty() {
+                String::new()
+            } else {
+                format!(" {{{}}}", props.join(", "))
+            };
+            let _ = writeln!(
+                out,
+                "MATCH (a {{name: {}}}), (b {{name: {}}}) CREATE (a)-[:{}{props}]->(b);",
+                string_literal(&rel.from),
+                string_literal(&rel.to),
+                quote_identifier(&rel.name),
+            );
+        }
+        out
+    }
+
+    fn to_graphml(&self) -> Result<String, serde_json::Error> {
+        let mut out = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"labels\" for=\"node\" attr.name=\"labels\" attr.type=\"string\"/>\n",
+            "  <key id=\"observations\" for=\"node\" attr.name=\"observations\" attr.type=\"string\"/>\n",
+            "  <key id=\"properties\" for=\"all\" attr.name=\"properties\" attr.type=\"string\"/>\n",
+            "  <key id=\"name\" for=\"edge\" attr.name=\"name\" attr.type=\"string\"/>\n",
+            "  <graph id=\"memory\" edgedefault=\"directed\">\n",
+        ));
+        for entity in &self.entities {
+            let _ = writeln!(out, "    <node id=\"{}\">", xml_escape(&entity.name));
+            let _ = writeln!(
+                out,
+                "      <data key=\"labels\">{}</data>",
+                xml_escape(&entity.labels.join(":"))
+            );
+            let observations = serde_json::to_string(&entity.observations)?;
+            let _ = writeln!(
+                out,
+                "      <data key=\"observations\">{}</data>",
+                xml_escape(&observations)
+            );
+            graphml_properties(&mut out, &entity.properties)?;
+            out.push_str("    </node>\n");
+        }
+        for rel in &self.relationships {
+            let _ = writeln!(
+                out,
+                "    <edge source=\"{}\" target=\"{}\">",
+                xml_escape(&rel.from),
+                xml_escape(&rel.to)
+            );
+            let _ = writeln!(
+                out,
+                "      <data key=\"name\">{}</data>",
+                xml_escape(&rel.name)
+            );
+            graphml_properties(&mut out, &rel.properties)?;
+            out.push_str("    </edge>\n");
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        Ok(out)
+    }
+}
+
+fn sorted(properties: HashMap<String, MemoryValue>) -> BTreeMap<String, MemoryValue> {
+    properties.into_iter().collect()
+}
+
+/// Rebuild every JSON object with its keys in sorted order
+pub(crate) fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let sorted: BTreeMap<String, serde_json::Value> =
+                map.into_iter().map(|(k, v)| (k, sort_keys(v))).collect();
+            serde_json::Value::Object(sorted.into_iter().collect())
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(sort_keys).collect())
+        }
+        other => other,
+    }
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("`{}`", identifier.replace('`', "``"))
+}
+
+fn string_literal(value: &str) -> String {
+    // JSON string escapes are valid in Cypher string literals
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+fn property_literals(properties: &BTreeMap<String, MemoryValue>) -> Vec<String> {
+    properties
+        .iter()
+        .map(|(k, v)| format!("{}: {}", quote_identifier(k), cypher_literal(v)))
+        .collect()
+}
+
+fn cypher_literal(value: &MemoryValue) -> String {
+    match value {
+        MemoryValue::String(s) => string_literal(s),
+        MemoryValue::Integer(i) => i.to_string(),
+        MemoryValue::Float(f) => format!("{f:?}"),
+        MemoryValue::Boolean(b) => b.to_string(),
+        MemoryValue::List(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(|i| string_literal(i))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        MemoryValue::Date(d) => format!("date({})", string_literal(&d.to_string())),
+        MemoryValue::DateTime(dt) => format!("datetime({})", string_literal(&dt.to_rfc3339())),
+        MemoryValue::LocalDateTime(dt) => {
+            format!("localdatetime({})", string_literal(&dt.to_string()))
+        }
+        MemoryValue::Time(t) => format!("localtime({})", string_literal(&t.to_string())),
+        // Remaining types have no literal form; keep their JSON representation
+        other => string_literal(&sort_keys(serde_json::Value::from(other.clone())).to_string()),
+    }
+}
+
+fn graphml_properties(
+    out: &mut String,
+    properties: &BTreeMap<String, MemoryValue>,
+) -> Result<(), serde_json::Error> {
+    if properties.is_empty() {
+        return Ok(());
+    }
+    let json = sort_keys(serde_json::to_value(properties)?).to_string();
+    let _ = writeln!(
+        out,
+        "      <data key=\"properties\">{}</data>",
+        xml_escape(&json)
+    );
+    Ok(())
+}
+
+fn xml_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    fn entity(name: &str, labels: &[&str], rels: &[(&str, &str, &str)]) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            observations: vec![format!("about {name}")],
+            properties: HashMap::from([
+                ("z".to_string(), MemoryValue::Integer(1)),
+                ("a".to_string(), MemoryValue::String("x".to_string())),
+            ]),
+            relationships: rels
+                .iter()
+                .map(|(from, name, to)| MemoryRelationship {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    name: name.to_string(),
+                    properties: HashMap::default(),
+                })
+                .collect(),
+            revision: 0,
+        }
+    }
+
+    fn graph() -> Vec<MemoryEntity> {
+        vec![
+            entity("b", &["Task", "Memory"], &[("a", "contains", "b")]),
+            entity(
+                "a",
+                &["Project", "Memory"],
+                &[("a", "contains", "b"), ("a", "relates_to", "outside")],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_export_order_is_canonical() {
+        let mut reversed = graph();
+        reversed.reverse();
+        let export = GraphExport::from_entities(graph());
+        assert_eq!(export, GraphExport::from_entities(reversed));
+
+        assert_eq!(export.entities[0].name, "a");
+        assert_eq!(export.entities[0].labels, ["Memory", "Project"]);
+        assert_eq!(export.relationships.len(), 1);
+
+        for format in [
+            ExportFormat::Json,
+            ExportFormat::Cypher,
+            ExportFormat::Graphml,
+        ] {
+            let first = export.render(format).unwrap();
+            let second = GraphExport::from_entities(graph()).render(format).unwrap();
+            assert_eq!(first, second);
+            assert!(first.ends_with('\n'));
+        }
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let export = GraphExport::from_entities(graph());
+
+        let json = export.render(ExportFormat::Json).unwrap();
+        assert!(json.find("\"a\": \"x\"").unwrap() < json.find("\"z\": 1").unwrap());
+
+        let cypher = export.render(ExportFormat::Cypher).unwrap();
+        assert!(cypher.starts_with(
+            "CREATE (n:`Memory`:`Project` {name: \"a\", observations: [\"about a\"], `a`: \"x\", `z`: 1});\n"
+        ));
+        assert!(cypher.contains(
+            "MATCH (a {name: \"a\"}), (b {name: \"b\"}) CREATE (a)-[:`contains`]->(b);\n"
+        ));
+
+        let graphml = export.render(ExportFormat::Graphml).unwrap();
+        assert!(graphml.contains("<edge source=\"a\" target=\"b\">"));
+        assert!(graphml.contains("{&quot;a&quot;:&quot;x&quot;,&quot;z&quot;:1}"));
+    }
+
+    #[tokio::test]
+    async fn test_export_graph_uses_label_filter() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_by_labels()
+            .withf(|labels, mode, _| labels == ["Task".to_string()] && *mode == LabelMatchMode::Any)
+            .returning(|_, _, _| Ok(vec![entity("b", &["Task"], &[])]));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let command = ExportGraphCommand {
+            labels: vec!["Task".to_string()],
+        };
+        let export = export_graph(&ports, command).await.unwrap();
+        assert_eq!(export.entities.len(), 1);
+        assert!(export.relationships.is_empty());
+    }
+}
@@ -0,0 +1,128 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::source::observation_sources;
+use mm_memory::{MemoryEntity, MemoryRepository, ObservationSource, ValidationError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct FindBySourceCommand {
+    /// Source to trace; every field that is set must match
+    pub source: ObservationSource,
+}
+
+/// Entity with the observations learned from the requested source
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct SourceMatch {
+    pub entity: MemoryEntity,
+    /// Observations cited to the source
+    pub observations: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct FindBySourceResult {
+    pub matches: Vec<SourceMatch>,
+}
+
+pub type FindBySourceResultType<E> = CoreResult<FindBySourceResult, E>;
+
+/// Trace facts back to where they were learned
+#[instrument(skip(ports))]
+pub async fn find_by_source<M, G>(
+    ports: &Ports<M, G>,
+    command: FindBySourceCommand,
+) -> FindBySourceResultType<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    command.source.validate().map_err(ValidationError::from)?;
+
+    let entities = ports
+        .memory_service
+        .find_entities_by_source(&command.source)
+        .await
+        .map_err(CoreError::from)?;
+
+    let matches = entities
+        .into_iter()
+        .map(|entity| {
+            let observations = observation_sources(&entity.properties)
+                .into_iter()
+                .filter(|(o, s)| s.matches(&command.source) && entity.observations.contains(o))
+                .map(|(o, _)| o)
+                .collect();
+            SourceMatch {
+                entity,
+                observations,
+            }
+        })
+        .collect();
+
+    Ok(FindBySourceResult { matches })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::source::set_observation_source;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use mockall::predicate::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_find_by_source_lists_cited_observations() {
+        let source = ObservationSource {
+            file: Some("docs/design.md".to_string()),
+            ..Default::default()
+        };
+        let other = ObservationSource {
+            url: Some("https://example.com".to_string()),
+            ..Default::default()
+        };
+        let mut properties = HashMap::new();
+        set_observation_source(&mut properties, "from design", &source);
+        set_observation_source(&mut properties, "from web", &other);
+        let entity = MemoryEntity {
+            name: "decision:1".to_string(),
+            observations: vec!["from design".to_string(), "from web".to_string()],
+            properties,
+            ..Default::default()
+        };
+
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_by_source()
+            .with(eq("file:docs/design.md"))
+            .returning(move |_| Ok(vec![entity.clone()]));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let result = find_by_source(&ports, FindBySourceCommand { source })
+            .await
+            .unwrap();
+        assert_eq!(result.matches.len(), 1);
+        assert_eq!(result.matches[0].observations, ["from design"]);
+    }
+
+    #[tokio::test]
+    async fn test_find_by_source_requires_a_field() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_by_source().never();
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let result = find_by_source(
+            &ports,
+            FindBySourceCommand {
+                source: ObservationSource::default(),
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(CoreError::Validation(_))));
+    }
+}
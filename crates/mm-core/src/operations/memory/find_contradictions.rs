@@ -0,0 +1,285 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{LabelMatchMode, MemoryEntity, MemoryRepository, MemoryValue};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+/// Longest text before a `:` or `=` still treated as a key
+const MAX_KEY_LEN: usize = 40;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct FindContradictionsCommand {
+    /// Only inspect entities with any of these labels; all entities when empty
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+/// Why two facts were flagged
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContradictionKind {
+    /// An observation such as `status: archived` disagrees with a property
+    PropertyMismatch,
+    /// Two observations assign different values to the same key
+    ConflictingValues,
+    /// One observation negates another
+    Negation,
+}
+
+/// Possible contradiction within one entity
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct Contradiction {
+    /// Entity holding the conflicting facts
+    pub entity_name: String,
+    pub kind: ContradictionKind,
+    /// Conflicting observations, earliest recorded first
+    pub observations: Vec<String>,
+    /// Property the observation disagrees with, for property mismatches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub property: Option<String>,
+    /// Whether the client's model confirmed the conflict; `None` when only
+    /// the heuristics were applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmed: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct FindContradictionsResult {
+    pub contradictions: Vec<Contradiction>,
+}
+
+pub type FindContradictionsResultType<E> = CoreResult<FindContradictionsResult, E>;
+
+/// Flag entities whose facts disagree with each other
+///
+/// Detection is heuristic: `key: value` observations are compared with each
+/// other and with the entity's properties, and observations that only differ
+/// by a negation are paired up. Results are candidates for human review.
+#[instrument(skip(ports), fields(label_count = command.labels.len()))]
+pub async fn find_contradictions<M, G>(
+    ports: &Ports<M, G>,
+    command: FindContradictionsCommand,
+) -> FindContradictionsResultType<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let entities = ports
+        .memory_service
+        .find_entities_by_labels(&command.labels, LabelMatchMode::Any, None)
+        .await
+        .map_err(CoreError::from)?;
+
+    let contradictions = entities.iter().flat_map(detect_contradictions).collect();
+    Ok(FindContradictionsResult { contradictions })
+}
+
+/// Apply the contradiction heuristics to a single entity
+pub fn detect_contradictions(entity: &MemoryEntity) -> Vec<Contradiction> {
+    let mut found = Vec::new();
+    let flag = |kind, observations: Vec<&String>, property: Option<&str>| Contradiction {
+        entity_name: entity.name.clone(),
+        kind,
+        observations: observations.into_iter().cloned().collect(),
+        property: property.map(str::to_string),
+        confirmed: None,
+    };
+
+    let assertions: Vec<_> = entity
+        .observations
+        .iter()
+        .filter_map(|o| parse_assertion(o).map(|(k, v)| (o, k, v)))
+        .collect();
+
+    for (observation, key, value) in &assertions {
+        if let Some(property) = entity.properties.get(key.as_str())
+            && let Some(expected) = scalar_text(property)
+            && expected != *value
+        {
+            found.push(flag(
+                ContradictionKind::PropertyMismatch,
+                vec![observation],
+                Some(key),
+            ));
+        }
+    }
+
+    for (i, (first, key_a, value_a)) in assertions.iter().enumerate() {
+        for (second, key_b, value_b) in &assertions[i + 1..] {
+            if key_a == key_b && value_a != value_b {
+                found.push(flag(
+                    ContradictionKind::ConflictingValues,
+                    vec![first, second],
+                    None,
+                ));
+            }
+        }
+    }
+
+    let normalized: Vec<_> = entity
+        .observations
+        .iter()
+        .map(|o| (o, normalize(o)))
+        .collect();
+    for (i, (first, a)) in normalized.iter().enumerate() {
+        for (second, b) in &normalized[i + 1..] {
+            if strip_negation(a).is_some_and(|s| s == *b)
+                || strip_negation(b).is_some_and(|s| s == *a)
+            {
+                found.push(flag(ContradictionKind::Negation, vec![first, second], None));
+            }
+        }
+    }
+
+    found
+}
+
+/// Split `key: value` or `key = value` into a snake_case key and a
+/// normalized value
+fn parse_assertion(observation: &str) -> Option<(String, String)> {
+    let (key, value) = observation.split_once([':', '='])?;
+    let key = key.trim();
+    let value = normalize(value);
+    if key.is_empty() || key.len() > MAX_KEY_LEN || value.is_empty() {
+        return None;
+    }
+    let key = key
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("_");
+    Some((key, value))
+}
+
+fn normalize(text: &str) -> String {
+    text.trim()
+        .trim_end_matches(['.', '!', ';'])
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Comparable text of simple property values
+fn scalar_text(value: &MemoryValue) -> Option<String> {
+    match value {
+        MemoryValue::String(_)
+        | MemoryValue::Integer(_)
+        | MemoryValue::Float(_)
+        | MemoryValue::Boolean(_) => Some(normalize(&value.to_string())),
+        _ => None,
+    }
+}
+
+/// The affirmative form of a negated sentence, if it contains a negation
+fn strip_negation(text: &str) -> Option<String> {
+    const NEGATIONS: &[(&str, &str)] = &[
+        ("cannot", "can"),
+        ("can't", "can"),
+        ("won't", "will"),
+        ("isn't", "is"),
+        ("aren't", "are"),
+        ("doesn't", "does"),
+        ("don't", "do"),
+        ("not", ""),
+        ("never", ""),
+    ];
+    let mut negated = false;
+    let words: Vec<&str> = text
+        .split(' ')
+        .filter_map(|word| match NEGATIONS.iter().find(|(n, _)| *n == word) {
+            Some((_, affirmative)) => {
+                negated = true;
+                (!affirmative.is_empty()).then_some(*affirmative)
+            }
+            None => Some(word),
+        })
+        .collect();
+    negated.then(|| words.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn entity(observations: &[&str]) -> MemoryEntity {
+        MemoryEntity {
+            name: "project:x".to_string(),
+            labels: vec!["Project".to_string()],
+            observations: observations.iter().map(|o| o.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_property_mismatch() {
+        let mut e = entity(&["Status: archived", "Owner: alice"]);
+        e.properties = HashMap::from([(
+            "status".to_string(),
+            MemoryValue::String("Active".to_string()),
+        )]);
+        let found = detect_contradictions(&e);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].kind, ContradictionKind::PropertyMismatch);
+        assert_eq!(found[0].property.as_deref(), Some("status"));
+    }
+
+    #[test]
+    fn test_conflicting_values() {
+        let found = detect_contradictions(&entity(&[
+            "database: postgres",
+            "database = sqlite",
+            "database: postgres.",
+        ]));
+        assert_eq!(found.len(), 2);
+        assert!(
+            found
+                .iter()
+                .all(|c| c.kind == ContradictionKind::ConflictingValues)
+        );
+        assert_eq!(found[0].observations[1], "database = sqlite");
+    }
+
+    #[test]
+    fn test_negation() {
+        let found = detect_contradictions(&entity(&[
+            "The API supports pagination",
+            "Uses tabs",
+            "The API does not support pagination",
+            "The build isn't reproducible",
+            "The build is reproducible.",
+        ]));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].kind, ContradictionKind::Negation);
+        assert_eq!(
+            found[0].observations,
+            ["The build isn't reproducible", "The build is reproducible."]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_contradictions_scans_entities() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_by_labels().returning(|_, _, _| {
+            Ok(vec![
+                entity(&["Lint: clippy", "Lint: rustfmt"]),
+                entity(&["Consistent"]),
+            ])
+        });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let result = find_contradictions(&ports, FindContradictionsCommand::default())
+            .await
+            .unwrap();
+        assert_eq!(result.contradictions.len(), 1);
+        assert_eq!(result.contradictions[0].confirmed, None);
+    }
+}
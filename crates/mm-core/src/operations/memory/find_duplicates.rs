@@ -0,0 +1,215 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{LabelMatchMode, MemoryEntity, MemoryRepository};
+use mm_utils::similarity::similarity;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::instrument;
+
+/// Score below which a pair is not reported unless asked for
+pub const DEFAULT_MIN_DUPLICATE_SCORE: f64 = 0.8;
+
+/// Share of the score coming from name similarity; the rest comes from
+/// shared relationships
+const NAME_WEIGHT: f64 = 0.7;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct FindDuplicatesCommand {
+    /// Only compare entities with any of these labels; all entities when empty
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Lowest score reported, between 0 and 1; 0.8 when unset
+    #[serde(default)]
+    pub min_score: Option<f64>,
+}
+
+/// Pair of entities that likely describe the same thing
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct DuplicateCandidate {
+    /// Entity to keep when merging: the one with more content
+    pub target: String,
+    /// Entity to fold into `target`
+    pub source: String,
+    /// Combined score between 0 and 1
+    pub score: f64,
+    /// Name similarity between 0 and 1
+    pub name_score: f64,
+    /// Relationships both entities have to the same entity
+    pub shared_relationships: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct FindDuplicatesResult {
+    /// Candidates ordered by descending score
+    pub candidates: Vec<DuplicateCandidate>,
+}
+
+pub type FindDuplicatesResultType<E> = CoreResult<FindDuplicatesResult, E>;
+
+/// Report pairs of entities that are likely duplicates
+///
+/// Entities sharing a label are scored by name similarity and by how many of
+/// their relationships lead to the same entities. Results are merge
+/// candidates for review; nothing is changed.
+#[instrument(skip(ports), fields(label_count = command.labels.len()))]
+pub async fn find_duplicates<M, G>(
+    ports: &Ports<M, G>,
+    command: FindDuplicatesCommand,
+) -> FindDuplicatesResultType<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let entities = ports
+        .memory_service
+        .find_entities_by_labels(&command.labels, LabelMatchMode::Any, None)
+        .await
+        .map_err(CoreError::from)?;
+    let min_score = command.min_score.unwrap_or(DEFAULT_MIN_DUPLICATE_SCORE);
+    Ok(FindDuplicatesResult {
+        candidates: duplicate_candidates(&entities, min_score),
+    })
+}
+
+/// Score every pair of `entities` sharing a label and keep those scoring at
+/// least `min_score`
+pub fn duplicate_candidates(entities: &[MemoryEntity], min_score: f64) -> Vec<DuplicateCandidate> {
+    let neighbors: Vec<HashSet<(&str, &str)>> = entities.iter().map(neighbors).collect();
+    let mut candidates = Vec::new();
+    for (i, a) in entities.iter().enumerate() {
+        for (j, b) in entities.iter().enumerate().skip(i + 1) {
+            if a.name == b.name || !a.labels.iter().any(|l| b.labels.contains(l)) {
+                continue;
+            }
+            let name_score = similarity(&a.name, &b.name);
+            let shared = neighbors[i]
+                .iter()
+                .filter(|(_, other)| *other != a.name && *other != b.name)
+                .filter(|n| neighbors[j].contains(n))
+                .count();
+            // Entities without relationships are judged by their names alone
+            let union = neighbors[i].union(&neighbors[j]).count();
+            let score = if union == 0 {
+                name_score
+            } else {
+                let relationship_score = shared as f64 / union as f64;
+                NAME_WEIGHT * name_score + (1.0 - NAME_WEIGHT) * relationship_score
+            };
+            if score < min_score {
+                continue;
+            }
+            let (target, source) = if content(b) > content(a) {
+                (b, a)
+            } else {
+                (a, b)
+            };
+            candidates.push(DuplicateCandidate {
+                target: target.name.clone(),
+                source: source.name.clone(),
+                score,
+                name_score,
+                shared_relationships: shared,
+            });
+        }
+    }
+    candidates.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.target.cmp(&b.target))
+            .then_with(|| a.source.cmp(&b.source))
+    });
+    candidates
+}
+
+/// Relationship types and the entities at their other end
+fn neighbors(entity: &MemoryEntity) -> HashSet<(&str, &str)> {
+    entity
+        .relationships
+        .iter()
+        .map(|r| {
+            let other = if r.from == entity.name {
+                &r.to
+            } else {
+                &r.from
+            };
+            (r.name.as_str(), other.as_str())
+        })
+        .collect()
+}
+
+/// How much an entity holds, to pick which of two duplicates to keep
+fn content(entity: &MemoryEntity) -> usize {
+    entity.observations.len() + entity.relationships.len() + entity.properties.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryConfig, MemoryRelationship, MemoryService, MockMemoryRepository};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn entity(name: &str, label: &str, related_to: &[&str]) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            labels: vec![label.to_string()],
+            relationships: related_to
+                .iter()
+                .map(|to| MemoryRelationship {
+                    from: name.to_string(),
+                    to: to.to_string(),
+                    name: "relates_to".to_string(),
+                    properties: HashMap::new(),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_duplicate_candidates() {
+        let entities = vec![
+            entity("tech:postgres", "Technology", &["mm"]),
+            entity("tech:postgresql", "Technology", &["mm", "other"]),
+            entity("task:postgres", "Task", &["mm"]),
+            entity("tech:redis", "Technology", &["mm"]),
+        ];
+        let candidates = duplicate_candidates(&entities, 0.7);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].target, "tech:postgresql");
+        assert_eq!(candidates[0].source, "tech:postgres");
+        assert_eq!(candidates[0].shared_relationships, 1);
+
+        let shared = duplicate_candidates(&entities, 0.0);
+        assert!(
+            shared
+                .iter()
+                .all(|c| c.target != "task:postgres" && c.source != "task:postgres")
+        );
+        assert!(shared.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicates_uses_default_threshold() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_by_labels().returning(|_, _, _| {
+            Ok(vec![
+                entity("tech:tokio", "Technology", &["mm"]),
+                entity("tech:tokio_rs", "Technology", &["mm"]),
+                entity("tech:serde", "Technology", &[]),
+            ])
+        });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let result = find_duplicates(&ports, FindDuplicatesCommand::default())
+            .await
+            .unwrap();
+        assert_eq!(result.candidates.len(), 1);
+        assert_eq!(result.candidates[0].source, "tech:tokio_rs");
+    }
+}
@@ -1,7 +1,11 @@
+use super::pagination::paginate;
 use crate::error::{CoreError, CoreResult};
 use crate::ports::Ports;
 use mm_git::GitRepository;
-use mm_memory::{LabelMatchMode, MemoryEntity, MemoryRepository};
+use mm_memory::confidence::{entity_confidence, rank_entities};
+use mm_memory::{
+    LabelMatchMode, MemoryEntity, MemoryRepository, PropertyFilter, PropertySort, ValidationError,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
@@ -11,11 +15,32 @@ pub struct FindEntitiesByLabelsCommand {
     pub labels: Vec<String>,
     pub match_mode: LabelMatchMode,
     pub required_label: Option<String>,
+    /// Skip entities whose confidence is below this score
+    #[serde(default)]
+    pub min_confidence: Option<f64>,
+    /// Maximum number of entities to return; all of them when unset
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// `next_cursor` of the previous page
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Property predicates every entity must satisfy
+    #[serde(default)]
+    pub properties: Vec<PropertyFilter>,
+    /// Order by these properties instead of by confidence
+    #[serde(default)]
+    pub sort_by: Vec<PropertySort>,
+    /// Skip entities carrying any of these labels
+    #[serde(default)]
+    pub exclude_labels: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct FindEntitiesByLabelsResult {
     pub entities: Vec<MemoryEntity>,
+    /// Cursor of the next page, absent on the last one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 pub type FindEntitiesByLabelsResultType<E> = CoreResult<FindEntitiesByLabelsResult, E>;
@@ -31,14 +56,108 @@ where
     M::Error: std::error::Error + Send + Sync + 'static,
     G::Error: std::error::Error + Send + Sync + 'static,
 {
-    let entities = ports
+    let mut entities = ports
         .memory_service
-        .find_entities_by_labels(
+        .find_entities_by_labels_filtered(
             &command.labels,
             command.match_mode,
             command.required_label.clone(),
+            &command.properties,
+            &command.sort_by,
         )
         .await
         .map_err(CoreError::from)?;
-    Ok(FindEntitiesByLabelsResult { entities })
+    if !command.exclude_labels.is_empty() {
+        entities.retain(|e| !e.labels.iter().any(|l| command.exclude_labels.contains(l)));
+    }
+    if let Some(min) = command.min_confidence {
+        entities.retain(|e| entity_confidence(e) >= min);
+    }
+    if command.sort_by.is_empty() {
+        // Sort by name first so entities of equal confidence keep a stable
+        // order across pages
+        entities.sort_by(|a, b| a.name.cmp(&b.name));
+        rank_entities(&mut entities);
+    }
+    let (entities, next_cursor) = paginate(entities, command.limit, command.cursor.as_deref())
+        .map_err(ValidationError::from)?;
+    Ok(FindEntitiesByLabelsResult {
+        entities,
+        next_cursor,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository, SortDirection};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_sort_by_keeps_repository_order() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_by_labels_filtered()
+            .withf(|_, _, _, _, sort| sort.len() == 1 && sort[0].property == "adopted_at")
+            .returning(|_, _, _, _, _| {
+                Ok(["b", "a"]
+                    .into_iter()
+                    .map(|name| MemoryEntity {
+                        name: name.to_string(),
+                        ..Default::default()
+                    })
+                    .collect())
+            });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let command = FindEntitiesByLabelsCommand {
+            labels: vec!["Technology".to_string()],
+            match_mode: LabelMatchMode::Any,
+            required_label: None,
+            min_confidence: None,
+            limit: None,
+            cursor: None,
+            properties: Vec::new(),
+            sort_by: vec![PropertySort {
+                property: "adopted_at".to_string(),
+                direction: SortDirection::Desc,
+            }],
+            exclude_labels: Vec::new(),
+        };
+
+        let result = find_entities_by_labels(&ports, command).await.unwrap();
+        let names: Vec<&str> = result.entities.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["b", "a"]);
+    }
+
+    #[tokio::test]
+    async fn test_exclude_labels_skips_entities() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_by_labels().returning(|_, _, _| {
+            Ok([("a", "Note"), ("b", "Session")]
+                .into_iter()
+                .map(|(name, label)| MemoryEntity {
+                    name: name.to_string(),
+                    labels: vec!["Memory".to_string(), label.to_string()],
+                    ..Default::default()
+                })
+                .collect())
+        });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let command = FindEntitiesByLabelsCommand {
+            labels: vec!["Memory".to_string()],
+            match_mode: LabelMatchMode::Any,
+            required_label: None,
+            min_confidence: None,
+            limit: None,
+            cursor: None,
+            properties: Vec::new(),
+            sort_by: Vec::new(),
+            exclude_labels: vec!["Session".to_string()],
+        };
+
+        let result = find_entities_by_labels(&ports, command).await.unwrap();
+        let names: Vec<&str> = result.entities.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["a"]);
+    }
 }
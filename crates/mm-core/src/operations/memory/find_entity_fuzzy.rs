@@ -0,0 +1,105 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{FuzzyMatch, MemoryRepository};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+/// Number of matches returned unless a limit is given
+pub const DEFAULT_FUZZY_LIMIT: usize = 5;
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct FindEntityFuzzyCommand {
+    /// Possibly misspelled entity name
+    pub name: String,
+    /// Maximum number of matches
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct FindEntityFuzzyResult {
+    /// Closest names first
+    pub matches: Vec<FuzzyMatch>,
+}
+
+pub type FindEntityFuzzyResultType<E> = CoreResult<FindEntityFuzzyResult, E>;
+
+/// Find entities whose names resemble a possibly mistyped name
+#[instrument(skip(ports))]
+pub async fn find_entity_fuzzy<M, G>(
+    ports: &Ports<M, G>,
+    command: FindEntityFuzzyCommand,
+) -> FindEntityFuzzyResultType<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.name);
+
+    let matches = ports
+        .memory_service
+        .find_entity_fuzzy(&command.name, command.limit.unwrap_or(DEFAULT_FUZZY_LIMIT))
+        .await
+        .map_err(CoreError::from)?;
+    Ok(FindEntityFuzzyResult { matches })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    fn entity(name: &str) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            labels: vec!["Memory".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_entity_fuzzy_ranks_candidates() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_fuzzy()
+            .withf(|name, limit, required| {
+                name == "project:midle_manager"
+                    && *limit == 1
+                    && required.as_deref() == Some("Memory")
+            })
+            .returning(|name, limit, _| {
+                Ok(mm_memory::fuzzy::rank_by_name(
+                    vec![entity("project:middle_manager"), entity("project:other")],
+                    name,
+                    limit,
+                ))
+            });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let command = FindEntityFuzzyCommand {
+            name: "project:midle_manager".to_string(),
+            limit: Some(1),
+        };
+        let result = find_entity_fuzzy(&ports, command).await.unwrap();
+        assert_eq!(result.matches.len(), 1);
+        assert_eq!(result.matches[0].entity.name, "project:middle_manager");
+    }
+
+    #[tokio::test]
+    async fn test_find_entity_fuzzy_rejects_empty_name() {
+        let service = MemoryService::new(MockMemoryRepository::new(), MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let command = FindEntityFuzzyCommand {
+            name: String::new(),
+            limit: None,
+        };
+        let result = find_entity_fuzzy(&ports, command).await;
+        assert!(matches!(result, Err(CoreError::Validation(_))));
+    }
+}
@@ -0,0 +1,121 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{MemoryRelationship, MemoryRepository};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+fn default_max_depth() -> u32 {
+    3
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct FindPathCommand {
+    pub from: String,
+    pub to: String,
+    /// Most relationships to follow (1-5)
+    #[serde(default = "default_max_depth")]
+    pub max_depth: u32,
+    /// Only follow relationships of these types; any type when empty
+    #[serde(default)]
+    pub relationship_types: Vec<String>,
+}
+
+/// How two entities are connected
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct EntityPath {
+    /// Entity names from `from` to `to`
+    pub entities: Vec<String>,
+    /// Relationships between consecutive entities, as stored, so one may
+    /// point against the path
+    pub relationships: Vec<MemoryRelationship>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct FindPathResult {
+    /// A shortest path, or `None` when the entities are not connected
+    /// within `max_depth` relationships
+    pub path: Option<EntityPath>,
+}
+
+pub type FindPathResultType<E> = CoreResult<FindPathResult, E>;
+
+/// Find a shortest path between two entities
+#[instrument(skip(ports), fields(from = %command.from, to = %command.to))]
+pub async fn find_path<M, G>(
+    ports: &Ports<M, G>,
+    command: FindPathCommand,
+) -> FindPathResultType<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.from);
+    validate_name!(command.to);
+
+    let relationships = ports
+        .memory_service
+        .find_path(
+            &command.from,
+            &command.to,
+            command.max_depth,
+            &command.relationship_types,
+        )
+        .await
+        .map_err(CoreError::from)?;
+    let path = relationships.map(|relationships| {
+        let mut entities = vec![command.from.clone()];
+        for rel in &relationships {
+            let last = entities.last().map(String::as_str);
+            let next = if last == Some(rel.from.as_str()) {
+                &rel.to
+            } else {
+                &rel.from
+            };
+            entities.push(next.clone());
+        }
+        EntityPath {
+            entities,
+            relationships,
+        }
+    });
+    Ok(FindPathResult { path })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use mockall::predicate::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_find_path_lists_entities_in_order() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_path()
+            .with(eq("a"), eq("c"), eq(3u32), always())
+            .returning(|_, _, _, _| {
+                let rel = |from: &str, to: &str| MemoryRelationship {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    name: "relates_to".to_string(),
+                    properties: HashMap::new(),
+                };
+                Ok(Some(vec![rel("a", "b"), rel("c", "b")]))
+            });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let command = FindPathCommand {
+            from: "a".to_string(),
+            to: "c".to_string(),
+            max_depth: 3,
+            relationship_types: vec![],
+        };
+        let path = find_path(&ports, command).await.unwrap().path.unwrap();
+        assert_eq!(path.entities, ["a", "b", "c"]);
+    }
+}
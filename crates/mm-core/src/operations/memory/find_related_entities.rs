@@ -1,7 +1,8 @@
 use crate::error::{CoreError, CoreResult};
 use crate::ports::Ports;
-use crate::validate_name;
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
 use mm_git::GitRepository;
+use mm_memory::confidence::{entity_confidence, rank_entities};
 use mm_memory::{MemoryEntity, MemoryRepository, RelationshipDirection};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -13,6 +14,9 @@ pub struct FindRelatedEntitiesCommand {
     pub relationship: Option<String>,
     pub direction: Option<RelationshipDirection>,
     pub depth: u32,
+    /// Skip entities whose confidence is below this score
+    #[serde(default)]
+    pub min_confidence: Option<f64>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
@@ -22,11 +26,40 @@ pub struct FindRelatedEntitiesResult {
 
 pub type FindRelatedEntitiesResultType<E> = CoreResult<FindRelatedEntitiesResult, E>;
 
+/// Related entities delivered as the repository produces them
+pub type RelatedEntityStream<E> = BoxStream<'static, CoreResult<MemoryEntity, E>>;
+
 #[instrument(skip(ports), fields(name = %command.name, depth = command.depth))]
 pub async fn find_related_entities<M, G>(
     ports: &Ports<M, G>,
     command: FindRelatedEntitiesCommand,
 ) -> FindRelatedEntitiesResultType<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let mut entities: Vec<_> = stream_related_entities(ports, command)
+        .await?
+        .try_collect()
+        .await?;
+    rank_entities(&mut entities);
+
+    Ok(FindRelatedEntitiesResult { entities })
+}
+
+/// Stream related entities without collecting the whole traversal.
+///
+/// Callers that can report partial results consume this directly; deep
+/// traversals are only read from the backend as fast as they are consumed.
+/// Entities below `min_confidence` are dropped as they arrive. Ranking needs
+/// every row, so the stream keeps the backend's order.
+#[instrument(skip(ports), fields(name = %command.name, depth = command.depth))]
+pub async fn stream_related_entities<M, G>(
+    ports: &Ports<M, G>,
+    command: FindRelatedEntitiesCommand,
+) -> CoreResult<RelatedEntityStream<M::Error>, M::Error>
 where
     M: MemoryRepository + Send + Sync,
     G: GitRepository + Send + Sync,
@@ -35,24 +68,32 @@ where
 {
     validate_name!(command.name);
 
-    let entities = ports
+    let stream = ports
         .memory_service
-        .find_related_entities(
+        .stream_related_entities(
             &command.name,
-            command.relationship.clone(),
+            command.relationship,
             command.direction,
             command.depth,
         )
         .await
         .map_err(CoreError::from)?;
 
-    Ok(FindRelatedEntitiesResult { entities })
+    let min_confidence = command.min_confidence;
+    Ok(stream
+        .map_err(CoreError::from)
+        .try_filter(move |entity| {
+            let keep = min_confidence.is_none_or(|min| entity_confidence(entity) >= min);
+            futures::future::ready(keep)
+        })
+        .boxed())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::ports::Ports;
+    use futures::stream;
     use mm_memory::{MemoryConfig, MemoryError, MemoryService, MockMemoryRepository};
     use mockall::predicate::*;
     use std::sync::Arc;
@@ -64,14 +105,14 @@ mod tests {
             name: "b".into(),
             ..Default::default()
         }];
-        mock.expect_find_related_entities()
+        mock.expect_stream_related_entities()
             .with(
                 eq("a"),
                 eq(Some("rel".to_string())),
                 eq(Some(RelationshipDirection::Outgoing)),
                 eq(2u32),
             )
-            .returning(move |_, _, _, _| Ok(expected.clone()));
+            .returning(move |_, _, _, _| Ok(stream::iter(expected.clone()).map(Ok).boxed()));
 
         let service = MemoryService::new(mock, MemoryConfig::default());
         let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
@@ -81,16 +122,51 @@ mod tests {
             relationship: Some("rel".into()),
             direction: Some(RelationshipDirection::Outgoing),
             depth: 2,
+            min_confidence: None,
         };
 
         let res = find_related_entities(&ports, cmd).await.unwrap();
         assert_eq!(res.entities.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_find_related_entities_min_confidence() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_stream_related_entities()
+            .returning(|_, _, _, _| {
+                let entity = |name: &str, confidence: f64| MemoryEntity {
+                    name: name.into(),
+                    properties: [(
+                        mm_memory::confidence::CONFIDENCE_PROPERTY.to_string(),
+                        mm_memory::MemoryValue::Float(confidence),
+                    )]
+                    .into(),
+                    ..Default::default()
+                };
+                let entities = vec![entity("low", 0.2), entity("mid", 0.6), entity("high", 0.9)];
+                Ok(stream::iter(entities).map(Ok).boxed())
+            });
+
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let cmd = FindRelatedEntitiesCommand {
+            name: "a".into(),
+            relationship: None,
+            direction: None,
+            depth: 1,
+            min_confidence: Some(0.5),
+        };
+
+        let res = find_related_entities(&ports, cmd).await.unwrap();
+        let names: Vec<_> = res.entities.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["high", "mid"]);
+    }
+
     #[tokio::test]
     async fn test_find_related_entities_empty_name() {
         let mut mock = MockMemoryRepository::new();
-        mock.expect_find_related_entities().never();
+        mock.expect_stream_related_entities().never();
         let service = MemoryService::new(mock, MemoryConfig::default());
         let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
 
@@ -99,6 +175,7 @@ mod tests {
             relationship: None,
             direction: None,
             depth: 1,
+            min_confidence: None,
         };
 
         let res = find_related_entities(&ports, cmd).await;
@@ -108,7 +185,7 @@ mod tests {
     #[tokio::test]
     async fn test_find_related_entities_repo_error() {
         let mut mock = MockMemoryRepository::new();
-        mock.expect_find_related_entities()
+        mock.expect_stream_related_entities()
             .returning(|_, _, _, _| Err(MemoryError::query_error("fail")));
         let service = MemoryService::new(mock, MemoryConfig::default());
         let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
@@ -118,9 +195,41 @@ mod tests {
             relationship: None,
             direction: None,
             depth: 1,
+            min_confidence: None,
         };
 
         let res = find_related_entities(&ports, cmd).await;
         assert!(matches!(res, Err(CoreError::Memory(_))));
     }
+
+    #[tokio::test]
+    async fn test_stream_related_entities_yields_rows_before_error() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_stream_related_entities()
+            .returning(|_, _, _, _| {
+                let rows = vec![
+                    Ok(MemoryEntity {
+                        name: "b".into(),
+                        ..Default::default()
+                    }),
+                    Err(MemoryError::query_error("connection lost")),
+                ];
+                Ok(stream::iter(rows).boxed())
+            });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let cmd = FindRelatedEntitiesCommand {
+            name: "a".into(),
+            relationship: None,
+            direction: None,
+            depth: 3,
+            min_confidence: None,
+        };
+
+        let mut stream = stream_related_entities(&ports, cmd).await.unwrap();
+        let first = stream.try_next().await.unwrap().unwrap();
+        assert_eq!(first.name, "b");
+        assert!(matches!(stream.try_next().await, Err(CoreError::Memory(_))));
+    }
 }
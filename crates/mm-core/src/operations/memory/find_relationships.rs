@@ -1,7 +1,9 @@
+use super::pagination::paginate;
 use crate::error::{CoreError, CoreResult};
 use crate::ports::Ports;
 use mm_git::GitRepository;
-use mm_memory::{MemoryRelationship, MemoryRepository};
+use mm_memory::confidence::{rank_relationships, relationship_confidence};
+use mm_memory::{MemoryRelationship, MemoryRepository, ValidationError};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
@@ -11,11 +13,23 @@ pub struct FindRelationshipsCommand {
     pub from: Option<String>,
     pub to: Option<String>,
     pub name: Option<String>,
+    /// Skip relationships whose confidence is below this score
+    #[serde(default)]
+    pub min_confidence: Option<f64>,
+    /// Maximum number of relationships to return; all of them when unset
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// `next_cursor` of the previous page
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct FindRelationshipsResult {
     pub relationships: Vec<MemoryRelationship>,
+    /// Cursor of the next page, absent on the last one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 pub type FindRelationshipsResultType<E> = CoreResult<FindRelationshipsResult, E>;
@@ -31,7 +45,7 @@ where
     M::Error: std::error::Error + Send + Sync + 'static,
     G::Error: std::error::Error + Send + Sync + 'static,
 {
-    let rels = ports
+    let mut rels = ports
         .memory_service
         .find_relationships(
             command.from.clone(),
@@ -40,7 +54,17 @@ where
         )
         .await
         .map_err(CoreError::from)?;
+    if let Some(min) = command.min_confidence {
+        rels.retain(|r| relationship_confidence(r) >= min);
+    }
+    // Sort first so relationships of equal confidence keep a stable order
+    // across pages
+    rels.sort_by(|a, b| (&a.from, &a.name, &a.to).cmp(&(&b.from, &b.name, &b.to)));
+    rank_relationships(&mut rels);
+    let (relationships, next_cursor) =
+        paginate(rels, command.limit, command.cursor.as_deref()).map_err(ValidationError::from)?;
     Ok(FindRelationshipsResult {
-        relationships: rels,
+        relationships,
+        next_cursor,
     })
 }
@@ -1,6 +1,5 @@
 use crate::error::{CoreError, CoreResult};
 use crate::ports::Ports;
-use crate::validate_name;
 use mm_git::GitRepository;
 use mm_memory::{EntityUpdate, MemoryEntity, MemoryRepository, value::MemoryValue};
 use schemars::JsonSchema;
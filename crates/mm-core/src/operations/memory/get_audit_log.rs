@@ -0,0 +1,87 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use chrono::{DateTime, Utc};
+use mm_git::GitRepository;
+use mm_memory::{AuditEntry, AuditQuery, MemoryRepository};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct GetAuditLogCommand {
+    #[serde(default)]
+    pub actor: Option<String>,
+    #[serde(default)]
+    pub operation: Option<String>,
+    /// Only changes affecting this entity
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    #[schemars(with = "Option<String>")]
+    pub since: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+pub type GetAuditLogResult<E> = CoreResult<Vec<AuditEntry>, E>;
+
+/// Recorded changes to the graph, newest first
+///
+/// Changes are only recorded while `memory.audit_log` is set.
+#[instrument(skip(ports))]
+pub async fn get_audit_log<M, G>(
+    ports: &Ports<M, G>,
+    command: GetAuditLogCommand,
+) -> GetAuditLogResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let query = AuditQuery {
+        actor: command.actor,
+        operation: command.operation,
+        name: command.name,
+        since: command.since,
+        limit: command.limit,
+    };
+    ports
+        .memory_service
+        .audit_log(&query)
+        .map_err(CoreError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::audit::InMemoryAuditSink;
+    use mm_memory::{AuditSink, MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_filters_by_name() {
+        let sink = Arc::new(InMemoryAuditSink::default());
+        for names in [vec!["a"], vec!["b"], vec!["a", "c"]] {
+            sink.record(AuditEntry {
+                timestamp: Utc::now(),
+                actor: "agent".to_string(),
+                operation: "update_entity".to_string(),
+                names: names.into_iter().map(str::to_string).collect(),
+                correlation_id: None,
+            })
+            .unwrap();
+        }
+        let service = MemoryService::new(MockMemoryRepository::new(), MemoryConfig::default())
+            .with_audit_sink(sink);
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let command = GetAuditLogCommand {
+            name: Some("a".to_string()),
+            ..Default::default()
+        };
+        let entries = get_audit_log(&ports, command).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].names, ["a", "c"]);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{EntityVersion, MemoryRepository};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct GetEntityHistoryCommand {
+    pub name: String,
+    /// Maximum number of versions to return; all when unset
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+pub type GetEntityHistoryResult<E> = CoreResult<Vec<EntityVersion>, E>;
+
+/// Earlier versions of an entity, newest first
+///
+/// Versions are only recorded while `memory.history` is enabled.
+#[instrument(skip(ports))]
+pub async fn get_entity_history<M, G>(
+    ports: &Ports<M, G>,
+    command: GetEntityHistoryCommand,
+) -> GetEntityHistoryResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.name);
+
+    ports
+        .memory_service
+        .entity_history(&command.name, command.limit)
+        .await
+        .map_err(CoreError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{
+        MemoryConfig, MemoryEntity, MemoryRelationship, MemoryService, MemoryValue,
+        MockMemoryRepository,
+    };
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn linked(name: &str, previous: Option<&str>, version: i64) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            properties: HashMap::from([("version".to_string(), MemoryValue::Integer(version))]),
+            relationships: previous
+                .map(|to| MemoryRelationship {
+                    from: name.to_string(),
+                    to: to.to_string(),
+                    name: "previous_version".to_string(),
+                    properties: HashMap::new(),
+                })
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_history_follows_chain_newest_first() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|name| {
+            Ok(match name {
+                "e" => Some(linked("e", Some("e@v2"), 0)),
+                "e@v2" => Some(linked("e@v2", Some("e@v1"), 2)),
+                "e@v1" => Some(linked("e@v1", None, 1)),
+                _ => None,
+            })
+        });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let command = GetEntityHistoryCommand {
+            name: "e".to_string(),
+            limit: None,
+        };
+        let history = get_entity_history(&ports, command).await.unwrap();
+        let versions: Vec<i64> = history.iter().map(|v| v.version).collect();
+        assert_eq!(versions, [2, 1]);
+        assert!(history[0].properties.is_empty());
+
+        let command = GetEntityHistoryCommand {
+            name: "e".to_string(),
+            limit: Some(1),
+        };
+        assert_eq!(get_entity_history(&ports, command).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{MemoryEntity, MemoryError, MemoryRelationship, MemoryRepository};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::instrument;
+
+fn default_depth() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct GetNeighborhoodCommand {
+    /// Entity at the center of the subgraph
+    pub name: String,
+    /// Most relationships between the seed and an included entity (1-5)
+    #[serde(default = "default_depth")]
+    pub depth: u32,
+    /// Only follow and include relationships of this type
+    #[serde(default)]
+    pub relationship: Option<String>,
+}
+
+/// Entities within reach of a seed entity and every relationship between them
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct GetNeighborhoodResult {
+    /// The seed first, then the entities reached from it
+    pub entities: Vec<MemoryEntity>,
+    /// Relationships whose both ends are in `entities`
+    pub relationships: Vec<MemoryRelationship>,
+}
+
+pub type GetNeighborhoodResultType<E> = CoreResult<GetNeighborhoodResult, E>;
+
+/// Export the subgraph within `depth` hops of an entity
+///
+/// Relationships are followed in either direction. Edges between two
+/// reached entities are included even when the traversal did not use them.
+#[instrument(skip(ports), fields(name = %command.name, depth = command.depth))]
+pub async fn get_neighborhood<M, G>(
+    ports: &Ports<M, G>,
+    command: GetNeighborhoodCommand,
+) -> GetNeighborhoodResultType<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.name);
+
+    let service = &ports.memory_service;
+    let seed = service
+        .find_entity_by_name(&command.name)
+        .await
+        .map_err(CoreError::from)?
+        .ok_or_else(|| CoreError::Memory(MemoryError::EntityNotFound(command.name.clone())))?;
+    let related = service
+        .find_related_entities(
+            &command.name,
+            command.relationship.clone(),
+            None,
+            command.depth,
+        )
+        .await
+        .map_err(CoreError::from)?;
+
+    let mut entities = vec![seed];
+    entities.extend(related.into_iter().filter(|e| e.name != command.name));
+    let names: HashSet<&str> = entities.iter().map(|e| e.name.as_str()).collect();
+
+    let mut relationships = Vec::new();
+    for entity in &entities {
+        let outgoing = service
+            .find_relationships(
+                Some(entity.name.clone()),
+                None,
+                command.relationship.clone(),
+            )
+            .await
+            .map_err(CoreError::from)?;
+        relationships.extend(
+            outgoing
+                .into_iter()
+                .filter(|rel| names.contains(rel.to.as_str())),
+        );
+    }
+
+    Ok(GetNeighborhoodResult {
+        entities,
+        relationships,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository, RelationshipDirection};
+    use mockall::predicate::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn rel(from: &str, to: &str) -> MemoryRelationship {
+        MemoryRelationship {
+            from: from.to_string(),
+            to: to.to_string(),
+            name: "relates_to".to_string(),
+            properties: HashMap::new(),
+        }
+    }
+
+    fn entity(name: &str) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_neighborhood_keeps_edges_between_reached_entities() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name()
+            .with(eq("a"))
+            .returning(|_| Ok(Some(entity("a"))));
+        mock.expect_find_related_entities()
+            .with(
+                eq("a"),
+                eq(None::<String>),
+                eq(None::<RelationshipDirection>),
+                eq(1u32),
+            )
+            .returning(|_, _, _, _| Ok(vec![entity("b"), entity("c")]));
+        mock.expect_find_relationships().returning(|from, _, _| {
+            Ok(match from.as_deref() {
+                Some("a") => vec![rel("a", "b")],
+                Some("b") => vec![rel("b", "c"), rel("b", "d")],
+                Some("c") => vec![rel("c", "a")],
+                _ => vec![],
+            })
+        });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let command = GetNeighborhoodCommand {
+            name: "a".to_string(),
+            depth: 1,
+            relationship: None,
+        };
+
+        let result = get_neighborhood(&ports, command).await.unwrap();
+        let names: Vec<&str> = result.entities.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+        assert_eq!(
+            result.relationships,
+            [rel("a", "b"), rel("b", "c"), rel("c", "a")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_neighborhood_missing_seed() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|_| Ok(None));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let command = GetNeighborhoodCommand {
+            name: "gone".to_string(),
+            depth: 1,
+            relationship: None,
+        };
+
+        let result = get_neighborhood(&ports, command).await;
+        assert!(matches!(
+            result,
+            Err(CoreError::Memory(MemoryError::EntityNotFound(name))) if name == "gone"
+        ));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use chrono::{Duration, Utc};
+use mm_git::GitRepository;
+use mm_memory::observation_record::observation_records;
+use mm_memory::{MemoryError, MemoryRepository, ObservationRecord};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct GetObservationsCommand {
+    /// Entity whose observations to list
+    pub name: String,
+    /// Only observations written at least this many days ago, or at an
+    /// unknown time
+    #[serde(default)]
+    pub older_than_days: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct GetObservationsResult {
+    /// Observations in entity order, with when and by whom they were written
+    /// when recorded
+    pub observations: Vec<ObservationRecord>,
+}
+
+pub type GetObservationsResultType<E> = CoreResult<GetObservationsResult, E>;
+
+/// List the observations of an entity with their authors
+#[instrument(skip(ports), fields(name = %command.name))]
+pub async fn get_observations<M, G>(
+    ports: &Ports<M, G>,
+    command: GetObservationsCommand,
+) -> GetObservationsResultType<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.name);
+
+    let entity = ports
+        .memory_service
+        .find_entity_by_name(&command.name)
+        .await
+        .map_err(CoreError::from)?
+        .ok_or_else(|| CoreError::Memory(MemoryError::EntityNotFound(command.name.clone())))?;
+
+    let cutoff = command
+        .older_than_days
+        .map(|days| Utc::now() - Duration::days(days.into()));
+    let observations = observation_records(&entity)
+        .into_iter()
+        .filter(|record| match (cutoff, record.created_at) {
+            (Some(cutoff), Some(created_at)) => created_at <= cutoff,
+            _ => true,
+        })
+        .collect();
+
+    Ok(GetObservationsResult { observations })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::observation_record::stamp_observations;
+    use mm_memory::{
+        MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository, ObservationAuthor,
+    };
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_get_observations_filters_by_age() {
+        let mut entity = MemoryEntity {
+            name: "topic".to_string(),
+            observations: vec!["old".into(), "new".into(), "unknown".into()],
+            ..Default::default()
+        };
+        let author = |days: i64| ObservationAuthor {
+            created_at: Utc::now() - Duration::days(days),
+            agent: Some("agent".to_string()),
+        };
+        stamp_observations(&mut entity.properties, &["old".into()], &author(30));
+        stamp_observations(&mut entity.properties, &["new".into()], &author(1));
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name()
+            .returning(move |_| Ok(Some(entity.clone())));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let command = GetObservationsCommand {
+            name: "topic".to_string(),
+            older_than_days: Some(7),
+        };
+        let result = get_observations(&ports, command).await.unwrap();
+        let texts: Vec<&str> = result
+            .observations
+            .iter()
+            .map(|o| o.text.as_str())
+            .collect();
+        assert_eq!(texts, ["old", "unknown"]);
+        assert_eq!(result.observations[0].agent.as_deref(), Some("agent"));
+        assert!(result.observations[1].created_at.is_none());
+    }
+}
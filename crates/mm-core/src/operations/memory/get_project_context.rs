@@ -1,9 +1,13 @@
 use crate::operations::memory::git::types::GitRepositoryProperties;
-use crate::operations::memory::projects::{ProjectContext, ProjectProperties};
+use crate::operations::memory::projects::{
+    PROJECT_CONTEXT_SCHEMA_VERSION, ProjectContext, ProjectProperties,
+};
 use crate::operations::memory::tasks::TaskProperties;
 use mm_git::GitRepository;
+use mm_memory::confidence::rank_entities;
 use mm_memory::{
-    MemoryEntity, MemoryError, MemoryRepository, RelationshipDirection, value::MemoryValue,
+    EntitySnapshot, MemoryEntity, MemoryError, MemoryRepository, RelatedQuery,
+    RelationshipDirection, value::MemoryValue,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -17,6 +21,9 @@ use mm_memory::labels::{
 use crate::error::{CoreError, CoreResult};
 use crate::ports::Ports;
 
+/// Prefix of git repository entity names, followed by `owner/name`
+pub const REPOSITORY_ENTITY_PREFIX: &str = "tech:git:repo:";
+
 /// Filter for finding a project
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -33,6 +40,9 @@ pub enum ProjectFilter {
 pub struct GetProjectContextCommand {
     /// Filter to use for finding the project
     pub filter: ProjectFilter,
+    /// Leave out related entities carrying any of these labels
+    #[serde(default)]
+    pub exclude_labels: Vec<String>,
 }
 
 /// Result of retrieving project context
@@ -74,6 +84,23 @@ where
     Ok(entities)
 }
 
+/// Related-entity queries of a project context, in the order
+/// [`context_from_snapshot`] reads their results
+fn context_queries() -> Vec<RelatedQuery> {
+    let query = |relationship: Option<&str>, direction| RelatedQuery {
+        relationship: relationship.map(str::to_string),
+        direction: Some(direction),
+        depth: 1,
+    };
+    vec![
+        query(Some("contains"), RelationshipDirection::Outgoing),
+        query(Some("relates_to"), RelationshipDirection::Incoming),
+        query(Some("contains"), RelationshipDirection::Incoming),
+        query(None, RelationshipDirection::Both),
+        query(Some("uses"), RelationshipDirection::Outgoing),
+    ]
+}
+
 /// Get project context by name or repository
 #[instrument(skip(ports), err)]
 pub async fn get_project_context<M, G>(
@@ -86,118 +113,98 @@ where
     M::Error: std::error::Error + Send + Sync + 'static,
     G::Error: std::error::Error + Send + Sync + 'static,
 {
-    match command.filter {
-        ProjectFilter::Name(name) => {
-            // Try to find the project by name
-            let project_entity = ports
-                .memory_service
-                .find_entity_by_name_typed::<ProjectProperties>(&name)
-                .await
-                .map_err(CoreError::from)?;
-
-            if let Some(entity) = project_entity {
-                let context = build_project_context(ports, entity).await?;
-                Ok(GetProjectContextResult { context })
-            } else {
-                Err(CoreError::Memory(MemoryError::entity_not_found(name)))
-            }
-        }
+    let name = match command.filter {
+        ProjectFilter::Name(name) => name,
         ProjectFilter::Repository(repo_name) => {
             // Try to find the project by repository name
-            let repo_name = format!("tech:git:repo:{}", repo_name);
+            let repo_name = format!("{REPOSITORY_ENTITY_PREFIX}{repo_name}");
             let repo_entity = ports
                 .memory_service
                 .find_entity_by_name(&repo_name)
                 .await
                 .map_err(CoreError::from)?;
+            let Some(repo) = repo_entity else {
+                return Err(CoreError::Memory(MemoryError::entity_not_found(repo_name)));
+            };
+
+            // Find projects contained by this repository
+            let projects = related_by_label::<_, _, ProjectProperties>(
+                ports,
+                &repo.name,
+                Some("contains".to_string()),
+                Some(RelationshipDirection::Outgoing),
+                1,
+                PROJECT_LABEL,
+            )
+            .await?;
 
-            if let Some(repo) = repo_entity {
-                // Find projects contained by this repository
-                let projects = related_by_label::<_, _, ProjectProperties>(
-                    ports,
-                    &repo.name,
-                    Some("contains".to_string()),
-                    Some(RelationshipDirection::Outgoing),
-                    1,
-                    PROJECT_LABEL,
-                )
-                .await?;
-
-                if projects.is_empty() {
-                    Err(CoreError::Memory(MemoryError::entity_not_found(format!(
+            match projects.as_slice() {
+                [] => {
+                    return Err(CoreError::Memory(MemoryError::entity_not_found(format!(
                         "No projects found for repository {}",
                         repo_name
-                    ))))
-                } else if projects.len() > 1 {
+                    ))));
+                }
+                [project] => project.name.clone(),
+                [project, ..] => {
                     debug!(
                         "Multiple projects found for repository {}, using first one",
                         repo_name
                     );
-                    let context = build_project_context(ports, projects[0].clone()).await?;
-                    Ok(GetProjectContextResult { context })
-                } else {
-                    let context = build_project_context(ports, projects[0].clone()).await?;
-                    Ok(GetProjectContextResult { context })
+                    project.name.clone()
                 }
-            } else {
-                Err(CoreError::Memory(MemoryError::entity_not_found(repo_name)))
             }
         }
+    };
+
+    // The project and everything around it are read together, in one
+    // transaction where the backend has them
+    let mut snapshot = ports
+        .memory_service
+        .find_entity_snapshot(&name, &context_queries())
+        .await
+        .map_err(CoreError::from)?
+        .ok_or_else(|| CoreError::Memory(MemoryError::entity_not_found(name)))?;
+    if !command.exclude_labels.is_empty() {
+        for related in &mut snapshot.related {
+            related.retain(|e| !e.labels.iter().any(|l| command.exclude_labels.contains(l)));
+        }
     }
+    Ok(GetProjectContextResult {
+        context: context_from_snapshot(snapshot),
+    })
 }
 
-/// Build project context from a project entity
-async fn build_project_context<M, G>(
-    ports: &Ports<M, G>,
-    project: MemoryEntity<ProjectProperties>,
-) -> CoreResult<ProjectContext, M::Error>
+/// Entities of `related` carrying `label`
+fn with_label<P>(related: Vec<MemoryEntity>, label: &str) -> Vec<MemoryEntity<P>>
 where
-    M: MemoryRepository + Send + Sync,
-    G: GitRepository + Send + Sync,
-    M::Error: std::error::Error + Send + Sync + 'static,
-    G::Error: std::error::Error + Send + Sync + 'static,
+    P: JsonSchema
+        + From<HashMap<String, MemoryValue>>
+        + Into<HashMap<String, MemoryValue>>
+        + Clone
+        + std::fmt::Debug
+        + Default,
 {
-    // Find tasks related to this project
-    let tasks = related_by_label::<_, _, TaskProperties>(
-        ports,
-        &project.name,
-        Some("contains".to_string()),
-        Some(RelationshipDirection::Outgoing),
-        1,
-        TASK_LABEL,
-    )
-    .await?;
-
-    // Find notes related to this project
-    let notes = related_by_label::<_, _, HashMap<String, MemoryValue>>(
-        ports,
-        &project.name,
-        Some("relates_to".to_string()),
-        Some(RelationshipDirection::Incoming),
-        1,
-        NOTE_LABEL,
-    )
-    .await?;
-
-    // Find associated git repository if any
-    let git_repository = related_by_label::<_, _, GitRepositoryProperties>(
-        ports,
-        &project.name,
-        Some("contains".to_string()),
-        Some(RelationshipDirection::Incoming),
-        1,
-        GIT_REPOSITORY_LABEL,
-    )
-    .await?
-    .into_iter()
-    .next();
-
-    // Find other entities related to this project
-    let other_related = ports
-        .memory_service
-        .find_related_entities(&project.name, None, Some(RelationshipDirection::Both), 1)
-        .await
-        .map_err(CoreError::from)?
+    related
+        .into_iter()
+        .filter(|e| e.labels.iter().any(|l| l == label))
+        .map(MemoryEntity::into_typed)
+        .collect()
+}
+
+/// Build project context from a snapshot taken with [`context_queries`]
+fn context_from_snapshot(snapshot: EntitySnapshot) -> ProjectContext {
+    let mut related = snapshot.related.into_iter();
+    let mut next = || related.next().unwrap_or_default();
+
+    let tasks = with_label::<TaskProperties>(next(), TASK_LABEL);
+    let mut notes = with_label::<HashMap<String, MemoryValue>>(next(), NOTE_LABEL);
+    let git_repository = with_label::<GitRepositoryProperties>(next(), GIT_REPOSITORY_LABEL)
+        .into_iter()
+        .next();
+
+    // Other entities related to this project
+    let mut other_related: Vec<_> = next()
         .into_iter()
         .filter(|e| {
             !e.labels.contains(&TASK_LABEL.to_string())
@@ -207,23 +214,99 @@ where
         })
         .collect();
 
-    // Find technologies used by this project
-    let technologies = related_by_label::<_, _, HashMap<String, MemoryValue>>(
-        ports,
-        &project.name,
-        Some("uses".to_string()),
-        Some(RelationshipDirection::Outgoing),
-        1,
-        TECHNOLOGY_LABEL,
-    )
-    .await?;
-
-    Ok(ProjectContext {
-        project,
+    let mut technologies = with_label::<HashMap<String, MemoryValue>>(next(), TECHNOLOGY_LABEL);
+
+    // Established facts come before uncertain inferences
+    rank_entities(&mut notes);
+    rank_entities(&mut technologies);
+    rank_entities(&mut other_related);
+
+    ProjectContext {
+        schema_version: PROJECT_CONTEXT_SCHEMA_VERSION,
+        project: snapshot.entity.into_typed(),
         git_repository,
         tasks,
         notes,
         technologies,
         other_related_entities: other_related,
-    })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    fn entity(name: &str, label: &str) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            labels: vec![label.to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_context_is_read_from_one_snapshot() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_snapshot()
+            .withf(|name, queries| name == "proj" && queries == context_queries().as_slice())
+            .times(1)
+            .returning(|_, _| {
+                Ok(Some(EntitySnapshot {
+                    entity: entity("proj", PROJECT_LABEL),
+                    related: vec![
+                        vec![entity("task", TASK_LABEL), entity("other", "Person")],
+                        vec![entity("note", NOTE_LABEL)],
+                        vec![entity("repo", GIT_REPOSITORY_LABEL)],
+                        vec![entity("task", TASK_LABEL), entity("other", "Person")],
+                        vec![entity("rust", TECHNOLOGY_LABEL)],
+                    ],
+                }))
+            });
+        mock.expect_find_entity_by_name().never();
+        mock.expect_find_related_entities().never();
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let command = GetProjectContextCommand {
+            filter: ProjectFilter::Name("proj".to_string()),
+            exclude_labels: Vec::new(),
+        };
+
+        let context = get_project_context(&ports, command).await.unwrap().context;
+        assert_eq!(context.project.name, "proj");
+        assert_eq!(context.tasks.len(), 1);
+        assert_eq!(context.notes[0].name, "note");
+        assert_eq!(context.git_repository.unwrap().name, "repo");
+        assert_eq!(context.technologies[0].name, "rust");
+        assert_eq!(context.other_related_entities.len(), 1);
+        assert_eq!(context.other_related_entities[0].name, "other");
+    }
+
+    #[tokio::test]
+    async fn test_context_skips_excluded_labels() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_snapshot().returning(|_, _| {
+            Ok(Some(EntitySnapshot {
+                entity: entity("proj", PROJECT_LABEL),
+                related: vec![
+                    vec![],
+                    vec![],
+                    vec![],
+                    vec![entity("person", "Person"), entity("session", "Session")],
+                    vec![],
+                ],
+            }))
+        });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let command = GetProjectContextCommand {
+            filter: ProjectFilter::Name("proj".to_string()),
+            exclude_labels: vec!["Session".to_string()],
+        };
+
+        let context = get_project_context(&ports, command).await.unwrap().context;
+        assert_eq!(context.other_related_entities.len(), 1);
+        assert_eq!(context.other_related_entities[0].name, "person");
+    }
 }
@@ -1,3 +1,5 @@
+use mm_memory::EntityKind;
+use mm_memory::labels::GIT_REPOSITORY_LABEL;
 use mm_memory::value::MemoryValue;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -41,3 +43,7 @@ impl From<GitRepositoryProperties> for HashMap<String, MemoryValue> {
         map
     }
 }
+
+impl EntityKind for GitRepositoryProperties {
+    const LABEL: &'static str = GIT_REPOSITORY_LABEL;
+}
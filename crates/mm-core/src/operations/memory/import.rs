@@ -0,0 +1,471 @@
+//! Writing an exported graph into a memory backend.
+//!
+//! Used by backup restores and by copies between backends. The import can be
+//! limited to some labels or to one project's subtree, and a
+//! [`ConflictPolicy`] decides what happens to entities that already exist.
+
+use super::export::GraphExport;
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use chrono::Utc;
+use mm_git::GitRepository;
+use mm_memory::merge::{PropertyConflict, apply_resolutions, merge_into, property_conflicts};
+use mm_memory::{
+    EntityUpdate, LabelsUpdate, MemoryEntity, MemoryError, MemoryRelationship, MemoryRepository,
+    MemoryValue, ObservationsUpdate, PropertiesUpdate,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use tracing::instrument;
+
+/// What to do with imported entities whose name is already in use
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// Keep the stored entity untouched
+    #[default]
+    Skip,
+    /// Replace the stored observations, properties and labels with the imported ones
+    Overwrite,
+    /// Add the imported observations, labels and missing properties to the
+    /// stored entity
+    Merge,
+}
+
+/// Which part of a graph to import and how
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct ImportOptions {
+    /// Only import entities with any of these labels; everything when empty
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Only import this project entity and the entities reachable from it
+    /// through outgoing relationships
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub on_conflict: ConflictPolicy,
+    /// Values kept for conflicting properties when merging, by entity and
+    /// property; the stored value is kept for the others
+    #[serde(default)]
+    pub resolutions: HashMap<String, HashMap<String, MemoryValue>>,
+}
+
+/// Outcome of an import
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ImportSummary {
+    /// Entities that did not exist before
+    pub created: usize,
+    /// Existing entities overwritten or merged into
+    pub updated: usize,
+    /// Existing entities left untouched
+    pub skipped: usize,
+    /// Relationships added
+    pub relationships: usize,
+}
+
+pub type ImportGraphResult<E> = CoreResult<ImportSummary, E>;
+
+/// Names of `project` and the entities reachable from it in `graph`
+pub(crate) fn project_subtree(graph: &GraphExport, project: &str) -> HashSet<String> {
+    let mut subtree = HashSet::from([project.to_string()]);
+    let mut queue = VecDeque::from([project]);
+    while let Some(current) = queue.pop_front() {
+        for rel in graph.relationships.iter().filter(|r| r.from == current) {
+            if subtree.insert(rel.to.clone()) {
+                queue.push_back(&rel.to);
+            }
+        }
+    }
+    subtree
+}
+
+/// Part of `graph` selected by `options`
+///
+/// Relationships are kept when both ends are selected. Fails with
+/// [`MemoryError::EntityNotFound`] when the project is not in the graph.
+pub fn select_subgraph<E>(graph: GraphExport, options: &ImportOptions) -> CoreResult<GraphExport, E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let subtree = match &options.project {
+        Some(project) if !graph.entities.iter().any(|e| &e.name == project) => {
+            return Err(CoreError::Memory(MemoryError::EntityNotFound(
+                project.clone(),
+            )));
+        }
+        Some(project) => Some(project_subtree(&graph, project)),
+        None => None,
+    };
+    let entities: Vec<_> = graph
+        .entities
+        .into_iter()
+        .filter(|e| subtree.as_ref().is_none_or(|s| s.contains(&e.name)))
+        .filter(|e| {
+            options.labels.is_empty() || e.labels.iter().any(|l| options.labels.contains(l))
+        })
+        .collect();
+    let names: HashSet<&str> = entities.iter().map(|e| e.name.as_str()).collect();
+    let relationships = graph
+        .relationships
+        .into_iter()
+        .filter(|r| names.contains(r.from.as_str()) && names.contains(r.to.as_str()))
+        .collect();
+    Ok(GraphExport {
+        entities,
+        relationships,
+    })
+}
+
+/// Updates that turn `current` into `desired`
+///
+/// Labels can only be added or removed per update, so removals come as a
+/// second update.
+fn replace_update(current: &MemoryEntity, desired: &MemoryEntity) -> Vec<EntityUpdate> {
+    let mut updates = vec![EntityUpdate {
+        observations: Some(ObservationsUpdate {
+            add: None,
+            remove: None,
+            set: Some(desired.observations.clone()),
+        }),
+        properties: Some(PropertiesUpdate {
+            add: None,
+            remove: None,
+            set: Some(desired.properties.clone()),
+        }),
+        labels: Some(LabelsUpdate {
+            add: Some(desired.labels.clone()),
+            remove: None,
+        }),
+        expected_revision: None,
+        force: false,
+    }];
+    let extra: Vec<String> = current
+        .labels
+        .iter()
+        .filter(|l| !desired.labels.contains(l))
+        .cloned()
+        .collect();
+    if !extra.is_empty() {
+        updates.push(EntityUpdate {
+            labels: Some(LabelsUpdate {
+                add: None,
+                remove: Some(extra),
+            }),
+            // The archive decides the labels, reserved or not
+            force: true,
+            ..Default::default()
+        });
+    }
+    updates
+}
+
+/// Properties whose stored and imported values differ
+fn import_property_conflicts(
+    current: &MemoryEntity,
+    imported: &MemoryEntity,
+) -> Vec<PropertyConflict> {
+    property_conflicts(&[
+        ("stored", &current.properties),
+        ("imported", &imported.properties),
+    ])
+}
+
+/// Properties of existing entities that merging the part of `graph` selected
+/// by `options` would leave at their stored value, by entity
+#[instrument(skip(ports, graph), fields(entities = graph.entities.len()), err)]
+pub async fn import_conflicts<M, G>(
+    ports: &Ports<M, G>,
+    graph: GraphExport,
+    options: &ImportOptions,
+) -> CoreResult<BTreeMap<String, Vec<PropertyConflict>>, M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let graph = select_subgraph(graph, options)?;
+    let mut conflicts = BTreeMap::new();
+    for entity in graph.entities.into_iter().map(MemoryEntity::from) {
+        let Some(current) = ports
+            .memory_service
+            .find_entity_by_name(&entity.name)
+            .await
+            .map_err(CoreError::from)?
+        else {
+            continue;
+        };
+        let found = import_property_conflicts(&current, &entity);
+        if !found.is_empty() {
+            conflicts.insert(entity.name, found);
+        }
+    }
+    Ok(conflicts)
+}
+
+/// Write the part of `graph` selected by `options`
+///
+/// Existing entities are handled according to `on_conflict`, then new
+/// entities and the missing relationships between imported entities are
+/// written in one batch. `progress` is called with the number of entities
+/// checked so far and the number selected.
+#[instrument(skip(ports, graph, progress), fields(entities = graph.entities.len()), err)]
+pub async fn import_graph<M, G>(
+    ports: &Ports<M, G>,
+    graph: GraphExport,
+    options: &ImportOptions,
+    progress: impl Fn(usize, usize) + Send + Sync,
+) -> ImportGraphResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let graph = select_subgraph(graph, options)?;
+    let selected: Vec<MemoryEntity> = graph.entities.into_iter().map(Into::into).collect();
+
+    let service = &ports.memory_service;
+    let mut summary = ImportSummary::default();
+    let mut new_entities = Vec::new();
+    let mut stored_relationships = HashSet::new();
+    for (done, entity) in selected.iter().enumerate() {
+        progress(done, selected.len());
+        let Some(current) = service
+            .find_entity_by_name(&entity.name)
+            .await
+            .map_err(CoreError::from)?
+        else {
+            new_entities.push(entity.clone());
+            continue;
+        };
+        stored_relationships.extend(
+            current
+                .relationships
+                .iter()
+                .map(|r| (r.from.clone(), r.name.clone(), r.to.clone())),
+        );
+        let desired = match options.on_conflict {
+            ConflictPolicy::Skip => {
+                summary.skipped += 1;
+                continue;
+            }
+            ConflictPolicy::Overwrite => entity.clone(),
+            ConflictPolicy::Merge => {
+                let conflicts = import_property_conflicts(&current, entity);
+                let mut merged = merge_into(current.clone(), vec![entity.clone()]);
+                if let Some(resolutions) = options.resolutions.get(&entity.name) {
+                    apply_resolutions(&mut merged, &conflicts, resolutions, Utc::now());
+                }
+                merged
+            }
+        };
+        for update in replace_update(&current, &desired) {
+            service
+                .update_entity(&entity.name, &update)
+                .await
+                .map_err(CoreError::from)?;
+        }
+        summary.updated += 1;
+    }
+    progress(selected.len(), selected.len());
+
+    let relationships: Vec<MemoryRelationship> = graph
+        .relationships
+        .into_iter()
+        .filter(|r| !stored_relationships.contains(&(r.from.clone(), r.name.clone(), r.to.clone())))
+        .map(Into::into)
+        .collect();
+    let errors = service
+        .create_project_graph_typed(None, &new_entities, &relationships)
+        .await
+        .map_err(CoreError::from)?;
+    if !errors.is_empty() {
+        let entity_errors = errors.entities.into_iter().map(|e| e.in_field("entities"));
+        let relationship_errors = errors
+            .relationships
+            .into_iter()
+            .map(|e| e.in_field("relationships"));
+        return Err(CoreError::BatchValidation(
+            entity_errors.chain(relationship_errors).collect(),
+        ));
+    }
+    summary.created = new_entities.len();
+    summary.relationships = relationships.len();
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::memory::export::{ExportedEntity, ExportedRelationship};
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    type TestError = std::convert::Infallible;
+
+    fn graph() -> GraphExport {
+        let entity = |name: &str, labels: &[&str]| ExportedEntity {
+            name: name.to_string(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            observations: vec![],
+            properties: BTreeMap::new(),
+        };
+        GraphExport {
+            entities: vec![
+                entity("a:project:one", &["Memory", "Project"]),
+                entity("a:task:one", &["Memory", "Task"]),
+                entity("b:project:other", &["Project"]),
+            ],
+            relationships: vec![ExportedRelationship {
+                from: "a:project:one".to_string(),
+                to: "a:task:one".to_string(),
+                name: "contains".to_string(),
+                properties: BTreeMap::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_select_subgraph() {
+        let project = ImportOptions {
+            project: Some("a:project:one".to_string()),
+            ..Default::default()
+        };
+        let selected = select_subgraph::<TestError>(graph(), &project).unwrap();
+        assert_eq!(selected.entities.len(), 2);
+        assert_eq!(selected.relationships.len(), 1);
+
+        let labels = ImportOptions {
+            labels: vec!["Project".to_string()],
+            ..Default::default()
+        };
+        let selected = select_subgraph::<TestError>(graph(), &labels).unwrap();
+        assert_eq!(selected.entities.len(), 2);
+        assert!(selected.relationships.is_empty());
+
+        let missing = ImportOptions {
+            project: Some("c:project:gone".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            select_subgraph::<TestError>(graph(), &missing),
+            Err(CoreError::Memory(MemoryError::EntityNotFound(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_import_skips_existing_entities() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|name| {
+            Ok((name == "a:task:one").then(|| MemoryEntity {
+                name: name.to_string(),
+                labels: vec!["Task".to_string()],
+                ..Default::default()
+            }))
+        });
+        mock.expect_update_entity().never();
+        mock.expect_create_graph()
+            .withf(|entities, relationships| {
+                entities.len() == 1
+                    && entities[0].name == "a:project:one"
+                    && relationships.len() == 1
+                    && relationships[0].to == "a:task:one"
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let options = ImportOptions {
+            project: Some("a:project:one".to_string()),
+            ..Default::default()
+        };
+        let calls = std::sync::Mutex::new(Vec::new());
+        let summary = import_graph(&ports, graph(), &options, |done, total| {
+            calls.lock().unwrap().push((done, total))
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                created: 1,
+                updated: 0,
+                skipped: 1,
+                relationships: 1,
+            }
+        );
+        assert_eq!(calls.into_inner().unwrap().last(), Some(&(2, 2)));
+    }
+
+    #[tokio::test]
+    async fn test_import_merge_applies_resolutions() {
+        let stored = MemoryEntity {
+            name: "a:task:one".to_string(),
+            labels: vec!["Memory".to_string(), "Task".to_string()],
+            properties: HashMap::from([("status".to_string(), MemoryValue::String("todo".into()))]),
+            ..Default::default()
+        };
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name()
+            .returning(move |name| Ok((name == "a:task:one").then(|| stored.clone())));
+        mock.expect_update_entity()
+            .withf(|_, update| {
+                update
+                    .properties
+                    .as_ref()
+                    .and_then(|p| p.set.as_ref())
+                    .is_some_and(|set| set["status"] == MemoryValue::String("done".into()))
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock.expect_create_graph().returning(|_, _| Ok(()));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let mut graph = graph();
+        graph.entities[1]
+            .properties
+            .insert("status".to_string(), MemoryValue::String("done".into()));
+        let mut options = ImportOptions {
+            labels: vec!["Task".to_string()],
+            on_conflict: ConflictPolicy::Merge,
+            ..Default::default()
+        };
+
+        let conflicts = import_conflicts(&ports, graph.clone(), &options)
+            .await
+            .unwrap();
+        let status = &conflicts["a:task:one"][0];
+        assert_eq!(status.values[1].from, "imported");
+        options.resolutions = HashMap::from([(
+            "a:task:one".to_string(),
+            HashMap::from([("status".to_string(), status.values[1].value.clone())]),
+        )]);
+        let summary = import_graph(&ports, graph, &options, |_, _| {})
+            .await
+            .unwrap();
+        assert_eq!(summary.updated, 1);
+    }
+
+    #[test]
+    fn test_replace_update_removes_extra_labels() {
+        let current = MemoryEntity {
+            name: "a".to_string(),
+            labels: vec!["Task".to_string(), "Stale".to_string()],
+            ..Default::default()
+        };
+        let desired = MemoryEntity {
+            labels: vec!["Task".to_string()],
+            ..current.clone()
+        };
+        let updates = replace_update(&current, &desired);
+        assert_eq!(updates.len(), 2);
+        assert_eq!(
+            updates[1].labels.as_ref().and_then(|l| l.remove.clone()),
+            Some(vec!["Stale".to_string()])
+        );
+    }
+}
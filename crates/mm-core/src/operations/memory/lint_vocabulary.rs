@@ -0,0 +1,296 @@
+use mm_git::GitRepository;
+use mm_memory::{MemoryConfig, MemoryRepository};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write;
+use tracing::instrument;
+
+use crate::error::CoreResult;
+use crate::ports::Ports;
+
+#[derive(Debug, Clone, Default)]
+pub struct LintVocabularyCommand {}
+
+/// Label or relationship type used in the graph but not allowed by the
+/// configuration
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct UnknownTerm {
+    pub name: String,
+    /// Entities or relationships using it
+    pub count: u64,
+}
+
+/// Configured label or relationship type that nothing in the graph uses
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct UnusedTerm {
+    pub name: String,
+    /// Project whose vocabulary lists it; `None` for the global lists
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+}
+
+/// Drift between the configured vocabulary and the graph
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct VocabularyLint {
+    pub unknown_labels: Vec<UnknownTerm>,
+    pub unused_labels: Vec<UnusedTerm>,
+    pub unknown_relationships: Vec<UnknownTerm>,
+    pub unused_relationships: Vec<UnusedTerm>,
+}
+
+impl VocabularyLint {
+    /// Whether configuration and graph agree
+    pub fn is_clean(&self) -> bool {
+        self.unknown_labels.is_empty()
+            && self.unused_labels.is_empty()
+            && self.unknown_relationships.is_empty()
+            && self.unused_relationships.is_empty()
+    }
+
+    /// TOML for the `[memory]` lists that would resolve the drift
+    ///
+    /// Unknown terms are added to the global lists and unused ones dropped
+    /// from the list that declares them. Only lists that change are printed.
+    pub fn suggested_config(&self, config: &MemoryConfig) -> String {
+        let mut out = String::new();
+        let global = |unused: &[UnusedTerm]| -> HashSet<String> {
+            unused
+                .iter()
+                .filter(|t| t.project.is_none())
+                .map(|t| t.name.clone())
+                .collect()
+        };
+        let mut lists = Vec::new();
+        if let Some(list) = revise(
+            &config.allowed_labels,
+            &global(&self.unused_labels),
+            &self.unknown_labels,
+        ) {
+            lists.push(("allowed_labels", list));
+        }
+        if let Some(list) = revise(
+            &config.allowed_relationships,
+            &global(&self.unused_relationships),
+            &self.unknown_relationships,
+        ) {
+            lists.push(("allowed_relationships", list));
+        }
+        if !lists.is_empty() {
+            out.push_str("[memory]\n");
+            for (key, list) in lists {
+                let _ = writeln!(out, "{key} = {list}");
+            }
+        }
+
+        let mut projects: Vec<_> = config.projects.iter().collect();
+        projects.sort_by_key(|(name, _)| name.as_str());
+        for (project, vocabulary) in projects {
+            let scoped = |unused: &[UnusedTerm]| -> HashSet<String> {
+                unused
+                    .iter()
+                    .filter(|t| t.project.as_deref() == Some(project.as_str()))
+                    .map(|t| t.name.clone())
+                    .collect()
+            };
+            let labels = revise(
+                &vocabulary.allowed_labels,
+                &scoped(&self.unused_labels),
+                &[],
+            );
+            let relationships = revise(
+                &vocabulary.allowed_relationships,
+                &scoped(&self.unused_relationships),
+                &[],
+            );
+            if labels.is_none() && relationships.is_none() {
+                continue;
+            }
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            let _ = writeln!(out, "[memory.projects.{project}]");
+            if let Some(list) = labels {
+                let _ = writeln!(out, "allowed_labels = {list}");
+            }
+            if let Some(list) = relationships {
+                let _ = writeln!(out, "allowed_relationships = {list}");
+            }
+        }
+        out
+    }
+}
+
+/// `configured` without `unused` and with `unknown`, rendered as a TOML
+/// array, or `None` when nothing changes
+fn revise(
+    configured: &HashSet<String>,
+    unused: &HashSet<String>,
+    unknown: &[UnknownTerm],
+) -> Option<String> {
+    if unused.is_empty() && unknown.is_empty() {
+        return None;
+    }
+    let list: BTreeSet<&str> = configured
+        .iter()
+        .filter(|t| !unused.contains(*t))
+        .map(String::as_str)
+        .chain(unknown.iter().map(|t| t.name.as_str()))
+        .collect();
+    serde_json::to_string(&list).ok()
+}
+
+/// Terms in `used` that `known` rejects
+fn unknown_terms(used: &[(String, u64)], known: impl Fn(&str) -> bool) -> Vec<UnknownTerm> {
+    used.iter()
+        .filter(|(name, _)| !known(name))
+        .map(|(name, count)| UnknownTerm {
+            name: name.clone(),
+            count: *count,
+        })
+        .collect()
+}
+
+/// Terms listed in `configured` that are missing from `used`
+fn unused_terms<'a>(
+    configured: impl Iterator<Item = (Option<&'a String>, &'a HashSet<String>)>,
+    used: &HashSet<&str>,
+) -> Vec<UnusedTerm> {
+    let mut unused: Vec<UnusedTerm> = configured
+        .flat_map(|(project, terms)| {
+            terms
+                .iter()
+                .filter(|t| !used.contains(t.as_str()))
+                .map(move |t| UnusedTerm {
+                    name: t.clone(),
+                    project: project.cloned(),
+                })
+        })
+        .collect();
+    unused.sort_by(|a, b| (&a.project, &a.name).cmp(&(&b.project, &b.name)));
+    unused
+}
+
+/// Compare the configured vocabulary with the labels and relationship types
+/// used in the graph
+#[instrument(skip(ports))]
+pub async fn lint_vocabulary<M, G>(
+    ports: &Ports<M, G>,
+    _command: LintVocabularyCommand,
+) -> CoreResult<VocabularyLint, M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+{
+    let config = ports.memory_service.memory_config();
+    let labels = ports.memory_service.list_labels().await?;
+    let relationships = ports.memory_service.list_relationship_types().await?;
+
+    let used_labels: HashSet<&str> = labels.iter().map(|(l, _)| l.as_str()).collect();
+    let used_relationships: HashSet<&str> = relationships.iter().map(|(r, _)| r.as_str()).collect();
+
+    Ok(VocabularyLint {
+        unknown_labels: unknown_terms(&labels, |l| config.is_label_known(l)),
+        unused_labels: unused_terms(
+            std::iter::once((None, &config.allowed_labels)).chain(
+                config
+                    .projects
+                    .iter()
+                    .map(|(p, v)| (Some(p), &v.allowed_labels)),
+            ),
+            &used_labels,
+        ),
+        unknown_relationships: unknown_terms(&relationships, |r| config.is_relationship_known(r)),
+        unused_relationships: unused_terms(
+            std::iter::once((None, &config.allowed_relationships)).chain(
+                config
+                    .projects
+                    .iter()
+                    .map(|(p, v)| (Some(p), &v.allowed_relationships)),
+            ),
+            &used_relationships,
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryService, MockMemoryRepository, ProjectVocabulary};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn config() -> MemoryConfig {
+        MemoryConfig {
+            allowed_labels: HashSet::from(["Decision".to_string(), "Stale".to_string()]),
+            allowed_relationships: HashSet::from(["supersedes".to_string()]),
+            projects: HashMap::from([(
+                "alpha".to_string(),
+                ProjectVocabulary {
+                    allowed_labels: HashSet::from(["Experiment".to_string()]),
+                    allowed_relationships: HashSet::from(["measures".to_string()]),
+                },
+            )]),
+            ..MemoryConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lint_vocabulary_reports_drift() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_list_labels().returning(|| {
+            Ok(vec![
+                ("Decision".to_string(), 3),
+                ("Experiment".to_string(), 1),
+                ("Memory".to_string(), 5),
+                ("Milestone".to_string(), 2),
+            ])
+        });
+        mock.expect_list_relationship_types().returning(|| {
+            Ok(vec![
+                ("blocks".to_string(), 4),
+                ("relates_to".to_string(), 7),
+                ("supersedes".to_string(), 1),
+            ])
+        });
+        let service = MemoryService::new(mock, config());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let lint = lint_vocabulary(&ports, LintVocabularyCommand::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            lint.unknown_labels,
+            [UnknownTerm {
+                name: "Milestone".to_string(),
+                count: 2
+            }]
+        );
+        assert_eq!(
+            lint.unused_labels,
+            [UnusedTerm {
+                name: "Stale".to_string(),
+                project: None
+            }]
+        );
+        assert_eq!(lint.unknown_relationships[0].name, "blocks");
+        assert_eq!(
+            lint.unused_relationships,
+            [UnusedTerm {
+                name: "measures".to_string(),
+                project: Some("alpha".to_string())
+            }]
+        );
+        assert!(!lint.is_clean());
+
+        assert_eq!(
+            lint.suggested_config(&config()),
+            "[memory]\n\
+             allowed_labels = [\"Decision\",\"Milestone\"]\n\
+             allowed_relationships = [\"blocks\",\"supersedes\"]\n\
+             \n\
+             [memory.projects.alpha]\n\
+             allowed_relationships = []\n"
+        );
+    }
+}
@@ -1,6 +1,5 @@
 use mm_git::GitRepository;
-use mm_memory::labels::PROJECT_LABEL;
-use mm_memory::{LabelMatchMode, MemoryEntity, MemoryRepository};
+use mm_memory::{MemoryRepository, ProjectSummary};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
@@ -18,8 +17,8 @@ pub struct ListProjectsCommand {
 /// Result of listing projects
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ListProjectsResult {
-    /// List of available projects
-    pub projects: Vec<MemoryEntity>,
+    /// Available projects with their task counts and latest activity
+    pub projects: Vec<ProjectSummary>,
 }
 
 /// List all available projects
@@ -37,13 +36,13 @@ where
     // Find all projects
     let mut projects = ports
         .memory_service
-        .find_entities_by_labels(&[PROJECT_LABEL.to_string()], LabelMatchMode::All, None)
+        .list_project_summaries()
         .await
         .map_err(CoreError::from)?;
 
     // Apply name filter if provided
     if let Some(filter) = command.name_filter {
-        projects.retain(|p| {
+        projects.retain(|ProjectSummary { project: p, .. }| {
             p.name.contains(&filter) || p.observations.iter().any(|o| o.contains(&filter))
         });
     }
@@ -55,7 +54,10 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use mm_memory::labels::PROJECT_LABEL;
+    use mm_memory::{
+        DEFAULT_MEMORY_LABEL, MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository,
+    };
     use mockall::predicate::*;
     use std::collections::HashMap;
     use std::sync::Arc;
@@ -71,6 +73,7 @@ mod tests {
             observations: vec!["A project for managing memory".to_string()],
             properties: HashMap::new(),
             relationships: Vec::new(),
+            revision: 0,
         };
 
         let project2 = MemoryEntity {
@@ -79,17 +82,19 @@ mod tests {
             observations: vec!["A project for managing Nix flakes".to_string()],
             properties: HashMap::new(),
             relationships: Vec::new(),
+            revision: 0,
         };
 
         mock_repo
-            .expect_find_entities_by_labels()
-            .with(
-                eq(vec![PROJECT_LABEL.to_string()]),
-                eq(LabelMatchMode::All),
-                always(),
-            )
+            .expect_list_project_summaries()
+            .with(eq(Some(DEFAULT_MEMORY_LABEL.to_string())))
             .times(1)
-            .returning(move |_, _, _| Ok(vec![project1.clone(), project2.clone()]));
+            .returning(move |_| {
+                Ok(vec![
+                    ProjectSummary::new(project1.clone(), 2, 1, None),
+                    ProjectSummary::new(project2.clone(), 0, 0, None),
+                ])
+            });
 
         let service = MemoryService::new(mock_repo, MemoryConfig::default());
         let ports = Ports::noop().with(|p| {
@@ -101,8 +106,13 @@ mod tests {
         let result = list_projects(&ports, command).await.unwrap();
 
         assert_eq!(result.projects.len(), 2);
-        assert_eq!(result.projects[0].name, "andoriyu:project:middle_manager");
-        assert_eq!(result.projects[1].name, "andoriyu:project:flakes");
+        assert_eq!(
+            result.projects[0].project.name,
+            "andoriyu:project:middle_manager"
+        );
+        assert_eq!(result.projects[0].task_count, 2);
+        assert_eq!(result.projects[0].open_task_count, 1);
+        assert_eq!(result.projects[1].project.name, "andoriyu:project:flakes");
     }
 
     #[tokio::test]
@@ -116,6 +126,7 @@ mod tests {
             observations: vec!["A project for managing memory".to_string()],
             properties: HashMap::new(),
             relationships: Vec::new(),
+            revision: 0,
         };
 
         let project2 = MemoryEntity {
@@ -124,17 +135,19 @@ mod tests {
             observations: vec!["A project for managing Nix flakes".to_string()],
             properties: HashMap::new(),
             relationships: Vec::new(),
+            revision: 0,
         };
 
         mock_repo
-            .expect_find_entities_by_labels()
-            .with(
-                eq(vec![PROJECT_LABEL.to_string()]),
-                eq(LabelMatchMode::All),
-                always(),
-            )
+            .expect_list_project_summaries()
+            .with(eq(Some(DEFAULT_MEMORY_LABEL.to_string())))
             .times(1)
-            .returning(move |_, _, _| Ok(vec![project1.clone(), project2.clone()]));
+            .returning(move |_| {
+                Ok(vec![
+                    ProjectSummary::new(project1.clone(), 2, 1, None),
+                    ProjectSummary::new(project2.clone(), 0, 0, None),
+                ])
+            });
 
         let service = MemoryService::new(mock_repo, MemoryConfig::default());
         let ports = Ports::noop().with(|p| {
@@ -148,7 +161,7 @@ mod tests {
         let result = list_projects(&ports, command).await.unwrap();
 
         assert_eq!(result.projects.len(), 1);
-        assert_eq!(result.projects[0].name, "andoriyu:project:flakes");
+        assert_eq!(result.projects[0].project.name, "andoriyu:project:flakes");
     }
 
     #[tokio::test]
@@ -157,14 +170,9 @@ mod tests {
 
         // Setup mock for find_entities_by_labels
         mock_repo
-            .expect_find_entities_by_labels()
-            .with(
-                eq(vec![PROJECT_LABEL.to_string()]),
-                eq(LabelMatchMode::All),
-                always(),
-            )
+            .expect_list_project_summaries()
             .times(1)
-            .returning(move |_, _, _| Ok(vec![]));
+            .returning(move |_| Ok(vec![]));
 
         let service = MemoryService::new(mock_repo, MemoryConfig::default());
         let ports = Ports::noop().with(|p| {
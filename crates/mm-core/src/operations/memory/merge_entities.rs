@@ -0,0 +1,120 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::merge::PropertyConflict;
+use mm_memory::{MemoryEntity, MemoryRepository, MemoryValue};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::instrument;
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct MergeEntitiesCommand {
+    /// Entity that is kept
+    pub target: String,
+    /// Duplicates folded into the target and then deleted
+    pub sources: Vec<String>,
+    /// Values kept for conflicting properties instead of the target's
+    #[serde(default)]
+    pub resolutions: HashMap<String, MemoryValue>,
+}
+
+pub type MergeEntitiesResult<E> = CoreResult<MemoryEntity, E>;
+
+pub type MergeConflictsResult<E> = CoreResult<Vec<PropertyConflict>, E>;
+
+/// Combine duplicate entities into one, returning the merged entity
+#[instrument(skip(ports), fields(target = %command.target, sources_count = command.sources.len()))]
+pub async fn merge_entities<M, G>(
+    ports: &Ports<M, G>,
+    command: MergeEntitiesCommand,
+) -> MergeEntitiesResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.target);
+    for name in &command.sources {
+        validate_name!(name);
+    }
+
+    ports
+        .memory_service
+        .merge_entities(&command.target, &command.sources, &command.resolutions)
+        .await
+        .map_err(CoreError::from)
+}
+
+/// Properties whose value differs between the entities `command` would merge
+#[instrument(skip(ports), fields(target = %command.target, sources_count = command.sources.len()))]
+pub async fn merge_conflicts<M, G>(
+    ports: &Ports<M, G>,
+    command: &MergeEntitiesCommand,
+) -> MergeConflictsResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.target);
+    for name in &command.sources {
+        validate_name!(name);
+    }
+
+    ports
+        .memory_service
+        .merge_conflicts(&command.target, &command.sources)
+        .await
+        .map_err(CoreError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryConfig, MemoryError, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_merge_entities_missing_source() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|name| {
+            Ok((name == "a:project:mm").then(|| MemoryEntity {
+                name: name.to_string(),
+                ..Default::default()
+            }))
+        });
+        mock.expect_merge_entities().never();
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let command = MergeEntitiesCommand {
+            target: "a:project:mm".to_string(),
+            sources: vec!["a:project:gone".to_string()],
+            resolutions: HashMap::new(),
+        };
+        let result = merge_entities(&ports, command).await;
+        assert!(matches!(
+            result,
+            Err(CoreError::Memory(MemoryError::EntityNotFound(name))) if name == "a:project:gone"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_merge_entities_empty_source_name() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_merge_entities().never();
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let command = MergeEntitiesCommand {
+            target: "a:project:mm".to_string(),
+            sources: vec![String::new()],
+            resolutions: HashMap::new(),
+        };
+        let result = merge_entities(&ports, command).await;
+        assert!(matches!(result, Err(CoreError::Validation(_))));
+    }
+}
@@ -1,29 +1,78 @@
 #[macro_use]
 mod common;
+mod attachments;
 mod generic;
 mod git;
+mod observation_metadata;
+mod pagination;
 // Re-export label constants from the memory crate
 pub use mm_memory::labels;
 mod projects;
+mod review;
 mod tasks;
 
-pub(crate) use validate_name;
-
+pub mod add_observations;
+pub mod backup;
+pub mod copy;
 pub mod create_entity;
+pub mod create_from_template;
 pub mod create_relationship;
 pub mod delete_entities;
 pub mod delete_relationships;
+pub mod expire_memories;
+pub mod export;
+pub mod find_by_source;
+pub mod find_contradictions;
+pub mod find_duplicates;
 pub mod find_entities_by_labels;
+pub mod find_entity_fuzzy;
+pub mod find_path;
 pub mod find_related_entities;
 pub mod find_relationships;
+pub mod get_audit_log;
 pub mod get_entity;
+pub mod get_entity_history;
 pub mod get_graph_meta;
+pub mod get_neighborhood;
+pub mod get_observations;
 pub mod get_project_context;
+pub mod import;
+pub mod lint_vocabulary;
 pub mod list_projects;
+pub mod merge_entities;
+pub mod project_digest;
+pub mod purge_deleted;
+pub mod remember;
+pub mod rename_entity;
+pub mod replace_in_observations;
+pub mod restore_entities;
+pub mod scan_secrets;
+pub mod slugify_name;
+pub mod stream_project_context;
+pub mod transfer_ownership;
+pub mod update_entities;
 pub mod update_entity;
 pub mod update_relationship;
+pub mod validate_only;
 
-pub use create_entity::{CreateEntitiesCommand, CreateEntitiesResult, create_entities};
+pub use add_observations::{AddObservationsCommand, AddObservationsResult, add_observations};
+pub use attachments::{
+    AttachFileReferenceCommand, AttachFileReferenceResult, AttachmentState, AttachmentStatus,
+    FileAttachment, VerifyAttachments, VerifyAttachmentsCommand, VerifyAttachmentsResult,
+    attach_file_reference, verify_attachments,
+};
+pub use backup::{
+    BACKUP_FORMAT_VERSION, BackupArchive, BackupManifest, CreateBackupCommand, CreateBackupResult,
+    ManifestMismatch, RestoreBackupCommand, RestoreBackupResult, create_backup, restore_backup,
+};
+pub use copy::{CopyGraphCommand, CopyGraphResult, copy_graph};
+pub use create_entity::{
+    CreateEntitiesCommand, CreateEntitiesOutcome, CreateEntitiesResult, create_entities,
+};
+pub use create_from_template::{
+    CreateFromTemplateCommand, CreateFromTemplateResult, CreateFromTemplateResultType,
+    create_from_template,
+};
 pub use create_relationship::{
     CreateRelationshipsCommand, CreateRelationshipsResult, create_relationships,
 };
@@ -31,36 +80,120 @@ pub use delete_entities::{DeleteEntitiesCommand, DeleteEntitiesResult, delete_en
 pub use delete_relationships::{
     DeleteRelationshipsCommand, DeleteRelationshipsResult, delete_relationships,
 };
+pub use expire_memories::{
+    ExpireMemoriesCommand, ExpireMemoriesResult, ExpireMemoriesResultType, expire_memories,
+};
+pub use export::{
+    ExportFormat, ExportGraphCommand, ExportGraphResult, ExportedEntity, ExportedRelationship,
+    GraphExport, export_graph,
+};
+pub use find_by_source::{
+    FindBySourceCommand, FindBySourceResult, FindBySourceResultType, SourceMatch, find_by_source,
+};
+pub use find_contradictions::{
+    Contradiction, ContradictionKind, FindContradictionsCommand, FindContradictionsResult,
+    FindContradictionsResultType, detect_contradictions, find_contradictions,
+};
+pub use find_duplicates::{
+    DEFAULT_MIN_DUPLICATE_SCORE, DuplicateCandidate, FindDuplicatesCommand, FindDuplicatesResult,
+    FindDuplicatesResultType, duplicate_candidates, find_duplicates,
+};
 pub use find_entities_by_labels::{
     FindEntitiesByLabelsCommand, FindEntitiesByLabelsResult, FindEntitiesByLabelsResultType,
     find_entities_by_labels,
 };
+pub use find_entity_fuzzy::{
+    DEFAULT_FUZZY_LIMIT, FindEntityFuzzyCommand, FindEntityFuzzyResult, FindEntityFuzzyResultType,
+    find_entity_fuzzy,
+};
+pub use find_path::{EntityPath, FindPathCommand, FindPathResult, FindPathResultType, find_path};
 pub use find_related_entities::{
     FindRelatedEntitiesCommand, FindRelatedEntitiesResult, FindRelatedEntitiesResultType,
-    find_related_entities,
+    RelatedEntityStream, find_related_entities, stream_related_entities,
 };
 pub use find_relationships::{
     FindRelationshipsCommand, FindRelationshipsResult, FindRelationshipsResultType,
     find_relationships,
 };
 pub use generic::{get_entity_generic, update_entity_generic};
+pub use get_audit_log::{GetAuditLogCommand, GetAuditLogResult, get_audit_log};
 pub use get_entity::{GetEntityCommand, GetEntityResult, get_entity};
+pub use get_entity_history::{GetEntityHistoryCommand, GetEntityHistoryResult, get_entity_history};
 pub use get_graph_meta::{
     GRAPH_ROOT, GetGraphMetaCommand, GetGraphMetaResult, GetGraphMetaResultType, get_graph_meta,
 };
+pub use get_neighborhood::{
+    GetNeighborhoodCommand, GetNeighborhoodResult, GetNeighborhoodResultType, get_neighborhood,
+};
+pub use get_observations::{
+    GetObservationsCommand, GetObservationsResult, GetObservationsResultType, get_observations,
+};
 pub use get_project_context::{
-    GetProjectContextCommand, GetProjectContextResult, ProjectFilter, get_project_context,
+    GetProjectContextCommand, GetProjectContextResult, ProjectFilter, REPOSITORY_ENTITY_PREFIX,
+    get_project_context,
+};
+pub use import::{
+    ConflictPolicy, ImportGraphResult, ImportOptions, ImportSummary, import_conflicts,
+    import_graph, select_subgraph,
 };
 pub use labels::*;
+pub use lint_vocabulary::{
+    LintVocabularyCommand, UnknownTerm, UnusedTerm, VocabularyLint, lint_vocabulary,
+};
 pub use list_projects::{ListProjectsCommand, ListProjectsResult, list_projects};
-pub use projects::{ProjectContext, ProjectProperties, ProjectStatus, ProjectType};
+pub use merge_entities::{
+    MergeConflictsResult, MergeEntitiesCommand, MergeEntitiesResult, merge_conflicts,
+    merge_entities,
+};
+pub use project_digest::{
+    DigestEntry, ProjectDigest, ProjectDigestCommand, ProjectDigestResult, project_digest,
+};
+pub use projects::{
+    PROJECT_CONTEXT_SCHEMA_VERSION, ProjectContext, ProjectContextChunk, ProjectProperties,
+    ProjectStatus, ProjectType,
+};
+pub use purge_deleted::{PurgeDeletedCommand, PurgeDeletedResult, purge_deleted};
+pub use remember::{RememberAction, RememberCommand, RememberResult, remember};
+pub use rename_entity::{RenameEntityCommand, RenameEntityResult, rename_entity};
+pub use replace_in_observations::{
+    ObservationChanges, ObservationEdit, ReplaceInObservationsCommand, ReplaceInObservationsResult,
+    replace_in_observations,
+};
+pub use restore_entities::{RestoreEntitiesCommand, RestoreEntitiesResult, restore_entities};
+pub use review::{
+    ForgetProposal, ListForgetProposalsCommand, ListForgetProposalsResult, ProposeForgetCommand,
+    ProposeForgetResult, ResolveForgetProposalCommand, ResolveForgetProposalResult, ReviewDecision,
+    list_forget_proposals, propose_forget, resolve_forget_proposal,
+};
+pub use scan_secrets::{ScanSecretsCommand, ScanSecretsResult, SecretFinding, scan_secrets};
+pub use slugify_name::{
+    SlugifyNameCommand, SlugifyNameResult, SlugifyNameResultType, slugify_name,
+};
+pub use stream_project_context::{
+    StreamProjectContextCommand, StreamProjectContextResult, StreamProjectContextResultType,
+    stream_project_context,
+};
 pub use tasks::{
-    CreateTasksCommand, CreateTasksResult, DeleteTaskCommand, DeleteTaskResult, GetTaskCommand,
-    GetTaskResult, ListTasksCommand, ListTasksResult, Priority, TaskInput, TaskProperties,
-    TaskStatus, TaskType, UpdateTaskCommand, UpdateTaskResult, create_tasks, delete_task, get_task,
-    list_tasks, update_task,
+    BiasFactor, CreateTasksCommand, CreateTasksResult, CriticalPathCommand, CriticalPathReport,
+    CriticalPathResult, DEPENDS_ON_RELATIONSHIP, DeleteTaskCommand, DeleteTaskResult,
+    EstimateCalibration, EstimateCalibrationCommand, EstimateCalibrationResult, GetTaskCommand,
+    GetTaskResult, ListTasksCommand, ListTasksResult, Priority, StandupItem, StandupSummary,
+    StandupSummaryCommand, StandupSummaryResult, TaskDependency, TaskGraph, TaskGraphCommand,
+    TaskGraphResult, TaskInput, TaskNode, TaskProperties, TaskReport, TaskReportCommand,
+    TaskReportResult, TaskSchedule, TaskStatus, TaskType, UpdateTaskCommand, UpdateTaskOutcome,
+    UpdateTaskResult, WipScope, WipViolation, analyze_critical_path, create_tasks, critical_path,
+    delete_task, estimate_calibration, get_task, list_tasks, standup_summary, task_graph,
+    task_report, update_task,
+};
+pub use transfer_ownership::{
+    CREATED_BY_PROPERTY, OwnershipChange, TransferOwnershipCommand, TransferOwnershipResult,
+    transfer_ownership,
 };
+pub use update_entities::{UpdateEntitiesCommand, UpdateEntitiesResult, update_entities};
 pub use update_entity::{UpdateEntityCommand, UpdateEntityResult, update_entity};
 pub use update_relationship::{
     UpdateRelationshipCommand, UpdateRelationshipResult, update_relationship,
 };
+pub use validate_only::{
+    ValidateOnlyCommand, ValidateOnlyResult, ValidateOnlyResultType, validate_only,
+};
@@ -0,0 +1,182 @@
+use mm_git::GitRepository;
+use mm_memory::confidence::{
+    OBSERVATION_CONFIDENCE_PROPERTY, is_valid_confidence, set_observation_confidence,
+};
+use mm_memory::source::{OBSERVATION_SOURCES_PROPERTY, SOURCES_PROPERTY, set_observation_source};
+use mm_memory::{
+    EntityUpdate, MemoryRepository, MemoryValue, ObservationSource, PropertiesUpdate,
+    ValidationError, ValidationErrorKind,
+};
+use std::collections::HashMap;
+
+use crate::error::CoreResult;
+use crate::ports::Ports;
+
+/// Metadata recorded for every observation a command writes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObservationMetadata {
+    /// Confidence between 0 and 1; unscored observations count as fact
+    pub confidence: Option<f64>,
+    /// Where the observations were learned
+    pub source: Option<ObservationSource>,
+}
+
+impl ObservationMetadata {
+    /// Whether there is nothing to record
+    pub fn is_empty(&self) -> bool {
+        self.confidence.is_none() && self.source.is_none()
+    }
+
+    /// Reject metadata that cannot be stored for `subject`
+    pub fn validate(&self, subject: &str) -> Result<(), ValidationError> {
+        if let Some(confidence) = self.confidence
+            && !is_valid_confidence(confidence)
+        {
+            return Err(ValidationErrorKind::InvalidConfidence(subject.to_string()).into());
+        }
+        if let Some(source) = &self.source {
+            source.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Properties recording this metadata for each of `observations`
+    ///
+    /// Metadata already in `existing` is carried over so that adding some
+    /// observations keeps the scores and citations of the others.
+    pub fn properties(
+        &self,
+        existing: &HashMap<String, MemoryValue>,
+        observations: &[String],
+    ) -> HashMap<String, MemoryValue> {
+        let mut properties = HashMap::new();
+        let carry_over = |properties: &mut HashMap<String, MemoryValue>, key: &str| {
+            if let Some(value) = existing.get(key) {
+                properties.insert(key.to_string(), value.clone());
+            }
+        };
+        if let Some(confidence) = self.confidence {
+            carry_over(&mut properties, OBSERVATION_CONFIDENCE_PROPERTY);
+            for observation in observations {
+                set_observation_confidence(&mut properties, observation, confidence);
+            }
+        }
+        if let Some(source) = &self.source {
+            carry_over(&mut properties, OBSERVATION_SOURCES_PROPERTY);
+            carry_over(&mut properties, SOURCES_PROPERTY);
+            for observation in observations {
+                set_observation_source(&mut properties, observation, source);
+            }
+        }
+        properties
+    }
+}
+
+/// Add `metadata` for the observations `update` writes to its properties
+///
+/// Existing metadata of `name` is read first so that it is carried over.
+pub(crate) async fn record_in_update<M, G>(
+    ports: &Ports<M, G>,
+    name: &str,
+    update: &mut EntityUpdate,
+    metadata: &ObservationMetadata,
+) -> CoreResult<(), M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let Some(observations) = &update.observations else {
+        return Ok(());
+    };
+    let written: Vec<String> = observations
+        .add
+        .iter()
+        .chain(&observations.set)
+        .flatten()
+        .cloned()
+        .collect();
+    if metadata.is_empty() || written.is_empty() {
+        return Ok(());
+    }
+    metadata.validate(name)?;
+
+    let existing = ports
+        .memory_service
+        .find_entity_by_name(name)
+        .await?
+        .map(|e| e.properties)
+        .unwrap_or_default();
+    let recorded = metadata.properties(&existing, &written);
+    let properties = update.properties.get_or_insert(PropertiesUpdate {
+        add: None,
+        remove: None,
+        set: None,
+    });
+    // A property set replaces everything, so the metadata has to be part of it
+    let target = match &mut properties.set {
+        Some(set) => set,
+        None => properties.add.get_or_insert_default(),
+    };
+    target.extend(recorded);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::confidence::observation_confidence;
+    use mm_memory::source::observation_source;
+
+    #[test]
+    fn test_properties_keep_existing_scores() {
+        let mut existing = HashMap::new();
+        set_observation_confidence(&mut existing, "old", 0.4);
+        let metadata = ObservationMetadata {
+            confidence: Some(0.8),
+            ..Default::default()
+        };
+
+        let properties = metadata.properties(&existing, &["new".to_string()]);
+        assert_eq!(observation_confidence(&properties, "old"), 0.4);
+        assert_eq!(observation_confidence(&properties, "new"), 0.8);
+    }
+
+    #[test]
+    fn test_properties_keep_existing_sources() {
+        let doc = ObservationSource {
+            url: Some("https://example.com".to_string()),
+            ..Default::default()
+        };
+        let mut existing = HashMap::new();
+        set_observation_source(&mut existing, "old", &doc);
+        let chat = ObservationSource {
+            conversation_id: Some("c1".to_string()),
+            ..Default::default()
+        };
+        let metadata = ObservationMetadata {
+            source: Some(chat.clone()),
+            ..Default::default()
+        };
+
+        let properties = metadata.properties(&existing, &["new".to_string()]);
+        assert_eq!(observation_source(&properties, "old"), Some(doc));
+        assert_eq!(observation_source(&properties, "new"), Some(chat));
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid() {
+        let metadata = ObservationMetadata {
+            confidence: Some(1.5),
+            ..Default::default()
+        };
+        assert!(metadata.validate("e").is_err());
+        let metadata = ObservationMetadata {
+            source: Some(ObservationSource::default()),
+            ..Default::default()
+        };
+        assert!(metadata.validate("e").is_err());
+        assert!(ObservationMetadata::default().validate("e").is_ok());
+    }
+}
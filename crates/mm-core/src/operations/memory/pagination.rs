@@ -0,0 +1,69 @@
+use mm_memory::ValidationErrorKind;
+
+/// Page of `items` of at most `limit` starting at `cursor`
+///
+/// Returns the page and the cursor of the next one, which is `None` on the
+/// last page. Cursors are opaque to callers; `items` must be in a stable
+/// order for them to stay meaningful between calls.
+pub(crate) fn paginate<T>(
+    items: Vec<T>,
+    limit: Option<usize>,
+    cursor: Option<&str>,
+) -> Result<(Vec<T>, Option<String>), ValidationErrorKind> {
+    let start = match cursor {
+        Some(c) => c
+            .parse::<usize>()
+            .ok()
+            .filter(|&start| start <= items.len())
+            .ok_or_else(|| ValidationErrorKind::InvalidCursor(c.to_string()))?,
+        None => 0,
+    };
+    let end = match limit {
+        Some(0) => return Err(ValidationErrorKind::InvalidLimit),
+        Some(limit) => start.saturating_add(limit).min(items.len()),
+        None => items.len(),
+    };
+    let next_cursor = (end < items.len()).then(|| end.to_string());
+    let page = items.into_iter().skip(start).take(end - start).collect();
+    Ok((page, next_cursor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pages_follow_cursors() {
+        let items: Vec<u32> = (0..5).collect();
+        let (page, next) = paginate(items.clone(), Some(2), None).unwrap();
+        assert_eq!(page, [0, 1]);
+        let (page, next) = paginate(items.clone(), Some(2), next.as_deref()).unwrap();
+        assert_eq!(page, [2, 3]);
+        let (page, next) = paginate(items.clone(), Some(2), next.as_deref()).unwrap();
+        assert_eq!(page, [4]);
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn test_no_limit_returns_everything() {
+        let (page, next) = paginate(vec![1, 2, 3], None, None).unwrap();
+        assert_eq!(page, [1, 2, 3]);
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn test_rejects_bad_cursor_and_limit() {
+        assert_eq!(
+            paginate(vec![1], Some(1), Some("abc")),
+            Err(ValidationErrorKind::InvalidCursor("abc".to_string()))
+        );
+        assert_eq!(
+            paginate(vec![1], Some(1), Some("5")),
+            Err(ValidationErrorKind::InvalidCursor("5".to_string()))
+        );
+        assert_eq!(
+            paginate(vec![1], Some(0), None),
+            Err(ValidationErrorKind::InvalidLimit)
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use chrono::{DateTime, Utc};
+use mm_git::GitRepository;
+use mm_memory::audit::AuditQuery;
+use mm_memory::expiry::{ARCHIVED_AT_PROPERTY, timestamp};
+use mm_memory::labels::{DECISION_LABEL, TASK_LABEL};
+use mm_memory::{MemoryEntity, MemoryError, MemoryRepository, MemoryValue, RelationshipDirection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::instrument;
+
+use super::tasks::TaskStatus;
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+
+#[derive(Debug, Clone)]
+pub struct ProjectDigestCommand {
+    pub project_name: String,
+    /// Start of the period to summarize
+    pub since: DateTime<Utc>,
+}
+
+/// Entity mentioned in a digest
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct DigestEntry {
+    pub name: String,
+    pub labels: Vec<String>,
+}
+
+/// What changed in a project over a period, each list sorted by name
+///
+/// Creations and updates are taken from the audit log, so they are only
+/// reported when `memory.audit_log` is set. Archiving is read from the
+/// `archived_at` timestamp it leaves on the entity.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ProjectDigest {
+    pub project_name: String,
+    pub since: DateTime<Utc>,
+    /// Done tasks updated during the period
+    pub completed_tasks: Vec<DigestEntry>,
+    /// `Decision` entities created during the period
+    pub decisions: Vec<DigestEntry>,
+    /// Other entities added to the project during the period
+    pub created: Vec<DigestEntry>,
+    /// Entities archived during the period
+    pub archived: Vec<DigestEntry>,
+}
+
+pub type ProjectDigestResult<E> = CoreResult<ProjectDigest, E>;
+
+fn entry(entity: &MemoryEntity) -> DigestEntry {
+    DigestEntry {
+        name: entity.name.clone(),
+        labels: entity.labels.clone(),
+    }
+}
+
+fn has_label(entity: &MemoryEntity, label: &str) -> bool {
+    entity.labels.iter().any(|l| l == label)
+}
+
+/// Summarize how the entities a project contains changed since a point in time
+#[instrument(skip(ports))]
+pub async fn project_digest<M, G>(
+    ports: &Ports<M, G>,
+    command: ProjectDigestCommand,
+) -> ProjectDigestResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.project_name);
+    if ports
+        .memory_service
+        .find_entity_by_name(&command.project_name)
+        .await?
+        .is_none()
+    {
+        return Err(CoreError::Memory(MemoryError::entity_not_found(
+            command.project_name,
+        )));
+    }
+
+    let mut entities = ports
+        .memory_service
+        .find_related_entities(
+            &command.project_name,
+            Some("contains".to_string()),
+            Some(RelationshipDirection::Outgoing),
+            1,
+        )
+        .await?;
+    entities.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let since = command.since;
+    let changes = ports.memory_service.audit_log(&AuditQuery {
+        since: Some(since),
+        ..Default::default()
+    })?;
+    let touched_by = |operations: &[&str]| -> HashSet<&str> {
+        changes
+            .iter()
+            .filter(|e| operations.contains(&e.operation.as_str()))
+            .flat_map(|e| e.names.iter().map(String::as_str))
+            .collect()
+    };
+    let created = touched_by(&["create_entities", "create_graph"]);
+    let updated = touched_by(&["update_entity"]);
+    let mut digest = ProjectDigest {
+        project_name: command.project_name,
+        since,
+        completed_tasks: Vec::new(),
+        decisions: Vec::new(),
+        created: Vec::new(),
+        archived: Vec::new(),
+    };
+    for entity in &entities {
+        let done = matches!(
+            entity.properties.get("status"),
+            Some(MemoryValue::String(s)) if s == TaskStatus::Done.as_ref()
+        );
+        let name = entity.name.as_str();
+        if has_label(entity, TASK_LABEL) && done && updated.contains(name) {
+            digest.completed_tasks.push(entry(entity));
+        }
+        if created.contains(name) {
+            if has_label(entity, DECISION_LABEL) {
+                digest.decisions.push(entry(entity));
+            } else {
+                digest.created.push(entry(entity));
+            }
+        }
+        if timestamp(&entity.properties, ARCHIVED_AT_PROPERTY).is_some_and(|t| t >= since) {
+            digest.archived.push(entry(entity));
+        }
+    }
+    Ok(digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use mm_memory::audit::{AuditEntry, AuditSink, InMemoryAuditSink};
+    use mm_memory::labels::{NOTE_LABEL, PROJECT_LABEL};
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn entity(name: &str, label: &str, properties: &[(&str, MemoryValue)]) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            labels: vec![label.to_string()],
+            properties: properties
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect::<HashMap<_, _>>(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_project_digest_buckets_recent_changes() {
+        let now = Utc::now();
+        let ago = |days: i64| now - Duration::days(days);
+        let done = MemoryValue::String("done".to_string());
+        let entities = vec![
+            entity("task:shipped", TASK_LABEL, &[("status", done.clone())]),
+            entity("task:old", TASK_LABEL, &[("status", done)]),
+            entity("decision:db", DECISION_LABEL, &[]),
+            entity("note:new", NOTE_LABEL, &[]),
+            entity(
+                "note:gone",
+                NOTE_LABEL,
+                &[("archived_at", MemoryValue::DateTime(ago(4).into()))],
+            ),
+            entity("note:untimed", NOTE_LABEL, &[]),
+        ];
+
+        let audit = Arc::new(InMemoryAuditSink::default());
+        for (days, operation, name) in [
+            (30, "create_entities", "task:shipped"),
+            (20, "update_entity", "task:old"),
+            (3, "create_graph", "note:new"),
+            (2, "create_entities", "decision:db"),
+            (1, "update_entity", "task:shipped"),
+        ] {
+            audit
+                .record(AuditEntry {
+                    timestamp: ago(days),
+                    actor: "agent".to_string(),
+                    operation: operation.to_string(),
+                    names: vec![name.to_string()],
+                    correlation_id: None,
+                })
+                .unwrap();
+        }
+
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|name| {
+            Ok(Some(MemoryEntity {
+                name: name.to_string(),
+                labels: vec![PROJECT_LABEL.to_string()],
+                ..Default::default()
+            }))
+        });
+        mock.expect_find_related_entities()
+            .returning(move |_, _, _, _| Ok(entities.clone()));
+        let service = MemoryService::new(mock, MemoryConfig::default()).with_audit_sink(audit);
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let digest = project_digest(
+            &ports,
+            ProjectDigestCommand {
+                project_name: "project:x".to_string(),
+                since: ago(7),
+            },
+        )
+        .await
+        .unwrap();
+        let names =
+            |entries: &[DigestEntry]| entries.iter().map(|e| e.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&digest.completed_tasks), ["task:shipped"]);
+        assert_eq!(names(&digest.decisions), ["decision:db"]);
+        assert_eq!(names(&digest.created), ["note:new"]);
+        assert_eq!(names(&digest.archived), ["note:gone"]);
+    }
+
+    #[tokio::test]
+    async fn test_project_digest_missing_project() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|_| Ok(None));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let result = project_digest(
+            &ports,
+            ProjectDigestCommand {
+                project_name: "project:missing".to_string(),
+                since: Utc::now(),
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(CoreError::Memory(_))));
+    }
+}
@@ -1,3 +1,6 @@
 pub mod types;
 
-pub use types::{ProjectContext, ProjectProperties, ProjectStatus, ProjectType};
+pub use types::{
+    PROJECT_CONTEXT_SCHEMA_VERSION, ProjectContext, ProjectContextChunk, ProjectProperties,
+    ProjectStatus, ProjectType,
+};
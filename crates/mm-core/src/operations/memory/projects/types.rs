@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
-use mm_memory::{MemoryEntity, value::MemoryValue};
+use mm_memory::labels::PROJECT_LABEL;
+use mm_memory::{EntityKind, MemoryEntity, PropertyRule, value::MemoryValue};
 use std::collections::HashMap;
 
 use crate::operations::memory::{git::types::GitRepositoryProperties, tasks::TaskProperties};
@@ -39,29 +40,105 @@ impl Default for ProjectProperties {
     }
 }
 
-/// Context information about a project
+/// Version of the [`ProjectContext`] JSON layout
+///
+/// Bumped when a field is renamed, removed or changes meaning. Adding a
+/// category keeps the version, so parsers should ignore unknown fields.
+pub const PROJECT_CONTEXT_SCHEMA_VERSION: u32 = 1;
 
+/// Context information about a project
+///
+/// Related entities are grouped into one field per category. Categories
+/// missing from a payload deserialize as empty.
 #[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
 pub struct ProjectContext {
+    /// Layout version, see [`PROJECT_CONTEXT_SCHEMA_VERSION`]
+    #[serde(default = "ProjectContext::current_schema_version")]
+    pub schema_version: u32,
+
     /// The project entity
     pub project: MemoryEntity<ProjectProperties>,
 
     /// Associated git repository (if any)
+    #[serde(default)]
     pub git_repository: Option<MemoryEntity<GitRepositoryProperties>>,
 
     /// Tasks associated with the project
+    #[serde(default)]
     pub tasks: Vec<MemoryEntity<TaskProperties>>,
 
     /// Technologies used in the project
+    #[serde(default)]
     pub technologies: Vec<MemoryEntity>,
 
     /// Notes related to the project
+    #[serde(default)]
     pub notes: Vec<MemoryEntity>,
 
     /// Other related entities
+    #[serde(default)]
     pub other_related_entities: Vec<MemoryEntity>,
 }
 
+impl ProjectContext {
+    /// Helper for serde default of `schema_version`
+    fn current_schema_version() -> u32 {
+        PROJECT_CONTEXT_SCHEMA_VERSION
+    }
+
+    /// Split the context into chunks, most important first
+    ///
+    /// The project chunk always comes first; empty categories are dropped.
+    pub fn into_chunks(self) -> Vec<ProjectContextChunk> {
+        let mut chunks = vec![ProjectContextChunk::Project {
+            schema_version: self.schema_version,
+            project: Box::new(self.project),
+            git_repository: self.git_repository.map(Box::new),
+        }];
+        if !self.tasks.is_empty() {
+            chunks.push(ProjectContextChunk::Tasks { tasks: self.tasks });
+        }
+        if !self.technologies.is_empty() {
+            chunks.push(ProjectContextChunk::Technologies {
+                technologies: self.technologies,
+            });
+        }
+        if !self.notes.is_empty() || !self.other_related_entities.is_empty() {
+            chunks.push(ProjectContextChunk::Misc {
+                notes: self.notes,
+                other_related_entities: self.other_related_entities,
+            });
+        }
+        chunks
+    }
+}
+
+/// Part of a [`ProjectContext`], tagged by `section`
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(tag = "section", rename_all = "snake_case")]
+pub enum ProjectContextChunk {
+    /// The project itself and its repository
+    Project {
+        schema_version: u32,
+        project: Box<MemoryEntity<ProjectProperties>>,
+        #[serde(default)]
+        git_repository: Option<Box<MemoryEntity<GitRepositoryProperties>>>,
+    },
+    /// Tasks associated with the project
+    Tasks {
+        tasks: Vec<MemoryEntity<TaskProperties>>,
+    },
+    /// Technologies used in the project
+    Technologies { technologies: Vec<MemoryEntity> },
+    /// Notes and any other related entities
+    Misc {
+        #[serde(default)]
+        notes: Vec<MemoryEntity>,
+        #[serde(default)]
+        other_related_entities: Vec<MemoryEntity>,
+    },
+}
+
 /// Project status
 #[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
 pub enum ProjectStatus {
@@ -178,3 +255,82 @@ impl From<ProjectProperties> for HashMap<String, MemoryValue> {
         map
     }
 }
+
+impl EntityKind for ProjectProperties {
+    const LABEL: &'static str = PROJECT_LABEL;
+    const RULES: &'static [(&'static str, PropertyRule)] = &[
+        (
+            "status",
+            PropertyRule::OneOf(&["active", "maintenance", "archived", "planning"]),
+        ),
+        (
+            "project_type",
+            PropertyRule::OneOf(&[
+                "application",
+                "library",
+                "tool",
+                "configuration",
+                "documentation",
+                "other",
+            ]),
+        ),
+    ];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_utils::IntoJsonSchema;
+
+    #[test]
+    fn test_project_context_schema_lists_categories() {
+        let schema = <ProjectContext as IntoJsonSchema>::json_schema();
+        let properties = schema["properties"].as_object().unwrap();
+        for field in [
+            "schema_version",
+            "project",
+            "git_repository",
+            "tasks",
+            "technologies",
+            "notes",
+            "other_related_entities",
+        ] {
+            assert!(properties.contains_key(field), "missing {field}");
+        }
+        assert_eq!(schema["required"], serde_json::json!(["project"]));
+    }
+
+    #[test]
+    fn test_into_chunks_orders_sections_and_drops_empty() {
+        let context = ProjectContext {
+            schema_version: PROJECT_CONTEXT_SCHEMA_VERSION,
+            project: MemoryEntity {
+                name: "project:x".to_string(),
+                ..Default::default()
+            },
+            git_repository: None,
+            tasks: vec![MemoryEntity::default()],
+            technologies: Vec::new(),
+            notes: vec![MemoryEntity::default()],
+            other_related_entities: Vec::new(),
+        };
+        let sections: Vec<String> = context
+            .into_chunks()
+            .iter()
+            .map(|c| serde_json::to_value(c).unwrap()["section"].to_string())
+            .collect();
+        assert_eq!(sections, ["\"project\"", "\"tasks\"", "\"misc\""]);
+    }
+
+    #[test]
+    fn test_project_context_tolerates_missing_and_unknown_categories() {
+        let context: ProjectContext = serde_json::from_value(serde_json::json!({
+            "project": {"name": "project:x", "labels": ["Project"], "observations": []},
+            "components": [],
+        }))
+        .unwrap();
+        assert_eq!(context.schema_version, PROJECT_CONTEXT_SCHEMA_VERSION);
+        assert!(context.tasks.is_empty());
+        assert!(context.git_repository.is_none());
+    }
+}
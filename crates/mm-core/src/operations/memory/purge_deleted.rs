@@ -0,0 +1,76 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::MemoryRepository;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct PurgeDeletedCommand {
+    /// Entities to purge; the whole trash when empty
+    #[serde(default)]
+    pub names: Vec<String>,
+}
+
+pub type PurgeDeletedResult<E> = CoreResult<Vec<String>, E>;
+
+/// Permanently delete entities in the trash, returning their names
+#[instrument(skip(ports), fields(names_count = command.names.len()))]
+pub async fn purge_deleted<M, G>(
+    ports: &Ports<M, G>,
+    command: PurgeDeletedCommand,
+) -> PurgeDeletedResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    ports
+        .memory_service
+        .purge_deleted(&command.names)
+        .await
+        .map_err(CoreError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{
+        DELETED_LABEL, MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository,
+    };
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_purge_only_named_entities() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_by_labels()
+            .withf(|labels, _, _| labels == [DELETED_LABEL.to_string()])
+            .returning(|_, _, _| {
+                Ok(["a", "b"]
+                    .into_iter()
+                    .map(|name| MemoryEntity {
+                        name: name.to_string(),
+                        labels: vec![DELETED_LABEL.to_string()],
+                        ..Default::default()
+                    })
+                    .collect())
+            });
+        mock.expect_delete_entities()
+            .withf(|names| names == ["b".to_string()])
+            .times(1)
+            .returning(|_| Ok(()));
+        let config = MemoryConfig {
+            soft_delete: true,
+            ..MemoryConfig::default()
+        };
+        let service = MemoryService::new(mock, config);
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let command = PurgeDeletedCommand {
+            names: vec!["b".to_string(), "live".to_string()],
+        };
+        assert_eq!(purge_deleted(&ports, command).await.unwrap(), ["b"]);
+    }
+}
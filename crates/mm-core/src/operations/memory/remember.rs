@@ -0,0 +1,310 @@
+use mm_git::GitRepository;
+use mm_memory::labels::NOTE_LABEL;
+use mm_memory::{
+    EntityUpdate, LabelsUpdate, MemoryConfig, MemoryEntity, MemoryRepository, ObservationSource,
+    ObservationsUpdate, PropertiesUpdate, ValidationError, ValidationErrorKind,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::instrument;
+
+use super::common::handle_batch_result;
+use super::observation_metadata::ObservationMetadata;
+use crate::error::CoreResult;
+use crate::ports::Ports;
+
+/// Maximum number of words taken from the text when deriving an entity name
+const NAME_WORDS: usize = 8;
+
+/// Command for quickly capturing a piece of knowledge
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct RememberCommand {
+    /// Free text to remember
+    pub text: String,
+    /// Labels for the entity; defaults to `Note` for new entities
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Entity the text is about; derived from the text when omitted
+    #[serde(default)]
+    pub entity_name: Option<String>,
+    /// Confidence in the text between 0 and 1; unscored text counts as fact
+    #[serde(default)]
+    pub confidence: Option<f64>,
+    /// Where the text was learned
+    #[serde(default)]
+    pub source: Option<ObservationSource>,
+}
+
+/// How the remembered text was stored
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RememberAction {
+    /// A new entity was created with the text as its first observation
+    Created,
+    /// The text was added as an observation to an existing entity
+    ObservationAdded,
+}
+
+/// Result of remembering a piece of text
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct RememberResult {
+    /// Name of the entity holding the text
+    pub entity_name: String,
+    /// Whether the entity was created or updated
+    pub action: RememberAction,
+}
+
+/// Store free text in the memory graph
+///
+/// The text becomes an observation on `entity_name` when that entity exists
+/// and a new entity otherwise. Without an explicit target the name follows
+/// `memory.name_template`, with the first label as the label and the leading
+/// words of the text as the title, so remembering the same fact twice lands
+/// on the same entity.
+#[instrument(skip(ports), err)]
+pub async fn remember<M, G>(
+    ports: &Ports<M, G>,
+    command: RememberCommand,
+) -> CoreResult<RememberResult, M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let text = command.text.trim().to_string();
+    if text.is_empty() {
+        return Err(ValidationError::from(ValidationErrorKind::EmptyObservation).into());
+    }
+    let metadata = ObservationMetadata {
+        confidence: command.confidence,
+        source: command.source,
+    };
+    metadata.validate(&text)?;
+
+    let entity_name = match command.entity_name {
+        Some(name) => name,
+        None => derive_entity_name(ports.memory_service.memory_config(), &text, &command.labels),
+    };
+    validate_name!(entity_name);
+
+    let existing = ports
+        .memory_service
+        .find_entity_by_name(&entity_name)
+        .await?;
+
+    let action = match existing {
+        Some(entity) => {
+            let missing: Vec<String> = command
+                .labels
+                .into_iter()
+                .filter(|l| !entity.labels.contains(l))
+                .collect();
+            let metadata = metadata.properties(&entity.properties, std::slice::from_ref(&text));
+            let properties = (!metadata.is_empty()).then_some(PropertiesUpdate {
+                add: Some(metadata),
+                remove: None,
+                set: None,
+            });
+            let update = EntityUpdate {
+                observations: Some(ObservationsUpdate {
+                    add: Some(vec![text]),
+                    remove: None,
+                    set: None,
+                }),
+                labels: (!missing.is_empty()).then_some(LabelsUpdate {
+                    add: Some(missing),
+                    remove: None,
+                }),
+                properties,
+                expected_revision: None,
+                force: false,
+            };
+            ports
+                .memory_service
+                .update_entity(&entity_name, &update)
+                .await?;
+            RememberAction::ObservationAdded
+        }
+        None => {
+            let labels = if command.labels.is_empty() {
+                vec![NOTE_LABEL.to_string()]
+            } else {
+                command.labels
+            };
+            let properties = metadata.properties(&HashMap::new(), std::slice::from_ref(&text));
+            let entity = MemoryEntity {
+                name: entity_name.clone(),
+                labels,
+                observations: vec![text],
+                properties,
+                ..Default::default()
+            };
+            handle_batch_result("entities", || {
+                ports
+                    .memory_service
+                    .create_entities(std::slice::from_ref(&entity))
+            })
+            .await?;
+            RememberAction::Created
+        }
+    };
+
+    Ok(RememberResult {
+        entity_name,
+        action,
+    })
+}
+
+/// Name for `text` following the configured name template
+///
+/// The first label, or `Note`, fills `{{label}}` and the leading words of the
+/// text fill `{{slug}}`.
+fn derive_entity_name(config: &MemoryConfig, text: &str, labels: &[String]) -> String {
+    let label = labels.first().map_or(NOTE_LABEL, String::as_str);
+    let title = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .take(NAME_WORDS)
+        .collect::<Vec<_>>()
+        .join(" ");
+    config.entity_name(None, label, &title)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::CoreError;
+    use mm_memory::{MemoryService, MockMemoryRepository};
+    use mockall::predicate::*;
+    use std::sync::Arc;
+
+    fn ports_with(
+        mock: MockMemoryRepository,
+    ) -> Ports<MockMemoryRepository, mm_git::repository::MockGitRepository> {
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        Ports::noop().with(|p| p.memory_service = Arc::new(service))
+    }
+
+    #[test]
+    fn test_derive_entity_name() {
+        let mut config = MemoryConfig::default();
+        assert_eq!(
+            derive_entity_name(
+                &config,
+                "Neo4j needs APOC for label updates!",
+                &["UsefulQuery".to_string()]
+            ),
+            "useful_query:neo4j_needs_apoc_for_label_updates"
+        );
+        config.name_template = "memory:{{label}}:{{slug}}".to_string();
+        assert_eq!(
+            derive_entity_name(&config, "one two three four five six seven eight nine", &[]),
+            "memory:note:one_two_three_four_five_six_seven_eight"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remember_creates_entity() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name()
+            .with(eq("note:prefer_small_commits"))
+            .returning(|_| Ok(None));
+        mock.expect_create_entities()
+            .withf(|e| {
+                e.len() == 1
+                    && e[0].labels.contains(&NOTE_LABEL.to_string())
+                    && e[0].observations == ["Prefer small commits"]
+                    && mm_memory::source::observation_source(
+                        &e[0].properties,
+                        "Prefer small commits",
+                    )
+                    .is_some_and(|s| s.conversation_id.as_deref() == Some("c1"))
+            })
+            .returning(|_| Ok(()));
+
+        let result = remember(
+            &ports_with(mock),
+            RememberCommand {
+                text: "Prefer small commits".to_string(),
+                labels: vec![],
+                entity_name: None,
+                confidence: None,
+                source: Some(ObservationSource {
+                    conversation_id: Some("c1".to_string()),
+                    ..Default::default()
+                }),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.entity_name, "note:prefer_small_commits");
+        assert_eq!(result.action, RememberAction::Created);
+    }
+
+    #[tokio::test]
+    async fn test_remember_adds_observation_to_existing() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name()
+            .with(eq("tech:language:rust"))
+            .returning(|name| {
+                Ok(Some(MemoryEntity {
+                    name: name.to_string(),
+                    labels: vec!["Language".to_string()],
+                    ..Default::default()
+                }))
+            });
+        mock.expect_update_entity()
+            .withf(|name, update| {
+                name == "tech:language:rust"
+                    && update
+                        .observations
+                        .as_ref()
+                        .and_then(|o| o.add.clone())
+                        .is_some_and(|a| a == ["Editions are opt-in"])
+                    && update.labels.is_none()
+                    && update
+                        .properties
+                        .as_ref()
+                        .and_then(|p| p.add.clone())
+                        .is_some_and(|p| {
+                            mm_memory::confidence::observation_confidence(&p, "Editions are opt-in")
+                                == 0.7
+                        })
+            })
+            .returning(|_, _| Ok(()));
+
+        let result = remember(
+            &ports_with(mock),
+            RememberCommand {
+                text: "Editions are opt-in".to_string(),
+                labels: vec!["Language".to_string()],
+                entity_name: Some("tech:language:rust".to_string()),
+                confidence: Some(0.7),
+                source: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.action, RememberAction::ObservationAdded);
+    }
+
+    #[tokio::test]
+    async fn test_remember_rejects_empty_text() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().never();
+        let result = remember(
+            &ports_with(mock),
+            RememberCommand {
+                text: "   ".to_string(),
+                labels: vec![],
+                entity_name: None,
+                confidence: None,
+                source: None,
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(CoreError::Validation(_))));
+    }
+}
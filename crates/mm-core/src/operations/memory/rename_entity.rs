@@ -0,0 +1,90 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::MemoryRepository;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct RenameEntityCommand {
+    /// Current entity name
+    pub name: String,
+    /// Name to give the entity; must not be in use
+    pub new_name: String,
+}
+
+pub type RenameEntityResult<E> = CoreResult<(), E>;
+
+/// Rename an entity and rewrite the relationships that reference it
+#[instrument(skip(ports))]
+pub async fn rename_entity<M, G>(
+    ports: &Ports<M, G>,
+    command: RenameEntityCommand,
+) -> RenameEntityResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.name);
+    validate_name!(command.new_name, ports);
+
+    ports
+        .memory_service
+        .rename_entity(&command.name, &command.new_name)
+        .await
+        .map_err(CoreError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use mockall::predicate::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_rename_entity_success() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name()
+            .with(eq("a:task:old"))
+            .returning(|name| {
+                Ok(Some(MemoryEntity {
+                    name: name.to_string(),
+                    ..Default::default()
+                }))
+            });
+        mock.expect_find_entity_by_name()
+            .with(eq("a:task:new"))
+            .returning(|_| Ok(None));
+        mock.expect_rename_entity()
+            .with(eq("a:task:old"), eq("a:task:new"))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let command = RenameEntityCommand {
+            name: "a:task:old".to_string(),
+            new_name: "a:task:new".to_string(),
+        };
+        rename_entity(&ports, command).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rename_entity_empty_name() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_rename_entity().never();
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let command = RenameEntityCommand {
+            name: "a:task:old".to_string(),
+            new_name: String::new(),
+        };
+        let result = rename_entity(&ports, command).await;
+        assert!(matches!(result, Err(CoreError::Validation(_))));
+    }
+}
@@ -0,0 +1,258 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{
+    EntityUpdate, LabelMatchMode, MemoryError, MemoryRepository, ObservationsUpdate,
+    RelationshipDirection, ValidationError, ValidationErrorKind,
+};
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct ReplaceInObservationsCommand {
+    /// Text to look for
+    pub pattern: String,
+    /// Text to put in its place; may use `$1` or `${name}` groups when
+    /// `regex` is set
+    pub replacement: String,
+    /// Treat `pattern` as a regular expression instead of literal text
+    #[serde(default)]
+    pub regex: bool,
+    /// Only change entities with any of these labels
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Only change this project entity and the entities it contains
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Report the changes without applying them; on unless turned off
+    #[serde(default = "default_true")]
+    pub dry_run: bool,
+}
+
+/// One rewritten observation
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct ObservationEdit {
+    pub before: String,
+    pub after: String,
+}
+
+/// Observations rewritten on one entity
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct ObservationChanges {
+    pub name: String,
+    pub edits: Vec<ObservationEdit>,
+}
+
+pub type ReplaceInObservationsResult<E> = CoreResult<Vec<ObservationChanges>, E>;
+
+/// Text replacement compiled from a command
+enum Replacer {
+    Literal(String),
+    Regex(Regex),
+}
+
+impl Replacer {
+    fn replace(&self, text: &str, replacement: &str) -> String {
+        match self {
+            Replacer::Literal(pattern) => text.replace(pattern.as_str(), replacement),
+            Replacer::Regex(re) => re.replace_all(text, replacement).into_owned(),
+        }
+    }
+}
+
+/// Rewrite observation text across the graph
+///
+/// Entities are those with any of `labels` (all when empty), narrowed to a
+/// project's entities when `project` is given. Changes are returned sorted by
+/// entity name and only written when `dry_run` is off.
+#[instrument(skip(ports), fields(regex = command.regex, dry_run = command.dry_run), err)]
+pub async fn replace_in_observations<M, G>(
+    ports: &Ports<M, G>,
+    command: ReplaceInObservationsCommand,
+) -> ReplaceInObservationsResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let invalid = || {
+        CoreError::Validation(ValidationError::from(ValidationErrorKind::InvalidPattern(
+            command.pattern.clone(),
+        )))
+    };
+    if command.pattern.is_empty() {
+        return Err(invalid());
+    }
+    let replacer = if command.regex {
+        Replacer::Regex(Regex::new(&command.pattern).map_err(|_| invalid())?)
+    } else {
+        Replacer::Literal(command.pattern.clone())
+    };
+
+    let service = &ports.memory_service;
+    let mut entities = match &command.project {
+        Some(project) => {
+            validate_name!(project);
+            let Some(project_entity) = service.find_entity_by_name(project).await? else {
+                return Err(CoreError::Memory(MemoryError::entity_not_found(
+                    project.clone(),
+                )));
+            };
+            let mut entities = service
+                .find_related_entities(
+                    project,
+                    Some("contains".to_string()),
+                    Some(RelationshipDirection::Outgoing),
+                    1,
+                )
+                .await?;
+            entities.push(project_entity);
+            entities.retain(|e| {
+                command.labels.is_empty() || e.labels.iter().any(|l| command.labels.contains(l))
+            });
+            entities
+        }
+        None => {
+            service
+                .find_entities_by_labels(&command.labels, LabelMatchMode::Any, None)
+                .await?
+        }
+    };
+    entities.sort_by(|a, b| a.name.cmp(&b.name));
+    entities.dedup_by(|a, b| a.name == b.name);
+
+    let mut changes = Vec::new();
+    for entity in entities {
+        let rewritten: Vec<String> = entity
+            .observations
+            .iter()
+            .map(|o| replacer.replace(o, &command.replacement))
+            .collect();
+        let edits: Vec<ObservationEdit> = entity
+            .observations
+            .iter()
+            .zip(&rewritten)
+            .filter(|(before, after)| before != after)
+            .map(|(before, after)| ObservationEdit {
+                before: before.clone(),
+                after: after.clone(),
+            })
+            .collect();
+        if edits.is_empty() {
+            continue;
+        }
+        if !command.dry_run {
+            let update = EntityUpdate {
+                observations: Some(ObservationsUpdate {
+                    add: None,
+                    remove: None,
+                    set: Some(rewritten),
+                }),
+                ..Default::default()
+            };
+            service.update_entity(&entity.name, &update).await?;
+        }
+        changes.push(ObservationChanges {
+            name: entity.name,
+            edits,
+        });
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    fn command(pattern: &str, replacement: &str, regex: bool) -> ReplaceInObservationsCommand {
+        ReplaceInObservationsCommand {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            regex,
+            labels: vec![],
+            project: None,
+            dry_run: true,
+        }
+    }
+
+    fn ports(dry_run: bool) -> Ports<MockMemoryRepository, mm_git::repository::MockGitRepository> {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_by_labels().returning(|_, _, _| {
+            Ok(vec![
+                MemoryEntity {
+                    name: "b".to_string(),
+                    observations: vec!["uses mm-core".to_string(), "unrelated".to_string()],
+                    ..Default::default()
+                },
+                MemoryEntity {
+                    name: "a".to_string(),
+                    observations: vec!["nothing here".to_string()],
+                    ..Default::default()
+                },
+            ])
+        });
+        if dry_run {
+            mock.expect_update_entity().never();
+        } else {
+            mock.expect_update_entity()
+                .withf(|name, update| {
+                    name == "b"
+                        && update.observations.as_ref().and_then(|o| o.set.clone())
+                            == Some(vec![
+                                "uses middle-core".to_string(),
+                                "unrelated".to_string(),
+                            ])
+                })
+                .times(1)
+                .returning(|_, _| Ok(()));
+        }
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        Ports::noop().with(|p| p.memory_service = Arc::new(service))
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_reports_without_writing() {
+        let changes = replace_in_observations(&ports(true), command("mm-", "middle-", false))
+            .await
+            .unwrap();
+        assert_eq!(
+            changes,
+            [ObservationChanges {
+                name: "b".to_string(),
+                edits: vec![ObservationEdit {
+                    before: "uses mm-core".to_string(),
+                    after: "uses middle-core".to_string(),
+                }],
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_regex_replacement_is_written() {
+        let command = ReplaceInObservationsCommand {
+            dry_run: false,
+            ..command(r"\bmm-(\w+)", "middle-$1", true)
+        };
+        let changes = replace_in_observations(&ports(false), command)
+            .await
+            .unwrap();
+        assert_eq!(changes.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_regex_is_rejected() {
+        let err = replace_in_observations(&ports(true), command("(", "", true))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CoreError::Validation(_)));
+    }
+}
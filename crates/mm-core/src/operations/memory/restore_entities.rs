@@ -0,0 +1,60 @@
+use super::common::handle_batch_result;
+use crate::error::CoreResult;
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::MemoryRepository;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct RestoreEntitiesCommand {
+    pub names: Vec<String>,
+}
+
+pub type RestoreEntitiesResult<E> = CoreResult<(), E>;
+
+/// Take soft-deleted entities out of the trash
+#[instrument(skip(ports), fields(names_count = command.names.len()))]
+pub async fn restore_entities<M, G>(
+    ports: &Ports<M, G>,
+    command: RestoreEntitiesCommand,
+) -> RestoreEntitiesResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    handle_batch_result("names", || {
+        ports.memory_service.restore_entities(&command.names)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CoreError;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_restore_entities_not_in_trash() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|_| Ok(None));
+        mock.expect_update_entity().never();
+        let config = MemoryConfig {
+            soft_delete: true,
+            ..MemoryConfig::default()
+        };
+        let service = MemoryService::new(mock, config);
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let command = RestoreEntitiesCommand {
+            names: vec!["gone".to_string()],
+        };
+        let err = restore_entities(&ports, command).await.unwrap_err();
+        assert!(
+            matches!(err, CoreError::BatchValidation(ref errors) if errors[0].path == "names[0]")
+        );
+    }
+}
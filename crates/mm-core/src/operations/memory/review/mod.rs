@@ -0,0 +1,11 @@
+pub mod types;
+
+mod propose_forget;
+mod resolve;
+
+pub use propose_forget::{ProposeForgetCommand, ProposeForgetResult, propose_forget};
+pub use resolve::{
+    ListForgetProposalsCommand, ListForgetProposalsResult, ResolveForgetProposalCommand,
+    ResolveForgetProposalResult, ReviewDecision, list_forget_proposals, resolve_forget_proposal,
+};
+pub use types::ForgetProposal;
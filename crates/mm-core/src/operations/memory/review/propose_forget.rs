@@ -0,0 +1,163 @@
+use chrono::Utc;
+use mm_git::GitRepository;
+use mm_memory::{EntityUpdate, MemoryError, MemoryRepository, PropertiesUpdate};
+use tracing::instrument;
+
+use super::super::common::handle_batch_result;
+use super::types::{ForgetProposal, proposal_name};
+use crate::error::CoreResult;
+use crate::ports::Ports;
+
+#[derive(Debug, Clone)]
+pub struct ProposeForgetCommand {
+    /// Entity to forget or to remove observations from
+    pub entity_name: String,
+    /// Observations to remove; empty proposes deleting the whole entity
+    pub observations: Vec<String>,
+    /// Why the memory should be forgotten
+    pub reason: Option<String>,
+}
+
+pub type ProposeForgetResult<E> = CoreResult<ForgetProposal, E>;
+
+/// Queue an entity or some of its observations for deletion
+///
+/// Nothing is deleted until a human approves the proposal. Proposing again
+/// for the same entity merges into the pending proposal.
+#[instrument(skip(ports), fields(entity_name = %command.entity_name))]
+pub async fn propose_forget<M, G>(
+    ports: &Ports<M, G>,
+    command: ProposeForgetCommand,
+) -> ProposeForgetResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.entity_name);
+
+    if ports
+        .memory_service
+        .find_entity_by_name(&command.entity_name)
+        .await?
+        .is_none()
+    {
+        return Err(MemoryError::entity_not_found(command.entity_name).into());
+    }
+
+    let name = proposal_name(&command.entity_name);
+    let proposal = ForgetProposal {
+        name: name.clone(),
+        target: command.entity_name,
+        observations: command.observations,
+        reason: command.reason,
+        proposed_by: ports.memory_service.memory_config().agent_name.clone(),
+        proposed_at: Utc::now(),
+    };
+
+    let existing = ports
+        .memory_service
+        .find_entity_by_name(&name)
+        .await?
+        .as_ref()
+        .and_then(ForgetProposal::from_entity);
+
+    match existing {
+        Some(mut pending) => {
+            pending.merge(proposal);
+            let update = EntityUpdate {
+                properties: Some(PropertiesUpdate {
+                    add: None,
+                    remove: None,
+                    set: Some(pending.properties()),
+                }),
+                ..Default::default()
+            };
+            ports.memory_service.update_entity(&name, &update).await?;
+            Ok(pending)
+        }
+        None => {
+            let entity = proposal.to_entity();
+            handle_batch_result("entities", || {
+                ports
+                    .memory_service
+                    .create_entities(std::slice::from_ref(&entity))
+            })
+            .await?;
+            Ok(proposal)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::CoreError;
+    use mm_memory::labels::FORGET_PROPOSAL_LABEL;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use mockall::predicate::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_propose_forget_queues_proposal() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name()
+            .with(eq("tech:tool:old"))
+            .returning(|name| {
+                Ok(Some(MemoryEntity {
+                    name: name.to_string(),
+                    ..Default::default()
+                }))
+            });
+        mock.expect_find_entity_by_name()
+            .with(eq("review:forget:tech:tool:old"))
+            .returning(|_| Ok(None));
+        mock.expect_create_entities()
+            .withf(|e| {
+                e.len() == 1
+                    && e[0].labels.contains(&FORGET_PROPOSAL_LABEL.to_string())
+                    && ForgetProposal::from_entity(&e[0])
+                        .is_some_and(|p| p.target == "tech:tool:old" && p.forgets_entity())
+            })
+            .returning(|_| Ok(()));
+        mock.expect_delete_entities().never();
+
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let proposal = propose_forget(
+            &ports,
+            ProposeForgetCommand {
+                entity_name: "tech:tool:old".to_string(),
+                observations: vec![],
+                reason: Some("replaced".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(proposal.name, "review:forget:tech:tool:old");
+    }
+
+    #[tokio::test]
+    async fn test_propose_forget_missing_entity() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|_| Ok(None));
+        mock.expect_create_entities().never();
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let result = propose_forget(
+            &ports,
+            ProposeForgetCommand {
+                entity_name: "missing".to_string(),
+                observations: vec![],
+                reason: None,
+            },
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(CoreError::Memory(MemoryError::EntityNotFound(_)))
+        ));
+    }
+}
@@ -0,0 +1,230 @@
+use mm_git::GitRepository;
+use mm_memory::labels::FORGET_PROPOSAL_LABEL;
+use mm_memory::{LabelMatchMode, MemoryError, MemoryRepository};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use super::super::common::handle_batch_result;
+use super::types::ForgetProposal;
+use crate::error::CoreResult;
+use crate::ports::Ports;
+
+#[derive(Debug, Clone, Default)]
+pub struct ListForgetProposalsCommand {}
+
+pub type ListForgetProposalsResult<E> = CoreResult<Vec<ForgetProposal>, E>;
+
+/// List pending forget proposals, oldest first
+#[instrument(skip(ports))]
+pub async fn list_forget_proposals<M, G>(
+    ports: &Ports<M, G>,
+    _command: ListForgetProposalsCommand,
+) -> ListForgetProposalsResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+{
+    let entities = ports
+        .memory_service
+        .find_entities_by_labels(
+            &[FORGET_PROPOSAL_LABEL.to_string()],
+            LabelMatchMode::All,
+            None,
+        )
+        .await?;
+    let mut proposals: Vec<_> = entities
+        .iter()
+        .filter_map(ForgetProposal::from_entity)
+        .collect();
+    proposals.sort_by_key(|p| p.proposed_at);
+    Ok(proposals)
+}
+
+/// Decision taken on a forget proposal
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    /// Forget the memory as proposed
+    Approve,
+    /// Keep the memory and discard the proposal
+    Reject,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolveForgetProposalCommand {
+    /// Name of the proposal entity
+    pub name: String,
+    pub decision: ReviewDecision,
+}
+
+pub type ResolveForgetProposalResult<E> = CoreResult<ForgetProposal, E>;
+
+/// Approve or reject a pending forget proposal
+///
+/// Approving deletes the target entity or removes the proposed observations
+/// from it. Either way the proposal leaves the queue.
+#[instrument(skip(ports), fields(name = %command.name))]
+pub async fn resolve_forget_proposal<M, G>(
+    ports: &Ports<M, G>,
+    command: ResolveForgetProposalCommand,
+) -> ResolveForgetProposalResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.name);
+
+    let proposal = ports
+        .memory_service
+        .find_entity_by_name(&command.name)
+        .await?
+        .as_ref()
+        .and_then(ForgetProposal::from_entity)
+        .ok_or_else(|| MemoryError::entity_not_found(command.name.clone()))?;
+
+    if command.decision == ReviewDecision::Approve {
+        if proposal.forgets_entity() {
+            handle_batch_result("entities", || {
+                ports
+                    .memory_service
+                    .delete_entities(std::slice::from_ref(&proposal.target))
+            })
+            .await?;
+        } else {
+            ports
+                .memory_service
+                .remove_observations(&proposal.target, &proposal.observations)
+                .await?;
+        }
+    }
+
+    handle_batch_result("entities", || {
+        ports
+            .memory_service
+            .delete_entities(std::slice::from_ref(&proposal.name))
+    })
+    .await?;
+
+    Ok(proposal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::types::proposal_name;
+    use super::*;
+    use chrono::Utc;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use mockall::predicate::*;
+    use std::sync::Arc;
+
+    fn proposal(observations: &[&str]) -> ForgetProposal {
+        ForgetProposal {
+            name: proposal_name("tech:tool:old"),
+            target: "tech:tool:old".to_string(),
+            observations: observations.iter().map(|o| o.to_string()).collect(),
+            reason: None,
+            proposed_by: "agent".to_string(),
+            proposed_at: Utc::now(),
+        }
+    }
+
+    fn mock_with(proposal: ForgetProposal) -> MockMemoryRepository {
+        let entity = proposal.to_entity();
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name()
+            .with(eq("review:forget:tech:tool:old"))
+            .returning(move |_| Ok(Some(entity.clone())));
+        mock.expect_delete_entities()
+            .withf(|names| names == ["review:forget:tech:tool:old"])
+            .times(1)
+            .returning(|_| Ok(()));
+        mock
+    }
+
+    fn ports_with(
+        mock: MockMemoryRepository,
+    ) -> Ports<MockMemoryRepository, mm_git::repository::MockGitRepository> {
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        Ports::noop().with(|p| p.memory_service = Arc::new(service))
+    }
+
+    #[tokio::test]
+    async fn test_approve_deletes_entity() {
+        let mut mock = mock_with(proposal(&[]));
+        mock.expect_delete_entities()
+            .withf(|names| names == ["tech:tool:old"])
+            .times(1)
+            .returning(|_| Ok(()));
+
+        resolve_forget_proposal(
+            &ports_with(mock),
+            ResolveForgetProposalCommand {
+                name: proposal_name("tech:tool:old"),
+                decision: ReviewDecision::Approve,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_approve_removes_observations() {
+        let mut mock = mock_with(proposal(&["stale"]));
+        mock.expect_remove_observations()
+            .withf(|name, obs| name == "tech:tool:old" && obs == ["stale"])
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        resolve_forget_proposal(
+            &ports_with(mock),
+            ResolveForgetProposalCommand {
+                name: proposal_name("tech:tool:old"),
+                decision: ReviewDecision::Approve,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reject_only_drops_proposal() {
+        let mut mock = mock_with(proposal(&["stale"]));
+        mock.expect_remove_observations().never();
+
+        resolve_forget_proposal(
+            &ports_with(mock),
+            ResolveForgetProposalCommand {
+                name: proposal_name("tech:tool:old"),
+                decision: ReviewDecision::Reject,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_forget_proposals() {
+        let mut older = proposal(&[]);
+        older.proposed_at = Utc::now() - chrono::Duration::hours(1);
+        older.name = "review:forget:older".to_string();
+        let newer = proposal(&["stale"]);
+        let entities = vec![newer.to_entity(), older.to_entity()];
+
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_by_labels()
+            .returning(move |_, _, _| Ok(entities.clone()));
+
+        let proposals =
+            list_forget_proposals(&ports_with(mock), ListForgetProposalsCommand::default())
+                .await
+                .unwrap();
+        let names: Vec<_> = proposals.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["review:forget:older", "review:forget:tech:tool:old"]
+        );
+    }
+}
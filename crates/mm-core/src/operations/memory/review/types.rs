@@ -0,0 +1,164 @@
+use chrono::{DateTime, Utc};
+use mm_memory::labels::FORGET_PROPOSAL_LABEL;
+use mm_memory::{MemoryEntity, MemoryValue};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Name of the proposal entity queued for `target`
+///
+/// There is at most one pending proposal per target; proposing again merges
+/// into it.
+pub fn proposal_name(target: &str) -> String {
+    format!("review:forget:{}", target)
+}
+
+/// Request to forget an entity or some of its observations, awaiting review
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct ForgetProposal {
+    /// Name of the proposal entity
+    pub name: String,
+    /// Entity that would be affected
+    pub target: String,
+    /// Observations to remove; empty means the whole entity is deleted
+    pub observations: Vec<String>,
+    /// Why the memory should be forgotten
+    pub reason: Option<String>,
+    /// Agent that made the proposal
+    pub proposed_by: String,
+    /// When the proposal was made
+    #[schemars(with = "String")]
+    pub proposed_at: DateTime<Utc>,
+}
+
+impl ForgetProposal {
+    /// Whether approving the proposal deletes the whole entity
+    pub fn forgets_entity(&self) -> bool {
+        self.observations.is_empty()
+    }
+
+    /// Merge a newer proposal for the same target into this one
+    pub fn merge(&mut self, newer: ForgetProposal) {
+        if self.forgets_entity() || newer.forgets_entity() {
+            self.observations.clear();
+        } else {
+            for o in newer.observations {
+                if !self.observations.contains(&o) {
+                    self.observations.push(o);
+                }
+            }
+        }
+        if newer.reason.is_some() {
+            self.reason = newer.reason;
+        }
+        self.proposed_by = newer.proposed_by;
+        self.proposed_at = newer.proposed_at;
+    }
+
+    /// Read a proposal from its entity
+    ///
+    /// Returns `None` if the entity is not a proposal or lacks a target.
+    pub fn from_entity(entity: &MemoryEntity) -> Option<Self> {
+        if !entity.labels.iter().any(|l| l == FORGET_PROPOSAL_LABEL) {
+            return None;
+        }
+        let props = &entity.properties;
+        let string = |key: &str| match props.get(key) {
+            Some(MemoryValue::String(s)) => Some(s.clone()),
+            _ => None,
+        };
+        let observations = match props.get("observations_to_forget") {
+            Some(MemoryValue::List(list)) => list.clone(),
+            _ => Vec::new(),
+        };
+        let proposed_at = match props.get("proposed_at") {
+            Some(MemoryValue::DateTime(dt)) => dt.with_timezone(&Utc),
+            Some(MemoryValue::String(s)) => DateTime::parse_from_rfc3339(s)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+            _ => Utc::now(),
+        };
+        Some(Self {
+            name: entity.name.clone(),
+            target: string("target")?,
+            observations,
+            reason: string("reason"),
+            proposed_by: string("proposed_by").unwrap_or_default(),
+            proposed_at,
+        })
+    }
+
+    /// Properties stored on the proposal entity
+    pub fn properties(&self) -> HashMap<String, MemoryValue> {
+        let mut map = HashMap::new();
+        map.insert(
+            "target".to_string(),
+            MemoryValue::String(self.target.clone()),
+        );
+        map.insert(
+            "observations_to_forget".to_string(),
+            MemoryValue::List(self.observations.clone()),
+        );
+        if let Some(reason) = &self.reason {
+            map.insert("reason".to_string(), MemoryValue::String(reason.clone()));
+        }
+        map.insert(
+            "proposed_by".to_string(),
+            MemoryValue::String(self.proposed_by.clone()),
+        );
+        map.insert(
+            "proposed_at".to_string(),
+            MemoryValue::DateTime(self.proposed_at.into()),
+        );
+        map
+    }
+
+    /// Entity representing the proposal in the review queue
+    pub fn to_entity(&self) -> MemoryEntity {
+        MemoryEntity {
+            name: self.name.clone(),
+            labels: vec![FORGET_PROPOSAL_LABEL.to_string()],
+            properties: self.properties(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proposal(observations: &[&str]) -> ForgetProposal {
+        ForgetProposal {
+            name: proposal_name("tech:tool:old"),
+            target: "tech:tool:old".to_string(),
+            observations: observations.iter().map(|o| o.to_string()).collect(),
+            reason: None,
+            proposed_by: "agent".to_string(),
+            proposed_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_entity_round_trip() {
+        let mut original = proposal(&["stale"]);
+        original.reason = Some("outdated".to_string());
+        let restored = ForgetProposal::from_entity(&original.to_entity()).unwrap();
+        assert_eq!(restored.target, original.target);
+        assert_eq!(restored.observations, original.observations);
+        assert_eq!(restored.reason, original.reason);
+        assert!(ForgetProposal::from_entity(&MemoryEntity::default()).is_none());
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut existing = proposal(&["a"]);
+        existing.merge(proposal(&["b", "a"]));
+        assert_eq!(existing.observations, ["a", "b"]);
+
+        existing.merge(proposal(&[]));
+        assert!(existing.forgets_entity());
+        existing.merge(proposal(&["c"]));
+        assert!(existing.forgets_entity());
+    }
+}
@@ -0,0 +1,93 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{MemoryRepository, ValidationError, ValidationErrorKind};
+use mm_utils::slugify;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct SlugifyNameCommand {
+    /// Human-readable title, e.g. `Fix the build`
+    pub title: String,
+    /// Label of the entity to name
+    pub label: String,
+    /// Project filling `{{project}}`, `default_project` when omitted
+    #[serde(default)]
+    pub project_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct SlugifyNameResult {
+    pub name: String,
+}
+
+pub type SlugifyNameResultType<E> = CoreResult<SlugifyNameResult, E>;
+
+/// Suggest an entity name for a title using the configured `name_template`
+///
+/// Nothing is looked up, so the name may already be taken.
+#[instrument(skip(ports), fields(title = %command.title))]
+pub async fn slugify_name<M, G>(
+    ports: &Ports<M, G>,
+    command: SlugifyNameCommand,
+) -> SlugifyNameResultType<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    if slugify(&command.title).is_empty() {
+        return Err(CoreError::Validation(ValidationError::from(
+            ValidationErrorKind::EmptyEntityName,
+        )));
+    }
+    let name = ports.memory_service.memory_config().entity_name(
+        command.project_name.as_deref(),
+        &command.label,
+        &command.title,
+    );
+    Ok(SlugifyNameResult { name })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_git::repository::MockGitRepository;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    fn ports(config: MemoryConfig) -> Ports<MockMemoryRepository, MockGitRepository> {
+        let service = MemoryService::new(MockMemoryRepository::new(), config);
+        Ports::noop().with(|p| p.memory_service = Arc::new(service))
+    }
+
+    #[tokio::test]
+    async fn test_slugify_name_uses_template() {
+        let ports = ports(MemoryConfig {
+            default_project: Some("mm".to_string()),
+            name_template: "{{project}}:{{label}}:{{slug}}".to_string(),
+            ..MemoryConfig::default()
+        });
+        let command = SlugifyNameCommand {
+            title: "Use SurrealDB for storage".to_string(),
+            label: "Decision".to_string(),
+            project_name: None,
+        };
+        let result = slugify_name(&ports, command).await.unwrap();
+        assert_eq!(result.name, "mm:decision:use_surreal_db_for_storage");
+    }
+
+    #[tokio::test]
+    async fn test_slugify_name_rejects_blank_title() {
+        let command = SlugifyNameCommand {
+            title: " ?! ".to_string(),
+            label: "Task".to_string(),
+            project_name: None,
+        };
+        let result = slugify_name(&ports(MemoryConfig::default()), command).await;
+        assert!(matches!(result, Err(CoreError::Validation(_))));
+    }
+}
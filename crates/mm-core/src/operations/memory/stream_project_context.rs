@@ -0,0 +1,103 @@
+use mm_git::GitRepository;
+use mm_memory::MemoryRepository;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use super::get_project_context::{GetProjectContextCommand, ProjectFilter, get_project_context};
+use super::projects::ProjectContextChunk;
+use crate::error::CoreResult;
+use crate::ports::Ports;
+
+#[derive(Debug, Clone)]
+pub struct StreamProjectContextCommand {
+    /// Filter to use for finding the project
+    pub filter: ProjectFilter,
+    /// Leave out related entities carrying any of these labels
+    pub exclude_labels: Vec<String>,
+}
+
+/// Project context split into prioritized chunks
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StreamProjectContextResult {
+    /// Project core first, then tasks, technologies and everything else
+    pub chunks: Vec<ProjectContextChunk>,
+}
+
+pub type StreamProjectContextResultType<E> = CoreResult<StreamProjectContextResult, E>;
+
+/// Retrieve project context as chunks ordered by importance
+///
+/// Clients with little room can stop reading after the first chunks.
+#[instrument(skip(ports))]
+pub async fn stream_project_context<M, G>(
+    ports: &Ports<M, G>,
+    command: StreamProjectContextCommand,
+) -> StreamProjectContextResultType<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let result = get_project_context(
+        ports,
+        GetProjectContextCommand {
+            filter: command.filter,
+            exclude_labels: command.exclude_labels,
+        },
+    )
+    .await?;
+    Ok(StreamProjectContextResult {
+        chunks: result.context.into_chunks(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::labels::{PROJECT_LABEL, TASK_LABEL};
+    use mm_memory::{
+        EntitySnapshot, MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository,
+    };
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_stream_project_context_puts_project_first() {
+        let project = MemoryEntity {
+            name: "project:x".to_string(),
+            labels: vec![PROJECT_LABEL.to_string()],
+            ..Default::default()
+        };
+        let task = MemoryEntity {
+            name: "task:1".to_string(),
+            labels: vec![TASK_LABEL.to_string()],
+            ..Default::default()
+        };
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_snapshot().returning(move |_, _| {
+            Ok(Some(EntitySnapshot {
+                entity: project.clone(),
+                related: vec![vec![task.clone()], vec![], vec![], vec![], vec![]],
+            }))
+        });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let result = stream_project_context(
+            &ports,
+            StreamProjectContextCommand {
+                filter: ProjectFilter::Name("project:x".to_string()),
+                exclude_labels: Vec::new(),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            &result.chunks[..],
+            [
+                ProjectContextChunk::Project { project, .. },
+                ProjectContextChunk::Tasks { tasks },
+            ] if project.name == "project:x" && tasks[0].name == "task:1"
+        ));
+    }
+}
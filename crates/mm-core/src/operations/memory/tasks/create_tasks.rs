@@ -1,10 +1,13 @@
-use super::super::common::handle_batch_result;
+use super::estimate_calibration::calibrate_project;
 use super::types::TaskProperties;
 use crate::error::{CoreError, CoreResult};
 use crate::ports::Ports;
 use mm_git::GitRepository;
 use mm_memory::MemoryRepository;
-use mm_memory::{MemoryEntity, MemoryRelationship, ValidationError, ValidationErrorKind};
+use mm_memory::{
+    BatchItemError, EntityKind, GraphBatchErrors, GraphPreview, MemoryEntity, MemoryRelationship,
+    ValidationError, ValidationErrorKind,
+};
 use std::collections::HashMap;
 use tracing::instrument;
 
@@ -19,6 +22,9 @@ pub struct TaskInput {
 pub struct CreateTasksCommand {
     pub tasks: Vec<TaskInput>,
     pub project_name: Option<String>,
+    /// Set `suggested_estimate_hours` on estimated tasks from the estimate
+    /// bias of the project's finished tasks
+    pub calibrate_estimates: bool,
 }
 
 pub type CreateTasksResult<E> = CoreResult<(), E>;
@@ -34,95 +40,208 @@ where
     M::Error: std::error::Error + Send + Sync + 'static,
     G::Error: std::error::Error + Send + Sync + 'static,
 {
-    let project_name = match command
-        .project_name
-        .or_else(|| ports.memory_service.memory_config().default_project.clone())
+    let project_name = resolve_project(ports, command.project_name)?;
+    let mut tasks = command.tasks;
+    check_task_inputs(ports, &tasks).await?;
+
+    if command.calibrate_estimates
+        && tasks
+            .iter()
+            .any(|t| t.task.properties.estimate_hours.is_some())
     {
-        Some(p) => p,
-        None => return Err(CoreError::MissingProject),
-    };
+        let calibration = calibrate_project(ports, &project_name).await?;
+        for input in &mut tasks {
+            let properties = &mut input.task.properties;
+            properties.suggested_estimate_hours = calibration.suggest(properties);
+        }
+    }
+
+    // The tasks and the relationships to them are written as one batch so a
+    // rejected relationship cannot leave orphaned tasks behind
+    let batch = TaskBatch::new(&project_name, &tasks);
+    let errors = ports
+        .memory_service
+        .typed::<TaskProperties>()
+        .create_with_relationships(Some(&project_name), &batch.entities, &batch.relationships)
+        .await?;
+    batch.check(&tasks, errors)
+}
+
+/// Run the checks of [`create_tasks`] without writing anything
+///
+/// Fails like [`create_tasks`] would; otherwise returns the tasks and
+/// relationships as they would be stored. Estimates are not calibrated.
+pub(crate) async fn preview_tasks<M, G>(
+    ports: &Ports<M, G>,
+    project_name: Option<String>,
+    tasks: &[TaskInput],
+) -> CoreResult<GraphPreview, M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let project_name = resolve_project(ports, project_name)?;
+    check_task_inputs(ports, tasks).await?;
+    let batch = TaskBatch::new(&project_name, tasks);
+    let mut preview = ports
+        .memory_service
+        .typed::<TaskProperties>()
+        .preview_with_relationships(Some(&project_name), &batch.entities, &batch.relationships)
+        .await?;
+    batch.check(tasks, std::mem::take(&mut preview.errors))?;
+    Ok(preview)
+}
 
-    let tasks = command.tasks;
-    let new_names: std::collections::HashSet<String> =
-        tasks.iter().map(|t| t.task.name.clone()).collect();
+fn resolve_project<M, G>(
+    ports: &Ports<M, G>,
+    project_name: Option<String>,
+) -> CoreResult<String, M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+{
+    project_name
+        .or_else(|| ports.memory_service.memory_config().default_project.clone())
+        .ok_or(CoreError::MissingProject)
+}
+
+/// Validate property values and dependencies
+async fn check_task_inputs<M, G>(
+    ports: &Ports<M, G>,
+    tasks: &[TaskInput],
+) -> CoreResult<(), M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let new_names: std::collections::HashSet<&String> =
+        tasks.iter().map(|t| &t.task.name).collect();
 
-    // Validate dependencies
     let mut validation_errors = Vec::new();
-    for task in &tasks {
-        if task.depends_on.iter().any(|d| d == &task.task.name) {
-            validation_errors.push((
-                task.task.name.clone(),
-                ValidationError(vec![ValidationErrorKind::SelfDependency(
-                    task.task.name.clone(),
-                )]),
-            ));
+    for (index, task) in tasks.iter().enumerate() {
+        let name = &task.task.name;
+        let errs = TaskProperties::validate(&task.task.properties.clone().into());
+        if !errs.is_empty() {
+            validation_errors
+                .push(BatchItemError::new(index, name, ValidationError(errs)).in_field("tasks"));
         }
-        for dep in &task.depends_on {
-            if dep != &task.task.name
-                && !new_names.contains(dep)
+        for (dep_index, dep) in task.depends_on.iter().enumerate() {
+            let kind = if dep == name {
+                ValidationErrorKind::SelfDependency(name.clone())
+            } else if !new_names.contains(dep)
                 && ports
                     .memory_service
                     .find_entity_by_name(dep)
                     .await?
                     .is_none()
             {
-                validation_errors.push((
-                    task.task.name.clone(),
-                    ValidationError(vec![ValidationErrorKind::DependencyNotFound(dep.clone())]),
-                ));
-            }
+                ValidationErrorKind::DependencyNotFound(dep.clone())
+            } else {
+                continue;
+            };
+            validation_errors.push(
+                BatchItemError::new(index, name, kind.into())
+                    .in_field("tasks")
+                    .at(&format!(".depends_on[{dep_index}]")),
+            );
         }
     }
     if !validation_errors.is_empty() {
         return Err(CoreError::BatchValidation(validation_errors));
     }
+    Ok(())
+}
 
-    // Create the task entities
-    let entities: Vec<MemoryEntity<TaskProperties>> =
-        tasks.iter().map(|t| t.task.clone()).collect();
-    handle_batch_result(|| ports.memory_service.create_entities_typed(&entities)).await?;
-
-    let mut relationships: Vec<MemoryRelationship> = Vec::new();
-    for task in &tasks {
-        relationships.push(MemoryRelationship {
-            from: project_name.clone(),
-            to: task.task.name.clone(),
-            name: "contains".to_string(),
-            properties: HashMap::default(),
-        });
+/// Tasks with the `contains` and `depends_on` relationships created along
+/// with them
+struct TaskBatch {
+    entities: Vec<MemoryEntity<TaskProperties>>,
+    relationships: Vec<MemoryRelationship>,
+    /// Task index and location within the task each relationship comes from
+    origins: Vec<(usize, String)>,
+}
 
-        for dependency in &task.depends_on {
+impl TaskBatch {
+    fn new(project_name: &str, tasks: &[TaskInput]) -> Self {
+        let entities = tasks.iter().map(|t| t.task.clone()).collect();
+        let mut relationships = Vec::new();
+        let mut origins = Vec::new();
+        for (index, task) in tasks.iter().enumerate() {
             relationships.push(MemoryRelationship {
-                from: task.task.name.clone(),
-                to: dependency.clone(),
-                name: "depends_on".to_string(),
+                from: project_name.to_string(),
+                to: task.task.name.clone(),
+                name: "contains".to_string(),
                 properties: HashMap::default(),
             });
+            origins.push((index, String::new()));
+
+            for (dep_index, dependency) in task.depends_on.iter().enumerate() {
+                relationships.push(MemoryRelationship {
+                    from: task.task.name.clone(),
+                    to: dependency.clone(),
+                    name: "depends_on".to_string(),
+                    properties: HashMap::default(),
+                });
+                origins.push((index, format!(".depends_on[{dep_index}]")));
+            }
+        }
+        Self {
+            entities,
+            relationships,
+            origins,
         }
     }
 
-    handle_batch_result(|| ports.memory_service.create_relationships(&relationships)).await?;
-
-    Ok(())
+    /// Fail with `errors`, located within the tasks they come from
+    fn check<E>(&self, tasks: &[TaskInput], errors: GraphBatchErrors) -> CoreResult<(), E>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        if errors.is_empty() {
+            return Ok(());
+        }
+        let relationship_errors = errors.relationships.into_iter().map(|e| {
+            let (index, location) = &self.origins[e.index];
+            BatchItemError::new(*index, &tasks[*index].task.name, e.error).at(location)
+        });
+        Err(CoreError::BatchValidation(
+            errors
+                .entities
+                .into_iter()
+                .chain(relationship_errors)
+                .map(|e| e.in_field("tasks"))
+                .collect(),
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::operations::memory::tasks::TaskStatus;
     use mm_memory::labels::TASK_LABEL;
-    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository, ValidationErrorKind};
+    use mm_memory::{
+        MemoryConfig, MemoryService, MemoryValue, MockMemoryRepository, ValidationErrorKind,
+    };
     use std::collections::HashSet;
     use std::sync::Arc;
 
     #[tokio::test]
     async fn test_create_tasks_success() {
         let mut mock = MockMemoryRepository::new();
-        mock.expect_create_entities()
-            .withf(|ents| ents.len() == 1 && ents[0].name == "task:1")
-            .returning(|_| Ok(()));
-        mock.expect_create_relationships()
-            .withf(|rels| rels.len() == 1 && rels[0].name == "contains")
-            .returning(|_| Ok(()));
+        mock.expect_create_graph()
+            .withf(|ents, rels| {
+                ents.len() == 1
+                    && ents[0].name == "task:1"
+                    && rels.len() == 1
+                    && rels[0].name == "contains"
+            })
+            .returning(|_, _| Ok(()));
 
         let service = MemoryService::new(
             mock,
@@ -147,6 +266,7 @@ mod tests {
                 depends_on: Vec::new(),
             }],
             project_name: None,
+            calibrate_estimates: false,
         };
 
         let res = create_tasks(&ports, cmd).await;
@@ -165,12 +285,11 @@ mod tests {
                     ..Default::default()
                 }))
             });
-        mock.expect_create_entities()
-            .withf(|ents| ents.len() == 1 && ents[0].name == "task:2")
-            .returning(|_| Ok(()));
-        mock.expect_create_relationships()
-            .withf(|rels| {
-                rels.len() == 2
+        mock.expect_create_graph()
+            .withf(|ents, rels| {
+                ents.len() == 1
+                    && ents[0].name == "task:2"
+                    && rels.len() == 2
                     && rels
                         .iter()
                         .any(|r| r.from == "proj" && r.to == "task:2" && r.name == "contains")
@@ -178,7 +297,7 @@ mod tests {
                         .iter()
                         .any(|r| r.from == "task:2" && r.to == "task:1" && r.name == "depends_on")
             })
-            .returning(|_| Ok(()));
+            .returning(|_, _| Ok(()));
 
         let service = MemoryService::new(
             mock,
@@ -203,6 +322,7 @@ mod tests {
                 depends_on: vec!["task:1".into()],
             }],
             project_name: None,
+            calibrate_estimates: false,
         };
 
         let res = create_tasks(&ports, cmd).await;
@@ -212,8 +332,7 @@ mod tests {
     #[tokio::test]
     async fn test_create_tasks_missing_project() {
         let mut mock = MockMemoryRepository::new();
-        mock.expect_create_entities().never();
-        mock.expect_create_relationships().never();
+        mock.expect_create_graph().never();
 
         let service = MemoryService::new(mock, MemoryConfig::default());
         let ports = Ports::noop().with(|p| {
@@ -230,6 +349,7 @@ mod tests {
                 depends_on: Vec::new(),
             }],
             project_name: None,
+            calibrate_estimates: false,
         };
 
         let res = create_tasks(&ports, cmd).await;
@@ -239,8 +359,7 @@ mod tests {
     #[tokio::test]
     async fn test_create_tasks_empty_name() {
         let mut mock = MockMemoryRepository::new();
-        mock.expect_create_entities().never();
-        mock.expect_create_relationships().never();
+        mock.expect_create_graph().never();
 
         let service = MemoryService::new(
             mock,
@@ -263,21 +382,21 @@ mod tests {
                 depends_on: Vec::new(),
             }],
             project_name: None,
+            calibrate_estimates: false,
         };
 
         let res = create_tasks(&ports, cmd).await;
         assert!(matches!(
             res,
             Err(CoreError::BatchValidation(ref errs))
-                if errs.iter().any(|(_, e)| e.0.contains(&ValidationErrorKind::EmptyEntityName))
+                if errs.iter().any(|BatchItemError { error: e, .. }| e.0.contains(&ValidationErrorKind::EmptyEntityName))
         ));
     }
 
     #[tokio::test]
     async fn test_create_tasks_self_dependency() {
         let mut mock = MockMemoryRepository::new();
-        mock.expect_create_entities().never();
-        mock.expect_create_relationships().never();
+        mock.expect_create_graph().never();
 
         let service = MemoryService::new(
             mock,
@@ -302,14 +421,17 @@ mod tests {
                 depends_on: vec!["task:1".into()],
             }],
             project_name: None,
+            calibrate_estimates: false,
         };
 
         let res = create_tasks(&ports, cmd).await;
         assert!(
             matches!(res, Err(CoreError::BatchValidation(ref errs)) if errs
             .iter()
-            .any(|(n, e)| {
-                n == "task:1" && e.0.iter().any(|k| matches!(k, ValidationErrorKind::SelfDependency(_)))
+            .any(|BatchItemError { name: n, path, error: e, .. }| {
+                n == "task:1"
+                    && path == "tasks[0].depends_on[0]"
+                    && e.0.iter().any(|k| matches!(k, ValidationErrorKind::SelfDependency(_)))
             }))
         );
     }
@@ -326,16 +448,12 @@ mod tests {
                     ..Default::default()
                 }))
             });
-        mock.expect_create_entities()
-            .withf(|ents| {
+        mock.expect_create_graph()
+            .withf(|ents, rels| {
                 ents.len() == 2
                     && ents.iter().any(|e| e.name == "task:1")
                     && ents.iter().any(|e| e.name == "task:2")
-            })
-            .returning(|_| Ok(()));
-        mock.expect_create_relationships()
-            .withf(|rels| {
-                rels.len() == 5
+                    && rels.len() == 5
                     && rels
                         .iter()
                         .any(|r| r.from == "proj" && r.to == "task:1" && r.name == "contains")
@@ -352,7 +470,7 @@ mod tests {
                         r.from == "task:2" && r.to == "other:task" && r.name == "depends_on"
                     })
             })
-            .returning(|_| Ok(()));
+            .returning(|_, _| Ok(()));
 
         let service = MemoryService::new(
             mock,
@@ -387,6 +505,7 @@ mod tests {
                 },
             ],
             project_name: None,
+            calibrate_estimates: false,
         };
 
         let res = create_tasks(&ports, cmd).await;
@@ -399,8 +518,7 @@ mod tests {
         mock.expect_find_entity_by_name()
             .with(mockall::predicate::eq("missing:task"))
             .returning(|_| Ok(None));
-        mock.expect_create_entities().never();
-        mock.expect_create_relationships().never();
+        mock.expect_create_graph().never();
 
         let service = MemoryService::new(
             mock,
@@ -425,15 +543,119 @@ mod tests {
                 depends_on: vec!["missing:task".into()],
             }],
             project_name: None,
+            calibrate_estimates: false,
         };
 
         let res = create_tasks(&ports, cmd).await;
         assert!(matches!(
             res,
             Err(CoreError::BatchValidation(ref errs))
-                if errs.iter().any(|(n, e)| {
+                if errs.iter().any(|BatchItemError { name: n, error: e, .. }| {
                     n == "task:1" && e.0.contains(&ValidationErrorKind::DependencyNotFound("missing:task".into()))
                 })
         ));
     }
+
+    #[tokio::test]
+    async fn test_rejected_relationship_writes_nothing() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_create_graph().never();
+
+        let service = MemoryService::new(
+            mock,
+            MemoryConfig {
+                default_project: Some("proj".into()),
+                ..MemoryConfig::default()
+            },
+        );
+        let ports = Ports::noop().with(|p| {
+            p.memory_service = Arc::new(service);
+        });
+
+        let task = |name: &str| MemoryEntity::<TaskProperties> {
+            name: name.into(),
+            labels: vec![TASK_LABEL.to_string()],
+            ..Default::default()
+        };
+        let cmd = CreateTasksCommand {
+            tasks: vec![
+                TaskInput {
+                    task: task("task:1"),
+                    depends_on: Vec::new(),
+                },
+                TaskInput {
+                    task: task("task:2"),
+                    depends_on: vec!["task:1".into(), "task:1".into()],
+                },
+            ],
+            project_name: None,
+            calibrate_estimates: false,
+        };
+
+        let res = create_tasks(&ports, cmd).await;
+        assert!(matches!(
+            res,
+            Err(CoreError::BatchValidation(ref errs))
+                if matches!(&errs[..], [BatchItemError { index: 1, path, .. }] if path == "tasks[1].depends_on[1]")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_tasks_calibrates_estimates() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_tasks()
+            .withf(|filter| filter.project == "proj")
+            .returning(|_| {
+                Ok(vec![MemoryEntity {
+                    name: "task:old".into(),
+                    labels: vec![TASK_LABEL.to_string()],
+                    properties: TaskProperties {
+                        status: TaskStatus::Done,
+                        estimate_hours: Some(2.0),
+                        actual_hours: Some(3.0),
+                        ..Default::default()
+                    }
+                    .into(),
+                    ..Default::default()
+                }])
+            });
+        mock.expect_create_graph()
+            .withf(|ents, _| {
+                ents.len() == 1
+                    && ents[0].properties.get("suggested_estimate_hours")
+                        == Some(&MemoryValue::Float(6.0))
+            })
+            .returning(|_, _| Ok(()));
+
+        let service = MemoryService::new(
+            mock,
+            MemoryConfig {
+                default_project: Some("proj".into()),
+                ..MemoryConfig::default()
+            },
+        );
+        let ports = Ports::noop().with(|p| {
+            p.memory_service = Arc::new(service);
+        });
+
+        let cmd = CreateTasksCommand {
+            tasks: vec![TaskInput {
+                task: MemoryEntity::<TaskProperties> {
+                    name: "task:new".into(),
+                    labels: vec![TASK_LABEL.to_string()],
+                    properties: TaskProperties {
+                        estimate_hours: Some(4.0),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                depends_on: Vec::new(),
+            }],
+            project_name: None,
+            calibrate_estimates: true,
+        };
+
+        let res = create_tasks(&ports, cmd).await;
+        assert!(res.is_ok());
+    }
 }
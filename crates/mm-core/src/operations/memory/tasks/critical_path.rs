@@ -0,0 +1,282 @@
+use super::task_graph::{TaskGraph, TaskGraphCommand, TaskNode, task_graph};
+use super::types::TaskStatus;
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{MemoryRepository, ValidationError, ValidationErrorKind};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use tracing::instrument;
+
+/// Slack below this many hours counts as none
+const SLACK_EPSILON: f64 = 1e-9;
+
+/// Command for computing the critical path of a project
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct CriticalPathCommand {
+    /// Project whose tasks to schedule; the default project when unset
+    pub project_name: Option<String>,
+}
+
+/// Schedule of one task, in hours from the start of the project
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct TaskSchedule {
+    pub name: String,
+    /// Remaining effort; zero for finished tasks
+    pub duration_hours: f64,
+    pub earliest_start: f64,
+    pub earliest_finish: f64,
+    pub latest_start: f64,
+    pub latest_finish: f64,
+    /// How long the task can slip without delaying the project
+    pub slack: f64,
+}
+
+/// Longest dependency chain of a project and the slack of every task
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct CriticalPathReport {
+    /// Hours until every task is finished
+    pub total_hours: f64,
+    /// Tasks on the critical path, first to last
+    pub path: Vec<String>,
+    /// Schedule per task, sorted by earliest start then name
+    pub tasks: Vec<TaskSchedule>,
+    /// Open tasks without an estimate, counted as taking no time
+    pub unestimated: Vec<String>,
+}
+
+pub type CriticalPathResult<E> = CoreResult<CriticalPathReport, E>;
+
+/// Remaining effort of a task
+fn duration(task: &TaskNode) -> f64 {
+    match task.status {
+        TaskStatus::Done | TaskStatus::Cancelled => 0.0,
+        _ => task.estimate_hours.unwrap_or(0.0).max(0.0),
+    }
+}
+
+/// Schedule `graph` with the critical path method
+///
+/// Fails when the dependencies contain a cycle.
+pub fn analyze_critical_path(graph: &TaskGraph) -> Result<CriticalPathReport, ValidationError> {
+    let index: BTreeMap<&str, usize> = graph
+        .tasks
+        .iter()
+        .enumerate()
+        .map(|(i, t)| (t.name.as_str(), i))
+        .collect();
+    let n = graph.tasks.len();
+    let mut predecessors = vec![Vec::new(); n];
+    let mut successors = vec![Vec::new(); n];
+    for dependency in &graph.dependencies {
+        if let (Some(&task), Some(&dep)) = (
+            index.get(dependency.task.as_str()),
+            index.get(dependency.depends_on.as_str()),
+        ) {
+            predecessors[task].push(dep);
+            successors[dep].push(task);
+        }
+    }
+
+    // Kahn's algorithm; anything left unvisited is on or behind a cycle
+    let mut pending: Vec<usize> = predecessors.iter().map(Vec::len).collect();
+    let mut queue: VecDeque<usize> = (0..n).filter(|&i| pending[i] == 0).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(i) = queue.pop_front() {
+        order.push(i);
+        for &s in &successors[i] {
+            pending[s] -= 1;
+            if pending[s] == 0 {
+                queue.push_back(s);
+            }
+        }
+    }
+    if order.len() < n {
+        let cycle: Vec<&str> = (0..n)
+            .filter(|&i| pending[i] > 0)
+            .map(|i| graph.tasks[i].name.as_str())
+            .collect();
+        return Err(ValidationError(vec![ValidationErrorKind::DependencyCycle(
+            cycle.join(", "),
+        )]));
+    }
+
+    let durations: Vec<f64> = graph.tasks.iter().map(duration).collect();
+    let mut earliest_start = vec![0.0_f64; n];
+    for &i in &order {
+        earliest_start[i] = predecessors[i]
+            .iter()
+            .map(|&p| earliest_start[p] + durations[p])
+            .fold(0.0, f64::max);
+    }
+    let total_hours = (0..n)
+        .map(|i| earliest_start[i] + durations[i])
+        .fold(0.0, f64::max);
+    let mut latest_finish = vec![total_hours; n];
+    for &i in order.iter().rev() {
+        latest_finish[i] = successors[i]
+            .iter()
+            .map(|&s| latest_finish[s] - durations[s])
+            .fold(total_hours, f64::min);
+    }
+    let slack = |i: usize| latest_finish[i] - durations[i] - earliest_start[i];
+
+    // Walk back from the task finishing last through predecessors that
+    // leave it no slack
+    let mut path = Vec::new();
+    let mut current = (0..n)
+        .filter(|&i| slack(i) < SLACK_EPSILON)
+        .filter(|&i| (earliest_start[i] + durations[i] - total_hours).abs() < SLACK_EPSILON)
+        .min_by(|&a, &b| graph.tasks[a].name.cmp(&graph.tasks[b].name));
+    while let Some(i) = current {
+        path.push(graph.tasks[i].name.clone());
+        current = predecessors[i]
+            .iter()
+            .copied()
+            .filter(|&p| slack(p) < SLACK_EPSILON)
+            .filter(|&p| {
+                (earliest_start[p] + durations[p] - earliest_start[i]).abs() < SLACK_EPSILON
+            })
+            .min_by(|&a, &b| graph.tasks[a].name.cmp(&graph.tasks[b].name));
+    }
+    path.reverse();
+
+    let mut tasks: Vec<TaskSchedule> = (0..n)
+        .map(|i| TaskSchedule {
+            name: graph.tasks[i].name.clone(),
+            duration_hours: durations[i],
+            earliest_start: earliest_start[i],
+            earliest_finish: earliest_start[i] + durations[i],
+            latest_start: latest_finish[i] - durations[i],
+            latest_finish: latest_finish[i],
+            slack: slack(i).max(0.0),
+        })
+        .collect();
+    tasks.sort_by(|a, b| {
+        a.earliest_start
+            .total_cmp(&b.earliest_start)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let unestimated = graph
+        .tasks
+        .iter()
+        .filter(|t| t.estimate_hours.is_none())
+        .filter(|t| !matches!(t.status, TaskStatus::Done | TaskStatus::Cancelled))
+        .map(|t| t.name.clone())
+        .collect();
+
+    Ok(CriticalPathReport {
+        total_hours,
+        path,
+        tasks,
+        unestimated,
+    })
+}
+
+/// Compute the critical path and per-task slack of a project
+///
+/// Uses `estimate_hours` of open tasks along their `depends_on` edges.
+#[instrument(skip(ports), err)]
+pub async fn critical_path<M, G>(
+    ports: &Ports<M, G>,
+    command: CriticalPathCommand,
+) -> CriticalPathResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let graph = task_graph(
+        ports,
+        TaskGraphCommand {
+            project_name: command.project_name,
+        },
+    )
+    .await?;
+    analyze_critical_path(&graph).map_err(CoreError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::memory::tasks::task_graph::TaskDependency;
+    use crate::operations::memory::tasks::types::Priority;
+
+    fn node(name: &str, hours: Option<f64>, status: TaskStatus) -> TaskNode {
+        TaskNode {
+            name: name.to_string(),
+            status,
+            priority: Priority::Medium,
+            estimate_hours: hours,
+        }
+    }
+
+    fn dep(task: &str, depends_on: &str) -> TaskDependency {
+        TaskDependency {
+            task: task.to_string(),
+            depends_on: depends_on.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_critical_path_and_slack() {
+        // design(2) -> build(5) -> ship(1), docs(1) only needs design
+        let graph = TaskGraph {
+            tasks: vec![
+                node("build", Some(5.0), TaskStatus::InProgress),
+                node("design", Some(2.0), TaskStatus::Todo),
+                node("docs", Some(1.0), TaskStatus::Todo),
+                node("ship", Some(1.0), TaskStatus::Todo),
+                node("triage", None, TaskStatus::Todo),
+            ],
+            dependencies: vec![
+                dep("build", "design"),
+                dep("docs", "design"),
+                dep("ship", "build"),
+                dep("ship", "docs"),
+            ],
+        };
+
+        let report = analyze_critical_path(&graph).unwrap();
+        assert_eq!(report.total_hours, 8.0);
+        assert_eq!(report.path, ["design", "build", "ship"]);
+        let docs = report.tasks.iter().find(|t| t.name == "docs").unwrap();
+        assert_eq!(docs.earliest_start, 2.0);
+        assert_eq!(docs.slack, 4.0);
+        assert_eq!(report.unestimated, ["triage"]);
+    }
+
+    #[test]
+    fn test_finished_tasks_take_no_time() {
+        let graph = TaskGraph {
+            tasks: vec![
+                node("a", Some(3.0), TaskStatus::Done),
+                node("b", Some(2.0), TaskStatus::Todo),
+            ],
+            dependencies: vec![dep("b", "a")],
+        };
+        let report = analyze_critical_path(&graph).unwrap();
+        assert_eq!(report.total_hours, 2.0);
+        assert_eq!(report.path, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_cycle_is_rejected() {
+        let graph = TaskGraph {
+            tasks: vec![
+                node("a", Some(1.0), TaskStatus::Todo),
+                node("b", Some(1.0), TaskStatus::Todo),
+                node("c", Some(1.0), TaskStatus::Todo),
+            ],
+            dependencies: vec![dep("a", "b"), dep("b", "a"), dep("c", "a")],
+        };
+        let err = analyze_critical_path(&graph).unwrap_err();
+        assert!(matches!(
+            &err.0[..],
+            [ValidationErrorKind::DependencyCycle(names)] if names.contains('a') && names.contains('b')
+        ));
+    }
+}
@@ -0,0 +1,247 @@
+use super::types::{TaskProperties, TaskStatus};
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{MemoryRepository, TaskFilter};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::instrument;
+
+/// Command for measuring how far estimates of a project were off
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct EstimateCalibrationCommand {
+    /// Project whose finished tasks to compare; the default project when unset
+    pub project_name: Option<String>,
+}
+
+/// Ratio of actual to estimated effort over a group of finished tasks
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct BiasFactor {
+    /// Number of tasks with both an estimate and an actual
+    pub samples: usize,
+    /// Total actual hours divided by total estimated hours; above 1 means
+    /// estimates were too optimistic
+    pub factor: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Totals {
+    samples: usize,
+    estimated: f64,
+    actual: f64,
+}
+
+impl Totals {
+    fn add(&mut self, estimated: f64, actual: f64) {
+        self.samples += 1;
+        self.estimated += estimated;
+        self.actual += actual;
+    }
+
+    fn factor(self) -> BiasFactor {
+        BiasFactor {
+            samples: self.samples,
+            factor: self.actual / self.estimated,
+        }
+    }
+}
+
+/// Estimate bias of finished tasks, overall and per task type and assignee
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct EstimateCalibration {
+    /// Bias over every sample; unset when there are none
+    pub overall: Option<BiasFactor>,
+    pub by_task_type: BTreeMap<String, BiasFactor>,
+    pub by_assignee: BTreeMap<String, BiasFactor>,
+}
+
+pub type EstimateCalibrationResult<E> = CoreResult<EstimateCalibration, E>;
+
+impl EstimateCalibration {
+    /// Compare estimates with actuals of the done tasks among `tasks`
+    ///
+    /// Tasks without a positive estimate or without an actual are skipped.
+    pub fn from_tasks<'a>(tasks: impl IntoIterator<Item = &'a TaskProperties>) -> Self {
+        let mut overall = Totals::default();
+        let mut by_task_type: BTreeMap<String, Totals> = BTreeMap::new();
+        let mut by_assignee: BTreeMap<String, Totals> = BTreeMap::new();
+        for task in tasks {
+            if task.status != TaskStatus::Done {
+                continue;
+            }
+            let (Some(estimated), Some(actual)) = (task.estimate_hours, task.actual_hours) else {
+                continue;
+            };
+            if estimated <= 0.0 || actual < 0.0 {
+                continue;
+            }
+            overall.add(estimated, actual);
+            by_task_type
+                .entry(task.task_type.as_ref().to_string())
+                .or_default()
+                .add(estimated, actual);
+            if let Some(assignee) = &task.assignee {
+                by_assignee
+                    .entry(assignee.clone())
+                    .or_default()
+                    .add(estimated, actual);
+            }
+        }
+
+        EstimateCalibration {
+            overall: (overall.samples > 0).then(|| overall.factor()),
+            by_task_type: by_task_type
+                .into_iter()
+                .map(|(k, t)| (k, t.factor()))
+                .collect(),
+            by_assignee: by_assignee
+                .into_iter()
+                .map(|(k, t)| (k, t.factor()))
+                .collect(),
+        }
+    }
+
+    /// Calibrated version of the estimate of `task`
+    ///
+    /// Uses the most specific bias known: the assignee's, then the task
+    /// type's, then the overall one.
+    pub fn suggest(&self, task: &TaskProperties) -> Option<f64> {
+        let estimate = task.estimate_hours?;
+        let bias = task
+            .assignee
+            .as_ref()
+            .and_then(|a| self.by_assignee.get(a))
+            .or_else(|| self.by_task_type.get(task.task_type.as_ref()))
+            .or(self.overall.as_ref())?;
+        Some(estimate * bias.factor)
+    }
+}
+
+/// Load the finished tasks of a project and measure their estimate bias
+pub(crate) async fn calibrate_project<M, G>(
+    ports: &Ports<M, G>,
+    project_name: &str,
+) -> EstimateCalibrationResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let filter = TaskFilter {
+        statuses: vec![TaskStatus::Done.as_ref().to_string()],
+        ..TaskFilter::for_project(project_name)
+    };
+    let tasks = ports
+        .memory_service
+        .find_tasks_typed::<TaskProperties>(&filter)
+        .await?;
+    Ok(EstimateCalibration::from_tasks(
+        tasks.iter().map(|t| &t.properties),
+    ))
+}
+
+/// Compare `estimate_hours` with `actual_hours` across the done tasks of a
+/// project
+#[instrument(skip(ports), err)]
+pub async fn estimate_calibration<M, G>(
+    ports: &Ports<M, G>,
+    command: EstimateCalibrationCommand,
+) -> EstimateCalibrationResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let project_name = match command
+        .project_name
+        .or_else(|| ports.memory_service.memory_config().default_project.clone())
+    {
+        Some(p) => p,
+        None => return Err(CoreError::MissingProject),
+    };
+    calibrate_project(ports, &project_name).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::memory::tasks::types::TaskType;
+
+    fn done(
+        task_type: TaskType,
+        assignee: Option<&str>,
+        estimate: f64,
+        actual: f64,
+    ) -> TaskProperties {
+        TaskProperties {
+            task_type,
+            status: TaskStatus::Done,
+            assignee: assignee.map(str::to_string),
+            estimate_hours: Some(estimate),
+            actual_hours: Some(actual),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_bias_per_type_and_assignee() {
+        let tasks = [
+            done(TaskType::Bug, Some("ann"), 2.0, 4.0),
+            done(TaskType::Bug, None, 2.0, 2.0),
+            done(TaskType::Feature, Some("bob"), 4.0, 3.0),
+            TaskProperties {
+                estimate_hours: Some(1.0),
+                actual_hours: Some(10.0),
+                status: TaskStatus::InProgress,
+                ..Default::default()
+            },
+        ];
+        let calibration = EstimateCalibration::from_tasks(&tasks);
+
+        assert_eq!(
+            calibration.overall,
+            Some(BiasFactor {
+                samples: 3,
+                factor: 9.0 / 8.0,
+            })
+        );
+        assert_eq!(calibration.by_task_type["bug"].factor, 1.5);
+        assert_eq!(calibration.by_assignee["ann"].factor, 2.0);
+        assert_eq!(calibration.by_assignee["bob"].factor, 0.75);
+    }
+
+    #[test]
+    fn test_suggest_prefers_most_specific_bias() {
+        let calibration = EstimateCalibration::from_tasks(&[
+            done(TaskType::Bug, Some("ann"), 2.0, 4.0),
+            done(TaskType::Bug, None, 2.0, 2.0),
+            done(TaskType::Chore, None, 1.0, 1.0),
+        ]);
+        let new = |task_type, assignee: Option<&str>| TaskProperties {
+            task_type,
+            assignee: assignee.map(str::to_string),
+            estimate_hours: Some(2.0),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            calibration.suggest(&new(TaskType::Bug, Some("ann"))),
+            Some(4.0)
+        );
+        assert_eq!(
+            calibration.suggest(&new(TaskType::Bug, Some("cat"))),
+            Some(3.0)
+        );
+        assert_eq!(
+            calibration.suggest(&new(TaskType::Feature, None)),
+            Some(2.0 * 7.0 / 5.0)
+        );
+        assert_eq!(
+            EstimateCalibration::default().suggest(&new(TaskType::Bug, None)),
+            None
+        );
+    }
+}
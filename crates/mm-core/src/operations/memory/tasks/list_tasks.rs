@@ -1,8 +1,10 @@
-use super::types::TaskProperties;
+use super::types::{Priority, TaskProperties, TaskStatus};
 use crate::error::{CoreError, CoreResult};
 use crate::ports::Ports;
+use chrono::NaiveDate;
 use mm_git::GitRepository;
-use mm_memory::{MemoryEntity, MemoryRepository, RelationshipDirection, labels::TASK_LABEL};
+use mm_memory::date::deserialize_optional_date;
+use mm_memory::{MemoryEntity, MemoryRepository, TaskFilter};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
@@ -14,6 +16,20 @@ pub struct ListTasksCommand {
     pub project_name: Option<String>,
     /// Labels to filter tasks
     pub labels: Vec<String>,
+    /// Only include tasks with this status
+    #[serde(default)]
+    pub status: Option<TaskStatus>,
+    /// Only include tasks with this priority
+    #[serde(default)]
+    pub priority: Option<Priority>,
+    /// Only include tasks due before this date
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
+    #[schemars(with = "Option<String>")]
+    pub due_before: Option<NaiveDate>,
+    /// Only include tasks due on or after this date
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
+    #[schemars(with = "Option<String>")]
+    pub due_after: Option<NaiveDate>,
 }
 
 /// Result of listing tasks
@@ -23,7 +39,8 @@ pub struct ListTasksResult {
     pub tasks: Vec<MemoryEntity<TaskProperties>>,
 }
 
-/// List tasks for a project optionally filtered by labels
+/// List tasks for a project optionally filtered by labels, status, priority
+/// and due date
 #[instrument(skip(ports), err)]
 pub async fn list_tasks<M, G>(
     ports: &Ports<M, G>,
@@ -43,23 +60,26 @@ where
         None => return Err(CoreError::MissingProject),
     };
 
-    let mut tasks = ports
+    let filter = TaskFilter {
+        project: project_name,
+        labels: command.labels,
+        statuses: command
+            .status
+            .map(|s| vec![s.as_ref().to_string()])
+            .unwrap_or_default(),
+        priorities: command
+            .priority
+            .map(|p| vec![p.as_ref().to_string()])
+            .unwrap_or_default(),
+        due_before: command.due_before,
+        due_after: command.due_after,
+    };
+
+    let tasks = ports
         .memory_service
-        .find_related_entities_typed::<TaskProperties>(
-            &project_name,
-            Some("contains".to_string()),
-            Some(RelationshipDirection::Outgoing),
-            1,
-        )
+        .find_tasks_typed::<TaskProperties>(&filter)
         .await
-        .map_err(CoreError::from)?
-        .into_iter()
-        .filter(|t| t.labels.contains(&TASK_LABEL.to_string()))
-        .collect::<Vec<_>>();
-
-    for label in command.labels {
-        tasks.retain(|t| t.labels.contains(&label));
-    }
+        .map_err(CoreError::from)?;
 
     Ok(ListTasksResult { tasks })
 }
@@ -69,7 +89,7 @@ mod tests {
     use super::*;
     use crate::ports::Ports;
     use mm_memory::{
-        MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository, RelationshipDirection,
+        MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository,
         labels::{ACTIVE_LABEL, TASK_LABEL},
         value::MemoryValue,
     };
@@ -86,6 +106,7 @@ mod tests {
             observations: vec![],
             properties: props.clone(),
             relationships: vec![],
+            revision: 0,
         };
         let task2 = MemoryEntity {
             name: "task:2".into(),
@@ -93,16 +114,12 @@ mod tests {
             observations: vec![],
             properties: props.clone(),
             relationships: vec![],
+            revision: 0,
         };
         let mut mock = MockMemoryRepository::new();
-        mock.expect_find_related_entities()
-            .with(
-                eq("proj"),
-                eq(Some("contains".to_string())),
-                eq(Some(RelationshipDirection::Outgoing)),
-                eq(1u32),
-            )
-            .returning(move |_, _, _, _| Ok(vec![task1.clone(), task2.clone()]));
+        mock.expect_find_tasks()
+            .with(eq(TaskFilter::for_project("proj")))
+            .returning(move |_| Ok(vec![task1.clone(), task2.clone()]));
 
         let service = MemoryService::new(
             mock,
@@ -116,6 +133,10 @@ mod tests {
         let cmd = ListTasksCommand {
             project_name: None,
             labels: vec![],
+            status: None,
+            priority: None,
+            due_before: None,
+            due_after: None,
         };
         let result = list_tasks(&ports, cmd).await.unwrap();
         assert_eq!(result.tasks.len(), 2);
@@ -131,6 +152,7 @@ mod tests {
             observations: vec![],
             properties: props.clone(),
             relationships: vec![],
+            revision: 0,
         };
         let task2 = MemoryEntity {
             name: "task:2".into(),
@@ -138,10 +160,22 @@ mod tests {
             observations: vec![],
             properties: props.clone(),
             relationships: vec![],
+            revision: 0,
         };
         let mut mock = MockMemoryRepository::new();
-        mock.expect_find_related_entities()
-            .returning(move |_, _, _, _| Ok(vec![task1.clone(), task2.clone()]));
+        let expected = TaskFilter {
+            labels: vec![ACTIVE_LABEL.to_string()],
+            statuses: vec!["done".to_string()],
+            ..TaskFilter::for_project("proj")
+        };
+        mock.expect_find_tasks()
+            .with(eq(expected))
+            .returning(move |f| {
+                Ok([task1.clone(), task2.clone()]
+                    .into_iter()
+                    .filter(|t| f.labels.iter().all(|l| t.labels.contains(l)))
+                    .collect())
+            });
 
         let service = MemoryService::new(
             mock,
@@ -154,6 +188,10 @@ mod tests {
         let cmd = ListTasksCommand {
             project_name: None,
             labels: vec![ACTIVE_LABEL.to_string()],
+            status: Some(TaskStatus::Done),
+            priority: None,
+            due_before: None,
+            due_after: None,
         };
         let result = list_tasks(&ports, cmd).await.unwrap();
         assert_eq!(result.tasks.len(), 1);
@@ -163,12 +201,16 @@ mod tests {
     #[tokio::test]
     async fn test_list_tasks_missing_project() {
         let mut mock = MockMemoryRepository::new();
-        mock.expect_find_related_entities().never();
+        mock.expect_find_tasks().never();
         let service = MemoryService::new(mock, MemoryConfig::default());
         let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
         let cmd = ListTasksCommand {
             project_name: None,
             labels: vec![],
+            status: None,
+            priority: None,
+            due_before: None,
+            due_after: None,
         };
         let res = list_tasks(&ports, cmd).await;
         assert!(matches!(res, Err(CoreError::MissingProject)));
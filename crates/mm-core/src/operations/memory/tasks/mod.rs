@@ -1,14 +1,38 @@
 pub mod types;
 
 mod create_tasks;
+mod critical_path;
 mod delete_task;
+mod estimate_calibration;
 mod get_task;
 mod list_tasks;
+mod standup_summary;
+mod task_graph;
+mod task_report;
 mod update_task;
+mod wip;
 
+pub(crate) use create_tasks::preview_tasks;
 pub use create_tasks::{CreateTasksCommand, CreateTasksResult, TaskInput, create_tasks};
+pub use critical_path::{
+    CriticalPathCommand, CriticalPathReport, CriticalPathResult, TaskSchedule,
+    analyze_critical_path, critical_path,
+};
 pub use delete_task::{DeleteTaskCommand, DeleteTaskResult, delete_task};
+pub use estimate_calibration::{
+    BiasFactor, EstimateCalibration, EstimateCalibrationCommand, EstimateCalibrationResult,
+    estimate_calibration,
+};
 pub use get_task::{GetTaskCommand, GetTaskResult, get_task};
 pub use list_tasks::{ListTasksCommand, ListTasksResult, list_tasks};
+pub use standup_summary::{
+    StandupItem, StandupSummary, StandupSummaryCommand, StandupSummaryResult, standup_summary,
+};
+pub use task_graph::{
+    DEPENDS_ON_RELATIONSHIP, TaskDependency, TaskGraph, TaskGraphCommand, TaskGraphResult,
+    TaskNode, task_graph,
+};
+pub use task_report::{TaskReport, TaskReportCommand, TaskReportResult, task_report};
 pub use types::{Priority, TaskProperties, TaskStatus, TaskType};
-pub use update_task::{UpdateTaskCommand, UpdateTaskResult, update_task};
+pub use update_task::{UpdateTaskCommand, UpdateTaskOutcome, UpdateTaskResult, update_task};
+pub use wip::{WipScope, WipViolation};
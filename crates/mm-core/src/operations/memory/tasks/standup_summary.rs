@@ -0,0 +1,222 @@
+use super::types::{TaskProperties, TaskStatus};
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use chrono::{DateTime, Utc};
+use mm_git::GitRepository;
+use mm_memory::{MemoryEntity, MemoryRepository, TaskFilter};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+/// Command for summarizing recent task activity of a project
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct StandupSummaryCommand {
+    /// Project to summarize; the default project when unset
+    pub project_name: Option<String>,
+    /// Start of the window for finished tasks
+    #[schemars(with = "String")]
+    pub since: DateTime<Utc>,
+}
+
+/// Task mentioned in a standup summary
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct StandupItem {
+    pub name: String,
+    pub description: String,
+    pub assignee: Option<String>,
+}
+
+/// What got done, what is under way and what is stuck, sorted by task name
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct StandupSummary {
+    pub project_name: String,
+    #[schemars(with = "String")]
+    pub since: DateTime<Utc>,
+    /// Tasks moved to done since the start of the window
+    pub done: Vec<StandupItem>,
+    pub in_progress: Vec<StandupItem>,
+    pub blocked: Vec<StandupItem>,
+}
+
+pub type StandupSummaryResult<E> = CoreResult<StandupSummary, E>;
+
+impl StandupSummary {
+    /// Render the summary as markdown ready to paste into chat
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "## Standup: {}\n_Since {}_\n",
+            self.project_name,
+            self.since.format("%Y-%m-%d %H:%M UTC")
+        );
+        for (title, items) in [
+            ("Done", &self.done),
+            ("In progress", &self.in_progress),
+            ("Blocked", &self.blocked),
+        ] {
+            out.push_str(&format!("\n**{title}**\n"));
+            if items.is_empty() {
+                out.push_str("- _Nothing_\n");
+            }
+            for item in items {
+                out.push_str(&format!("- {}", item.name));
+                if !item.description.is_empty() {
+                    out.push_str(&format!(": {}", item.description));
+                }
+                if let Some(assignee) = &item.assignee {
+                    out.push_str(&format!(" (@{assignee})"));
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+fn item(task: &MemoryEntity<TaskProperties>) -> StandupItem {
+    StandupItem {
+        name: task.name.clone(),
+        description: task.properties.description.clone(),
+        assignee: task.properties.assignee.clone(),
+    }
+}
+
+/// Summarize the tasks of a project for a standup
+///
+/// A task counts as done within the window when its last update is not older
+/// than `since`. Tasks in progress or blocked are listed regardless of age.
+#[instrument(skip(ports), err)]
+pub async fn standup_summary<M, G>(
+    ports: &Ports<M, G>,
+    command: StandupSummaryCommand,
+) -> StandupSummaryResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let project_name = match command
+        .project_name
+        .or_else(|| ports.memory_service.memory_config().default_project.clone())
+    {
+        Some(p) => p,
+        None => return Err(CoreError::MissingProject),
+    };
+
+    let filter = TaskFilter {
+        statuses: [
+            TaskStatus::Done,
+            TaskStatus::InProgress,
+            TaskStatus::Blocked,
+        ]
+        .iter()
+        .map(|s| s.as_ref().to_string())
+        .collect(),
+        ..TaskFilter::for_project(project_name.clone())
+    };
+    let mut tasks = ports
+        .memory_service
+        .find_tasks_typed::<TaskProperties>(&filter)
+        .await?;
+    tasks.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut summary = StandupSummary {
+        project_name,
+        since: command.since,
+        done: Vec::new(),
+        in_progress: Vec::new(),
+        blocked: Vec::new(),
+    };
+    for task in &tasks {
+        match task.properties.status {
+            TaskStatus::Done if task.properties.updated_at >= command.since => {
+                summary.done.push(item(task))
+            }
+            TaskStatus::InProgress => summary.in_progress.push(item(task)),
+            TaskStatus::Blocked => summary.blocked.push(item(task)),
+            _ => {}
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use mm_memory::labels::TASK_LABEL;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    fn task(name: &str, status: TaskStatus, updated_at: DateTime<Utc>) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            labels: vec![TASK_LABEL.to_string()],
+            properties: TaskProperties {
+                description: format!("About {name}"),
+                status,
+                updated_at,
+                ..Default::default()
+            }
+            .into(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_standup_summary_groups_tasks() {
+        let now = Utc::now();
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_tasks()
+            .withf(|filter| filter.project == "project:x" && filter.statuses.len() == 3)
+            .returning(move |_| {
+                Ok(vec![
+                    task("task:old", TaskStatus::Done, now - Duration::days(3)),
+                    task("task:shipped", TaskStatus::Done, now - Duration::hours(2)),
+                    task("task:stuck", TaskStatus::Blocked, now - Duration::days(5)),
+                    task("task:wip", TaskStatus::InProgress, now - Duration::days(2)),
+                ])
+            });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let summary = standup_summary(
+            &ports,
+            StandupSummaryCommand {
+                project_name: Some("project:x".to_string()),
+                since: now - Duration::days(1),
+            },
+        )
+        .await
+        .unwrap();
+        let names =
+            |items: &[StandupItem]| items.iter().map(|i| i.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&summary.done), ["task:shipped"]);
+        assert_eq!(names(&summary.in_progress), ["task:wip"]);
+        assert_eq!(names(&summary.blocked), ["task:stuck"]);
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let summary = StandupSummary {
+            project_name: "project:x".to_string(),
+            since: DateTime::parse_from_rfc3339("2025-07-01T09:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            done: vec![StandupItem {
+                name: "task:a".to_string(),
+                description: "Ship it".to_string(),
+                assignee: Some("ann".to_string()),
+            }],
+            in_progress: vec![],
+            blocked: vec![],
+        };
+        assert_eq!(
+            summary.to_markdown(),
+            "## Standup: project:x\n_Since 2025-07-01 09:00 UTC_\n\n\
+             **Done**\n- task:a: Ship it (@ann)\n\n\
+             **In progress**\n- _Nothing_\n\n\
+             **Blocked**\n- _Nothing_\n"
+        );
+    }
+}
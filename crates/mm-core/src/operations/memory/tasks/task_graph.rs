@@ -0,0 +1,183 @@
+use super::types::{Priority, TaskProperties, TaskStatus};
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{MemoryRepository, TaskFilter};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::instrument;
+
+/// Relationship linking a task to a task it depends on
+pub const DEPENDS_ON_RELATIONSHIP: &str = "depends_on";
+
+/// Command for loading the task dependency graph of a project
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct TaskGraphCommand {
+    /// Project whose tasks to load; the default project when unset
+    pub project_name: Option<String>,
+}
+
+/// Task in a dependency graph
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct TaskNode {
+    pub name: String,
+    pub status: TaskStatus,
+    pub priority: Priority,
+    #[serde(default)]
+    pub estimate_hours: Option<f64>,
+}
+
+/// `task` cannot start before `depends_on` is done
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+pub struct TaskDependency {
+    pub task: String,
+    pub depends_on: String,
+}
+
+/// Tasks of a project and the `depends_on` edges between them, sorted by name
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct TaskGraph {
+    pub tasks: Vec<TaskNode>,
+    pub dependencies: Vec<TaskDependency>,
+}
+
+pub type TaskGraphResult<E> = CoreResult<TaskGraph, E>;
+
+/// Load the tasks of a project and the dependencies between them
+///
+/// Only task nodes and `depends_on` edges are fetched. Edges leading to tasks
+/// of other projects are left out.
+#[instrument(skip(ports), err)]
+pub async fn task_graph<M, G>(
+    ports: &Ports<M, G>,
+    command: TaskGraphCommand,
+) -> TaskGraphResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let project_name = match command
+        .project_name
+        .or_else(|| ports.memory_service.memory_config().default_project.clone())
+    {
+        Some(p) => p,
+        None => return Err(CoreError::MissingProject),
+    };
+
+    let mut tasks: Vec<TaskNode> = ports
+        .memory_service
+        .find_tasks_typed::<TaskProperties>(&TaskFilter::for_project(project_name))
+        .await?
+        .into_iter()
+        .map(|t| TaskNode {
+            name: t.name,
+            status: t.properties.status,
+            priority: t.properties.priority,
+            estimate_hours: t.properties.estimate_hours,
+        })
+        .collect();
+    tasks.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let names: HashSet<&str> = tasks.iter().map(|t| t.name.as_str()).collect();
+    let mut dependencies: Vec<TaskDependency> = ports
+        .memory_service
+        .find_relationships(None, None, Some(DEPENDS_ON_RELATIONSHIP.to_string()))
+        .await?
+        .into_iter()
+        .filter(|r| names.contains(r.from.as_str()) && names.contains(r.to.as_str()))
+        .map(|r| TaskDependency {
+            task: r.from,
+            depends_on: r.to,
+        })
+        .collect();
+    dependencies.sort_by(|a, b| (&a.task, &a.depends_on).cmp(&(&b.task, &b.depends_on)));
+    dependencies.dedup();
+
+    Ok(TaskGraph {
+        tasks,
+        dependencies,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{
+        MemoryConfig, MemoryEntity, MemoryRelationship, MemoryService, MockMemoryRepository,
+        labels::TASK_LABEL,
+    };
+    use mockall::predicate::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn task(name: &str) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            labels: vec![TASK_LABEL.to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn depends_on(from: &str, to: &str) -> MemoryRelationship {
+        MemoryRelationship {
+            from: from.to_string(),
+            to: to.to_string(),
+            name: DEPENDS_ON_RELATIONSHIP.to_string(),
+            properties: HashMap::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_task_graph_keeps_edges_within_project() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_tasks()
+            .withf(|filter| filter.project == "project:x")
+            .returning(|_| Ok(vec![task("task:b"), task("task:a")]));
+        mock.expect_find_relationships()
+            .with(eq(None), eq(None), eq(Some("depends_on".to_string())))
+            .returning(|_, _, _| {
+                Ok(vec![
+                    depends_on("task:b", "task:a"),
+                    depends_on("task:b", "task:elsewhere"),
+                ])
+            });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let graph = task_graph(
+            &ports,
+            TaskGraphCommand {
+                project_name: Some("project:x".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            graph
+                .tasks
+                .iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>(),
+            ["task:a", "task:b"]
+        );
+        assert_eq!(
+            graph.dependencies,
+            [TaskDependency {
+                task: "task:b".to_string(),
+                depends_on: "task:a".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_task_graph_requires_project() {
+        let service = MemoryService::new(MockMemoryRepository::new(), MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let result = task_graph(&ports, TaskGraphCommand { project_name: None }).await;
+        assert!(matches!(result, Err(CoreError::MissingProject)));
+    }
+}
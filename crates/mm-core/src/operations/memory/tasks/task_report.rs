@@ -0,0 +1,181 @@
+use super::types::{TaskProperties, TaskStatus};
+use super::wip::{WipScope, WipViolation, check_limit, in_progress_by_assignee};
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{MemoryRepository, TaskFilter};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use tracing::instrument;
+
+/// Command for summarizing the tasks of a project
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct TaskReportCommand {
+    /// Project to report on; the default project when unset
+    pub project_name: Option<String>,
+}
+
+/// Task counts and WIP limit violations of a project
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct TaskReport {
+    pub project_name: String,
+    /// Number of tasks per status
+    pub status_counts: BTreeMap<String, usize>,
+    /// Tasks currently in progress, sorted by name
+    pub in_progress: Vec<String>,
+    /// Exceeded limits of the project and of assignees with tasks in it
+    pub violations: Vec<WipViolation>,
+}
+
+pub type TaskReportResult<E> = CoreResult<TaskReport, E>;
+
+/// Summarize the tasks of a project and check them against the WIP limits
+///
+/// Assignee limits count in-progress tasks across all projects.
+#[instrument(skip(ports), err)]
+pub async fn task_report<M, G>(
+    ports: &Ports<M, G>,
+    command: TaskReportCommand,
+) -> TaskReportResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let project_name = match command
+        .project_name
+        .or_else(|| ports.memory_service.memory_config().default_project.clone())
+    {
+        Some(p) => p,
+        None => return Err(CoreError::MissingProject),
+    };
+    let limits = ports.memory_service.memory_config().wip_limits.clone();
+
+    let tasks = ports
+        .memory_service
+        .find_tasks_typed::<TaskProperties>(&TaskFilter::for_project(project_name.clone()))
+        .await?;
+
+    let mut status_counts = BTreeMap::new();
+    let mut in_progress = Vec::new();
+    let mut assignees = BTreeSet::new();
+    for task in tasks {
+        *status_counts
+            .entry(task.properties.status.as_ref().to_string())
+            .or_insert(0) += 1;
+        if task.properties.status == TaskStatus::InProgress {
+            assignees.extend(task.properties.assignee);
+            in_progress.push(task.name);
+        }
+    }
+    in_progress.sort();
+
+    let mut violations: Vec<WipViolation> = check_limit(
+        WipScope::Project(project_name.clone()),
+        limits.per_project,
+        in_progress.clone(),
+    )
+    .into_iter()
+    .collect();
+    if limits.per_assignee.is_some() && !assignees.is_empty() {
+        let mut by_assignee = in_progress_by_assignee(ports).await?;
+        for assignee in assignees {
+            let tasks = by_assignee.remove(&assignee).unwrap_or_default();
+            violations.extend(check_limit(
+                WipScope::Assignee(assignee),
+                limits.per_assignee,
+                tasks,
+            ));
+        }
+    }
+
+    Ok(TaskReport {
+        project_name,
+        status_counts,
+        in_progress,
+        violations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::labels::TASK_LABEL;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository, WipLimits};
+    use std::sync::Arc;
+
+    fn task(name: &str, status: TaskStatus, assignee: Option<&str>) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            labels: vec![TASK_LABEL.to_string()],
+            properties: TaskProperties {
+                status,
+                assignee: assignee.map(str::to_string),
+                ..Default::default()
+            }
+            .into(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_task_report_lists_violations() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_tasks().returning(|_| {
+            Ok(vec![
+                task("task:a", TaskStatus::InProgress, Some("ann")),
+                task("task:b", TaskStatus::InProgress, None),
+                task("task:c", TaskStatus::Todo, None),
+            ])
+        });
+        mock.expect_find_entities_by_labels().returning(|_, _, _| {
+            Ok(vec![
+                task("task:a", TaskStatus::InProgress, Some("ann")),
+                task("task:elsewhere", TaskStatus::InProgress, Some("ann")),
+            ])
+        });
+        let config = MemoryConfig {
+            wip_limits: WipLimits {
+                per_project: Some(1),
+                per_assignee: Some(1),
+                enforce: false,
+            },
+            ..MemoryConfig::default()
+        };
+        let service = MemoryService::new(mock, config);
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let report = task_report(
+            &ports,
+            TaskReportCommand {
+                project_name: Some("project:x".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.status_counts["in_progress"], 2);
+        assert_eq!(report.in_progress, ["task:a", "task:b"]);
+        assert_eq!(
+            report
+                .violations
+                .iter()
+                .map(|v| v.scope.clone())
+                .collect::<Vec<_>>(),
+            [
+                WipScope::Project("project:x".to_string()),
+                WipScope::Assignee("ann".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_task_report_requires_project() {
+        let service = MemoryService::new(MockMemoryRepository::new(), MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let result = task_report(&ports, TaskReportCommand { project_name: None }).await;
+        assert!(matches!(result, Err(CoreError::MissingProject)));
+    }
+}
@@ -1,5 +1,7 @@
-use chrono::{DateTime, Utc};
-use mm_memory::MemoryValue;
+use chrono::{DateTime, NaiveDate, Utc};
+use mm_memory::date::{deserialize_optional_date, value_to_date};
+use mm_memory::labels::TASK_LABEL;
+use mm_memory::{EntityKind, MemoryValue, PropertyRule};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -52,9 +54,11 @@ pub struct TaskProperties {
     #[schemars(with = "String")]
     pub updated_at: DateTime<Utc>,
 
-    /// When the task is due
+    /// When the task is due: a date, a timestamp or a relative date such as
+    /// "tomorrow" or "next friday"; stored as a date
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
     #[schemars(with = "Option<String>")]
-    pub due_date: Option<DateTime<Utc>>,
+    pub due_date: Option<NaiveDate>,
 
     /// Task type
     pub task_type: TaskType,
@@ -64,6 +68,22 @@ pub struct TaskProperties {
 
     /// Task priority
     pub priority: Priority,
+
+    /// Estimated effort in hours
+    #[serde(default)]
+    pub estimate_hours: Option<f64>,
+
+    /// Who is working on the task
+    #[serde(default)]
+    pub assignee: Option<String>,
+
+    /// Effort the task actually took, in hours
+    #[serde(default)]
+    pub actual_hours: Option<f64>,
+
+    /// Estimate adjusted by the bias of past estimates, in hours
+    #[serde(default)]
+    pub suggested_estimate_hours: Option<f64>,
 }
 
 impl Default for TaskProperties {
@@ -76,10 +96,24 @@ impl Default for TaskProperties {
             task_type: TaskType::Feature,
             status: TaskStatus::Todo,
             priority: Priority::Medium,
+            estimate_hours: None,
+            assignee: None,
+            actual_hours: None,
+            suggested_estimate_hours: None,
         }
     }
 }
 
+/// Number of hours stored as a float, an integer or a numeric string
+fn hours(value: Option<MemoryValue>) -> Option<f64> {
+    match value {
+        Some(MemoryValue::Float(f)) => Some(f),
+        Some(MemoryValue::Integer(i)) => Some(i as f64),
+        Some(MemoryValue::String(s)) => s.parse().ok(),
+        _ => None,
+    }
+}
+
 impl From<HashMap<String, MemoryValue>> for TaskProperties {
     fn from(mut map: HashMap<String, MemoryValue>) -> Self {
         let description = match map.remove("description") {
@@ -104,13 +138,7 @@ impl From<HashMap<String, MemoryValue>> for TaskProperties {
             _ => Utc::now(),
         };
 
-        let due_date = match map.remove("due_date") {
-            Some(MemoryValue::DateTime(dt)) => Some(dt.with_timezone(&Utc)),
-            Some(MemoryValue::String(s)) => DateTime::parse_from_rfc3339(&s)
-                .map(|dt| dt.with_timezone(&Utc))
-                .ok(),
-            _ => None,
-        };
+        let due_date = map.remove("due_date").as_ref().and_then(value_to_date);
 
         let task_type = match map.remove("task_type") {
             Some(MemoryValue::String(s)) => TaskType::from_str(&s).unwrap_or(TaskType::Feature),
@@ -129,6 +157,15 @@ impl From<HashMap<String, MemoryValue>> for TaskProperties {
             _ => TaskProperties::default().priority,
         };
 
+        let estimate_hours = hours(map.remove("estimate_hours"));
+        let actual_hours = hours(map.remove("actual_hours"));
+        let suggested_estimate_hours = hours(map.remove("suggested_estimate_hours"));
+
+        let assignee = match map.remove("assignee") {
+            Some(MemoryValue::String(s)) if !s.is_empty() => Some(s),
+            _ => None,
+        };
+
         TaskProperties {
             description,
             created_at,
@@ -137,6 +174,10 @@ impl From<HashMap<String, MemoryValue>> for TaskProperties {
             task_type,
             status,
             priority,
+            estimate_hours,
+            assignee,
+            actual_hours,
+            suggested_estimate_hours,
         }
     }
 }
@@ -157,7 +198,7 @@ impl From<TaskProperties> for HashMap<String, MemoryValue> {
             MemoryValue::DateTime(props.updated_at.into()),
         );
         if let Some(due) = props.due_date {
-            map.insert("due_date".to_string(), MemoryValue::DateTime(due.into()));
+            map.insert("due_date".to_string(), MemoryValue::Date(due));
         }
         map.insert(
             "task_type".to_string(),
@@ -171,10 +212,65 @@ impl From<TaskProperties> for HashMap<String, MemoryValue> {
             "priority".to_string(),
             MemoryValue::String(props.priority.as_ref().to_string()),
         );
+        if let Some(estimate) = props.estimate_hours {
+            map.insert("estimate_hours".to_string(), MemoryValue::Float(estimate));
+        }
+        if let Some(assignee) = props.assignee {
+            map.insert("assignee".to_string(), MemoryValue::String(assignee));
+        }
+        if let Some(actual) = props.actual_hours {
+            map.insert("actual_hours".to_string(), MemoryValue::Float(actual));
+        }
+        if let Some(suggested) = props.suggested_estimate_hours {
+            map.insert(
+                "suggested_estimate_hours".to_string(),
+                MemoryValue::Float(suggested),
+            );
+        }
         map
     }
 }
 
+/// Due dates further in the past are rejected as likely typos
+pub const MAX_DUE_DATE_DAYS_IN_PAST: u64 = 365;
+
+const NON_NEGATIVE: PropertyRule = PropertyRule::Range {
+    min: Some(0.0),
+    max: None,
+};
+
+impl EntityKind for TaskProperties {
+    const LABEL: &'static str = TASK_LABEL;
+    const RULES: &'static [(&'static str, PropertyRule)] = &[
+        (
+            "priority",
+            PropertyRule::OneOf(&["low", "medium", "high", "critical"]),
+        ),
+        (
+            "status",
+            PropertyRule::OneOf(&[
+                "todo",
+                "inprogress",
+                "in_progress",
+                "blocked",
+                "done",
+                "cancelled",
+            ]),
+        ),
+        (
+            "task_type",
+            PropertyRule::OneOf(&["feature", "bug", "chore", "improvement"]),
+        ),
+        ("estimate_hours", NON_NEGATIVE),
+        ("actual_hours", NON_NEGATIVE),
+        ("suggested_estimate_hours", NON_NEGATIVE),
+        (
+            "due_date",
+            PropertyRule::MaxDaysInPast(MAX_DUE_DATE_DAYS_IN_PAST),
+        ),
+    ];
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -205,4 +301,56 @@ mod tests {
         assert_eq!(props.status, TaskStatus::Done);
         assert_eq!(props.priority, Priority::Critical);
     }
+
+    #[test]
+    fn test_task_rules() {
+        let props = TaskProperties {
+            estimate_hours: Some(-2.0),
+            due_date: Some(Utc::now().date_naive() - chrono::Days::new(400)),
+            ..Default::default()
+        };
+        let errors = TaskProperties::validate(&props.into());
+        assert!(matches!(
+            &errors[..],
+            [
+                mm_memory::ValidationErrorKind::OutOfRange { property: estimate, .. },
+                mm_memory::ValidationErrorKind::DateTooFarInPast { property: due, .. },
+            ] if estimate == "estimate_hours" && due == "due_date"
+        ));
+
+        let update =
+            HashMap::from([("priority".to_string(), MemoryValue::String("urgent".into()))]);
+        assert!(matches!(
+            &TaskProperties::validate(&update)[..],
+            [mm_memory::ValidationErrorKind::InvalidEnumValue { .. }]
+        ));
+        assert!(TaskProperties::validate(&TaskProperties::default().into()).is_empty());
+    }
+
+    #[test]
+    fn test_due_date_normalized_to_date() {
+        let due = NaiveDate::from_ymd_opt(2025, 7, 1).unwrap();
+        let mut map = HashMap::new();
+        map.insert(
+            "due_date".to_string(),
+            MemoryValue::String("2025-07-01T09:00:00Z".into()),
+        );
+        let props = TaskProperties::from(map);
+        assert_eq!(props.due_date, Some(due));
+
+        let map: HashMap<String, MemoryValue> = props.into();
+        assert_eq!(map.get("due_date"), Some(&MemoryValue::Date(due)));
+
+        let props: TaskProperties = serde_json::from_value(serde_json::json!({
+            "description": "",
+            "created_at": "2025-06-01T00:00:00Z",
+            "updated_at": "2025-06-01T00:00:00Z",
+            "due_date": "2025/07/01",
+            "task_type": "Bug",
+            "status": "Todo",
+            "priority": "Low",
+        }))
+        .unwrap();
+        assert_eq!(props.due_date, Some(due));
+    }
 }
@@ -1,15 +1,225 @@
-#[cfg(test)]
-use crate::error::CoreError;
-#[cfg(test)]
-use mm_memory::EntityUpdate;
+use super::types::{TaskProperties, TaskStatus};
+use super::wip::{
+    WipScope, WipViolation, check_limit, in_progress_by_assignee, in_progress_in_project,
+    projects_of_task,
+};
+use crate::error::{CoreError, CoreResult};
+use crate::operations::memory::generic::update_entity_generic;
+use crate::ports::Ports;
+use crate::warning::{HasWarnings, Warning};
+use chrono::Utc;
+use mm_git::GitRepository;
+use mm_memory::date::{parse_date, value_to_date};
+use mm_memory::{
+    EntityKind, EntityUpdate, MemoryRepository, MemoryValue, PropertiesUpdate, ValidationError,
+    ValidationErrorKind,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::instrument;
+
+#[derive(Debug, Clone)]
+pub struct UpdateTaskCommand {
+    pub name: String,
+    pub update: EntityUpdate,
+}
+
+/// Outcome of a task update
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub struct UpdateTaskOutcome {
+    /// WIP limits exceeded by the update when they are not enforced
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+impl HasWarnings for UpdateTaskOutcome {
+    fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+}
+
+pub type UpdateTaskResult<E> = CoreResult<UpdateTaskOutcome, E>;
+
+/// Update a task, checking WIP limits when it moves to `in_progress`
+///
+/// Exceeded limits are returned as warnings, or reject the update when
+/// `wip_limits.enforce` is set. The task's `updated_at` is stamped with the
+/// time of the update.
+#[instrument(skip(ports), fields(name = %command.name))]
+pub async fn update_task<M, G>(
+    ports: &Ports<M, G>,
+    mut command: UpdateTaskCommand,
+) -> UpdateTaskResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.name);
+    normalize_due_date(&mut command.update).map_err(ValidationError::from)?;
+    if let Some(props) = &command.update.properties {
+        let errors: Vec<ValidationErrorKind> = [props.add.as_ref(), props.set.as_ref()]
+            .into_iter()
+            .flatten()
+            .flat_map(TaskProperties::validate)
+            .collect();
+        if !errors.is_empty() {
+            return Err(CoreError::Validation(ValidationError(errors)));
+        }
+    }
+
+    let violations = wip_violations(ports, &command.name, &command.update).await?;
+    if !violations.is_empty() && ports.memory_service.memory_config().wip_limits.enforce {
+        return Err(CoreError::Validation(ValidationError(
+            violations
+                .iter()
+                .map(|w| ValidationErrorKind::WipLimitExceeded(w.to_string()))
+                .collect(),
+        )));
+    }
+
+    let mut update = command.update;
+    stamp_updated_at(&mut update);
+    update_entity_generic(ports, &command.name, &update).await?;
+    Ok(UpdateTaskOutcome {
+        warnings: violations
+            .into_iter()
+            .map(Warning::WipLimitExceeded)
+            .collect(),
+    })
+}
+
+/// Record the current time as `updated_at` in `update`
+fn stamp_updated_at(update: &mut EntityUpdate) {
+    let now = MemoryValue::DateTime(Utc::now().into());
+    let properties = update.properties.get_or_insert(PropertiesUpdate {
+        add: None,
+        remove: None,
+        set: None,
+    });
+    if let Some(remove) = &mut properties.remove {
+        remove.retain(|key| key != "updated_at");
+    }
+    // A property set replaces everything, so the stamp has to be part of it
+    let target = match &mut properties.set {
+        Some(set) => set,
+        None => properties.add.get_or_insert_default(),
+    };
+    target.insert("updated_at".to_string(), now);
+}
+
+/// Store a `due_date` set by `update` as a date
+///
+/// Strings may use any format accepted by [`parse_date`], e.g. "next friday".
+fn normalize_due_date(update: &mut EntityUpdate) -> Result<(), ValidationErrorKind> {
+    let Some(props) = &mut update.properties else {
+        return Ok(());
+    };
+    for map in [props.add.as_mut(), props.set.as_mut()]
+        .into_iter()
+        .flatten()
+    {
+        if let Some(value) = map.get_mut("due_date") {
+            let date = match &*value {
+                MemoryValue::String(s) => parse_date(s)?,
+                other => value_to_date(other)
+                    .ok_or_else(|| ValidationErrorKind::InvalidDate(other.to_string()))?,
+            };
+            *value = MemoryValue::Date(date);
+        }
+    }
+    Ok(())
+}
+
+/// Properties of an entity after `update` is applied
+fn apply_properties(
+    mut current: HashMap<String, MemoryValue>,
+    update: &PropertiesUpdate,
+) -> HashMap<String, MemoryValue> {
+    if let Some(set) = &update.set {
+        return set.clone();
+    }
+    if let Some(add) = &update.add {
+        current.extend(add.clone());
+    }
+    for key in update.remove.iter().flatten() {
+        current.remove(key);
+    }
+    current
+}
+
+/// Limits exceeded by applying `update` to the task `name`
+///
+/// Only updates that put a task in progress, or hand an in-progress task to
+/// someone else, add work and are checked.
+async fn wip_violations<M, G>(
+    ports: &Ports<M, G>,
+    name: &str,
+    update: &EntityUpdate,
+) -> CoreResult<Vec<WipViolation>, M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let limits = &ports.memory_service.memory_config().wip_limits;
+    if limits.per_project.is_none() && limits.per_assignee.is_none() {
+        return Ok(Vec::new());
+    }
+    let Some(props) = &update.properties else {
+        return Ok(Vec::new());
+    };
+    let Some(current) = ports.memory_service.find_entity_by_name(name).await? else {
+        return Ok(Vec::new());
+    };
+    let after = TaskProperties::from(apply_properties(current.properties.clone(), props));
+    if after.status != TaskStatus::InProgress {
+        return Ok(Vec::new());
+    }
+    let before = TaskProperties::from(current.properties);
+    let was_in_progress = before.status == TaskStatus::InProgress;
 
-generate_update_wrapper!(UpdateTaskCommand, update_task, UpdateTaskResult);
+    let mut violations = Vec::new();
+    if !was_in_progress && limits.per_project.is_some() {
+        for project in projects_of_task(ports, name).await? {
+            let mut tasks = in_progress_in_project(ports, &project).await?;
+            tasks.push(name.to_string());
+            violations.extend(check_limit(
+                WipScope::Project(project),
+                limits.per_project,
+                tasks,
+            ));
+        }
+    }
+    if let Some(assignee) = after.assignee
+        && limits.per_assignee.is_some()
+        && !(was_in_progress && before.assignee.as_ref() == Some(&assignee))
+    {
+        let mut tasks = in_progress_by_assignee(ports)
+            .await?
+            .remove(&assignee)
+            .unwrap_or_default();
+        tasks.push(name.to_string());
+        violations.extend(check_limit(
+            WipScope::Assignee(assignee),
+            limits.per_assignee,
+            tasks,
+        ));
+    }
+    Ok(violations)
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ports::Ports;
-    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use mm_memory::labels::{PROJECT_LABEL, TASK_LABEL};
+    use mm_memory::{
+        MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository, RelationshipDirection,
+        WipLimits,
+    };
     use std::sync::Arc;
 
     #[tokio::test]
@@ -32,6 +242,24 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_update_task_stamps_updated_at() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_update_entity()
+            .withf(|_, update| {
+                let add = update.properties.as_ref().and_then(|p| p.add.as_ref());
+                matches!(
+                    add.and_then(|add| add.get("updated_at")),
+                    Some(MemoryValue::DateTime(_))
+                )
+            })
+            .returning(|_, _| Ok(()));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        update_task(&ports, start("task:1")).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_update_task_empty_name() {
         let mut mock = MockMemoryRepository::new();
@@ -47,4 +275,186 @@ mod tests {
         let res = update_task(&ports, cmd).await;
         assert!(matches!(res, Err(CoreError::Validation(_))));
     }
+
+    fn task(name: &str, status: TaskStatus) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            labels: vec![TASK_LABEL.to_string()],
+            properties: TaskProperties {
+                status,
+                ..Default::default()
+            }
+            .into(),
+            ..Default::default()
+        }
+    }
+
+    fn start(name: &str) -> UpdateTaskCommand {
+        UpdateTaskCommand {
+            name: name.to_string(),
+            update: EntityUpdate {
+                properties: Some(PropertiesUpdate {
+                    add: Some(HashMap::from([(
+                        "status".to_string(),
+                        MemoryValue::String("inprogress".to_string()),
+                    )])),
+                    remove: None,
+                    set: None,
+                }),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_task_normalizes_due_date() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_update_entity()
+            .withf(|_, update| {
+                let add = update.properties.as_ref().unwrap().add.as_ref().unwrap();
+                matches!(add.get("due_date"), Some(MemoryValue::Date(_)))
+            })
+            .returning(|_, _| Ok(()));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let mut cmd = start("task:1");
+        let add = cmd
+            .update
+            .properties
+            .as_mut()
+            .unwrap()
+            .add
+            .as_mut()
+            .unwrap();
+        add.clear();
+        add.insert(
+            "due_date".to_string(),
+            MemoryValue::String("next friday".to_string()),
+        );
+        update_task(&ports, cmd).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_update_task_rejects_unknown_priority() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_update_entity().never();
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let mut cmd = start("task:1");
+        let add = cmd
+            .update
+            .properties
+            .as_mut()
+            .unwrap()
+            .add
+            .as_mut()
+            .unwrap();
+        add.insert(
+            "priority".to_string(),
+            MemoryValue::String("urgent".to_string()),
+        );
+        let result = update_task(&ports, cmd).await;
+        assert!(matches!(
+            result,
+            Err(CoreError::Validation(ValidationError(ref errs)))
+                if matches!(&errs[..], [ValidationErrorKind::InvalidEnumValue { property, .. }] if property == "priority")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_update_task_rejects_invalid_due_date() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_update_entity().never();
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let mut cmd = start("task:1");
+        let add = cmd
+            .update
+            .properties
+            .as_mut()
+            .unwrap()
+            .add
+            .as_mut()
+            .unwrap();
+        add.clear();
+        add.insert(
+            "due_date".to_string(),
+            MemoryValue::String("someday".to_string()),
+        );
+        let result = update_task(&ports, cmd).await;
+        assert!(matches!(
+            result,
+            Err(CoreError::Validation(ValidationError(ref errs)))
+                if matches!(&errs[..], [ValidationErrorKind::InvalidDate(_)])
+        ));
+    }
+
+    fn project_at_limit(enforce: bool) -> MockMemoryRepository {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name()
+            .returning(|name| Ok(Some(task(name, TaskStatus::Todo))));
+        mock.expect_find_related_entities()
+            .withf(|name, _, dir, _| {
+                name == "task:new" && *dir == Some(RelationshipDirection::Incoming)
+            })
+            .returning(|_, _, _, _| {
+                Ok(vec![MemoryEntity {
+                    name: "project:x".to_string(),
+                    labels: vec![PROJECT_LABEL.to_string()],
+                    ..Default::default()
+                }])
+            });
+        mock.expect_find_tasks()
+            .withf(|filter| filter.project == "project:x" && filter.statuses == ["in_progress"])
+            .returning(|_| Ok(vec![task("task:busy", TaskStatus::InProgress)]));
+        if enforce {
+            mock.expect_update_entity().never();
+        } else {
+            mock.expect_update_entity().returning(|_, _| Ok(()));
+        }
+        mock
+    }
+
+    fn config(enforce: bool) -> MemoryConfig {
+        MemoryConfig {
+            wip_limits: WipLimits {
+                per_project: Some(1),
+                per_assignee: None,
+                enforce,
+            },
+            ..MemoryConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_task_warns_over_wip_limit() {
+        let service = MemoryService::new(project_at_limit(false), config(false));
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let outcome = update_task(&ports, start("task:new")).await.unwrap();
+        assert_eq!(
+            outcome.warnings,
+            [Warning::WipLimitExceeded(WipViolation {
+                scope: WipScope::Project("project:x".to_string()),
+                limit: 1,
+                tasks: vec!["task:busy".to_string(), "task:new".to_string()],
+            })]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_task_enforces_wip_limit() {
+        let service = MemoryService::new(project_at_limit(true), config(true));
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let result = update_task(&ports, start("task:new")).await;
+        assert!(matches!(
+            result,
+            Err(CoreError::Validation(ValidationError(ref errs)))
+                if matches!(&errs[..], [ValidationErrorKind::WipLimitExceeded(_)])
+        ));
+    }
 }
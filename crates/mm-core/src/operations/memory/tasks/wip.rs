@@ -0,0 +1,160 @@
+use super::types::{TaskProperties, TaskStatus};
+use crate::error::CoreResult;
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::labels::PROJECT_LABEL;
+use mm_memory::{MemoryRepository, RelationshipDirection, TaskFilter};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// What a work-in-progress limit is counted over
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(tag = "scope", content = "name", rename_all = "snake_case")]
+pub enum WipScope {
+    Project(String),
+    Assignee(String),
+}
+
+/// More tasks are in progress than a limit allows
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+pub struct WipViolation {
+    #[serde(flatten)]
+    pub scope: WipScope,
+    pub limit: usize,
+    /// Tasks in progress within the scope, sorted by name
+    pub tasks: Vec<String>,
+}
+
+impl fmt::Display for WipViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (kind, name) = match &self.scope {
+            WipScope::Project(name) => ("project", name),
+            WipScope::Assignee(name) => ("assignee", name),
+        };
+        write!(
+            f,
+            "{kind} '{name}' has {} tasks in progress (limit {})",
+            self.tasks.len(),
+            self.limit
+        )
+    }
+}
+
+/// Violation of `limit` when `tasks` are in progress within `scope`
+pub(crate) fn check_limit(
+    scope: WipScope,
+    limit: Option<usize>,
+    mut tasks: Vec<String>,
+) -> Option<WipViolation> {
+    let limit = limit?;
+    tasks.sort();
+    tasks.dedup();
+    (tasks.len() > limit).then_some(WipViolation {
+        scope,
+        limit,
+        tasks,
+    })
+}
+
+/// Names of the in-progress tasks of `project`
+pub(crate) async fn in_progress_in_project<M, G>(
+    ports: &Ports<M, G>,
+    project: &str,
+) -> CoreResult<Vec<String>, M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let filter = TaskFilter {
+        statuses: vec![TaskStatus::InProgress.as_ref().to_string()],
+        ..TaskFilter::for_project(project)
+    };
+    let tasks = ports
+        .memory_service
+        .find_tasks_typed::<TaskProperties>(&filter)
+        .await?;
+    Ok(tasks.into_iter().map(|t| t.name).collect())
+}
+
+/// Names of the in-progress tasks of every assignee, across all projects
+pub(crate) async fn in_progress_by_assignee<M, G>(
+    ports: &Ports<M, G>,
+) -> CoreResult<BTreeMap<String, Vec<String>>, M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let mut by_assignee: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for task in ports
+        .memory_service
+        .typed::<TaskProperties>()
+        .list()
+        .await?
+    {
+        if task.properties.status != TaskStatus::InProgress {
+            continue;
+        }
+        if let Some(assignee) = task.properties.assignee {
+            by_assignee.entry(assignee).or_default().push(task.name);
+        }
+    }
+    Ok(by_assignee)
+}
+
+/// Projects containing the task `name`
+pub(crate) async fn projects_of_task<M, G>(
+    ports: &Ports<M, G>,
+    name: &str,
+) -> CoreResult<Vec<String>, M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let related = ports
+        .memory_service
+        .find_related_entities(
+            name,
+            Some("contains".to_string()),
+            Some(RelationshipDirection::Incoming),
+            1,
+        )
+        .await?;
+    Ok(related
+        .into_iter()
+        .filter(|e| e.labels.iter().any(|l| l == PROJECT_LABEL))
+        .map(|e| e.name)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_limit() {
+        let tasks = vec!["b".to_string(), "a".to_string(), "a".to_string()];
+        assert_eq!(
+            check_limit(WipScope::Project("p".to_string()), Some(2), tasks.clone()),
+            None
+        );
+        assert_eq!(
+            check_limit(WipScope::Project("p".to_string()), None, tasks.clone()),
+            None
+        );
+
+        let violation = check_limit(WipScope::Assignee("ann".to_string()), Some(1), tasks).unwrap();
+        assert_eq!(violation.tasks, ["a", "b"]);
+        assert_eq!(
+            violation.to_string(),
+            "assignee 'ann' has 2 tasks in progress (limit 1)"
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{
+    EntityUpdate, LabelMatchMode, MemoryEntity, MemoryError, MemoryRepository, MemoryValue,
+    PropertiesUpdate, ValidationError, ValidationErrorKind,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::instrument;
+
+/// Entity property naming the agent that created it
+pub const CREATED_BY_PROPERTY: &str = "created_by";
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct TransferOwnershipCommand {
+    /// Agent giving up the entities
+    pub from: String,
+    /// Agent taking them over
+    pub to: String,
+    /// Entities to transfer; every entity owned by `from` when empty
+    #[serde(default)]
+    pub names: Vec<String>,
+    /// Report the changes without applying them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Change made to one transferred entity
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct OwnershipChange {
+    pub name: String,
+    /// Name with the new agent's namespace, when the old one was embedded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_name: Option<String>,
+    /// Whether `created_by` was reassigned
+    pub created_by: bool,
+}
+
+pub type TransferOwnershipResult<E> = CoreResult<Vec<OwnershipChange>, E>;
+
+/// Change that moves `entity` from agent `from` to `to`, if it belongs to `from`
+fn ownership_change(entity: &MemoryEntity, from: &str, to: &str) -> Option<OwnershipChange> {
+    let created_by = matches!(
+        entity.properties.get(CREATED_BY_PROPERTY),
+        Some(MemoryValue::String(agent)) if agent == from
+    );
+    let new_name = entity
+        .name
+        .strip_prefix(from)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .map(|rest| format!("{to}:{rest}"));
+    (created_by || new_name.is_some()).then(|| OwnershipChange {
+        name: entity.name.clone(),
+        new_name,
+        created_by,
+    })
+}
+
+/// Reassign entities from one agent to another
+///
+/// An entity belongs to an agent when its `created_by` property names the
+/// agent or its name starts with the agent's namespace, e.g. `agent:task:x`.
+/// Both are rewritten; renamed entities keep their relationships. Nothing is
+/// changed when any new name is already taken.
+#[instrument(skip(ports), fields(from = %command.from, to = %command.to, names_count = command.names.len()))]
+pub async fn transfer_ownership<M, G>(
+    ports: &Ports<M, G>,
+    command: TransferOwnershipCommand,
+) -> TransferOwnershipResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.from);
+    validate_name!(command.to);
+    let invalid: Vec<_> = [&command.from, &command.to]
+        .into_iter()
+        .filter(|agent| agent.contains(':'))
+        .map(|agent| ValidationErrorKind::InvalidIdentifier(agent.clone()))
+        .collect();
+    if !invalid.is_empty() {
+        return Err(CoreError::Validation(ValidationError(invalid)));
+    }
+
+    let service = &ports.memory_service;
+    let entities = if command.names.is_empty() {
+        service
+            .find_entities_by_labels(&[], LabelMatchMode::Any, None)
+            .await
+            .map_err(CoreError::from)?
+    } else {
+        let mut entities = Vec::with_capacity(command.names.len());
+        for name in &command.names {
+            validate_name!(name);
+            let entity = service
+                .find_entity_by_name(name)
+                .await
+                .map_err(CoreError::from)?
+                .ok_or_else(|| CoreError::Memory(MemoryError::EntityNotFound(name.clone())))?;
+            entities.push(entity);
+        }
+        entities
+    };
+    let mut changes: Vec<OwnershipChange> = entities
+        .iter()
+        .filter_map(|e| ownership_change(e, &command.from, &command.to))
+        .collect();
+    changes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut taken = Vec::new();
+    for new_name in changes.iter().filter_map(|c| c.new_name.as_ref()) {
+        if service
+            .find_entity_by_name(new_name)
+            .await
+            .map_err(CoreError::from)?
+            .is_some()
+        {
+            taken.push(ValidationErrorKind::EntityExists(new_name.clone()));
+        }
+    }
+    if !taken.is_empty() {
+        return Err(CoreError::Validation(ValidationError(taken)));
+    }
+    if command.dry_run {
+        return Ok(changes);
+    }
+
+    let reassign = EntityUpdate {
+        properties: Some(PropertiesUpdate {
+            add: Some(HashMap::from([(
+                CREATED_BY_PROPERTY.to_string(),
+                MemoryValue::String(command.to.clone()),
+            )])),
+            remove: None,
+            set: None,
+        }),
+        ..Default::default()
+    };
+    for change in &changes {
+        if change.created_by {
+            service
+                .update_entity(&change.name, &reassign)
+                .await
+                .map_err(CoreError::from)?;
+        }
+        if let Some(new_name) = &change.new_name {
+            service
+                .rename_entity(&change.name, new_name)
+                .await
+                .map_err(CoreError::from)?;
+        }
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    fn entity(name: &str, created_by: Option<&str>) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            labels: vec!["Memory".to_string()],
+            properties: created_by
+                .map(|a| {
+                    HashMap::from([(
+                        CREATED_BY_PROPERTY.to_string(),
+                        MemoryValue::String(a.to_string()),
+                    )])
+                })
+                .unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ownership_change() {
+        let change = ownership_change(&entity("old:task:one", Some("old")), "old", "new");
+        assert_eq!(
+            change,
+            Some(OwnershipChange {
+                name: "old:task:one".to_string(),
+                new_name: Some("new:task:one".to_string()),
+                created_by: true,
+            })
+        );
+        let change = ownership_change(&entity("shared:note", Some("old")), "old", "new").unwrap();
+        assert_eq!(change.new_name, None);
+        assert!(ownership_change(&entity("older:task", None), "old", "new").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_rejects_taken_names_without_changes() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_by_labels()
+            .returning(|_, _, _| Ok(vec![entity("old:task:one", None)]));
+        mock.expect_find_entity_by_name()
+            .returning(|name| Ok(Some(entity(name, None))));
+        mock.expect_rename_entity().never();
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let command = TransferOwnershipCommand {
+            from: "old".to_string(),
+            to: "new".to_string(),
+            names: vec![],
+            dry_run: false,
+        };
+        let err = transfer_ownership(&ports, command).await.unwrap_err();
+        assert!(matches!(
+            err,
+            CoreError::Validation(ValidationError(ref kinds))
+                if kinds == &[ValidationErrorKind::EntityExists("new:task:one".to_string())]
+        ));
+    }
+}
@@ -0,0 +1,67 @@
+use super::common::handle_batch_result;
+use crate::error::CoreResult;
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{MemoryRepository, NamedEntityUpdate};
+use tracing::instrument;
+
+/// Command to update several entities at once
+#[derive(Debug, Clone)]
+pub struct UpdateEntitiesCommand {
+    pub updates: Vec<NamedEntityUpdate>,
+}
+
+pub type UpdateEntitiesResult<E> = CoreResult<(), E>;
+
+#[instrument(skip(ports), fields(updates_count = command.updates.len()))]
+pub async fn update_entities<M, G>(
+    ports: &Ports<M, G>,
+    command: UpdateEntitiesCommand,
+) -> UpdateEntitiesResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    handle_batch_result("updates", || {
+        ports.memory_service.update_entities(&command.updates)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::CoreError;
+    use mm_memory::{EntityUpdate, MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_update_entities_reports_invalid_items() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_update_entities()
+            .withf(|updates| updates.len() == 1 && updates[0].name == "a")
+            .times(1)
+            .returning(|_| Ok(()));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let command = UpdateEntitiesCommand {
+            updates: vec![
+                NamedEntityUpdate {
+                    name: "a".to_string(),
+                    update: EntityUpdate::default(),
+                },
+                NamedEntityUpdate {
+                    name: String::new(),
+                    update: EntityUpdate::default(),
+                },
+            ],
+        };
+        let result = update_entities(&ports, command).await;
+        assert!(matches!(
+            result,
+            Err(CoreError::BatchValidation(ref errs)) if errs.len() == 1 && errs[0].path == "updates[1]"
+        ));
+    }
+}
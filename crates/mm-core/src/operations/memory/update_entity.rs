@@ -1,15 +1,56 @@
-#[cfg(test)]
-use crate::error::CoreError;
-#[cfg(test)]
-use mm_memory::EntityUpdate;
+use super::generic::update_entity_generic;
+use super::observation_metadata::{ObservationMetadata, record_in_update};
+use crate::error::CoreResult;
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{EntityUpdate, MemoryRepository, ObservationSource};
+use tracing::instrument;
+
+/// Command to update an entity
+#[derive(Debug, Clone)]
+pub struct UpdateEntityCommand {
+    pub name: String,
+    pub update: EntityUpdate,
+    /// Confidence recorded for the observations the update writes
+    pub confidence: Option<f64>,
+    /// Source cited for the observations the update writes
+    pub source: Option<ObservationSource>,
+}
+
+pub type UpdateEntityResult<E> = CoreResult<(), E>;
 
-generate_update_wrapper!(UpdateEntityCommand, update_entity, UpdateEntityResult);
+#[instrument(skip(ports), fields(name = %command.name))]
+pub async fn update_entity<M, G>(
+    ports: &Ports<M, G>,
+    command: UpdateEntityCommand,
+) -> UpdateEntityResult<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    validate_name!(command.name);
+
+    let mut update = command.update;
+    let metadata = ObservationMetadata {
+        confidence: command.confidence,
+        source: command.source,
+    };
+    record_in_update(ports, &command.name, &mut update, &metadata).await?;
+    update_entity_generic(ports, &command.name, &update).await
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::CoreError;
     use crate::ports::Ports;
-    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use mm_memory::confidence::{observation_confidence, set_observation_confidence};
+    use mm_memory::{
+        MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository, ObservationsUpdate,
+    };
+    use std::collections::HashMap;
     use std::sync::Arc;
 
     #[tokio::test]
@@ -25,11 +66,58 @@ mod tests {
         let cmd = UpdateEntityCommand {
             name: "test:entity".into(),
             update: EntityUpdate::default(),
+            confidence: None,
+            source: None,
         };
         let res = update_entity(&ports, cmd).await;
         assert!(res.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_update_entity_records_confidence() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|_| {
+            let mut properties = HashMap::new();
+            set_observation_confidence(&mut properties, "old", 0.3);
+            Ok(Some(MemoryEntity {
+                name: "test:entity".into(),
+                observations: vec!["old".into()],
+                properties,
+                ..Default::default()
+            }))
+        });
+        mock.expect_update_entity()
+            .withf(|_, update| {
+                update
+                    .properties
+                    .as_ref()
+                    .and_then(|p| p.add.as_ref())
+                    .is_some_and(|add| {
+                        observation_confidence(add, "old") == 0.3
+                            && observation_confidence(add, "new") == 0.6
+                    })
+            })
+            .returning(|_, _| Ok(()));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| {
+            p.memory_service = Arc::new(service);
+        });
+        let cmd = UpdateEntityCommand {
+            name: "test:entity".into(),
+            update: EntityUpdate {
+                observations: Some(ObservationsUpdate {
+                    add: Some(vec!["new".into()]),
+                    remove: None,
+                    set: None,
+                }),
+                ..Default::default()
+            },
+            confidence: Some(0.6),
+            source: None,
+        };
+        update_entity(&ports, cmd).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_update_entity_empty_name() {
         let mut mock = MockMemoryRepository::new();
@@ -41,6 +129,8 @@ mod tests {
         let cmd = UpdateEntityCommand {
             name: "".into(),
             update: EntityUpdate::default(),
+            confidence: None,
+            source: None,
         };
         let res = update_entity(&ports, cmd).await;
         assert!(matches!(res, Err(CoreError::Validation(_))));
@@ -0,0 +1,159 @@
+use super::tasks::{TaskInput, preview_tasks};
+use crate::error::{CoreError, CoreResult};
+use crate::ports::Ports;
+use mm_git::GitRepository;
+use mm_memory::{MemoryEntity, MemoryRelationship, MemoryRepository};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+/// Payloads of `create_entities`, `create_relationships` and `create_tasks`
+/// to check
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct ValidateOnlyCommand {
+    /// Project whose vocabulary applies; the default project when unset
+    #[serde(default)]
+    pub project_name: Option<String>,
+    #[serde(default)]
+    pub entities: Vec<MemoryEntity>,
+    /// Checked as if created together with `entities`
+    #[serde(default)]
+    pub relationships: Vec<MemoryRelationship>,
+    #[serde(default)]
+    pub tasks: Vec<TaskInput>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct ValidateOnlyResult {
+    /// Whether every item would be accepted
+    pub valid: bool,
+    /// Problems found, each starting with the location of the offending
+    /// input, e.g. `tasks[0].depends_on[1]`
+    pub errors: Vec<String>,
+    /// Valid entities and tasks as they would be stored, with defaults
+    /// applied and secrets redacted
+    pub entities: Vec<MemoryEntity>,
+    /// Valid relationships as they would be stored
+    pub relationships: Vec<MemoryRelationship>,
+}
+
+pub type ValidateOnlyResultType<E> = CoreResult<ValidateOnlyResult, E>;
+
+/// Run the validation of the create operations without writing anything
+#[instrument(skip(ports, command), fields(entities_count = command.entities.len(), rel_count = command.relationships.len(), tasks_count = command.tasks.len()))]
+pub async fn validate_only<M, G>(
+    ports: &Ports<M, G>,
+    command: ValidateOnlyCommand,
+) -> ValidateOnlyResultType<M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+    G::Error: std::error::Error + Send + Sync + 'static,
+{
+    let mut result = ValidateOnlyResult::default();
+    if !command.entities.is_empty() || !command.relationships.is_empty() {
+        let preview = ports
+            .memory_service
+            .preview_project_graph_typed(
+                command.project_name.as_deref(),
+                &command.entities,
+                &command.relationships,
+            )
+            .await?;
+        let entity_errors = preview.errors.entities.into_iter();
+        let relationship_errors = preview.errors.relationships.into_iter();
+        result.errors.extend(
+            entity_errors
+                .map(|e| e.in_field("entities").to_string())
+                .chain(relationship_errors.map(|e| e.in_field("relationships").to_string())),
+        );
+        result.entities = preview.entities;
+        result.relationships = preview.relationships;
+    }
+    if !command.tasks.is_empty() {
+        match preview_tasks(ports, command.project_name.clone(), &command.tasks).await {
+            Ok(preview) => {
+                result.entities.extend(preview.entities);
+                result.relationships.extend(preview.relationships);
+            }
+            Err(CoreError::BatchValidation(errors)) => {
+                result.errors.extend(errors.iter().map(ToString::to_string));
+            }
+            Err(CoreError::Validation(error)) => result.errors.push(error.to_string()),
+            Err(CoreError::MissingProject) => {
+                result
+                    .errors
+                    .push("tasks: no project specified".to_string());
+            }
+            Err(error) => return Err(error),
+        }
+    }
+    result.valid = result.errors.is_empty();
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_validate_only_reports_without_writing() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_create_graph().never();
+        mock.expect_create_entities().never();
+        let service = MemoryService::new(
+            mock,
+            MemoryConfig {
+                default_label: Some("Memory".to_string()),
+                ..MemoryConfig::default()
+            },
+        );
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let command = ValidateOnlyCommand {
+            entities: vec![
+                MemoryEntity {
+                    name: "a".to_string(),
+                    ..Default::default()
+                },
+                MemoryEntity::default(),
+            ],
+            relationships: vec![MemoryRelationship {
+                from: "a".to_string(),
+                to: "a".to_string(),
+                name: "relates_to".to_string(),
+                properties: HashMap::new(),
+            }],
+            ..Default::default()
+        };
+
+        let result = validate_only(&ports, command).await.unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.errors.len(), 2);
+        assert!(result.errors[0].starts_with("entities[1]"));
+        assert!(result.errors[1].starts_with("relationships[0]"));
+        assert_eq!(result.entities.len(), 1);
+        assert_eq!(result.entities[0].labels, ["Memory"]);
+    }
+
+    #[tokio::test]
+    async fn test_validate_only_tasks_need_a_project() {
+        let ports = Ports::noop();
+        let command = ValidateOnlyCommand {
+            tasks: vec![TaskInput {
+                task: MemoryEntity {
+                    name: "task:1".to_string(),
+                    ..Default::default()
+                },
+                depends_on: vec![],
+            }],
+            ..Default::default()
+        };
+
+        let result = validate_only(&ports, command).await.unwrap();
+        assert_eq!(result.errors, ["tasks: no project specified"]);
+    }
+}
@@ -0,0 +1,55 @@
+use mm_git::GitRepository;
+use mm_memory::MemoryRepository;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::error::CoreResult;
+use crate::ports::Ports;
+use crate::root::Root;
+
+/// Command for listing the client roots known to the server
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct ListRootsCommand {}
+
+/// Result of listing client roots
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct ListRootsResult {
+    /// Normalized roots in the order the client reported them
+    pub roots: Vec<Root>,
+}
+
+/// List the workspace roots the server is currently using
+#[instrument(skip(ports))]
+pub async fn list_roots<M, G>(
+    ports: &Ports<M, G>,
+    _command: ListRootsCommand,
+) -> CoreResult<ListRootsResult, M::Error>
+where
+    M: MemoryRepository + Send + Sync,
+    G: GitRepository + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+{
+    let roots = ports.roots.read().await.roots().to_vec();
+    Ok(ListRootsResult { roots })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RootCollection;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    #[tokio::test]
+    async fn test_list_roots() {
+        let root = Root::new(Some("repo".to_string()), "file:///tmp/repo".to_string());
+        let collection = RootCollection::from_roots(vec![root.clone()]);
+        let ports = Ports::noop().with(|p| p.roots = Arc::new(RwLock::new(collection)));
+
+        let result = list_roots(&ports, ListRootsCommand::default())
+            .await
+            .unwrap();
+        assert_eq!(result.roots, vec![root]);
+    }
+}
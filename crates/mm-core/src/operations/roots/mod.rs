@@ -0,0 +1,3 @@
+pub mod list_roots;
+
+pub use list_roots::{ListRootsCommand, ListRootsResult, list_roots};
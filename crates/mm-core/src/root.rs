@@ -1,6 +1,14 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use url::Url;
+
+/// Workspace root advertised by the client
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct Root {
+    /// Optional human-readable name of the root
     pub name: Option<String>,
+    /// `file://` URI of the root directory
     pub uri: String,
 }
 
@@ -8,6 +16,11 @@ impl Root {
     pub fn new(name: Option<String>, uri: String) -> Self {
         Self { name, uri }
     }
+
+    /// Local filesystem path of the root, if the URI is a valid `file://` URI
+    pub fn path(&self) -> Option<PathBuf> {
+        Url::parse(&self.uri).ok()?.to_file_path().ok()
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -51,4 +64,12 @@ mod tests {
         coll.set_roots(vec![]);
         assert!(coll.roots().is_empty());
     }
+
+    #[test]
+    fn test_root_path() {
+        let root = Root::new(None, "file:///tmp/my%20project".to_string());
+        assert_eq!(root.path(), Some(PathBuf::from("/tmp/my project")));
+        let remote = Root::new(None, "https://example.com/repo".to_string());
+        assert_eq!(remote.path(), None);
+    }
 }
@@ -0,0 +1,61 @@
+use crate::operations::memory::WipViolation;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Soft issue noticed by an operation that still succeeded
+///
+/// Warnings are returned alongside the result so callers can react to them
+/// without the call failing.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Warning {
+    /// The entity had no labels, so the configured default label was applied
+    DefaultLabelApplied { entity: String, label: String },
+    /// A task transition exceeded a work-in-progress limit
+    WipLimitExceeded(WipViolation),
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::DefaultLabelApplied { entity, label } => {
+                write!(
+                    f,
+                    "'{entity}' had no labels; default label '{label}' applied"
+                )
+            }
+            Warning::WipLimitExceeded(violation) => write!(f, "{violation}"),
+        }
+    }
+}
+
+/// Results of operations that may carry warnings
+pub trait HasWarnings {
+    fn warnings(&self) -> &[Warning] {
+        &[]
+    }
+}
+
+impl HasWarnings for () {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warning_serializes_with_kind() {
+        let warning = Warning::DefaultLabelApplied {
+            entity: "note:a".to_string(),
+            label: "Memory".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&warning).unwrap(),
+            serde_json::json!({"kind": "default_label_applied", "entity": "note:a", "label": "Memory"})
+        );
+        assert_eq!(
+            warning.to_string(),
+            "'note:a' had no labels; default label 'Memory' applied"
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Resolution of tool-supplied paths against the client roots.
+
+use mm_utils::normalize_path;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::root::RootCollection;
+
+/// Errors produced while resolving a workspace path
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum WorkspacePathError {
+    /// A relative path was given without roots or a working directory
+    #[error("Cannot resolve relative path '{0}' without a workspace root")]
+    NoRoots(PathBuf),
+
+    /// The path resolves to a location outside every client root
+    #[error("Path '{0}' is outside the workspace roots")]
+    OutsideRoots(PathBuf),
+}
+
+/// Resolve `path` against the client roots.
+///
+/// Relative paths are joined onto the first root, so agents can pass `"."`
+/// or `"crates/mm-core"`. The result is normalized lexically and must stay
+/// inside one of the roots; when the path exists, symlinks are resolved and
+/// checked as well. When the client did not provide any roots, absolute paths
+/// are accepted as-is and relative ones are joined onto the current
+/// directory, which keeps the CLI and root-less clients working.
+pub fn resolve_workspace_path(
+    roots: &RootCollection,
+    path: &Path,
+) -> Result<PathBuf, WorkspacePathError> {
+    let root_paths: Vec<PathBuf> = roots
+        .roots()
+        .iter()
+        .filter_map(|r| r.path())
+        .map(|p| normalize_path(&p))
+        .collect();
+
+    if root_paths.is_empty() {
+        if path.is_absolute() {
+            return Ok(normalize_path(path));
+        }
+        return std::env::current_dir()
+            .map(|cwd| normalize_path(&cwd.join(path)))
+            .map_err(|_| WorkspacePathError::NoRoots(path.to_path_buf()));
+    }
+
+    let resolved = if path.is_absolute() {
+        normalize_path(path)
+    } else {
+        normalize_path(&root_paths[0].join(path))
+    };
+
+    if !root_paths.iter().any(|root| resolved.starts_with(root)) {
+        return Err(WorkspacePathError::OutsideRoots(path.to_path_buf()));
+    }
+
+    // Lexical checks cannot see symlinks pointing elsewhere; compare the
+    // canonical forms for paths that exist on disk.
+    if let Ok(canonical) = resolved.canonicalize() {
+        let inside = root_paths
+            .iter()
+            .filter_map(|root| root.canonicalize().ok())
+            .any(|root| canonical.starts_with(root));
+        if !inside {
+            return Err(WorkspacePathError::OutsideRoots(path.to_path_buf()));
+        }
+    }
+
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::root::Root;
+
+    fn roots(uris: &[&str]) -> RootCollection {
+        RootCollection::from_roots(
+            uris.iter()
+                .map(|u| Root::new(None, u.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_relative_paths_use_first_root() {
+        let roots = roots(&["file:///work/repo", "file:///work/other"]);
+        assert_eq!(
+            resolve_workspace_path(&roots, Path::new(".")).unwrap(),
+            PathBuf::from("/work/repo")
+        );
+        assert_eq!(
+            resolve_workspace_path(&roots, Path::new("crates/mm-core")).unwrap(),
+            PathBuf::from("/work/repo/crates/mm-core")
+        );
+        assert_eq!(
+            resolve_workspace_path(&roots, Path::new("/work/other/src")).unwrap(),
+            PathBuf::from("/work/other/src")
+        );
+    }
+
+    #[test]
+    fn test_traversal_is_rejected() {
+        let roots = roots(&["file:///work/repo"]);
+        assert_eq!(
+            resolve_workspace_path(&roots, Path::new("../secret")),
+            Err(WorkspacePathError::OutsideRoots(PathBuf::from("../secret")))
+        );
+        assert!(resolve_workspace_path(&roots, Path::new("/etc")).is_err());
+        assert!(resolve_workspace_path(&roots, Path::new("/work/repository")).is_err());
+        assert!(resolve_workspace_path(&roots, Path::new("a/../../repo/b")).is_ok());
+    }
+
+    #[test]
+    fn test_without_roots() {
+        let roots = RootCollection::default();
+        assert_eq!(
+            resolve_workspace_path(&roots, Path::new("/tmp/repo/.")).unwrap(),
+            PathBuf::from("/tmp/repo")
+        );
+    }
+
+    #[test]
+    fn test_relative_paths_without_roots_use_cwd() {
+        let roots = RootCollection::default();
+        let cwd = std::env::current_dir().unwrap();
+        assert_eq!(
+            resolve_workspace_path(&roots, Path::new(".")).unwrap(),
+            normalize_path(&cwd)
+        );
+        assert_eq!(
+            resolve_workspace_path(&roots, Path::new("src/../Cargo.toml")).unwrap(),
+            normalize_path(&cwd.join("Cargo.toml"))
+        );
+    }
+}
@@ -0,0 +1,17 @@
+//! In-memory memory repository for the Middle Manager project.
+//!
+//! This crate is an adapter like `mm-memory-neo4j`, but keeps the graph in
+//! process instead of talking to a database. It is meant for demos, local
+//! experiments and integration tests that should not need a running Neo4j
+//! instance. The graph is lost when the repository is dropped.
+#![warn(clippy::all)]
+mod repository;
+
+pub use repository::InMemoryRepository;
+
+use mm_memory::{MemoryConfig, MemoryService};
+
+/// Create a memory service backed by an empty [`InMemoryRepository`]
+pub fn create_inmem_service(config: MemoryConfig) -> MemoryService<InMemoryRepository> {
+    MemoryService::new(InMemoryRepository::new(), config)
+}
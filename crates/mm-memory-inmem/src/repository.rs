@@ -0,0 +1,688 @@
+use async_trait::async_trait;
+use mm_memory::relationship::RelationshipRef;
+use mm_memory::source::SOURCES_PROPERTY;
+use mm_memory::{
+    BackendInfo, EntityUpdate, LabelMatchMode, MemoryEntity, MemoryRelationship, MemoryRepository,
+    MemoryResult, MemoryValue, NamedEntityUpdate, PropertiesUpdate, RelationshipDirection,
+    RelationshipUpdate, ValidationError, ValidationErrorKind,
+};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::convert::Infallible;
+use std::sync::{Mutex, MutexGuard};
+
+#[derive(Debug, Default)]
+struct Graph {
+    /// Entities keyed by name, so listings come back sorted by name
+    entities: BTreeMap<String, MemoryEntity>,
+    relationships: Vec<MemoryRelationship>,
+}
+
+impl Graph {
+    fn entity_mut(&mut self, name: &str) -> Option<&mut MemoryEntity> {
+        self.entities.get_mut(name)
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.entities.contains_key(name)
+    }
+
+    /// Store `entities`, replacing any with the same name
+    ///
+    /// A replaced entity's revision keeps counting up from the stored one.
+    fn insert_entities(&mut self, entities: &[MemoryEntity]) {
+        for entity in entities {
+            let revision = self
+                .entities
+                .get(&entity.name)
+                .map_or(1, |stored| stored.revision + 1)
+                .max(entity.revision);
+            self.entities.insert(
+                entity.name.clone(),
+                MemoryEntity {
+                    relationships: vec![],
+                    revision,
+                    ..entity.clone()
+                },
+            );
+        }
+    }
+
+    /// Store the `relationships` whose endpoints both exist
+    fn insert_relationships(&mut self, relationships: &[MemoryRelationship]) {
+        for rel in relationships {
+            if self.contains(&rel.from) && self.contains(&rel.to) {
+                self.relationships.push(rel.clone());
+            }
+        }
+    }
+
+    /// Check the revision `update` expects `name` to be at
+    fn check_revision(&self, name: &str, update: &EntityUpdate) -> MemoryResult<(), Infallible> {
+        update.check_revision(name, self.entities.get(name).map(|e| e.revision))
+    }
+
+    /// Apply `update` to `name` and bump its revision; missing entities are
+    /// ignored
+    fn update_entity(&mut self, name: &str, update: &EntityUpdate) {
+        let Some(entity) = self.entity_mut(name) else {
+            return;
+        };
+
+        if let Some(obs) = &update.observations {
+            obs.apply_to(&mut entity.observations);
+        }
+
+        if let Some(props) = &update.properties {
+            InMemoryRepository::apply_properties(&mut entity.properties, props);
+        }
+
+        if let Some(labels) = &update.labels {
+            if let Some(add) = &labels.add {
+                for label in add {
+                    if !entity.labels.contains(label) {
+                        entity.labels.push(label.clone());
+                    }
+                }
+            } else if let Some(remove) = &labels.remove {
+                entity.labels.retain(|l| !remove.contains(l));
+            }
+        }
+        entity.revision += 1;
+    }
+
+    /// Clone an entity with the relationships touching it attached
+    fn hydrate(&self, entity: &MemoryEntity) -> MemoryEntity {
+        let mut entity = entity.clone();
+        entity.relationships = self
+            .relationships
+            .iter()
+            .filter(|r| r.from == entity.name || r.to == entity.name)
+            .cloned()
+            .collect();
+        entity
+    }
+}
+
+/// [`MemoryRepository`] keeping the whole graph in memory
+///
+/// Nothing is persisted, which makes it a fit for demos and tests that should
+/// not need a database. Behaves like the Neo4j adapter for the operations the
+/// services rely on: writes against missing entities are silently ignored,
+/// relationships are only created between existing entities and deleting an
+/// entity drops the relationships touching it. Creating an entity whose name
+/// already exists replaces the stored one.
+#[derive(Debug, Default)]
+pub struct InMemoryRepository {
+    graph: Mutex<Graph>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seed the repository with `entities`
+    ///
+    /// Relationships listed on the entities are stored as well.
+    pub fn with_entities(self, entities: impl IntoIterator<Item = MemoryEntity>) -> Self {
+        {
+            let mut graph = self.lock();
+            for mut entity in entities {
+                let relationships = std::mem::take(&mut entity.relationships);
+                graph.entities.insert(entity.name.clone(), entity);
+                graph.relationships.extend(relationships);
+            }
+        }
+        self
+    }
+
+    /// Seed the repository with `relationships`
+    pub fn with_relationships(
+        self,
+        relationships: impl IntoIterator<Item = MemoryRelationship>,
+    ) -> Self {
+        self.lock().relationships.extend(relationships);
+        self
+    }
+
+    /// Snapshot of every stored entity with its relationships attached
+    pub fn entities(&self) -> Vec<MemoryEntity> {
+        let graph = self.lock();
+        graph.entities.values().map(|e| graph.hydrate(e)).collect()
+    }
+
+    /// Snapshot of every stored relationship
+    pub fn relationships(&self) -> Vec<MemoryRelationship> {
+        self.lock().relationships.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Graph> {
+        // A panicking caller must not poison the graph for the tasks that
+        // follow.
+        self.graph.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn require_name(name: &str) -> MemoryResult<(), Infallible> {
+        if name.is_empty() {
+            return Err(ValidationError::from(ValidationErrorKind::EmptyEntityName).into());
+        }
+        Ok(())
+    }
+
+    fn apply_properties(
+        properties: &mut HashMap<String, mm_memory::MemoryValue>,
+        update: &PropertiesUpdate,
+    ) {
+        if let Some(add) = &update.add {
+            properties.extend(add.clone());
+        } else if let Some(remove) = &update.remove {
+            for key in remove {
+                properties.remove(key);
+            }
+        } else if let Some(set) = &update.set {
+            *properties = set.clone();
+        }
+    }
+
+    fn edit_observations(&self, name: &str, edit: impl FnOnce(&mut Vec<String>)) {
+        if let Some(entity) = self.lock().entity_mut(name) {
+            edit(&mut entity.observations);
+            entity.revision += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl MemoryRepository for InMemoryRepository {
+    type Error = Infallible;
+
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            backend: "inmem".to_string(),
+            database: None,
+        }
+    }
+
+    async fn create_entities(&self, entities: &[MemoryEntity]) -> MemoryResult<(), Self::Error> {
+        self.lock().insert_entities(entities);
+        Ok(())
+    }
+
+    async fn find_entity_by_name(
+        &self,
+        name: &str,
+    ) -> MemoryResult<Option<MemoryEntity>, Self::Error> {
+        Self::require_name(name)?;
+        let graph = self.lock();
+        Ok(graph.entities.get(name).map(|e| graph.hydrate(e)))
+    }
+
+    async fn find_existing_names(
+        &self,
+        names: &[String],
+    ) -> MemoryResult<Vec<String>, Self::Error> {
+        let graph = self.lock();
+        Ok(names
+            .iter()
+            .filter(|name| graph.entities.contains_key(name.as_str()))
+            .cloned()
+            .collect())
+    }
+
+    async fn set_observations(
+        &self,
+        name: &str,
+        observations: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        Self::require_name(name)?;
+        self.edit_observations(name, |current| *current = observations.to_vec());
+        Ok(())
+    }
+
+    async fn add_observations(
+        &self,
+        name: &str,
+        observations: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        Self::require_name(name)?;
+        self.edit_observations(name, |current| {
+            for observation in observations {
+                if !current.contains(observation) {
+                    current.push(observation.clone());
+                }
+            }
+        });
+        Ok(())
+    }
+
+    async fn remove_all_observations(&self, name: &str) -> MemoryResult<(), Self::Error> {
+        self.set_observations(name, &[]).await
+    }
+
+    async fn remove_observations(
+        &self,
+        name: &str,
+        observations: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        Self::require_name(name)?;
+        self.edit_observations(name, |current| {
+            current.retain(|o| !observations.contains(o))
+        });
+        Ok(())
+    }
+
+    async fn create_relationships(
+        &self,
+        relationships: &[MemoryRelationship],
+    ) -> MemoryResult<(), Self::Error> {
+        self.lock().insert_relationships(relationships);
+        Ok(())
+    }
+
+    async fn create_graph(
+        &self,
+        entities: &[MemoryEntity],
+        relationships: &[MemoryRelationship],
+    ) -> MemoryResult<(), Self::Error> {
+        let mut graph = self.lock();
+        graph.insert_entities(entities);
+        graph.insert_relationships(relationships);
+        Ok(())
+    }
+
+    async fn delete_entities(&self, names: &[String]) -> MemoryResult<(), Self::Error> {
+        let mut graph = self.lock();
+        for name in names {
+            graph.entities.remove(name);
+        }
+        graph
+            .relationships
+            .retain(|r| !names.contains(&r.from) && !names.contains(&r.to));
+        Ok(())
+    }
+
+    async fn delete_relationships(
+        &self,
+        relationships: &[RelationshipRef],
+    ) -> MemoryResult<(), Self::Error> {
+        self.lock().relationships.retain(|r| {
+            !relationships
+                .iter()
+                .any(|d| d.from == r.from && d.to == r.to && d.name == r.name)
+        });
+        Ok(())
+    }
+
+    async fn find_relationships(
+        &self,
+        from: Option<String>,
+        to: Option<String>,
+        name: Option<String>,
+    ) -> MemoryResult<Vec<MemoryRelationship>, Self::Error> {
+        Ok(self
+            .lock()
+            .relationships
+            .iter()
+            .filter(|r| from.as_ref().is_none_or(|f| &r.from == f))
+            .filter(|r| to.as_ref().is_none_or(|t| &r.to == t))
+            .filter(|r| name.as_ref().is_none_or(|n| &r.name == n))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_entities_by_labels(
+        &self,
+        labels: &[String],
+        match_mode: LabelMatchMode,
+        required_label: Option<String>,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        let graph = self.lock();
+        Ok(graph
+            .entities
+            .values()
+            .filter(|e| required_label.as_ref().is_none_or(|l| e.labels.contains(l)))
+            .filter(|e| {
+                labels.is_empty()
+                    || match match_mode {
+                        LabelMatchMode::Any => labels.iter().any(|l| e.labels.contains(l)),
+                        LabelMatchMode::All => labels.iter().all(|l| e.labels.contains(l)),
+                    }
+            })
+            .map(|e| graph.hydrate(e))
+            .collect())
+    }
+
+    async fn find_entities_by_source(
+        &self,
+        source_key: &str,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        let graph = self.lock();
+        Ok(graph
+            .entities
+            .values()
+            .filter(|e| match e.properties.get(SOURCES_PROPERTY) {
+                Some(MemoryValue::List(keys)) => keys.iter().any(|k| k == source_key),
+                _ => false,
+            })
+            .map(|e| graph.hydrate(e))
+            .collect())
+    }
+
+    async fn list_labels(&self) -> MemoryResult<Vec<(String, u64)>, Self::Error> {
+        let graph = self.lock();
+        let mut counts = BTreeMap::new();
+        for label in graph.entities.values().flat_map(|e| &e.labels) {
+            *counts.entry(label.clone()).or_insert(0) += 1;
+        }
+        Ok(counts.into_iter().collect())
+    }
+
+    async fn list_relationship_types(&self) -> MemoryResult<Vec<(String, u64)>, Self::Error> {
+        let graph = self.lock();
+        let mut counts = BTreeMap::new();
+        for rel in &graph.relationships {
+            *counts.entry(rel.name.clone()).or_insert(0) += 1;
+        }
+        Ok(counts.into_iter().collect())
+    }
+
+    async fn find_related_entities(
+        &self,
+        name: &str,
+        relationship_type: Option<String>,
+        direction: Option<RelationshipDirection>,
+        depth: u32,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        Self::require_name(name)?;
+        let direction = direction.unwrap_or(RelationshipDirection::Both);
+        let graph = self.lock();
+        if !graph.contains(name) {
+            return Ok(vec![]);
+        }
+
+        // Breadth-first walk so entities come back ordered by distance.
+        let mut seen: HashSet<&str> = HashSet::from([name]);
+        let mut found = Vec::new();
+        let mut queue = VecDeque::from([(name, 0u32)]);
+        while let Some((current, distance)) = queue.pop_front() {
+            if distance == depth {
+                continue;
+            }
+            let neighbours = graph
+                .relationships
+                .iter()
+                .filter(|r| relationship_type.as_ref().is_none_or(|t| &r.name == t))
+                .filter_map(|r| match direction {
+                    RelationshipDirection::Outgoing => (r.from == current).then_some(r.to.as_str()),
+                    RelationshipDirection::Incoming => (r.to == current).then_some(r.from.as_str()),
+                    RelationshipDirection::Both if r.from == current => Some(r.to.as_str()),
+                    RelationshipDirection::Both => (r.to == current).then_some(r.from.as_str()),
+                });
+            for next in neighbours {
+                if seen.insert(next) {
+                    found.push(next);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+
+        Ok(found
+            .into_iter()
+            .filter_map(|n| graph.entities.get(n))
+            .map(|e| graph.hydrate(e))
+            .collect())
+    }
+
+    async fn rename_entity(&self, old: &str, new: &str) -> MemoryResult<(), Self::Error> {
+        Self::require_name(old)?;
+        Self::require_name(new)?;
+        let mut graph = self.lock();
+        let Some(mut entity) = graph.entities.remove(old) else {
+            return Ok(());
+        };
+        entity.name = new.to_string();
+        entity.revision += 1;
+        graph.entities.insert(new.to_string(), entity);
+        for rel in &mut graph.relationships {
+            if rel.from == old {
+                rel.from = new.to_string();
+            }
+            if rel.to == old {
+                rel.to = new.to_string();
+            }
+        }
+        Ok(())
+    }
+
+    async fn merge_entities(
+        &self,
+        merged: &MemoryEntity,
+        sources: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        Self::require_name(&merged.name)?;
+        let mut graph = self.lock();
+        for name in sources {
+            graph.entities.remove(name);
+        }
+        let touched = |name: &String| name == &merged.name || sources.contains(name);
+        graph
+            .relationships
+            .retain(|r| !touched(&r.from) && !touched(&r.to));
+        graph.insert_entities(std::slice::from_ref(merged));
+        graph.insert_relationships(&merged.relationships);
+        Ok(())
+    }
+
+    async fn update_entity(
+        &self,
+        name: &str,
+        update: &EntityUpdate,
+    ) -> MemoryResult<(), Self::Error> {
+        Self::require_name(name)?;
+        let mut graph = self.lock();
+        graph.check_revision(name, update)?;
+        graph.update_entity(name, update);
+        Ok(())
+    }
+
+    /// Checks every expected revision before applying any update
+    async fn update_entities(
+        &self,
+        updates: &[NamedEntityUpdate],
+    ) -> MemoryResult<(), Self::Error> {
+        for item in updates {
+            Self::require_name(&item.name)?;
+        }
+        let mut graph = self.lock();
+        for item in updates {
+            graph.check_revision(&item.name, &item.update)?;
+        }
+        for item in updates {
+            graph.update_entity(&item.name, &item.update);
+        }
+        Ok(())
+    }
+
+    async fn update_relationship(
+        &self,
+        from: &str,
+        to: &str,
+        name: &str,
+        update: &RelationshipUpdate,
+    ) -> MemoryResult<(), Self::Error> {
+        if let Some(props) = &update.properties {
+            let mut graph = self.lock();
+            for rel in graph
+                .relationships
+                .iter_mut()
+                .filter(|r| r.from == from && r.to == to && r.name == name)
+            {
+                Self::apply_properties(&mut rel.properties, props);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::{MemoryError, ObservationsUpdate};
+
+    fn entity(name: &str) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            labels: vec!["Example".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn rel(from: &str, to: &str) -> MemoryRelationship {
+        MemoryRelationship {
+            from: from.to_string(),
+            to: to.to_string(),
+            name: "relates_to".to_string(),
+            properties: HashMap::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn relationships_require_both_endpoints() {
+        let repo = InMemoryRepository::new().with_entities([entity("a"), entity("b")]);
+        repo.create_relationships(&[rel("a", "b"), rel("a", "missing")])
+            .await
+            .unwrap();
+
+        assert_eq!(repo.relationships(), vec![rel("a", "b")]);
+        let a = repo.find_entity_by_name("a").await.unwrap().unwrap();
+        assert_eq!(a.relationships, vec![rel("a", "b")]);
+    }
+
+    #[tokio::test]
+    async fn stale_updates_are_rejected_unapplied() {
+        let repo = InMemoryRepository::new().with_entities([entity("a")]);
+        let revision = repo
+            .find_entity_by_name("a")
+            .await
+            .unwrap()
+            .unwrap()
+            .revision;
+        let update = |expected| EntityUpdate {
+            observations: Some(ObservationsUpdate {
+                add: Some(vec!["seen".to_string()]),
+                remove: None,
+                set: None,
+            }),
+            expected_revision: Some(expected),
+            ..Default::default()
+        };
+
+        let err = repo.update_entity("a", &update(revision + 1)).await;
+        assert!(matches!(err, Err(MemoryError::RevisionConflict { .. })));
+        let a = repo.find_entity_by_name("a").await.unwrap().unwrap();
+        assert!(a.observations.is_empty());
+
+        repo.update_entity("a", &update(revision)).await.unwrap();
+        let a = repo.find_entity_by_name("a").await.unwrap().unwrap();
+        assert_eq!(a.observations, ["seen"]);
+        assert_eq!(a.revision, revision + 1);
+
+        let missing = repo.update_entity("b", &update(revision)).await;
+        assert!(matches!(missing, Err(MemoryError::EntityNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn batch_updates_apply_all_or_nothing() {
+        let repo = InMemoryRepository::new().with_entities([entity("a"), entity("b")]);
+        let item = |name: &str, expected| NamedEntityUpdate {
+            name: name.to_string(),
+            update: EntityUpdate {
+                observations: Some(ObservationsUpdate {
+                    add: Some(vec!["seen".to_string()]),
+                    remove: None,
+                    set: None,
+                }),
+                expected_revision: expected,
+                ..Default::default()
+            },
+        };
+        let observations = |repo: &InMemoryRepository| -> Vec<Vec<String>> {
+            repo.entities()
+                .into_iter()
+                .map(|e| e.observations)
+                .collect()
+        };
+
+        let stale = repo
+            .update_entities(&[item("a", None), item("b", Some(7))])
+            .await;
+        assert!(matches!(stale, Err(MemoryError::RevisionConflict { .. })));
+        assert_eq!(observations(&repo), [Vec::<String>::new(), vec![]]);
+
+        repo.update_entities(&[item("a", None), item("b", Some(0))])
+            .await
+            .unwrap();
+        assert_eq!(observations(&repo), [["seen"], ["seen"]]);
+    }
+
+    #[tokio::test]
+    async fn related_entities_respect_direction_and_depth() {
+        let repo = InMemoryRepository::new()
+            .with_entities([entity("a"), entity("b"), entity("c")])
+            .with_relationships([rel("a", "b"), rel("b", "c")]);
+
+        let names =
+            |entities: Vec<MemoryEntity>| entities.into_iter().map(|e| e.name).collect::<Vec<_>>();
+        let out = repo
+            .find_related_entities("a", None, Some(RelationshipDirection::Outgoing), 1)
+            .await
+            .unwrap();
+        assert_eq!(names(out), ["b"]);
+
+        let out = repo
+            .find_related_entities("a", None, Some(RelationshipDirection::Outgoing), 2)
+            .await
+            .unwrap();
+        assert_eq!(names(out), ["b", "c"]);
+
+        let out = repo
+            .find_related_entities("c", None, Some(RelationshipDirection::Outgoing), 2)
+            .await
+            .unwrap();
+        assert!(out.is_empty());
+
+        let out = repo
+            .find_related_entities("c", None, None, 2)
+            .await
+            .unwrap();
+        assert_eq!(names(out), ["b", "a"]);
+    }
+
+    #[tokio::test]
+    async fn writes_bump_revision() {
+        let repo = InMemoryRepository::new();
+        repo.create_entities(&[entity("a")]).await.unwrap();
+        repo.add_observations("a", &["x".to_string()])
+            .await
+            .unwrap();
+        repo.rename_entity("a", "b").await.unwrap();
+        let b = repo.find_entity_by_name("b").await.unwrap().unwrap();
+        assert_eq!(b.revision, 3);
+
+        // Replacing an entity does not reset its revision
+        repo.create_entities(&[entity("b")]).await.unwrap();
+        let b = repo.find_entity_by_name("b").await.unwrap().unwrap();
+        assert_eq!(b.revision, 4);
+    }
+
+    #[tokio::test]
+    async fn delete_entities_drops_relationships() {
+        let repo = InMemoryRepository::new()
+            .with_entities([entity("a"), entity("b")])
+            .with_relationships([rel("a", "b")]);
+
+        repo.delete_entities(&["b".to_string()]).await.unwrap();
+        assert!(repo.relationships().is_empty());
+        assert_eq!(repo.entities().len(), 1);
+    }
+}
@@ -0,0 +1,9 @@
+use mm_memory::test_suite::run_memory_service_test_suite;
+use mm_memory_inmem::InMemoryRepository;
+
+#[tokio::test]
+async fn in_memory_repository_passes_memory_test_suite() {
+    run_memory_service_test_suite(InMemoryRepository::new())
+        .await
+        .unwrap();
+}
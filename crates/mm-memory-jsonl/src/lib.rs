@@ -0,0 +1,26 @@
+//! Append-only JSON Lines memory repository for the Middle Manager project.
+//!
+//! Every write is appended to a `.jsonl` file as one record and applied to an
+//! in-memory index, which serves all reads. Opening the file replays the
+//! records, so the graph survives restarts without a database. This suits
+//! air-gapped machines and projects that commit their memory graph into the
+//! repository; [`JsonlRepository::compact`] rewrites the log as a snapshot to
+//! keep such diffs small.
+#![warn(clippy::all)]
+mod repository;
+
+pub use repository::JsonlRepository;
+
+use mm_memory::{MemoryConfig, MemoryResult, MemoryService};
+use std::path::Path;
+
+/// Create a memory service backed by the JSON Lines file at `path`
+///
+/// The file is created on the first write if it does not exist.
+pub async fn create_jsonl_service(
+    path: impl AsRef<Path>,
+    config: MemoryConfig,
+) -> MemoryResult<MemoryService<JsonlRepository>, std::io::Error> {
+    let repository = JsonlRepository::open(path).await?;
+    Ok(MemoryService::new(repository, config))
+}
@@ -0,0 +1,670 @@
+use async_trait::async_trait;
+use mm_memory::relationship::RelationshipRef;
+use mm_memory::{
+    BackendInfo, EntityUpdate, LabelMatchMode, MemoryEntity, MemoryError, MemoryRelationship,
+    MemoryRepository, MemoryResult, NamedEntityUpdate, RelationshipDirection, RelationshipUpdate,
+    ValidationError, ValidationErrorKind,
+};
+use mm_memory_inmem::InMemoryRepository;
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+
+/// One line of the log: a write as it was issued against the repository
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Record {
+    CreateEntities {
+        entities: Vec<MemoryEntity>,
+    },
+    SetObservations {
+        name: String,
+        observations: Vec<String>,
+    },
+    AddObservations {
+        name: String,
+        observations: Vec<String>,
+    },
+    RemoveObservations {
+        name: String,
+        observations: Vec<String>,
+    },
+    CreateRelationships {
+        relationships: Vec<MemoryRelationship>,
+    },
+    CreateGraph {
+        entities: Vec<MemoryEntity>,
+        relationships: Vec<MemoryRelationship>,
+    },
+    DeleteEntities {
+        names: Vec<String>,
+    },
+    DeleteRelationships {
+        relationships: Vec<RelationshipRef>,
+    },
+    UpdateEntity {
+        name: String,
+        update: EntityUpdate,
+    },
+    UpdateEntities {
+        updates: Vec<NamedEntityUpdate>,
+    },
+    UpdateRelationship {
+        from: String,
+        to: String,
+        name: String,
+        update: RelationshipUpdate,
+    },
+    RenameEntity {
+        old: String,
+        new: String,
+    },
+    MergeEntities {
+        merged: MemoryEntity,
+        sources: Vec<String>,
+    },
+}
+
+impl Record {
+    /// Reject a record that the index would refuse, before it is logged
+    async fn check(&self, index: &InMemoryRepository) -> MemoryResult<(), Infallible> {
+        match self {
+            Record::UpdateEntity { name, update } => {
+                let actual = index.find_entity_by_name(name).await?.map(|e| e.revision);
+                update.check_revision(name, actual)
+            }
+            Record::UpdateEntities { updates } => {
+                for item in updates {
+                    let actual = index
+                        .find_entity_by_name(&item.name)
+                        .await?
+                        .map(|e| e.revision);
+                    item.update.check_revision(&item.name, actual)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn apply(&self, index: &InMemoryRepository) -> MemoryResult<(), Infallible> {
+        match self {
+            Record::CreateEntities { entities } => index.create_entities(entities).await,
+            Record::SetObservations { name, observations } => {
+                index.set_observations(name, observations).await
+            }
+            Record::AddObservations { name, observations } => {
+                index.add_observations(name, observations).await
+            }
+            Record::RemoveObservations { name, observations } => {
+                index.remove_observations(name, observations).await
+            }
+            Record::CreateRelationships { relationships } => {
+                index.create_relationships(relationships).await
+            }
+            Record::CreateGraph {
+                entities,
+                relationships,
+            } => index.create_graph(entities, relationships).await,
+            Record::DeleteEntities { names } => index.delete_entities(names).await,
+            Record::DeleteRelationships { relationships } => {
+                index.delete_relationships(relationships).await
+            }
+            Record::UpdateEntity { name, update } => index.update_entity(name, update).await,
+            Record::UpdateEntities { updates } => index.update_entities(updates).await,
+            Record::UpdateRelationship {
+                from,
+                to,
+                name,
+                update,
+            } => index.update_relationship(from, to, name, update).await,
+            Record::RenameEntity { old, new } => index.rename_entity(old, new).await,
+            Record::MergeEntities { merged, sources } => {
+                index.merge_entities(merged, sources).await
+            }
+        }
+    }
+}
+
+/// Carry an error of the index over to this repository's error type
+///
+/// The index only reports validation failures.
+fn from_index(error: MemoryError<Infallible>) -> MemoryError<io::Error> {
+    match error {
+        MemoryError::ValidationError(e) => MemoryError::ValidationError(e),
+        MemoryError::EntityNotFound(name) => MemoryError::EntityNotFound(name),
+        MemoryError::ReadOnly => MemoryError::ReadOnly,
+        MemoryError::SoftDeleteDisabled => MemoryError::SoftDeleteDisabled,
+        MemoryError::RevisionConflict {
+            name,
+            expected,
+            actual,
+        } => MemoryError::RevisionConflict {
+            name,
+            expected,
+            actual,
+        },
+        other => MemoryError::runtime_error(other.to_string()),
+    }
+}
+
+/// [`MemoryRepository`] persisting writes to an append-only JSON Lines file
+///
+/// Reads are answered from an [`InMemoryRepository`] index, so the repository
+/// behaves exactly like the in-memory one while it is open. Each write is
+/// appended to the file before it is applied to the index. Property values are
+/// stored as plain JSON; after a reopen, values that serialize to strings,
+/// such as dates, come back as strings.
+#[derive(Debug)]
+pub struct JsonlRepository {
+    path: PathBuf,
+    index: InMemoryRepository,
+    /// Held while a record is appended and applied, so the file and the
+    /// index see writes in the same order
+    write_lock: Mutex<()>,
+}
+
+impl JsonlRepository {
+    /// Open the log at `path` and replay it into the index
+    ///
+    /// A missing file is treated as an empty graph and created on the first
+    /// write.
+    pub async fn open(path: impl AsRef<Path>) -> MemoryResult<Self, io::Error> {
+        let path = path.as_ref().to_path_buf();
+        let index = InMemoryRepository::new();
+
+        match File::open(&path) {
+            Ok(file) => {
+                for (number, line) in BufReader::new(file).lines().enumerate() {
+                    let line = line.map_err(|e| {
+                        MemoryError::connection_error_with_source(
+                            format!("Failed to read {}", path.display()),
+                            e,
+                        )
+                    })?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let record: Record = serde_json::from_str(&line).map_err(|e| {
+                        MemoryError::runtime_error_with_source(
+                            format!(
+                                "Invalid record on line {} of {}",
+                                number + 1,
+                                path.display()
+                            ),
+                            e,
+                        )
+                    })?;
+                    record.apply(&index).await.map_err(from_index)?;
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(MemoryError::connection_error_with_source(
+                    format!("Failed to open {}", path.display()),
+                    e,
+                ));
+            }
+        }
+
+        Ok(Self {
+            path,
+            index,
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    /// Path of the log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Rewrite the log as the current graph
+    ///
+    /// Every entity, sorted by name, and then every relationship gets its own
+    /// record, which keeps diffs of a committed log readable. The new log is
+    /// written next to the old one and renamed over it.
+    pub async fn compact(&self) -> MemoryResult<(), io::Error> {
+        let _guard = self.write_lock.lock().await;
+
+        let entities = self
+            .index
+            .entities()
+            .into_iter()
+            .map(|e| Record::CreateEntities {
+                entities: vec![MemoryEntity {
+                    relationships: vec![],
+                    ..e
+                }],
+            });
+        let relationships =
+            self.index
+                .relationships()
+                .into_iter()
+                .map(|r| Record::CreateRelationships {
+                    relationships: vec![r],
+                });
+
+        let mut contents = String::new();
+        for record in entities.chain(relationships) {
+            contents.push_str(&serde_json::to_string(&record)?);
+            contents.push('\n');
+        }
+
+        let tmp = self.path.with_extension("jsonl.tmp");
+        fs::write(&tmp, contents)
+            .and_then(|()| fs::rename(&tmp, &self.path))
+            .map_err(|e| {
+                MemoryError::query_error_with_source(
+                    format!("Failed to compact {}", self.path.display()),
+                    e,
+                )
+            })
+    }
+
+    /// Append `record` to the log, then apply it to the index
+    async fn write(&self, record: Record) -> MemoryResult<(), io::Error> {
+        let _guard = self.write_lock.lock().await;
+        record.check(&self.index).await.map_err(from_index)?;
+
+        let mut line = serde_json::to_string(&record)?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| {
+                MemoryError::query_error_with_source(
+                    format!("Failed to append to {}", self.path.display()),
+                    e,
+                )
+            })?;
+
+        record.apply(&self.index).await.map_err(from_index)
+    }
+
+    /// Reject an empty entity name before it reaches the log
+    fn require_name(name: &str) -> MemoryResult<(), io::Error> {
+        if name.is_empty() {
+            return Err(ValidationError::from(ValidationErrorKind::EmptyEntityName).into());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MemoryRepository for JsonlRepository {
+    type Error = io::Error;
+
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            backend: "jsonl".to_string(),
+            database: Some(self.path.display().to_string()),
+        }
+    }
+
+    async fn create_entities(&self, entities: &[MemoryEntity]) -> MemoryResult<(), Self::Error> {
+        self.write(Record::CreateEntities {
+            entities: entities
+                .iter()
+                .map(|e| MemoryEntity {
+                    relationships: vec![],
+                    ..e.clone()
+                })
+                .collect(),
+        })
+        .await
+    }
+
+    async fn find_entity_by_name(
+        &self,
+        name: &str,
+    ) -> MemoryResult<Option<MemoryEntity>, Self::Error> {
+        self.index
+            .find_entity_by_name(name)
+            .await
+            .map_err(from_index)
+    }
+
+    async fn find_existing_names(
+        &self,
+        names: &[String],
+    ) -> MemoryResult<Vec<String>, Self::Error> {
+        self.index
+            .find_existing_names(names)
+            .await
+            .map_err(from_index)
+    }
+
+    async fn set_observations(
+        &self,
+        name: &str,
+        observations: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        Self::require_name(name)?;
+        self.write(Record::SetObservations {
+            name: name.to_string(),
+            observations: observations.to_vec(),
+        })
+        .await
+    }
+
+    async fn add_observations(
+        &self,
+        name: &str,
+        observations: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        Self::require_name(name)?;
+        self.write(Record::AddObservations {
+            name: name.to_string(),
+            observations: observations.to_vec(),
+        })
+        .await
+    }
+
+    async fn remove_all_observations(&self, name: &str) -> MemoryResult<(), Self::Error> {
+        self.set_observations(name, &[]).await
+    }
+
+    async fn remove_observations(
+        &self,
+        name: &str,
+        observations: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        Self::require_name(name)?;
+        self.write(Record::RemoveObservations {
+            name: name.to_string(),
+            observations: observations.to_vec(),
+        })
+        .await
+    }
+
+    async fn create_relationships(
+        &self,
+        relationships: &[MemoryRelationship],
+    ) -> MemoryResult<(), Self::Error> {
+        self.write(Record::CreateRelationships {
+            relationships: relationships.to_vec(),
+        })
+        .await
+    }
+
+    /// Logged as a single record, so a crash cannot keep half of the batch
+    async fn create_graph(
+        &self,
+        entities: &[MemoryEntity],
+        relationships: &[MemoryRelationship],
+    ) -> MemoryResult<(), Self::Error> {
+        self.write(Record::CreateGraph {
+            entities: entities
+                .iter()
+                .map(|e| MemoryEntity {
+                    relationships: vec![],
+                    ..e.clone()
+                })
+                .collect(),
+            relationships: relationships.to_vec(),
+        })
+        .await
+    }
+
+    async fn delete_entities(&self, names: &[String]) -> MemoryResult<(), Self::Error> {
+        self.write(Record::DeleteEntities {
+            names: names.to_vec(),
+        })
+        .await
+    }
+
+    async fn delete_relationships(
+        &self,
+        relationships: &[RelationshipRef],
+    ) -> MemoryResult<(), Self::Error> {
+        self.write(Record::DeleteRelationships {
+            relationships: relationships.to_vec(),
+        })
+        .await
+    }
+
+    async fn find_relationships(
+        &self,
+        from: Option<String>,
+        to: Option<String>,
+        name: Option<String>,
+    ) -> MemoryResult<Vec<MemoryRelationship>, Self::Error> {
+        self.index
+            .find_relationships(from, to, name)
+            .await
+            .map_err(from_index)
+    }
+
+    async fn find_entities_by_labels(
+        &self,
+        labels: &[String],
+        match_mode: LabelMatchMode,
+        required_label: Option<String>,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        self.index
+            .find_entities_by_labels(labels, match_mode, required_label)
+            .await
+            .map_err(from_index)
+    }
+
+    async fn find_entities_by_source(
+        &self,
+        source_key: &str,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        self.index
+            .find_entities_by_source(source_key)
+            .await
+            .map_err(from_index)
+    }
+
+    async fn list_labels(&self) -> MemoryResult<Vec<(String, u64)>, Self::Error> {
+        self.index.list_labels().await.map_err(from_index)
+    }
+
+    async fn list_relationship_types(&self) -> MemoryResult<Vec<(String, u64)>, Self::Error> {
+        self.index
+            .list_relationship_types()
+            .await
+            .map_err(from_index)
+    }
+
+    async fn find_related_entities(
+        &self,
+        name: &str,
+        relationship_type: Option<String>,
+        direction: Option<RelationshipDirection>,
+        depth: u32,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        self.index
+            .find_related_entities(name, relationship_type, direction, depth)
+            .await
+            .map_err(from_index)
+    }
+
+    async fn update_entity(
+        &self,
+        name: &str,
+        update: &EntityUpdate,
+    ) -> MemoryResult<(), Self::Error> {
+        Self::require_name(name)?;
+        self.write(Record::UpdateEntity {
+            name: name.to_string(),
+            update: update.clone(),
+        })
+        .await
+    }
+
+    /// Logged as a single record, so a crash cannot keep half of the batch
+    async fn update_entities(
+        &self,
+        updates: &[NamedEntityUpdate],
+    ) -> MemoryResult<(), Self::Error> {
+        for item in updates {
+            Self::require_name(&item.name)?;
+        }
+        self.write(Record::UpdateEntities {
+            updates: updates.to_vec(),
+        })
+        .await
+    }
+
+    async fn update_relationship(
+        &self,
+        from: &str,
+        to: &str,
+        name: &str,
+        update: &RelationshipUpdate,
+    ) -> MemoryResult<(), Self::Error> {
+        self.write(Record::UpdateRelationship {
+            from: from.to_string(),
+            to: to.to_string(),
+            name: name.to_string(),
+            update: update.clone(),
+        })
+        .await
+    }
+
+    async fn rename_entity(&self, old: &str, new: &str) -> MemoryResult<(), Self::Error> {
+        Self::require_name(old)?;
+        Self::require_name(new)?;
+        self.write(Record::RenameEntity {
+            old: old.to_string(),
+            new: new.to_string(),
+        })
+        .await
+    }
+
+    /// Logged as a single record, so a crash cannot keep half of the merge
+    async fn merge_entities(
+        &self,
+        merged: &MemoryEntity,
+        sources: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        Self::require_name(&merged.name)?;
+        self.write(Record::MergeEntities {
+            merged: merged.clone(),
+            sources: sources.to_vec(),
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn entity(name: &str) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            labels: vec!["Example".to_string()],
+            observations: vec!["first".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn rel(from: &str, to: &str) -> MemoryRelationship {
+        MemoryRelationship {
+            from: from.to_string(),
+            to: to.to_string(),
+            name: "relates_to".to_string(),
+            properties: HashMap::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn reopen_replays_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memory.jsonl");
+
+        let repo = JsonlRepository::open(&path).await.unwrap();
+        repo.create_entities(&[entity("a"), entity("b"), entity("c")])
+            .await
+            .unwrap();
+        repo.create_relationships(&[rel("a", "b"), rel("b", "c")])
+            .await
+            .unwrap();
+        repo.add_observations("a", &["second".to_string()])
+            .await
+            .unwrap();
+        repo.delete_entities(&["c".to_string()]).await.unwrap();
+        drop(repo);
+
+        let repo = JsonlRepository::open(&path).await.unwrap();
+        let a = repo.find_entity_by_name("a").await.unwrap().unwrap();
+        assert_eq!(a.observations, ["first", "second"]);
+        assert_eq!(a.relationships, vec![rel("a", "b")]);
+        assert!(repo.find_entity_by_name("c").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn compact_writes_one_record_per_entity_and_relationship() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memory.jsonl");
+
+        let repo = JsonlRepository::open(&path).await.unwrap();
+        repo.create_entities(&[entity("b"), entity("a")])
+            .await
+            .unwrap();
+        repo.create_relationships(&[rel("a", "b")]).await.unwrap();
+        repo.set_observations("a", &[]).await.unwrap();
+        repo.compact().await.unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 3);
+
+        let repo = JsonlRepository::open(&path).await.unwrap();
+        let a = repo.find_entity_by_name("a").await.unwrap().unwrap();
+        assert!(a.observations.is_empty());
+        assert_eq!(
+            repo.find_relationships(None, None, None)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn create_graph_is_one_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memory.jsonl");
+
+        let repo = JsonlRepository::open(&path).await.unwrap();
+        repo.create_graph(&[entity("a"), entity("b")], &[rel("a", "b")])
+            .await
+            .unwrap();
+        drop(repo);
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+
+        let repo = JsonlRepository::open(&path).await.unwrap();
+        let a = repo.find_entity_by_name("a").await.unwrap().unwrap();
+        assert_eq!(a.relationships, vec![rel("a", "b")]);
+    }
+
+    #[tokio::test]
+    async fn empty_names_are_not_logged() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memory.jsonl");
+
+        let repo = JsonlRepository::open(&path).await.unwrap();
+        assert!(repo.set_observations("", &[]).await.is_err());
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn invalid_record_reports_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memory.jsonl");
+        fs::write(&path, "\n{\"op\":\"unknown\"}\n").unwrap();
+
+        let err = JsonlRepository::open(&path).await.unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{err}");
+    }
+}
@@ -0,0 +1,11 @@
+use mm_memory::test_suite::run_memory_service_test_suite;
+use mm_memory_jsonl::JsonlRepository;
+
+#[tokio::test]
+async fn jsonl_repository_passes_memory_test_suite() {
+    let dir = tempfile::tempdir().unwrap();
+    let repository = JsonlRepository::open(dir.path().join("memory.jsonl"))
+        .await
+        .unwrap();
+    run_memory_service_test_suite(repository).await.unwrap();
+}
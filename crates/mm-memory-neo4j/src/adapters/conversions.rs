@@ -3,6 +3,10 @@ use mm_memory::{MemoryError, MemoryValue};
 use neo4rs::BoltType;
 use std::collections::HashMap;
 
+/// Prefix of the strings holding [`MemoryValue::Json`] values, since Neo4j
+/// properties cannot nest
+pub(crate) const JSON_VALUE_PREFIX: &str = "mm:json:";
+
 /// Convert a [`MemoryValue`] directly into a [`neo4rs::BoltType`].
 ///
 /// This avoids the indirection through `serde_json::Value` when
@@ -35,6 +39,7 @@ pub(crate) fn memory_value_to_bolt(
         MemoryValue::DateTime(dt) => (*dt).into(),
         MemoryValue::LocalDateTime(dt) => (*dt).into(),
         MemoryValue::Duration(d) => (*d).into(),
+        MemoryValue::Json(value) => format!("{JSON_VALUE_PREFIX}{value}").into(),
     })
 }
 
@@ -45,7 +50,14 @@ pub(crate) fn bolt_to_memory_value(
     bolt: BoltType,
 ) -> Result<MemoryValue, MemoryError<neo4rs::Error>> {
     Ok(match bolt {
-        BoltType::String(s) => MemoryValue::String(s.value),
+        BoltType::String(s) => match s
+            .value
+            .strip_prefix(JSON_VALUE_PREFIX)
+            .and_then(|json| serde_json::from_str(json).ok())
+        {
+            Some(value) => MemoryValue::Json(value),
+            None => MemoryValue::String(s.value),
+        },
         BoltType::Integer(i) => MemoryValue::Integer(i.value),
         BoltType::Float(f) => MemoryValue::Float(f.value),
         BoltType::Boolean(b) => MemoryValue::Boolean(b.value),
@@ -144,6 +156,15 @@ mod tests {
         assert_eq!(v, back);
     }
 
+    #[test]
+    fn round_trip_json() {
+        let v = MemoryValue::Json(serde_json::json!({"steps": [{"done": true}]}));
+        let bolt = memory_value_to_bolt(&v).unwrap();
+        assert!(matches!(&bolt, BoltType::String(s) if s.value.starts_with(JSON_VALUE_PREFIX)));
+        let back = bolt_to_memory_value(bolt).unwrap();
+        assert_eq!(v, back);
+    }
+
     #[test]
     fn round_trip_boolean() {
         let v = MemoryValue::Boolean(true);
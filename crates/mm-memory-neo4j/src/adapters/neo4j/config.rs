@@ -1,4 +1,51 @@
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Cypher dialect spoken by the Bolt server
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CypherDialect {
+    /// Neo4j with the APOC plugin installed
+    #[default]
+    Neo4j,
+    /// Memgraph, which ships without APOC
+    ///
+    /// Labels and relationship types of new entities and relationships are
+    /// quoted into the query text instead of being passed to APOC procedures.
+    Memgraph,
+}
+
+/// Connection pool settings; unset values keep the driver defaults
+///
+/// neo4rs only exposes the pool size. The timeout and interval below are
+/// enforced by [`Neo4jRepository`](super::Neo4jRepository) around the pool
+/// rather than per connection.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Neo4jPoolConfig {
+    /// Maximum number of open connections, 16 unless set
+    #[serde(default)]
+    pub max_connections: Option<usize>,
+
+    /// Seconds the server has to answer a probe query when the pool is
+    /// created; queries and connection checkouts are not bounded by it
+    #[serde(default)]
+    pub startup_timeout_secs: Option<u64>,
+
+    /// Seconds after which the whole pool is dropped and rebuilt, so
+    /// long-lived connections are eventually reopened
+    #[serde(default)]
+    pub rebuild_interval_secs: Option<u64>,
+}
+
+impl Neo4jPoolConfig {
+    pub fn startup_timeout(&self) -> Option<Duration> {
+        self.startup_timeout_secs.map(Duration::from_secs)
+    }
+
+    pub fn rebuild_interval(&self) -> Option<Duration> {
+        self.rebuild_interval_secs.map(Duration::from_secs)
+    }
+}
 
 /// Configuration for connecting to Neo4j
 #[derive(Clone, Deserialize, Serialize)]
@@ -12,6 +59,18 @@ pub struct Neo4jConfig {
     /// Password for authentication
     #[serde(skip_serializing)]
     pub password: String,
+
+    /// Cypher dialect of the server, `neo4j` unless set
+    #[serde(default)]
+    pub dialect: CypherDialect,
+
+    /// Connection pool settings
+    #[serde(default)]
+    pub pool: Neo4jPoolConfig,
+
+    /// Database all queries run against; the server's default when unset
+    #[serde(default)]
+    pub database: Option<String>,
 }
 
 impl std::fmt::Debug for Neo4jConfig {
@@ -20,13 +79,16 @@ impl std::fmt::Debug for Neo4jConfig {
             .field("uri", &self.uri)
             .field("username", &self.username)
             .field("password", &"***")
+            .field("dialect", &self.dialect)
+            .field("pool", &self.pool)
+            .field("database", &self.database)
             .finish()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Neo4jConfig;
+    use super::*;
 
     #[test]
     fn debug_redacts_password() {
@@ -34,10 +96,61 @@ mod tests {
             uri: "neo4j://localhost:7687".to_string(),
             username: "user".to_string(),
             password: "secret".to_string(),
+            dialect: CypherDialect::Neo4j,
+            pool: Neo4jPoolConfig::default(),
+            database: None,
         };
 
         let dbg = format!("{cfg:?}");
         assert!(!dbg.contains("secret"));
         assert!(dbg.contains("***"));
     }
+
+    #[test]
+    fn dialect_defaults_to_neo4j() {
+        let cfg: Neo4jConfig = serde_json::from_str(
+            r#"{"uri": "bolt://localhost", "username": "u", "password": "p"}"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.dialect, CypherDialect::Neo4j);
+
+        let cfg: Neo4jConfig = serde_json::from_str(
+            r#"{"uri": "bolt://localhost", "username": "u", "password": "p", "dialect": "memgraph"}"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.dialect, CypherDialect::Memgraph);
+    }
+
+    #[test]
+    fn database_is_optional() {
+        let cfg: Neo4jConfig = serde_json::from_str(
+            r#"{"uri": "bolt://localhost", "username": "u", "password": "p"}"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.database, None);
+
+        let cfg: Neo4jConfig = serde_json::from_str(
+            r#"{"uri": "bolt://localhost", "username": "u", "password": "p", "database": "agent_a"}"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.database.as_deref(), Some("agent_a"));
+    }
+
+    #[test]
+    fn pool_settings_are_optional() {
+        let cfg: Neo4jConfig = serde_json::from_str(
+            r#"{"uri": "bolt://localhost", "username": "u", "password": "p"}"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.pool, Neo4jPoolConfig::default());
+
+        let cfg: Neo4jConfig = serde_json::from_str(
+            r#"{"uri": "bolt://localhost", "username": "u", "password": "p",
+                "pool": {"max_connections": 32, "startup_timeout_secs": 5, "rebuild_interval_secs": 3600}}"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.pool.max_connections, Some(32));
+        assert_eq!(cfg.pool.startup_timeout(), Some(Duration::from_secs(5)));
+        assert_eq!(cfg.pool.rebuild_interval(), Some(Duration::from_secs(3600)));
+    }
 }
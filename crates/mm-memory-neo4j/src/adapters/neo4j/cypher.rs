@@ -0,0 +1,236 @@
+//! Small builder for the Cypher statements issued by the Neo4j adapter.
+//!
+//! Queries are assembled from clauses, `WHERE` fragments and parameters so
+//! repository methods never splice user-provided values into the query text.
+//! Identifiers that cannot be parameterized (labels, relationship types and
+//! property keys) go through [`quote_identifier`].
+
+use mm_memory::{MemoryResult, RelationshipDirection, ValidationError, ValidationErrorKind};
+use neo4rs::{BoltType, Query};
+
+/// Tail shared by every query returning entities bound to `n`.
+///
+/// Collects the relationships touching `n` and returns the `n` and `rels`
+/// columns expected by [`memory_entity_from_row`](super::helpers::memory_entity_from_row).
+pub(super) const RETURN_ENTITIES: &str = "OPTIONAL MATCH (n)-[r]-() \
+     WITH n, collect(CASE WHEN r IS NOT NULL THEN {from: startNode(r).name, to: endNode(r).name, name: type(r), properties: properties(r)} END) AS rels \
+     RETURN n, [x IN rels WHERE x IS NOT NULL] AS rels";
+
+/// Quote a label, relationship type or property key for interpolation into
+/// Cypher.
+///
+/// Identifiers cannot be passed as query parameters, so they are wrapped in
+/// backticks with any embedded backtick doubled. Empty identifiers and those
+/// containing control characters are rejected outright.
+pub(super) fn quote_identifier(identifier: &str) -> MemoryResult<String, neo4rs::Error> {
+    if identifier.is_empty() || identifier.chars().any(char::is_control) {
+        return Err(
+            ValidationError::from(ValidationErrorKind::InvalidIdentifier(
+                identifier.to_string(),
+            ))
+            .into(),
+        );
+    }
+    Ok(format!("`{}`", identifier.replace('`', "``")))
+}
+
+/// Quote labels into a node label expression such as ``:`Memory`:`Task` ``.
+pub(super) fn label_expression(labels: &[String]) -> MemoryResult<String, neo4rs::Error> {
+    labels
+        .iter()
+        .map(|label| Ok(format!(":{}", quote_identifier(label)?)))
+        .collect()
+}
+
+/// Build a variable-length relationship pattern such as `-[r:`knows`*1..3]->`.
+pub(super) fn relationship_pattern(
+    variable: &str,
+    relationship_type: Option<&str>,
+    direction: RelationshipDirection,
+    depth: u32,
+) -> MemoryResult<String, neo4rs::Error> {
+    let rel_type = match relationship_type {
+        Some(t) => format!(":{}", quote_identifier(t)?),
+        None => String::default(),
+    };
+    let rel = format!("[{}{}*1..{}]", variable, rel_type, depth);
+    Ok(match direction {
+        RelationshipDirection::Outgoing => format!("-{}->", rel),
+        RelationshipDirection::Incoming => format!("<-{}-", rel),
+        RelationshipDirection::Both => format!("-{}-", rel),
+    })
+}
+
+/// Incrementally built Cypher statement with its parameters.
+#[derive(Debug, Clone, Default)]
+pub(super) struct CypherQuery {
+    clauses: Vec<String>,
+    conditions: Vec<String>,
+    params: Vec<(String, BoltType)>,
+    creates: bool,
+}
+
+impl CypherQuery {
+    pub(super) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark the query as creating nodes or relationships, so that running it
+    /// twice would create duplicates.
+    pub(super) fn creating(mut self) -> Self {
+        self.creates = true;
+        self
+    }
+
+    /// Whether running the query again leaves the graph as running it once.
+    pub(super) fn is_idempotent(&self) -> bool {
+        !self.creates
+    }
+
+    /// Append a clause, closing any pending `WHERE` conditions first.
+    pub(super) fn clause(mut self, clause: impl Into<String>) -> Self {
+        self.flush_conditions();
+        self.clauses.push(clause.into());
+        self
+    }
+
+    /// Match a node bound to `variable` by the name stored in `$param`.
+    pub(super) fn match_entity(self, variable: &str, param: &str) -> Self {
+        self.clause(format!("MATCH ({} {{name: ${}}})", variable, param))
+    }
+
+    /// Add a condition to the `WHERE` clause of the most recent clause.
+    ///
+    /// Multiple conditions are combined with `AND`.
+    pub(super) fn and_where(mut self, condition: impl Into<String>) -> Self {
+        self.conditions.push(condition.into());
+        self
+    }
+
+    /// Bind a query parameter.
+    pub(super) fn param(mut self, key: &str, value: impl Into<BoltType>) -> Self {
+        self.params.push((key.to_string(), value.into()));
+        self
+    }
+
+    /// Finish the query with [`RETURN_ENTITIES`].
+    pub(super) fn return_entities(self) -> Self {
+        self.clause(RETURN_ENTITIES)
+    }
+
+    /// Render the query text.
+    pub(super) fn cypher(&self) -> String {
+        let mut parts = self.clauses.clone();
+        if let Some(where_clause) = self.where_clause() {
+            parts.push(where_clause);
+        }
+        parts.join(" ")
+    }
+
+    /// Convert into a [`Query`] with all parameters bound.
+    pub(super) fn build(self) -> Query {
+        let mut query = Query::new(self.cypher());
+        for (key, value) in self.params {
+            query = query.param(&key, value);
+        }
+        query
+    }
+
+    fn where_clause(&self) -> Option<String> {
+        if self.conditions.is_empty() {
+            None
+        } else {
+            Some(format!("WHERE {}", self.conditions.join(" AND ")))
+        }
+    }
+
+    fn flush_conditions(&mut self) {
+        if let Some(where_clause) = self.where_clause() {
+            self.clauses.push(where_clause);
+            self.conditions.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_memory::MemoryError;
+
+    #[test]
+    fn quote_identifier_escapes_backticks() {
+        assert_eq!(quote_identifier("relates_to").unwrap(), "`relates_to`");
+        assert_eq!(
+            quote_identifier("a`]->() DETACH DELETE n //").unwrap(),
+            "`a``]->() DETACH DELETE n //`"
+        );
+    }
+
+    #[test]
+    fn quote_identifier_rejects_invalid() {
+        assert!(matches!(
+            quote_identifier(""),
+            Err(MemoryError::ValidationError(_))
+        ));
+        assert!(matches!(
+            quote_identifier("bad\nname"),
+            Err(MemoryError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn label_expression_quotes_each_label() {
+        assert_eq!(
+            label_expression(&["Memory".to_string(), "a`b".to_string()]).unwrap(),
+            ":`Memory`:`a``b`"
+        );
+        assert_eq!(label_expression(&[]).unwrap(), "");
+        assert!(label_expression(&[String::new()]).is_err());
+    }
+
+    #[test]
+    fn relationship_pattern_directions() {
+        assert_eq!(
+            relationship_pattern("r", Some("knows"), RelationshipDirection::Outgoing, 2).unwrap(),
+            "-[r:`knows`*1..2]->"
+        );
+        assert_eq!(
+            relationship_pattern("r", None, RelationshipDirection::Incoming, 1).unwrap(),
+            "<-[r*1..1]-"
+        );
+        assert_eq!(
+            relationship_pattern("r", None, RelationshipDirection::Both, 3).unwrap(),
+            "-[r*1..3]-"
+        );
+    }
+
+    #[test]
+    fn conditions_attach_to_preceding_clause() {
+        let query = CypherQuery::new()
+            .clause("MATCH (a)-[r]->(b)")
+            .and_where("a.name = $from")
+            .and_where("type(r) = $type")
+            .clause("SET r += $props");
+        assert_eq!(
+            query.cypher(),
+            "MATCH (a)-[r]->(b) WHERE a.name = $from AND type(r) = $type SET r += $props"
+        );
+    }
+
+    #[test]
+    fn trailing_conditions_are_rendered() {
+        let query = CypherQuery::new()
+            .match_entity("n", "name")
+            .and_where("n.status = $status");
+        assert_eq!(
+            query.cypher(),
+            "MATCH (n {name: $name}) WHERE n.status = $status"
+        );
+    }
+
+    #[test]
+    fn empty_where_is_omitted() {
+        let query = CypherQuery::new().clause("MATCH (n)").return_entities();
+        assert_eq!(query.cypher(), format!("MATCH (n) {}", RETURN_ENTITIES));
+    }
+}
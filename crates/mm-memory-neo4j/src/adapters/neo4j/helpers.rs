@@ -3,6 +3,9 @@ use mm_memory::{MemoryEntity, MemoryError, MemoryRelationship, MemoryResult, Mem
 use neo4rs::{self, Node};
 use std::collections::HashMap;
 
+/// Node property holding [`MemoryEntity::revision`]
+pub(super) const REVISION_PROPERTY: &str = "_mm_revision";
+
 pub(super) fn extract_observations_from_bolt(
     bolt: neo4rs::BoltType,
 ) -> MemoryResult<Vec<String>, neo4rs::Error> {
@@ -197,7 +200,7 @@ pub(super) fn memory_entity_from_node(
 
     let mut properties: HashMap<String, MemoryValue> = HashMap::default();
     for key in node.keys() {
-        if key != "name" && key != "observations" {
+        if key != "name" && key != "observations" && key != REVISION_PROPERTY {
             let bolt: neo4rs::BoltType = node.get(key).map_err(|e| {
                 MemoryError::runtime_error_with_source(
                     "Failed to decode node properties".to_string(),
@@ -210,6 +213,8 @@ pub(super) fn memory_entity_from_node(
     }
 
     let relationships = parse_relationships_from_bolt(rels_bolt)?;
+    // Nodes written before revisions were tracked have none
+    let revision = node.get::<i64>(REVISION_PROPERTY).unwrap_or(0);
 
     Ok(MemoryEntity {
         name: entity_name,
@@ -217,5 +222,21 @@ pub(super) fn memory_entity_from_node(
         observations,
         properties,
         relationships,
+        revision: u64::try_from(revision).unwrap_or(0),
     })
 }
+
+/// Decode a row shaped as `RETURN n, rels` into a [`MemoryEntity`].
+pub(super) fn memory_entity_from_row(
+    row: &neo4rs::Row,
+) -> MemoryResult<MemoryEntity, neo4rs::Error> {
+    let node = row.get::<Node>("n").map_err(|e| {
+        MemoryError::runtime_error_with_source("Failed to get node from result".to_string(), e)
+    })?;
+
+    let rels_bolt = row.get::<neo4rs::BoltType>("rels").map_err(|e| {
+        MemoryError::runtime_error_with_source("Failed to decode relationships".to_string(), e)
+    })?;
+
+    memory_entity_from_node(&node, rels_bolt)
+}
@@ -1,6 +1,8 @@
 mod config;
+mod cypher;
 mod helpers;
 mod repository;
+mod retry;
 
-pub use config::Neo4jConfig;
+pub use config::{CypherDialect, Neo4jConfig, Neo4jPoolConfig};
 pub use repository::Neo4jRepository;
@@ -1,105 +1,724 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use async_trait::async_trait;
-use neo4rs::{self, Graph, Node, Query};
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use neo4rs::{self, ConfigBuilder, Graph};
+use std::time::Instant;
+use tokio::sync::RwLock;
 use tracing::instrument;
 
-use super::config::Neo4jConfig;
-use super::helpers::memory_entity_from_node;
+use super::config::{CypherDialect, Neo4jConfig};
+use super::cypher::{CypherQuery, label_expression, quote_identifier, relationship_pattern};
+use super::helpers::{REVISION_PROPERTY, memory_entity_from_row};
+use super::retry::with_retry;
 use crate::adapters::conversions::{bolt_to_memory_value, memory_value_to_bolt};
+use mm_memory::fuzzy::{FuzzyMatch, MIN_FUZZY_SCORE, rank_by_name};
 use mm_memory::{
-    EntityUpdate, LabelMatchMode, MemoryEntity, MemoryError, MemoryRelationship, MemoryRepository,
-    MemoryResult, PropertiesUpdate, RelationshipDirection, RelationshipUpdate, ValidationError,
-    ValidationErrorKind, relationship::RelationshipRef,
+    BackendInfo, EntitySnapshot, EntityStream, EntityUpdate, LabelMatchMode, MemoryEntity,
+    MemoryError, MemoryRelationship, MemoryRepository, MemoryResult, MemoryValue,
+    NamedEntityUpdate, ProjectSummary, PropertiesUpdate, PropertyFilter, PropertyOperator,
+    PropertySort, RelatedQuery, RelationshipDirection, RelationshipUpdate, RetryConfig,
+    SortDirection, TaskFilter, ValidationError, ValidationErrorKind,
+    date::value_to_datetime,
+    labels::{PROJECT_LABEL, TASK_LABEL},
+    relationship::RelationshipRef,
+    repository::CLOSED_TASK_STATUSES,
+    source::SOURCES_PROPERTY,
 };
 
-pub struct Neo4jRepository {
+/// Match an entity by `$name` and take its write lock before any reads.
+///
+/// Writing a transient property forces Neo4j to lock the node up front, so
+/// the observations or revision read afterwards cannot be changed by a
+/// concurrent writer.
+const OBSERVATIONS_LOCK: &str = "MATCH (n {name: $name}) SET n._mm_lock = true WITH n";
+
+/// Drop the transient lock property set by [`OBSERVATIONS_LOCK`].
+const OBSERVATIONS_UNLOCK: &str = "REMOVE n._mm_lock";
+
+/// Increment the revision of the entity matched as `n`.
+const BUMP_REVISION: &str = "SET n._mm_revision = coalesce(n._mm_revision, 0) + 1";
+
+/// Fold `$observations` into the list of `n`, skipping any already present.
+const ADD_OBSERVATIONS: &str = "SET n.observations = reduce(acc = coalesce(n.observations, []), \
+     o IN $observations | CASE WHEN o IN acc THEN acc ELSE acc + o END)";
+
+/// Drop `$observations` from the list of `n`.
+const REMOVE_OBSERVATIONS: &str =
+    "SET n.observations = [o IN coalesce(n.observations, []) WHERE NOT o IN $observations]";
+
+/// Rows of an executed query, fetched from the server as they are consumed
+type RowStream = BoxStream<'static, Result<neo4rs::Row, neo4rs::Error>>;
+
+fn bolt_map(
+    values: &HashMap<String, MemoryValue>,
+) -> MemoryResult<HashMap<String, neo4rs::BoltType>, neo4rs::Error> {
+    let mut map: HashMap<String, neo4rs::BoltType> = HashMap::new();
+    for (k, v) in values {
+        map.insert(k.clone(), memory_value_to_bolt(v)?);
+    }
+    Ok(map)
+}
+
+/// Connection pool and when it was created
+struct Pool {
     graph: Graph,
+    created_at: Instant,
+}
+
+pub struct Neo4jRepository {
+    pool: RwLock<Pool>,
+    config: Neo4jConfig,
+    dialect: CypherDialect,
+    retry: RetryConfig,
 }
 
 impl Neo4jRepository {
-    #[instrument(skip(config), fields(uri = %config.uri))]
+    #[instrument(skip(config), fields(uri = %config.uri, database = ?config.database))]
     pub async fn new(config: Neo4jConfig) -> Result<Self, MemoryError<neo4rs::Error>> {
-        let graph = Graph::new(&config.uri, &config.username, &config.password)
+        let graph = Self::connect(&config).await?;
+
+        Ok(Self {
+            pool: RwLock::new(Pool {
+                graph,
+                created_at: Instant::now(),
+            }),
+            dialect: config.dialect,
+            config,
+            retry: RetryConfig::default(),
+        })
+    }
+
+    /// Retry queries failing with transient errors as configured in `retry`
+    pub fn with_retry_config(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Create the constraints and indexes that name lookups rely on.
+    ///
+    /// Names of entities labelled `entity_label`, normally the default label,
+    /// get a uniqueness constraint; task and project names get an index.
+    /// Existing constraints and indexes are left alone.
+    #[instrument(skip(self))]
+    pub async fn ensure_schema(
+        &self,
+        entity_label: Option<&str>,
+    ) -> MemoryResult<(), neo4rs::Error> {
+        if let Some(label) = entity_label {
+            let query = match self.dialect {
+                CypherDialect::Neo4j => CypherQuery::new().clause(format!(
+                    "CREATE CONSTRAINT {} IF NOT EXISTS FOR (n:{}) REQUIRE n.name IS UNIQUE",
+                    quote_identifier(&format!("mm_{}_name_unique", label))?,
+                    quote_identifier(label)?
+                )),
+                // Memgraph constraints are unnamed and creating one twice is a no-op
+                CypherDialect::Memgraph => CypherQuery::new().clause(format!(
+                    "CREATE CONSTRAINT ON (n:{}) ASSERT n.name IS UNIQUE",
+                    quote_identifier(label)?
+                )),
+            };
+            self.run(
+                query,
+                &format!("create unique constraint on {}.name", label),
+            )
+            .await?;
+        }
+        for label in [TASK_LABEL, PROJECT_LABEL] {
+            if Some(label) != entity_label {
+                self.create_property_index(label, "name").await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Create a connection pool as configured in `config.pool`.
+    ///
+    /// With a startup timeout set, the server must answer a trivial query
+    /// within it.
+    async fn connect(config: &Neo4jConfig) -> MemoryResult<Graph, neo4rs::Error> {
+        let error = |e: neo4rs::Error| {
+            MemoryError::connection_error_with_source(
+                format!("Failed to connect to Neo4j at {}", config.uri),
+                e,
+            )
+        };
+
+        let mut builder = ConfigBuilder::default()
+            .uri(&config.uri)
+            .user(&config.username)
+            .password(&config.password);
+        if let Some(max_connections) = config.pool.max_connections {
+            builder = builder.max_connections(max_connections);
+        }
+        if let Some(database) = &config.database {
+            builder = builder.db(database.as_str());
+        }
+        let graph = Graph::connect(builder.build().map_err(error)?)
             .await
-            .map_err(|e| {
-                MemoryError::connection_error_with_source(
-                    format!("Failed to connect to Neo4j at {}", config.uri),
+            .map_err(error)?;
+
+        if let Some(timeout) = config.pool.startup_timeout() {
+            tokio::time::timeout(timeout, graph.run(neo4rs::query("RETURN 1")))
+                .await
+                .map_err(|_| {
+                    MemoryError::connection_error(format!(
+                        "Timed out after {}s connecting to Neo4j at {}",
+                        timeout.as_secs(),
+                        config.uri
+                    ))
+                })?
+                .map_err(error)?;
+        }
+        Ok(graph)
+    }
+
+    /// Current connection pool, rebuilt once it is older than
+    /// `rebuild_interval_secs`.
+    async fn graph(&self) -> MemoryResult<Graph, neo4rs::Error> {
+        let Some(interval) = self.config.pool.rebuild_interval() else {
+            return Ok(self.pool.read().await.graph.clone());
+        };
+        {
+            let pool = self.pool.read().await;
+            if pool.created_at.elapsed() < interval {
+                return Ok(pool.graph.clone());
+            }
+        }
+
+        let mut pool = self.pool.write().await;
+        // Another caller may have replaced the pool while we waited.
+        if pool.created_at.elapsed() >= interval {
+            tracing::debug!("Replacing Neo4j connection pool");
+            *pool = Pool {
+                graph: Self::connect(&self.config).await?,
+                created_at: Instant::now(),
+            };
+        }
+        Ok(pool.graph.clone())
+    }
+
+    /// Run a query that produces no rows of interest.
+    async fn run(&self, query: CypherQuery, context: &str) -> MemoryResult<(), neo4rs::Error> {
+        let query = &query;
+        with_retry(
+            &self.retry,
+            context,
+            query.is_idempotent(),
+            move || async move {
+                self.graph()
+                    .await?
+                    .run(query.clone().build())
+                    .await
+                    .map_err(|e| {
+                        MemoryError::query_error_with_source(format!("Failed to {}", context), e)
+                    })
+            },
+        )
+        .await
+    }
+
+    /// Execute a query and return its rows, reporting failures as `message`.
+    async fn execute(
+        &self,
+        query: CypherQuery,
+        message: &str,
+    ) -> MemoryResult<RowStream, neo4rs::Error> {
+        let query = &query;
+        with_retry(
+            &self.retry,
+            message,
+            query.is_idempotent(),
+            move || async move {
+                self.graph()
+                    .await?
+                    .execute(query.clone().build())
+                    .await
+                    .map(|rows| rows.into_stream().into_stream().boxed())
+                    .map_err(|e| MemoryError::query_error_with_source(message, e))
+            },
+        )
+        .await
+    }
+
+    /// Run several queries in one transaction, rolling back if any fails.
+    async fn run_in_transaction(
+        &self,
+        queries: Vec<CypherQuery>,
+        context: &str,
+    ) -> MemoryResult<(), neo4rs::Error> {
+        let queries = &queries;
+        let idempotent = queries.iter().all(CypherQuery::is_idempotent);
+        with_retry(&self.retry, context, idempotent, move || async move {
+            let error =
+                |e| MemoryError::query_error_with_source(format!("Failed to {}", context), e);
+            let graph = self.graph().await?;
+            let mut txn = graph.start_txn().await.map_err(error)?;
+            if let Err(e) = txn
+                .run_queries(queries.iter().cloned().map(CypherQuery::build))
+                .await
+            {
+                let _ = txn.rollback().await;
+                return Err(error(e));
+            }
+            txn.commit().await.map_err(error)
+        })
+        .await
+    }
+
+    /// Run `guard` and then `queries` in one transaction, unless one of
+    /// `required` is missing from the `name` column of the guard's rows.
+    ///
+    /// Whatever the guard checked still holds when the queries commit, as
+    /// long as it locks what it reads. Returns whether the queries ran.
+    async fn run_guarded(
+        &self,
+        guard: CypherQuery,
+        required: &HashSet<String>,
+        queries: Vec<CypherQuery>,
+        context: &str,
+    ) -> MemoryResult<bool, neo4rs::Error> {
+        let (guard, queries) = (&guard, &queries);
+        let idempotent = guard.is_idempotent() && queries.iter().all(CypherQuery::is_idempotent);
+        with_retry(&self.retry, context, idempotent, move || async move {
+            let error =
+                |e| MemoryError::query_error_with_source(format!("Failed to {}", context), e);
+            let graph = self.graph().await?;
+            let mut txn = graph.start_txn().await.map_err(error)?;
+            let outcome: Result<bool, neo4rs::Error> = async {
+                let mut rows = txn.execute(guard.clone().build()).await?;
+                let mut matched = HashSet::new();
+                while let Some(row) = rows.next(txn.handle()).await? {
+                    if let Ok(name) = row.get::<String>("name") {
+                        matched.insert(name);
+                    }
+                }
+                let proceed = required.is_subset(&matched);
+                if proceed {
+                    txn.run_queries(queries.iter().cloned().map(CypherQuery::build))
+                        .await?;
+                }
+                Ok(proceed)
+            }
+            .await;
+            match outcome {
+                Ok(true) => txn.commit().await.map(|_| true).map_err(error),
+                Ok(false) => {
+                    let _ = txn.rollback().await;
+                    Ok(false)
+                }
+                Err(e) => {
+                    let _ = txn.rollback().await;
+                    Err(error(e))
+                }
+            }
+        })
+        .await
+    }
+
+    /// Run `queries`, in a transaction when there is more than one.
+    async fn run_all(
+        &self,
+        mut queries: Vec<CypherQuery>,
+        context: &str,
+    ) -> MemoryResult<(), neo4rs::Error> {
+        if queries.len() == 1 {
+            let query = queries.remove(0);
+            return self.run(query, context).await;
+        }
+        self.run_in_transaction(queries, context).await
+    }
+
+    /// Execute queries ending in `RETURN n, rels` in one read transaction.
+    ///
+    /// Neo4j isolates transactions at read committed, so a write committed
+    /// while the queries run can still show up in the later ones. Memgraph's
+    /// default snapshot isolation keeps them on one view.
+    async fn entities_in_transaction(
+        &self,
+        queries: Vec<CypherQuery>,
+        context: &str,
+    ) -> MemoryResult<Vec<Vec<MemoryEntity>>, neo4rs::Error> {
+        let queries = &queries;
+        with_retry(&self.retry, context, true, move || async move {
+            let error =
+                |e| MemoryError::query_error_with_source(format!("Failed to {}", context), e);
+            let graph = self.graph().await?;
+            let mut txn = graph.start_txn().await.map_err(error)?;
+            let read = async {
+                let mut results = Vec::with_capacity(queries.len());
+                for query in queries {
+                    let mut rows = txn.execute(query.clone().build()).await.map_err(error)?;
+                    let mut entities = Vec::new();
+                    while let Some(row) = rows.next(txn.handle()).await.map_err(error)? {
+                        entities.push(memory_entity_from_row(&row)?);
+                    }
+                    results.push(entities);
+                }
+                Ok::<_, MemoryError<neo4rs::Error>>(results)
+            }
+            .await;
+            match read {
+                Ok(results) => {
+                    txn.commit().await.map_err(error)?;
+                    Ok(results)
+                }
+                Err(e) => {
+                    let _ = txn.rollback().await;
+                    Err(e)
+                }
+            }
+        })
+        .await
+    }
+
+    /// Query matching the entities related to `$name` as `n`.
+    fn related_query(
+        name: &str,
+        relationship_type: Option<&str>,
+        direction: Option<RelationshipDirection>,
+        depth: u32,
+    ) -> MemoryResult<CypherQuery, neo4rs::Error> {
+        let pattern = relationship_pattern(
+            "r",
+            relationship_type,
+            direction.unwrap_or(RelationshipDirection::Both),
+            depth,
+        )?;
+        Ok(CypherQuery::new()
+            .match_entity("start", "name")
+            .clause(format!("MATCH (start){}(n)", pattern))
+            .clause("WITH DISTINCT n")
+            .return_entities()
+            .param("name", name.to_string()))
+    }
+
+    /// Queries creating `entities`, which must not be empty.
+    fn create_entities_queries(
+        &self,
+        entities: &[MemoryEntity],
+    ) -> MemoryResult<Vec<CypherQuery>, neo4rs::Error> {
+        // Rows are grouped by label set so that, without APOC, each group
+        // can be created by a query with its labels written out.
+        let mut batches: BTreeMap<Vec<String>, Vec<HashMap<String, neo4rs::BoltType>>> =
+            BTreeMap::new();
+        for entity in entities {
+            let mut props = bolt_map(&entity.properties)?;
+            props.insert("name".to_string(), entity.name.clone().into());
+            props.insert(
+                "observations".to_string(),
+                entity.observations.clone().into(),
+            );
+            props.insert(
+                REVISION_PROPERTY.to_string(),
+                (entity.revision.max(1) as i64).into(),
+            );
+
+            let mut row: HashMap<String, neo4rs::BoltType> = HashMap::default();
+            row.insert("labels".to_string(), entity.labels.clone().into());
+            row.insert("props".to_string(), props.into());
+            let key = match self.dialect {
+                CypherDialect::Neo4j => Vec::new(),
+                CypherDialect::Memgraph => entity.labels.clone(),
+            };
+            batches.entry(key).or_default().push(row);
+        }
+
+        match self.dialect {
+            CypherDialect::Neo4j => Ok(vec![
+                CypherQuery::new()
+                    .clause("UNWIND $rows AS row")
+                    .clause("CALL apoc.create.node(row.labels, row.props) YIELD node")
+                    .clause("RETURN count(node)")
+                    .creating()
+                    .param("rows", batches.into_values().flatten().collect::<Vec<_>>()),
+            ]),
+            CypherDialect::Memgraph => batches
+                .into_iter()
+                .map(|(labels, rows)| {
+                    Ok(CypherQuery::new()
+                        .clause("UNWIND $rows AS row")
+                        .clause(format!("CREATE (n{})", label_expression(&labels)?))
+                        .clause("SET n = row.props")
+                        .clause("RETURN count(n)")
+                        .param("rows", rows)
+                        .creating())
+                })
+                .collect(),
+        }
+    }
+
+    /// Queries creating `relationships`, which must not be empty.
+    fn create_relationships_queries(
+        &self,
+        relationships: &[MemoryRelationship],
+    ) -> MemoryResult<Vec<CypherQuery>, neo4rs::Error> {
+        let mut batches: BTreeMap<&str, Vec<HashMap<String, neo4rs::BoltType>>> = BTreeMap::new();
+        for rel in relationships {
+            let mut row: HashMap<String, neo4rs::BoltType> = HashMap::default();
+            row.insert("from".to_string(), rel.from.clone().into());
+            row.insert("to".to_string(), rel.to.clone().into());
+            row.insert("name".to_string(), rel.name.clone().into());
+            row.insert("props".to_string(), bolt_map(&rel.properties)?.into());
+            let key = match self.dialect {
+                CypherDialect::Neo4j => "",
+                CypherDialect::Memgraph => rel.name.as_str(),
+            };
+            batches.entry(key).or_default().push(row);
+        }
+
+        match self.dialect {
+            CypherDialect::Neo4j => Ok(vec![
+                CypherQuery::new()
+                    .clause("UNWIND $rows AS row")
+                    .clause("MATCH (a {name: row.from}), (b {name: row.to})")
+                    .clause("CALL apoc.create.relationship(a, row.name, row.props, b) YIELD rel")
+                    .clause("RETURN count(rel)")
+                    .creating()
+                    .param("rows", batches.into_values().flatten().collect::<Vec<_>>()),
+            ]),
+            CypherDialect::Memgraph => batches
+                .into_iter()
+                .map(|(name, rows)| {
+                    Ok(CypherQuery::new()
+                        .clause("UNWIND $rows AS row")
+                        .clause("MATCH (a {name: row.from}), (b {name: row.to})")
+                        .clause(format!("CREATE (a)-[rel:{}]->(b)", quote_identifier(name)?))
+                        .clause("SET rel = row.props")
+                        .clause("RETURN count(rel)")
+                        .param("rows", rows)
+                        .creating())
+                })
+                .collect(),
+        }
+    }
+
+    /// Execute a query returning `name` and `count` columns.
+    async fn name_counts(
+        &self,
+        query: CypherQuery,
+        context: &str,
+    ) -> MemoryResult<Vec<(String, u64)>, neo4rs::Error> {
+        let mut result = self
+            .execute(query, &format!("Failed to query {}", context))
+            .await?;
+
+        let mut counts = Vec::new();
+        while let Some(row) = result.try_next().await.map_err(|e| {
+            MemoryError::query_error_with_source(format!("Failed to fetch {}", context), e)
+        })? {
+            let name = row.get::<String>("name").map_err(|e| {
+                MemoryError::runtime_error_with_source("Failed to get name".to_string(), e)
+            })?;
+            let count = row.get::<i64>("count").map_err(|e| {
+                MemoryError::runtime_error_with_source("Failed to get count".to_string(), e)
+            })?;
+            counts.push((name, count.max(0) as u64));
+        }
+        Ok(counts)
+    }
+
+    /// Execute a query returning `from`, `to`, `name` and `props` columns.
+    async fn relationship_rows(
+        &self,
+        query: CypherQuery,
+        context: &str,
+    ) -> MemoryResult<Vec<MemoryRelationship>, neo4rs::Error> {
+        let mut result = self
+            .execute(query, &format!("Failed to query {}", context))
+            .await?;
+
+        let mut rels = Vec::new();
+        while let Some(row) = result.try_next().await.map_err(|e| {
+            MemoryError::query_error_with_source(format!("Failed to fetch {}", context), e)
+        })? {
+            let from = row.get::<String>("from").map_err(|e| {
+                MemoryError::runtime_error_with_source("Failed to get from".to_string(), e)
+            })?;
+            let to = row.get::<String>("to").map_err(|e| {
+                MemoryError::runtime_error_with_source("Failed to get to".to_string(), e)
+            })?;
+            let name = row.get::<String>("name").map_err(|e| {
+                MemoryError::runtime_error_with_source("Failed to get name".to_string(), e)
+            })?;
+            let props_bolt = row.get::<neo4rs::BoltType>("props").map_err(|e| {
+                MemoryError::runtime_error_with_source("Failed to decode props".to_string(), e)
+            })?;
+            let mut properties = HashMap::new();
+            if let neo4rs::BoltType::Map(map) = props_bolt {
+                for (k, v) in &map.value {
+                    let mv = bolt_to_memory_value(v.clone())?;
+                    properties.insert(k.to_string(), mv);
+                }
+            }
+            rels.push(MemoryRelationship {
+                from,
+                to,
+                name,
+                properties,
+            });
+        }
+        Ok(rels)
+    }
+
+    /// Match entities `n` by labels, starting from `required_label` if any.
+    fn labels_query(
+        labels: &[String],
+        match_mode: LabelMatchMode,
+        required_label: Option<&str>,
+    ) -> MemoryResult<CypherQuery, neo4rs::Error> {
+        // Matching the required label in the pattern lets Neo4j start from the
+        // label scan and use any property indexes declared for it.
+        let pattern = match required_label {
+            Some(lbl) => format!("MATCH (n:{})", quote_identifier(lbl)?),
+            None => "MATCH (n)".to_string(),
+        };
+        let mut query = CypherQuery::new().clause(pattern);
+        if !labels.is_empty() {
+            let expr = match match_mode {
+                LabelMatchMode::Any => "ANY(l IN $labels WHERE l IN labels(n))",
+                LabelMatchMode::All => "ALL(l IN $labels WHERE l IN labels(n))",
+            };
+            query = query.and_where(expr).param("labels", labels.to_vec());
+        }
+        Ok(query)
+    }
+
+    /// Execute a query ending in `RETURN n, rels` and stream the entities.
+    async fn entity_stream(
+        &self,
+        query: CypherQuery,
+        context: &str,
+    ) -> MemoryResult<EntityStream<neo4rs::Error>, neo4rs::Error> {
+        tracing::debug!("Executing Neo4j query: {}", query.cypher());
+
+        let result = self
+            .execute(query, &format!("Failed to execute {}", context))
+            .await?;
+
+        let context = context.to_string();
+        let stream = result
+            .map_err(move |e| {
+                MemoryError::query_error_with_source(
+                    format!("Failed to retrieve {} results", context),
                     e,
                 )
-            })?;
+            })
+            .and_then(|row| async move { memory_entity_from_row(&row) });
 
-        Ok(Self { graph })
+        Ok(stream.boxed())
     }
 
-    #[instrument(skip(self, params, update))]
-    async fn apply_property_update(
+    /// Queries applying the observation, property and label changes of
+    /// `update` to `name`, without bumping its revision
+    fn entity_update_queries(
         &self,
-        match_clause: &str,
+        name: &str,
+        update: &EntityUpdate,
+    ) -> MemoryResult<Vec<CypherQuery>, neo4rs::Error> {
+        let entity = || {
+            CypherQuery::new()
+                .match_entity("n", "name")
+                .param("name", name.to_string())
+        };
+        let mut queries = Vec::new();
+
+        if let Some(obs) = &update.observations {
+            let change = match (&obs.set, &obs.add, &obs.remove) {
+                (Some(set), _, _) => Some(("SET n.observations = $observations", set)),
+                (None, Some(add), _) => Some((ADD_OBSERVATIONS, add)),
+                (None, None, Some(remove)) => Some((REMOVE_OBSERVATIONS, remove)),
+                (None, None, None) => None,
+            };
+            if let Some((clause, list)) = change {
+                queries.push(entity().clause(clause).param("observations", list.clone()));
+            }
+        }
+
+        if let Some(props) = &update.properties {
+            queries.extend(Self::property_update_query(
+                entity(),
+                "n",
+                props,
+                Some(&["name", "observations", REVISION_PROPERTY]),
+            )?);
+        }
+
+        if let Some(labels) = &update.labels {
+            // On Neo4j labels go through APOC as a parameter instead of
+            // being interpolated into the query text.
+            let change = match (&labels.add, &labels.remove) {
+                (Some(add), _) => Some(("apoc.create.addLabels", add, "add")),
+                (None, Some(remove)) => Some(("apoc.create.removeLabels", remove, "remove")),
+                (None, None) => None,
+            };
+            if let Some((procedure, list, action)) = change
+                && !list.is_empty()
+            {
+                let query = match self.dialect {
+                    CypherDialect::Neo4j => entity()
+                        .clause(format!("CALL {}(n, $labels) YIELD node", procedure))
+                        .clause("RETURN count(node)")
+                        .param("labels", list.clone()),
+                    CypherDialect::Memgraph => {
+                        let keyword = if action == "add" { "SET" } else { "REMOVE" };
+                        entity().clause(format!("{keyword} n{}", label_expression(list)?))
+                    }
+                };
+                queries.push(query);
+            }
+        }
+
+        Ok(queries)
+    }
+
+    /// Query applying `update` to the properties of the element matched by
+    /// `base` as `identifier`, if there is anything to change.
+    ///
+    /// The keys in `preserve` survive a `set`, which otherwise replaces the
+    /// whole property map.
+    fn property_update_query(
+        base: CypherQuery,
         identifier: &str,
-        params: &[(&str, String)],
         update: &PropertiesUpdate,
         preserve: Option<&[&str]>,
-        context: &str,
-    ) -> MemoryResult<(), neo4rs::Error> {
+    ) -> MemoryResult<Option<CypherQuery>, neo4rs::Error> {
         if let Some(add) = &update.add {
-            let mut map: HashMap<String, neo4rs::BoltType> = HashMap::new();
-            for (k, v) in add {
-                map.insert(k.clone(), memory_value_to_bolt(v)?);
-            }
-            let qstr = format!("{} SET {} += $props", match_clause, identifier);
-            let mut query = Query::new(qstr).param("props", map);
-            for (k, v) in params {
-                query = query.param(k, v.clone());
-            }
-            self.graph.run(query).await.map_err(|e| {
-                MemoryError::query_error_with_source(format!("Failed to add {}", context), e)
-            })?;
+            let query = base
+                .clause(format!("SET {} += $props", identifier))
+                .param("props", bolt_map(add)?);
+            return Ok(Some(query));
         } else if let Some(remove) = &update.remove {
             if !remove.is_empty() {
                 let fields = remove
                     .iter()
-                    .map(|k| format!("{}.`{}`", identifier, k))
-                    .collect::<Vec<_>>()
+                    .map(|k| Ok(format!("{}.{}", identifier, quote_identifier(k)?)))
+                    .collect::<MemoryResult<Vec<_>, neo4rs::Error>>()?
                     .join(", ");
-                let qstr = format!("{} REMOVE {}", match_clause, fields);
-                let mut query = Query::new(qstr);
-                for (k, v) in params {
-                    query = query.param(k, v.clone());
-                }
-                self.graph.run(query).await.map_err(|e| {
-                    MemoryError::query_error_with_source(format!("Failed to remove {}", context), e)
-                })?;
+                return Ok(Some(base.clause(format!("REMOVE {}", fields))));
             }
         } else if let Some(set_map) = &update.set {
-            let mut map: HashMap<String, neo4rs::BoltType> = HashMap::new();
-            for (k, v) in set_map {
-                map.insert(k.clone(), memory_value_to_bolt(v)?);
-            }
-            let qstr = if let Some(keep) = preserve {
-                let cond = keep
+            let query = if let Some(keep) = preserve {
+                // Capture the preserved keys, replace the whole map and then
+                // restore them on top of the new properties.
+                let kept = keep
                     .iter()
-                    .map(|k| format!("x <> '{}'", k))
-                    .collect::<Vec<_>>()
-                    .join(" AND ");
-                format!(
-                    "{match_clause} WITH {id}, keys({id}) AS k UNWIND [x IN k WHERE {cond}] AS key REMOVE {id}[key] WITH {id} SET {id} += $props",
-                    match_clause = match_clause,
-                    id = identifier,
-                    cond = cond
-                )
+                    .map(|k| {
+                        let key = quote_identifier(k)?;
+                        Ok(format!("{key}: {identifier}.{key}"))
+                    })
+                    .collect::<MemoryResult<Vec<_>, neo4rs::Error>>()?
+                    .join(", ");
+                base.clause(format!("WITH {identifier}, {{{kept}}} AS kept"))
+                    .clause(format!("SET {identifier} = $props"))
+                    .clause(format!("SET {identifier} += kept"))
             } else {
-                format!("{} SET {} = $props", match_clause, identifier)
+                base.clause(format!("SET {} = $props", identifier))
             };
-            let mut query = Query::new(qstr).param("props", map);
-            for (k, v) in params {
-                query = query.param(k, v.clone());
-            }
-            self.graph.run(query).await.map_err(|e| {
-                MemoryError::query_error_with_source(format!("Failed to set {}", context), e)
-            })?;
+            return Ok(Some(query.param("props", bolt_map(set_map)?)));
         }
-        Ok(())
+        Ok(None)
     }
 }
 
@@ -107,43 +726,27 @@ impl Neo4jRepository {
 impl MemoryRepository for Neo4jRepository {
     type Error = neo4rs::Error;
 
+    fn backend_info(&self) -> BackendInfo {
+        let backend = match self.dialect {
+            CypherDialect::Neo4j => "neo4j",
+            CypherDialect::Memgraph => "memgraph",
+        };
+        BackendInfo {
+            backend: backend.to_string(),
+            database: Some(match &self.config.database {
+                Some(database) => format!("{}/{}", self.config.uri, database),
+                None => self.config.uri.clone(),
+            }),
+        }
+    }
+
     #[instrument(skip(self, entities), fields(count = entities.len()))]
     async fn create_entities(&self, entities: &[MemoryEntity]) -> MemoryResult<(), Self::Error> {
         if entities.is_empty() {
             return Ok(());
         }
-
-        let mut batch: Vec<HashMap<String, neo4rs::BoltType>> = Vec::default();
-        for entity in entities {
-            let mut props: HashMap<String, neo4rs::BoltType> = HashMap::default();
-            props.insert("name".to_string(), entity.name.clone().into());
-            props.insert(
-                "observations".to_string(),
-                entity.observations.clone().into(),
-            );
-
-            for (k, v) in &entity.properties {
-                let bolt = memory_value_to_bolt(v)?;
-                props.insert(k.clone(), bolt);
-            }
-
-            let mut row: HashMap<String, neo4rs::BoltType> = HashMap::default();
-            row.insert("labels".to_string(), entity.labels.clone().into());
-            row.insert("props".to_string(), props.into());
-            batch.push(row);
-        }
-
-        let query = Query::new(
-            "UNWIND $rows AS row CALL apoc.create.node(row.labels, row.props) YIELD node RETURN count(node)"
-                .to_string(),
-        )
-        .param("rows", batch);
-
-        self.graph.run(query).await.map_err(|e| {
-            MemoryError::query_error_with_source("Failed to create entities".to_string(), e)
-        })?;
-
-        Ok(())
+        let queries = self.create_entities_queries(entities)?;
+        self.run_all(queries, "create entities").await
     }
 
     #[instrument(skip(self), fields(name = %name))]
@@ -155,51 +758,31 @@ impl MemoryRepository for Neo4jRepository {
             return Err(ValidationError::from(ValidationErrorKind::EmptyEntityName).into());
         }
 
-        let query = Query::new(
-            "MATCH (n {name: $name}) \n \
-             OPTIONAL MATCH (n)-[r]-() \n \
-             WITH n, collect(CASE WHEN r IS NOT NULL THEN {from: startNode(r).name, to: endNode(r).name, name: type(r), properties: properties(r)} END) as rels\n \
-             RETURN n, [x IN rels WHERE x IS NOT NULL] as rels"
-                .to_string(),
-        )
-        .param("name", name.to_string());
+        let query = CypherQuery::new()
+            .match_entity("n", "name")
+            .return_entities()
+            .param("name", name.to_string());
 
-        let mut result = self.graph.execute(query).await.map_err(|e| {
-            MemoryError::query_error_with_source(
-                format!("Failed to execute query to find entity {}", name),
-                e,
-            )
-        })?;
-
-        if let Some(row) = result.next().await.map_err(|e| {
-            MemoryError::query_error_with_source(
-                format!("Failed to retrieve result for entity {}", name),
-                e,
-            )
-        })? {
-            let node = match row.get::<Node>("n") {
-                Ok(n) => n,
-                Err(e) => {
-                    return Err(MemoryError::runtime_error_with_source(
-                        format!("Failed to get node from result for entity {}", name),
-                        e,
-                    ));
-                }
-            };
-
-            let rels_bolt = row.get::<neo4rs::BoltType>("rels").map_err(|e| {
-                MemoryError::runtime_error_with_source(
-                    "Failed to decode relationships".to_string(),
-                    e,
-                )
-            })?;
-
-            let entity = memory_entity_from_node(&node, rels_bolt)?;
+        self.entity_stream(query, &format!("query to find entity {}", name))
+            .await?
+            .try_next()
+            .await
+    }
 
-            Ok(Some(entity))
-        } else {
-            Ok(None)
+    #[instrument(skip(self, names), fields(names_count = names.len()))]
+    async fn find_existing_names(
+        &self,
+        names: &[String],
+    ) -> MemoryResult<Vec<String>, Self::Error> {
+        if names.is_empty() {
+            return Ok(Vec::new());
         }
+        let query = CypherQuery::new()
+            .clause("MATCH (n) WHERE n.name IN $names")
+            .clause("RETURN n.name AS name, count(n) AS count")
+            .param("names", names.to_vec());
+        let counts = self.name_counts(query, "existing names").await?;
+        Ok(counts.into_iter().map(|(name, _)| name).collect())
     }
 
     #[instrument(skip(self, observations), fields(name = %name))]
@@ -212,19 +795,15 @@ impl MemoryRepository for Neo4jRepository {
             return Err(ValidationError::from(ValidationErrorKind::EmptyEntityName).into());
         }
 
-        let query =
-            Query::new("MATCH (n {name: $name}) SET n.observations = $observations".to_string())
-                .param("name", name.to_string())
-                .param("observations", observations.to_vec());
-
-        self.graph.run(query).await.map_err(|e| {
-            MemoryError::query_error_with_source(
-                format!("Failed to set observations for entity {}", name),
-                e,
-            )
-        })?;
+        let query = CypherQuery::new()
+            .match_entity("n", "name")
+            .clause("SET n.observations = $observations")
+            .clause(BUMP_REVISION)
+            .param("name", name.to_string())
+            .param("observations", observations.to_vec());
 
-        Ok(())
+        self.run(query, &format!("set observations for entity {}", name))
+            .await
     }
 
     #[instrument(skip(self, observations), fields(name = %name))]
@@ -233,21 +812,24 @@ impl MemoryRepository for Neo4jRepository {
         name: &str,
         observations: &[String],
     ) -> MemoryResult<(), Self::Error> {
-        let query = Query::new(
-            "MATCH (n {name: $name}) SET n.observations = coalesce(n.observations, []) + $observations"
-                .to_string(),
-        )
-        .param("name", name.to_string())
-        .param("observations", observations.to_vec());
-
-        self.graph.run(query).await.map_err(|e| {
-            MemoryError::query_error_with_source(
-                format!("Failed to add observations for {}", name),
-                e,
-            )
-        })?;
+        if name.is_empty() {
+            return Err(ValidationError::from(ValidationErrorKind::EmptyEntityName).into());
+        }
 
-        Ok(())
+        // Fold the new observations into the stored list server-side so that
+        // duplicates (including repeats within the request) are skipped in the
+        // same statement that writes them. The node is write-locked before the
+        // current list is read so concurrent writers cannot lose updates.
+        let query = CypherQuery::new()
+            .clause(OBSERVATIONS_LOCK)
+            .clause(ADD_OBSERVATIONS)
+            .clause(BUMP_REVISION)
+            .clause(OBSERVATIONS_UNLOCK)
+            .param("name", name.to_string())
+            .param("observations", observations.to_vec());
+
+        self.run(query, &format!("add observations for {}", name))
+            .await
     }
 
     #[instrument(skip(self), fields(name = %name))]
@@ -261,21 +843,20 @@ impl MemoryRepository for Neo4jRepository {
         name: &str,
         observations: &[String],
     ) -> MemoryResult<(), Self::Error> {
-        let query = Query::new(
-            "MATCH (n {name: $name}) SET n.observations = [o IN coalesce(n.observations, []) WHERE NOT o IN $remove]"
-                .to_string(),
-        )
-        .param("name", name.to_string())
-        .param("remove", observations.to_vec());
+        if name.is_empty() {
+            return Err(ValidationError::from(ValidationErrorKind::EmptyEntityName).into());
+        }
 
-        self.graph.run(query).await.map_err(|e| {
-            MemoryError::query_error_with_source(
-                format!("Failed to remove observations for {}", name),
-                e,
-            )
-        })?;
+        let query = CypherQuery::new()
+            .clause(OBSERVATIONS_LOCK)
+            .clause(REMOVE_OBSERVATIONS)
+            .clause(BUMP_REVISION)
+            .clause(OBSERVATIONS_UNLOCK)
+            .param("name", name.to_string())
+            .param("observations", observations.to_vec());
 
-        Ok(())
+        self.run(query, &format!("remove observations for {}", name))
+            .await
     }
 
     #[instrument(skip(self, relationships), fields(count = relationships.len()))]
@@ -286,34 +867,28 @@ impl MemoryRepository for Neo4jRepository {
         if relationships.is_empty() {
             return Ok(());
         }
+        let queries = self.create_relationships_queries(relationships)?;
+        self.run_all(queries, "create relationships").await
+    }
 
-        let mut rows: Vec<HashMap<String, neo4rs::BoltType>> = Vec::default();
-        for rel in relationships {
-            let mut props: HashMap<String, neo4rs::BoltType> = HashMap::default();
-            for (k, v) in &rel.properties {
-                let bolt = memory_value_to_bolt(v)?;
-                props.insert(k.clone(), bolt);
-            }
-
-            let mut row: HashMap<String, neo4rs::BoltType> = HashMap::default();
-            row.insert("from".to_string(), rel.from.clone().into());
-            row.insert("to".to_string(), rel.to.clone().into());
-            row.insert("name".to_string(), rel.name.clone().into());
-            row.insert("props".to_string(), props.into());
-            rows.push(row);
+    #[instrument(skip(self, entities, relationships), fields(entities = entities.len(), relationships = relationships.len()))]
+    async fn create_graph(
+        &self,
+        entities: &[MemoryEntity],
+        relationships: &[MemoryRelationship],
+    ) -> MemoryResult<(), Self::Error> {
+        let mut queries = Vec::new();
+        if !entities.is_empty() {
+            queries.extend(self.create_entities_queries(entities)?);
         }
-
-        let query = Query::new(
-            "UNWIND $rows AS row MATCH (a {name: row.from}), (b {name: row.to}) CALL apoc.create.relationship(a, row.name, row.props, b) YIELD rel RETURN count(rel)"
-                .to_string(),
-        )
-        .param("rows", rows);
-
-        self.graph.run(query).await.map_err(|e| {
-            MemoryError::query_error_with_source("Failed to create relationships".to_string(), e)
-        })?;
-
-        Ok(())
+        if !relationships.is_empty() {
+            queries.extend(self.create_relationships_queries(relationships)?);
+        }
+        if queries.is_empty() {
+            return Ok(());
+        }
+        self.run_in_transaction(queries, "create entities and relationships")
+            .await
     }
 
     #[instrument(skip(self), fields(name = %name, depth))]
@@ -324,65 +899,65 @@ impl MemoryRepository for Neo4jRepository {
         direction: Option<RelationshipDirection>,
         depth: u32,
     ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        self.stream_related_entities(name, relationship_type, direction, depth)
+            .await?
+            .try_collect()
+            .await
+    }
+
+    #[instrument(skip(self), fields(name = %name, depth))]
+    async fn stream_related_entities(
+        &self,
+        name: &str,
+        relationship_type: Option<String>,
+        direction: Option<RelationshipDirection>,
+        depth: u32,
+    ) -> MemoryResult<EntityStream<Self::Error>, Self::Error> {
         if name.is_empty() {
             return Err(ValidationError::from(ValidationErrorKind::EmptyEntityName).into());
         }
 
-        let dir = direction.unwrap_or(RelationshipDirection::Both);
-        let rel_type = relationship_type
-            .as_deref()
-            .map(|t| format!(":{}", t))
-            .unwrap_or_default();
-        let pattern = match dir {
-            RelationshipDirection::Outgoing => format!("-[r{}*1..{}]->", rel_type, depth),
-            RelationshipDirection::Incoming => format!("<-[r{}*1..{}]-", rel_type, depth),
-            RelationshipDirection::Both => format!("-[r{}*1..{}]-", rel_type, depth),
-        };
-
-        let query_str = format!(
-            "MATCH (start {{name: $name}}) MATCH (start){}(n)\n \
-             WITH DISTINCT n\n \
-             OPTIONAL MATCH (n)-[r]-()\n \
-             WITH n, collect(CASE WHEN r IS NOT NULL THEN {{from: startNode(r).name, to: endNode(r).name, name: type(r), properties: properties(r)}} END) as rels\n \
-             RETURN n, [x IN rels WHERE x IS NOT NULL] as rels",
-            pattern
-        );
-
-        let query = Query::new(query_str).param("name", name.to_string());
-        let mut result = self.graph.execute(query).await.map_err(|e| {
-            MemoryError::query_error_with_source(
-                format!("Failed to execute related entity query for {}", name),
-                e,
-            )
-        })?;
-
-        let mut entities = Vec::new();
-        while let Some(row) = result.next().await.map_err(|e| {
-            MemoryError::query_error_with_source(
-                format!("Failed to retrieve related entity results for {}", name),
-                e,
-            )
-        })? {
-            let node = row.get::<Node>("n").map_err(|e| {
-                MemoryError::runtime_error_with_source(
-                    "Failed to get node from result".to_string(),
-                    e,
-                )
-            })?;
-
-            let rels_bolt = row.get::<neo4rs::BoltType>("rels").map_err(|e| {
-                MemoryError::runtime_error_with_source(
-                    "Failed to decode relationships".to_string(),
-                    e,
-                )
-            })?;
+        let query = Self::related_query(name, relationship_type.as_deref(), direction, depth)?;
+        self.entity_stream(query, &format!("related entity query for {}", name))
+            .await
+    }
 
-            let entity = memory_entity_from_node(&node, rels_bolt)?;
+    #[instrument(skip(self, queries), fields(name = %name, queries = queries.len()))]
+    async fn find_entity_snapshot(
+        &self,
+        name: &str,
+        queries: &[RelatedQuery],
+    ) -> MemoryResult<Option<EntitySnapshot>, Self::Error> {
+        if name.is_empty() {
+            return Err(ValidationError::from(ValidationErrorKind::EmptyEntityName).into());
+        }
 
-            entities.push(entity);
+        let mut built = vec![
+            CypherQuery::new()
+                .match_entity("n", "name")
+                .return_entities()
+                .param("name", name.to_string()),
+        ];
+        for query in queries {
+            built.push(Self::related_query(
+                name,
+                query.relationship.as_deref(),
+                query.direction,
+                query.depth,
+            )?);
         }
 
-        Ok(entities)
+        let mut results = self
+            .entities_in_transaction(built, &format!("read snapshot of {}", name))
+            .await?
+            .into_iter();
+        let Some(entity) = results.next().and_then(|found| found.into_iter().next()) else {
+            return Ok(None);
+        };
+        Ok(Some(EntitySnapshot {
+            entity,
+            related: results.collect(),
+        }))
     }
 
     #[instrument(skip(self, labels), fields(labels_count = labels.len()))]
@@ -392,136 +967,374 @@ impl MemoryRepository for Neo4jRepository {
         match_mode: LabelMatchMode,
         required_label: Option<String>,
     ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
-        let mut conditions = Vec::new();
-        if required_label.is_some() {
-            conditions.push("$required IN labels(n)".to_string());
-        }
-        if !labels.is_empty() {
-            let expr = match match_mode {
-                LabelMatchMode::Any => "ANY(l IN $labels WHERE l IN labels(n))".to_string(),
-                LabelMatchMode::All => "ALL(l IN $labels WHERE l IN labels(n))".to_string(),
-            };
-            conditions.push(expr);
-        }
-        let where_clause = if conditions.is_empty() {
-            String::default()
-        } else {
-            format!("WHERE {}", conditions.join(" AND "))
-        };
-
-        let query_str = format!(
-            "MATCH (n) {where_clause}\n \
-             OPTIONAL MATCH (n)-[r]-()\n \
-             WITH n, collect(CASE WHEN r IS NOT NULL THEN {{from: startNode(r).name, to: endNode(r).name, name: type(r), properties: properties(r)}} END) as rels\n \
-             RETURN n, [x IN rels WHERE x IS NOT NULL] as rels",
-            where_clause = where_clause
-        );
+        self.stream_entities_by_labels(labels, match_mode, required_label)
+            .await?
+            .try_collect()
+            .await
+    }
 
-        tracing::debug!("Executing Neo4j query: {}", query_str);
+    #[instrument(skip(self, labels), fields(labels_count = labels.len()))]
+    async fn stream_entities_by_labels(
+        &self,
+        labels: &[String],
+        match_mode: LabelMatchMode,
+        required_label: Option<String>,
+    ) -> MemoryResult<EntityStream<Self::Error>, Self::Error> {
         tracing::debug!(
             "Query parameters: labels={:?}, required={:?}",
             labels,
             required_label
         );
 
-        let mut query = Query::new(query_str).param("labels", labels.to_vec());
-        if let Some(lbl) = required_label {
-            query = query.param("required", lbl);
+        let query = Self::labels_query(labels, match_mode, required_label.as_deref())?;
+        self.entity_stream(query.return_entities(), "label query")
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn find_entities_fuzzy(
+        &self,
+        name: &str,
+        limit: usize,
+        required_label: Option<String>,
+    ) -> MemoryResult<Vec<FuzzyMatch>, Self::Error> {
+        let query = Self::labels_query(&[], LabelMatchMode::Any, required_label.as_deref())?;
+        // APOC preselects candidates; they are ranked with the same scoring
+        // as other backends
+        let query = match self.dialect {
+            CypherDialect::Neo4j => query
+                .and_where(
+                    "apoc.text.levenshteinSimilarity(toLower(n.name), $name) >= $min \
+                     OR apoc.text.sorensenDiceSimilarity(toLower(n.name), $name) >= $min",
+                )
+                .param("name", name.to_lowercase())
+                .param("min", MIN_FUZZY_SCORE),
+            CypherDialect::Memgraph => query,
+        };
+        let candidates = self
+            .entity_stream(query.return_entities(), "fuzzy name query")
+            .await?
+            .try_collect()
+            .await?;
+        Ok(rank_by_name(candidates, name, limit))
+    }
+
+    #[instrument(skip(self, labels, filters), fields(labels_count = labels.len(), filters_count = filters.len()))]
+    async fn find_entities_by_labels_filtered(
+        &self,
+        labels: &[String],
+        match_mode: LabelMatchMode,
+        required_label: Option<String>,
+        filters: &[PropertyFilter],
+        sort: &[PropertySort],
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        let mut query = Self::labels_query(labels, match_mode, required_label.as_deref())?;
+        for (i, filter) in filters.iter().enumerate() {
+            let property = format!("n.{}", quote_identifier(&filter.property)?);
+            let param = format!("filter_{i}");
+            let condition = match filter.op {
+                PropertyOperator::Equals => format!("{property} = ${param}"),
+                PropertyOperator::GreaterThan => format!("{property} > ${param}"),
+                PropertyOperator::LessThan => format!("{property} < ${param}"),
+                // Appending to an empty list leaves lists unchanged and wraps
+                // anything else, which tells list properties from strings
+                PropertyOperator::Contains => format!(
+                    "CASE WHEN {property} = [] + {property} THEN ${param} IN {property} \
+                     ELSE {property} CONTAINS ${param} END"
+                ),
+            };
+            query = query
+                .and_where(condition)
+                .param(&param, memory_value_to_bolt(&filter.value)?);
         }
 
-        let mut result = self.graph.execute(query).await.map_err(|e| {
-            MemoryError::query_error_with_source("Failed to execute label query".to_string(), e)
-        })?;
+        let mut query = query.return_entities();
+        if !sort.is_empty() {
+            // Missing values sort last in either direction, ties by name
+            let mut keys = Vec::with_capacity(sort.len() * 2 + 1);
+            for key in sort {
+                let property = format!("n.{}", quote_identifier(&key.property)?);
+                let direction = match key.direction {
+                    SortDirection::Asc => "ASC",
+                    SortDirection::Desc => "DESC",
+                };
+                keys.push(format!("{property} IS NULL"));
+                keys.push(format!("{property} {direction}"));
+            }
+            keys.push("n.name".to_string());
+            query = query.clause(format!("ORDER BY {}", keys.join(", ")));
+        }
 
-        let mut entities = Vec::new();
-        while let Some(row) = result.next().await.map_err(|e| {
-            MemoryError::query_error_with_source(
-                "Failed to retrieve label query results".to_string(),
-                e,
-            )
-        })? {
-            let node = row.get::<Node>("n").map_err(|e| {
-                MemoryError::runtime_error_with_source(
-                    "Failed to get node from result".to_string(),
-                    e,
-                )
-            })?;
+        self.entity_stream(query, "filtered label query")
+            .await?
+            .try_collect()
+            .await
+    }
 
-            let rels_bolt = row.get::<neo4rs::BoltType>("rels").map_err(|e| {
-                MemoryError::runtime_error_with_source(
-                    "Failed to decode relationships".to_string(),
-                    e,
-                )
-            })?;
+    #[instrument(skip(self), fields(source_key = %source_key))]
+    async fn find_entities_by_source(
+        &self,
+        source_key: &str,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        let query = CypherQuery::new()
+            .clause("MATCH (n)")
+            .and_where(format!(
+                "$source IN coalesce(n.{}, [])",
+                quote_identifier(SOURCES_PROPERTY)?
+            ))
+            .param("source", source_key.to_string());
+
+        self.entity_stream(query.return_entities(), "source query")
+            .await?
+            .try_collect()
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_labels(&self) -> MemoryResult<Vec<(String, u64)>, Self::Error> {
+        let query = CypherQuery::new().clause(
+            "MATCH (n) UNWIND labels(n) AS name RETURN name, count(*) AS count ORDER BY name",
+        );
+        self.name_counts(query, "labels").await
+    }
 
-            let entity = memory_entity_from_node(&node, rels_bolt)?;
+    #[instrument(skip(self))]
+    async fn list_relationship_types(&self) -> MemoryResult<Vec<(String, u64)>, Self::Error> {
+        let query = CypherQuery::new()
+            .clause("MATCH ()-[r]->() RETURN type(r) AS name, count(r) AS count ORDER BY name");
+        self.name_counts(query, "relationship types").await
+    }
 
-            entities.push(entity);
+    #[instrument(skip(self), fields(project = %filter.project))]
+    async fn find_tasks(
+        &self,
+        filter: &TaskFilter,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        let mut query = CypherQuery::new()
+            .clause(format!(
+                "MATCH (p {{name: $project}})-[:contains]->(n:{})",
+                quote_identifier(TASK_LABEL)?
+            ))
+            .param("project", filter.project.clone());
+        if !filter.labels.is_empty() {
+            query = query
+                .and_where("ALL(l IN $labels WHERE l IN labels(n))")
+                .param("labels", filter.labels.clone());
+        }
+        if !filter.statuses.is_empty() {
+            query = query
+                .and_where("n.status IN $statuses")
+                .param("statuses", filter.statuses.clone());
+        }
+        if !filter.priorities.is_empty() {
+            query = query
+                .and_where("n.priority IN $priorities")
+                .param("priorities", filter.priorities.clone());
+        }
+        // Due dates are stored as dates; older tasks may still hold timestamps
+        let due = match self.dialect {
+            CypherDialect::Neo4j => "date(n.due_date)",
+            CypherDialect::Memgraph => "n.due_date",
+        };
+        if let Some(before) = filter.due_before {
+            query = query.and_where(format!("{due} < $due_before")).param(
+                "due_before",
+                memory_value_to_bolt(&MemoryValue::Date(before))?,
+            );
+        }
+        if let Some(after) = filter.due_after {
+            query = query.and_where(format!("{due} >= $due_after")).param(
+                "due_after",
+                memory_value_to_bolt(&MemoryValue::Date(after))?,
+            );
         }
+        let query = query.clause("WITH DISTINCT n").return_entities();
 
-        Ok(entities)
+        self.entity_stream(query, &format!("task query for {}", filter.project))
+            .await?
+            .try_collect()
+            .await
     }
 
-    async fn update_entity(
+    #[instrument(skip(self))]
+    async fn list_project_summaries(
         &self,
-        name: &str,
-        update: &EntityUpdate,
-    ) -> MemoryResult<(), Self::Error> {
-        if let Some(obs) = &update.observations {
-            if let Some(set) = &obs.set {
-                self.set_observations(name, set).await?;
-            } else if let Some(add) = &obs.add {
-                self.add_observations(name, add).await?;
-            } else if let Some(remove) = &obs.remove {
-                self.remove_observations(name, remove).await?;
-            }
-        }
+        required_label: Option<String>,
+    ) -> MemoryResult<Vec<ProjectSummary>, Self::Error> {
+        let query = Self::labels_query(
+            &[PROJECT_LABEL.to_string()],
+            LabelMatchMode::All,
+            required_label.as_deref(),
+        )?
+        .clause(format!(
+            "OPTIONAL MATCH (n)-[:contains]->(t:{})",
+            quote_identifier(TASK_LABEL)?
+        ))
+        .clause(
+            "WITH n, count(DISTINCT t) AS task_count, \
+             count(DISTINCT CASE WHEN NOT toLower(coalesce(t.status, '')) IN $closed THEN t END) AS open_task_count, \
+             max(t.updated_at) AS last_task_update \
+             RETURN n, [] AS rels, task_count, open_task_count, last_task_update ORDER BY n.name",
+        )
+        .param(
+            "closed",
+            CLOSED_TASK_STATUSES
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>(),
+        );
 
-        if let Some(props) = &update.properties {
-            let params = [("name", name.to_string())];
-            self.apply_property_update(
-                "MATCH (n {name: $name})",
-                "n",
-                &params,
-                props,
-                Some(&["name", "observations"]),
-                &format!("properties for {}", name),
-            )
+        let mut result = self
+            .execute(query, "Failed to query project summaries")
             .await?;
-        }
-
-        if let Some(labels) = &update.labels {
-            if let Some(add) = &labels.add {
-                if !add.is_empty() {
-                    let label_str = add.iter().map(|l| format!(":`{}`", l)).collect::<String>();
-                    let query_str = format!("MATCH (n {{name: $name}}) SET n{}", label_str);
-                    let query = Query::new(query_str).param("name", name.to_string());
-                    self.graph.run(query).await.map_err(|e| {
-                        MemoryError::query_error_with_source(
-                            format!("Failed to add labels for {}", name),
+        let mut summaries = Vec::new();
+        while let Some(row) = result.try_next().await.map_err(|e| {
+            MemoryError::query_error_with_source("Failed to fetch project summaries", e)
+        })? {
+            let count = |column: &str| {
+                row.get::<i64>(column)
+                    .map(|c| c.max(0) as u64)
+                    .map_err(|e| {
+                        MemoryError::runtime_error_with_source(
+                            format!("Failed to get {}", column),
                             e,
                         )
-                    })?;
-                }
-            } else if let Some(remove) = &labels.remove {
-                if !remove.is_empty() {
-                    let label_str = remove
-                        .iter()
-                        .map(|l| format!(":`{}`", l))
-                        .collect::<String>();
-                    let query_str = format!("MATCH (n {{name: $name}}) REMOVE n{}", label_str);
-                    let query = Query::new(query_str).param("name", name.to_string());
-                    self.graph.run(query).await.map_err(|e| {
-                        MemoryError::query_error_with_source(
-                            format!("Failed to remove labels for {}", name),
+                    })
+            };
+            let last_task_update =
+                row.get::<neo4rs::BoltType>("last_task_update")
+                    .map_err(|e| {
+                        MemoryError::runtime_error_with_source(
+                            "Failed to get last_task_update".to_string(),
                             e,
                         )
                     })?;
-                }
+            let last_task_update = match last_task_update {
+                neo4rs::BoltType::Null(_) => None,
+                bolt => value_to_datetime(&bolt_to_memory_value(bolt)?),
+            };
+            summaries.push(ProjectSummary::new(
+                memory_entity_from_row(&row)?,
+                count("task_count")?,
+                count("open_task_count")?,
+                last_task_update,
+            ));
+        }
+        Ok(summaries)
+    }
+
+    #[instrument(skip(self))]
+    async fn create_property_index(
+        &self,
+        label: &str,
+        property: &str,
+    ) -> MemoryResult<(), Self::Error> {
+        let query = match self.dialect {
+            CypherDialect::Neo4j => {
+                let index_name = quote_identifier(&format!("mm_{}_{}", label, property))?;
+                CypherQuery::new().clause(format!(
+                    "CREATE INDEX {} IF NOT EXISTS FOR (n:{}) ON (n.{})",
+                    index_name,
+                    quote_identifier(label)?,
+                    quote_identifier(property)?
+                ))
+            }
+            // Memgraph indexes are unnamed and creating one twice is a no-op
+            CypherDialect::Memgraph => CypherQuery::new().clause(format!(
+                "CREATE INDEX ON :{}({})",
+                quote_identifier(label)?,
+                quote_identifier(property)?
+            )),
+        };
+        self.run(query, &format!("create index on {}.{}", label, property))
+            .await
+    }
+
+    async fn update_entity(
+        &self,
+        name: &str,
+        update: &EntityUpdate,
+    ) -> MemoryResult<(), Self::Error> {
+        let item = NamedEntityUpdate {
+            name: name.to_string(),
+            update: update.clone(),
+        };
+        self.update_entities(std::slice::from_ref(&item)).await
+    }
+
+    async fn update_entities(
+        &self,
+        updates: &[NamedEntityUpdate],
+    ) -> MemoryResult<(), Self::Error> {
+        if updates.is_empty() {
+            return Ok(());
+        }
+        let mut queries = Vec::new();
+        let mut changed = Vec::new();
+        for item in updates {
+            let before = queries.len();
+            queries.extend(self.entity_update_queries(&item.name, &item.update)?);
+            if queries.len() > before {
+                changed.push(item.name.clone());
             }
         }
+        let names: Vec<String> = updates.iter().map(|item| item.name.clone()).collect();
+        queries.push(
+            CypherQuery::new()
+                .clause("UNWIND $changed AS name MATCH (n {name: name})")
+                .clause(BUMP_REVISION)
+                .param("changed", changed),
+        );
+        queries.push(
+            CypherQuery::new()
+                .clause("UNWIND $names AS name MATCH (n {name: name})")
+                .clause(OBSERVATIONS_UNLOCK)
+                .param("names", names.clone()),
+        );
 
-        Ok(())
+        // The guard write-locks every node before reading its revision, so no
+        // other writer can move it until the updates commit. Items without an
+        // expected revision pass -1, which always matches.
+        let items: Vec<HashMap<String, neo4rs::BoltType>> = updates
+            .iter()
+            .map(|item| {
+                let expected = item.update.expected_revision.map_or(-1, |r| r as i64);
+                HashMap::from([
+                    ("name".to_string(), item.name.clone().into()),
+                    ("expected".to_string(), expected.into()),
+                ])
+            })
+            .collect();
+        let guard = CypherQuery::new()
+            .clause("UNWIND $items AS item MATCH (n {name: item.name})")
+            .clause("SET n._mm_lock = true WITH n, item")
+            .and_where(format!(
+                "item.expected < 0 OR coalesce(n.{REVISION_PROPERTY}, 0) = item.expected"
+            ))
+            .clause("RETURN n.name AS name")
+            .param("items", items);
+        let required: HashSet<String> = updates
+            .iter()
+            .filter(|item| item.update.expected_revision.is_some())
+            .map(|item| item.name.clone())
+            .collect();
+        let context = format!("update {} entities", updates.len());
+        if self
+            .run_guarded(guard, &required, queries, &context)
+            .await?
+        {
+            return Ok(());
+        }
+        for item in updates {
+            let actual = self
+                .find_entity_by_name(&item.name)
+                .await?
+                .map(|e| e.revision);
+            item.update.check_revision(&item.name, actual)?;
+        }
+        Err(MemoryError::query_error(format!(
+            "Failed to {context}: an expected revision changed while checking it"
+        )))
     }
 
     async fn update_relationship(
@@ -532,34 +1345,76 @@ impl MemoryRepository for Neo4jRepository {
         update: &RelationshipUpdate,
     ) -> MemoryResult<(), Self::Error> {
         if let Some(props) = &update.properties {
-            let match_clause = format!(
-                "MATCH (a {{name: $from}})-[r:`{}`]->(b {{name: $to}})",
-                name
-            );
-            let params = [("from", from.to_string()), ("to", to.to_string())];
-            self.apply_property_update(
-                &match_clause,
-                "r",
-                &params,
-                props,
-                None,
-                "relationship properties",
-            )
-            .await?;
+            let base = CypherQuery::new()
+                .clause("MATCH (a {name: $from})-[r]->(b {name: $to})")
+                .and_where("type(r) = $type")
+                .param("from", from.to_string())
+                .param("to", to.to_string())
+                .param("type", name.to_string());
+            if let Some(query) = Self::property_update_query(base, "r", props, None)? {
+                self.run(query, "update relationship properties").await?;
+            }
         }
         Ok(())
     }
 
+    /// Relationships are attached to the node, so only the name changes
+    #[instrument(skip(self))]
+    async fn rename_entity(&self, old: &str, new: &str) -> MemoryResult<(), Self::Error> {
+        if old.is_empty() || new.is_empty() {
+            return Err(ValidationError::from(ValidationErrorKind::EmptyEntityName).into());
+        }
+        let query = CypherQuery::new()
+            .match_entity("n", "name")
+            .clause("SET n.name = $new_name")
+            .clause(BUMP_REVISION)
+            .param("name", old.to_string())
+            .param("new_name", new.to_string());
+        self.run(query, &format!("rename entity {}", old)).await
+    }
+
+    /// Recreate the merged entity with its relationships in the transaction
+    /// that deletes it and the sources
+    #[instrument(skip(self, merged), fields(name = %merged.name))]
+    async fn merge_entities(
+        &self,
+        merged: &MemoryEntity,
+        sources: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        if merged.name.is_empty() {
+            return Err(ValidationError::from(ValidationErrorKind::EmptyEntityName).into());
+        }
+        let mut names = sources.to_vec();
+        names.push(merged.name.clone());
+        let mut queries = vec![
+            CypherQuery::new()
+                .clause("MATCH (n)")
+                .and_where("n.name IN $names")
+                .clause("DETACH DELETE n")
+                .param("names", names),
+        ];
+        let merged_revision = MemoryEntity {
+            revision: merged.revision + 1,
+            ..merged.clone()
+        };
+        queries.extend(self.create_entities_queries(std::slice::from_ref(&merged_revision))?);
+        if !merged.relationships.is_empty() {
+            queries.extend(self.create_relationships_queries(&merged.relationships)?);
+        }
+        self.run_in_transaction(queries, &format!("merge into {}", merged.name))
+            .await
+    }
+
     async fn delete_entities(&self, names: &[String]) -> MemoryResult<(), Self::Error> {
         if names.is_empty() {
             return Ok(());
         }
-        let query = Query::new("MATCH (n) WHERE n.name IN $names DETACH DELETE n".to_string())
+        let query = CypherQuery::new()
+            .clause("MATCH (n)")
+            .and_where("n.name IN $names")
+            .clause("DETACH DELETE n")
             .param("names", names.to_vec());
-        self.graph.run(query).await.map_err(|e| {
-            MemoryError::query_error_with_source("Failed to delete entities".to_string(), e)
-        })?;
-        Ok(())
+        self.run(query, "delete entities").await
     }
 
     async fn delete_relationships(
@@ -581,17 +1436,14 @@ impl MemoryRepository for Neo4jRepository {
             })
             .collect();
 
-        let query = Query::new(
-            "UNWIND $rows AS row MATCH (a {name: row.from})-[r]->(b {name: row.to}) \
-             WHERE type(r) = row.name DELETE r"
-                .to_string(),
-        )
-        .param("rows", rows);
+        let query = CypherQuery::new()
+            .clause("UNWIND $rows AS row")
+            .clause("MATCH (a {name: row.from})-[r]->(b {name: row.to})")
+            .and_where("type(r) = row.name")
+            .clause("DELETE r")
+            .param("rows", rows);
 
-        self.graph.run(query).await.map_err(|e| {
-            MemoryError::query_error_with_source("Failed to delete relationships".to_string(), e)
-        })?;
-        Ok(())
+        self.run(query, "delete relationships").await
     }
 
     async fn find_relationships(
@@ -600,69 +1452,56 @@ impl MemoryRepository for Neo4jRepository {
         to: Option<String>,
         name: Option<String>,
     ) -> MemoryResult<Vec<MemoryRelationship>, Self::Error> {
-        let mut query_str = String::from("MATCH (a)-[r]->(b)");
-        let mut conditions = Vec::new();
-        if from.is_some() {
-            conditions.push("a.name = $from".to_string());
-        }
-        if to.is_some() {
-            conditions.push("b.name = $to".to_string());
-        }
-        if name.is_some() {
-            conditions.push("type(r) = $type".to_string());
-        }
-        if !conditions.is_empty() {
-            query_str.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
-        }
-        query_str.push_str(
-            " RETURN a.name as from, b.name as to, type(r) as name, properties(r) as props",
-        );
-
-        let mut query = Query::new(query_str);
+        let mut query = CypherQuery::new().clause("MATCH (a)-[r]->(b)");
         if let Some(f) = from {
-            query = query.param("from", f.to_string());
+            query = query.and_where("a.name = $from").param("from", f);
         }
         if let Some(t) = to {
-            query = query.param("to", t.to_string());
+            query = query.and_where("b.name = $to").param("to", t);
         }
         if let Some(n) = name {
-            query = query.param("type", n.to_string());
+            query = query.and_where("type(r) = $type").param("type", n);
         }
+        let query = query
+            .clause("RETURN a.name as from, b.name as to, type(r) as name, properties(r) as props");
+        self.relationship_rows(query, "relationships").await
+    }
 
-        let mut result = self.graph.execute(query).await.map_err(|e| {
-            MemoryError::query_error_with_source("Failed to query relationships".to_string(), e)
-        })?;
-
-        let mut rels = Vec::new();
-        while let Some(row) = result.next().await.map_err(|e| {
-            MemoryError::query_error_with_source("Failed to fetch relationships".to_string(), e)
-        })? {
-            let from = row.get::<String>("from").map_err(|e| {
-                MemoryError::runtime_error_with_source("Failed to get from".to_string(), e)
-            })?;
-            let to = row.get::<String>("to").map_err(|e| {
-                MemoryError::runtime_error_with_source("Failed to get to".to_string(), e)
-            })?;
-            let name = row.get::<String>("name").map_err(|e| {
-                MemoryError::runtime_error_with_source("Failed to get name".to_string(), e)
-            })?;
-            let props_bolt = row.get::<neo4rs::BoltType>("props").map_err(|e| {
-                MemoryError::runtime_error_with_source("Failed to decode props".to_string(), e)
-            })?;
-            let mut properties = HashMap::new();
-            if let neo4rs::BoltType::Map(map) = props_bolt {
-                for (k, v) in &map.value {
-                    let mv = bolt_to_memory_value(v.clone())?;
-                    properties.insert(k.to_string(), mv);
-                }
-            }
-            rels.push(MemoryRelationship {
-                from,
-                to,
-                name,
-                properties,
-            });
+    #[instrument(skip(self, types), fields(from = %from, to = %to, max_depth))]
+    async fn find_path(
+        &self,
+        from: &str,
+        to: &str,
+        max_depth: u32,
+        types: &[String],
+    ) -> MemoryResult<Option<Vec<MemoryRelationship>>, Self::Error> {
+        if from == to {
+            return Ok(Some(Vec::new()));
         }
-        Ok(rels)
+        let types = match types {
+            [] => String::new(),
+            types => {
+                let quoted = types
+                    .iter()
+                    .map(|t| quote_identifier(t))
+                    .collect::<Result<Vec<_>, _>>()?;
+                format!(":{}", quoted.join("|"))
+            }
+        };
+        let query = CypherQuery::new()
+            .clause("MATCH (a {name: $from}), (b {name: $to})")
+            .clause(format!(
+                "MATCH p = shortestPath((a)-[{types}*..{max_depth}]-(b))"
+            ))
+            .clause("UNWIND range(0, size(relationships(p)) - 1) AS i")
+            .clause("WITH relationships(p)[i] AS r, i")
+            .clause(concat!(
+                "RETURN startNode(r).name as from, endNode(r).name as to, ",
+                "type(r) as name, properties(r) as props ORDER BY i"
+            ))
+            .param("from", from.to_string())
+            .param("to", to.to_string());
+        let path = self.relationship_rows(query, "path").await?;
+        Ok((!path.is_empty()).then_some(path))
     }
 }
@@ -0,0 +1,142 @@
+use std::future::Future;
+
+use mm_memory::{MemoryError, MemoryResult, RetryConfig};
+
+/// Neo4j status codes of failures that may succeed when tried again
+const TRANSIENT_CODES: &[&str] = &[
+    "Neo.TransientError.",
+    "Neo.ClientError.Cluster.NotALeader",
+    "Neo.ClientError.General.ForbiddenOnReadOnlyDatabase",
+];
+
+/// Whether `error` is likely to go away on its own, e.g. a dropped
+/// connection or a cluster leader switch
+///
+/// A connection lost while a query runs leaves it unknown whether the query
+/// took effect, so such failures only count for `idempotent` queries.
+/// Failing to connect at all and transient status codes mean the server did
+/// not apply anything.
+pub(super) fn is_transient(error: &MemoryError<neo4rs::Error>, idempotent: bool) -> bool {
+    match error {
+        MemoryError::ConnectionError { .. } => true,
+        MemoryError::QueryError {
+            source: Some(source),
+            ..
+        } => match source {
+            neo4rs::Error::IOError { .. } | neo4rs::Error::ConnectionError => idempotent,
+            other => {
+                let message = other.to_string();
+                TRANSIENT_CODES.iter().any(|code| message.contains(code))
+            }
+        },
+        _ => false,
+    }
+}
+
+/// Run `operation` until it succeeds, fails permanently or has been
+/// attempted `config.max_attempts` times, backing off between attempts.
+///
+/// Operations that are not `idempotent` are only retried when they cannot
+/// have been applied; see [`is_transient`].
+pub(super) async fn with_retry<T, F, Fut>(
+    config: &RetryConfig,
+    context: &str,
+    idempotent: bool,
+    mut operation: F,
+) -> MemoryResult<T, neo4rs::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = MemoryResult<T, neo4rs::Error>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Err(e) if attempt < config.max_attempts && is_transient(&e, idempotent) => {
+                let delay = config.backoff(attempt);
+                tracing::warn!(
+                    attempt,
+                    delay_ms = delay.as_millis() as u64,
+                    "Transient Neo4j error, retrying {}: {}",
+                    context,
+                    e
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn config(max_attempts: u32) -> RetryConfig {
+        RetryConfig {
+            max_attempts,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_transient_errors() {
+        let calls = &AtomicU32::new(0);
+        let result = with_retry(&config(3), "test", true, move || async move {
+            if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(MemoryError::connection_error("dropped"))
+            } else {
+                Ok(42)
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let calls = &AtomicU32::new(0);
+        let result: MemoryResult<(), _> =
+            with_retry(&config(2), "test", true, move || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(MemoryError::connection_error("dropped"))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn permanent_errors_are_not_retried() {
+        let calls = &AtomicU32::new(0);
+        let result: MemoryResult<(), _> =
+            with_retry(&config(3), "test", true, move || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(MemoryError::query_error("syntax error"))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn lost_connections_are_only_retried_when_idempotent() {
+        for (idempotent, expected_calls) in [(true, 3), (false, 1)] {
+            let calls = &AtomicU32::new(0);
+            let result: MemoryResult<(), _> =
+                with_retry(&config(3), "test", idempotent, move || async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Err(MemoryError::query_error_with_source(
+                        "dropped",
+                        neo4rs::Error::ConnectionError,
+                    ))
+                })
+                .await;
+            assert!(result.is_err());
+            assert_eq!(calls.load(Ordering::SeqCst), expected_calls);
+        }
+    }
+}
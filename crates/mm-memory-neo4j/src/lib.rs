@@ -38,7 +38,7 @@ use tracing::instrument;
 pub mod adapters;
 
 // Re-export main types for convenience
-pub use adapters::neo4j::{Neo4jConfig, Neo4jRepository};
+pub use adapters::neo4j::{CypherDialect, Neo4jConfig, Neo4jPoolConfig, Neo4jRepository};
 pub use mm_memory::{
     DEFAULT_MEMORY_LABEL, LabelMatchMode, MemoryConfig, MemoryEntity, MemoryError,
     MemoryRepository, MemoryResult, MemoryService, ValidationError,
@@ -51,6 +51,8 @@ pub type Error = neo4rs::Error;
 /// Create a Neo4j-based memory service
 ///
 /// This is a convenience function that creates a Neo4j repository and wraps it in a memory service.
+/// The name constraint and indexes of [`Neo4jRepository::ensure_schema`] are created on the way;
+/// failing to create them, e.g. because existing entities share a name, is logged but not fatal.
 ///
 /// # Arguments
 ///
@@ -67,7 +69,7 @@ pub type Error = neo4rs::Error;
 /// # Example
 ///
 /// ```no_run
-/// use mm_memory_neo4j::{Neo4jConfig, create_neo4j_service};
+/// use mm_memory_neo4j::{MemoryConfig, Neo4jConfig, create_neo4j_service};
 ///
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -75,6 +77,9 @@ pub type Error = neo4rs::Error;
 ///         uri: "neo4j://localhost:7688".to_string(),
 ///         username: "neo4j".to_string(),
 ///         password: "password".to_string(),
+///         dialect: Default::default(),
+///         pool: Default::default(),
+///         database: None,
 ///     };
 ///
 ///     let service = create_neo4j_service(config, MemoryConfig::default()).await?;
@@ -89,6 +94,30 @@ pub async fn create_neo4j_service(
     config: Neo4jConfig,
     memory_config: MemoryConfig,
 ) -> Result<MemoryService<Neo4jRepository>, MemoryError<neo4rs::Error>> {
-    let repository = Neo4jRepository::new(config).await?;
+    let repository = create_neo4j_repository(config, &memory_config).await?;
     Ok(MemoryService::new(repository, memory_config))
 }
+
+/// Connect a [`Neo4jRepository`] set up for `memory_config`
+///
+/// Applies the retry settings and creates the schema like
+/// [`create_neo4j_service`], for callers that wrap the repository themselves.
+#[instrument(skip(memory_config), fields(uri = %config.uri))]
+pub async fn create_neo4j_repository(
+    config: Neo4jConfig,
+    memory_config: &MemoryConfig,
+) -> Result<Neo4jRepository, MemoryError<neo4rs::Error>> {
+    let repository = Neo4jRepository::new(config)
+        .await?
+        .with_retry_config(memory_config.retry.clone());
+    if let Err(e) = repository
+        .ensure_schema(memory_config.default_label.as_deref())
+        .await
+    {
+        tracing::warn!(
+            "Failed to create Neo4j schema, name lookups may be slow: {}",
+            e
+        );
+    }
+    Ok(repository)
+}
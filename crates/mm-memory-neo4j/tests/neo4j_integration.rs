@@ -1,18 +1,21 @@
 use mm_memory::relationship::RelationshipRef;
 use mm_memory::test_suite::run_memory_service_test_suite;
-use mm_memory::{MemoryRelationship, MemoryValue, RelationshipDirection};
+use mm_memory::{MemoryRelationship, MemoryRepository, MemoryValue, RelationshipDirection};
 use mm_memory_neo4j::LabelMatchMode;
 use mm_memory_neo4j::{
     MemoryConfig, MemoryEntity, MemoryError, MemoryService, Neo4jConfig, Neo4jRepository,
     create_neo4j_service,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 async fn new_test_service(label: &str) -> MemoryService<Neo4jRepository> {
     let config = Neo4jConfig {
         uri: "neo4j://localhost:7688".to_string(),
         username: "neo4j".to_string(),
         password: "password".to_string(),
+        dialect: Default::default(),
+        pool: Default::default(),
+        database: None,
     };
 
     create_neo4j_service(
@@ -25,6 +28,31 @@ async fn new_test_service(label: &str) -> MemoryService<Neo4jRepository> {
             allowed_labels: std::iter::once("Example".to_string()).collect(),
             default_project: None,
             agent_name: "test".to_string(),
+            indexed_properties: HashMap::default(),
+            self_loop_relationships: std::collections::HashSet::default(),
+            merge_relationships: false,
+            wip_limits: Default::default(),
+            projects: HashMap::default(),
+            expiry: HashMap::default(),
+            retry: Default::default(),
+            batch_size: 500,
+            read_only: false,
+            soft_delete: false,
+            history: false,
+            audit_log: None,
+            redaction: Vec::new(),
+            reject_duplicate_names: false,
+            acyclic_relationships: std::collections::HashSet::default(),
+            relationship_rules: std::collections::HashMap::new(),
+            name_template: "{{label}}:{{slug}}".to_string(),
+            normalize_datetimes: false,
+            local_utc_offset_minutes: 0,
+            track_observations: false,
+            templates: HashMap::new(),
+            label_parents: HashMap::new(),
+            reserved_labels: HashSet::default(),
+            reserved_relationships: HashSet::default(),
+            naming: Default::default(),
         },
     )
     .await
@@ -38,6 +66,9 @@ async fn test_connection_error() {
         uri: "invalid://localhost:7687".to_string(),
         username: "neo4j".to_string(),
         password: "wrong".to_string(),
+        dialect: Default::default(),
+        pool: Default::default(),
+        database: None,
     };
 
     let result = create_neo4j_service(config, MemoryConfig::default()).await;
@@ -182,6 +213,24 @@ async fn test_add_and_remove_observations() {
         .unwrap();
     assert_eq!(after_add.observations, vec!["obs1", "obs2", "obs3"]);
 
+    service
+        .add_observations(
+            entity_name,
+            &["obs1".to_string(), "obs4".to_string(), "obs4".to_string()],
+        )
+        .await
+        .unwrap();
+
+    let after_dedup = service
+        .find_entity_by_name(entity_name)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        after_dedup.observations,
+        vec!["obs1", "obs2", "obs3", "obs4"]
+    );
+
     service
         .remove_observations(entity_name, &["obs2".to_string()])
         .await
@@ -192,7 +241,7 @@ async fn test_add_and_remove_observations() {
         .await
         .unwrap()
         .unwrap();
-    assert_eq!(after_remove.observations, vec!["obs1", "obs3"]);
+    assert_eq!(after_remove.observations, vec!["obs1", "obs3", "obs4"]);
 
     service.remove_all_observations(entity_name).await.unwrap();
 
@@ -204,6 +253,48 @@ async fn test_add_and_remove_observations() {
     assert!(cleared.observations.is_empty());
 }
 
+#[tokio::test]
+async fn test_concurrent_add_observations() {
+    let service = std::sync::Arc::new(new_test_service("TestConcurrentObs").await);
+
+    let entity_name = "test:entity:concurrent";
+    let entity = MemoryEntity {
+        name: entity_name.to_string(),
+        labels: vec!["Example".to_string()],
+        ..Default::default()
+    };
+    service
+        .delete_entities(&[entity_name.to_string()])
+        .await
+        .unwrap();
+    service
+        .create_entities(std::slice::from_ref(&entity))
+        .await
+        .unwrap();
+
+    let handles = (0..10)
+        .map(|i| {
+            let service = service.clone();
+            tokio::spawn(async move {
+                service
+                    .add_observations(entity_name, &[format!("obs{i}")])
+                    .await
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.await.unwrap().unwrap();
+    }
+
+    let found = service
+        .find_entity_by_name(entity_name)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.observations.len(), 10);
+    assert!(!found.properties.contains_key("_mm_lock"));
+}
+
 #[tokio::test]
 async fn test_create_relationship() {
     let service = new_test_service("RelationshipTest").await;
@@ -294,7 +385,7 @@ async fn test_delete_relationships() {
         name: "relates_to".to_string(),
     };
     service
-        .delete_relationships(std::slice::from_ref(&rel_ref))
+        .delete_relationships(std::slice::from_ref(&rel_ref), false)
         .await
         .unwrap();
 
@@ -368,6 +459,9 @@ async fn test_find_entities_by_labels() {
         uri: "neo4j://localhost:7688".to_string(),
         username: "neo4j".to_string(),
         password: "password".to_string(),
+        dialect: Default::default(),
+        pool: Default::default(),
+        database: None,
     };
 
     let service = create_neo4j_service(
@@ -382,6 +476,31 @@ async fn test_find_entities_by_labels() {
                 .collect(),
             default_project: None,
             agent_name: "test".to_string(),
+            indexed_properties: HashMap::default(),
+            self_loop_relationships: std::collections::HashSet::default(),
+            merge_relationships: false,
+            wip_limits: Default::default(),
+            projects: HashMap::default(),
+            expiry: HashMap::default(),
+            retry: Default::default(),
+            batch_size: 500,
+            read_only: false,
+            soft_delete: false,
+            history: false,
+            audit_log: None,
+            redaction: Vec::new(),
+            reject_duplicate_names: false,
+            acyclic_relationships: std::collections::HashSet::default(),
+            relationship_rules: std::collections::HashMap::new(),
+            name_template: "{{label}}:{{slug}}".to_string(),
+            normalize_datetimes: false,
+            local_utc_offset_minutes: 0,
+            track_observations: false,
+            templates: HashMap::new(),
+            label_parents: HashMap::new(),
+            reserved_labels: HashSet::default(),
+            reserved_relationships: HashSet::default(),
+            naming: Default::default(),
         },
     )
     .await
@@ -433,8 +552,133 @@ async fn test_run_memory_service_suite() {
         uri: "neo4j://localhost:7688".to_string(),
         username: "neo4j".to_string(),
         password: "password".to_string(),
+        dialect: Default::default(),
+        pool: Default::default(),
+        database: None,
     };
 
     let repo = Neo4jRepository::new(config).await.unwrap();
     run_memory_service_test_suite(repo).await.unwrap();
 }
+
+#[tokio::test]
+async fn test_init_schema_is_idempotent() {
+    let config = Neo4jConfig {
+        uri: "neo4j://localhost:7688".to_string(),
+        username: "neo4j".to_string(),
+        password: "password".to_string(),
+        dialect: Default::default(),
+        pool: Default::default(),
+        database: None,
+    };
+    let service = create_neo4j_service(
+        config,
+        MemoryConfig {
+            indexed_properties: HashMap::from([(
+                "Task".to_string(),
+                vec!["status".to_string(), "due_date".to_string()],
+            )]),
+            ..MemoryConfig::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let first = service.init_schema().await.unwrap();
+    let second = service.init_schema().await.unwrap();
+    assert_eq!(first.len(), 2);
+    assert_eq!(first, second);
+}
+
+#[tokio::test]
+async fn test_ensure_schema_enforces_unique_names() {
+    let config = Neo4jConfig {
+        uri: "neo4j://localhost:7688".to_string(),
+        username: "neo4j".to_string(),
+        password: "password".to_string(),
+        dialect: Default::default(),
+        pool: Default::default(),
+        database: None,
+    };
+    let repo = Neo4jRepository::new(config).await.unwrap();
+    let label = "TestUniqueName";
+    repo.ensure_schema(Some(label)).await.unwrap();
+    repo.ensure_schema(Some(label)).await.unwrap();
+
+    let entity = MemoryEntity {
+        name: "test:unique_name".to_string(),
+        labels: vec![label.to_string()],
+        ..Default::default()
+    };
+    let _ = repo
+        .delete_entities(std::slice::from_ref(&entity.name))
+        .await;
+    repo.create_entities(std::slice::from_ref(&entity))
+        .await
+        .unwrap();
+    assert!(
+        repo.create_entities(std::slice::from_ref(&entity))
+            .await
+            .is_err()
+    );
+    repo.delete_entities(&[entity.name]).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_find_tasks_pushes_down_filters() {
+    use mm_memory::TaskFilter;
+
+    let service = new_test_service("TestFindTasks").await;
+    let project = "test:project:find_tasks";
+    let names = [
+        project.to_string(),
+        "test:task:find_tasks_todo".to_string(),
+        "test:task:find_tasks_done".to_string(),
+    ];
+    service.delete_entities(&names).await.unwrap();
+
+    let task = |name: &str, status: &str| MemoryEntity {
+        name: name.to_string(),
+        labels: vec!["Task".to_string()],
+        properties: HashMap::from([(
+            "status".to_string(),
+            MemoryValue::String(status.to_string()),
+        )]),
+        ..Default::default()
+    };
+    let entities = vec![
+        MemoryEntity {
+            name: project.to_string(),
+            labels: vec!["Project".to_string()],
+            ..Default::default()
+        },
+        task(&names[1], "todo"),
+        task(&names[2], "done"),
+    ];
+    service.create_entities(&entities).await.unwrap();
+    let rels = names[1..]
+        .iter()
+        .map(|t| MemoryRelationship {
+            from: project.to_string(),
+            to: t.clone(),
+            name: "contains".to_string(),
+            properties: HashMap::default(),
+        })
+        .collect::<Vec<_>>();
+    service.create_relationships(&rels).await.unwrap();
+
+    let all = service
+        .find_tasks_typed::<HashMap<String, MemoryValue>>(&TaskFilter::for_project(project))
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 2);
+
+    let mut filter = TaskFilter::for_project(project);
+    filter.statuses = vec!["done".to_string()];
+    let done = service
+        .find_tasks_typed::<HashMap<String, MemoryValue>>(&filter)
+        .await
+        .unwrap();
+    assert_eq!(done.len(), 1);
+    assert_eq!(done[0].name, names[2]);
+}
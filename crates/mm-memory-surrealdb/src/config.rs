@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for connecting to SurrealDB
+#[derive(Clone, Deserialize, Serialize)]
+pub struct SurrealConfig {
+    /// Endpoint of the server, e.g. `ws://localhost:8000`, or `mem://` for
+    /// an in-process database when the engine is compiled in
+    pub url: String,
+
+    /// Namespace holding the database, `middle_manager` unless set
+    #[serde(default = "default_namespace")]
+    pub namespace: String,
+
+    /// Database holding the memory graph, `memory` unless set
+    #[serde(default = "default_database")]
+    pub database: String,
+
+    /// Root user to sign in as; no sign in happens when unset
+    #[serde(default)]
+    pub username: Option<String>,
+
+    /// Password of `username`
+    #[serde(default, skip_serializing)]
+    pub password: Option<String>,
+}
+
+fn default_namespace() -> String {
+    "middle_manager".to_string()
+}
+
+fn default_database() -> String {
+    "memory".to_string()
+}
+
+impl SurrealConfig {
+    /// Configuration for `url` with the default namespace and database
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            namespace: default_namespace(),
+            database: default_database(),
+            username: None,
+            password: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for SurrealConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SurrealConfig")
+            .field("url", &self.url)
+            .field("namespace", &self.namespace)
+            .field("database", &self.database)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "***"))
+            .finish()
+    }
+}
@@ -0,0 +1,29 @@
+//! SurrealDB memory repository for the Middle Manager project.
+//!
+//! This crate is an adapter like `mm-memory-neo4j` for users who already run
+//! SurrealDB. Entities are records of the `entity` table keyed by name and
+//! relationships are `relationship` edges between them. Property values are
+//! stored with their type so every [`MemoryValue`](mm_memory::MemoryValue)
+//! variant reads back unchanged.
+#![warn(clippy::all)]
+// The repository error type is surrealdb::Error, which is large but fixed
+// by the MemoryRepository contract.
+#![allow(clippy::result_large_err)]
+mod config;
+mod repository;
+mod value;
+
+pub use config::SurrealConfig;
+pub use repository::SurrealRepository;
+pub use surrealdb;
+
+use mm_memory::{MemoryConfig, MemoryResult, MemoryService};
+
+/// Create a memory service backed by the SurrealDB database in `config`
+pub async fn create_surreal_service(
+    config: SurrealConfig,
+    memory_config: MemoryConfig,
+) -> MemoryResult<MemoryService<SurrealRepository>, surrealdb::Error> {
+    let repository = SurrealRepository::connect(config).await?;
+    Ok(MemoryService::new(repository, memory_config))
+}
@@ -0,0 +1,857 @@
+use async_trait::async_trait;
+use mm_memory::relationship::RelationshipRef;
+use mm_memory::source::SOURCES_PROPERTY;
+use mm_memory::{
+    BackendInfo, EntityUpdate, LabelMatchMode, MemoryEntity, MemoryError, MemoryRelationship,
+    MemoryRepository, MemoryResult, PropertiesUpdate, RelationshipDirection, RelationshipUpdate,
+    ValidationError, ValidationErrorKind,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use surrealdb::engine::any::{self, Any};
+use surrealdb::opt::auth::Root;
+use surrealdb::{RecordId, Surreal};
+use tracing::instrument;
+
+use crate::config::SurrealConfig;
+use crate::value::{StoredValue, from_stored, to_stored};
+
+const ENTITY_FIELDS: &str = "name, labels, observations, properties, revision";
+const RELATIONSHIP_FIELDS: &str =
+    "id, record::id(in) AS source, record::id(out) AS target, name, properties";
+
+/// Upsert `$entities`, replacing stored entities with the same name
+const CREATE_ENTITIES: &str = "
+FOR $entity IN $entities {
+    UPSERT type::thing('entity', $entity.name) CONTENT $entity;
+};";
+
+/// Relate the entities of `$relationships` whose endpoints both exist
+const CREATE_RELATIONSHIPS: &str = "
+FOR $rel IN $relationships {
+    LET $source = type::thing('entity', $rel.source);
+    LET $target = type::thing('entity', $rel.target);
+    IF record::exists($source) AND record::exists($target) {
+        RELATE $source->relationship->$target SET name = $rel.name, properties = $rel.properties;
+    };
+};";
+
+/// An entity as stored in the `entity` table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EntityRecord {
+    name: String,
+    labels: Vec<String>,
+    observations: Vec<String>,
+    #[serde(default)]
+    properties: HashMap<String, StoredValue>,
+    #[serde(default)]
+    revision: u64,
+}
+
+impl EntityRecord {
+    fn from_entity(entity: &MemoryEntity) -> Self {
+        Self {
+            name: entity.name.clone(),
+            labels: entity.labels.clone(),
+            observations: entity.observations.clone(),
+            properties: to_stored(&entity.properties),
+            revision: entity.revision.max(1),
+        }
+    }
+
+    fn into_entity(self) -> MemoryResult<MemoryEntity, surrealdb::Error> {
+        let properties = from_stored(self.properties).map_err(|e| {
+            MemoryError::query_error(format!("Invalid property of '{}': {e}", self.name))
+        })?;
+        Ok(MemoryEntity {
+            name: self.name,
+            labels: self.labels,
+            observations: self.observations,
+            properties,
+            relationships: vec![],
+            revision: self.revision,
+        })
+    }
+}
+
+/// A relationship as stored in the `relationship` edge table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RelationshipRecord {
+    #[serde(default, skip_serializing)]
+    id: Option<RecordId>,
+    source: String,
+    target: String,
+    name: String,
+    #[serde(default)]
+    properties: HashMap<String, StoredValue>,
+}
+
+impl RelationshipRecord {
+    fn from_relationship(rel: &MemoryRelationship) -> Self {
+        Self {
+            id: None,
+            source: rel.from.clone(),
+            target: rel.to.clone(),
+            name: rel.name.clone(),
+            properties: to_stored(&rel.properties),
+        }
+    }
+
+    fn into_relationship(self) -> MemoryResult<MemoryRelationship, surrealdb::Error> {
+        let properties = from_stored(self.properties).map_err(|e| {
+            MemoryError::query_error(format!(
+                "Invalid property of relationship '{}': {e}",
+                self.name
+            ))
+        })?;
+        Ok(MemoryRelationship {
+            from: self.source,
+            to: self.target,
+            name: self.name,
+            properties,
+        })
+    }
+}
+
+/// Relationship key without properties, used for deletion
+#[derive(Debug, Serialize)]
+struct RelationshipKey {
+    source: String,
+    target: String,
+    name: String,
+}
+
+fn query_error(context: &str) -> impl Fn(surrealdb::Error) -> MemoryError<surrealdb::Error> + '_ {
+    move |e| MemoryError::query_error_with_source(format!("Failed to {context}"), e)
+}
+
+/// [`MemoryRepository`] storing the graph in SurrealDB
+///
+/// Behaves like the other adapters: writes against missing entities are
+/// silently ignored, relationships are only created between existing
+/// entities and deleting an entity drops the relationships touching it.
+/// Creating an entity whose name already exists replaces the stored one.
+pub struct SurrealRepository {
+    db: Surreal<Any>,
+    config: SurrealConfig,
+}
+
+impl SurrealRepository {
+    /// Connect to the server in `config`, sign in and select its database
+    #[instrument(skip(config), fields(url = %config.url))]
+    pub async fn connect(config: SurrealConfig) -> MemoryResult<Self, surrealdb::Error> {
+        let error = |e: surrealdb::Error| {
+            MemoryError::connection_error_with_source(
+                format!("Failed to connect to SurrealDB at {}", config.url),
+                e,
+            )
+        };
+
+        let db = any::connect(config.url.as_str()).await.map_err(error)?;
+        if let Some(username) = &config.username {
+            db.signin(Root {
+                username,
+                password: config.password.as_deref().unwrap_or_default(),
+            })
+            .await
+            .map_err(error)?;
+        }
+        db.use_ns(config.namespace.as_str())
+            .use_db(config.database.as_str())
+            .await
+            .map_err(error)?;
+        db.query("DEFINE INDEX IF NOT EXISTS relationship_name ON relationship FIELDS name;")
+            .await
+            .and_then(|r| r.check())
+            .map_err(query_error("define relationship index"))?;
+
+        Ok(Self { db, config })
+    }
+
+    fn require_name(name: &str) -> MemoryResult<(), surrealdb::Error> {
+        if name.is_empty() {
+            return Err(ValidationError::from(ValidationErrorKind::EmptyEntityName).into());
+        }
+        Ok(())
+    }
+
+    async fn entity_record(
+        &self,
+        name: &str,
+    ) -> MemoryResult<Option<EntityRecord>, surrealdb::Error> {
+        let mut response = self
+            .db
+            .query(format!(
+                "SELECT {ENTITY_FIELDS} FROM type::thing('entity', $name)"
+            ))
+            .bind(("name", name.to_string()))
+            .await
+            .map_err(query_error("find entity"))?;
+        let records: Vec<EntityRecord> = response.take(0).map_err(query_error("find entity"))?;
+        Ok(records.into_iter().next())
+    }
+
+    async fn entity_records(
+        &self,
+        condition: &str,
+        bindings: impl Serialize + 'static,
+    ) -> MemoryResult<Vec<EntityRecord>, surrealdb::Error> {
+        let mut response = self
+            .db
+            .query(format!(
+                "SELECT {ENTITY_FIELDS} FROM entity WHERE {condition} ORDER BY name"
+            ))
+            .bind(bindings)
+            .await
+            .map_err(query_error("find entities"))?;
+        response.take(0).map_err(query_error("find entities"))
+    }
+
+    /// Relationships with an endpoint in `names`, of type `name` if set
+    async fn relationships_touching(
+        &self,
+        names: &[String],
+        name: Option<String>,
+    ) -> MemoryResult<Vec<RelationshipRecord>, surrealdb::Error> {
+        let mut sql = format!(
+            "SELECT {RELATIONSHIP_FIELDS} FROM relationship \
+             WHERE (record::id(in) IN $names OR record::id(out) IN $names)"
+        );
+        if name.is_some() {
+            sql.push_str(" AND name = $name");
+        }
+        let mut response = self
+            .db
+            .query(sql)
+            .bind(("names", names.to_vec()))
+            .bind(("name", name))
+            .await
+            .map_err(query_error("find relationships"))?;
+        response.take(0).map_err(query_error("find relationships"))
+    }
+
+    /// Convert `records` to entities with the relationships touching them
+    async fn hydrate(
+        &self,
+        records: Vec<EntityRecord>,
+    ) -> MemoryResult<Vec<MemoryEntity>, surrealdb::Error> {
+        if records.is_empty() {
+            return Ok(vec![]);
+        }
+        let names: Vec<String> = records.iter().map(|r| r.name.clone()).collect();
+        let relationships = self
+            .relationships_touching(&names, None)
+            .await?
+            .into_iter()
+            .map(RelationshipRecord::into_relationship)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        records
+            .into_iter()
+            .map(|record| {
+                let mut entity = record.into_entity()?;
+                entity.relationships = relationships
+                    .iter()
+                    .filter(|r| r.from == entity.name || r.to == entity.name)
+                    .cloned()
+                    .collect();
+                Ok(entity)
+            })
+            .collect()
+    }
+
+    /// Read the entity called `name`, apply `edit` and write it back
+    ///
+    /// Missing entities are ignored.
+    async fn modify_entity(
+        &self,
+        name: &str,
+        edit: impl FnOnce(&mut EntityRecord),
+    ) -> MemoryResult<(), surrealdb::Error> {
+        self.modify_entity_checked(name, &EntityUpdate::default(), edit)
+            .await
+    }
+
+    /// Like [`modify_entity`](Self::modify_entity), but first checks the
+    /// revision `update` expects
+    ///
+    /// With an expected revision the write only matches the record at that
+    /// revision, so a concurrent change turns into a conflict instead of
+    /// being overwritten.
+    async fn modify_entity_checked(
+        &self,
+        name: &str,
+        update: &EntityUpdate,
+        edit: impl FnOnce(&mut EntityRecord),
+    ) -> MemoryResult<(), surrealdb::Error> {
+        Self::require_name(name)?;
+        let Some(mut record) = self.entity_record(name).await? else {
+            return update.check_revision(name, None);
+        };
+        update.check_revision(name, Some(record.revision))?;
+        let revision = record.revision;
+        edit(&mut record);
+        record.revision += 1;
+        let guard = match update.expected_revision {
+            Some(_) => " WHERE revision = $revision",
+            None => "",
+        };
+        let mut response = self
+            .db
+            .query(format!(
+                "UPDATE type::thing('entity', $name) CONTENT $entity{guard}"
+            ))
+            .bind(("name", name.to_string()))
+            .bind(("entity", record))
+            .bind(("revision", revision))
+            .await
+            .and_then(|r| r.check())
+            .map_err(query_error("update entity"))?;
+        let updated: Vec<EntityRecord> = response.take(0).map_err(query_error("update entity"))?;
+        if updated.is_empty() {
+            let actual = self.entity_record(name).await?.map(|r| r.revision);
+            return update.check_revision(name, actual);
+        }
+        Ok(())
+    }
+
+    fn apply_properties(properties: &mut HashMap<String, StoredValue>, update: &PropertiesUpdate) {
+        if let Some(add) = &update.add {
+            properties.extend(to_stored(add));
+        } else if let Some(remove) = &update.remove {
+            for key in remove {
+                properties.remove(key);
+            }
+        } else if let Some(set) = &update.set {
+            *properties = to_stored(set);
+        }
+    }
+}
+
+#[async_trait]
+impl MemoryRepository for SurrealRepository {
+    type Error = surrealdb::Error;
+
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            backend: "surrealdb".to_string(),
+            database: Some(format!(
+                "{} ({}/{})",
+                self.config.url, self.config.namespace, self.config.database
+            )),
+        }
+    }
+
+    #[instrument(skip(self, entities), fields(count = entities.len()))]
+    async fn create_entities(&self, entities: &[MemoryEntity]) -> MemoryResult<(), Self::Error> {
+        if entities.is_empty() {
+            return Ok(());
+        }
+        let records: Vec<EntityRecord> = entities.iter().map(EntityRecord::from_entity).collect();
+        self.db
+            .query(CREATE_ENTITIES)
+            .bind(("entities", records))
+            .await
+            .and_then(|r| r.check())
+            .map_err(query_error("create entities"))?;
+        Ok(())
+    }
+
+    async fn find_entity_by_name(
+        &self,
+        name: &str,
+    ) -> MemoryResult<Option<MemoryEntity>, Self::Error> {
+        Self::require_name(name)?;
+        let Some(record) = self.entity_record(name).await? else {
+            return Ok(None);
+        };
+        Ok(self.hydrate(vec![record]).await?.pop())
+    }
+
+    async fn find_existing_names(
+        &self,
+        names: &[String],
+    ) -> MemoryResult<Vec<String>, Self::Error> {
+        let mut response = self
+            .db
+            .query("SELECT VALUE name FROM entity WHERE name IN $names")
+            .bind(("names", names.to_vec()))
+            .await
+            .map_err(query_error("find existing names"))?;
+        response.take(0).map_err(query_error("find existing names"))
+    }
+
+    async fn set_observations(
+        &self,
+        name: &str,
+        observations: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        self.modify_entity(name, |entity| entity.observations = observations.to_vec())
+            .await
+    }
+
+    async fn add_observations(
+        &self,
+        name: &str,
+        observations: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        self.modify_entity(name, |entity| {
+            for observation in observations {
+                if !entity.observations.contains(observation) {
+                    entity.observations.push(observation.clone());
+                }
+            }
+        })
+        .await
+    }
+
+    async fn remove_all_observations(&self, name: &str) -> MemoryResult<(), Self::Error> {
+        self.set_observations(name, &[]).await
+    }
+
+    async fn remove_observations(
+        &self,
+        name: &str,
+        observations: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        self.modify_entity(name, |entity| {
+            entity.observations.retain(|o| !observations.contains(o))
+        })
+        .await
+    }
+
+    #[instrument(skip(self, relationships), fields(count = relationships.len()))]
+    async fn create_relationships(
+        &self,
+        relationships: &[MemoryRelationship],
+    ) -> MemoryResult<(), Self::Error> {
+        if relationships.is_empty() {
+            return Ok(());
+        }
+        let records: Vec<RelationshipRecord> = relationships
+            .iter()
+            .map(RelationshipRecord::from_relationship)
+            .collect();
+        self.db
+            .query(CREATE_RELATIONSHIPS)
+            .bind(("relationships", records))
+            .await
+            .and_then(|r| r.check())
+            .map_err(query_error("create relationships"))?;
+        Ok(())
+    }
+
+    /// Create both batches in one transaction
+    async fn create_graph(
+        &self,
+        entities: &[MemoryEntity],
+        relationships: &[MemoryRelationship],
+    ) -> MemoryResult<(), Self::Error> {
+        let entities: Vec<EntityRecord> = entities.iter().map(EntityRecord::from_entity).collect();
+        let relationships: Vec<RelationshipRecord> = relationships
+            .iter()
+            .map(RelationshipRecord::from_relationship)
+            .collect();
+        self.db
+            .query(format!(
+                "BEGIN TRANSACTION;{CREATE_ENTITIES}{CREATE_RELATIONSHIPS}\nCOMMIT TRANSACTION;"
+            ))
+            .bind(("entities", entities))
+            .bind(("relationships", relationships))
+            .await
+            .and_then(|r| r.check())
+            .map_err(query_error("create graph"))?;
+        Ok(())
+    }
+
+    async fn delete_entities(&self, names: &[String]) -> MemoryResult<(), Self::Error> {
+        if names.is_empty() {
+            return Ok(());
+        }
+        self.db
+            .query(
+                "DELETE relationship WHERE record::id(in) IN $names OR record::id(out) IN $names;
+                 DELETE entity WHERE name IN $names;",
+            )
+            .bind(("names", names.to_vec()))
+            .await
+            .and_then(|r| r.check())
+            .map_err(query_error("delete entities"))?;
+        Ok(())
+    }
+
+    async fn delete_relationships(
+        &self,
+        relationships: &[RelationshipRef],
+    ) -> MemoryResult<(), Self::Error> {
+        if relationships.is_empty() {
+            return Ok(());
+        }
+        let keys: Vec<RelationshipKey> = relationships
+            .iter()
+            .map(|r| RelationshipKey {
+                source: r.from.clone(),
+                target: r.to.clone(),
+                name: r.name.clone(),
+            })
+            .collect();
+        self.db
+            .query(
+                "FOR $rel IN $relationships {
+                    DELETE relationship WHERE record::id(in) = $rel.source
+                        AND record::id(out) = $rel.target AND name = $rel.name;
+                };",
+            )
+            .bind(("relationships", keys))
+            .await
+            .and_then(|r| r.check())
+            .map_err(query_error("delete relationships"))?;
+        Ok(())
+    }
+
+    async fn find_relationships(
+        &self,
+        from: Option<String>,
+        to: Option<String>,
+        name: Option<String>,
+    ) -> MemoryResult<Vec<MemoryRelationship>, Self::Error> {
+        let mut conditions = vec!["true"];
+        if from.is_some() {
+            conditions.push("record::id(in) = $source");
+        }
+        if to.is_some() {
+            conditions.push("record::id(out) = $target");
+        }
+        if name.is_some() {
+            conditions.push("name = $name");
+        }
+        let mut response = self
+            .db
+            .query(format!(
+                "SELECT {RELATIONSHIP_FIELDS} FROM relationship WHERE {}",
+                conditions.join(" AND ")
+            ))
+            .bind(("source", from))
+            .bind(("target", to))
+            .bind(("name", name))
+            .await
+            .map_err(query_error("find relationships"))?;
+        let records: Vec<RelationshipRecord> = response
+            .take(0)
+            .map_err(query_error("find relationships"))?;
+        records
+            .into_iter()
+            .map(RelationshipRecord::into_relationship)
+            .collect()
+    }
+
+    async fn find_entities_by_labels(
+        &self,
+        labels: &[String],
+        match_mode: LabelMatchMode,
+        required_label: Option<String>,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        let mut conditions = vec!["true"];
+        if !labels.is_empty() {
+            conditions.push(match match_mode {
+                LabelMatchMode::Any => "labels CONTAINSANY $labels",
+                LabelMatchMode::All => "labels CONTAINSALL $labels",
+            });
+        }
+        if required_label.is_some() {
+            conditions.push("labels CONTAINS $required");
+        }
+
+        #[derive(Serialize)]
+        struct Bindings {
+            labels: Vec<String>,
+            required: Option<String>,
+        }
+        let records = self
+            .entity_records(
+                &conditions.join(" AND "),
+                Bindings {
+                    labels: labels.to_vec(),
+                    required: required_label,
+                },
+            )
+            .await?;
+        self.hydrate(records).await
+    }
+
+    async fn find_entities_by_source(
+        &self,
+        source_key: &str,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        let records = self
+            .entity_records(
+                &format!(
+                    "properties.{SOURCES_PROPERTY}.type = 'list' \
+                     AND properties.{SOURCES_PROPERTY}.value CONTAINS $key"
+                ),
+                ("key", source_key.to_string()),
+            )
+            .await?;
+        self.hydrate(records).await
+    }
+
+    async fn list_labels(&self) -> MemoryResult<Vec<(String, u64)>, Self::Error> {
+        let mut response = self
+            .db
+            .query("SELECT VALUE labels FROM entity")
+            .await
+            .map_err(query_error("list labels"))?;
+        let labels: Vec<Vec<String>> = response.take(0).map_err(query_error("list labels"))?;
+        let mut counts = BTreeMap::new();
+        for label in labels.into_iter().flatten() {
+            *counts.entry(label).or_insert(0) += 1;
+        }
+        Ok(counts.into_iter().collect())
+    }
+
+    async fn list_relationship_types(&self) -> MemoryResult<Vec<(String, u64)>, Self::Error> {
+        #[derive(Deserialize)]
+        struct TypeCount {
+            name: String,
+            count: u64,
+        }
+        let mut response = self
+            .db
+            .query("SELECT name, count() AS count FROM relationship GROUP BY name ORDER BY name")
+            .await
+            .map_err(query_error("list relationship types"))?;
+        let counts: Vec<TypeCount> = response
+            .take(0)
+            .map_err(query_error("list relationship types"))?;
+        Ok(counts.into_iter().map(|c| (c.name, c.count)).collect())
+    }
+
+    async fn find_related_entities(
+        &self,
+        name: &str,
+        relationship_type: Option<String>,
+        direction: Option<RelationshipDirection>,
+        depth: u32,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        Self::require_name(name)?;
+        let direction = direction.unwrap_or(RelationshipDirection::Both);
+        if self.entity_record(name).await?.is_none() {
+            return Ok(vec![]);
+        }
+
+        // Breadth-first walk, one query per level, so entities come back
+        // ordered by distance.
+        let mut seen: HashSet<String> = HashSet::from([name.to_string()]);
+        let mut found = Vec::new();
+        let mut frontier = vec![name.to_string()];
+        for _ in 0..depth {
+            if frontier.is_empty() {
+                break;
+            }
+            let edges = self
+                .relationships_touching(&frontier, relationship_type.clone())
+                .await?;
+            let mut next = Vec::new();
+            for current in &frontier {
+                let neighbours = edges.iter().filter_map(|r| match direction {
+                    RelationshipDirection::Outgoing => (&r.source == current).then_some(&r.target),
+                    RelationshipDirection::Incoming => (&r.target == current).then_some(&r.source),
+                    RelationshipDirection::Both if &r.source == current => Some(&r.target),
+                    RelationshipDirection::Both => (&r.target == current).then_some(&r.source),
+                });
+                for neighbour in neighbours {
+                    if seen.insert(neighbour.clone()) {
+                        found.push(neighbour.clone());
+                        next.push(neighbour.clone());
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        let mut records: HashMap<String, EntityRecord> = self
+            .entity_records("name IN $names", ("names", found.clone()))
+            .await?
+            .into_iter()
+            .map(|r| (r.name.clone(), r))
+            .collect();
+        let ordered = found.iter().filter_map(|n| records.remove(n)).collect();
+        self.hydrate(ordered).await
+    }
+
+    async fn create_property_index(
+        &self,
+        _label: &str,
+        property: &str,
+    ) -> MemoryResult<(), Self::Error> {
+        // Labels are values rather than tables here, so one index on the
+        // `entity` table serves every label.
+        if property.is_empty()
+            || !property
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(
+                ValidationError::from(ValidationErrorKind::InvalidIdentifier(property.to_string()))
+                    .into(),
+            );
+        }
+        self.db
+            .query(format!(
+                "DEFINE INDEX IF NOT EXISTS entity_{property} ON entity \
+                 FIELDS properties.{property}.value"
+            ))
+            .await
+            .and_then(|r| r.check())
+            .map_err(query_error("create property index"))?;
+        Ok(())
+    }
+
+    async fn update_entity(
+        &self,
+        name: &str,
+        update: &EntityUpdate,
+    ) -> MemoryResult<(), Self::Error> {
+        self.modify_entity_checked(name, update, |entity| {
+            if let Some(obs) = &update.observations {
+                if let Some(set) = &obs.set {
+                    entity.observations = set.clone();
+                } else if let Some(add) = &obs.add {
+                    for observation in add {
+                        if !entity.observations.contains(observation) {
+                            entity.observations.push(observation.clone());
+                        }
+                    }
+                } else if let Some(remove) = &obs.remove {
+                    entity.observations.retain(|o| !remove.contains(o));
+                }
+            }
+
+            if let Some(props) = &update.properties {
+                Self::apply_properties(&mut entity.properties, props);
+            }
+
+            if let Some(labels) = &update.labels {
+                if let Some(add) = &labels.add {
+                    for label in add {
+                        if !entity.labels.contains(label) {
+                            entity.labels.push(label.clone());
+                        }
+                    }
+                } else if let Some(remove) = &labels.remove {
+                    entity.labels.retain(|l| !remove.contains(l));
+                }
+            }
+        })
+        .await
+    }
+
+    async fn update_relationship(
+        &self,
+        from: &str,
+        to: &str,
+        name: &str,
+        update: &RelationshipUpdate,
+    ) -> MemoryResult<(), Self::Error> {
+        let Some(props) = &update.properties else {
+            return Ok(());
+        };
+        let edges = self
+            .relationships_touching(&[from.to_string()], Some(name.to_string()))
+            .await?;
+        for mut edge in edges
+            .into_iter()
+            .filter(|r| r.source == from && r.target == to)
+        {
+            let Some(id) = edge.id.take() else {
+                continue;
+            };
+            Self::apply_properties(&mut edge.properties, props);
+            self.db
+                .query("UPDATE $id SET properties = $properties")
+                .bind(("id", id))
+                .bind(("properties", edge.properties))
+                .await
+                .and_then(|r| r.check())
+                .map_err(query_error("update relationship"))?;
+        }
+        Ok(())
+    }
+
+    /// Record ids are the entity names, so the entity and its relationships
+    /// are recreated under the new name in one transaction
+    async fn rename_entity(&self, old: &str, new: &str) -> MemoryResult<(), Self::Error> {
+        Self::require_name(old)?;
+        Self::require_name(new)?;
+        let Some(mut record) = self.entity_record(old).await? else {
+            return Ok(());
+        };
+        record.name = new.to_string();
+        record.revision += 1;
+        let rename = |n: String| if n == old { new.to_string() } else { n };
+        let relationships: Vec<RelationshipRecord> = self
+            .relationships_touching(&[old.to_string()], None)
+            .await?
+            .into_iter()
+            .map(|r| RelationshipRecord {
+                id: None,
+                source: rename(r.source),
+                target: rename(r.target),
+                ..r
+            })
+            .collect();
+        self.db
+            .query(format!(
+                "BEGIN TRANSACTION;{CREATE_ENTITIES}{CREATE_RELATIONSHIPS}
+                 DELETE relationship WHERE record::id(in) = $old OR record::id(out) = $old;
+                 DELETE type::thing('entity', $old);
+                 COMMIT TRANSACTION;"
+            ))
+            .bind(("entities", vec![record]))
+            .bind(("relationships", relationships))
+            .bind(("old", old.to_string()))
+            .await
+            .and_then(|r| r.check())
+            .map_err(query_error("rename entity"))?;
+        Ok(())
+    }
+
+    /// Replace the entity and its relationships and delete the sources in
+    /// one transaction
+    async fn merge_entities(
+        &self,
+        merged: &MemoryEntity,
+        sources: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        Self::require_name(&merged.name)?;
+        let mut names = sources.to_vec();
+        names.push(merged.name.clone());
+        let relationships: Vec<RelationshipRecord> = merged
+            .relationships
+            .iter()
+            .map(RelationshipRecord::from_relationship)
+            .collect();
+        self.db
+            .query(format!(
+                "BEGIN TRANSACTION;
+                 DELETE relationship WHERE record::id(in) IN $names OR record::id(out) IN $names;
+                 DELETE entity WHERE name IN $sources;
+                 {CREATE_ENTITIES}{CREATE_RELATIONSHIPS}
+                 COMMIT TRANSACTION;"
+            ))
+            .bind(("names", names))
+            .bind(("sources", sources.to_vec()))
+            .bind((
+                "entities",
+                vec![EntityRecord {
+                    revision: merged.revision + 1,
+                    ..EntityRecord::from_entity(merged)
+                }],
+            ))
+            .bind(("relationships", relationships))
+            .await
+            .and_then(|r| r.check())
+            .map_err(query_error("merge entities"))?;
+        Ok(())
+    }
+}
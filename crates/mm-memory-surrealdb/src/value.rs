@@ -0,0 +1,146 @@
+//! Typed storage of [`MemoryValue`]s.
+//!
+//! `MemoryValue` serializes untagged, so a date would read back as a plain
+//! string. Properties are therefore stored as `{ type, value }` objects that
+//! keep the variant.
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use mm_memory::MemoryValue;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// A [`MemoryValue`] as stored in SurrealDB
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub(crate) enum StoredValue {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    Bytes(Vec<u8>),
+    List(Vec<String>),
+    Map(HashMap<String, String>),
+    Date(NaiveDate),
+    Time(NaiveTime),
+    OffsetTime { time: NaiveTime, offset_secs: i32 },
+    DateTime(DateTime<FixedOffset>),
+    LocalDateTime(NaiveDateTime),
+    Duration(Duration),
+    Json(serde_json::Value),
+}
+
+impl From<&MemoryValue> for StoredValue {
+    fn from(value: &MemoryValue) -> Self {
+        match value.clone() {
+            MemoryValue::String(s) => Self::String(s),
+            MemoryValue::Integer(i) => Self::Integer(i),
+            MemoryValue::Float(f) => Self::Float(f),
+            MemoryValue::Boolean(b) => Self::Boolean(b),
+            MemoryValue::Bytes(b) => Self::Bytes(b),
+            MemoryValue::List(l) => Self::List(l),
+            MemoryValue::Map(m) => Self::Map(m),
+            MemoryValue::Date(d) => Self::Date(d),
+            MemoryValue::Time(t) => Self::Time(t),
+            MemoryValue::OffsetTime { time, offset } => Self::OffsetTime {
+                time,
+                offset_secs: offset.local_minus_utc(),
+            },
+            MemoryValue::DateTime(dt) => Self::DateTime(dt),
+            MemoryValue::LocalDateTime(dt) => Self::LocalDateTime(dt),
+            MemoryValue::Duration(d) => Self::Duration(d),
+            MemoryValue::Json(v) => Self::Json(v),
+        }
+    }
+}
+
+impl TryFrom<StoredValue> for MemoryValue {
+    type Error = String;
+
+    fn try_from(value: StoredValue) -> Result<Self, Self::Error> {
+        Ok(match value {
+            StoredValue::String(s) => Self::String(s),
+            StoredValue::Integer(i) => Self::Integer(i),
+            StoredValue::Float(f) => Self::Float(f),
+            StoredValue::Boolean(b) => Self::Boolean(b),
+            StoredValue::Bytes(b) => Self::Bytes(b),
+            StoredValue::List(l) => Self::List(l),
+            StoredValue::Map(m) => Self::Map(m),
+            StoredValue::Date(d) => Self::Date(d),
+            StoredValue::Time(t) => Self::Time(t),
+            StoredValue::OffsetTime { time, offset_secs } => Self::OffsetTime {
+                time,
+                offset: FixedOffset::east_opt(offset_secs)
+                    .ok_or_else(|| format!("Invalid offset seconds: {offset_secs}"))?,
+            },
+            StoredValue::DateTime(dt) => Self::DateTime(dt),
+            StoredValue::LocalDateTime(dt) => Self::LocalDateTime(dt),
+            StoredValue::Duration(d) => Self::Duration(d),
+            StoredValue::Json(v) => Self::Json(v),
+        })
+    }
+}
+
+/// Convert a property map for storage
+pub(crate) fn to_stored(properties: &HashMap<String, MemoryValue>) -> HashMap<String, StoredValue> {
+    properties
+        .iter()
+        .map(|(k, v)| (k.clone(), StoredValue::from(v)))
+        .collect()
+}
+
+/// Convert a stored property map back
+pub(crate) fn from_stored(
+    properties: HashMap<String, StoredValue>,
+) -> Result<HashMap<String, MemoryValue>, String> {
+    properties
+        .into_iter()
+        .map(|(k, v)| Ok((k, MemoryValue::try_from(v)?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_variant_round_trips() {
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let time = NaiveTime::from_hms_opt(12, 30, 0).unwrap();
+        let values = [
+            MemoryValue::String("s".to_string()),
+            MemoryValue::Integer(-7),
+            MemoryValue::Float(1.5),
+            MemoryValue::Boolean(true),
+            MemoryValue::Bytes(vec![0, 255]),
+            MemoryValue::List(vec!["a".to_string()]),
+            MemoryValue::Map(HashMap::from([("k".to_string(), "v".to_string())])),
+            MemoryValue::Date(date),
+            MemoryValue::Time(time),
+            MemoryValue::OffsetTime { time, offset },
+            MemoryValue::DateTime(date.and_time(time).and_local_timezone(offset).unwrap()),
+            MemoryValue::LocalDateTime(date.and_time(time)),
+            MemoryValue::Duration(Duration::new(90, 5)),
+            MemoryValue::Json(serde_json::json!([{"k": [1, "v"]}])),
+        ];
+
+        for value in values {
+            let stored = StoredValue::from(&value);
+            let json = serde_json::to_value(&stored).unwrap();
+            let stored: StoredValue = serde_json::from_value(json).unwrap();
+            assert_eq!(MemoryValue::try_from(stored).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn date_keeps_its_type() {
+        let stored = StoredValue::from(&MemoryValue::Date(
+            NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+        ));
+        assert_eq!(
+            serde_json::to_value(&stored).unwrap(),
+            serde_json::json!({"type": "date", "value": "2024-05-01"})
+        );
+    }
+}
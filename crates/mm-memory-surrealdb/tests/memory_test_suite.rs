@@ -0,0 +1,53 @@
+use chrono::{FixedOffset, NaiveDate, NaiveTime};
+use mm_memory::test_suite::run_memory_service_test_suite;
+use mm_memory::{MemoryEntity, MemoryRepository, MemoryValue};
+use mm_memory_surrealdb::{SurrealConfig, SurrealRepository};
+use std::collections::HashMap;
+use std::time::Duration;
+
+async fn repository() -> SurrealRepository {
+    SurrealRepository::connect(SurrealConfig::new("mem://"))
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn surreal_repository_passes_memory_test_suite() {
+    run_memory_service_test_suite(repository().await)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn property_values_keep_their_type() {
+    let repo = repository().await;
+    let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    let time = NaiveTime::from_hms_opt(9, 15, 0).unwrap();
+    let offset = FixedOffset::west_opt(5 * 3600).unwrap();
+    let properties = HashMap::from([
+        ("date".to_string(), MemoryValue::Date(date)),
+        (
+            "text".to_string(),
+            MemoryValue::String("2024-05-01".to_string()),
+        ),
+        (
+            "offset_time".to_string(),
+            MemoryValue::OffsetTime { time, offset },
+        ),
+        (
+            "duration".to_string(),
+            MemoryValue::Duration(Duration::from_millis(1500)),
+        ),
+        ("bytes".to_string(), MemoryValue::Bytes(vec![1, 2, 3])),
+    ]);
+    let entity = MemoryEntity {
+        name: "typed".to_string(),
+        labels: vec!["Example".to_string()],
+        properties: properties.clone(),
+        ..Default::default()
+    };
+
+    repo.create_entities(&[entity]).await.unwrap();
+    let stored = repo.find_entity_by_name("typed").await.unwrap().unwrap();
+    assert_eq!(stored.properties, properties);
+}
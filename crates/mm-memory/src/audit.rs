@@ -0,0 +1,199 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::future::Future;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+tokio::task_local! {
+    static CORRELATION_ID: String;
+}
+
+/// Run `fut` with `id` attached to the audit entries it produces
+pub async fn with_correlation_id<F: Future>(id: String, fut: F) -> F::Output {
+    CORRELATION_ID.scope(id, fut).await
+}
+
+/// Correlation id of the surrounding [`with_correlation_id`] call
+pub fn current_correlation_id() -> Option<String> {
+    CORRELATION_ID.try_with(Clone::clone).ok()
+}
+
+/// One change to the graph
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct AuditEntry {
+    #[schemars(with = "String")]
+    pub timestamp: DateTime<Utc>,
+    /// `agent_name` of the server that made the change
+    pub actor: String,
+    /// Service method, e.g. `update_entity`
+    pub operation: String,
+    /// Entities affected
+    pub names: Vec<String>,
+    /// Tool call that caused the change, when made through the MCP server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+}
+
+/// Filter on audit entries; unset fields match everything
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct AuditQuery {
+    #[serde(default)]
+    pub actor: Option<String>,
+    #[serde(default)]
+    pub operation: Option<String>,
+    /// Only entries affecting this entity
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Only entries at or after this time
+    #[serde(default)]
+    #[schemars(with = "Option<String>")]
+    pub since: Option<DateTime<Utc>>,
+    /// Maximum number of entries, newest first
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl AuditQuery {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        self.actor.as_ref().is_none_or(|a| &entry.actor == a)
+            && self
+                .operation
+                .as_ref()
+                .is_none_or(|o| &entry.operation == o)
+            && self.name.as_ref().is_none_or(|n| entry.names.contains(n))
+            && self.since.is_none_or(|since| entry.timestamp >= since)
+    }
+
+    /// Matching entries of `entries`, oldest first, as newest first
+    fn select(&self, entries: impl DoubleEndedIterator<Item = AuditEntry>) -> Vec<AuditEntry> {
+        entries
+            .rev()
+            .filter(|e| self.matches(e))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+/// Destination of audit entries
+pub trait AuditSink: Send + Sync {
+    fn record(&self, entry: AuditEntry) -> io::Result<()>;
+
+    /// Entries matching `query`, newest first
+    fn entries(&self, query: &AuditQuery) -> io::Result<Vec<AuditEntry>>;
+}
+
+/// Audit trail kept in memory, mostly for tests
+#[derive(Debug, Default)]
+pub struct InMemoryAuditSink {
+    entries: Mutex<Vec<AuditEntry>>,
+}
+
+impl AuditSink for InMemoryAuditSink {
+    fn record(&self, entry: AuditEntry) -> io::Result<()> {
+        self.entries.lock().expect("audit lock").push(entry);
+        Ok(())
+    }
+
+    fn entries(&self, query: &AuditQuery) -> io::Result<Vec<AuditEntry>> {
+        let entries = self.entries.lock().expect("audit lock");
+        Ok(query.select(entries.iter().cloned()))
+    }
+}
+
+/// Audit trail appended to a JSON Lines file
+#[derive(Debug)]
+pub struct JsonlAuditSink {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl JsonlAuditSink {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+}
+
+impl AuditSink for JsonlAuditSink {
+    fn record(&self, entry: AuditEntry) -> io::Result<()> {
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+        let _guard = self.lock.lock().expect("audit lock");
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())
+    }
+
+    fn entries(&self, query: &AuditQuery) -> io::Result<Vec<AuditEntry>> {
+        let _guard = self.lock.lock().expect("audit lock");
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                entries.push(serde_json::from_str(&line)?);
+            }
+        }
+        Ok(query.select(entries.into_iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(operation: &str, names: &[&str]) -> AuditEntry {
+        AuditEntry {
+            timestamp: Utc::now(),
+            actor: "tester".to_string(),
+            operation: operation.to_string(),
+            names: names.iter().map(|n| n.to_string()).collect(),
+            correlation_id: current_correlation_id(),
+        }
+    }
+
+    #[tokio::test]
+    async fn jsonl_sink_round_trips_newest_first() {
+        let dir = std::env::temp_dir().join(format!("mm-audit-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let sink = JsonlAuditSink::new(dir.join("audit.jsonl"));
+        assert!(sink.entries(&AuditQuery::default()).unwrap().is_empty());
+
+        sink.record(entry("create_entities", &["a", "b"])).unwrap();
+        with_correlation_id("abc".to_string(), async {
+            sink.record(entry("update_entity", &["a"])).unwrap();
+        })
+        .await;
+        sink.record(entry("delete_entities", &["b"])).unwrap();
+
+        let query = AuditQuery {
+            name: Some("a".to_string()),
+            ..Default::default()
+        };
+        let entries = sink.entries(&query).unwrap();
+        let operations: Vec<&str> = entries.iter().map(|e| e.operation.as_str()).collect();
+        assert_eq!(operations, ["update_entity", "create_entities"]);
+        assert_eq!(entries[0].correlation_id.as_deref(), Some("abc"));
+
+        let query = AuditQuery {
+            limit: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            sink.entries(&query).unwrap()[0].operation,
+            "delete_entities"
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
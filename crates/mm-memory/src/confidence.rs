@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use crate::entity::MemoryEntity;
+use crate::observation_meta::{get_observation_meta, observation_meta, set_observation_meta};
+use crate::relationship::MemoryRelationship;
+use crate::validation_error::ValidationErrorKind;
+use crate::value::MemoryValue;
+
+/// Property holding the confidence of an entity or relationship
+pub const CONFIDENCE_PROPERTY: &str = "confidence";
+
+/// Entity property holding per-observation confidence scores
+pub const OBSERVATION_CONFIDENCE_PROPERTY: &str = "observation_confidence";
+
+/// Confidence assumed for facts recorded without a score
+pub const DEFAULT_CONFIDENCE: f64 = 1.0;
+
+/// Whether `value` is a valid confidence score
+pub fn is_valid_confidence(value: f64) -> bool {
+    (0.0..=1.0).contains(&value)
+}
+
+fn parse_confidence(value: &MemoryValue) -> Option<f64> {
+    match value {
+        MemoryValue::Float(f) => Some(*f),
+        MemoryValue::Integer(i) => Some(*i as f64),
+        MemoryValue::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Confidence recorded directly in `properties`, if any
+pub fn property_confidence(properties: &HashMap<String, MemoryValue>) -> Option<f64> {
+    properties
+        .get(CONFIDENCE_PROPERTY)
+        .and_then(parse_confidence)
+}
+
+/// Confidence of a single observation stored in `properties`
+pub fn observation_confidence(properties: &HashMap<String, MemoryValue>, observation: &str) -> f64 {
+    get_observation_meta(properties, OBSERVATION_CONFIDENCE_PROPERTY, observation)
+        .unwrap_or(DEFAULT_CONFIDENCE)
+}
+
+/// Record the confidence of `observation` in `properties`
+pub fn set_observation_confidence(
+    properties: &mut HashMap<String, MemoryValue>,
+    observation: &str,
+    confidence: f64,
+) {
+    set_observation_meta(
+        properties,
+        OBSERVATION_CONFIDENCE_PROPERTY,
+        observation,
+        confidence,
+    );
+}
+
+/// Overall confidence of an entity
+///
+/// An explicit `confidence` property wins. Otherwise the entity is as
+/// trustworthy as its best observation, and unscored observations count as
+/// established facts.
+pub fn entity_confidence(entity: &MemoryEntity) -> f64 {
+    if let Some(confidence) = property_confidence(&entity.properties) {
+        return confidence;
+    }
+    entity
+        .observations
+        .iter()
+        .map(|o| observation_confidence(&entity.properties, o))
+        .reduce(f64::max)
+        .unwrap_or(DEFAULT_CONFIDENCE)
+}
+
+/// Confidence of a relationship, defaulting to [`DEFAULT_CONFIDENCE`]
+pub fn relationship_confidence(relationship: &MemoryRelationship) -> f64 {
+    property_confidence(&relationship.properties).unwrap_or(DEFAULT_CONFIDENCE)
+}
+
+/// Order observations from most to least confident, keeping the original
+/// order among equals
+pub fn rank_observations(entity: &mut MemoryEntity) {
+    let properties = &entity.properties;
+    entity.observations.sort_by(|a, b| {
+        observation_confidence(properties, b).total_cmp(&observation_confidence(properties, a))
+    });
+}
+
+/// Order entities from most to least confident, ranking their observations too
+pub fn rank_entities(entities: &mut [MemoryEntity]) {
+    for entity in entities.iter_mut() {
+        rank_observations(entity);
+    }
+    entities.sort_by(|a, b| entity_confidence(b).total_cmp(&entity_confidence(a)));
+}
+
+/// Order relationships from most to least confident
+pub fn rank_relationships(relationships: &mut [MemoryRelationship]) {
+    relationships.sort_by(|a, b| relationship_confidence(b).total_cmp(&relationship_confidence(a)));
+}
+
+/// Validate confidence scores stored in `properties`
+pub fn validate_confidence(properties: &HashMap<String, MemoryValue>) -> Vec<ValidationErrorKind> {
+    let mut errs = Vec::new();
+    if let Some(value) = properties.get(CONFIDENCE_PROPERTY)
+        && !parse_confidence(value).is_some_and(is_valid_confidence)
+    {
+        errs.push(ValidationErrorKind::InvalidConfidence(
+            CONFIDENCE_PROPERTY.to_string(),
+        ));
+    }
+    match properties.get(OBSERVATION_CONFIDENCE_PROPERTY) {
+        None => {}
+        Some(MemoryValue::List(items)) => {
+            let scores = observation_meta::<f64>(properties, OBSERVATION_CONFIDENCE_PROPERTY);
+            if scores.len() != items.len() {
+                errs.push(ValidationErrorKind::InvalidConfidence(
+                    OBSERVATION_CONFIDENCE_PROPERTY.to_string(),
+                ));
+            }
+            for (observation, score) in scores {
+                if !is_valid_confidence(score) {
+                    errs.push(ValidationErrorKind::InvalidConfidence(observation));
+                }
+            }
+        }
+        Some(_) => errs.push(ValidationErrorKind::InvalidConfidence(
+            OBSERVATION_CONFIDENCE_PROPERTY.to_string(),
+        )),
+    }
+    errs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(observations: &[&str]) -> MemoryEntity {
+        MemoryEntity {
+            name: "test:entity".to_string(),
+            labels: vec!["Test".to_string()],
+            observations: observations.iter().map(|o| o.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_unscored_defaults_to_established() {
+        let e = entity(&["fact"]);
+        assert_eq!(entity_confidence(&e), DEFAULT_CONFIDENCE);
+        assert_eq!(observation_confidence(&e.properties, "fact"), 1.0);
+    }
+
+    #[test]
+    fn test_rank_observations_and_entities() {
+        let mut guess = entity(&["maybe", "perhaps"]);
+        set_observation_confidence(&mut guess.properties, "maybe", 0.2);
+        set_observation_confidence(&mut guess.properties, "perhaps", 0.6);
+        let fact = entity(&["fact"]);
+        let mut entities = vec![guess, fact];
+        rank_entities(&mut entities);
+
+        assert_eq!(entities[0].observations, ["fact"]);
+        assert_eq!(entities[1].observations, ["perhaps", "maybe"]);
+        assert_eq!(entity_confidence(&entities[1]), 0.6);
+    }
+
+    #[test]
+    fn test_validate_confidence() {
+        let mut props = HashMap::new();
+        props.insert(CONFIDENCE_PROPERTY.to_string(), MemoryValue::Float(0.5));
+        set_observation_confidence(&mut props, "ok", 0.9);
+        assert!(validate_confidence(&props).is_empty());
+
+        props.insert(CONFIDENCE_PROPERTY.to_string(), MemoryValue::Float(1.5));
+        set_observation_confidence(&mut props, "bad", -0.1);
+        let errs = validate_confidence(&props);
+        assert_eq!(errs.len(), 2);
+        assert!(errs.contains(&ValidationErrorKind::InvalidConfidence("bad".to_string())));
+    }
+}
@@ -1,7 +1,18 @@
+use chrono::{FixedOffset, Offset, Utc};
+use mm_utils::{TemplateVars, slugify, template};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::Duration;
 
+use crate::entity_template::EntityTemplate;
+use crate::expiry::ExpiryPolicy;
 use crate::labels::*;
+use crate::naming::NamingPolicy;
+use crate::property_rules::PropertyType;
+use crate::redaction::RedactionRule;
+use crate::validation_error::ValidationErrorKind;
+use crate::value::MemoryValue;
 
 /// Configuration options for memory service behavior
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -33,6 +44,261 @@ pub struct MemoryConfig {
     /// Name of the agent using this configuration
     #[serde(default)]
     pub agent_name: String,
+
+    /// Frequently filtered properties per label that should be indexed,
+    /// e.g. `Task = ["status", "due_date"]`
+    #[serde(default)]
+    pub indexed_properties: HashMap<String, Vec<String>>,
+
+    /// Relationship names that may connect an entity to itself
+    #[serde(default)]
+    pub self_loop_relationships: HashSet<String>,
+
+    /// Reject relationships that already exist in the graph instead of
+    /// creating a parallel edge
+    #[serde(default)]
+    pub merge_relationships: bool,
+
+    /// Limits on tasks in progress at the same time
+    #[serde(default)]
+    pub wip_limits: WipLimits,
+
+    /// Extra vocabulary per project, keyed by project name
+    #[serde(default)]
+    pub projects: HashMap<String, ProjectVocabulary>,
+
+    /// Expiry policies per label, e.g.
+    /// `GitStatusSnapshot = { ttl = "1d", action = "archive" }`
+    #[serde(default)]
+    pub expiry: HashMap<String, ExpiryPolicy>,
+
+    /// Retries of operations failing with transient backend errors
+    #[serde(default)]
+    pub retry: RetryConfig,
+
+    /// Entities or relationships written to the backend per call when
+    /// creating large batches
+    #[serde(default = "MemoryConfig::default_batch_size")]
+    pub batch_size: usize,
+
+    /// Reject every operation that would change the graph
+    #[serde(default)]
+    pub read_only: bool,
+
+    /// Move deleted entities to the trash by tagging them `Deleted` instead
+    /// of removing them; trashed entities can be restored until purged
+    #[serde(default)]
+    pub soft_delete: bool,
+
+    /// Keep the previous state of an entity as an `EntityVersion` entity on
+    /// every `update_entity`
+    #[serde(default)]
+    pub history: bool,
+
+    /// JSON Lines file receiving an audit entry for every change
+    #[serde(default)]
+    pub audit_log: Option<PathBuf>,
+
+    /// Secrets masked in observations and properties before they are stored
+    #[serde(default)]
+    pub redaction: Vec<RedactionRule>,
+
+    /// Reject new entities whose name is already in use instead of creating
+    /// a second entity with that name
+    #[serde(default)]
+    pub reject_duplicate_names: bool,
+
+    /// Relationship names that must not form a cycle, e.g. `depends_on`
+    #[serde(default)]
+    pub acyclic_relationships: HashSet<String>,
+
+    /// Constraints on the ends of relationships, keyed by relationship name
+    #[serde(default)]
+    pub relationship_rules: HashMap<String, RelationshipRule>,
+
+    /// Template of the names suggested by `slugify_name`, with `{{label}}`
+    /// and `{{slug}}` on top of the usual template variables
+    #[serde(default = "MemoryConfig::default_name_template")]
+    pub name_template: String,
+
+    /// Store datetime properties in UTC and the values of date properties,
+    /// such as `due_date`, as dates, rejecting those that are not
+    #[serde(default)]
+    pub normalize_datetimes: bool,
+
+    /// Offset from UTC in minutes assumed for datetimes written without one
+    #[serde(default)]
+    pub local_utc_offset_minutes: i32,
+
+    /// Record when and by which agent each observation was written
+    #[serde(default)]
+    pub track_observations: bool,
+
+    /// Templates for `create_from_template`, keyed by name; templates stored
+    /// in the graph are used for names not listed here
+    #[serde(default)]
+    pub templates: HashMap<String, EntityTemplate>,
+
+    /// Labels implied by another label, e.g. `BugTask = ["Task"]`; entities
+    /// get the ancestors of their labels when created or labeled, so
+    /// queries for `Task` find bug tasks too
+    #[serde(default)]
+    pub label_parents: HashMap<String, Vec<String>>,
+
+    /// Labels `update_entity` removes only when forced, e.g. `Memory`
+    #[serde(default)]
+    pub reserved_labels: HashSet<String>,
+
+    /// Relationship names `delete_relationships` removes only when forced,
+    /// e.g. `contains`
+    #[serde(default)]
+    pub reserved_relationships: HashSet<String>,
+
+    /// Format names of new entities must follow, e.g.
+    /// `{ segments = "scope:type:slug" }`
+    #[serde(default)]
+    pub naming: NamingPolicy,
+}
+
+/// Labels and relationships allowed only within one project, on top of the
+/// global `allowed_labels` and `allowed_relationships`
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ProjectVocabulary {
+    #[serde(default)]
+    pub allowed_labels: HashSet<String>,
+
+    #[serde(default)]
+    pub allowed_relationships: HashSet<String>,
+}
+
+/// Which entities a relationship type may connect and how many of them;
+/// unset constraints are not checked
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RelationshipRule {
+    /// The source must have one of these labels
+    #[serde(default)]
+    pub from_labels: Vec<String>,
+
+    /// The target must have one of these labels
+    #[serde(default)]
+    pub to_labels: Vec<String>,
+
+    /// Most relationships of this type leaving one entity
+    #[serde(default)]
+    pub max_outgoing: Option<usize>,
+
+    /// Most relationships of this type reaching one entity
+    #[serde(default)]
+    pub max_incoming: Option<usize>,
+
+    /// Properties relationships of this type may carry and their types
+    #[serde(default)]
+    pub properties: BTreeMap<String, PropertyType>,
+
+    /// Reject properties not listed in `properties`
+    #[serde(default)]
+    pub strict_properties: bool,
+}
+
+impl RelationshipRule {
+    /// Problems with `properties` given the declared ones
+    pub fn check_properties(
+        &self,
+        properties: &HashMap<String, MemoryValue>,
+    ) -> Vec<ValidationErrorKind> {
+        let mut keys: Vec<&String> = properties.keys().collect();
+        keys.sort();
+        keys.into_iter()
+            .filter_map(|key| match self.properties.get(key) {
+                Some(ty) if !ty.accepts(&properties[key]) => {
+                    Some(ValidationErrorKind::InvalidPropertyType {
+                        property: key.clone(),
+                        expected: ty.name(),
+                    })
+                }
+                None if self.strict_properties => {
+                    Some(ValidationErrorKind::UnknownProperty(key.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// `properties` as `key: type` pairs, e.g. `reason: string, since: date`
+    pub fn describe_properties(&self) -> String {
+        self.properties
+            .iter()
+            .map(|(key, ty)| format!("{key}: {}", ty.name()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Maximum number of `in_progress` tasks; unset limits are not checked
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct WipLimits {
+    /// Per project
+    #[serde(default)]
+    pub per_project: Option<usize>,
+
+    /// Per assignee, counted across all projects
+    #[serde(default)]
+    pub per_assignee: Option<usize>,
+
+    /// Reject transitions over a limit instead of warning about them
+    #[serde(default)]
+    pub enforce: bool,
+}
+
+/// Retries with exponential backoff for transient backend errors, such as a
+/// dropped connection or a cluster leader switch
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RetryConfig {
+    /// Attempts per operation including the first; 1 disables retries
+    #[serde(default = "RetryConfig::default_max_attempts")]
+    pub max_attempts: u32,
+
+    /// Delay before the first retry, doubled for each further one
+    #[serde(default = "RetryConfig::default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+
+    /// Upper bound on the delay between attempts
+    #[serde(default = "RetryConfig::default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+}
+
+impl RetryConfig {
+    fn default_max_attempts() -> u32 {
+        3
+    }
+
+    fn default_initial_backoff_ms() -> u64 {
+        100
+    }
+
+    fn default_max_backoff_ms() -> u64 {
+        2_000
+    }
+
+    /// Delay before retry number `retry`, counting from 1
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u64 << retry.saturating_sub(1).min(32);
+        Duration::from_millis(
+            self.initial_backoff_ms
+                .saturating_mul(factor)
+                .min(self.max_backoff_ms),
+        )
+    }
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: Self::default_max_attempts(),
+            initial_backoff_ms: Self::default_initial_backoff_ms(),
+            max_backoff_ms: Self::default_max_backoff_ms(),
+        }
+    }
 }
 
 /// Default label used when none is specified in the configuration
@@ -57,6 +323,7 @@ pub const DEFAULT_RELATIONSHIPS: &[&str] = &[
     "tagged_with",
     "example_of",
     "depends_on",
+    "previous_version",
 ];
 
 /// Default set of allowed label names derived from the schema
@@ -119,6 +386,12 @@ pub const DEFAULT_LABELS: &[&str] = &[
     MAINTENANCE_LABEL,
     LABEL_LABEL,
     LANGUAGE_LABEL,
+    FORGET_PROPOSAL_LABEL,
+    ARCHIVED_LABEL,
+    DELETED_LABEL,
+    ENTITY_VERSION_LABEL,
+    DECISION_LABEL,
+    ENTITY_TEMPLATE_LABEL,
 ];
 
 impl MemoryConfig {
@@ -126,6 +399,128 @@ impl MemoryConfig {
     fn default_true() -> bool {
         true
     }
+
+    fn default_batch_size() -> usize {
+        500
+    }
+
+    fn default_name_template() -> String {
+        "{{label}}:{{slug}}".to_string()
+    }
+
+    /// Vocabulary of `project`, falling back to `default_project`
+    fn project_vocabulary(&self, project: Option<&str>) -> Option<&ProjectVocabulary> {
+        project
+            .or(self.default_project.as_deref())
+            .and_then(|p| self.projects.get(p))
+    }
+
+    /// Whether `label` may be used in `project`
+    ///
+    /// Always true when `allow_default_labels` is off.
+    pub fn is_label_allowed(&self, label: &str, project: Option<&str>) -> bool {
+        !self.allow_default_labels
+            || self.default_label.as_deref() == Some(label)
+            || DEFAULT_LABELS.contains(&label)
+            || self.allowed_labels.contains(label)
+            || self.label_parents.contains_key(label)
+            || self
+                .project_vocabulary(project)
+                .is_some_and(|v| v.allowed_labels.contains(label))
+    }
+
+    /// Whether relationship `name` may be used in `project`
+    ///
+    /// Always true when `allow_default_relationships` is off.
+    pub fn is_relationship_allowed(&self, name: &str, project: Option<&str>) -> bool {
+        !self.allow_default_relationships
+            || DEFAULT_RELATIONSHIPS.contains(&name)
+            || self.allowed_relationships.contains(name)
+            || self
+                .project_vocabulary(project)
+                .is_some_and(|v| v.allowed_relationships.contains(name))
+    }
+
+    /// Whether `label` is allowed globally or in any project
+    pub fn is_label_known(&self, label: &str) -> bool {
+        self.is_label_allowed(label, None)
+            || self
+                .projects
+                .values()
+                .any(|v| v.allowed_labels.contains(label))
+    }
+
+    /// Whether relationship `name` is allowed globally or in any project
+    pub fn is_relationship_known(&self, name: &str) -> bool {
+        self.is_relationship_allowed(name, None)
+            || self
+                .projects
+                .values()
+                .any(|v| v.allowed_relationships.contains(name))
+    }
+
+    /// `labels` followed by their ancestors in `label_parents` that are not
+    /// among them yet
+    pub fn with_ancestor_labels(&self, labels: &[String]) -> Vec<String> {
+        let mut all = labels.to_vec();
+        let mut index = 0;
+        while let Some(label) = all.get(index) {
+            let parents = self.label_parents.get(label).cloned().unwrap_or_default();
+            for parent in parents {
+                if !all.contains(&parent) {
+                    all.push(parent);
+                }
+            }
+            index += 1;
+        }
+        all
+    }
+
+    /// Template variables for `project`, falling back to `default_project`
+    ///
+    /// `date` is today's UTC date in `YYYY-MM-DD` form.
+    pub fn template_vars(&self, project: Option<&str>) -> TemplateVars {
+        TemplateVars {
+            project: project
+                .or(self.default_project.as_deref())
+                .map(str::to_string),
+            agent: Some(self.agent_name.clone()).filter(|a| !a.is_empty()),
+            date: Some(chrono::Utc::now().format("%Y-%m-%d").to_string()),
+        }
+    }
+
+    /// Offset assumed for datetimes without one, UTC when
+    /// `local_utc_offset_minutes` is out of range
+    pub fn local_offset(&self) -> FixedOffset {
+        self.local_utc_offset_minutes
+            .checked_mul(60)
+            .and_then(FixedOffset::east_opt)
+            .unwrap_or_else(|| Utc.fix())
+    }
+
+    /// Entity name for `title` following `name_template`
+    ///
+    /// `{{label}}` and `{{slug}}` are the slugs of `label` and `title`.
+    pub fn entity_name(&self, project: Option<&str>, label: &str, title: &str) -> String {
+        let vars = self.template_vars(project);
+        let label = slugify(label);
+        let slug = slugify(title);
+        template::render(&self.name_template, |name| match name {
+            "label" => Some(label.as_str()),
+            "slug" => Some(slug.as_str()),
+            _ => vars.get(name),
+        })
+    }
+
+    /// Expiry policy for an entity with `labels`
+    ///
+    /// When several labels have a policy the shortest time to live wins.
+    pub fn expiry_policy<S: AsRef<str>>(&self, labels: &[S]) -> Option<&ExpiryPolicy> {
+        labels
+            .iter()
+            .filter_map(|l| self.expiry.get(l.as_ref()))
+            .min_by_key(|p| p.ttl)
+    }
 }
 
 impl Default for MemoryConfig {
@@ -138,6 +533,129 @@ impl Default for MemoryConfig {
             allowed_labels: HashSet::default(),
             default_project: None,
             agent_name: "unknown".to_string(),
+            indexed_properties: HashMap::default(),
+            self_loop_relationships: HashSet::default(),
+            merge_relationships: false,
+            wip_limits: WipLimits::default(),
+            projects: HashMap::default(),
+            expiry: HashMap::default(),
+            retry: RetryConfig::default(),
+            batch_size: Self::default_batch_size(),
+            read_only: false,
+            soft_delete: false,
+            history: false,
+            audit_log: None,
+            redaction: Vec::new(),
+            reject_duplicate_names: false,
+            acyclic_relationships: HashSet::default(),
+            relationship_rules: HashMap::new(),
+            name_template: Self::default_name_template(),
+            normalize_datetimes: false,
+            local_utc_offset_minutes: 0,
+            track_observations: false,
+            templates: HashMap::new(),
+            label_parents: HashMap::new(),
+            reserved_labels: HashSet::default(),
+            reserved_relationships: HashSet::default(),
+            naming: NamingPolicy::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_vocabulary() {
+        let config = MemoryConfig {
+            allowed_labels: HashSet::from(["Global".to_string()]),
+            default_project: Some("alpha".to_string()),
+            projects: HashMap::from([
+                (
+                    "alpha".to_string(),
+                    ProjectVocabulary {
+                        allowed_labels: HashSet::from(["Experiment".to_string()]),
+                        allowed_relationships: HashSet::from(["measures".to_string()]),
+                    },
+                ),
+                ("beta".to_string(), ProjectVocabulary::default()),
+            ]),
+            ..MemoryConfig::default()
+        };
+
+        assert!(config.is_label_allowed("Global", Some("beta")));
+        assert!(config.is_label_allowed("Experiment", None));
+        assert!(config.is_label_allowed("Experiment", Some("alpha")));
+        assert!(!config.is_label_allowed("Experiment", Some("beta")));
+        assert!(config.is_relationship_allowed("measures", None));
+        assert!(!config.is_relationship_allowed("measures", Some("beta")));
+        assert!(config.is_relationship_allowed("depends_on", Some("beta")));
+        assert!(config.is_relationship_known("measures"));
+        assert!(config.is_label_known("Experiment"));
+        assert!(!config.is_label_known("Unknown"));
+    }
+
+    #[test]
+    fn test_with_ancestor_labels() {
+        let config = MemoryConfig {
+            label_parents: HashMap::from([
+                ("BugTask".to_string(), vec!["Task".to_string()]),
+                ("Task".to_string(), vec!["WorkItem".to_string()]),
+                ("WorkItem".to_string(), vec!["Task".to_string()]),
+            ]),
+            ..MemoryConfig::default()
+        };
+
+        assert_eq!(
+            config.with_ancestor_labels(&["BugTask".to_string(), "Memory".to_string()]),
+            ["BugTask", "Memory", "Task", "WorkItem"]
+        );
+        assert!(config.is_label_allowed("BugTask", None));
+        assert!(!config.is_label_allowed("FeatureTask", None));
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        let retry = RetryConfig::default();
+        assert_eq!(retry.backoff(1), Duration::from_millis(100));
+        assert_eq!(retry.backoff(3), Duration::from_millis(400));
+        assert_eq!(retry.backoff(10), Duration::from_millis(2_000));
+        assert_eq!(retry.backoff(u32::MAX), Duration::from_millis(2_000));
+    }
+
+    #[test]
+    fn test_template_vars() {
+        let config = MemoryConfig {
+            default_project: Some("alpha".to_string()),
+            agent_name: "tester".to_string(),
+            ..MemoryConfig::default()
+        };
+
+        let vars = config.template_vars(None);
+        assert_eq!(vars.project.as_deref(), Some("alpha"));
+        assert_eq!(vars.agent.as_deref(), Some("tester"));
+        assert_eq!(
+            config.template_vars(Some("beta")).project.as_deref(),
+            Some("beta")
+        );
+        assert_eq!(
+            config.template_vars(None).render("{{agent}}@{{project}}"),
+            "tester@alpha"
+        );
+    }
+
+    #[test]
+    fn test_entity_name() {
+        let mut config = MemoryConfig::default();
+        assert_eq!(
+            config.entity_name(None, "Technology", "Rust Language"),
+            "technology:rust_language"
+        );
+        config.name_template = "{{project}}:{{label}}:{{slug}}".to_string();
+        assert_eq!(
+            config.entity_name(Some("mm"), "Task", "Fix the build!"),
+            "mm:task:fix_the_build"
+        );
+    }
+}
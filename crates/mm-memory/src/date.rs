@@ -0,0 +1,275 @@
+use chrono::{
+    DateTime, Datelike, Days, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday,
+};
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+
+use crate::validation_error::ValidationErrorKind;
+use crate::value::MemoryValue;
+
+/// Parse a date written by a person or an agent, relative to today in UTC
+///
+/// See [`parse_date_relative_to`] for the accepted forms.
+pub fn parse_date(input: &str) -> Result<NaiveDate, ValidationErrorKind> {
+    parse_date_relative_to(input, Utc::now().date_naive())
+}
+
+/// Parse a date, resolving relative forms against `today`
+///
+/// Accepted forms, case-insensitive:
+/// - RFC 3339 and ISO 8601 timestamps, keeping the date they were written in
+/// - `YYYY-MM-DD` and `YYYY/MM/DD`
+/// - `today`, `tomorrow`, `yesterday` and `next week`
+/// - `in N days` and `in N weeks`
+/// - a weekday such as `friday` or `this fri`, meaning the next such day
+///   with today included, and `next friday`, which skips today
+pub fn parse_date_relative_to(
+    input: &str,
+    today: NaiveDate,
+) -> Result<NaiveDate, ValidationErrorKind> {
+    let trimmed = input.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(trimmed) {
+        return Ok(dt.date_naive());
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(trimmed, format) {
+            return Ok(dt.date());
+        }
+    }
+    for format in ["%Y-%m-%d", "%Y/%m/%d"] {
+        if let Ok(date) = NaiveDate::parse_from_str(trimmed, format) {
+            return Ok(date);
+        }
+    }
+
+    let lower = trimmed.to_lowercase();
+    let words: Vec<&str> = lower.split_whitespace().collect();
+    let date = match words.as_slice() {
+        ["today"] => Some(today),
+        ["tomorrow"] => today.checked_add_days(Days::new(1)),
+        ["yesterday"] => today.checked_sub_days(Days::new(1)),
+        ["next", "week"] => today.checked_add_days(Days::new(7)),
+        ["in", count, unit] => count.parse::<u64>().ok().and_then(|n| {
+            let days = match *unit {
+                "day" | "days" => Some(n),
+                "week" | "weeks" => n.checked_mul(7),
+                _ => None,
+            }?;
+            today.checked_add_days(Days::new(days))
+        }),
+        ["next", day] => day
+            .parse::<Weekday>()
+            .ok()
+            .and_then(|w| upcoming(today, w, false)),
+        [day] | ["this", day] => day
+            .parse::<Weekday>()
+            .ok()
+            .and_then(|w| upcoming(today, w, true)),
+        _ => None,
+    };
+    date.ok_or_else(|| ValidationErrorKind::InvalidDate(input.to_string()))
+}
+
+/// First `weekday` after `today`, or `today` itself when `include_today`
+fn upcoming(today: NaiveDate, weekday: Weekday, include_today: bool) -> Option<NaiveDate> {
+    let ahead = (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+    let ahead = if ahead == 0 && !include_today {
+        7
+    } else {
+        ahead
+    };
+    today.checked_add_days(Days::new(ahead.into()))
+}
+
+/// Calendar date held by a property value
+///
+/// Timestamps keep the date they were written in and strings are parsed with
+/// [`parse_date`].
+pub fn value_to_date(value: &MemoryValue) -> Option<NaiveDate> {
+    match value {
+        MemoryValue::Date(d) => Some(*d),
+        MemoryValue::DateTime(dt) => Some(dt.date_naive()),
+        MemoryValue::LocalDateTime(dt) => Some(dt.date()),
+        MemoryValue::String(s) => parse_date(s).ok(),
+        _ => None,
+    }
+}
+
+/// Instant held by a datetime value, or by a string in RFC 3339
+///
+/// Datetimes without an offset are taken to be in UTC.
+pub fn value_to_datetime(value: &MemoryValue) -> Option<DateTime<Utc>> {
+    match value {
+        MemoryValue::DateTime(dt) => Some(dt.with_timezone(&Utc)),
+        MemoryValue::LocalDateTime(dt) => Some(dt.and_utc()),
+        MemoryValue::String(s) => DateTime::parse_from_rfc3339(s.trim())
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc)),
+        _ => None,
+    }
+}
+
+/// Whether a property named `key` holds a date, like `due_date`
+pub fn is_date_property(key: &str) -> bool {
+    key == "date" || key.ends_with("_date")
+}
+
+/// `value` converted to UTC when it is a datetime
+///
+/// Datetimes without an offset are taken to be at `local`.
+pub fn datetime_to_utc(value: &MemoryValue, local: FixedOffset) -> Option<DateTime<FixedOffset>> {
+    match value {
+        MemoryValue::DateTime(dt) => Some(dt.with_timezone(&Utc).fixed_offset()),
+        MemoryValue::LocalDateTime(dt) => local
+            .from_local_datetime(dt)
+            .single()
+            .map(|dt| dt.with_timezone(&Utc).fixed_offset()),
+        _ => None,
+    }
+}
+
+/// Convert the datetimes of `properties` to UTC and the values of date
+/// properties to dates
+///
+/// Values of date properties that cannot be read as a date are left as they
+/// are and reported as `InvalidDate`, in key order.
+pub fn normalize_datetimes(
+    properties: &mut HashMap<String, MemoryValue>,
+    local: FixedOffset,
+) -> Vec<ValidationErrorKind> {
+    let mut keys: Vec<String> = properties.keys().cloned().collect();
+    keys.sort();
+    let mut errors = Vec::new();
+    for key in keys {
+        let Some(value) = properties.get_mut(&key) else {
+            continue;
+        };
+        if is_date_property(&key) {
+            match value_to_date(value) {
+                Some(date) => *value = MemoryValue::Date(date),
+                None => errors.push(ValidationErrorKind::InvalidDate(value.to_string())),
+            }
+        } else if let Some(utc) = datetime_to_utc(value, local) {
+            *value = MemoryValue::DateTime(utc);
+        }
+    }
+    errors
+}
+
+/// Deserialize an optional date with [`parse_date`]
+///
+/// Use together with `#[serde(default)]` so a missing field becomes `None`.
+pub fn deserialize_optional_date<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|s| parse_date(&s).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    // 2025-06-11 is a Wednesday
+    fn parse(input: &str) -> Result<NaiveDate, ValidationErrorKind> {
+        parse_date_relative_to(input, date(2025, 6, 11))
+    }
+
+    #[test]
+    fn absolute_forms() {
+        assert_eq!(parse("2025-07-01"), Ok(date(2025, 7, 1)));
+        assert_eq!(parse(" 2025/07/01 "), Ok(date(2025, 7, 1)));
+        assert_eq!(parse("2025-07-01T23:30:00-05:00"), Ok(date(2025, 7, 1)));
+        assert_eq!(parse("2025-07-01T10:00:00Z"), Ok(date(2025, 7, 1)));
+        assert_eq!(parse("2025-07-01 10:00:00"), Ok(date(2025, 7, 1)));
+    }
+
+    #[test]
+    fn relative_forms() {
+        assert_eq!(parse("Today"), Ok(date(2025, 6, 11)));
+        assert_eq!(parse("tomorrow"), Ok(date(2025, 6, 12)));
+        assert_eq!(parse("yesterday"), Ok(date(2025, 6, 10)));
+        assert_eq!(parse("next week"), Ok(date(2025, 6, 18)));
+        assert_eq!(parse("in 3 days"), Ok(date(2025, 6, 14)));
+        assert_eq!(parse("in 2 weeks"), Ok(date(2025, 6, 25)));
+    }
+
+    #[test]
+    fn weekdays() {
+        assert_eq!(parse("friday"), Ok(date(2025, 6, 13)));
+        assert_eq!(parse("next Friday"), Ok(date(2025, 6, 13)));
+        assert_eq!(parse("wednesday"), Ok(date(2025, 6, 11)));
+        assert_eq!(parse("this wed"), Ok(date(2025, 6, 11)));
+        assert_eq!(parse("next wednesday"), Ok(date(2025, 6, 18)));
+        assert_eq!(parse("monday"), Ok(date(2025, 6, 16)));
+    }
+
+    #[test]
+    fn rejects_unknown_input() {
+        for input in ["", "soon", "in many days", "2025-13-01", "next month"] {
+            assert_eq!(
+                parse(input),
+                Err(ValidationErrorKind::InvalidDate(input.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn dates_from_values() {
+        assert_eq!(
+            value_to_date(&MemoryValue::Date(date(2025, 7, 1))),
+            Some(date(2025, 7, 1))
+        );
+        assert_eq!(
+            value_to_date(&MemoryValue::DateTime(
+                DateTime::parse_from_rfc3339("2025-07-01T12:00:00Z").unwrap()
+            )),
+            Some(date(2025, 7, 1))
+        );
+        assert_eq!(
+            value_to_date(&MemoryValue::String("2025-07-01".into())),
+            Some(date(2025, 7, 1))
+        );
+        assert_eq!(value_to_date(&MemoryValue::Integer(1)), None);
+    }
+
+    #[test]
+    fn normalizes_datetimes_and_dates() {
+        let local = FixedOffset::east_opt(2 * 3600).unwrap();
+        let mut properties = HashMap::from([
+            (
+                "seen_at".to_string(),
+                MemoryValue::DateTime(
+                    DateTime::parse_from_rfc3339("2025-07-01T12:00:00-05:00").unwrap(),
+                ),
+            ),
+            (
+                "updated".to_string(),
+                MemoryValue::LocalDateTime(date(2025, 7, 1).and_hms_opt(1, 0, 0).unwrap()),
+            ),
+            (
+                "due_date".to_string(),
+                MemoryValue::String("2025-07-04".into()),
+            ),
+            ("start_date".to_string(), MemoryValue::String("soon".into())),
+        ]);
+
+        let errors = normalize_datetimes(&mut properties, local);
+        assert_eq!(errors, [ValidationErrorKind::InvalidDate("soon".into())]);
+        assert_eq!(
+            properties["seen_at"],
+            MemoryValue::DateTime(DateTime::parse_from_rfc3339("2025-07-01T17:00:00Z").unwrap())
+        );
+        assert_eq!(
+            properties["updated"],
+            MemoryValue::DateTime(DateTime::parse_from_rfc3339("2025-06-30T23:00:00Z").unwrap())
+        );
+        assert_eq!(properties["due_date"], MemoryValue::Date(date(2025, 7, 4)));
+    }
+}
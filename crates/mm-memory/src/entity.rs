@@ -28,4 +28,30 @@ where
     /// Relationships connected to the entity
     #[serde(default)]
     pub relationships: Vec<MemoryRelationship>,
+    /// Counter the backend increments whenever the entity's name, labels,
+    /// observations or properties change; stored entities start at 1
+    #[serde(default)]
+    pub revision: u64,
+}
+
+impl MemoryEntity {
+    /// The entity with its properties converted to `P`
+    pub fn into_typed<P>(self) -> MemoryEntity<P>
+    where
+        P: JsonSchema
+            + Into<HashMap<String, MemoryValue>>
+            + From<HashMap<String, MemoryValue>>
+            + Clone
+            + std::fmt::Debug
+            + Default,
+    {
+        MemoryEntity {
+            name: self.name,
+            labels: self.labels,
+            observations: self.observations,
+            properties: P::from(self.properties),
+            relationships: self.relationships,
+            revision: self.revision,
+        }
+    }
 }
@@ -0,0 +1,152 @@
+use mm_utils::{TemplateVars, template};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::entity::MemoryEntity;
+use crate::relationship::MemoryRelationship;
+use crate::validation_error::ValidationErrorKind;
+use crate::value::MemoryValue;
+
+/// Prefix of the names of template entities, followed by the template name
+pub const TEMPLATE_NAME_PREFIX: &str = "template:";
+
+/// Property of a template entity holding the template as JSON
+pub const TEMPLATE_PROPERTY: &str = "template";
+
+/// Shape of the entities created from a named template
+///
+/// Strings in properties, observations and relationship targets may use
+/// `{{name}}` for the new entity's name along with the usual template
+/// variables.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct EntityTemplate {
+    #[serde(default)]
+    pub labels: Vec<String>,
+
+    /// Properties set unless given when instantiating
+    #[serde(default)]
+    pub properties: HashMap<String, MemoryValue>,
+
+    #[serde(default)]
+    pub observations: Vec<String>,
+
+    /// Relationships from the new entity
+    #[serde(default)]
+    pub relationships: Vec<TemplateRelationship>,
+}
+
+/// Relationship of type `name` from an entity created from a template to `to`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct TemplateRelationship {
+    pub name: String,
+    pub to: String,
+}
+
+impl EntityTemplate {
+    /// Template defined by `entity`, read from its [`TEMPLATE_PROPERTY`]
+    pub fn from_entity(entity: &MemoryEntity) -> Result<Self, ValidationErrorKind> {
+        let invalid = || ValidationErrorKind::InvalidTemplate(entity.name.clone());
+        match entity.properties.get(TEMPLATE_PROPERTY) {
+            Some(MemoryValue::Json(value)) => {
+                serde_json::from_value(value.clone()).map_err(|_| invalid())
+            }
+            Some(MemoryValue::String(json)) => serde_json::from_str(json).map_err(|_| invalid()),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Entity `name` shaped by this template and its relationships
+    pub fn instantiate(
+        &self,
+        name: &str,
+        vars: &TemplateVars,
+    ) -> (MemoryEntity, Vec<MemoryRelationship>) {
+        let render = |text: &str| {
+            template::render(text, |var| match var {
+                "name" => Some(name),
+                _ => vars.get(var),
+            })
+        };
+        let properties = self
+            .properties
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    MemoryValue::String(text) => MemoryValue::String(render(text)),
+                    other => other.clone(),
+                };
+                (key.clone(), value)
+            })
+            .collect();
+        let entity = MemoryEntity {
+            name: name.to_string(),
+            labels: self.labels.clone(),
+            observations: self.observations.iter().map(|o| render(o)).collect(),
+            properties,
+            ..Default::default()
+        };
+        let relationships = self
+            .relationships
+            .iter()
+            .map(|rel| MemoryRelationship {
+                from: name.to_string(),
+                to: render(&rel.to),
+                name: rel.name.clone(),
+                properties: HashMap::new(),
+            })
+            .collect();
+        (entity, relationships)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instantiate_renders_variables() {
+        let template: EntityTemplate = serde_json::from_value(serde_json::json!({
+            "labels": ["Decision"],
+            "properties": {"status": "proposed", "title": "ADR {{name}}", "number": 1},
+            "relationships": [{"name": "relates_to", "to": "{{project}}:project"}]
+        }))
+        .unwrap();
+        let vars = TemplateVars {
+            project: Some("mm".to_string()),
+            ..Default::default()
+        };
+
+        let (entity, relationships) = template.instantiate("adr:1", &vars);
+        assert_eq!(entity.labels, ["Decision"]);
+        assert_eq!(
+            entity.properties["title"],
+            MemoryValue::String("ADR adr:1".to_string())
+        );
+        assert_eq!(entity.properties["number"], MemoryValue::Integer(1));
+        assert_eq!(relationships[0].from, "adr:1");
+        assert_eq!(relationships[0].to, "mm:project");
+    }
+
+    #[test]
+    fn test_from_entity_reads_template_property() {
+        let mut entity = MemoryEntity {
+            name: "template:adr".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            EntityTemplate::from_entity(&entity),
+            Err(ValidationErrorKind::InvalidTemplate(
+                "template:adr".to_string()
+            ))
+        );
+        entity.properties.insert(
+            TEMPLATE_PROPERTY.to_string(),
+            MemoryValue::String(r#"{"labels": ["Decision"]}"#.to_string()),
+        );
+        assert_eq!(
+            EntityTemplate::from_entity(&entity).unwrap().labels,
+            ["Decision"]
+        );
+    }
+}
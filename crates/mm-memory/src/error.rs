@@ -44,6 +44,22 @@ where
     /// Error when an entity is not found
     #[error("Entity not found: {0}")]
     EntityNotFound(String),
+
+    /// Error when a write is attempted while `read_only` is set
+    #[error("Memory graph is read-only")]
+    ReadOnly,
+
+    /// Error when the trash is used while `soft_delete` is not set
+    #[error("Soft delete is not enabled")]
+    SoftDeleteDisabled,
+
+    /// Error when an entity changed since the revision a write expected
+    #[error("Entity {name} is at revision {actual}, expected {expected}")]
+    RevisionConflict {
+        name: String,
+        expected: u64,
+        actual: u64,
+    },
 }
 
 impl<E> MemoryError<E>
@@ -99,6 +115,39 @@ where
     pub fn entity_not_found<S: Into<String>>(entity_name: S) -> Self {
         Self::EntityNotFound(entity_name.into())
     }
+
+    /// Convert the backend error carried by this error with `f`
+    pub fn map_source<F, T>(self, f: F) -> MemoryError<T>
+    where
+        F: FnOnce(E) -> T,
+        T: StdError + Send + Sync + 'static,
+    {
+        match self {
+            Self::ConnectionError { message, source } => MemoryError::ConnectionError {
+                message,
+                source: source.map(f),
+            },
+            Self::QueryError { message, source } => MemoryError::QueryError {
+                message,
+                source: source.map(f),
+            },
+            Self::RuntimeError { message, source } => MemoryError::RuntimeError { message, source },
+            Self::SerializationError(e) => MemoryError::SerializationError(e),
+            Self::ValidationError(e) => MemoryError::ValidationError(e),
+            Self::EntityNotFound(name) => MemoryError::EntityNotFound(name),
+            Self::ReadOnly => MemoryError::ReadOnly,
+            Self::RevisionConflict {
+                name,
+                expected,
+                actual,
+            } => MemoryError::RevisionConflict {
+                name,
+                expected,
+                actual,
+            },
+            Self::SoftDeleteDisabled => MemoryError::SoftDeleteDisabled,
+        }
+    }
 }
 
 pub type MemoryResult<T, E> = Result<T, MemoryError<E>>;
@@ -0,0 +1,170 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::entity::MemoryEntity;
+use crate::labels::ARCHIVED_LABEL;
+use crate::value::MemoryValue;
+
+/// Entity property recording when it was created
+pub const CREATED_AT_PROPERTY: &str = "created_at";
+
+/// Entity property recording when it was archived after expiring
+pub const ARCHIVED_AT_PROPERTY: &str = "archived_at";
+
+/// What happens to an entity once its time to live has passed
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpiryAction {
+    /// Remove the entity and its relationships
+    #[default]
+    Delete,
+    /// Keep the entity but tag it with [`ARCHIVED_LABEL`]
+    Archive,
+}
+
+/// Time to live written as a number followed by `s`, `m`, `h`, `d` or `w`,
+/// e.g. `90m` or `1d`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Ttl(TimeDelta);
+
+impl Ttl {
+    pub fn duration(&self) -> TimeDelta {
+        self.0
+    }
+}
+
+impl FromStr for Ttl {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split = s.char_indices().last().map_or(0, |(i, _)| i);
+        let (amount, unit) = s.split_at(split);
+        let amount: i64 = amount
+            .parse()
+            .map_err(|_| format!("invalid time to live `{s}`"))?;
+        let duration = match unit {
+            "s" => TimeDelta::try_seconds(amount),
+            "m" => TimeDelta::try_minutes(amount),
+            "h" => TimeDelta::try_hours(amount),
+            "d" => TimeDelta::try_days(amount),
+            "w" => TimeDelta::try_weeks(amount),
+            _ => None,
+        };
+        match duration {
+            Some(d) if d > TimeDelta::zero() => Ok(Ttl(d)),
+            _ => Err(format!("invalid time to live `{s}`")),
+        }
+    }
+}
+
+impl TryFrom<String> for Ttl {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Ttl> for String {
+    fn from(value: Ttl) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for Ttl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.0.num_seconds();
+        let (amount, unit) = [(604_800, "w"), (86_400, "d"), (3_600, "h"), (60, "m")]
+            .into_iter()
+            .find(|(size, _)| secs % size == 0)
+            .map_or((secs, "s"), |(size, unit)| (secs / size, unit));
+        write!(f, "{amount}{unit}")
+    }
+}
+
+/// Expiry rule for entities carrying a label
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ExpiryPolicy {
+    /// How long after creation the entity expires
+    pub ttl: Ttl,
+    /// What to do with expired entities
+    #[serde(default)]
+    pub action: ExpiryAction,
+}
+
+impl ExpiryPolicy {
+    /// Whether `entity` outlived this policy at `now`
+    ///
+    /// Entities without a creation time never expire and archived entities
+    /// are not expired again.
+    pub fn is_expired(&self, entity: &MemoryEntity, now: DateTime<Utc>) -> bool {
+        if entity.labels.iter().any(|l| l == ARCHIVED_LABEL) {
+            return false;
+        }
+        created_at(&entity.properties).is_some_and(|created| created + self.ttl.0 <= now)
+    }
+}
+
+/// Creation time recorded in `properties`, if any
+pub fn created_at(properties: &HashMap<String, MemoryValue>) -> Option<DateTime<Utc>> {
+    timestamp(properties, CREATED_AT_PROPERTY)
+}
+
+/// Point in time stored under `key`, either as a date-time or an RFC 3339
+/// string
+pub fn timestamp(properties: &HashMap<String, MemoryValue>, key: &str) -> Option<DateTime<Utc>> {
+    match properties.get(key)? {
+        MemoryValue::DateTime(dt) => Some(dt.with_timezone(&Utc)),
+        MemoryValue::String(s) => DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ttl_parse_and_display() {
+        let ttl: Ttl = "1d".parse().unwrap();
+        assert_eq!(ttl.duration(), TimeDelta::days(1));
+        assert_eq!(ttl.to_string(), "1d");
+        assert_eq!("90m".parse::<Ttl>().unwrap().to_string(), "90m");
+        assert_eq!("120m".parse::<Ttl>().unwrap().to_string(), "2h");
+        assert!("0h".parse::<Ttl>().is_err());
+        assert!("1y".parse::<Ttl>().is_err());
+        assert!("".parse::<Ttl>().is_err());
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let policy = ExpiryPolicy {
+            ttl: "1d".parse().unwrap(),
+            action: ExpiryAction::Delete,
+        };
+        let now = Utc::now();
+        let mut entity = MemoryEntity {
+            name: "snapshot".to_string(),
+            labels: vec!["GitStatusSnapshot".to_string()],
+            ..Default::default()
+        };
+        assert!(!policy.is_expired(&entity, now));
+
+        entity.properties.insert(
+            CREATED_AT_PROPERTY.to_string(),
+            MemoryValue::DateTime((now - TimeDelta::hours(25)).into()),
+        );
+        assert!(policy.is_expired(&entity, now));
+        assert!(!policy.is_expired(&entity, now - TimeDelta::hours(2)));
+
+        entity.labels.push(ARCHIVED_LABEL.to_string());
+        assert!(!policy.is_expired(&entity, now));
+    }
+}
@@ -0,0 +1,71 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::entity::MemoryEntity;
+use mm_utils::similarity::similarity;
+
+/// Similarity below which an entity name is not considered a match
+pub const MIN_FUZZY_SCORE: f64 = 0.5;
+
+/// Entity found by a fuzzy name lookup
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct FuzzyMatch {
+    pub entity: MemoryEntity,
+    /// Name similarity between 0 and 1; 1 is an exact (case-insensitive) match
+    pub score: f64,
+}
+
+/// Score `entities` against `name` and keep the best `limit` matches
+///
+/// Matches are ordered by descending score, then by name.
+pub fn rank_by_name(entities: Vec<MemoryEntity>, name: &str, limit: usize) -> Vec<FuzzyMatch> {
+    let mut matches: Vec<FuzzyMatch> = entities
+        .into_iter()
+        .map(|entity| FuzzyMatch {
+            score: similarity(&entity.name, name),
+            entity,
+        })
+        .filter(|m| m.score >= MIN_FUZZY_SCORE)
+        .collect();
+    matches.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.entity.name.cmp(&b.entity.name))
+    });
+    matches.truncate(limit);
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(name: &str) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ranks_closest_names_first() {
+        let entities = vec![
+            entity("andoriyu:project:middle_manager"),
+            entity("andoriyu:project:middleware"),
+            entity("someone:task:write_docs"),
+        ];
+        let matches = rank_by_name(entities, "andoriyu:project:midle_manager", 5);
+        let names: Vec<&str> = matches.iter().map(|m| m.entity.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "andoriyu:project:middle_manager",
+                "andoriyu:project:middleware"
+            ]
+        );
+
+        let matches = rank_by_name(vec![entity("a:b"), entity("a:c")], "a:b", 1);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].score, 1.0);
+    }
+}
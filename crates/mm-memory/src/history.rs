@@ -0,0 +1,168 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::entity::MemoryEntity;
+use crate::expiry::timestamp;
+use crate::labels::ENTITY_VERSION_LABEL;
+use crate::relationship::MemoryRelationship;
+use crate::value::MemoryValue;
+
+/// Relationship from an entity to its latest version, and from each version
+/// to the one before it
+pub const PREVIOUS_VERSION_RELATIONSHIP: &str = "previous_version";
+
+/// Version entity property naming the entity it is a version of
+pub const VERSION_OF_PROPERTY: &str = "version_of";
+/// Version entity property holding its number, counting from 1
+pub const VERSION_PROPERTY: &str = "version";
+/// Version entity property recording when the version was replaced
+pub const RECORDED_AT_PROPERTY: &str = "recorded_at";
+/// Version entity property naming the agent that replaced the version
+pub const RECORDED_BY_PROPERTY: &str = "recorded_by";
+/// Version entity property holding the entity's labels
+pub const VERSION_LABELS_PROPERTY: &str = "version_labels";
+
+const META_PROPERTIES: &[&str] = &[
+    VERSION_OF_PROPERTY,
+    VERSION_PROPERTY,
+    RECORDED_AT_PROPERTY,
+    RECORDED_BY_PROPERTY,
+    VERSION_LABELS_PROPERTY,
+];
+
+/// Earlier state of an entity, recorded before an update replaced it
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct EntityVersion {
+    pub version: i64,
+    /// When the update replacing this version happened
+    #[schemars(with = "Option<String>")]
+    pub recorded_at: Option<DateTime<Utc>>,
+    /// Agent that made the update
+    pub recorded_by: Option<String>,
+    pub labels: Vec<String>,
+    pub observations: Vec<String>,
+    pub properties: HashMap<String, MemoryValue>,
+}
+
+/// Name of version `version` of entity `name`
+pub fn version_name(name: &str, version: i64) -> String {
+    format!("{name}@v{version}")
+}
+
+/// Name of the latest recorded version of `entity`, if any
+pub fn latest_version(entity: &MemoryEntity) -> Option<&str> {
+    entity
+        .relationships
+        .iter()
+        .find(|r| r.from == entity.name && r.name == PREVIOUS_VERSION_RELATIONSHIP)
+        .map(|r| r.to.as_str())
+}
+
+/// Entity storing `current` as version `version`, and the relationships
+/// linking it into the history chain behind `previous`
+pub fn version_entity(
+    current: &MemoryEntity,
+    version: i64,
+    previous: Option<&str>,
+    now: DateTime<Utc>,
+    agent: &str,
+) -> (MemoryEntity, Vec<MemoryRelationship>) {
+    let name = version_name(&current.name, version);
+    let mut properties = current.properties.clone();
+    properties.extend([
+        (
+            VERSION_OF_PROPERTY.to_string(),
+            MemoryValue::String(current.name.clone()),
+        ),
+        (VERSION_PROPERTY.to_string(), MemoryValue::Integer(version)),
+        (
+            RECORDED_AT_PROPERTY.to_string(),
+            MemoryValue::DateTime(now.into()),
+        ),
+        (
+            RECORDED_BY_PROPERTY.to_string(),
+            MemoryValue::String(agent.to_string()),
+        ),
+        (
+            VERSION_LABELS_PROPERTY.to_string(),
+            MemoryValue::List(current.labels.clone()),
+        ),
+    ]);
+    let link = |from: &str, to: &str| MemoryRelationship {
+        from: from.to_string(),
+        to: to.to_string(),
+        name: PREVIOUS_VERSION_RELATIONSHIP.to_string(),
+        properties: HashMap::new(),
+    };
+    let mut relationships = vec![link(&current.name, &name)];
+    if let Some(previous) = previous {
+        relationships.push(link(&name, previous));
+    }
+    let entity = MemoryEntity {
+        name,
+        labels: vec![ENTITY_VERSION_LABEL.to_string()],
+        observations: current.observations.clone(),
+        properties,
+        relationships: Vec::new(),
+        revision: 0,
+    };
+    (entity, relationships)
+}
+
+impl EntityVersion {
+    /// Read a version back from its entity
+    pub fn from_entity(entity: &MemoryEntity) -> Self {
+        let mut properties = entity.properties.clone();
+        properties.retain(|k, _| !META_PROPERTIES.contains(&k.as_str()));
+        Self {
+            version: match entity.properties.get(VERSION_PROPERTY) {
+                Some(MemoryValue::Integer(v)) => *v,
+                _ => 0,
+            },
+            recorded_at: timestamp(&entity.properties, RECORDED_AT_PROPERTY),
+            recorded_by: match entity.properties.get(RECORDED_BY_PROPERTY) {
+                Some(MemoryValue::String(agent)) => Some(agent.clone()),
+                _ => None,
+            },
+            labels: match entity.properties.get(VERSION_LABELS_PROPERTY) {
+                Some(MemoryValue::List(labels)) => labels.clone(),
+                _ => Vec::new(),
+            },
+            observations: entity.observations.clone(),
+            properties,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_round_trips() {
+        let current = MemoryEntity {
+            name: "a:task:one".to_string(),
+            labels: vec!["Task".to_string()],
+            observations: vec!["started".to_string()],
+            properties: HashMap::from([(
+                "status".to_string(),
+                MemoryValue::String("todo".to_string()),
+            )]),
+            ..Default::default()
+        };
+        let now = Utc::now();
+        let (entity, relationships) =
+            version_entity(&current, 2, Some("a:task:one@v1"), now, "tester");
+        assert_eq!(entity.name, "a:task:one@v2");
+        assert_eq!(relationships.len(), 2);
+        assert_eq!(relationships[1].to, "a:task:one@v1");
+
+        let version = EntityVersion::from_entity(&entity);
+        assert_eq!(version.version, 2);
+        assert_eq!(version.recorded_by.as_deref(), Some("tester"));
+        assert_eq!(version.labels, current.labels);
+        assert_eq!(version.properties, current.properties);
+    }
+}
@@ -58,3 +58,9 @@ pub const CONFIGURATION_LABEL: &str = "Configuration";
 pub const MAINTENANCE_LABEL: &str = "Maintenance";
 pub const LABEL_LABEL: &str = "Label";
 pub const LANGUAGE_LABEL: &str = "Language";
+pub const FORGET_PROPOSAL_LABEL: &str = "ForgetProposal";
+pub const ARCHIVED_LABEL: &str = "Archived";
+pub const DELETED_LABEL: &str = "Deleted";
+pub const ENTITY_VERSION_LABEL: &str = "EntityVersion";
+pub const DECISION_LABEL: &str = "Decision";
+pub const ENTITY_TEMPLATE_LABEL: &str = "EntityTemplate";
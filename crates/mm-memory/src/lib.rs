@@ -1,33 +1,71 @@
 #![warn(clippy::all)]
+pub mod audit;
+pub mod confidence;
 pub mod config;
+pub mod date;
 pub mod entity;
+pub mod entity_template;
 pub mod error;
+pub mod expiry;
+pub mod fuzzy;
+pub mod history;
 pub mod label_match_mode;
 pub mod labels;
+pub mod merge;
+pub mod naming;
+pub mod observation_meta;
+pub mod observation_record;
+pub mod property_filter;
+pub mod property_rules;
+pub mod redaction;
 pub mod relationship;
 pub mod relationship_direction;
 pub mod repository;
 pub mod service;
+pub mod source;
+pub mod task_filter;
+pub mod trash;
+pub mod typed;
 pub mod update;
 pub mod validation_error;
 pub mod value;
 
+pub use audit::{AuditEntry, AuditQuery, AuditSink};
 pub use config::{DEFAULT_LABELS, DEFAULT_RELATIONSHIPS};
-pub use config::{DEFAULT_MEMORY_LABEL, MemoryConfig};
+pub use config::{
+    DEFAULT_MEMORY_LABEL, MemoryConfig, ProjectVocabulary, RelationshipRule, RetryConfig, WipLimits,
+};
 pub use entity::MemoryEntity;
+pub use entity_template::{EntityTemplate, TemplateRelationship};
 pub use error::{MemoryError, MemoryResult};
+pub use expiry::{ExpiryAction, ExpiryPolicy, Ttl};
+pub use fuzzy::FuzzyMatch;
+pub use history::EntityVersion;
 pub use label_match_mode::LabelMatchMode;
 pub use labels::*;
+pub use naming::NamingPolicy;
+pub use observation_record::{ObservationAuthor, ObservationRecord};
+pub use property_filter::{PropertyFilter, PropertyOperator, PropertySort, SortDirection};
+pub use property_rules::{PropertyRule, PropertyType};
+pub use redaction::{RedactionRule, Redactor};
 pub use relationship::MemoryRelationship;
 pub use relationship_direction::RelationshipDirection;
-pub use repository::MemoryRepository;
 #[cfg(any(test, feature = "mock"))]
 pub use repository::MockMemoryRepository;
-pub use service::MemoryService;
+pub use repository::{
+    BackendInfo, EntitySnapshot, EntityStream, MemoryRepository, ProjectSummary, RelatedQuery,
+};
+pub use service::{GraphPreview, MemoryService};
+pub use source::ObservationSource;
+pub use task_filter::TaskFilter;
+pub use typed::{EntityKind, TypedMemoryService};
 pub use update::{
-    EntityUpdate, LabelsUpdate, ObservationsUpdate, PropertiesUpdate, RelationshipUpdate,
+    EntityUpdate, LabelsUpdate, NamedEntityUpdate, ObservationsUpdate, PropertiesUpdate,
+    RelationshipUpdate,
+};
+pub use validation_error::{
+    BatchItemError, GraphBatchErrors, ValidationError, ValidationErrorKind,
 };
-pub use validation_error::{ValidationError, ValidationErrorKind};
 pub use value::MemoryValue;
 
 #[cfg(test)]
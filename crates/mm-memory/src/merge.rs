@@ -0,0 +1,312 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::entity::MemoryEntity;
+use crate::observation_meta::set_observation_meta;
+use crate::relationship::MemoryRelationship;
+use crate::value::MemoryValue;
+
+/// Entity property recording how merge conflicts were resolved
+pub const MERGE_RESOLUTIONS_PROPERTY: &str = "merge_resolutions";
+
+/// A property holding different values on entities being merged
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct PropertyConflict {
+    pub property: String,
+    /// Distinct values in merge order; the first one is kept unless the
+    /// conflict is resolved otherwise
+    pub values: Vec<ConflictValue>,
+}
+
+/// One side of a [`PropertyConflict`]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ConflictValue {
+    /// Where the value comes from, e.g. the entity holding it
+    pub from: String,
+    pub value: MemoryValue,
+}
+
+/// Provenance of a resolved conflict, stored per property under
+/// [`MERGE_RESOLUTIONS_PROPERTY`]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct MergeResolution {
+    pub value: MemoryValue,
+    /// Values that were discarded
+    pub rejected: Vec<MemoryValue>,
+    pub resolved_at: DateTime<Utc>,
+}
+
+fn extend_unique(items: &mut Vec<String>, more: Vec<String>) {
+    for item in more {
+        if !items.contains(&item) {
+            items.push(item);
+        }
+    }
+}
+
+/// Fold duplicate `sources` into `target`
+///
+/// Observations, labels and list properties are combined in order without
+/// repeats; for other properties the target's value wins, then the first
+/// source's. Relationships of the sources are moved to the target, except
+/// ones that would repeat an existing relationship or only exist between the
+/// target and a source. The result lists every relationship the merged
+/// entity should have.
+pub fn merge_into(mut target: MemoryEntity, sources: Vec<MemoryEntity>) -> MemoryEntity {
+    let merged: HashSet<String> = sources.iter().map(|s| s.name.clone()).collect();
+    let mut relationships = std::mem::take(&mut target.relationships);
+
+    for source in sources {
+        extend_unique(&mut target.observations, source.observations);
+        extend_unique(&mut target.labels, source.labels);
+        for (key, value) in source.properties {
+            match (target.properties.get_mut(&key), value) {
+                (Some(MemoryValue::List(items)), MemoryValue::List(more)) => {
+                    extend_unique(items, more)
+                }
+                (Some(_), _) => {}
+                (None, value) => {
+                    target.properties.insert(key, value);
+                }
+            }
+        }
+        relationships.extend(source.relationships);
+    }
+
+    let rename = |name: String| {
+        if merged.contains(&name) {
+            target.name.clone()
+        } else {
+            name
+        }
+    };
+    let mut seen = HashSet::new();
+    target.relationships = relationships
+        .into_iter()
+        .filter_map(|rel| {
+            let self_loop = rel.from == rel.to;
+            let rel = MemoryRelationship {
+                from: rename(rel.from),
+                to: rename(rel.to),
+                ..rel
+            };
+            let collapsed = rel.from == rel.to && !self_loop;
+            let key = (rel.from.clone(), rel.name.clone(), rel.to.clone());
+            (!collapsed && seen.insert(key)).then_some(rel)
+        })
+        .collect();
+    target
+}
+
+/// Properties whose values differ between `candidates`, given in merge
+/// order as the property maps of named entities
+///
+/// List values are combined on merge and never conflict with each other.
+pub fn property_conflicts(
+    candidates: &[(&str, &HashMap<String, MemoryValue>)],
+) -> Vec<PropertyConflict> {
+    let keys: BTreeSet<&String> = candidates.iter().flat_map(|(_, p)| p.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let present: Vec<(&str, &MemoryValue)> = candidates
+                .iter()
+                .filter_map(|(from, p)| p.get(key).map(|v| (*from, v)))
+                .collect();
+            if present
+                .iter()
+                .all(|(_, v)| matches!(v, MemoryValue::List(_)))
+            {
+                return None;
+            }
+            let mut values: Vec<ConflictValue> = Vec::new();
+            for (from, value) in present {
+                if !values.iter().any(|v| &v.value == value) {
+                    values.push(ConflictValue {
+                        from: from.to_string(),
+                        value: value.clone(),
+                    });
+                }
+            }
+            (values.len() > 1).then(|| PropertyConflict {
+                property: key.clone(),
+                values,
+            })
+        })
+        .collect()
+}
+
+/// Set the properties of `merged` named in `resolutions` to the chosen
+/// values and record each resolution
+///
+/// Resolutions for properties without a conflict are ignored.
+pub fn apply_resolutions(
+    merged: &mut MemoryEntity,
+    conflicts: &[PropertyConflict],
+    resolutions: &HashMap<String, MemoryValue>,
+    resolved_at: DateTime<Utc>,
+) {
+    for conflict in conflicts {
+        let Some(value) = resolutions.get(&conflict.property) else {
+            continue;
+        };
+        let rejected = conflict
+            .values
+            .iter()
+            .filter(|v| &v.value != value)
+            .map(|v| v.value.clone())
+            .collect();
+        merged
+            .properties
+            .insert(conflict.property.clone(), value.clone());
+        set_observation_meta(
+            &mut merged.properties,
+            MERGE_RESOLUTIONS_PROPERTY,
+            &conflict.property,
+            MergeResolution {
+                value: value.clone(),
+                rejected,
+                resolved_at,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn rel(from: &str, name: &str, to: &str) -> MemoryRelationship {
+        MemoryRelationship {
+            from: from.to_string(),
+            to: to.to_string(),
+            name: name.to_string(),
+            properties: HashMap::new(),
+        }
+    }
+
+    fn entity(
+        name: &str,
+        labels: &[&str],
+        observations: &[&str],
+        properties: &[(&str, MemoryValue)],
+        relationships: Vec<MemoryRelationship>,
+    ) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            observations: observations.iter().map(|o| o.to_string()).collect(),
+            properties: properties
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect(),
+            relationships,
+            revision: 0,
+        }
+    }
+
+    #[test]
+    fn combines_content_and_moves_relationships() {
+        let target = entity(
+            "a:project:mm",
+            &["Project"],
+            &["uses rust"],
+            &[
+                ("status", MemoryValue::String("active".into())),
+                ("sources", MemoryValue::List(vec!["url:a".into()])),
+            ],
+            vec![
+                rel("a:project:mm", "relates_to", "a:project:middle"),
+                rel("a:project:mm", "contains", "a:task:one"),
+            ],
+        );
+        let source = entity(
+            "a:project:middle",
+            &["Project", "Memory"],
+            &["uses rust", "has a cli"],
+            &[
+                ("status", MemoryValue::String("archived".into())),
+                ("owner", MemoryValue::String("andoriyu".into())),
+                ("sources", MemoryValue::List(vec!["url:b".into()])),
+            ],
+            vec![
+                rel("a:project:mm", "relates_to", "a:project:middle"),
+                rel("a:project:middle", "contains", "a:task:one"),
+                rel("a:task:two", "depends_on", "a:project:middle"),
+            ],
+        );
+
+        let merged = merge_into(target, vec![source]);
+        assert_eq!(merged.labels, ["Project", "Memory"]);
+        assert_eq!(merged.observations, ["uses rust", "has a cli"]);
+        assert_eq!(
+            merged.properties["status"],
+            MemoryValue::String("active".into())
+        );
+        assert_eq!(
+            merged.properties["owner"],
+            MemoryValue::String("andoriyu".into())
+        );
+        assert_eq!(
+            merged.properties["sources"],
+            MemoryValue::List(vec!["url:a".into(), "url:b".into()])
+        );
+        assert_eq!(
+            merged.relationships,
+            [
+                rel("a:project:mm", "contains", "a:task:one"),
+                rel("a:task:two", "depends_on", "a:project:mm"),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_conflicting_properties() {
+        let target = entity(
+            "a",
+            &[],
+            &[],
+            &[
+                ("status", MemoryValue::String("active".into())),
+                ("tags", MemoryValue::List(vec!["x".into()])),
+            ],
+            vec![],
+        );
+        let source = entity(
+            "b",
+            &[],
+            &[],
+            &[
+                ("status", MemoryValue::String("archived".into())),
+                ("tags", MemoryValue::List(vec!["y".into()])),
+                ("owner", MemoryValue::String("andoriyu".into())),
+            ],
+            vec![],
+        );
+        let conflicts = property_conflicts(&[("a", &target.properties), ("b", &source.properties)]);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].property, "status");
+        assert_eq!(conflicts[0].values[1].from, "b");
+
+        let mut merged = merge_into(target, vec![source]);
+        let resolutions =
+            HashMap::from([("status".to_string(), MemoryValue::String("archived".into()))]);
+        let now = Utc::now();
+        apply_resolutions(&mut merged, &conflicts, &resolutions, now);
+        assert_eq!(
+            merged.properties["status"],
+            MemoryValue::String("archived".into())
+        );
+        let recorded: Vec<(String, MergeResolution)> = crate::observation_meta::observation_meta(
+            &merged.properties,
+            MERGE_RESOLUTIONS_PROPERTY,
+        );
+        assert_eq!(recorded[0].0, "status");
+        assert_eq!(
+            recorded[0].1.rejected,
+            [MemoryValue::String("active".into())]
+        );
+    }
+}
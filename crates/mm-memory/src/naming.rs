@@ -0,0 +1,142 @@
+//! Format of entity names.
+//!
+//! [`MemoryConfig::naming`](crate::MemoryConfig::naming) describes the names
+//! teams use for their graph nodes, either as a regular expression or as
+//! `:`-separated segments such as `scope:type:slug`. New entities whose name
+//! does not follow it are rejected with a hint on the expected format.
+
+use mm_utils::slugify;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::validation_error::ValidationErrorKind;
+
+/// Segment name that must hold a slug, as produced by `slugify_name`
+pub const SLUG_SEGMENT: &str = "slug";
+
+/// Format entity names must follow; every name is accepted when unset
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct NamingPolicy {
+    /// Regular expression names must match as a whole
+    #[serde(default)]
+    pub pattern: Option<String>,
+
+    /// `:`-separated segments names are made of, e.g. `scope:type:slug`;
+    /// every segment must be non-empty and a `slug` segment must be a slug
+    #[serde(default)]
+    pub segments: Option<String>,
+
+    /// Shown with rejected names instead of the expected format
+    #[serde(default)]
+    pub hint: Option<String>,
+}
+
+/// Compiled [`NamingPolicy`]
+#[derive(Debug, Clone)]
+pub struct NameChecker<'a> {
+    policy: &'a NamingPolicy,
+    pattern: Option<Regex>,
+}
+
+impl NamingPolicy {
+    /// Compile the policy, failing when `pattern` is not a valid regex
+    pub fn compile(&self) -> Result<NameChecker<'_>, ValidationErrorKind> {
+        let pattern = self
+            .pattern
+            .as_deref()
+            .map(|p| Regex::new(&format!("^(?:{p})$")))
+            .transpose()
+            .map_err(|_| ValidationErrorKind::InvalidPattern("naming".to_string()))?;
+        Ok(NameChecker {
+            policy: self,
+            pattern,
+        })
+    }
+
+    /// What names are expected to look like
+    fn hint(&self) -> String {
+        if let Some(hint) = &self.hint {
+            return hint.clone();
+        }
+        match (&self.segments, &self.pattern) {
+            (Some(segments), _) => format!("expected `{segments}`"),
+            (None, Some(pattern)) => format!("expected a name matching `{pattern}`"),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+impl NameChecker<'_> {
+    /// Check `name` against the policy
+    pub fn check(&self, name: &str) -> Result<(), ValidationErrorKind> {
+        let matches_pattern = self.pattern.as_ref().is_none_or(|p| p.is_match(name));
+        let matches_segments = self
+            .policy
+            .segments
+            .as_deref()
+            .is_none_or(|segments| matches_segments(name, segments));
+        if matches_pattern && matches_segments {
+            Ok(())
+        } else {
+            Err(ValidationErrorKind::InvalidEntityName {
+                name: name.to_string(),
+                hint: self.policy.hint(),
+            })
+        }
+    }
+}
+
+/// Whether `name` has one non-empty part per segment of `segments`
+fn matches_segments(name: &str, segments: &str) -> bool {
+    let parts: Vec<&str> = name.split(':').collect();
+    let expected: Vec<&str> = segments.split(':').collect();
+    parts.len() == expected.len()
+        && parts.iter().zip(&expected).all(|(part, segment)| {
+            !part.is_empty() && (*segment != SLUG_SEGMENT || slugify(part) == *part)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segments() {
+        let policy = NamingPolicy {
+            segments: Some("scope:type:slug".to_string()),
+            ..Default::default()
+        };
+        let checker = policy.compile().unwrap();
+        assert!(checker.check("mm:task:fix_build").is_ok());
+        assert!(checker.check("mm:task").is_err());
+        assert!(checker.check("mm::fix_build").is_err());
+        assert_eq!(
+            checker.check("mm:task:Fix Build"),
+            Err(ValidationErrorKind::InvalidEntityName {
+                name: "mm:task:Fix Build".to_string(),
+                hint: "expected `scope:type:slug`".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_pattern() {
+        let policy = NamingPolicy {
+            pattern: Some("[a-z]+:[a-z_]+".to_string()),
+            hint: Some("use kind:name".to_string()),
+            ..Default::default()
+        };
+        let checker = policy.compile().unwrap();
+        assert!(checker.check("tech:rust").is_ok());
+        assert!(matches!(
+            checker.check("tech:rust:extra"),
+            Err(ValidationErrorKind::InvalidEntityName { hint, .. }) if hint == "use kind:name"
+        ));
+        assert!(NamingPolicy::default().compile().unwrap().check("").is_ok());
+        let invalid = NamingPolicy {
+            pattern: Some("(".to_string()),
+            ..Default::default()
+        };
+        assert!(invalid.compile().is_err());
+    }
+}
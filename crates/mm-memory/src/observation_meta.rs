@@ -0,0 +1,87 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::value::MemoryValue;
+
+/// Metadata attached to a single observation
+///
+/// Observations are plain strings and graph stores only accept primitive
+/// property values or lists of them, so metadata lives in a list property
+/// on the entity with one JSON-encoded entry per observation.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+struct Entry<T> {
+    observation: String,
+    value: T,
+}
+
+/// All metadata entries stored under `key`, skipping malformed ones
+pub fn observation_meta<T: DeserializeOwned>(
+    properties: &HashMap<String, MemoryValue>,
+    key: &str,
+) -> Vec<(String, T)> {
+    match properties.get(key) {
+        Some(MemoryValue::List(items)) => items
+            .iter()
+            .filter_map(|item| serde_json::from_str::<Entry<T>>(item).ok())
+            .map(|e| (e.observation, e.value))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Metadata stored under `key` for `observation`
+pub fn get_observation_meta<T: DeserializeOwned>(
+    properties: &HashMap<String, MemoryValue>,
+    key: &str,
+    observation: &str,
+) -> Option<T> {
+    observation_meta(properties, key)
+        .into_iter()
+        .find(|(o, _)| o == observation)
+        .map(|(_, v)| v)
+}
+
+/// Record metadata under `key` for `observation`, replacing any previous entry
+pub fn set_observation_meta<T: Serialize>(
+    properties: &mut HashMap<String, MemoryValue>,
+    key: &str,
+    observation: &str,
+    value: T,
+) {
+    let mut entries: Vec<String> = match properties.remove(key) {
+        Some(MemoryValue::List(items)) => items
+            .into_iter()
+            .filter(|item| {
+                serde_json::from_str::<Entry<serde_json::Value>>(item)
+                    .is_ok_and(|e| e.observation != observation)
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let entry = Entry {
+        observation: observation.to_string(),
+        value,
+    };
+    if let Ok(encoded) = serde_json::to_string(&entry) {
+        entries.push(encoded);
+    }
+    properties.insert(key.to_string(), MemoryValue::List(entries));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_replaces_previous_entry() {
+        let mut props = HashMap::new();
+        set_observation_meta(&mut props, "meta", "a", 1);
+        set_observation_meta(&mut props, "meta", "b", 2);
+        set_observation_meta(&mut props, "meta", "a", 3);
+
+        assert_eq!(get_observation_meta::<i32>(&props, "meta", "a"), Some(3));
+        assert_eq!(observation_meta::<i32>(&props, "meta").len(), 2);
+        assert!(matches!(props.get("meta"), Some(MemoryValue::List(_))));
+    }
+}
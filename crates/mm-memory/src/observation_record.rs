@@ -0,0 +1,260 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::entity::MemoryEntity;
+use crate::observation_meta::{observation_meta, set_observation_meta};
+use crate::update::ObservationsUpdate;
+use crate::value::MemoryValue;
+
+/// Entity property holding who wrote each observation and when
+pub const OBSERVATION_AUTHORS_PROPERTY: &str = "observation_authors";
+
+/// When and by which agent an observation was written
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, JsonSchema)]
+pub struct ObservationAuthor {
+    #[schemars(with = "String")]
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+}
+
+/// An observation with its metadata
+///
+/// Deserializes from a bare string as well, as observations were written
+/// before they carried metadata.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, JsonSchema)]
+#[serde(from = "RecordRepr")]
+pub struct ObservationRecord {
+    pub text: String,
+    /// When the observation was written, if recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<String>")]
+    pub created_at: Option<DateTime<Utc>>,
+    /// Agent that wrote the observation, if recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum RecordRepr {
+    Text(String),
+    Record {
+        text: String,
+        #[serde(default)]
+        #[schemars(with = "Option<String>")]
+        created_at: Option<DateTime<Utc>>,
+        #[serde(default)]
+        agent: Option<String>,
+    },
+}
+
+impl From<RecordRepr> for ObservationRecord {
+    fn from(repr: RecordRepr) -> Self {
+        match repr {
+            RecordRepr::Text(text) => text.into(),
+            RecordRepr::Record {
+                text,
+                created_at,
+                agent,
+            } => Self {
+                text,
+                created_at,
+                agent,
+            },
+        }
+    }
+}
+
+impl From<String> for ObservationRecord {
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            created_at: None,
+            agent: None,
+        }
+    }
+}
+
+/// Authors recorded in `properties`, by observation
+pub fn observation_authors(
+    properties: &HashMap<String, MemoryValue>,
+) -> HashMap<String, ObservationAuthor> {
+    observation_meta(properties, OBSERVATION_AUTHORS_PROPERTY)
+        .into_iter()
+        .collect()
+}
+
+/// Properties holding the authors recorded in `properties` for the
+/// observations `keep` accepts
+pub fn kept_authors(
+    properties: &HashMap<String, MemoryValue>,
+    keep: impl Fn(&str) -> bool,
+) -> HashMap<String, MemoryValue> {
+    let mut kept = HashMap::new();
+    for (observation, author) in
+        observation_meta::<ObservationAuthor>(properties, OBSERVATION_AUTHORS_PROPERTY)
+    {
+        if keep(&observation) {
+            set_observation_meta(
+                &mut kept,
+                OBSERVATION_AUTHORS_PROPERTY,
+                &observation,
+                author,
+            );
+        }
+    }
+    kept
+}
+
+/// Record `author` for each of `observations` that has no author yet
+pub fn stamp_observations(
+    properties: &mut HashMap<String, MemoryValue>,
+    observations: &[String],
+    author: &ObservationAuthor,
+) {
+    let known = observation_authors(properties);
+    for observation in observations {
+        if !known.contains_key(observation) {
+            set_observation_meta(
+                properties,
+                OBSERVATION_AUTHORS_PROPERTY,
+                observation,
+                author,
+            );
+        }
+    }
+}
+
+/// [`OBSERVATION_AUTHORS_PROPERTY`] of `entity` once `update` is applied
+///
+/// Observations still present keep their author and those the update writes
+/// without one get `author`. Entries of observations the update drops are
+/// pruned, so a text written again later is stamped afresh. `None` means no
+/// observation is left with an author.
+pub fn authors_after(
+    entity: &MemoryEntity,
+    update: Option<&ObservationsUpdate>,
+    author: &ObservationAuthor,
+) -> Option<MemoryValue> {
+    let mut observations = entity.observations.clone();
+    let mut written: &[String] = &[];
+    if let Some(update) = update {
+        update.apply_to(&mut observations);
+        written = update
+            .set
+            .as_deref()
+            .or(update.add.as_deref())
+            .unwrap_or_default();
+    }
+    let mut properties = kept_authors(&entity.properties, |observation| {
+        observations.iter().any(|o| o == observation)
+    });
+    stamp_observations(&mut properties, written, author);
+    properties.remove(OBSERVATION_AUTHORS_PROPERTY)
+}
+
+/// Observations of `entity` with their recorded authors, in entity order
+pub fn observation_records(entity: &MemoryEntity) -> Vec<ObservationRecord> {
+    let mut authors = observation_authors(&entity.properties);
+    entity
+        .observations
+        .iter()
+        .map(|text| match authors.remove(text) {
+            Some(author) => ObservationRecord {
+                text: text.clone(),
+                created_at: Some(author.created_at),
+                agent: author.agent,
+            },
+            None => text.clone().into(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stamp_keeps_first_author() {
+        let first = ObservationAuthor {
+            created_at: "2025-01-01T00:00:00Z".parse().unwrap(),
+            agent: Some("a".to_string()),
+        };
+        let second = ObservationAuthor {
+            created_at: "2025-02-01T00:00:00Z".parse().unwrap(),
+            agent: Some("b".to_string()),
+        };
+        let mut entity = MemoryEntity {
+            observations: vec!["x".to_string(), "y".to_string(), "z".to_string()],
+            ..Default::default()
+        };
+        stamp_observations(&mut entity.properties, &["x".to_string()], &first);
+        stamp_observations(
+            &mut entity.properties,
+            &["x".to_string(), "y".to_string()],
+            &second,
+        );
+
+        let records = observation_records(&entity);
+        assert_eq!(records[0].agent.as_deref(), Some("a"));
+        assert_eq!(records[1].created_at, Some(second.created_at));
+        assert_eq!(records[2], ObservationRecord::from("z".to_string()));
+    }
+
+    #[test]
+    fn test_authors_after_prunes_dropped_observations() {
+        let first = ObservationAuthor {
+            created_at: "2025-01-01T00:00:00Z".parse().unwrap(),
+            agent: Some("a".to_string()),
+        };
+        let second = ObservationAuthor {
+            created_at: "2025-02-01T00:00:00Z".parse().unwrap(),
+            agent: Some("b".to_string()),
+        };
+        let mut entity = MemoryEntity {
+            observations: vec!["x".to_string(), "y".to_string()],
+            ..Default::default()
+        };
+        stamp_observations(&mut entity.properties, &entity.observations, &first);
+        let update = |add: Option<&str>, remove: Option<&str>, set: Option<&[&str]>| {
+            let list = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+            ObservationsUpdate {
+                add: add.map(|o| list(&[o])),
+                remove: remove.map(|o| list(&[o])),
+                set: set.map(list),
+            }
+        };
+        let authors_of = |update: &ObservationsUpdate| {
+            let mut properties = HashMap::new();
+            if let Some(authors) = authors_after(&entity, Some(update), &second) {
+                properties.insert(OBSERVATION_AUTHORS_PROPERTY.to_string(), authors);
+            }
+            observation_authors(&properties)
+        };
+
+        let added = authors_of(&update(Some("z"), None, None));
+        assert_eq!(added["x"], first);
+        assert_eq!(added["z"], second);
+        let removed = authors_of(&update(None, Some("x"), None));
+        assert!(!removed.contains_key("x"));
+        assert_eq!(removed["y"], first);
+        let replaced = authors_of(&update(None, None, Some(&["y", "x2"])));
+        assert_eq!(replaced.len(), 2);
+        assert_eq!(replaced["y"], first);
+        assert_eq!(replaced["x2"], second);
+        assert!(authors_after(&entity, Some(&update(None, None, Some(&[]))), &second).is_none());
+    }
+
+    #[test]
+    fn test_records_deserialize_from_strings() {
+        let records: Vec<ObservationRecord> = serde_json::from_str(
+            r#"["plain", {"text": "rich", "agent": "a", "created_at": "2025-01-01T00:00:00Z"}]"#,
+        )
+        .unwrap();
+        assert_eq!(records[0], ObservationRecord::from("plain".to_string()));
+        assert_eq!(records[1].agent.as_deref(), Some("a"));
+    }
+}
@@ -0,0 +1,262 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::entity::MemoryEntity;
+use crate::value::MemoryValue;
+
+/// Comparison applied by a [`PropertyFilter`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PropertyOperator {
+    /// Property equals the value
+    #[default]
+    Equals,
+    /// String property contains the value, or list property has it as an element
+    Contains,
+    /// Property is greater than the value
+    GreaterThan,
+    /// Property is less than the value
+    LessThan,
+}
+
+/// Predicate on a single entity property that repositories can push down into
+/// their queries
+///
+/// Entities without the property, or whose property cannot be compared with
+/// the value, never match.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct PropertyFilter {
+    /// Property key, e.g. `status`
+    pub property: String,
+    /// Comparison, `equals` unless set
+    #[serde(default)]
+    pub op: PropertyOperator,
+    /// Value compared against
+    pub value: MemoryValue,
+}
+
+impl PropertyFilter {
+    /// Filter matching entities whose `property` equals `value`
+    pub fn equals(property: impl Into<String>, value: MemoryValue) -> Self {
+        Self {
+            property: property.into(),
+            op: PropertyOperator::Equals,
+            value,
+        }
+    }
+
+    /// Check whether already loaded properties satisfy the filter
+    pub fn matches(&self, properties: &HashMap<String, MemoryValue>) -> bool {
+        let Some(actual) = properties.get(&self.property) else {
+            return false;
+        };
+        match self.op {
+            PropertyOperator::Equals => {
+                actual == &self.value || compare(actual, &self.value) == Some(Ordering::Equal)
+            }
+            PropertyOperator::Contains => match (actual, &self.value) {
+                (MemoryValue::String(s), MemoryValue::String(v)) => s.contains(v.as_str()),
+                (MemoryValue::List(items), MemoryValue::String(v)) => items.contains(v),
+                _ => false,
+            },
+            PropertyOperator::GreaterThan => {
+                compare(actual, &self.value) == Some(Ordering::Greater)
+            }
+            PropertyOperator::LessThan => compare(actual, &self.value) == Some(Ordering::Less),
+        }
+    }
+}
+
+/// Order two values of comparable types; integers and floats compare numerically
+fn compare(a: &MemoryValue, b: &MemoryValue) -> Option<Ordering> {
+    use MemoryValue::*;
+    match (a, b) {
+        (Integer(a), Integer(b)) => Some(a.cmp(b)),
+        (Integer(a), Float(b)) => (*a as f64).partial_cmp(b),
+        (Float(a), Integer(b)) => a.partial_cmp(&(*b as f64)),
+        (Float(a), Float(b)) => a.partial_cmp(b),
+        (String(a), String(b)) => Some(a.cmp(b)),
+        (Date(a), Date(b)) => Some(a.cmp(b)),
+        (Time(a), Time(b)) => Some(a.cmp(b)),
+        (DateTime(a), DateTime(b)) => Some(a.cmp(b)),
+        (LocalDateTime(a), LocalDateTime(b)) => Some(a.cmp(b)),
+        // Datetimes without an offset are taken to be in UTC
+        (DateTime(a), LocalDateTime(b)) => Some(a.naive_utc().cmp(b)),
+        (LocalDateTime(a), DateTime(b)) => Some(a.cmp(&b.naive_utc())),
+        (Duration(a), Duration(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+/// Check whether `properties` satisfy every filter
+pub fn matches_all(filters: &[PropertyFilter], properties: &HashMap<String, MemoryValue>) -> bool {
+    filters.iter().all(|filter| filter.matches(properties))
+}
+
+/// Order of a [`PropertySort`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    /// Smallest value first
+    #[default]
+    Asc,
+    /// Largest value first
+    Desc,
+}
+
+/// Sort key on a single entity property that repositories can push down into
+/// their queries
+///
+/// Entities without the property come last in either direction.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct PropertySort {
+    /// Property key, e.g. `adopted_at`
+    pub property: String,
+    /// `asc` unless set
+    #[serde(default)]
+    pub direction: SortDirection,
+}
+
+impl PropertySort {
+    /// Order two sets of already loaded properties by this key
+    pub fn compare(
+        &self,
+        a: &HashMap<String, MemoryValue>,
+        b: &HashMap<String, MemoryValue>,
+    ) -> Ordering {
+        match (a.get(&self.property), b.get(&self.property)) {
+            (Some(a), Some(b)) => {
+                let ordering = compare(a, b).unwrap_or(Ordering::Equal);
+                match self.direction {
+                    SortDirection::Asc => ordering,
+                    SortDirection::Desc => ordering.reverse(),
+                }
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+/// Sort already loaded entities by `sorts`, then by name
+pub fn sort_entities(entities: &mut [MemoryEntity], sorts: &[PropertySort]) {
+    entities.sort_by(|a, b| {
+        sorts
+            .iter()
+            .map(|sort| sort.compare(&a.properties, &b.properties))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| a.name.cmp(&b.name))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn properties() -> HashMap<String, MemoryValue> {
+        HashMap::from([
+            (
+                "status".to_string(),
+                MemoryValue::String("in_progress".into()),
+            ),
+            ("estimate".to_string(), MemoryValue::Integer(3)),
+            (
+                "tags".to_string(),
+                MemoryValue::List(vec!["backend".into(), "urgent".into()]),
+            ),
+        ])
+    }
+
+    fn filter(property: &str, op: PropertyOperator, value: MemoryValue) -> PropertyFilter {
+        PropertyFilter {
+            property: property.to_string(),
+            op,
+            value,
+        }
+    }
+
+    #[test]
+    fn equals_and_contains() {
+        let props = properties();
+        assert!(
+            PropertyFilter::equals("status", MemoryValue::String("in_progress".into()))
+                .matches(&props)
+        );
+        assert!(
+            !PropertyFilter::equals("status", MemoryValue::String("done".into())).matches(&props)
+        );
+        assert!(PropertyFilter::equals("estimate", MemoryValue::Float(3.0)).matches(&props));
+        assert!(!PropertyFilter::equals("missing", MemoryValue::Integer(3)).matches(&props));
+
+        let contains = |property, value: &str| {
+            filter(
+                property,
+                PropertyOperator::Contains,
+                MemoryValue::String(value.into()),
+            )
+            .matches(&props)
+        };
+        assert!(contains("status", "progress"));
+        assert!(contains("tags", "urgent"));
+        assert!(!contains("tags", "urg"));
+        assert!(!contains("estimate", "3"));
+    }
+
+    #[test]
+    fn ordering_comparisons() {
+        use PropertyOperator::{GreaterThan, LessThan};
+        let props = properties();
+        let check = |property, op, value| filter(property, op, value).matches(&props);
+        assert!(check("estimate", GreaterThan, MemoryValue::Integer(2)));
+        assert!(!check("estimate", GreaterThan, MemoryValue::Integer(3)));
+        assert!(check("estimate", LessThan, MemoryValue::Float(3.5)));
+        assert!(!check("status", LessThan, MemoryValue::Integer(1)));
+
+        let local = chrono::NaiveDate::from_ymd_opt(2025, 7, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let props = HashMap::from([("seen".to_string(), MemoryValue::LocalDateTime(local))]);
+        let offset = chrono::DateTime::parse_from_rfc3339("2025-07-01T13:00:00+02:00").unwrap();
+        assert!(filter("seen", GreaterThan, MemoryValue::DateTime(offset)).matches(&props));
+    }
+
+    #[test]
+    fn op_defaults_to_equals() {
+        let filter: PropertyFilter =
+            serde_json::from_str(r#"{"property": "status", "value": "done"}"#).unwrap();
+        assert_eq!(filter.op, PropertyOperator::Equals);
+
+        let filter: PropertyFilter =
+            serde_json::from_str(r#"{"property": "estimate", "op": "greater_than", "value": 2}"#)
+                .unwrap();
+        assert_eq!(filter.op, PropertyOperator::GreaterThan);
+        assert_eq!(filter.value, MemoryValue::Integer(2));
+    }
+
+    #[test]
+    fn sort_puts_missing_values_last() {
+        let entity = |name: &str, adopted: Option<i64>| MemoryEntity {
+            name: name.to_string(),
+            properties: adopted
+                .map(|year| HashMap::from([("adopted".to_string(), MemoryValue::Integer(year))]))
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        let mut entities = vec![
+            entity("a", None),
+            entity("b", Some(2020)),
+            entity("c", Some(2024)),
+            entity("d", Some(2020)),
+        ];
+        let sort: PropertySort =
+            serde_json::from_str(r#"{"property": "adopted", "direction": "desc"}"#).unwrap();
+        sort_entities(&mut entities, &[sort]);
+        let names: Vec<&str> = entities.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["c", "b", "d", "a"]);
+    }
+}
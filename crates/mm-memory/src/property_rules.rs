@@ -0,0 +1,247 @@
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::date::value_to_date;
+use crate::validation_error::ValidationErrorKind;
+use crate::value::MemoryValue;
+
+/// Constraint on the value of one property
+///
+/// Kinds of entities declare their constraints in
+/// [`EntityKind::RULES`](crate::EntityKind::RULES).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PropertyRule {
+    /// A string equal, ignoring case, to one of the listed values
+    OneOf(&'static [&'static str]),
+    /// A number, or a numeric string, within the inclusive bounds
+    Range { min: Option<f64>, max: Option<f64> },
+    /// A date at most this many days before today
+    MaxDaysInPast(u64),
+}
+
+impl PropertyRule {
+    /// Check `value` of `property`, resolving dates against `today`
+    pub fn check(
+        &self,
+        property: &str,
+        value: &MemoryValue,
+        today: NaiveDate,
+    ) -> Option<ValidationErrorKind> {
+        match *self {
+            PropertyRule::OneOf(allowed) => {
+                let accepted = matches!(
+                    value,
+                    MemoryValue::String(s) if allowed.iter().any(|a| a.eq_ignore_ascii_case(s))
+                );
+                (!accepted).then(|| ValidationErrorKind::InvalidEnumValue {
+                    property: property.to_string(),
+                    value: value.to_string(),
+                    allowed: allowed.join(", "),
+                })
+            }
+            PropertyRule::Range { min, max } => {
+                let number = match value {
+                    MemoryValue::Integer(i) => Some(*i as f64),
+                    MemoryValue::Float(f) => Some(*f),
+                    MemoryValue::String(s) => s.parse().ok(),
+                    _ => None,
+                };
+                let Some(number) = number.filter(|n: &f64| !n.is_nan()) else {
+                    return Some(ValidationErrorKind::InvalidPropertyType {
+                        property: property.to_string(),
+                        expected: "number",
+                    });
+                };
+                let in_range =
+                    min.is_none_or(|min| number >= min) && max.is_none_or(|max| number <= max);
+                (!in_range).then(|| ValidationErrorKind::OutOfRange {
+                    property: property.to_string(),
+                    value: number.to_string(),
+                    range: describe_range(min, max),
+                })
+            }
+            PropertyRule::MaxDaysInPast(days) => {
+                let Some(date) = value_to_date(value) else {
+                    return Some(ValidationErrorKind::InvalidPropertyType {
+                        property: property.to_string(),
+                        expected: "date",
+                    });
+                };
+                let earliest = today.checked_sub_days(Days::new(days))?;
+                (date < earliest).then(|| ValidationErrorKind::DateTooFarInPast {
+                    property: property.to_string(),
+                    date: date.to_string(),
+                    max_days: days,
+                })
+            }
+        }
+    }
+}
+
+/// Type a configured property value must have
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PropertyType {
+    String,
+    Integer,
+    /// An integer or a float
+    Number,
+    Boolean,
+    /// A date, a timestamp or a string in a recognized date format
+    Date,
+    /// A timestamp, with or without timezone
+    DateTime,
+    /// A list of strings
+    List,
+    /// A map of strings
+    Map,
+}
+
+impl PropertyType {
+    pub fn name(self) -> &'static str {
+        match self {
+            PropertyType::String => "string",
+            PropertyType::Integer => "integer",
+            PropertyType::Number => "number",
+            PropertyType::Boolean => "boolean",
+            PropertyType::Date => "date",
+            PropertyType::DateTime => "datetime",
+            PropertyType::List => "list",
+            PropertyType::Map => "map",
+        }
+    }
+
+    /// Whether `value` is of this type
+    pub fn accepts(self, value: &MemoryValue) -> bool {
+        match self {
+            PropertyType::String => matches!(value, MemoryValue::String(_)),
+            PropertyType::Integer => matches!(value, MemoryValue::Integer(_)),
+            PropertyType::Number => {
+                matches!(value, MemoryValue::Integer(_) | MemoryValue::Float(_))
+            }
+            PropertyType::Boolean => matches!(value, MemoryValue::Boolean(_)),
+            PropertyType::Date => value_to_date(value).is_some(),
+            PropertyType::DateTime => matches!(
+                value,
+                MemoryValue::DateTime(_) | MemoryValue::LocalDateTime(_)
+            ),
+            PropertyType::List => match value {
+                MemoryValue::List(_) => true,
+                MemoryValue::Json(json) => json.is_array(),
+                _ => false,
+            },
+            PropertyType::Map => match value {
+                MemoryValue::Map(_) => true,
+                MemoryValue::Json(json) => json.is_object(),
+                _ => false,
+            },
+        }
+    }
+}
+
+fn describe_range(min: Option<f64>, max: Option<f64>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("{min} to {max}"),
+        (Some(min), None) => format!("at least {min}"),
+        (None, Some(max)) => format!("at most {max}"),
+        (None, None) => "any number".to_string(),
+    }
+}
+
+/// Check the properties that have a rule; properties without one, and rules
+/// for absent properties, are ignored
+pub fn check_properties(
+    rules: &[(&str, PropertyRule)],
+    properties: &HashMap<String, MemoryValue>,
+    today: NaiveDate,
+) -> Vec<ValidationErrorKind> {
+    rules
+        .iter()
+        .filter_map(|(property, rule)| {
+            let value = properties.get(*property)?;
+            rule.check(property, value, today)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &[(&str, PropertyRule)] = &[
+        ("priority", PropertyRule::OneOf(&["low", "high"])),
+        (
+            "hours",
+            PropertyRule::Range {
+                min: Some(0.0),
+                max: Some(100.0),
+            },
+        ),
+        ("due", PropertyRule::MaxDaysInPast(30)),
+    ];
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 6, 11).unwrap()
+    }
+
+    fn check(property: &str, value: MemoryValue) -> Vec<ValidationErrorKind> {
+        check_properties(
+            RULES,
+            &HashMap::from([(property.to_string(), value)]),
+            today(),
+        )
+    }
+
+    #[test]
+    fn property_types() {
+        assert!(PropertyType::Number.accepts(&MemoryValue::Float(0.5)));
+        assert!(PropertyType::Date.accepts(&MemoryValue::String("2025-05-12".into())));
+        assert!(!PropertyType::Date.accepts(&MemoryValue::String("soon".into())));
+        assert!(!PropertyType::String.accepts(&MemoryValue::Integer(1)));
+    }
+
+    #[test]
+    fn accepts_valid_values() {
+        assert!(check("priority", MemoryValue::String("HIGH".into())).is_empty());
+        assert!(check("hours", MemoryValue::Integer(100)).is_empty());
+        assert!(check("hours", MemoryValue::String("2.5".into())).is_empty());
+        assert!(check("due", MemoryValue::String("2025-05-12".into())).is_empty());
+        assert!(check("other", MemoryValue::Boolean(true)).is_empty());
+    }
+
+    #[test]
+    fn reports_precise_errors() {
+        assert_eq!(
+            check("priority", MemoryValue::String("urgent".into())),
+            [ValidationErrorKind::InvalidEnumValue {
+                property: "priority".into(),
+                value: "urgent".into(),
+                allowed: "low, high".into(),
+            }]
+        );
+        assert_eq!(
+            check("hours", MemoryValue::Float(-1.0)),
+            [ValidationErrorKind::OutOfRange {
+                property: "hours".into(),
+                value: "-1".into(),
+                range: "0 to 100".into(),
+            }]
+        );
+        assert_eq!(
+            check("hours", MemoryValue::String("lots".into())),
+            [ValidationErrorKind::InvalidPropertyType {
+                property: "hours".into(),
+                expected: "number",
+            }]
+        );
+        assert_eq!(
+            check("due", MemoryValue::String("2025-05-11".into())),
+            [ValidationErrorKind::DateTooFarInPast {
+                property: "due".into(),
+                date: "2025-05-11".into(),
+                max_days: 30,
+            }]
+        );
+    }
+}
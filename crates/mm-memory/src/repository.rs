@@ -1,30 +1,163 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, BoxStream, StreamExt};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::error::Error as StdError;
 
+use crate::date::value_to_datetime;
 use crate::entity::MemoryEntity;
 use crate::error::MemoryResult;
+use crate::fuzzy::{FuzzyMatch, rank_by_name};
 use crate::label_match_mode::LabelMatchMode;
+use crate::labels::PROJECT_LABEL;
+use crate::property_filter::{PropertyFilter, PropertySort, matches_all, sort_entities};
 use crate::relationship::MemoryRelationship;
 use crate::relationship_direction::RelationshipDirection;
-use crate::update::{EntityUpdate, RelationshipUpdate};
+use crate::task_filter::TaskFilter;
+use crate::update::{
+    EntityUpdate, LabelsUpdate, NamedEntityUpdate, ObservationsUpdate, PropertiesUpdate,
+    RelationshipUpdate,
+};
+use crate::value::MemoryValue;
+
+/// Stream of entities produced incrementally by a repository query
+pub type EntityStream<E> = BoxStream<'static, MemoryResult<MemoryEntity, E>>;
+
+/// Kind of backend behind a repository and the database it is connected to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct BackendInfo {
+    /// Backend name, e.g. `neo4j`
+    pub backend: String,
+    /// Where the data lives, e.g. a URI or file path, without credentials
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+}
+
+/// One related-entity query of an [`EntitySnapshot`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelatedQuery {
+    pub relationship: Option<String>,
+    pub direction: Option<RelationshipDirection>,
+    pub depth: u32,
+}
+
+/// An entity and the entities related to it, read together
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EntitySnapshot {
+    pub entity: MemoryEntity,
+    /// Results of the [`RelatedQuery`]s, in the order they were given
+    pub related: Vec<Vec<MemoryEntity>>,
+}
+
+/// Task statuses that no longer count as open
+pub const CLOSED_TASK_STATUSES: [&str; 2] = ["done", "cancelled"];
+
+/// A project with the number of its tasks and when it last changed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ProjectSummary {
+    /// The project entity, without its relationships
+    #[serde(flatten)]
+    pub project: MemoryEntity,
+    /// Tasks the project `contains`
+    pub task_count: u64,
+    /// Tasks not `done` or `cancelled`
+    pub open_task_count: u64,
+    /// Latest `updated_at` of the project and its tasks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<String>")]
+    pub last_activity: Option<DateTime<Utc>>,
+}
+
+impl ProjectSummary {
+    /// Summarize `project` given the latest `updated_at` of its tasks
+    pub fn new(
+        mut project: MemoryEntity,
+        task_count: u64,
+        open_task_count: u64,
+        last_task_activity: Option<DateTime<Utc>>,
+    ) -> Self {
+        project.relationships.clear();
+        Self {
+            last_activity: updated_at(&project).max(last_task_activity),
+            project,
+            task_count,
+            open_task_count,
+        }
+    }
+
+    /// Summarize `project` from its loaded `tasks`
+    pub fn from_tasks(project: MemoryEntity, tasks: &[MemoryEntity]) -> Self {
+        let open_task_count = tasks
+            .iter()
+            .filter(|task| match task.properties.get("status") {
+                Some(MemoryValue::String(status)) => {
+                    !CLOSED_TASK_STATUSES.contains(&status.to_lowercase().as_str())
+                }
+                _ => true,
+            })
+            .count() as u64;
+        let last_task_activity = tasks.iter().filter_map(updated_at).max();
+        Self::new(
+            project,
+            tasks.len() as u64,
+            open_task_count,
+            last_task_activity,
+        )
+    }
+}
+
+fn updated_at(entity: &MemoryEntity) -> Option<DateTime<Utc>> {
+    entity
+        .properties
+        .get("updated_at")
+        .and_then(value_to_datetime)
+}
 
 #[cfg_attr(any(test, feature = "mock"), mockall::automock(type Error = std::convert::Infallible;))]
 #[async_trait]
 pub trait MemoryRepository {
     type Error: StdError + Send + Sync + 'static;
 
+    /// Backend and database this repository talks to, for diagnostics
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            backend: "unknown".to_string(),
+            database: None,
+        }
+    }
+
     async fn create_entities(&self, entities: &[MemoryEntity]) -> MemoryResult<(), Self::Error>;
     async fn find_entity_by_name(
         &self,
         name: &str,
     ) -> MemoryResult<Option<MemoryEntity>, Self::Error>;
 
+    /// Which of `names` are already used by stored entities
+    ///
+    /// The default implementation looks the names up one by one; backends
+    /// should answer with a single query.
+    async fn find_existing_names(
+        &self,
+        names: &[String],
+    ) -> MemoryResult<Vec<String>, Self::Error> {
+        let mut existing = Vec::new();
+        for name in names {
+            if self.find_entity_by_name(name).await?.is_some() {
+                existing.push(name.clone());
+            }
+        }
+        Ok(existing)
+    }
+
     async fn set_observations(
         &self,
         name: &str,
         observations: &[String],
     ) -> MemoryResult<(), Self::Error>;
 
+    /// Append observations to an entity, skipping any that are already present.
     async fn add_observations(
         &self,
         name: &str,
@@ -44,6 +177,27 @@ pub trait MemoryRepository {
         relationships: &[MemoryRelationship],
     ) -> MemoryResult<(), Self::Error>;
 
+    /// Create `entities` and `relationships` between them as one unit.
+    ///
+    /// Backends with transactions should store both batches or neither. The
+    /// default implementation creates the entities, then the relationships,
+    /// and deletes the entities again if the relationships fail.
+    async fn create_graph(
+        &self,
+        entities: &[MemoryEntity],
+        relationships: &[MemoryRelationship],
+    ) -> MemoryResult<(), Self::Error> {
+        self.create_entities(entities).await?;
+        if let Err(e) = self.create_relationships(relationships).await {
+            let names: Vec<String> = entities.iter().map(|e| e.name.clone()).collect();
+            if let Err(cleanup) = self.delete_entities(&names).await {
+                tracing::warn!("Failed to remove entities of a failed batch: {cleanup}");
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
     async fn delete_entities(&self, names: &[String]) -> MemoryResult<(), Self::Error>;
 
     async fn delete_relationships(
@@ -65,6 +219,83 @@ pub trait MemoryRepository {
         required_label: Option<String>,
     ) -> MemoryResult<Vec<MemoryEntity>, Self::Error>;
 
+    /// Stream entities matching `labels` as the backend produces them.
+    ///
+    /// The streaming counterpart of
+    /// [`find_entities_by_labels`](Self::find_entities_by_labels); the default
+    /// implementation falls back to the collected query.
+    async fn stream_entities_by_labels(
+        &self,
+        labels: &[String],
+        match_mode: LabelMatchMode,
+        required_label: Option<String>,
+    ) -> MemoryResult<EntityStream<Self::Error>, Self::Error> {
+        let entities = self
+            .find_entities_by_labels(labels, match_mode, required_label)
+            .await?;
+        Ok(stream::iter(entities.into_iter().map(Ok)).boxed())
+    }
+
+    /// Find entities whose names resemble `name`, best matches first
+    ///
+    /// The default implementation scores every entity labelled
+    /// `required_label` in memory; backends with text similarity functions
+    /// should preselect candidates in their query.
+    async fn find_entities_fuzzy(
+        &self,
+        name: &str,
+        limit: usize,
+        required_label: Option<String>,
+    ) -> MemoryResult<Vec<FuzzyMatch>, Self::Error> {
+        let entities = self
+            .find_entities_by_labels(&[], LabelMatchMode::Any, required_label)
+            .await?;
+        Ok(rank_by_name(entities, name, limit))
+    }
+
+    /// Find entities matching `labels` whose properties satisfy every filter,
+    /// ordered by `sort` then name when it is not empty
+    ///
+    /// The default implementation filters and sorts the label query's results
+    /// in memory; backends should push the filters and sort into their query.
+    async fn find_entities_by_labels_filtered(
+        &self,
+        labels: &[String],
+        match_mode: LabelMatchMode,
+        required_label: Option<String>,
+        filters: &[PropertyFilter],
+        sort: &[PropertySort],
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        let entities = self
+            .find_entities_by_labels(labels, match_mode, required_label)
+            .await?;
+        let mut entities: Vec<MemoryEntity> = entities
+            .into_iter()
+            .filter(|e| matches_all(filters, &e.properties))
+            .collect();
+        if !sort.is_empty() {
+            sort_entities(&mut entities, sort);
+        }
+        Ok(entities)
+    }
+
+    /// Find entities whose `sources` property contains `source_key`
+    ///
+    /// See [`ObservationSource::keys`](crate::ObservationSource::keys) for
+    /// the key format.
+    async fn find_entities_by_source(
+        &self,
+        source_key: &str,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error>;
+
+    /// Every label in use with the number of entities carrying it, sorted
+    /// by label
+    async fn list_labels(&self) -> MemoryResult<Vec<(String, u64)>, Self::Error>;
+
+    /// Every relationship type in use with the number of relationships of
+    /// that type, sorted by type
+    async fn list_relationship_types(&self) -> MemoryResult<Vec<(String, u64)>, Self::Error>;
+
     async fn find_related_entities(
         &self,
         name: &str,
@@ -73,12 +304,198 @@ pub trait MemoryRepository {
         depth: u32,
     ) -> MemoryResult<Vec<MemoryEntity>, Self::Error>;
 
+    /// Stream entities related to `name` as the backend produces them.
+    ///
+    /// Unlike [`find_related_entities`](Self::find_related_entities) the
+    /// result is not materialized up front, which lets callers emit partial
+    /// results for deep traversals. The default implementation falls back to
+    /// the collected query.
+    async fn stream_related_entities(
+        &self,
+        name: &str,
+        relationship_type: Option<String>,
+        direction: Option<RelationshipDirection>,
+        depth: u32,
+    ) -> MemoryResult<EntityStream<Self::Error>, Self::Error> {
+        let entities = self
+            .find_related_entities(name, relationship_type, direction, depth)
+            .await?;
+        Ok(stream::iter(entities.into_iter().map(Ok)).boxed())
+    }
+
+    /// Look up `name` and run `queries` from it, or `None` when it is missing
+    ///
+    /// Callers assembling one document from several queries use this so
+    /// that backends can read it together. The default implementation runs
+    /// the queries one after another; backends with transactions should run
+    /// them in a single read transaction, which is as consistent as the
+    /// backend's isolation level makes it.
+    async fn find_entity_snapshot(
+        &self,
+        name: &str,
+        queries: &[RelatedQuery],
+    ) -> MemoryResult<Option<EntitySnapshot>, Self::Error> {
+        let Some(entity) = self.find_entity_by_name(name).await? else {
+            return Ok(None);
+        };
+        let mut related = Vec::with_capacity(queries.len());
+        for query in queries {
+            related.push(
+                self.find_related_entities(
+                    name,
+                    query.relationship.clone(),
+                    query.direction,
+                    query.depth,
+                )
+                .await?,
+            );
+        }
+        Ok(Some(EntitySnapshot { entity, related }))
+    }
+
+    /// Entities labelled `Project` and `required_label`, each with counts of
+    /// its tasks and its latest activity
+    ///
+    /// The default implementation loads the tasks of every project; backends
+    /// should compute the summaries in one aggregate query.
+    async fn list_project_summaries(
+        &self,
+        required_label: Option<String>,
+    ) -> MemoryResult<Vec<ProjectSummary>, Self::Error> {
+        let projects = self
+            .find_entities_by_labels(
+                &[PROJECT_LABEL.to_string()],
+                LabelMatchMode::All,
+                required_label,
+            )
+            .await?;
+        let mut summaries = Vec::with_capacity(projects.len());
+        for project in projects {
+            let tasks = self
+                .find_tasks(&TaskFilter::for_project(project.name.clone()))
+                .await?;
+            summaries.push(ProjectSummary::from_tasks(project, &tasks));
+        }
+        Ok(summaries)
+    }
+
+    /// Relationships along a shortest path between `from` and `to`, in path
+    /// order, or `None` when they are not connected within `max_depth` hops
+    ///
+    /// Relationships are followed in either direction; only those of the
+    /// given `types` are followed unless it is empty. The default
+    /// implementation searches breadth-first with
+    /// [`find_entity_by_name`](Self::find_entity_by_name); backends should
+    /// use a native shortest-path query.
+    async fn find_path(
+        &self,
+        from: &str,
+        to: &str,
+        max_depth: u32,
+        types: &[String],
+    ) -> MemoryResult<Option<Vec<MemoryRelationship>>, Self::Error> {
+        if from == to {
+            return Ok(Some(Vec::new()));
+        }
+        // The relationship each reached entity was first reached through
+        let mut reached_by: HashMap<String, MemoryRelationship> = HashMap::new();
+        let mut seen = HashSet::from([from.to_string()]);
+        let mut frontier = vec![from.to_string()];
+        for _ in 0..max_depth {
+            let mut next = Vec::new();
+            for name in &frontier {
+                let Some(entity) = self.find_entity_by_name(name).await? else {
+                    continue;
+                };
+                for rel in entity.relationships {
+                    if !types.is_empty() && !types.contains(&rel.name) {
+                        continue;
+                    }
+                    let other = if rel.from == *name {
+                        &rel.to
+                    } else {
+                        &rel.from
+                    }
+                    .clone();
+                    if seen.insert(other.clone()) {
+                        reached_by.insert(other.clone(), rel);
+                        next.push(other);
+                    }
+                }
+            }
+            if seen.contains(to) {
+                let mut path = Vec::new();
+                let mut current = to.to_string();
+                while let Some(rel) = reached_by.remove(&current) {
+                    current = if rel.to == current {
+                        &rel.from
+                    } else {
+                        &rel.to
+                    }
+                    .clone();
+                    path.push(rel);
+                }
+                path.reverse();
+                return Ok(Some(path));
+            }
+            frontier = next;
+        }
+        Ok(None)
+    }
+
+    /// Find the tasks of `filter.project` that match `filter`.
+    ///
+    /// The default implementation loads every task of the project and filters
+    /// them in memory; backends should push the filter into their query.
+    async fn find_tasks(
+        &self,
+        filter: &TaskFilter,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        let tasks = self
+            .find_related_entities(
+                &filter.project,
+                Some("contains".to_string()),
+                Some(RelationshipDirection::Outgoing),
+                1,
+            )
+            .await?;
+        Ok(tasks.into_iter().filter(|t| filter.matches(t)).collect())
+    }
+
+    /// Ensure an index exists for `property` on entities labelled `label`.
+    ///
+    /// Backends without secondary indexes can rely on the default no-op.
+    async fn create_property_index(
+        &self,
+        _label: &str,
+        _property: &str,
+    ) -> MemoryResult<(), Self::Error> {
+        Ok(())
+    }
+
     async fn update_entity(
         &self,
         name: &str,
         update: &EntityUpdate,
     ) -> MemoryResult<(), Self::Error>;
 
+    /// Apply `updates` as one unit
+    ///
+    /// Expected revisions are checked against the entities as they were
+    /// before the batch. Backends with transactions should apply every update
+    /// or none, failing as a whole when any expected revision is stale. The
+    /// default implementation applies them in order and stops at the first
+    /// failure.
+    async fn update_entities(
+        &self,
+        updates: &[NamedEntityUpdate],
+    ) -> MemoryResult<(), Self::Error> {
+        for item in updates {
+            self.update_entity(&item.name, &item.update).await?;
+        }
+        Ok(())
+    }
+
     async fn update_relationship(
         &self,
         from: &str,
@@ -86,4 +503,86 @@ pub trait MemoryRepository {
         name: &str,
         update: &RelationshipUpdate,
     ) -> MemoryResult<(), Self::Error>;
+
+    /// Rename the entity `old` to `new`, keeping the relationships touching it
+    ///
+    /// Missing entities are ignored. The default implementation recreates the
+    /// entity and its relationships under the new name with
+    /// [`create_graph`](Self::create_graph) before deleting the old one, so
+    /// it is only as atomic as that; backends should rename in place.
+    async fn rename_entity(&self, old: &str, new: &str) -> MemoryResult<(), Self::Error> {
+        let Some(entity) = self.find_entity_by_name(old).await? else {
+            return Ok(());
+        };
+        let rename = |n: String| if n == old { new.to_string() } else { n };
+        let relationships: Vec<MemoryRelationship> = entity
+            .relationships
+            .iter()
+            .cloned()
+            .map(|r| MemoryRelationship {
+                from: rename(r.from),
+                to: rename(r.to),
+                ..r
+            })
+            .collect();
+        let renamed = MemoryEntity {
+            name: new.to_string(),
+            relationships: vec![],
+            ..entity
+        };
+        self.create_graph(&[renamed], &relationships).await?;
+        self.delete_entities(&[old.to_string()]).await
+    }
+
+    /// Replace the entity named like `merged` with it and delete `sources`
+    ///
+    /// `merged.relationships` lists every relationship the entity should end
+    /// up with, such as the result of [`merge_into`](crate::merge::merge_into).
+    /// The default implementation updates the entity in place, adds the
+    /// relationships it lacks and then deletes the sources; backends with
+    /// transactions should do all of it as one unit.
+    async fn merge_entities(
+        &self,
+        merged: &MemoryEntity,
+        sources: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        let existing = self
+            .find_entity_by_name(&merged.name)
+            .await?
+            .map(|e| e.relationships)
+            .unwrap_or_default();
+        let update = EntityUpdate {
+            observations: Some(ObservationsUpdate {
+                add: None,
+                remove: None,
+                set: Some(merged.observations.clone()),
+            }),
+            properties: Some(PropertiesUpdate {
+                add: None,
+                remove: None,
+                set: Some(merged.properties.clone()),
+            }),
+            labels: Some(LabelsUpdate {
+                add: Some(merged.labels.clone()),
+                remove: None,
+            }),
+            expected_revision: None,
+            force: false,
+        };
+        self.update_entity(&merged.name, &update).await?;
+        let missing: Vec<MemoryRelationship> = merged
+            .relationships
+            .iter()
+            .filter(|r| {
+                !existing
+                    .iter()
+                    .any(|e| e.from == r.from && e.to == r.to && e.name == r.name)
+            })
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            self.create_relationships(&missing).await?;
+        }
+        self.delete_entities(sources).await
+    }
 }
@@ -0,0 +1,165 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::observation_meta::{get_observation_meta, observation_meta, set_observation_meta};
+use crate::validation_error::ValidationErrorKind;
+use crate::value::MemoryValue;
+
+/// Entity property holding the source of each observation
+pub const OBSERVATION_SOURCES_PROPERTY: &str = "observation_sources";
+
+/// Entity property listing the [`ObservationSource::keys`] of all its
+/// observations, so backends can look entities up by source
+pub const SOURCES_PROPERTY: &str = "sources";
+
+/// Where an observation was learned
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq, JsonSchema)]
+pub struct ObservationSource {
+    /// Web page or document URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// File path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Identifier of the conversation the fact came up in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conversation_id: Option<String>,
+}
+
+impl ObservationSource {
+    /// Whether no source field is set
+    pub fn is_empty(&self) -> bool {
+        self.url.is_none() && self.file.is_none() && self.conversation_id.is_none()
+    }
+
+    /// Lookup keys for this source, one per set field
+    ///
+    /// Keys take the form `url:<url>`, `file:<path>` and
+    /// `conversation:<id>`.
+    pub fn keys(&self) -> Vec<String> {
+        [
+            self.url.as_ref().map(|u| format!("url:{u}")),
+            self.file.as_ref().map(|f| format!("file:{f}")),
+            self.conversation_id
+                .as_ref()
+                .map(|c| format!("conversation:{c}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Whether every field set on `filter` matches this source
+    pub fn matches(&self, filter: &ObservationSource) -> bool {
+        let field = |want: &Option<String>, have: &Option<String>| {
+            want.as_ref().is_none_or(|w| have.as_ref() == Some(w))
+        };
+        field(&filter.url, &self.url)
+            && field(&filter.file, &self.file)
+            && field(&filter.conversation_id, &self.conversation_id)
+    }
+
+    /// Reject sources with nothing to cite
+    pub fn validate(&self) -> Result<(), ValidationErrorKind> {
+        if self.is_empty() {
+            Err(ValidationErrorKind::EmptySource)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Source recorded for `observation`, if any
+pub fn observation_source(
+    properties: &HashMap<String, MemoryValue>,
+    observation: &str,
+) -> Option<ObservationSource> {
+    get_observation_meta(properties, OBSERVATION_SOURCES_PROPERTY, observation)
+}
+
+/// All observation sources recorded in `properties`
+pub fn observation_sources(
+    properties: &HashMap<String, MemoryValue>,
+) -> Vec<(String, ObservationSource)> {
+    observation_meta(properties, OBSERVATION_SOURCES_PROPERTY)
+}
+
+/// Record the source of `observation` and refresh the [`SOURCES_PROPERTY`]
+/// lookup keys
+pub fn set_observation_source(
+    properties: &mut HashMap<String, MemoryValue>,
+    observation: &str,
+    source: &ObservationSource,
+) {
+    set_observation_meta(
+        properties,
+        OBSERVATION_SOURCES_PROPERTY,
+        observation,
+        source,
+    );
+    let mut keys: Vec<String> = observation_sources(properties)
+        .iter()
+        .flat_map(|(_, s)| s.keys())
+        .collect();
+    keys.sort();
+    keys.dedup();
+    properties.insert(SOURCES_PROPERTY.to_string(), MemoryValue::List(keys));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_observation_source_updates_keys() {
+        let mut props = HashMap::new();
+        let doc = ObservationSource {
+            url: Some("https://example.com".to_string()),
+            ..Default::default()
+        };
+        let chat = ObservationSource {
+            conversation_id: Some("c1".to_string()),
+            ..Default::default()
+        };
+        set_observation_source(&mut props, "a", &doc);
+        set_observation_source(&mut props, "b", &chat);
+
+        assert_eq!(observation_source(&props, "a"), Some(doc.clone()));
+        assert_eq!(
+            props.get(SOURCES_PROPERTY),
+            Some(&MemoryValue::List(vec![
+                "conversation:c1".to_string(),
+                "url:https://example.com".to_string(),
+            ]))
+        );
+
+        set_observation_source(&mut props, "b", &doc);
+        assert_eq!(
+            props.get(SOURCES_PROPERTY),
+            Some(&MemoryValue::List(vec![
+                "url:https://example.com".to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_matches() {
+        let source = ObservationSource {
+            url: Some("https://example.com".to_string()),
+            conversation_id: Some("c1".to_string()),
+            ..Default::default()
+        };
+        let by_url = ObservationSource {
+            url: Some("https://example.com".to_string()),
+            ..Default::default()
+        };
+        let by_file = ObservationSource {
+            file: Some("README.md".to_string()),
+            ..Default::default()
+        };
+        assert!(source.matches(&by_url));
+        assert!(!source.matches(&by_file));
+        assert!(ObservationSource::default().validate().is_err());
+    }
+}
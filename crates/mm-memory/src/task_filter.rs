@@ -0,0 +1,137 @@
+use chrono::NaiveDate;
+
+use crate::date::value_to_date;
+use crate::entity::MemoryEntity;
+use crate::labels::TASK_LABEL;
+use crate::value::MemoryValue;
+
+/// Filter for task lookups that repositories can push down into their queries
+///
+/// Tasks are entities labelled `Task` that the project links to with an
+/// outgoing `contains` relationship. Empty lists and `None` values disable the
+/// corresponding filter.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskFilter {
+    /// Name of the project containing the tasks
+    pub project: String,
+    /// Labels every task must have in addition to `Task`
+    pub labels: Vec<String>,
+    /// Accepted values of the `status` property
+    pub statuses: Vec<String>,
+    /// Accepted values of the `priority` property
+    pub priorities: Vec<String>,
+    /// Only include tasks due strictly before this date
+    pub due_before: Option<NaiveDate>,
+    /// Only include tasks due on or after this date
+    pub due_after: Option<NaiveDate>,
+}
+
+impl TaskFilter {
+    /// Create a filter matching every task in `project`
+    pub fn for_project(project: impl Into<String>) -> Self {
+        Self {
+            project: project.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Check whether an already loaded entity satisfies the filter
+    ///
+    /// The project scope is not checked here since it depends on relationships
+    /// the caller used to load the entity.
+    pub fn matches(&self, entity: &MemoryEntity) -> bool {
+        if !entity.labels.iter().any(|l| l == TASK_LABEL) {
+            return false;
+        }
+        if !self.labels.iter().all(|l| entity.labels.contains(l)) {
+            return false;
+        }
+        if !Self::matches_any(&self.statuses, entity.properties.get("status")) {
+            return false;
+        }
+        if !Self::matches_any(&self.priorities, entity.properties.get("priority")) {
+            return false;
+        }
+        if self.due_before.is_some() || self.due_after.is_some() {
+            let Some(due) = entity.properties.get("due_date").and_then(value_to_date) else {
+                return false;
+            };
+            if self.due_before.is_some_and(|before| due >= before) {
+                return false;
+            }
+            if self.due_after.is_some_and(|after| due < after) {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn matches_any(accepted: &[String], value: Option<&MemoryValue>) -> bool {
+        if accepted.is_empty() {
+            return true;
+        }
+        matches!(value, Some(MemoryValue::String(s)) if accepted.contains(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+    use std::collections::HashMap;
+
+    fn task(status: &str, due: Option<&str>) -> MemoryEntity {
+        let mut properties = HashMap::new();
+        properties.insert("status".to_string(), MemoryValue::String(status.into()));
+        properties.insert("priority".to_string(), MemoryValue::String("high".into()));
+        if let Some(due) = due {
+            properties.insert(
+                "due_date".to_string(),
+                MemoryValue::DateTime(DateTime::parse_from_rfc3339(due).unwrap()),
+            );
+        }
+        MemoryEntity {
+            name: "task:1".into(),
+            labels: vec![TASK_LABEL.to_string()],
+            properties,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn empty_filter_matches_any_task() {
+        let filter = TaskFilter::for_project("proj");
+        assert!(filter.matches(&task("todo", None)));
+        assert!(!filter.matches(&MemoryEntity::default()));
+    }
+
+    #[test]
+    fn status_and_priority_filters() {
+        let mut filter = TaskFilter::for_project("proj");
+        filter.statuses = vec!["done".into()];
+        assert!(!filter.matches(&task("todo", None)));
+        assert!(filter.matches(&task("done", None)));
+
+        filter.priorities = vec!["low".into()];
+        assert!(!filter.matches(&task("done", None)));
+    }
+
+    #[test]
+    fn due_date_range() {
+        let mut filter = TaskFilter::for_project("proj");
+        filter.due_before = NaiveDate::from_ymd_opt(2025, 7, 1);
+        filter.due_after = NaiveDate::from_ymd_opt(2025, 6, 1);
+        assert!(filter.matches(&task("todo", Some("2025-06-15T00:00:00Z"))));
+        assert!(filter.matches(&task("todo", Some("2025-06-01T00:00:00Z"))));
+        assert!(!filter.matches(&task("todo", Some("2025-07-01T00:00:00Z"))));
+        assert!(!filter.matches(&task("todo", Some("2025-05-31T00:00:00Z"))));
+        assert!(!filter.matches(&task("todo", None)));
+
+        let mut dated = task("todo", None);
+        dated.properties.insert(
+            "due_date".to_string(),
+            MemoryValue::Date(NaiveDate::from_ymd_opt(2025, 6, 30).unwrap()),
+        );
+        assert!(filter.matches(&dated));
+    }
+}
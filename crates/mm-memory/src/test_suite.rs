@@ -1,5 +1,8 @@
+use crate::source::set_observation_source;
 use crate::{
-    MemoryConfig, MemoryEntity, MemoryRelationship, MemoryRepository, MemoryService, MemoryValue,
+    EntityUpdate, LabelMatchMode, MemoryConfig, MemoryEntity, MemoryError, MemoryRelationship,
+    MemoryRepository, MemoryService, MemoryValue, ObservationSource, PROJECT_LABEL, PropertyFilter,
+    TASK_LABEL, ValidationErrorKind,
 };
 use chrono::Utc;
 use std::collections::{HashMap, HashSet};
@@ -25,6 +28,31 @@ where
         allowed_labels: std::iter::once("Example".to_string()).collect(),
         default_project: None,
         agent_name: "test".to_string(),
+        indexed_properties: HashMap::default(),
+        self_loop_relationships: HashSet::default(),
+        merge_relationships: false,
+        wip_limits: Default::default(),
+        projects: HashMap::default(),
+        expiry: HashMap::default(),
+        retry: Default::default(),
+        batch_size: 500,
+        read_only: false,
+        soft_delete: false,
+        history: false,
+        audit_log: None,
+        redaction: Vec::new(),
+        reject_duplicate_names: true,
+        acyclic_relationships: std::iter::once("depends_on".to_string()).collect(),
+        relationship_rules: HashMap::new(),
+        name_template: "{{label}}:{{slug}}".to_string(),
+        normalize_datetimes: false,
+        local_utc_offset_minutes: 0,
+        track_observations: false,
+        templates: HashMap::new(),
+        label_parents: HashMap::new(),
+        reserved_labels: HashSet::default(),
+        reserved_relationships: HashSet::default(),
+        naming: Default::default(),
     };
 
     let service = MemoryService::new(repository, config);
@@ -55,6 +83,16 @@ where
         .await?;
     assert!(errs.is_empty());
 
+    // Names already in use are rejected
+    let errs = service
+        .create_entities(std::slice::from_ref(&entity_a))
+        .await?;
+    assert_eq!(errs.len(), 1);
+    assert!(matches!(
+        errs[0].error.0[0],
+        ValidationErrorKind::EntityExists(ref name) if *name == name_a
+    ));
+
     // Verify retrieval of entity and default label
     let fetched_a = service
         .find_entity_by_name(&name_a)
@@ -63,11 +101,28 @@ where
     assert!(fetched_a.labels.contains(&"TestSuite".to_string()));
     assert!(fetched_a.labels.contains(&"Example".to_string()));
     assert_eq!(fetched_a.observations, ["first".to_string()]);
+    assert_eq!(fetched_a.revision, 1);
     assert_eq!(
         fetched_a.properties.get("k"),
         Some(&MemoryValue::String("v".to_string()))
     );
 
+    // --- Property filters ---
+    let filtered = service
+        .find_entities_by_labels_filtered(
+            &["Example".to_string()],
+            LabelMatchMode::Any,
+            None,
+            &[PropertyFilter::equals(
+                "k",
+                MemoryValue::String("v".to_string()),
+            )],
+            &[],
+        )
+        .await?;
+    assert!(filtered.iter().any(|e| e.name == name_a));
+    assert!(!filtered.iter().any(|e| e.name == name_b));
+
     // --- Relationship creation ---
     let rel = MemoryRelationship {
         from: name_a.clone(),
@@ -75,7 +130,9 @@ where
         name: "relates_to".to_string(),
         properties: HashMap::default(),
     };
-    service.create_relationships(&[rel.clone()]).await?;
+    service
+        .create_relationships(std::slice::from_ref(&rel))
+        .await?;
 
     let fetched_a = service.find_entity_by_name(&name_a).await?.unwrap();
     assert!(
@@ -91,6 +148,7 @@ where
         .await?;
     let after_set = service.find_entity_by_name(&name_a).await?.unwrap();
     assert_eq!(after_set.observations, ["one", "two"]);
+    assert!(after_set.revision > fetched_a.revision);
 
     service
         .add_observations(&name_a, &["three".to_string()])
@@ -98,6 +156,12 @@ where
     let after_add = service.find_entity_by_name(&name_a).await?.unwrap();
     assert_eq!(after_add.observations, ["one", "two", "three"]);
 
+    service
+        .add_observations(&name_a, &["one".to_string(), "three".to_string()])
+        .await?;
+    let after_dup = service.find_entity_by_name(&name_a).await?.unwrap();
+    assert_eq!(after_dup.observations, ["one", "two", "three"]);
+
     service
         .remove_observations(&name_a, &["two".to_string()])
         .await?;
@@ -108,6 +172,21 @@ where
     let cleared = service.find_entity_by_name(&name_a).await?.unwrap();
     assert!(cleared.observations.is_empty());
 
+    // --- Optimistic concurrency ---
+    let stale = EntityUpdate {
+        expected_revision: Some(after_set.revision),
+        ..Default::default()
+    };
+    assert!(matches!(
+        service.update_entity(&name_a, &stale).await,
+        Err(MemoryError::RevisionConflict { .. })
+    ));
+    let current = EntityUpdate {
+        expected_revision: Some(cleared.revision),
+        ..Default::default()
+    };
+    service.update_entity(&name_a, &current).await?;
+
     // --- Validation and error handling ---
     let invalid = MemoryEntity::default();
     let errs = service.create_entities(&[invalid]).await?;
@@ -134,7 +213,6 @@ where
     service.create_entities(&extra).await?;
 
     // --- Find entities by labels ---
-    use crate::label_match_mode::LabelMatchMode;
     let by_example = service
         .find_entities_by_labels(&["Example".to_string()], LabelMatchMode::Any, None)
         .await?;
@@ -157,5 +235,254 @@ where
     assert!(required_only.iter().any(|e| e.name == name_a));
     assert!(required_only.iter().any(|e| e.name == name_b));
 
+    // --- Vocabulary in use ---
+    let labels = service.list_labels().await?;
+    assert!(labels.iter().any(|(l, n)| l == "Example" && *n >= 2));
+    assert!(labels.windows(2).all(|w| w[0].0 < w[1].0));
+    let rel_types = service.list_relationship_types().await?;
+    assert!(rel_types.iter().any(|(r, n)| r == "relates_to" && *n >= 1));
+
+    // --- Find entities by source ---
+    let source = ObservationSource {
+        url: Some(format!("https://example.com/{unique}")),
+        conversation_id: Some(format!("conversation-{unique}")),
+        ..Default::default()
+    };
+    let mut cited_props = HashMap::new();
+    set_observation_source(&mut cited_props, "cited", &source);
+    let cited = MemoryEntity {
+        name: format!("test:suite:cited:{unique}"),
+        labels: vec!["Example".to_string()],
+        observations: vec!["cited".to_string()],
+        properties: cited_props,
+        ..Default::default()
+    };
+    service
+        .create_entities(std::slice::from_ref(&cited))
+        .await?;
+
+    let by_url = service
+        .find_entities_by_source(&ObservationSource {
+            url: source.url.clone(),
+            ..Default::default()
+        })
+        .await?;
+    assert_eq!(by_url.len(), 1);
+    assert_eq!(by_url[0].name, cited.name);
+
+    let mismatched = service
+        .find_entities_by_source(&ObservationSource {
+            url: source.url.clone(),
+            conversation_id: Some("other".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    assert!(mismatched.is_empty());
+
+    // --- Entities and relationships created together ---
+    let graph_a = MemoryEntity::<HashMap<String, MemoryValue>> {
+        name: format!("test:suite:graph:a:{unique}"),
+        labels: vec!["Example".to_string()],
+        ..Default::default()
+    };
+    let graph_b = MemoryEntity {
+        name: format!("test:suite:graph:b:{unique}"),
+        ..graph_a.clone()
+    };
+    let graph_rel = MemoryRelationship {
+        from: graph_a.name.clone(),
+        to: graph_b.name.clone(),
+        name: "relates_to".to_string(),
+        properties: HashMap::default(),
+    };
+
+    // A rejected relationship keeps the entities from being written
+    let errs = service
+        .create_project_graph_typed(
+            None,
+            &[graph_a.clone(), graph_b.clone()],
+            &[graph_rel.clone(), graph_rel.clone()],
+        )
+        .await?;
+    assert_eq!(errs.relationships.len(), 1);
+    assert!(service.find_entity_by_name(&graph_a.name).await?.is_none());
+
+    let errs = service
+        .create_project_graph_typed(
+            None,
+            &[graph_a.clone(), graph_b.clone()],
+            std::slice::from_ref(&graph_rel),
+        )
+        .await?;
+    assert!(errs.is_empty());
+    let fetched = service.find_entity_by_name(&graph_a.name).await?.unwrap();
+    assert!(
+        fetched
+            .relationships
+            .iter()
+            .any(|r| r.to == graph_rel.to && r.name == graph_rel.name)
+    );
+
+    // --- Shortest path, followed against the relationship direction ---
+    let path = service
+        .find_path(&graph_b.name, &graph_a.name, 2, &[])
+        .await?
+        .expect("graph entities should be connected");
+    assert_eq!(path.len(), 1);
+    assert_eq!(path[0].from, graph_a.name);
+    let unrelated = service
+        .find_path(&graph_a.name, &name_a, 2, &["relates_to".to_string()])
+        .await?;
+    assert!(unrelated.is_none());
+
+    // --- Dependency cycles are rejected ---
+    let depends_on = |from: &MemoryEntity, to: &MemoryEntity| MemoryRelationship {
+        from: from.name.clone(),
+        to: to.name.clone(),
+        name: "depends_on".to_string(),
+        properties: HashMap::default(),
+    };
+    let cycle_a = MemoryEntity {
+        name: format!("test:suite:cycle:a:{unique}"),
+        ..graph_a.clone()
+    };
+    let cycle_b = MemoryEntity {
+        name: format!("test:suite:cycle:b:{unique}"),
+        ..graph_a.clone()
+    };
+    let errs = service
+        .create_project_graph_typed(
+            None,
+            &[cycle_a.clone(), cycle_b.clone()],
+            &[depends_on(&cycle_a, &cycle_b)],
+        )
+        .await?;
+    assert!(errs.is_empty());
+    let errs = service
+        .create_relationships(&[depends_on(&cycle_b, &cycle_a)])
+        .await?;
+    assert_eq!(
+        errs[0].error.0,
+        [ValidationErrorKind::DependencyCycle(format!(
+            "{} -> {} -> {}",
+            cycle_b.name, cycle_a.name, cycle_b.name
+        ))]
+    );
+
+    // --- Rename keeps relationships ---
+    let renamed = format!("test:suite:graph:renamed:{unique}");
+    service.rename_entity(&graph_b.name, &renamed).await?;
+    assert!(service.find_entity_by_name(&graph_b.name).await?.is_none());
+    let fetched = service.find_entity_by_name(&graph_a.name).await?.unwrap();
+    assert!(
+        fetched
+            .relationships
+            .iter()
+            .any(|r| r.to == renamed && r.name == graph_rel.name)
+    );
+    assert!(
+        service
+            .rename_entity(&renamed, &graph_a.name)
+            .await
+            .is_err()
+    );
+
+    // --- Merge moves relationships to the target ---
+    let duplicate = MemoryEntity::<HashMap<String, MemoryValue>> {
+        name: format!("test:suite:graph:duplicate:{unique}"),
+        labels: vec!["Example".to_string()],
+        observations: vec!["duplicate".to_string()],
+        ..Default::default()
+    };
+    let duplicate_rel = MemoryRelationship {
+        from: duplicate.name.clone(),
+        to: renamed.clone(),
+        name: "relates_to".to_string(),
+        properties: HashMap::default(),
+    };
+    let errs = service
+        .create_project_graph_typed(
+            None,
+            std::slice::from_ref(&duplicate),
+            std::slice::from_ref(&duplicate_rel),
+        )
+        .await?;
+    assert!(errs.is_empty());
+    service
+        .merge_entities(
+            &graph_a.name,
+            std::slice::from_ref(&duplicate.name),
+            &HashMap::new(),
+        )
+        .await?;
+    assert!(
+        service
+            .find_entity_by_name(&duplicate.name)
+            .await?
+            .is_none()
+    );
+    let merged = service.find_entity_by_name(&graph_a.name).await?.unwrap();
+    assert!(merged.observations.contains(&"duplicate".to_string()));
+    assert_eq!(
+        merged
+            .relationships
+            .iter()
+            .filter(|r| r.from == graph_a.name && r.to == renamed)
+            .count(),
+        1
+    );
+
+    // --- Project summaries count tasks and track activity ---
+    let project = MemoryEntity {
+        name: format!("test:suite:project:{unique}"),
+        labels: vec![PROJECT_LABEL.to_string()],
+        ..Default::default()
+    };
+    let task = |suffix: &str, status: &str, updated_at: &str| {
+        let mut properties = HashMap::new();
+        properties.insert(
+            "status".to_string(),
+            MemoryValue::String(status.to_string()),
+        );
+        properties.insert(
+            "updated_at".to_string(),
+            MemoryValue::DateTime(updated_at.parse().unwrap()),
+        );
+        MemoryEntity {
+            name: format!("test:suite:task:{suffix}:{unique}"),
+            labels: vec![TASK_LABEL.to_string()],
+            properties,
+            ..Default::default()
+        }
+    };
+    let tasks = [
+        task("open", "todo", "2025-01-01T00:00:00Z"),
+        task("done", "done", "2025-02-01T00:00:00Z"),
+    ];
+    let contains: Vec<MemoryRelationship> = tasks
+        .iter()
+        .map(|t| MemoryRelationship {
+            from: project.name.clone(),
+            to: t.name.clone(),
+            name: "contains".to_string(),
+            properties: HashMap::default(),
+        })
+        .collect();
+    let mut entities = vec![project.clone()];
+    entities.extend(tasks);
+    assert!(service.create_entities(&entities).await?.is_empty());
+    assert!(service.create_relationships(&contains).await?.is_empty());
+    let summaries = service.list_project_summaries().await?;
+    let summary = summaries
+        .iter()
+        .find(|s| s.project.name == project.name)
+        .expect("project summary");
+    assert_eq!(summary.task_count, 2);
+    assert_eq!(summary.open_task_count, 1);
+    assert_eq!(
+        summary.last_activity,
+        Some("2025-02-01T00:00:00Z".parse().unwrap())
+    );
+
     Ok(())
 }
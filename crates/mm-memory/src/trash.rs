@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+use crate::entity::MemoryEntity;
+use crate::labels::DELETED_LABEL;
+use crate::update::{EntityUpdate, LabelsUpdate, PropertiesUpdate};
+use crate::value::MemoryValue;
+
+/// Entity property recording when it was moved to the trash
+pub const DELETED_AT_PROPERTY: &str = "deleted_at";
+
+/// Whether `entity` is in the trash
+pub fn is_deleted(entity: &MemoryEntity) -> bool {
+    entity.labels.iter().any(|l| l == DELETED_LABEL)
+}
+
+/// Update moving an entity to the trash at `now`
+pub fn trash_update(now: DateTime<Utc>) -> EntityUpdate {
+    EntityUpdate {
+        labels: Some(LabelsUpdate {
+            add: Some(vec![DELETED_LABEL.to_string()]),
+            remove: None,
+        }),
+        properties: Some(PropertiesUpdate {
+            add: Some(HashMap::from([(
+                DELETED_AT_PROPERTY.to_string(),
+                MemoryValue::DateTime(now.into()),
+            )])),
+            remove: None,
+            set: None,
+        }),
+        observations: None,
+        expected_revision: None,
+        force: false,
+    }
+}
+
+/// Update taking an entity out of the trash
+pub fn restore_update() -> EntityUpdate {
+    EntityUpdate {
+        labels: Some(LabelsUpdate {
+            add: None,
+            remove: Some(vec![DELETED_LABEL.to_string()]),
+        }),
+        properties: Some(PropertiesUpdate {
+            add: None,
+            remove: Some(vec![DELETED_AT_PROPERTY.to_string()]),
+            set: None,
+        }),
+        observations: None,
+        expected_revision: None,
+        force: false,
+    }
+}
@@ -0,0 +1,326 @@
+use schemars::JsonSchema;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use tracing::instrument;
+
+use crate::property_rules::{PropertyRule, check_properties};
+use crate::{
+    BatchItemError, GraphBatchErrors, GraphPreview, LabelMatchMode, MemoryEntity,
+    MemoryRelationship, MemoryRepository, MemoryResult, MemoryService, ValidationError,
+    ValidationErrorKind, value::MemoryValue,
+};
+
+/// Typed properties of one kind of entity, e.g. tasks or projects
+///
+/// Every entity of the kind carries [`EntityKind::LABEL`]. Implementing this
+/// trait is enough to get create and lookup support through
+/// [`MemoryService::typed`].
+pub trait EntityKind:
+    JsonSchema
+    + Into<HashMap<String, MemoryValue>>
+    + From<HashMap<String, MemoryValue>>
+    + Clone
+    + std::fmt::Debug
+    + Default
+{
+    /// Label identifying entities of this kind
+    const LABEL: &'static str;
+
+    /// Constraints on property values, keyed by property name
+    const RULES: &'static [(&'static str, PropertyRule)] = &[];
+
+    /// Check `properties` against [`Self::RULES`]
+    ///
+    /// Only properties present in the map are checked, so partial updates
+    /// can be validated on their own.
+    fn validate(properties: &HashMap<String, MemoryValue>) -> Vec<ValidationErrorKind> {
+        check_properties(Self::RULES, properties, chrono::Utc::now().date_naive())
+    }
+}
+
+/// View of a [`MemoryService`] restricted to one kind of entity
+///
+/// Created entities get the kind's label, and lookups only return entities
+/// that carry it.
+pub struct TypedMemoryService<'a, R, P>
+where
+    R: MemoryRepository,
+{
+    service: &'a MemoryService<R>,
+    kind: PhantomData<P>,
+}
+
+impl<R: MemoryRepository + Sync> MemoryService<R> {
+    /// Access entities of kind `P` with typed properties
+    pub fn typed<P: EntityKind>(&self) -> TypedMemoryService<'_, R, P> {
+        TypedMemoryService {
+            service: self,
+            kind: PhantomData,
+        }
+    }
+}
+
+impl<R, P> TypedMemoryService<'_, R, P>
+where
+    R: MemoryRepository + Sync,
+    P: EntityKind,
+{
+    /// Create entities of this kind for `project`
+    ///
+    /// Entities breaking the kind's [`RULES`](EntityKind::RULES) are
+    /// reported and skipped. The kind's label is added to the others before
+    /// the usual validation runs.
+    #[instrument(skip(self, entities), fields(kind = P::LABEL, entities_count = entities.len()))]
+    pub async fn create(
+        &self,
+        project: Option<&str>,
+        entities: &[MemoryEntity<P>],
+    ) -> MemoryResult<Vec<BatchItemError>, R::Error> {
+        let mut errors = Vec::new();
+        let mut labelled: Vec<MemoryEntity<P>> = Vec::with_capacity(entities.len());
+        // Position in `entities` of each entry in `labelled`
+        let mut positions = Vec::with_capacity(entities.len());
+        for (index, entity) in entities.iter().enumerate() {
+            let errs = P::validate(&entity.properties.clone().into());
+            if !errs.is_empty() {
+                errors.push(BatchItemError::new(
+                    index,
+                    &entity.name,
+                    ValidationError(errs),
+                ));
+                continue;
+            }
+            let mut entity = entity.clone();
+            if !entity.labels.iter().any(|l| l == P::LABEL) {
+                entity.labels.push(P::LABEL.to_string());
+            }
+            labelled.push(entity);
+            positions.push(index);
+        }
+        let service_errors = self
+            .service
+            .create_project_entities_typed(project, &labelled)
+            .await?;
+        errors.extend(
+            service_errors
+                .into_iter()
+                .map(|e| BatchItemError::new(positions[e.index], e.name, e.error)),
+        );
+        errors.sort_by_key(|e| e.index);
+        Ok(errors)
+    }
+
+    /// Create entities of this kind together with `relationships` for
+    /// `project`
+    ///
+    /// Nothing is written when an entity breaks the kind's
+    /// [`RULES`](EntityKind::RULES) or the service rejects any item;
+    /// otherwise both batches are stored atomically.
+    #[instrument(skip(self, entities, relationships), fields(kind = P::LABEL, entities_count = entities.len(), rel_count = relationships.len()))]
+    pub async fn create_with_relationships(
+        &self,
+        project: Option<&str>,
+        entities: &[MemoryEntity<P>],
+        relationships: &[MemoryRelationship],
+    ) -> MemoryResult<GraphBatchErrors, R::Error> {
+        match Self::labelled(entities) {
+            Ok(labelled) => {
+                self.service
+                    .create_project_graph_typed(project, &labelled, relationships)
+                    .await
+            }
+            Err(errors) => Ok(GraphBatchErrors {
+                entities: errors,
+                relationships: Vec::new(),
+            }),
+        }
+    }
+
+    /// Validate entities of this kind together with `relationships` for
+    /// `project` like [`create_with_relationships`](Self::create_with_relationships)
+    /// does, without writing anything
+    #[instrument(skip(self, entities, relationships), fields(kind = P::LABEL, entities_count = entities.len(), rel_count = relationships.len()))]
+    pub async fn preview_with_relationships(
+        &self,
+        project: Option<&str>,
+        entities: &[MemoryEntity<P>],
+        relationships: &[MemoryRelationship],
+    ) -> MemoryResult<GraphPreview, R::Error> {
+        match Self::labelled(entities) {
+            Ok(labelled) => {
+                self.service
+                    .preview_project_graph_typed(project, &labelled, relationships)
+                    .await
+            }
+            Err(errors) => Ok(GraphPreview {
+                errors: GraphBatchErrors {
+                    entities: errors,
+                    relationships: Vec::new(),
+                },
+                ..GraphPreview::default()
+            }),
+        }
+    }
+
+    /// `entities` with the kind's label added, or the errors of those
+    /// breaking the kind's [`RULES`](EntityKind::RULES)
+    fn labelled(entities: &[MemoryEntity<P>]) -> Result<Vec<MemoryEntity<P>>, Vec<BatchItemError>> {
+        let errors: Vec<BatchItemError> = entities
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entity)| {
+                let errs = P::validate(&entity.properties.clone().into());
+                (!errs.is_empty())
+                    .then(|| BatchItemError::new(index, &entity.name, ValidationError(errs)))
+            })
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(entities
+            .iter()
+            .cloned()
+            .map(|mut entity| {
+                if !entity.labels.iter().any(|l| l == P::LABEL) {
+                    entity.labels.push(P::LABEL.to_string());
+                }
+                entity
+            })
+            .collect())
+    }
+
+    /// Find an entity of this kind by name
+    ///
+    /// Returns `None` when the entity exists but is of another kind.
+    #[instrument(skip(self), fields(kind = P::LABEL))]
+    pub async fn find(&self, name: &str) -> MemoryResult<Option<MemoryEntity<P>>, R::Error> {
+        let entity = self.service.find_entity_by_name_typed::<P>(name).await?;
+        Ok(entity.filter(|e| e.labels.iter().any(|l| l == P::LABEL)))
+    }
+
+    /// All entities of this kind
+    #[instrument(skip(self), fields(kind = P::LABEL))]
+    pub async fn list(&self) -> MemoryResult<Vec<MemoryEntity<P>>, R::Error> {
+        self.service
+            .find_entities_by_labels_typed::<P>(&[P::LABEL.to_string()], LabelMatchMode::Any, None)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemoryConfig, MockMemoryRepository};
+
+    #[derive(Debug, Clone, Default, JsonSchema)]
+    struct Decision {
+        outcome: String,
+    }
+
+    impl From<HashMap<String, MemoryValue>> for Decision {
+        fn from(mut map: HashMap<String, MemoryValue>) -> Self {
+            match map.remove("outcome") {
+                Some(MemoryValue::String(outcome)) => Decision { outcome },
+                _ => Decision::default(),
+            }
+        }
+    }
+
+    impl From<Decision> for HashMap<String, MemoryValue> {
+        fn from(decision: Decision) -> Self {
+            HashMap::from([("outcome".to_string(), MemoryValue::String(decision.outcome))])
+        }
+    }
+
+    impl EntityKind for Decision {
+        const LABEL: &'static str = "Decision";
+        const RULES: &'static [(&'static str, PropertyRule)] =
+            &[("outcome", PropertyRule::OneOf(&["ship", "hold"]))];
+    }
+
+    fn config() -> MemoryConfig {
+        MemoryConfig {
+            default_label: None,
+            allowed_labels: ["Decision".to_string()].into(),
+            ..MemoryConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_adds_kind_label() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_create_entities()
+            .withf(|entities| {
+                entities[0].labels == ["Decision"]
+                    && entities[0].properties.get("outcome")
+                        == Some(&MemoryValue::String("ship".to_string()))
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        let service = MemoryService::new(mock, config());
+
+        let errors = service
+            .typed::<Decision>()
+            .create(
+                None,
+                &[MemoryEntity {
+                    name: "decision:ship".to_string(),
+                    properties: Decision {
+                        outcome: "ship".to_string(),
+                    },
+                    ..Default::default()
+                }],
+            )
+            .await
+            .unwrap();
+        assert!(errors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_enforces_rules() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_create_entities().never();
+        let service = MemoryService::new(mock, config());
+
+        let errors = service
+            .typed::<Decision>()
+            .create(
+                None,
+                &[MemoryEntity {
+                    name: "decision:maybe".to_string(),
+                    properties: Decision {
+                        outcome: "maybe".to_string(),
+                    },
+                    ..Default::default()
+                }],
+            )
+            .await
+            .unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0].error.0[..],
+            [ValidationErrorKind::InvalidEnumValue { property, .. }] if property == "outcome"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_find_requires_kind_label() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|name| {
+            Ok(Some(MemoryEntity {
+                name: name.to_string(),
+                labels: vec![if name == "decision:ship" {
+                    "Decision".to_string()
+                } else {
+                    "Note".to_string()
+                }],
+                ..Default::default()
+            }))
+        });
+        let service = MemoryService::new(mock, config());
+        let decisions = service.typed::<Decision>();
+
+        assert!(decisions.find("decision:ship").await.unwrap().is_some());
+        assert!(decisions.find("note:ship").await.unwrap().is_none());
+    }
+}
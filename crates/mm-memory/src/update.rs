@@ -1,7 +1,9 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::error::Error as StdError;
 
+use crate::error::{MemoryError, MemoryResult};
 use crate::value::MemoryValue;
 
 /// Operations to modify an entity
@@ -16,6 +18,46 @@ pub struct EntityUpdate {
     /// Labels modifications
     #[serde(skip_serializing_if = "Option::is_none")]
     pub labels: Option<LabelsUpdate>,
+    /// Fail with a conflict unless the entity is still at this revision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_revision: Option<u64>,
+    /// Remove reserved labels as well
+    #[serde(default)]
+    pub force: bool,
+}
+
+impl EntityUpdate {
+    /// Check `expected_revision` against the revision `name` is stored at
+    ///
+    /// `actual` is `None` when the entity does not exist. Backends call this
+    /// while the entity is guarded against concurrent writers, so that the
+    /// revision cannot move before the update is applied.
+    pub fn check_revision<E>(&self, name: &str, actual: Option<u64>) -> MemoryResult<(), E>
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        let Some(expected) = self.expected_revision else {
+            return Ok(());
+        };
+        match actual {
+            None => Err(MemoryError::EntityNotFound(name.to_string())),
+            Some(actual) if actual != expected => Err(MemoryError::RevisionConflict {
+                name: name.to_string(),
+                expected,
+                actual,
+            }),
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+/// Changes to apply to one entity of a batch
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct NamedEntityUpdate {
+    /// Entity to update
+    pub name: String,
+    /// Changes to apply
+    pub update: EntityUpdate,
 }
 
 /// Update operations for observations
@@ -32,6 +74,26 @@ pub struct ObservationsUpdate {
     pub set: Option<Vec<String>>,
 }
 
+impl ObservationsUpdate {
+    /// Apply the update to `observations` the way the backends do
+    ///
+    /// `set` takes precedence over `add`, which takes precedence over
+    /// `remove`. Added observations that are already present are skipped.
+    pub fn apply_to(&self, observations: &mut Vec<String>) {
+        if let Some(set) = &self.set {
+            observations.clone_from(set);
+        } else if let Some(add) = &self.add {
+            for observation in add {
+                if !observations.contains(observation) {
+                    observations.push(observation.clone());
+                }
+            }
+        } else if let Some(remove) = &self.remove {
+            observations.retain(|o| !remove.contains(o));
+        }
+    }
+}
+
 /// Update operations for properties
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct PropertiesUpdate {
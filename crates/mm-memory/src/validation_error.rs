@@ -38,6 +38,142 @@ pub enum ValidationErrorKind {
     /// Error when a task depends on a non-existent task
     #[error("Dependency '{0}' not found")]
     DependencyNotFound(String),
+
+    /// Error when tasks or other entities depend on each other in a loop
+    #[error("Entities '{0}' form a dependency cycle")]
+    DependencyCycle(String),
+
+    /// Error when a label, relationship type or property key cannot be used
+    /// as a query identifier
+    #[error("Identifier '{0}' is not valid")]
+    InvalidIdentifier(String),
+
+    /// Error when an observation has no text
+    #[error("Observation cannot be empty")]
+    EmptyObservation,
+
+    /// Error when a confidence score is not a number between 0 and 1
+    #[error("Confidence for '{0}' must be a number between 0 and 1")]
+    InvalidConfidence(String),
+
+    /// Error when a source citation has no URL, file or conversation
+    #[error("Source must have a url, file or conversation_id")]
+    EmptySource,
+
+    /// Error when a relationship connects an entity to itself and its type
+    /// does not allow that
+    #[error("Relationship '{0}' cannot connect an entity to itself")]
+    SelfLoop(String),
+
+    /// Error when the same relationship appears twice in a batch, or already
+    /// exists when merging
+    #[error("Relationship '{0}' already exists")]
+    DuplicateRelationship(String),
+
+    /// Error when starting a task would exceed a work-in-progress limit
+    #[error("WIP limit exceeded: {0}")]
+    WipLimitExceeded(String),
+
+    /// Error when a date is in none of the accepted formats
+    #[error("'{0}' is not a recognized date")]
+    InvalidDate(String),
+
+    /// Error when a property must take one of a fixed set of values
+    #[error("Property '{property}' must be one of {allowed}, got '{value}'")]
+    InvalidEnumValue {
+        property: String,
+        value: String,
+        allowed: String,
+    },
+
+    /// Error when a numeric property is outside its allowed range
+    #[error("Property '{property}' must be {range}, got {value}")]
+    OutOfRange {
+        property: String,
+        value: String,
+        range: String,
+    },
+
+    /// Error when a property holds a value of the wrong type
+    #[error("Property '{property}' must be a {expected}")]
+    InvalidPropertyType {
+        property: String,
+        expected: &'static str,
+    },
+
+    /// Error when a date property lies further in the past than allowed
+    #[error("Property '{property}' is {date}, more than {max_days} days ago")]
+    DateTooFarInPast {
+        property: String,
+        date: String,
+        max_days: u64,
+    },
+
+    /// Error when a pagination cursor was not issued by a previous page
+    #[error("Cursor '{0}' is not valid")]
+    InvalidCursor(String),
+
+    /// Error when a valid item could not be written to the backend
+    #[error("Not stored: {0}")]
+    NotStored(String),
+
+    /// Error when an entity would take a name that is already in use
+    #[error("Entity '{0}' already exists")]
+    EntityExists(String),
+
+    /// Error when an entity to restore is not in the trash
+    #[error("Entity '{0}' is not in the trash")]
+    NotDeleted(String),
+
+    /// Error when a search pattern is empty or not a valid regular expression
+    #[error("Pattern '{0}' is not valid")]
+    InvalidPattern(String),
+
+    /// Error when a page size is zero
+    #[error("Limit must be at least 1")]
+    InvalidLimit,
+
+    /// Error when a property is not among those declared for the item
+    #[error("Property '{0}' is not allowed")]
+    UnknownProperty(String),
+
+    /// Error when an end of a relationship lacks the labels its type requires
+    #[error("Relationship '{relationship}' needs '{entity}' to have one of the labels {labels}")]
+    InvalidEndpoint {
+        relationship: String,
+        entity: String,
+        labels: String,
+    },
+
+    /// Error when an entity would have more relationships of a type than
+    /// allowed
+    #[error("'{entity}' can have at most {max} {direction} '{relationship}' relationships")]
+    CardinalityExceeded {
+        relationship: String,
+        entity: String,
+        direction: &'static str,
+        max: usize,
+    },
+
+    /// Error when no template has the given name
+    #[error("Template '{0}' does not exist")]
+    UnknownTemplate(String),
+
+    /// Error when a template entity does not hold a valid template
+    #[error("Entity '{0}' does not hold a valid template")]
+    InvalidTemplate(String),
+
+    /// Error when removing a reserved label without forcing it
+    #[error("Label '{0}' is reserved and can only be removed with force")]
+    ReservedLabel(String),
+
+    /// Error when deleting a reserved relationship without forcing it
+    #[error("Relationship '{0}' is reserved and can only be deleted with force")]
+    ReservedRelationship(String),
+
+    /// Error when an entity name does not follow the naming policy
+    #[error("Entity name '{name}' does not follow the naming policy: {hint}")]
+    InvalidEntityName { name: String, hint: String },
 }
 
 /// Collection of validation errors
@@ -58,3 +194,83 @@ impl From<ValidationErrorKind> for ValidationError {
         Self(vec![kind])
     }
 }
+
+/// Validation errors of one item in a batch
+///
+/// Items are identified by position because names may be empty or repeated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchItemError {
+    /// Position of the item in the batch
+    pub index: usize,
+    /// Name of the item as given, possibly empty
+    pub name: String,
+    /// Location of the offending input, e.g. `tasks[2].depends_on[0]`
+    pub path: String,
+    pub error: ValidationError,
+}
+
+impl BatchItemError {
+    /// Errors of the item at `index`, located at `[index]`
+    pub fn new(index: usize, name: impl Into<String>, error: ValidationError) -> Self {
+        Self {
+            index,
+            name: name.into(),
+            path: format!("[{index}]"),
+            error,
+        }
+    }
+
+    /// Prefix the path with the request field holding the batch
+    pub fn in_field(mut self, field: &str) -> Self {
+        self.path.insert_str(0, field);
+        self
+    }
+
+    /// Append a location within the item, e.g. `.depends_on[0]`
+    pub fn at(mut self, location: &str) -> Self {
+        self.path.push_str(location);
+        self
+    }
+}
+
+impl std::fmt::Display for BatchItemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.name.is_empty() {
+            write!(f, "{}: {}", self.path, self.error)
+        } else {
+            write!(f, "{} ('{}'): {}", self.path, self.name, self.error)
+        }
+    }
+}
+
+/// Validation errors of entities and relationships created together
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphBatchErrors {
+    pub entities: Vec<BatchItemError>,
+    pub relationships: Vec<BatchItemError>,
+}
+
+impl GraphBatchErrors {
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty() && self.relationships.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_item_error_paths() {
+        let error = BatchItemError::new(2, "task:a", ValidationErrorKind::EmptyObservation.into())
+            .in_field("tasks")
+            .at(".depends_on[0]");
+        assert_eq!(error.path, "tasks[2].depends_on[0]");
+        assert_eq!(
+            error.to_string(),
+            "tasks[2].depends_on[0] ('task:a'): Observation cannot be empty"
+        );
+        let unnamed = BatchItemError::new(0, "", ValidationErrorKind::EmptyEntityName.into());
+        assert_eq!(unnamed.to_string(), "[0]: Entity name cannot be empty");
+    }
+}
@@ -87,6 +87,13 @@ pub enum MemoryValue {
         description = "Duration in nanoseconds"
     )]
     Duration(Duration),
+    // Nested lists and objects; last so flat values keep their variants
+    #[schemars(
+        with = "serde_json::Value",
+        title = "JSON",
+        description = "Nested lists and objects"
+    )]
+    Json(serde_json::Value),
 }
 
 impl From<MemoryValue> for serde_json::Value {
@@ -121,6 +128,7 @@ impl From<MemoryValue> for serde_json::Value {
             MemoryValue::DateTime(dt) => serde_json::Value::String(dt.to_rfc3339()),
             MemoryValue::LocalDateTime(dt) => serde_json::Value::String(dt.to_string()),
             MemoryValue::Duration(d) => serde_json::Value::String(format!("{}", d.as_nanos())),
+            MemoryValue::Json(value) => value,
         }
     }
 }
@@ -141,28 +149,22 @@ impl TryFrom<serde_json::Value> for MemoryValue {
                     MemoryValue::String(n.to_string())
                 }
             }
-            serde_json::Value::Array(arr) => {
-                // Convert array to list of strings
-                let strings = arr
-                    .into_iter()
-                    .map(|v| match v {
-                        serde_json::Value::String(s) => s,
-                        _ => v.to_string(),
-                    })
-                    .collect();
-                MemoryValue::List(strings)
+            serde_json::Value::Array(arr) if arr.iter().all(serde_json::Value::is_string) => {
+                MemoryValue::List(
+                    arr.into_iter()
+                        .filter_map(|v| v.as_str().map(str::to_string))
+                        .collect(),
+                )
             }
-            serde_json::Value::Object(obj) => {
-                // Convert object to map of strings
-                let mut map = HashMap::new();
-                for (k, v) in obj {
-                    let value_str = match v {
-                        serde_json::Value::String(s) => s,
-                        _ => v.to_string(),
-                    };
-                    map.insert(k, value_str);
-                }
-                MemoryValue::Map(map)
+            serde_json::Value::Object(obj) if obj.values().all(serde_json::Value::is_string) => {
+                MemoryValue::Map(
+                    obj.into_iter()
+                        .filter_map(|(k, v)| v.as_str().map(|s| (k, s.to_string())))
+                        .collect(),
+                )
+            }
+            value @ (serde_json::Value::Array(_) | serde_json::Value::Object(_)) => {
+                MemoryValue::Json(value)
             }
             serde_json::Value::Null => MemoryValue::String("null".to_string()),
         })
@@ -184,6 +186,29 @@ impl std::fmt::Display for MemoryValue {
             MemoryValue::DateTime(dt) => write!(f, "{}", dt),
             MemoryValue::LocalDateTime(dt) => write!(f, "{}", dt),
             MemoryValue::Duration(d) => write!(f, "{:?}", d),
+            MemoryValue::Json(value) => write!(f, "{}", value),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_nested_values_keep_their_structure() {
+        let nested = json!({"owners": [{"name": "a", "weight": 2}], "active": true});
+        let value: MemoryValue = serde_json::from_value(nested.clone()).unwrap();
+        assert_eq!(value, MemoryValue::Json(nested.clone()));
+        assert_eq!(MemoryValue::try_from(nested.clone()).unwrap(), value);
+        assert_eq!(serde_json::Value::from(value), nested);
+
+        let flat: MemoryValue = serde_json::from_value(json!(["a", "b"])).unwrap();
+        assert_eq!(flat, MemoryValue::List(vec!["a".into(), "b".into()]));
+        assert_eq!(
+            MemoryValue::try_from(json!({"k": "v"})).unwrap(),
+            MemoryValue::Map(HashMap::from([("k".into(), "v".into())]))
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Python bindings for the [`middle_manager`] facade.
+//!
+//! Build with `maturin develop` and use it from scripts or notebooks:
+//!
+//! ```python
+//! from mm_python import MiddleManager
+//!
+//! mm = MiddleManager.connect(["config/default.toml", "config/local.toml"])
+//! print(mm.list_tasks("my-project"))
+//! ```
+//!
+//! Entities, tasks and contexts cross the boundary as the same dicts the MCP
+//! tools take and return; validation happens in the shared core.
+#![warn(clippy::all)]
+
+use std::error::Error as StdError;
+use std::future::Future;
+use std::path::PathBuf;
+
+use middle_manager::memory::{LabelMatchMode, MemoryEntity};
+use middle_manager::operations::memory::{ProjectFilter, TaskInput};
+use middle_manager::{BackendError, CoreError, MiddleManager};
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pythonize::{depythonize, pythonize};
+use serde::Serialize;
+
+/// Error message followed by its sources, `: `-separated
+fn error_message(error: &dyn StdError) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+fn core_error(error: CoreError<BackendError>) -> PyErr {
+    let message = error_message(&error);
+    match error {
+        CoreError::Validation(_) | CoreError::BatchValidation(_) | CoreError::MissingProject => {
+            PyValueError::new_err(message)
+        }
+        _ => PyRuntimeError::new_err(message),
+    }
+}
+
+fn to_python<T: Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    Ok(pythonize(py, value)?.unbind())
+}
+
+/// Handle to a memory graph
+#[pyclass(name = "MiddleManager", frozen)]
+struct PyMiddleManager {
+    inner: MiddleManager,
+    runtime: tokio::runtime::Runtime,
+}
+
+impl PyMiddleManager {
+    /// Run `future` to completion without holding the GIL
+    fn block_on<F>(&self, py: Python<'_>, future: F) -> F::Output
+    where
+        F: Future + Send,
+        F::Output: Send,
+    {
+        py.allow_threads(|| self.runtime.block_on(future))
+    }
+}
+
+#[pymethods]
+impl PyMiddleManager {
+    /// Load the configuration files, as `mm-cli --config` does, and connect
+    #[staticmethod]
+    fn connect(py: Python<'_>, config_paths: Vec<PathBuf>) -> PyResult<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let inner = py
+            .allow_threads(|| runtime.block_on(MiddleManager::from_config_files(&config_paths)))
+            .map_err(|e| PyRuntimeError::new_err(format!("{e:#}")))?;
+        Ok(Self { inner, runtime })
+    }
+
+    /// Create entities; returns the warnings about applied defaults
+    #[pyo3(signature = (entities, project=None))]
+    fn create_entities(
+        &self,
+        py: Python<'_>,
+        entities: &Bound<'_, PyAny>,
+        project: Option<&str>,
+    ) -> PyResult<PyObject> {
+        let entities: Vec<MemoryEntity> = depythonize(entities)?;
+        let outcome = self
+            .block_on(py, self.inner.create_entities(entities, project))
+            .map_err(core_error)?;
+        to_python(py, &outcome.warnings)
+    }
+
+    /// Entity named `name`, or `None`
+    fn get_entity(&self, py: Python<'_>, name: &str) -> PyResult<PyObject> {
+        let entity = self
+            .block_on(py, self.inner.get_entity(name))
+            .map_err(core_error)?;
+        to_python(py, &entity)
+    }
+
+    /// Entities with any of `labels`, or all of them when `match_all` is set
+    #[pyo3(signature = (labels, match_all=false))]
+    fn find_entities_by_labels(
+        &self,
+        py: Python<'_>,
+        labels: Vec<String>,
+        match_all: bool,
+    ) -> PyResult<PyObject> {
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+        let match_mode = if match_all {
+            LabelMatchMode::All
+        } else {
+            LabelMatchMode::Any
+        };
+        let entities = self
+            .block_on(py, self.inner.find_entities_by_labels(&labels, match_mode))
+            .map_err(core_error)?;
+        to_python(py, &entities)
+    }
+
+    /// Create tasks given as `{"task": {...}, "depends_on": [...]}` dicts
+    #[pyo3(signature = (tasks, project=None))]
+    fn create_tasks(
+        &self,
+        py: Python<'_>,
+        tasks: &Bound<'_, PyAny>,
+        project: Option<&str>,
+    ) -> PyResult<()> {
+        let tasks: Vec<TaskInput> = depythonize(tasks)?;
+        self.block_on(py, self.inner.create_tasks(tasks, project))
+            .map_err(core_error)
+    }
+
+    /// Tasks of `project`, or the default project
+    #[pyo3(signature = (project=None))]
+    fn list_tasks(&self, py: Python<'_>, project: Option<&str>) -> PyResult<PyObject> {
+        let tasks = self
+            .block_on(py, self.inner.list_tasks(project))
+            .map_err(core_error)?;
+        to_python(py, &tasks)
+    }
+
+    /// Context of the project named `name` or linked to `repository`
+    #[pyo3(signature = (name=None, repository=None))]
+    fn project_context(
+        &self,
+        py: Python<'_>,
+        name: Option<String>,
+        repository: Option<String>,
+    ) -> PyResult<PyObject> {
+        let filter = match (name, repository) {
+            (Some(name), None) => ProjectFilter::Name(name),
+            (None, Some(repository)) => ProjectFilter::Repository(repository),
+            _ => {
+                return Err(PyValueError::new_err(
+                    "pass exactly one of `name` and `repository`",
+                ));
+            }
+        };
+        let context = self
+            .block_on(py, self.inner.project_context(filter))
+            .map_err(core_error)?;
+        to_python(py, &context)
+    }
+}
+
+#[pymodule]
+fn mm_python(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyMiddleManager>()
+}
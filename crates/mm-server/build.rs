@@ -0,0 +1,23 @@
+use std::process::Command;
+
+fn main() {
+    // Short hash of the commit the server was built from, when built from git
+    if let Some(commit) = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+    {
+        println!("cargo:rustc-env=MM_GIT_COMMIT={}", commit.trim());
+    }
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+
+    // Cargo features enabled for this build, comma separated
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(str::to_lowercase))
+        .map(|feature| feature.replace('_', "-"))
+        .collect();
+    features.sort();
+    println!("cargo:rustc-env=MM_FEATURES={}", features.join(","));
+}
@@ -0,0 +1,504 @@
+//! Memory and git backends compiled into the server.
+//!
+//! Each memory backend sits behind a cargo feature. [`MemoryBackend`] wraps
+//! whichever of them the configuration selects so the rest of the server
+//! works with a single repository type.
+
+#[cfg(not(any(
+    feature = "neo4j",
+    feature = "inmem",
+    feature = "jsonl",
+    feature = "surrealdb"
+)))]
+compile_error!("enable at least one memory backend feature: neo4j, inmem, jsonl or surrealdb");
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use mm_memory::relationship::RelationshipRef;
+use mm_memory::{
+    BackendInfo, EntitySnapshot, EntityStream, EntityUpdate, FuzzyMatch, LabelMatchMode,
+    MemoryConfig, MemoryEntity, MemoryError, MemoryRelationship, MemoryRepository, MemoryResult,
+    NamedEntityUpdate, ProjectSummary, PropertyFilter, PropertySort, RelatedQuery,
+    RelationshipDirection, RelationshipUpdate, TaskFilter,
+};
+use serde::{Deserialize, Serialize};
+use std::error::Error as StdError;
+use std::fmt;
+
+use crate::Config;
+
+/// Memory backend chosen with the `backend` config key
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    #[default]
+    Neo4j,
+    Inmem,
+    Jsonl,
+    Surrealdb,
+}
+
+impl fmt::Display for BackendKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BackendKind::Neo4j => "neo4j",
+            BackendKind::Inmem => "inmem",
+            BackendKind::Jsonl => "jsonl",
+            BackendKind::Surrealdb => "surrealdb",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Error of whichever memory backend is in use
+#[derive(Debug)]
+pub struct BackendError(Box<dyn StdError + Send + Sync>);
+
+impl BackendError {
+    fn new<E: StdError + Send + Sync + 'static>(error: E) -> Self {
+        Self(Box::new(error))
+    }
+}
+
+impl fmt::Display for BackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl StdError for BackendError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.0.source()
+    }
+}
+
+fn wrap<E: StdError + Send + Sync + 'static>(error: MemoryError<E>) -> MemoryError<BackendError> {
+    error.map_source(BackendError::new)
+}
+
+fn wrap_stream<E: StdError + Send + Sync + 'static>(
+    stream: EntityStream<E>,
+) -> EntityStream<BackendError> {
+    stream.map(|entity| entity.map_err(wrap)).boxed()
+}
+
+/// Memory repository of the backend selected at runtime
+pub enum MemoryBackend {
+    #[cfg(feature = "neo4j")]
+    Neo4j(mm_memory_neo4j::Neo4jRepository),
+    #[cfg(feature = "inmem")]
+    Inmem(mm_memory_inmem::InMemoryRepository),
+    #[cfg(feature = "jsonl")]
+    Jsonl(mm_memory_jsonl::JsonlRepository),
+    #[cfg(feature = "surrealdb")]
+    Surrealdb(mm_memory_surrealdb::SurrealRepository),
+}
+
+impl MemoryBackend {
+    /// Connect to the backend selected in `config`
+    ///
+    /// Fails if that backend was not compiled in.
+    pub async fn connect(
+        config: &Config,
+        memory_config: &MemoryConfig,
+    ) -> MemoryResult<Self, BackendError> {
+        // Unused when the selected backend is compiled out
+        let _ = memory_config;
+        match config.backend {
+            #[cfg(feature = "neo4j")]
+            BackendKind::Neo4j => {
+                mm_memory_neo4j::create_neo4j_repository(config.neo4j.clone(), memory_config)
+                    .await
+                    .map(Self::Neo4j)
+                    .map_err(wrap)
+            }
+            #[cfg(feature = "inmem")]
+            BackendKind::Inmem => Ok(Self::Inmem(mm_memory_inmem::InMemoryRepository::new())),
+            #[cfg(feature = "jsonl")]
+            BackendKind::Jsonl => {
+                let jsonl = config.jsonl.as_ref().ok_or_else(|| {
+                    MemoryError::connection_error("backend is jsonl but [jsonl] is not configured")
+                })?;
+                mm_memory_jsonl::JsonlRepository::open(&jsonl.path)
+                    .await
+                    .map(Self::Jsonl)
+                    .map_err(wrap)
+            }
+            #[cfg(feature = "surrealdb")]
+            BackendKind::Surrealdb => {
+                let surrealdb = config.surrealdb.clone().ok_or_else(|| {
+                    MemoryError::connection_error(
+                        "backend is surrealdb but [surrealdb] is not configured",
+                    )
+                })?;
+                mm_memory_surrealdb::SurrealRepository::connect(surrealdb)
+                    .await
+                    .map(Self::Surrealdb)
+                    .map_err(wrap)
+            }
+            #[allow(unreachable_patterns)]
+            other => Err(MemoryError::connection_error(format!(
+                "the {other} backend is not compiled in; rebuild with `--features {other}`"
+            ))),
+        }
+    }
+}
+
+/// Run `$body` with `$repo` bound to the active backend's repository
+macro_rules! dispatch {
+    ($self:expr, $repo:ident => $body:expr) => {
+        match $self {
+            #[cfg(feature = "neo4j")]
+            MemoryBackend::Neo4j($repo) => $body,
+            #[cfg(feature = "inmem")]
+            MemoryBackend::Inmem($repo) => $body,
+            #[cfg(feature = "jsonl")]
+            MemoryBackend::Jsonl($repo) => $body,
+            #[cfg(feature = "surrealdb")]
+            MemoryBackend::Surrealdb($repo) => $body,
+        }
+    };
+}
+
+#[async_trait]
+impl MemoryRepository for MemoryBackend {
+    type Error = BackendError;
+
+    fn backend_info(&self) -> BackendInfo {
+        dispatch!(self, repo => repo.backend_info())
+    }
+
+    async fn create_entities(&self, entities: &[MemoryEntity]) -> MemoryResult<(), Self::Error> {
+        dispatch!(self, repo => repo.create_entities(entities).await.map_err(wrap))
+    }
+
+    async fn find_entity_by_name(
+        &self,
+        name: &str,
+    ) -> MemoryResult<Option<MemoryEntity>, Self::Error> {
+        dispatch!(self, repo => repo.find_entity_by_name(name).await.map_err(wrap))
+    }
+
+    async fn find_entity_snapshot(
+        &self,
+        name: &str,
+        queries: &[RelatedQuery],
+    ) -> MemoryResult<Option<EntitySnapshot>, Self::Error> {
+        dispatch!(self, repo => repo.find_entity_snapshot(name, queries).await.map_err(wrap))
+    }
+
+    async fn find_path(
+        &self,
+        from: &str,
+        to: &str,
+        max_depth: u32,
+        types: &[String],
+    ) -> MemoryResult<Option<Vec<MemoryRelationship>>, Self::Error> {
+        dispatch!(self, repo => repo.find_path(from, to, max_depth, types).await.map_err(wrap))
+    }
+
+    async fn find_existing_names(
+        &self,
+        names: &[String],
+    ) -> MemoryResult<Vec<String>, Self::Error> {
+        dispatch!(self, repo => repo.find_existing_names(names).await.map_err(wrap))
+    }
+
+    async fn set_observations(
+        &self,
+        name: &str,
+        observations: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        dispatch!(self, repo => repo.set_observations(name, observations).await.map_err(wrap))
+    }
+
+    async fn add_observations(
+        &self,
+        name: &str,
+        observations: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        dispatch!(self, repo => repo.add_observations(name, observations).await.map_err(wrap))
+    }
+
+    async fn remove_all_observations(&self, name: &str) -> MemoryResult<(), Self::Error> {
+        dispatch!(self, repo => repo.remove_all_observations(name).await.map_err(wrap))
+    }
+
+    async fn remove_observations(
+        &self,
+        name: &str,
+        observations: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        dispatch!(self, repo => repo.remove_observations(name, observations).await.map_err(wrap))
+    }
+
+    async fn create_relationships(
+        &self,
+        relationships: &[MemoryRelationship],
+    ) -> MemoryResult<(), Self::Error> {
+        dispatch!(self, repo => repo.create_relationships(relationships).await.map_err(wrap))
+    }
+
+    async fn create_graph(
+        &self,
+        entities: &[MemoryEntity],
+        relationships: &[MemoryRelationship],
+    ) -> MemoryResult<(), Self::Error> {
+        dispatch!(self, repo => repo.create_graph(entities, relationships).await.map_err(wrap))
+    }
+
+    async fn delete_entities(&self, names: &[String]) -> MemoryResult<(), Self::Error> {
+        dispatch!(self, repo => repo.delete_entities(names).await.map_err(wrap))
+    }
+
+    async fn delete_relationships(
+        &self,
+        relationships: &[RelationshipRef],
+    ) -> MemoryResult<(), Self::Error> {
+        dispatch!(self, repo => repo.delete_relationships(relationships).await.map_err(wrap))
+    }
+
+    async fn find_relationships(
+        &self,
+        from: Option<String>,
+        to: Option<String>,
+        name: Option<String>,
+    ) -> MemoryResult<Vec<MemoryRelationship>, Self::Error> {
+        dispatch!(self, repo => repo.find_relationships(from, to, name).await.map_err(wrap))
+    }
+
+    async fn find_entities_by_labels(
+        &self,
+        labels: &[String],
+        match_mode: LabelMatchMode,
+        required_label: Option<String>,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        dispatch!(self, repo => repo
+            .find_entities_by_labels(labels, match_mode, required_label)
+            .await
+            .map_err(wrap))
+    }
+
+    async fn stream_entities_by_labels(
+        &self,
+        labels: &[String],
+        match_mode: LabelMatchMode,
+        required_label: Option<String>,
+    ) -> MemoryResult<EntityStream<Self::Error>, Self::Error> {
+        dispatch!(self, repo => repo
+            .stream_entities_by_labels(labels, match_mode, required_label)
+            .await
+            .map(wrap_stream)
+            .map_err(wrap))
+    }
+
+    async fn find_entities_fuzzy(
+        &self,
+        name: &str,
+        limit: usize,
+        required_label: Option<String>,
+    ) -> MemoryResult<Vec<FuzzyMatch>, Self::Error> {
+        dispatch!(self, repo => repo
+            .find_entities_fuzzy(name, limit, required_label)
+            .await
+            .map_err(wrap))
+    }
+
+    async fn find_entities_by_labels_filtered(
+        &self,
+        labels: &[String],
+        match_mode: LabelMatchMode,
+        required_label: Option<String>,
+        filters: &[PropertyFilter],
+        sort: &[PropertySort],
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        dispatch!(self, repo => repo
+            .find_entities_by_labels_filtered(labels, match_mode, required_label, filters, sort)
+            .await
+            .map_err(wrap))
+    }
+
+    async fn find_entities_by_source(
+        &self,
+        source_key: &str,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        dispatch!(self, repo => repo.find_entities_by_source(source_key).await.map_err(wrap))
+    }
+
+    async fn list_labels(&self) -> MemoryResult<Vec<(String, u64)>, Self::Error> {
+        dispatch!(self, repo => repo.list_labels().await.map_err(wrap))
+    }
+
+    async fn list_relationship_types(&self) -> MemoryResult<Vec<(String, u64)>, Self::Error> {
+        dispatch!(self, repo => repo.list_relationship_types().await.map_err(wrap))
+    }
+
+    async fn find_related_entities(
+        &self,
+        name: &str,
+        relationship_type: Option<String>,
+        direction: Option<RelationshipDirection>,
+        depth: u32,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        dispatch!(self, repo => repo
+            .find_related_entities(name, relationship_type, direction, depth)
+            .await
+            .map_err(wrap))
+    }
+
+    async fn stream_related_entities(
+        &self,
+        name: &str,
+        relationship_type: Option<String>,
+        direction: Option<RelationshipDirection>,
+        depth: u32,
+    ) -> MemoryResult<EntityStream<Self::Error>, Self::Error> {
+        dispatch!(self, repo => repo
+            .stream_related_entities(name, relationship_type, direction, depth)
+            .await
+            .map(wrap_stream)
+            .map_err(wrap))
+    }
+
+    async fn find_tasks(
+        &self,
+        filter: &TaskFilter,
+    ) -> MemoryResult<Vec<MemoryEntity>, Self::Error> {
+        dispatch!(self, repo => repo.find_tasks(filter).await.map_err(wrap))
+    }
+
+    async fn list_project_summaries(
+        &self,
+        required_label: Option<String>,
+    ) -> MemoryResult<Vec<ProjectSummary>, Self::Error> {
+        dispatch!(self, repo => repo.list_project_summaries(required_label).await.map_err(wrap))
+    }
+
+    async fn create_property_index(
+        &self,
+        label: &str,
+        property: &str,
+    ) -> MemoryResult<(), Self::Error> {
+        dispatch!(self, repo => repo.create_property_index(label, property).await.map_err(wrap))
+    }
+
+    async fn update_entity(
+        &self,
+        name: &str,
+        update: &EntityUpdate,
+    ) -> MemoryResult<(), Self::Error> {
+        dispatch!(self, repo => repo.update_entity(name, update).await.map_err(wrap))
+    }
+
+    async fn update_entities(
+        &self,
+        updates: &[NamedEntityUpdate],
+    ) -> MemoryResult<(), Self::Error> {
+        dispatch!(self, repo => repo.update_entities(updates).await.map_err(wrap))
+    }
+
+    async fn rename_entity(&self, old: &str, new: &str) -> MemoryResult<(), Self::Error> {
+        dispatch!(self, repo => repo.rename_entity(old, new).await.map_err(wrap))
+    }
+
+    async fn merge_entities(
+        &self,
+        merged: &MemoryEntity,
+        sources: &[String],
+    ) -> MemoryResult<(), Self::Error> {
+        dispatch!(self, repo => repo.merge_entities(merged, sources).await.map_err(wrap))
+    }
+
+    async fn update_relationship(
+        &self,
+        from: &str,
+        to: &str,
+        name: &str,
+        update: &RelationshipUpdate,
+    ) -> MemoryResult<(), Self::Error> {
+        dispatch!(self, repo => repo
+            .update_relationship(from, to, name, update)
+            .await
+            .map_err(wrap))
+    }
+}
+
+/// Git repository used by the server
+#[cfg(feature = "git")]
+pub type GitBackend = mm_git_git2::Git2Repository;
+
+/// Git repository used by the server
+#[cfg(not(feature = "git"))]
+pub type GitBackend = NoGitRepository;
+
+/// Git repository of builds without git support; every call fails
+#[cfg(not(feature = "git"))]
+#[derive(Debug, Default)]
+pub struct NoGitRepository;
+
+#[cfg(not(feature = "git"))]
+#[async_trait]
+impl mm_git::GitRepository for NoGitRepository {
+    type Error = std::convert::Infallible;
+
+    async fn get_status(
+        &self,
+        _path: &std::path::Path,
+    ) -> mm_git::GitResult<mm_git::GitStatus, Self::Error> {
+        Err(mm_git::GitError::repository_error(
+            "git support is not compiled in; rebuild with `--features git`",
+        ))
+    }
+}
+
+/// Git service of the backend compiled in
+pub fn create_git_service() -> mm_git::GitService<GitBackend> {
+    #[cfg(feature = "git")]
+    {
+        mm_git_git2::create_git_service()
+    }
+    #[cfg(not(feature = "git"))]
+    {
+        mm_git::GitService::new(NoGitRepository)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "inmem"))]
+    #[tokio::test]
+    async fn test_missing_backend_is_reported() {
+        let config = Config {
+            backend: BackendKind::Inmem,
+            ..Config::default()
+        };
+        let err = match MemoryBackend::connect(&config, &config.memory).await {
+            Ok(_) => panic!("inmem backend should not be compiled in"),
+            Err(err) => err,
+        };
+        assert!(err.to_string().contains("--features inmem"));
+    }
+
+    #[cfg(feature = "inmem")]
+    #[tokio::test]
+    async fn test_inmem_backend_round_trip() {
+        let config = Config {
+            backend: BackendKind::Inmem,
+            ..Config::default()
+        };
+        let backend = MemoryBackend::connect(&config, &config.memory)
+            .await
+            .unwrap();
+        assert_eq!(backend.backend_info().backend, "inmem");
+
+        let entity = MemoryEntity {
+            name: "a".to_string(),
+            labels: vec!["Example".to_string()],
+            ..Default::default()
+        };
+        backend.create_entities(&[entity]).await.unwrap();
+        assert!(backend.find_entity_by_name("a").await.unwrap().is_some());
+    }
+}
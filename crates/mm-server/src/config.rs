@@ -1,17 +1,237 @@
 use config::{Config as ConfigBuilder, ConfigError, File, FileFormat};
-use mm_memory::MemoryConfig;
+use mm_memory::{EntityTemplate, MemoryConfig};
+#[cfg(feature = "neo4j")]
 use mm_memory_neo4j::Neo4jConfig;
+#[cfg(feature = "surrealdb")]
+use mm_memory_surrealdb::SurrealConfig;
+use mm_utils::template::render;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::backend::BackendKind;
+
+/// Config file looked up in the working directory and its git root
+pub const WORKSPACE_CONFIG_FILE: &str = ".middle-manager.toml";
+
+/// Config file looked up in the user's config directory
+pub const USER_CONFIG_FILE: &str = "middle-manager/config.toml";
+
+/// Team conventions committed to a repository, relative to its root
+pub const PROJECT_CONFIG_FILE: &str = ".mm/project.toml";
 
 /// Configuration for mm-server
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
+    /// Memory backend, `neo4j` unless set
+    #[serde(default)]
+    pub backend: BackendKind,
+
     /// Neo4j configuration
+    #[cfg(feature = "neo4j")]
+    #[serde(default = "default_neo4j")]
     pub neo4j: Neo4jConfig,
 
+    /// JSON Lines file of the `jsonl` backend
+    #[cfg(feature = "jsonl")]
+    #[serde(default)]
+    pub jsonl: Option<JsonlConfig>,
+
+    /// SurrealDB connection of the `surrealdb` backend
+    #[cfg(feature = "surrealdb")]
+    #[serde(default)]
+    pub surrealdb: Option<SurrealConfig>,
+
     /// Memory related configuration
     pub memory: MemoryConfig,
+
+    /// Log file rotation and retention
+    #[serde(default)]
+    pub logging: LoggingConfig,
+
+    /// Instructions sent to MCP clients on initialization
+    #[serde(default)]
+    pub instructions: InstructionsConfig,
+
+    /// Named backends that commands such as `copy` can select instead of the
+    /// top-level one
+    #[serde(default)]
+    pub profiles: BTreeMap<String, BackendProfile>,
+
+    /// Load the context and tasks of `memory.default_project` in the
+    /// background after initialization, so the first `get_project_context`
+    /// and `list_tasks` calls for it return without querying the backend
+    #[serde(default)]
+    pub preload_context: bool,
+
+    /// Send tool results longer than this many bytes as a gzip-compressed,
+    /// base64-encoded resource instead of plain JSON text
+    #[serde(default)]
+    pub compress_results_over: Option<usize>,
+
+    /// Keep tool results longer than this many bytes as `mm://results/{id}`
+    /// resources and return their URI with a summary instead
+    #[serde(default)]
+    pub spill_results_over: Option<usize>,
+
+    /// Seconds a spilled tool result stays readable
+    #[serde(default = "default_spilled_results_ttl_secs")]
+    pub spilled_results_ttl_secs: u64,
+}
+
+pub(crate) fn default_spilled_results_ttl_secs() -> u64 {
+    600
+}
+
+/// Backend selection of a named profile
+///
+/// Connection settings left out fall back to the top-level ones.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BackendProfile {
+    pub backend: BackendKind,
+
+    #[cfg(feature = "neo4j")]
+    #[serde(default)]
+    pub neo4j: Option<Neo4jConfig>,
+
+    #[cfg(feature = "jsonl")]
+    #[serde(default)]
+    pub jsonl: Option<JsonlConfig>,
+
+    #[cfg(feature = "surrealdb")]
+    #[serde(default)]
+    pub surrealdb: Option<SurrealConfig>,
+}
+
+/// Memory settings a repository brings along in [`PROJECT_CONFIG_FILE`]
+///
+/// Labels and relationships add to the configured vocabulary; templates
+/// replace configured ones of the same name.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ProjectOverrides {
+    /// Project used when none is given
+    #[serde(default)]
+    pub default_project: Option<String>,
+
+    #[serde(default)]
+    pub allowed_labels: HashSet<String>,
+
+    #[serde(default)]
+    pub allowed_relationships: HashSet<String>,
+
+    #[serde(default)]
+    pub templates: HashMap<String, EntityTemplate>,
+}
+
+impl ProjectOverrides {
+    /// Read the [`PROJECT_CONFIG_FILE`] of the workspace at `root`, if it has one
+    pub fn load(root: &Path) -> Result<Option<Self>, ConfigError> {
+        let path = root.join(PROJECT_CONFIG_FILE);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        ConfigBuilder::builder()
+            .add_source(File::from(path).format(FileFormat::Toml))
+            .build()?
+            .try_deserialize()
+            .map(Some)
+    }
+
+    /// Merge the overrides into `memory`
+    pub fn apply(self, memory: &mut MemoryConfig) {
+        if self.default_project.is_some() {
+            memory.default_project = self.default_project;
+        }
+        memory.allowed_labels.extend(self.allowed_labels);
+        memory
+            .allowed_relationships
+            .extend(self.allowed_relationships);
+        memory.templates.extend(self.templates);
+    }
+}
+
+/// Settings of the `jsonl` backend
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct JsonlConfig {
+    /// File holding the memory graph; created on the first write
+    pub path: PathBuf,
+}
+
+#[cfg(feature = "neo4j")]
+fn default_neo4j() -> Neo4jConfig {
+    Neo4jConfig {
+        uri: "neo4j://localhost:7687".to_string(),
+        username: "neo4j".to_string(),
+        password: "password".to_string(),
+        dialect: Default::default(),
+        pool: Default::default(),
+        database: None,
+    }
+}
+
+/// Built-in MCP instructions, used unless `instructions.text` is set
+pub const DEFAULT_INSTRUCTIONS: &str =
+    "Middle Manager MCP Server provides tools for interacting with the memory graph.";
+
+/// MCP `instructions` text
+///
+/// Both fields are templates. Besides `{{project}}`, `{{agent}}` and
+/// `{{date}}` they may use `{{default_label}}`, `{{labels}}` and
+/// `{{relationships}}`, the latter two being the configured allowed names.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct InstructionsConfig {
+    /// Replaces the built-in instructions
+    #[serde(default)]
+    pub text: Option<String>,
+
+    /// Appended to the instructions after a blank line
+    #[serde(default)]
+    pub append: Option<String>,
+}
+
+/// When to start a new log file regardless of its size
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    /// Only when the size limit is reached
+    Never,
+    Hourly,
+    #[default]
+    Daily,
+}
+
+/// Rotation and retention of the log file
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct LoggingConfig {
+    /// Time-based rotation, `daily` unless set
+    #[serde(default)]
+    pub rotation: LogRotation,
+
+    /// Megabytes after which the log file is rotated; unlimited when unset
+    #[serde(default = "default_max_size_mb")]
+    pub max_size_mb: Option<u64>,
+
+    /// Rotated files kept next to the current one
+    #[serde(default = "default_max_files")]
+    pub max_files: usize,
+}
+
+fn default_max_size_mb() -> Option<u64> {
+    Some(10)
+}
+
+fn default_max_files() -> usize {
+    5
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            rotation: LogRotation::default(),
+            max_size_mb: default_max_size_mb(),
+            max_files: default_max_files(),
+        }
+    }
 }
 
 impl Config {
@@ -32,6 +252,88 @@ impl Config {
         builder.build()?.try_deserialize()
     }
 
+    /// Config files to load when none are given, lowest precedence first
+    ///
+    /// These are [`USER_CONFIG_FILE`] in `$XDG_CONFIG_HOME` (or
+    /// `~/.config`), then [`WORKSPACE_CONFIG_FILE`] in the git root containing
+    /// `cwd` and in `cwd` itself. Only files that exist are returned.
+    pub fn discover(cwd: &Path) -> Vec<PathBuf> {
+        let config_home = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")));
+        discover_in(cwd, config_home.as_deref())
+    }
+
+    /// Root of the workspace `cwd` belongs to: its git root, or `cwd` outside
+    /// a repository
+    pub fn workspace_root(cwd: &Path) -> &Path {
+        git_root(cwd).unwrap_or(cwd)
+    }
+
+    /// This configuration with the [`PROJECT_CONFIG_FILE`] of the workspace
+    /// at `root` merged in, if it has one
+    pub fn with_project_overrides(mut self, root: &Path) -> Result<Self, ConfigError> {
+        if let Some(overrides) = ProjectOverrides::load(root)? {
+            overrides.apply(&mut self.memory);
+        }
+        Ok(self)
+    }
+
+    /// This configuration with the backend of profile `name`
+    pub fn with_profile(&self, name: &str) -> Result<Self, ConfigError> {
+        let profile = self
+            .profiles
+            .get(name)
+            .ok_or_else(|| ConfigError::NotFound(format!("profiles.{name}")))?
+            .clone();
+        let mut config = self.clone();
+        config.backend = profile.backend;
+        #[cfg(feature = "neo4j")]
+        if let Some(neo4j) = profile.neo4j {
+            config.neo4j = neo4j;
+        }
+        #[cfg(feature = "jsonl")]
+        if profile.jsonl.is_some() {
+            config.jsonl = profile.jsonl;
+        }
+        #[cfg(feature = "surrealdb")]
+        if profile.surrealdb.is_some() {
+            config.surrealdb = profile.surrealdb;
+        }
+        Ok(config)
+    }
+
+    /// Render the MCP instructions with the memory configuration's variables
+    pub fn instructions(&self) -> String {
+        let vars = self.memory.template_vars(None);
+        let sorted = |names: &std::collections::HashSet<String>| {
+            let mut names: Vec<&str> = names.iter().map(String::as_str).collect();
+            names.sort_unstable();
+            names.join(", ")
+        };
+        let labels = sorted(&self.memory.allowed_labels);
+        let relationships = sorted(&self.memory.allowed_relationships);
+        let lookup = |name: &str| match name {
+            "default_label" => self.memory.default_label.as_deref(),
+            "labels" => Some(labels.as_str()),
+            "relationships" => Some(relationships.as_str()),
+            other => vars.get(other),
+        };
+
+        let text = self
+            .instructions
+            .text
+            .as_deref()
+            .unwrap_or(DEFAULT_INSTRUCTIONS);
+        let mut out = render(text, lookup);
+        if let Some(append) = &self.instructions.append {
+            out.push_str("\n\n");
+            out.push_str(&render(append, lookup));
+        }
+        out
+    }
+
     /// Load configuration from a string source (useful for testing)
     ///
     /// # Arguments
@@ -49,15 +351,44 @@ impl Config {
     }
 }
 
+/// Closest directory at or above `cwd` holding a `.git` entry
+fn git_root(cwd: &Path) -> Option<&Path> {
+    cwd.ancestors().find(|dir| dir.join(".git").exists())
+}
+
+fn discover_in(cwd: &Path, config_home: Option<&Path>) -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = config_home
+        .map(|dir| dir.join(USER_CONFIG_FILE))
+        .into_iter()
+        .collect();
+    if let Some(root) = git_root(cwd)
+        && root != cwd
+    {
+        candidates.push(root.join(WORKSPACE_CONFIG_FILE));
+    }
+    candidates.push(cwd.join(WORKSPACE_CONFIG_FILE));
+    candidates.retain(|path| path.is_file());
+    candidates
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
-            neo4j: Neo4jConfig {
-                uri: "neo4j://localhost:7687".to_string(),
-                username: "neo4j".to_string(),
-                password: "password".to_string(),
-            },
+            backend: BackendKind::default(),
+            #[cfg(feature = "neo4j")]
+            neo4j: default_neo4j(),
+            #[cfg(feature = "jsonl")]
+            jsonl: None,
+            #[cfg(feature = "surrealdb")]
+            surrealdb: None,
             memory: MemoryConfig::default(),
+            logging: LoggingConfig::default(),
+            instructions: InstructionsConfig::default(),
+            profiles: BTreeMap::new(),
+            preload_context: false,
+            compress_results_over: None,
+            spill_results_over: None,
+            spilled_results_ttl_secs: default_spilled_results_ttl_secs(),
         }
     }
 }
@@ -66,6 +397,75 @@ impl Default for Config {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_discover_workspace_and_user_config() {
+        let home = tempfile::tempdir().unwrap();
+        let repo = tempfile::tempdir().unwrap();
+        let nested = repo.path().join("crates/app");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        assert!(discover_in(&nested, Some(home.path())).is_empty());
+
+        let user = home.path().join(USER_CONFIG_FILE);
+        std::fs::create_dir_all(user.parent().unwrap()).unwrap();
+        std::fs::write(&user, "").unwrap();
+        let root = repo.path().join(WORKSPACE_CONFIG_FILE);
+        std::fs::write(&root, "").unwrap();
+        assert_eq!(
+            discover_in(&nested, Some(home.path())),
+            [user.clone(), root.clone()]
+        );
+
+        let local = nested.join(WORKSPACE_CONFIG_FILE);
+        std::fs::write(&local, "").unwrap();
+        assert_eq!(
+            discover_in(&nested, Some(home.path())),
+            [user, root.clone(), local]
+        );
+        assert_eq!(discover_in(repo.path(), None), [root]);
+    }
+
+    #[test]
+    fn test_project_overrides() {
+        let repo = tempfile::tempdir().unwrap();
+        let nested = repo.path().join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        assert_eq!(Config::workspace_root(&nested), repo.path());
+
+        let base = Config::load_from_string(
+            r#"
+[memory]
+default_project = "global"
+allowed_labels = ["Project"]
+"#,
+        )
+        .unwrap();
+        let unchanged = base.clone().with_project_overrides(repo.path()).unwrap();
+        assert_eq!(unchanged.memory.default_project.as_deref(), Some("global"));
+
+        std::fs::create_dir(repo.path().join(".mm")).unwrap();
+        std::fs::write(
+            repo.path().join(PROJECT_CONFIG_FILE),
+            r#"
+default_project = "mm"
+allowed_labels = ["Decision"]
+allowed_relationships = ["supersedes"]
+
+[templates.adr]
+labels = ["Decision"]
+"#,
+        )
+        .unwrap();
+        let config = base.with_project_overrides(repo.path()).unwrap();
+        assert_eq!(config.memory.default_project.as_deref(), Some("mm"));
+        assert!(config.memory.allowed_labels.contains("Project"));
+        assert!(config.memory.allowed_labels.contains("Decision"));
+        assert!(config.memory.allowed_relationships.contains("supersedes"));
+        assert_eq!(config.memory.templates["adr"].labels, ["Decision"]);
+    }
+
+    #[cfg(feature = "neo4j")]
     #[test]
     fn test_load_from_string() {
         let config_content = r#"
@@ -91,13 +491,134 @@ password = "test_password"
         assert!(config.memory.allow_default_relationships);
     }
 
+    #[test]
+    fn test_load_expiry_policies() {
+        let config = Config::load_from_string(
+            r#"
+[memory.expiry]
+GitStatusSnapshot = { ttl = "1d" }
+Temporal = { ttl = "2w", action = "archive" }
+[neo4j]
+uri = "neo4j://testhost:7687"
+username = "test_user"
+password = "test_password"
+"#,
+        )
+        .expect("Failed to load config from string");
+
+        let snapshot = &config.memory.expiry["GitStatusSnapshot"];
+        assert_eq!(snapshot.ttl.to_string(), "1d");
+        assert_eq!(snapshot.action, mm_memory::ExpiryAction::Delete);
+        assert_eq!(
+            config.memory.expiry["Temporal"].action,
+            mm_memory::ExpiryAction::Archive
+        );
+    }
+
+    #[test]
+    fn test_load_logging() {
+        let base = r#"
+[memory]
+[neo4j]
+uri = "neo4j://testhost:7687"
+username = "test_user"
+password = "test_password"
+"#;
+        let config = Config::load_from_string(base).expect("Failed to load config from string");
+        assert_eq!(config.logging, LoggingConfig::default());
+
+        let config = Config::load_from_string(&format!(
+            "{base}[logging]\nrotation = \"never\"\nmax_size_mb = 50\n"
+        ))
+        .expect("Failed to load config from string");
+        assert_eq!(config.logging.rotation, LogRotation::Never);
+        assert_eq!(config.logging.max_size_mb, Some(50));
+        assert_eq!(config.logging.max_files, 5);
+    }
+
+    #[test]
+    fn test_backend_selection() {
+        let config = Config::load_from_string("[memory]\n").unwrap();
+        assert_eq!(config.backend, BackendKind::Neo4j);
+
+        let config = Config::load_from_string(
+            "backend = \"jsonl\"\n[memory]\n[jsonl]\npath = \"memory.jsonl\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.backend, BackendKind::Jsonl);
+        #[cfg(feature = "jsonl")]
+        assert_eq!(
+            config.jsonl.unwrap().path,
+            std::path::PathBuf::from("memory.jsonl")
+        );
+    }
+
+    #[test]
+    fn test_profiles_override_backend() {
+        let config = Config::load_from_string(
+            r#"
+[memory]
+[profiles.local]
+backend = "jsonl"
+[profiles.local.jsonl]
+path = "local.jsonl"
+"#,
+        )
+        .unwrap();
+        let local = config.with_profile("local").unwrap();
+        assert_eq!(local.backend, BackendKind::Jsonl);
+        #[cfg(feature = "jsonl")]
+        assert_eq!(
+            local.jsonl.unwrap().path,
+            std::path::PathBuf::from("local.jsonl")
+        );
+        assert_eq!(config.backend, BackendKind::Neo4j);
+        assert!(config.with_profile("shared").is_err());
+    }
+
+    #[test]
+    fn test_instructions_from_config() {
+        let base = r#"
+[memory]
+default_label = "Memory"
+default_project = "mm"
+allowed_labels = ["Task", "Note"]
+[neo4j]
+uri = "neo4j://testhost:7687"
+username = "test_user"
+password = "test_password"
+"#;
+        let config = Config::load_from_string(base).expect("Failed to load config from string");
+        assert_eq!(config.instructions(), DEFAULT_INSTRUCTIONS);
+
+        let config = Config::load_from_string(&format!(
+            "{base}[instructions]\nappend = \"Project {{{{project}}}} uses {{{{labels}}}}; default {{{{ default_label }}}}\"\n"
+        ))
+        .expect("Failed to load config from string");
+        assert_eq!(
+            config.instructions(),
+            format!("{DEFAULT_INSTRUCTIONS}\n\nProject mm uses Note, Task; default Memory")
+        );
+
+        let config = Config::load_from_string(&format!(
+            "{base}[instructions]\ntext = \"Use {{{{project}}}}\"\n"
+        ))
+        .expect("Failed to load config from string");
+        assert_eq!(config.instructions(), "Use mm");
+    }
+
+    #[cfg(feature = "neo4j")]
     #[test]
     fn test_neo4j_config_exposed() {
         let config = Config {
+            backend: BackendKind::Neo4j,
             neo4j: Neo4jConfig {
                 uri: "neo4j://testconversion:7687".to_string(),
                 username: "test_conversion_user".to_string(),
                 password: "test_conversion_password".to_string(),
+                dialect: Default::default(),
+                pool: Default::default(),
+                database: None,
             },
             memory: MemoryConfig {
                 default_label: None,
@@ -107,7 +628,43 @@ password = "test_password"
                 allowed_labels: std::collections::HashSet::default(),
                 default_project: None,
                 agent_name: "test".to_string(),
+                indexed_properties: std::collections::HashMap::default(),
+                self_loop_relationships: std::collections::HashSet::default(),
+                merge_relationships: false,
+                wip_limits: Default::default(),
+                projects: std::collections::HashMap::default(),
+                expiry: std::collections::HashMap::default(),
+                retry: Default::default(),
+                batch_size: 500,
+                read_only: false,
+                soft_delete: false,
+                history: false,
+                audit_log: None,
+                redaction: Vec::new(),
+                reject_duplicate_names: false,
+                acyclic_relationships: std::collections::HashSet::default(),
+                relationship_rules: std::collections::HashMap::new(),
+                name_template: "{{label}}:{{slug}}".to_string(),
+                normalize_datetimes: false,
+                local_utc_offset_minutes: 0,
+                track_observations: false,
+                templates: std::collections::HashMap::new(),
+                label_parents: std::collections::HashMap::new(),
+                reserved_labels: std::collections::HashSet::default(),
+                reserved_relationships: std::collections::HashSet::default(),
+                naming: Default::default(),
             },
+            #[cfg(feature = "jsonl")]
+            jsonl: None,
+            #[cfg(feature = "surrealdb")]
+            surrealdb: None,
+            logging: LoggingConfig::default(),
+            instructions: InstructionsConfig::default(),
+            profiles: BTreeMap::new(),
+            preload_context: false,
+            compress_results_over: None,
+            spill_results_over: None,
+            spilled_results_ttl_secs: default_spilled_results_ttl_secs(),
         };
 
         assert_eq!(config.neo4j.uri, "neo4j://testconversion:7687");
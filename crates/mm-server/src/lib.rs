@@ -7,22 +7,29 @@
 //! calls into core domain operations.
 #![warn(clippy::all)]
 use async_trait::async_trait;
-use std::path::Path;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 use anyhow::Result as AnyResult;
 
-use mm_core::Ports;
+use mm_core::operations::memory::{
+    GetProjectContextCommand, ProjectFilter, REPOSITORY_ENTITY_PREFIX, get_project_context,
+};
+use mm_core::{Ports, Root};
 use mm_git::{GitRepository, GitService};
-use mm_git_git2::{Git2Repository, create_git_service};
 use mm_memory::{MemoryRepository, MemoryService};
-use mm_memory_neo4j::{Neo4jRepository, create_neo4j_service, neo4rs};
 
+pub mod backend;
+use backend::{GitBackend, MemoryBackend, create_git_service};
 mod config;
-pub use config::Config;
+pub use config::{
+    BackendProfile, Config, DEFAULT_INSTRUCTIONS, InstructionsConfig, JsonlConfig, LogRotation,
+    LoggingConfig, PROJECT_CONFIG_FILE, ProjectOverrides, USER_CONFIG_FILE, WORKSPACE_CONFIG_FILE,
+};
 
 use rust_mcp_sdk::schema::{
-    ListToolsResult, Result as McpResult, RpcError, schema_utils::CallToolError,
+    CallToolResult, ListToolsResult, Result as McpResult, RpcError, schema_utils::CallToolError,
 };
 use rust_mcp_sdk::{
     McpServer, StdioTransport, TransportOptions,
@@ -32,16 +39,21 @@ use rust_mcp_sdk::{
         ServerCapabilitiesResources, ServerCapabilitiesTools,
     },
 };
-use tracing::{debug, error};
+use tracing::{Instrument, debug, error};
 
 pub mod mcp;
 use mcp::MMTools;
+use mcp::correlation::CorrelationId;
+use mcp::spill::ResultStore;
+mod preload;
 mod resources;
 mod roots;
+use preload::{ContextPreload, Preloaded};
 
 use clap::Subcommand;
 use rust_mcp_sdk::schema::{ListResourceTemplatesResult, ListResourcesResult};
-use serde_json::Value;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
 
 /// Subcommands for interacting with tools via the CLI
 #[derive(Subcommand, Debug, Clone)]
@@ -69,21 +81,96 @@ pub struct MiddleManagerHandler<M, G>
 where
     M: MemoryRepository + Send + Sync + 'static,
     G: GitRepository + Send + Sync + 'static,
-    M::Error: From<neo4rs::Error> + Send + Sync + 'static,
 {
-    ports: Arc<Ports<M, G>>,
+    /// Replaced once the client's roots bring project overrides
+    ports: RwLock<Arc<Ports<M, G>>>,
+    /// Whether the default project's context is loaded after initialization
+    preload_context: bool,
+    preload: Arc<ContextPreload>,
+    /// Size in bytes above which tool results are sent compressed
+    compress_results_over: Option<usize>,
+    /// Size in bytes above which tool results are kept as resources
+    spill_results_over: Option<usize>,
+    results: Arc<ResultStore>,
 }
 
 impl<M, G> MiddleManagerHandler<M, G>
 where
     M: MemoryRepository + Send + Sync + 'static,
     G: GitRepository + Send + Sync + 'static,
-    M::Error: From<neo4rs::Error> + Send + Sync + 'static,
 {
     /// Create a new Middle Manager MCP server handler
     pub fn new(memory_service: MemoryService<M>, git_service: GitService<G>) -> Self {
         let ports = Arc::new(Ports::new(Arc::new(memory_service), Arc::new(git_service)));
-        Self { ports }
+        Self {
+            ports: RwLock::new(ports),
+            preload_context: false,
+            preload: Arc::default(),
+            compress_results_over: None,
+            spill_results_over: None,
+            results: Arc::new(ResultStore::new(Duration::from_secs(
+                config::default_spilled_results_ttl_secs(),
+            ))),
+        }
+    }
+
+    /// The ports tools run against
+    fn ports(&self) -> Arc<Ports<M, G>> {
+        self.ports.read().expect("ports lock").clone()
+    }
+
+    /// Load the context and tasks of the default project in the background
+    fn start_preload(&self) {
+        let ports = self.ports();
+        let project = ports.memory_service.memory_config().default_project.clone();
+        let Some(project) = project.filter(|_| self.preload_context) else {
+            return;
+        };
+        let preload = self.preload.clone();
+        preload.start();
+        tokio::spawn(async move {
+            let command = GetProjectContextCommand {
+                filter: ProjectFilter::Name(project.clone()),
+                exclude_labels: Vec::new(),
+            };
+            let result = match get_project_context(&ports, command).await {
+                Ok(result) => result,
+                Err(err) => {
+                    debug!("Failed to preload context of {project}: {err}");
+                    preload.fail();
+                    return;
+                }
+            };
+            // Lets a lookup by repository resolve to the preloaded project
+            let repository = result
+                .context
+                .git_repository
+                .as_ref()
+                .and_then(|r| r.name.strip_prefix(REPOSITORY_ENTITY_PREFIX))
+                .map(str::to_string);
+            // Same shape as the `get_project_context` tool returns
+            let context = serde_json::to_value(&result)
+                .ok()
+                .map(|json| CallToolResult::text_content(json.to_string(), None));
+            let tasks = mcp::ListTasksTool {
+                project_name: Some(project.clone()),
+                labels: vec![],
+                status: None,
+                priority: None,
+                due_before: None,
+                due_after: None,
+            }
+            .call_tool(&ports)
+            .await
+            .inspect_err(|err| debug!("Failed to preload tasks of {project}: {err}"))
+            .ok();
+            preload.finish(Preloaded {
+                project,
+                repository,
+                context,
+                tasks,
+            });
+        });
     }
 
     /// Request the client's roots and store them if supported.
@@ -91,12 +178,9 @@ where
         if runtime.client_supports_root_list().unwrap_or(false) {
             match runtime.list_roots(None).await {
                 Ok(result) => {
-                    let roots = result
-                        .roots
-                        .into_iter()
-                        .map(roots::from_sdk_root)
-                        .collect::<Vec<_>>();
-                    let mut collection = self.ports.roots.write().await;
+                    let roots = roots::normalize_roots(result.roots);
+                    let ports = self.ports();
+                    let mut collection = ports.roots.write().await;
                     collection.set_roots(roots);
                 }
                 Err(err) => {
@@ -106,6 +190,42 @@ where
         }
     }
 
+    /// Merge the [`PROJECT_CONFIG_FILE`] of the first client root that has one
+    ///
+    /// The server is started wherever the client chooses, so its working
+    /// directory says nothing about the workspace; the roots do.
+    async fn apply_project_overrides(&self) {
+        let ports = self.ports();
+        let paths: Vec<PathBuf> = ports
+            .roots
+            .read()
+            .await
+            .roots()
+            .iter()
+            .filter_map(Root::path)
+            .collect();
+        for path in paths {
+            match ProjectOverrides::load(Config::workspace_root(&path)) {
+                Ok(Some(overrides)) => {
+                    let mut memory = ports.memory_service.memory_config().clone();
+                    overrides.apply(&mut memory);
+                    let updated = Ports::with_all(
+                        Arc::new(ports.memory_service.with_config(memory)),
+                        ports.git_service.clone(),
+                        ports.roots.clone(),
+                    );
+                    *self.ports.write().expect("ports lock") = Arc::new(updated);
+                    return;
+                }
+                Ok(None) => {}
+                Err(err) => error!(
+                    "Failed to load {PROJECT_CONFIG_FILE} of {}: {err}",
+                    path.display()
+                ),
+            }
+        }
+    }
+
     async fn handle_list_tools_request(
         &self,
         _request: rust_mcp_sdk::schema::ListToolsRequest,
@@ -115,7 +235,7 @@ where
         Ok(ListToolsResult {
             meta: None,
             next_cursor: None,
-            tools: MMTools::tools(),
+            tools: mcp::configured_tools(self.ports().memory_service.memory_config()),
         })
     }
 
@@ -152,7 +272,15 @@ where
         _runtime: &dyn McpServer,
     ) -> std::result::Result<rust_mcp_sdk::schema::ReadResourceResult, RpcError> {
         debug!("Handling read resource request: {}", request.params.uri);
-        let result = resources::read_resource(&self.ports, &request.params.uri)
+        let uri = &request.params.uri;
+        if uri.starts_with(mcp::spill::RESULTS_URI_PREFIX) {
+            let text = self.results.get(uri).ok_or_else(|| {
+                RpcError::method_not_found()
+                    .with_message(format!("Result '{uri}' expired or does not exist"))
+            })?;
+            return Ok(resources::text_resource(uri, "application/json", text));
+        }
+        let result = resources::read_resource(&self.ports(), &request.params.uri)
             .await
             .map_err(|err| RpcError::internal_error().with_message(err.to_string()))?;
         Ok(result)
@@ -161,16 +289,80 @@ where
     async fn handle_call_tool_request(
         &self,
         request: rust_mcp_sdk::schema::CallToolRequest,
-        _runtime: &dyn McpServer,
+        runtime: &dyn McpServer,
     ) -> std::result::Result<rust_mcp_sdk::schema::CallToolResult, CallToolError> {
         let tool_name = request.tool_name().to_string();
-        debug!("Handling call tool request: {}", tool_name);
+        let correlation_id = CorrelationId::new();
+        let span = tracing::info_span!(
+            "tool_call",
+            tool = %tool_name,
+            correlation_id = %correlation_id
+        );
+
+        let call = async {
+            debug!("Handling call tool request: {}", tool_name);
+
+            // Attempt to convert request parameters into MMTools enum
+            let tool_params = MMTools::try_from(request.params)
+                .map_err(|_| CallToolError::unknown_tool(tool_name.clone()))?;
+
+            let ports = self.ports();
+            // Preloaded results stay current until something may change the graph
+            match &tool_params {
+                // The preload has every related entity
+                MMTools::GetProjectContextTool(tool) if tool.exclude_labels.is_empty() => {
+                    let (name, repository) = (
+                        tool.project_name.as_deref(),
+                        tool.repository_name.as_deref(),
+                    );
+                    if let Some(result) = self.preload.take_context(name, repository).await {
+                        return Ok(result);
+                    }
+                }
+                MMTools::ListTasksTool(tool) if tool.is_unfiltered() => {
+                    let default_project = ports
+                        .memory_service
+                        .memory_config()
+                        .default_project
+                        .as_deref();
+                    if let Some(project) = tool.project_name.as_deref().or(default_project)
+                        && let Some(result) = self.preload.take_tasks(project).await
+                    {
+                        return Ok(result);
+                    }
+                }
+                tool if !tool.is_query() => self.preload.discard(),
+                _ => {}
+            }
 
-        // Attempt to convert request parameters into MMTools enum
-        let tool_params = MMTools::try_from(request.params)
-            .map_err(|_| CallToolError::unknown_tool(tool_name.clone()))?;
+            // Deep traversals report partial results through the runtime
+            if let MMTools::FindRelatedEntitiesTool(tool) = &tool_params {
+                return tool.call_tool_with_progress(&ports, runtime).await;
+            }
+
+            // Confirming contradictions needs the runtime to sample from the client
+            if let MMTools::FindContradictionsTool(tool) = &tool_params
+                && tool.confirm_with_client
+            {
+                return tool.call_tool_with_sampling(&ports, runtime).await;
+            }
 
-        tool_params.execute(&self.ports).await
+            tool_params.execute(&ports).await
+        }
+        .instrument(span);
+        // Changes made by the tool carry the id into the audit log
+        let mut result =
+            mm_memory::audit::with_correlation_id(correlation_id.to_string(), call).await;
+        // Spilled results become short pointers, so compression only applies
+        // to results under the spill threshold
+        if let Some(threshold) = self.spill_results_over {
+            result = result.map(|r| self.results.spill(r, threshold));
+        }
+        if let Some(threshold) = self.compress_results_over {
+            result = result.map(|r| mcp::compression::compress_large_result(r, threshold));
+        }
+
+        correlation_id.tag(result)
     }
 }
 
@@ -182,26 +374,38 @@ pub fn create_handler<M, G>(
 where
     M: MemoryRepository + Send + Sync + 'static,
     G: GitRepository + Send + Sync + 'static,
-    M::Error: From<neo4rs::Error> + Send + Sync + 'static,
 {
     MiddleManagerHandler::new(memory_service, git_service)
 }
 
 /// Load configuration and construct Ports from the provided paths.
+///
+/// The memory backend is the one named by `backend` in the configuration;
+/// it must have been compiled in. The [`PROJECT_CONFIG_FILE`] of the
+/// workspace the process runs in is merged into the memory settings; the
+/// server takes it from the client's roots instead.
 pub async fn create_ports_from_config<P: AsRef<Path>>(
     paths: &[P],
-) -> AnyResult<(Config, Ports<Neo4jRepository, Git2Repository>)> {
-    let config =
-        Config::load(paths).map_err(|e| anyhow::anyhow!("Failed to load configuration: {}", e))?;
+) -> AnyResult<(Config, Ports<MemoryBackend, GitBackend>)> {
+    let cwd = std::env::current_dir()?;
+    let config = Config::load(paths)
+        .and_then(|config| config.with_project_overrides(Config::workspace_root(&cwd)))
+        .map_err(|e| anyhow::anyhow!("Failed to load configuration: {}", e))?;
+    let ports = create_ports(&config).await?;
+    Ok((config, ports))
+}
 
-    let memory_service = create_neo4j_service(config.neo4j.clone(), config.memory.clone())
+/// Construct Ports for an already loaded configuration
+pub async fn create_ports(config: &Config) -> AnyResult<Ports<MemoryBackend, GitBackend>> {
+    let repository = MemoryBackend::connect(config, &config.memory)
         .await
-        .map_err(|e| anyhow::anyhow!("Failed to create Neo4j memory service: {}", e))?;
+        .map_err(|e| {
+            anyhow::anyhow!("Failed to create {} memory service: {}", config.backend, e)
+        })?;
+    let memory_service = MemoryService::new(repository, config.memory.clone());
 
     let git_service = create_git_service();
-    let ports = Ports::new(Arc::new(memory_service), Arc::new(git_service));
-
-    Ok((config, ports))
+    Ok(Ports::new(Arc::new(memory_service), Arc::new(git_service)))
 }
 
 #[async_trait]
@@ -209,10 +413,11 @@ impl<M, G> ServerHandler for MiddleManagerHandler<M, G>
 where
     M: MemoryRepository + Send + Sync + 'static,
     G: GitRepository + Send + Sync + 'static,
-    M::Error: From<neo4rs::Error> + Send + Sync + 'static,
 {
     async fn on_initialized(&self, runtime: &dyn McpServer) {
         self.update_client_roots(runtime).await;
+        self.apply_project_overrides().await;
+        self.start_preload();
     }
 
     async fn handle_list_tools_request(
@@ -264,36 +469,61 @@ where
     }
 }
 
+/// Experimental capability advertised when the memory graph is read-only
+pub const READ_ONLY_CAPABILITY: &str = "readOnly";
+
 /// Run the Middle Manager MCP server
 #[tracing::instrument(skip(config_paths), fields(paths = config_paths.len()))]
 pub async fn run_server<P: AsRef<Path>>(config_paths: &[P]) -> AnyResult<()> {
-    // Load configuration and create ports
-    let (config, ports) = create_ports_from_config(config_paths).await?;
-
-    tracing::info!("Starting Middle Manager MCP server");
-    tracing::debug!("Using Neo4j URI: {}", config.neo4j.uri);
+    // Project overrides come from the client's roots once it connects
+    let config = Config::load(config_paths)
+        .map_err(|e| anyhow::anyhow!("Failed to load configuration: {}", e))?;
+    let ports = create_ports(&config).await?;
+
+    mcp::server_info::mark_started();
+    let backend = ports.memory_service.backend_info();
+    tracing::info!(
+        version = mcp::server_info::VERSION,
+        git_commit = mcp::server_info::GIT_COMMIT.unwrap_or("unknown"),
+        backend = %backend.backend,
+        "Starting Middle Manager MCP server"
+    );
+    if let Some(database) = &backend.database {
+        tracing::debug!("Using database: {}", database);
+    }
 
     // Create server handler using the constructed ports
     let handler = MiddleManagerHandler {
-        ports: Arc::new(ports),
+        ports: RwLock::new(Arc::new(ports)),
+        preload_context: config.preload_context,
+        preload: Arc::default(),
+        compress_results_over: config.compress_results_over,
+        spill_results_over: config.spill_results_over,
+        results: Arc::new(ResultStore::new(Duration::from_secs(
+            config.spilled_results_ttl_secs,
+        ))),
     };
 
     // Create server details
     let server_details = InitializeResult {
         server_info: Implementation {
             name: "Middle Manager MCP Server".to_string(),
-            version: "0.1.0".to_string(),
+            version: mcp::server_info::VERSION.to_string(),
         },
         capabilities: ServerCapabilities {
             tools: Some(ServerCapabilitiesTools { list_changed: None }),
             resources: Some(ServerCapabilitiesResources::default()),
+            // Partial results are delivered as log messages
+            logging: Some(Map::new()),
+            // Tells clients up front that only query tools will succeed
+            experimental: config
+                .memory
+                .read_only
+                .then(|| HashMap::from([(READ_ONLY_CAPABILITY.to_string(), Map::new())])),
             ..ServerCapabilities::default()
         },
         meta: None,
-        instructions: Some(
-            "Middle Manager MCP Server provides tools for interacting with the memory graph."
-                .to_string(),
-        ),
+        instructions: Some(config.instructions()),
         protocol_version: LATEST_PROTOCOL_VERSION.to_string(),
     };
 
@@ -319,7 +549,7 @@ pub async fn run_tools<P: AsRef<Path>>(command: ToolsCommand, config_paths: &[P]
     match command {
         ToolsCommand::List => {
             println!("MMTools:");
-            for tool in MMTools::tools() {
+            for tool in mcp::configured_tools(ports.memory_service.memory_config()) {
                 let desc = tool.description.unwrap_or_default();
                 println!("  {} - {}", tool.name, desc);
             }
@@ -332,7 +562,7 @@ pub async fn run_tools<P: AsRef<Path>>(command: ToolsCommand, config_paths: &[P]
                 let result = ListToolsResult {
                     meta: None,
                     next_cursor: None,
-                    tools: MMTools::tools(),
+                    tools: mcp::configured_tools(ports.memory_service.memory_config()),
                 };
                 println!("{}", serde_json::to_string_pretty(&result)?);
             }
@@ -380,3 +610,41 @@ pub async fn run_tools<P: AsRef<Path>>(command: ToolsCommand, config_paths: &[P]
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_git::repository::MockGitRepository;
+    use mm_memory::{MemoryConfig, MockMemoryRepository};
+    use url::Url;
+
+    #[tokio::test]
+    async fn test_project_overrides_come_from_client_roots() {
+        let handler = create_handler(
+            MemoryService::new(MockMemoryRepository::new(), MemoryConfig::default()),
+            GitService::new(MockGitRepository::new()),
+        );
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".mm")).unwrap();
+        std::fs::write(
+            repo.path().join(PROJECT_CONFIG_FILE),
+            "default_project = \"mm\"\n",
+        )
+        .unwrap();
+
+        handler.apply_project_overrides().await;
+        let config = handler.ports().memory_service.memory_config().clone();
+        assert_eq!(config.default_project, None);
+
+        let uri = Url::from_directory_path(repo.path()).unwrap().to_string();
+        handler
+            .ports()
+            .roots
+            .write()
+            .await
+            .set_roots(vec![Root::new(None, uri)]);
+        handler.apply_project_overrides().await;
+        let config = handler.ports().memory_service.memory_config().clone();
+        assert_eq!(config.default_project.as_deref(), Some("mm"));
+    }
+}
@@ -0,0 +1,71 @@
+use mm_core::operations::memory::{AddObservationsCommand, add_observations};
+use mm_memory::ObservationSource;
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+#[mcp_tool(
+    name = "add_observations",
+    description = "Add observations to an existing entity, skipping ones it already has"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AddObservationsTool {
+    /// Entity to add the observations to
+    pub name: String,
+    /// Observations to add
+    pub observations: Vec<String>,
+    /// Confidence in the observations between 0 and 1; omit for established facts
+    #[serde(default)]
+    pub confidence: Option<f64>,
+    /// Where the observations were learned: `url`, `file` and/or `conversation_id`
+    #[serde(default)]
+    pub source: Option<ObservationSource>,
+}
+
+impl AddObservationsTool {
+    generate_call_tool!(
+        self,
+        AddObservationsCommand {
+            name,
+            observations,
+            confidence,
+            source
+        },
+        add_observations,
+        "Observations added"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|_| Ok(None));
+        mock.expect_update_entity()
+            .withf(|n, u| n == "e" && u.properties.is_some())
+            .returning(|_, _| Ok(()));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let tool = AddObservationsTool {
+            name: "e".into(),
+            observations: vec!["fact".into()],
+            confidence: Some(0.9),
+            source: None,
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        assert_eq!(text, "Observations added");
+    }
+
+    #[test]
+    fn test_schema_has_no_refs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<AddObservationsTool>();
+    }
+}
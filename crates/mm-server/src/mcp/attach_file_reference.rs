@@ -0,0 +1,73 @@
+use mm_core::operations::memory::{AttachFileReferenceCommand, attach_file_reference};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// MCP tool for recording a file snapshot on an entity
+#[mcp_tool(
+    name = "attach_file_reference",
+    description = "Attach a file to an entity, recording its path, workspace root and content hash"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AttachFileReferenceTool {
+    /// Name of the entity to attach the file to
+    pub entity_name: String,
+    /// Path to the file, absolute or relative to the workspace root
+    pub path: PathBuf,
+}
+
+impl AttachFileReferenceTool {
+    generate_call_tool!(
+        self,
+        AttachFileReferenceCommand {
+            entity_name => self.entity_name.clone(),
+            path
+        },
+        attach_file_reference
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("design.md");
+        std::fs::write(&path, b"design").unwrap();
+
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|name| {
+            Ok(Some(MemoryEntity {
+                name: name.to_string(),
+                ..Default::default()
+            }))
+        });
+        mock.expect_find_relationships()
+            .returning(|_, _, _| Ok(vec![]));
+        mock.expect_create_relationships().returning(|_| Ok(()));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let tool = AttachFileReferenceTool {
+            entity_name: "decision:1".to_string(),
+            path: path.clone(),
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["path"], path.display().to_string());
+        assert!(value["content_hash"].as_str().unwrap().len() == 64);
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<AttachFileReferenceTool>();
+    }
+}
@@ -0,0 +1,91 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use rust_mcp_sdk::schema::{
+    BlobResourceContents, CallToolResult, CallToolResultContentItem, EmbeddedResource,
+    EmbeddedResourceResource,
+};
+use std::io::Write;
+
+/// URI of the embedded resource holding a compressed tool result
+pub const COMPRESSED_RESULT_URI: &str = "mm://result.json.gz";
+
+/// MIME type of a compressed tool result
+pub const COMPRESSED_RESULT_MIME_TYPE: &str = "application/gzip";
+
+/// Replace the text of `result` with a gzip-compressed, base64-encoded
+/// resource when it is longer than `threshold` bytes
+///
+/// A short text block saying how to decode it comes first. Results with
+/// anything but a single text block are returned as they are.
+pub fn compress_large_result(result: CallToolResult, threshold: usize) -> CallToolResult {
+    let [CallToolResultContentItem::TextContent(text)] = result.content.as_slice() else {
+        return result;
+    };
+    if text.text.len() <= threshold {
+        return result;
+    }
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    let compressed = match encoder
+        .write_all(text.text.as_bytes())
+        .and_then(|()| encoder.finish())
+    {
+        Ok(compressed) => compressed,
+        Err(err) => {
+            tracing::warn!("Failed to compress tool result: {err}");
+            return result;
+        }
+    };
+    let note = format!(
+        "Result of {} bytes compressed: base64-decode and gunzip the {} resource to get the JSON",
+        text.text.len(),
+        COMPRESSED_RESULT_URI
+    );
+    let blob = BlobResourceContents {
+        blob: STANDARD.encode(compressed),
+        mime_type: Some(COMPRESSED_RESULT_MIME_TYPE.to_string()),
+        uri: COMPRESSED_RESULT_URI.to_string(),
+    };
+    let mut compressed = CallToolResult::text_content(note, None);
+    compressed
+        .content
+        .push(CallToolResultContentItem::EmbeddedResource(
+            EmbeddedResource::new(EmbeddedResourceResource::BlobResourceContents(blob), None),
+        ));
+    compressed.is_error = result.is_error;
+    compressed.meta = result.meta;
+    compressed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    #[test]
+    fn test_small_results_are_untouched() {
+        let result = compress_large_result(CallToolResult::text_content("{}".into(), None), 10);
+        assert_eq!(result.content[0].as_text_content().unwrap().text, "{}");
+    }
+
+    #[test]
+    fn test_large_results_round_trip() {
+        let json = format!("[{}]", vec!["1"; 1000].join(","));
+        let result = compress_large_result(CallToolResult::text_content(json.clone(), None), 100);
+        assert_eq!(result.content.len(), 2);
+        let CallToolResultContentItem::EmbeddedResource(resource) = &result.content[1] else {
+            panic!("expected an embedded resource");
+        };
+        let EmbeddedResourceResource::BlobResourceContents(blob) = &resource.resource else {
+            panic!("expected a blob");
+        };
+        let bytes = STANDARD.decode(&blob.blob).unwrap();
+        let mut decoded = String::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, json);
+    }
+}
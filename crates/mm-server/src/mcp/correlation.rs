@@ -0,0 +1,87 @@
+use rust_mcp_sdk::schema::{CallToolResult, schema_utils::CallToolError};
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Key of the correlation id in the `_meta` of tool results
+pub const CORRELATION_ID_KEY: &str = "correlationId";
+
+/// Identifier of one tool call, shared by its log lines, errors and result
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrelationId(String);
+
+impl CorrelationId {
+    /// Random 16 hex digit identifier
+    pub fn new() -> Self {
+        Self(format!("{:016x}", fastrand::u64(..)))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Attach the id to a tool result, or to the message of a failed call
+    pub fn tag(
+        &self,
+        result: Result<CallToolResult, CallToolError>,
+    ) -> Result<CallToolResult, CallToolError> {
+        match result {
+            Ok(mut result) => {
+                result
+                    .meta
+                    .get_or_insert_with(Map::new)
+                    .insert(CORRELATION_ID_KEY.to_string(), Value::from(self.as_str()));
+                Ok(result)
+            }
+            Err(err) => Err(CallToolError(
+                format!("{} (correlation id: {})", err.0, self).into(),
+            )),
+        }
+    }
+}
+
+impl Default for CorrelationId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_are_distinct_hex() {
+        let a = CorrelationId::new();
+        let b = CorrelationId::new();
+        assert_ne!(a, b);
+        assert_eq!(a.as_str().len(), 16);
+        assert!(a.as_str().chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn test_tag_result_and_error() {
+        let id = CorrelationId::new();
+
+        let result = id
+            .tag(Ok(CallToolResult::text_content("ok".to_string(), None)))
+            .unwrap();
+        assert_eq!(
+            result.meta.unwrap().get(CORRELATION_ID_KEY),
+            Some(&Value::from(id.as_str()))
+        );
+
+        let err = id
+            .tag(Err(CallToolError::unknown_tool("nope".to_string())))
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .ends_with(&format!("(correlation id: {id})"))
+        );
+    }
+}
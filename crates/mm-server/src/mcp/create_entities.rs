@@ -1,5 +1,5 @@
 use mm_core::operations::memory::{CreateEntitiesCommand, create_entities};
-use mm_memory::MemoryEntity;
+use mm_memory::{MemoryEntity, ObservationSource};
 use mm_utils::IntoJsonSchema;
 use rust_mcp_sdk::macros::mcp_tool;
 use serde::{Deserialize, Serialize};
@@ -12,15 +12,28 @@ use serde::{Deserialize, Serialize};
 pub struct CreateEntitiesTool {
     /// Entities to create
     pub entities: Vec<MemoryEntity>,
+    /// Confidence between 0 and 1 for every observation of the entities
+    #[serde(default)]
+    pub confidence: Option<f64>,
+    /// Where the observations of the entities were learned: `url`, `file` and/or `conversation_id`
+    #[serde(default)]
+    pub source: Option<ObservationSource>,
+    /// Project whose label vocabulary applies; the default project when omitted
+    #[serde(default)]
+    pub project_name: Option<String>,
 }
 
 impl CreateEntitiesTool {
     generate_call_tool!(
         self,
         CreateEntitiesCommand {
-            entities => self.entities.clone()
+            entities => self.entities.clone(),
+            confidence,
+            source,
+            project_name
         },
-        create_entities
+        create_entities,
+        "Entities created"
     );
 }
 
@@ -49,6 +62,9 @@ mod tests {
         let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
 
         let tool = CreateEntitiesTool {
+            confidence: None,
+            source: None,
+            project_name: None,
             entities: vec![MemoryEntity {
                 name: "test:entity".to_string(),
                 labels: vec!["Test".to_string()],
@@ -58,8 +74,7 @@ mod tests {
 
         let result = tool.call_tool(&ports).await.expect("tool should succeed");
         let text = result.content[0].as_text_content().unwrap().text.clone();
-        // With our new macro, we're returning null
-        assert_eq!(text, "null");
+        assert_eq!(text, "Entities created");
     }
 
     #[tokio::test]
@@ -72,6 +87,9 @@ mod tests {
         let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
 
         let tool = CreateEntitiesTool {
+            confidence: None,
+            source: None,
+            project_name: None,
             entities: vec![MemoryEntity {
                 name: "test:entity".to_string(),
                 labels: vec!["Test".to_string()],
@@ -0,0 +1,81 @@
+use mm_core::operations::memory::{CreateFromTemplateCommand, create_from_template};
+use mm_memory::MemoryValue;
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[mcp_tool(
+    name = "create_from_template",
+    description = "Create an entity from a named template that sets its labels, default properties and relationships, so entities of one kind such as decisions or technologies share a shape. Properties given here override the template's defaults"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CreateFromTemplateTool {
+    /// Name of the template
+    pub template: String,
+    /// Name of the new entity
+    pub name: String,
+    /// Project whose vocabulary and `{{project}}` variable apply; the default
+    /// project when omitted
+    #[serde(default)]
+    pub project_name: Option<String>,
+    /// Observations added to the template's
+    #[serde(default)]
+    pub observations: Vec<String>,
+    /// Properties overriding the template's defaults
+    #[serde(default)]
+    pub properties: HashMap<String, MemoryValue>,
+}
+
+impl CreateFromTemplateTool {
+    generate_call_tool!(
+        self,
+        CreateFromTemplateCommand {
+            template,
+            name,
+            project_name,
+            observations,
+            properties
+        },
+        create_from_template
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{EntityTemplate, MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_create_graph().returning(|_, _| Ok(()));
+        let config = MemoryConfig {
+            templates: HashMap::from([(
+                "technology".to_string(),
+                EntityTemplate {
+                    labels: vec!["Technology".to_string()],
+                    ..Default::default()
+                },
+            )]),
+            ..MemoryConfig::default()
+        };
+        let service = MemoryService::new(mock, config);
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let tool: CreateFromTemplateTool =
+            serde_json::from_str(r#"{"template": "technology", "name": "tech:tokio"}"#).unwrap();
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = &result.content[0].as_text_content().unwrap().text;
+        let value: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert_eq!(value["entity"]["labels"], serde_json::json!(["Technology"]));
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<CreateFromTemplateTool>();
+    }
+}
@@ -33,6 +33,10 @@ impl RelationshipInput {
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct CreateRelationshipsTool {
     pub relationships: Vec<RelationshipInput>,
+    /// Project whose relationship vocabulary applies; the default project
+    /// when omitted
+    #[serde(default)]
+    pub project_name: Option<String>,
 }
 
 impl CreateRelationshipsTool {
@@ -44,6 +48,7 @@ impl CreateRelationshipsTool {
                 .iter()
                 .map(RelationshipInput::to_memory_relationship)
                 .collect(),
+            project_name,
         },
         create_relationships,
         "Relationships created"
@@ -68,6 +73,7 @@ mod tests {
         let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
 
         let tool = CreateRelationshipsTool {
+            project_name: None,
             relationships: vec![RelationshipInput {
                 from: "a".to_string(),
                 to: "b".to_string(),
@@ -91,6 +97,7 @@ mod tests {
         let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
 
         let tool = CreateRelationshipsTool {
+            project_name: None,
             relationships: vec![RelationshipInput {
                 from: "a".to_string(),
                 to: "b".to_string(),
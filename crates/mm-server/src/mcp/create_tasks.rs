@@ -13,12 +13,19 @@ pub struct CreateTasksTool {
     pub tasks: Vec<TaskInput>,
     /// Project to associate with
     pub project_name: Option<String>,
+    /// Suggest calibrated estimates from the project's finished tasks
+    #[serde(default)]
+    pub calibrate_estimates: bool,
 }
 
 impl CreateTasksTool {
     generate_call_tool!(
         self,
-        CreateTasksCommand { tasks => self.tasks.clone(), project_name },
+        CreateTasksCommand {
+            tasks => self.tasks.clone(),
+            project_name,
+            calibrate_estimates
+        },
         create_tasks
     );
 }
@@ -37,12 +44,15 @@ mod tests {
     async fn test_call_tool_success() {
         let mut mock = MockMemoryRepository::new();
         mock.expect_find_entity_by_name().never();
-        mock.expect_create_entities()
-            .withf(|ents| ents.len() == 1 && ents[0].name == "task:1")
-            .returning(|_| Ok(()));
-        mock.expect_create_relationships()
-            .withf(|rels| rels.len() == 1 && rels[0].from == "proj" && rels[0].to == "task:1")
-            .returning(|_| Ok(()));
+        mock.expect_create_graph()
+            .withf(|ents, rels| {
+                ents.len() == 1
+                    && ents[0].name == "task:1"
+                    && rels.len() == 1
+                    && rels[0].from == "proj"
+                    && rels[0].to == "task:1"
+            })
+            .returning(|_, _| Ok(()));
 
         let service = MemoryService::new(
             mock,
@@ -65,6 +75,7 @@ mod tests {
                 depends_on: vec![],
             }],
             project_name: None,
+            calibrate_estimates: false,
         };
 
         let result = tool.call_tool(&ports).await.unwrap();
@@ -85,12 +96,11 @@ mod tests {
                     ..Default::default()
                 }))
             });
-        mock.expect_create_entities()
-            .withf(|ents| ents.len() == 1 && ents[0].name == "task:2")
-            .returning(|_| Ok(()));
-        mock.expect_create_relationships()
-            .withf(|rels| {
-                rels.len() == 2
+        mock.expect_create_graph()
+            .withf(|ents, rels| {
+                ents.len() == 1
+                    && ents[0].name == "task:2"
+                    && rels.len() == 2
                     && rels
                         .iter()
                         .any(|r| r.from == "proj" && r.to == "task:2" && r.name == "contains")
@@ -98,7 +108,7 @@ mod tests {
                         .iter()
                         .any(|r| r.from == "task:2" && r.to == "task:1" && r.name == "depends_on")
             })
-            .returning(|_| Ok(()));
+            .returning(|_, _| Ok(()));
 
         let service = MemoryService::new(
             mock,
@@ -122,6 +132,7 @@ mod tests {
                 depends_on: vec!["task:1".into()],
             }],
             project_name: None,
+            calibrate_estimates: false,
         };
 
         let result = tool.call_tool(&ports).await.unwrap();
@@ -11,12 +11,18 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct DeleteRelationshipsTool {
     pub relationships: Vec<RelationshipRef>,
+    /// Delete reserved relationships, such as a project's `contains`, too
+    #[serde(default)]
+    pub force: bool,
 }
 
 impl DeleteRelationshipsTool {
     generate_call_tool!(
         self,
-        DeleteRelationshipsCommand { relationships => self.relationships.clone() },
+        DeleteRelationshipsCommand {
+            relationships => self.relationships.clone(),
+            force => self.force
+        },
         delete_relationships,
         "Relationships deleted"
     );
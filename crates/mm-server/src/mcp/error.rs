@@ -25,10 +25,15 @@ where
         CoreError::Validation(e) => e.to_string(),
         CoreError::BatchValidation(v) => v
             .iter()
-            .map(|(name, err)| format!("{}: {}", name, err))
+            .map(ToString::to_string)
             .collect::<Vec<_>>()
             .join("; "),
         CoreError::MissingProject => "No project specified".to_string(),
+        CoreError::InvalidBackup(_) => error.to_string(),
+        CoreError::WorkspacePath(e) => e.to_string(),
+        CoreError::FileRead { path, source } => {
+            format!("Failed to read file '{}': {}", path.display(), source)
+        }
     };
 
     error_with_source(message, error)
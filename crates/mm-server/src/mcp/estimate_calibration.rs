@@ -0,0 +1,52 @@
+use mm_core::operations::memory::{EstimateCalibrationCommand, estimate_calibration};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+/// MCP tool for measuring estimate bias
+#[mcp_tool(
+    name = "estimate_calibration",
+    description = "Compare estimated with actual hours of a project's done tasks and report bias factors overall, per task type and per assignee"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct EstimateCalibrationTool {
+    /// Project to calibrate against; the default project when omitted
+    pub project_name: Option<String>,
+}
+
+impl EstimateCalibrationTool {
+    generate_call_tool!(
+        self,
+        EstimateCalibrationCommand { project_name },
+        estimate_calibration
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_tasks().returning(|_| Ok(vec![]));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let tool = EstimateCalibrationTool {
+            project_name: Some("project:x".to_string()),
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        assert!(text.contains("by_assignee"));
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<EstimateCalibrationTool>();
+    }
+}
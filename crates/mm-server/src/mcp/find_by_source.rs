@@ -0,0 +1,90 @@
+use mm_core::operations::memory::{FindBySourceCommand, find_by_source};
+use mm_memory::ObservationSource;
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+/// MCP tool for tracing facts back to their source
+#[mcp_tool(
+    name = "find_by_source",
+    description = "Find entities with observations learned from a URL, file or conversation. Every field given must match"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FindBySourceTool {
+    /// Web page or document URL
+    #[serde(default)]
+    pub url: Option<String>,
+    /// File path
+    #[serde(default)]
+    pub file: Option<String>,
+    /// Conversation identifier
+    #[serde(default)]
+    pub conversation_id: Option<String>,
+}
+
+impl FindBySourceTool {
+    generate_call_tool!(
+        self,
+        FindBySourceCommand {
+            source => ObservationSource {
+                url: self.url.clone(),
+                file: self.file.clone(),
+                conversation_id: self.conversation_id.clone(),
+            }
+        },
+        find_by_source
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::source::set_observation_source;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use mockall::predicate::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let mut properties = HashMap::new();
+        set_observation_source(
+            &mut properties,
+            "fact",
+            &ObservationSource {
+                url: Some("https://example.com".to_string()),
+                ..Default::default()
+            },
+        );
+        let entity = MemoryEntity {
+            name: "topic:1".to_string(),
+            observations: vec!["fact".to_string()],
+            properties,
+            ..Default::default()
+        };
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_by_source()
+            .with(eq("url:https://example.com"))
+            .returning(move |_| Ok(vec![entity.clone()]));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let tool = FindBySourceTool {
+            url: Some("https://example.com".to_string()),
+            file: None,
+            conversation_id: None,
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["matches"][0]["entity"]["name"], "topic:1");
+        assert_eq!(value["matches"][0]["observations"][0], "fact");
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<FindBySourceTool>();
+    }
+}
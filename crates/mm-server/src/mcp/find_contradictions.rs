@@ -0,0 +1,196 @@
+use mm_core::operations::memory::{Contradiction, FindContradictionsCommand, find_contradictions};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::McpServer;
+use rust_mcp_sdk::macros::mcp_tool;
+use rust_mcp_sdk::schema::schema_utils::CallToolError;
+use rust_mcp_sdk::schema::{
+    CallToolResult, CreateMessageRequestParams, CreateMessageResult, CreateMessageResultContent,
+    Role, SamplingMessage, SamplingMessageContent, TextContent,
+};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// MCP tool for surfacing conflicting facts in memory
+#[mcp_tool(
+    name = "find_contradictions",
+    description = "Flag entities whose properties and observations disagree, or whose observations negate each other. Set confirm_with_client to have the client's model double-check each candidate"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FindContradictionsTool {
+    /// Only inspect entities with any of these labels
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Ask the client to confirm each candidate via sampling, when supported
+    #[serde(default)]
+    pub confirm_with_client: bool,
+}
+
+impl FindContradictionsTool {
+    generate_call_tool!(
+        self,
+        FindContradictionsCommand { labels },
+        find_contradictions
+    );
+
+    /// Run the heuristics, then let the client's model confirm each candidate
+    ///
+    /// Falls back to the heuristic results when the client does not support
+    /// sampling or a sampling request fails.
+    pub async fn call_tool_with_sampling<M, G>(
+        &self,
+        ports: &mm_core::Ports<M, G>,
+        runtime: &dyn McpServer,
+    ) -> Result<CallToolResult, CallToolError>
+    where
+        M: mm_memory::MemoryRepository + Send + Sync,
+        G: mm_git::GitRepository + Send + Sync,
+        M::Error: std::error::Error + Send + Sync + 'static,
+        G::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let command = FindContradictionsCommand {
+            labels: self.labels.clone(),
+        };
+        let mut result = find_contradictions(ports, command)
+            .await
+            .map_err(crate::mcp::error::into_call_tool_error)?;
+
+        if runtime.client_supports_sampling().unwrap_or(false) {
+            for contradiction in &mut result.contradictions {
+                match runtime
+                    .create_message(confirmation_request(contradiction))
+                    .await
+                {
+                    Ok(reply) => contradiction.confirmed = parse_confirmation(&reply),
+                    Err(e) => {
+                        warn!("Sampling request failed: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+
+        let json = serde_json::to_value(result).map_err(crate::mcp::error::into_call_tool_error)?;
+        Ok(CallToolResult::text_content(json.to_string(), None))
+    }
+}
+
+/// Build the yes/no question sent to the client for one candidate
+fn confirmation_request(contradiction: &Contradiction) -> CreateMessageRequestParams {
+    let mut text = format!(
+        "Entity `{}` records these facts:\n",
+        contradiction.entity_name
+    );
+    for observation in &contradiction.observations {
+        text.push_str(&format!("- {observation}\n"));
+    }
+    if let Some(property) = &contradiction.property {
+        text.push_str(&format!(
+            "They were compared against its `{property}` property.\n"
+        ));
+    }
+    text.push_str("Do these facts contradict each other? Answer yes or no.");
+
+    CreateMessageRequestParams {
+        include_context: None,
+        max_tokens: 8,
+        messages: vec![SamplingMessage {
+            content: SamplingMessageContent::TextContent(TextContent::new(text, None)),
+            role: Role::User,
+        }],
+        metadata: None,
+        model_preferences: None,
+        stop_sequences: Vec::new(),
+        system_prompt: Some(
+            "You review a knowledge graph for conflicting facts. Reply with a single word."
+                .to_string(),
+        ),
+        temperature: Some(0.0),
+    }
+}
+
+/// Read a yes/no answer, ignoring anything else the model says
+fn parse_confirmation(reply: &CreateMessageResult) -> Option<bool> {
+    let CreateMessageResultContent::TextContent(content) = &reply.content else {
+        return None;
+    };
+    let answer = content.text.split_whitespace().next()?.to_lowercase();
+    match answer.trim_matches(|c: char| !c.is_alphabetic()) {
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_core::operations::memory::ContradictionKind;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    fn reply(text: &str) -> CreateMessageResult {
+        CreateMessageResult {
+            content: CreateMessageResultContent::TextContent(TextContent::new(
+                text.to_string(),
+                None,
+            )),
+            meta: None,
+            model: "test".to_string(),
+            role: Role::Assistant,
+            stop_reason: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_by_labels().returning(|_, _, _| {
+            Ok(vec![MemoryEntity {
+                name: "project:x".to_string(),
+                observations: vec!["CI: github".to_string(), "CI: gitlab".to_string()],
+                ..Default::default()
+            }])
+        });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let tool = FindContradictionsTool {
+            labels: vec![],
+            confirm_with_client: false,
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["contradictions"][0]["entity_name"], "project:x");
+        assert_eq!(value["contradictions"][0]["kind"], "conflicting_values");
+    }
+
+    #[test]
+    fn test_confirmation_round_trip() {
+        let contradiction = Contradiction {
+            entity_name: "project:x".to_string(),
+            kind: ContradictionKind::PropertyMismatch,
+            observations: vec!["status: archived".to_string()],
+            property: Some("status".to_string()),
+            confirmed: None,
+        };
+        let request = confirmation_request(&contradiction);
+        let SamplingMessageContent::TextContent(content) = &request.messages[0].content else {
+            panic!("expected text content");
+        };
+        assert!(content.text.contains("- status: archived"));
+        assert!(content.text.contains("`status` property"));
+
+        assert_eq!(parse_confirmation(&reply("Yes.")), Some(true));
+        assert_eq!(parse_confirmation(&reply(" no, they agree")), Some(false));
+        assert_eq!(parse_confirmation(&reply("No")), Some(false));
+        assert_eq!(parse_confirmation(&reply("maybe")), None);
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<FindContradictionsTool>();
+    }
+}
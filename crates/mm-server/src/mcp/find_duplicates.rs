@@ -0,0 +1,71 @@
+use mm_core::operations::memory::{FindDuplicatesCommand, find_duplicates};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+/// MCP tool for finding near-duplicate entities
+#[mcp_tool(
+    name = "find_duplicates",
+    description = "Report pairs of entities that likely describe the same thing, scored 0-1 by name similarity and shared relationships. Review the candidates, then fold them together with merge_entities"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FindDuplicatesTool {
+    /// Only compare entities with any of these labels
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Lowest score reported, 0.8 when omitted
+    #[serde(default)]
+    pub min_score: Option<f64>,
+}
+
+impl FindDuplicatesTool {
+    generate_call_tool!(
+        self,
+        FindDuplicatesCommand { labels, min_score },
+        find_duplicates
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_by_labels().returning(|_, _, _| {
+            let entity = |name: &str| MemoryEntity {
+                name: name.to_string(),
+                labels: vec!["Project".to_string()],
+                ..Default::default()
+            };
+            Ok(vec![
+                entity("andoriyu:project:middle_manager"),
+                entity("andoriyu:project:midle_manager"),
+            ])
+        });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let tool = FindDuplicatesTool {
+            labels: vec![],
+            min_score: Some(0.5),
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(
+            value["candidates"][0]["source"],
+            "andoriyu:project:midle_manager"
+        );
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<FindDuplicatesTool>();
+    }
+}
@@ -1,5 +1,5 @@
 use mm_core::operations::memory::{FindEntitiesByLabelsCommand, find_entities_by_labels};
-use mm_memory::LabelMatchMode;
+use mm_memory::{LabelMatchMode, PropertyFilter, PropertySort};
 use mm_utils::IntoJsonSchema;
 use rust_mcp_sdk::macros::mcp_tool;
 use serde::{Deserialize, Serialize};
@@ -13,6 +13,27 @@ pub struct FindEntitiesByLabelsTool {
     pub labels: Vec<String>,
     pub match_mode: LabelMatchMode,
     pub required_label: Option<String>,
+    /// Skip results whose confidence is below this score (0-1)
+    #[serde(default)]
+    pub min_confidence: Option<f64>,
+    /// Maximum number of results; all of them when omitted
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// `next_cursor` from the previous page to continue from
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Property predicates, e.g. `{"property": "status", "op": "equals", "value": "todo"}`;
+    /// `op` is one of `equals` (default), `contains`, `greater_than`, `less_than`
+    #[serde(default)]
+    pub properties: Vec<PropertyFilter>,
+    /// Order by properties instead of confidence, e.g.
+    /// `[{"property": "adopted_at", "direction": "desc"}]`; `direction` is
+    /// `asc` (default) or `desc` and entities without the property come last
+    #[serde(default)]
+    pub sort_by: Vec<PropertySort>,
+    /// Leave out entities carrying any of these labels, e.g. `["Session"]`
+    #[serde(default)]
+    pub exclude_labels: Vec<String>,
 }
 
 impl FindEntitiesByLabelsTool {
@@ -21,7 +42,13 @@ impl FindEntitiesByLabelsTool {
         FindEntitiesByLabelsCommand {
             labels => self.labels.clone(),
             match_mode => self.match_mode,
-            required_label => self.required_label.clone()
+            required_label => self.required_label.clone(),
+            min_confidence => self.min_confidence,
+            limit => self.limit,
+            cursor,
+            properties,
+            sort_by,
+            exclude_labels
         },
         find_entities_by_labels
     );
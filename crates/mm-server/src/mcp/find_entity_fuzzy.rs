@@ -0,0 +1,69 @@
+use mm_core::operations::memory::{FindEntityFuzzyCommand, find_entity_fuzzy};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+/// MCP tool for looking up entities by an approximate name
+#[mcp_tool(
+    name = "find_entity_fuzzy",
+    description = "Find entities whose names resemble a possibly misspelled name, closest first with a 0-1 score. Use it when get_entity finds nothing"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FindEntityFuzzyTool {
+    /// Approximate entity name, e.g. `andoriyu:project:midle_manager`
+    pub name: String,
+    /// Maximum number of matches, 5 when omitted
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl FindEntityFuzzyTool {
+    generate_call_tool!(
+        self,
+        FindEntityFuzzyCommand { name, limit },
+        find_entity_fuzzy
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::fuzzy::rank_by_name;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entities_fuzzy()
+            .returning(|name, limit, _| {
+                let entity = MemoryEntity {
+                    name: "andoriyu:project:middle_manager".to_string(),
+                    ..Default::default()
+                };
+                Ok(rank_by_name(vec![entity], name, limit))
+            });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let tool = FindEntityFuzzyTool {
+            name: "andoriyu:project:midle_manager".to_string(),
+            limit: None,
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(
+            value["matches"][0]["entity"]["name"],
+            "andoriyu:project:middle_manager"
+        );
+        assert!(value["matches"][0]["score"].as_f64().unwrap() > 0.9);
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<FindEntityFuzzyTool>();
+    }
+}
@@ -0,0 +1,60 @@
+use mm_core::operations::memory::{FindPathCommand, find_path};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+fn default_max_depth() -> u32 {
+    3
+}
+
+#[mcp_tool(
+    name = "find_path",
+    description = "Find a shortest chain of relationships connecting two entities, followed in either direction, to explain how they are related. Returns the entity names along the path and the relationships between them, or a null path when they are not connected within max_depth"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FindPathTool {
+    /// Entity to start from
+    pub from: String,
+    /// Entity to reach
+    pub to: String,
+    /// Most relationships to follow (1-5, default 3)
+    #[serde(default = "default_max_depth")]
+    pub max_depth: u32,
+    /// Only follow relationships of these types
+    #[serde(default)]
+    pub relationship_types: Vec<String>,
+}
+
+impl FindPathTool {
+    generate_call_tool!(
+        self,
+        FindPathCommand {
+            from,
+            to,
+            max_depth => self.max_depth,
+            relationship_types
+        },
+        find_path
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_not_connected() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_path().returning(|_, _, _, _| Ok(None));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let tool: FindPathTool = serde_json::from_str(r#"{"from": "a", "to": "b"}"#).unwrap();
+        assert_eq!(tool.max_depth, 3);
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        assert_eq!(text, r#"{"path":null}"#);
+    }
+}
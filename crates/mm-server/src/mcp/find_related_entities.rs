@@ -1,8 +1,25 @@
-use mm_core::operations::memory::{FindRelatedEntitiesCommand, find_related_entities};
-use mm_memory::RelationshipDirection;
+use futures::TryStreamExt;
+use mm_core::operations::memory::{
+    FindRelatedEntitiesCommand, FindRelatedEntitiesResult, find_related_entities,
+    stream_related_entities,
+};
+use mm_memory::confidence::rank_entities;
+use mm_memory::{MemoryEntity, RelationshipDirection};
 use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::McpServer;
 use rust_mcp_sdk::macros::mcp_tool;
+use rust_mcp_sdk::schema::{
+    CallToolResult, LoggingLevel, LoggingMessageNotificationParams, schema_utils::CallToolError,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::warn;
+
+/// Number of entities sent in each partial result notification
+const PARTIAL_RESULT_BATCH: usize = 25;
+
+/// Logger name attached to partial result notifications
+const PARTIAL_RESULT_LOGGER: &str = "find_related_entities";
 
 #[mcp_tool(
     name = "find_related_entities",
@@ -14,6 +31,9 @@ pub struct FindRelatedEntitiesTool {
     pub relationship: Option<String>,
     pub direction: Option<RelationshipDirection>,
     pub depth: u32,
+    /// Skip results whose confidence is below this score (0-1)
+    #[serde(default)]
+    pub min_confidence: Option<f64>,
 }
 
 impl FindRelatedEntitiesTool {
@@ -23,31 +43,104 @@ impl FindRelatedEntitiesTool {
             name,
             relationship,
             direction,
-            depth
+            depth,
+            min_confidence
         },
         find_related_entities
     );
+
+    /// Execute the tool, sending entities to the client as they arrive.
+    ///
+    /// Batches of entities go out as `notifications/message` log messages
+    /// while the traversal is still running. Each batch is awaited before the
+    /// next rows are read, so a slow client slows the query instead of the
+    /// server buffering the traversal. The final result still carries every
+    /// entity, ranked by confidence.
+    pub async fn call_tool_with_progress<M, G>(
+        &self,
+        ports: &mm_core::Ports<M, G>,
+        runtime: &dyn McpServer,
+    ) -> Result<CallToolResult, CallToolError>
+    where
+        M: mm_memory::MemoryRepository + Send + Sync,
+        G: mm_git::GitRepository + Send + Sync,
+        M::Error: std::error::Error + Send + Sync + 'static,
+        G::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let command = FindRelatedEntitiesCommand {
+            name: self.name.clone(),
+            relationship: self.relationship.clone(),
+            direction: self.direction,
+            depth: self.depth,
+            min_confidence: self.min_confidence,
+        };
+        let mut stream = stream_related_entities(ports, command)
+            .await
+            .map_err(crate::mcp::error::into_call_tool_error)?;
+
+        let mut entities = Vec::new();
+        let mut sent = 0;
+        let mut notify = true;
+        loop {
+            // CallToolError is not Send, so it must not be held across the
+            // notification await below
+            let entity = match stream.try_next().await {
+                Ok(Some(entity)) => entity,
+                Ok(None) => break,
+                Err(e) => return Err(crate::mcp::error::into_call_tool_error(e)),
+            };
+            entities.push(entity);
+            if notify && entities.len() - sent == PARTIAL_RESULT_BATCH {
+                notify = send_partial(runtime, &self.name, &entities[sent..]).await;
+                sent = entities.len();
+            }
+        }
+
+        // Partial batches arrive unranked; the final result is ranked
+        rank_entities(&mut entities);
+        let result = FindRelatedEntitiesResult { entities };
+        let json = serde_json::to_value(result).map_err(crate::mcp::error::into_call_tool_error)?;
+        Ok(CallToolResult::text_content(json.to_string(), None))
+    }
+}
+
+/// Send one batch of partial results, returning whether to keep sending
+async fn send_partial(runtime: &dyn McpServer, name: &str, batch: &[MemoryEntity]) -> bool {
+    let params = LoggingMessageNotificationParams {
+        data: json!({ "name": name, "entities": batch }),
+        level: LoggingLevel::Info,
+        logger: Some(PARTIAL_RESULT_LOGGER.to_string()),
+    };
+    match runtime.send_logging_message(params).await {
+        Ok(()) => true,
+        Err(e) => {
+            // The final result still has every entity
+            warn!("Failed to send partial results: {}", e);
+            false
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures::{StreamExt, stream};
     use mm_core::Ports;
-    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
     use mockall::predicate::*;
     use std::sync::Arc;
 
     #[tokio::test]
     async fn test_call_tool_success() {
         let mut mock = MockMemoryRepository::new();
-        mock.expect_find_related_entities()
+        mock.expect_stream_related_entities()
             .with(
                 eq("a"),
                 eq(Some("rel".to_string())),
                 eq(Some(RelationshipDirection::Outgoing)),
                 eq(2u32),
             )
-            .returning(|_, _, _, _| Ok(vec![MemoryEntity::default()]));
+            .returning(|_, _, _, _| Ok(stream::iter([Ok(MemoryEntity::default())]).boxed()));
         let service = MemoryService::new(mock, MemoryConfig::default());
         let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
 
@@ -56,6 +149,7 @@ mod tests {
             relationship: Some("rel".into()),
             direction: Some(RelationshipDirection::Outgoing),
             depth: 2,
+            min_confidence: None,
         };
 
         let result = tool.call_tool(&ports).await.expect("tool should succeed");
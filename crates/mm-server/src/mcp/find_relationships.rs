@@ -12,6 +12,15 @@ pub struct FindRelationshipsTool {
     pub from: Option<String>,
     pub to: Option<String>,
     pub name: Option<String>,
+    /// Skip results whose confidence is below this score (0-1)
+    #[serde(default)]
+    pub min_confidence: Option<f64>,
+    /// Maximum number of results; all of them when omitted
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// `next_cursor` from the previous page to continue from
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 impl FindRelationshipsTool {
@@ -20,7 +29,10 @@ impl FindRelationshipsTool {
         FindRelationshipsCommand {
             from => self.from.clone(),
             to => self.to.clone(),
-            name => self.name.clone()
+            name => self.name.clone(),
+            min_confidence => self.min_confidence,
+            limit => self.limit,
+            cursor
         },
         find_relationships
     );
@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use mm_core::operations::memory::{GetAuditLogCommand, get_audit_log};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+#[mcp_tool(
+    name = "get_audit_log",
+    description = "Changes made to the graph, newest first: when, by which agent, the operation and the entities it touched, and the correlation id of the tool call. Filter by actor, operation, entity name or time. Changes are recorded only when `memory.audit_log` is set"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetAuditLogTool {
+    /// Only changes made by this agent
+    #[serde(default)]
+    pub actor: Option<String>,
+    /// Only changes made by this operation, e.g. `update_entity`
+    #[serde(default)]
+    pub operation: Option<String>,
+    /// Only changes affecting this entity
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Only changes at or after this RFC 3339 timestamp
+    #[serde(default)]
+    #[schemars(with = "Option<String>")]
+    pub since: Option<DateTime<Utc>>,
+    /// Maximum number of changes to return
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl GetAuditLogTool {
+    generate_call_tool!(
+        self,
+        GetAuditLogCommand {
+            actor,
+            operation,
+            name,
+            since => self.since,
+            limit => self.limit
+        },
+        get_audit_log
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_without_audit_log() {
+        let service = MemoryService::new(MockMemoryRepository::new(), MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let tool = GetAuditLogTool {
+            actor: None,
+            operation: None,
+            name: None,
+            since: None,
+            limit: None,
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        assert_eq!(text, "[]");
+    }
+}
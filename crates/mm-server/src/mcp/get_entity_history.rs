@@ -0,0 +1,55 @@
+use mm_core::operations::memory::{GetEntityHistoryCommand, get_entity_history};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+#[mcp_tool(
+    name = "get_entity_history",
+    description = "Earlier versions of an entity's labels, observations and properties, newest first, with when and by which agent each was replaced. Versions are recorded only when `memory.history` is enabled"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetEntityHistoryTool {
+    pub name: String,
+    /// Maximum number of versions to return
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl GetEntityHistoryTool {
+    generate_call_tool!(
+        self,
+        GetEntityHistoryCommand {
+            name,
+            limit => self.limit
+        },
+        get_entity_history
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_without_history() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|name| {
+            Ok(Some(MemoryEntity {
+                name: name.to_string(),
+                ..Default::default()
+            }))
+        });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let tool = GetEntityHistoryTool {
+            name: "e".into(),
+            limit: None,
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        assert_eq!(text, "[]");
+    }
+}
@@ -11,7 +11,7 @@ use std::path::PathBuf;
 )]
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct GetGitStatusTool {
-    /// Path to the Git repository
+    /// Path to the Git repository, absolute or relative to the workspace root
     pub path: PathBuf,
 }
 
@@ -0,0 +1,68 @@
+use mm_core::operations::memory::{GetNeighborhoodCommand, get_neighborhood};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+fn default_depth() -> u32 {
+    1
+}
+
+#[mcp_tool(
+    name = "get_neighborhood",
+    description = "Export the subgraph around an entity: every entity within depth relationships of it, followed in either direction, and every relationship between those entities. Use it to load compact context about one area of the graph in a single call"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetNeighborhoodTool {
+    /// Entity at the center of the subgraph
+    pub name: String,
+    /// Most relationships from the center (1-5, default 1)
+    #[serde(default = "default_depth")]
+    pub depth: u32,
+    /// Only follow and include relationships of this type
+    #[serde(default)]
+    pub relationship: Option<String>,
+}
+
+impl GetNeighborhoodTool {
+    generate_call_tool!(
+        self,
+        GetNeighborhoodCommand {
+            name,
+            depth => self.depth,
+            relationship
+        },
+        get_neighborhood
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_lone_entity() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|name| {
+            Ok(Some(MemoryEntity {
+                name: name.to_string(),
+                ..Default::default()
+            }))
+        });
+        mock.expect_find_related_entities()
+            .returning(|_, _, _, _| Ok(vec![]));
+        mock.expect_find_relationships()
+            .returning(|_, _, _| Ok(vec![]));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let tool: GetNeighborhoodTool = serde_json::from_str(r#"{"name": "a"}"#).unwrap();
+        assert_eq!(tool.depth, 1);
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = &result.content[0].as_text_content().unwrap().text;
+        let value: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert_eq!(value["entities"][0]["name"], "a");
+        assert_eq!(value["relationships"], serde_json::json!([]));
+    }
+}
@@ -0,0 +1,67 @@
+use mm_core::operations::memory::{GetObservationsCommand, get_observations};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+/// MCP tool for listing observations with their authors
+#[mcp_tool(
+    name = "get_observations",
+    description = "List an entity's observations with when and by which agent each was written, when recorded. Use older_than_days to find stale ones"
+)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetObservationsTool {
+    /// Entity whose observations to list
+    pub name: String,
+    /// Only observations written at least this many days ago, or at an
+    /// unknown time
+    #[serde(default)]
+    pub older_than_days: Option<u32>,
+}
+
+impl GetObservationsTool {
+    generate_call_tool!(
+        self,
+        GetObservationsCommand {
+            name,
+            older_than_days
+        },
+        get_observations
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let entity = MemoryEntity {
+            name: "topic".to_string(),
+            observations: vec!["fact".to_string()],
+            ..Default::default()
+        };
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name()
+            .returning(move |_| Ok(Some(entity.clone())));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let tool = GetObservationsTool {
+            name: "topic".to_string(),
+            older_than_days: None,
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["observations"][0]["text"], "fact");
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<GetObservationsTool>();
+    }
+}
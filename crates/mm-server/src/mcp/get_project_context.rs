@@ -15,6 +15,10 @@ pub struct GetProjectContextTool {
 
     /// Repository name to look up (e.g., "andoriyu/middle-manager")
     pub repository_name: Option<String>,
+
+    /// Leave out related entities carrying any of these labels, e.g. `["Session"]`
+    #[serde(default)]
+    pub exclude_labels: Vec<String>,
 }
 
 impl GetProjectContextTool {
@@ -33,7 +37,8 @@ impl GetProjectContextTool {
                         .into_boxed_dyn_error()
                     ));
                 }
-            }
+            },
+            exclude_labels
         },
         get_project_context
     );
@@ -44,8 +49,9 @@ mod tests {
     use super::*;
     use mm_core::Ports;
     use mm_core::operations::memory::{PROJECT_LABEL, TECHNOLOGY_LABEL};
-    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
-    use mockall::predicate::*;
+    use mm_memory::{
+        EntitySnapshot, MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository,
+    };
     use std::collections::HashMap;
     use std::sync::Arc;
 
@@ -58,6 +64,7 @@ mod tests {
             observations: vec!["A project for managing memory".to_string()],
             properties: HashMap::new(),
             relationships: Vec::new(),
+            revision: 0,
         };
 
         let related_entity = MemoryEntity {
@@ -70,31 +77,19 @@ mod tests {
             observations: vec!["A systems programming language".to_string()],
             properties: HashMap::new(),
             relationships: Vec::new(),
+            revision: 0,
         };
 
         // Setup mock repository
         let mut mock = MockMemoryRepository::new();
 
-        // Clone entities for each closure to avoid moved value errors
-        let project_entity_clone1 = project_entity.clone();
-        mock.expect_find_entity_by_name()
-            .with(eq("andoriyu:project:middle_manager"))
-            .returning(move |_| Ok(Some(project_entity_clone1.clone())));
-
-        let project_entity_clone2 = project_entity.clone();
-        let related_entity_clone = related_entity.clone();
-        mock.expect_find_related_entities()
-            .with(
-                eq("andoriyu:project:middle_manager"),
-                always(),
-                always(),
-                always(),
-            )
-            .returning(move |_, _, _, _| {
-                Ok(vec![
-                    project_entity_clone2.clone(),
-                    related_entity_clone.clone(),
-                ])
+        mock.expect_find_entity_snapshot()
+            .withf(|name, _| name == "andoriyu:project:middle_manager")
+            .returning(move |_, _| {
+                Ok(Some(EntitySnapshot {
+                    entity: project_entity.clone(),
+                    related: vec![vec![project_entity.clone(), related_entity.clone()]; 5],
+                }))
             });
 
         // Create service and ports
@@ -105,6 +100,7 @@ mod tests {
         let tool = GetProjectContextTool {
             project_name: Some("andoriyu:project:middle_manager".to_string()),
             repository_name: None,
+            exclude_labels: Vec::new(),
         };
 
         let result = tool.call_tool(&ports).await.expect("tool should succeed");
@@ -123,6 +119,7 @@ mod tests {
         let tool = GetProjectContextTool {
             project_name: None,
             repository_name: None,
+            exclude_labels: Vec::new(),
         };
 
         let result = tool.call_tool(&ports).await;
@@ -4,7 +4,10 @@ use rust_mcp_sdk::macros::mcp_tool;
 use serde::{Deserialize, Serialize};
 
 /// MCP tool for listing available projects
-#[mcp_tool(name = "list_projects", description = "List all available projects")]
+#[mcp_tool(
+    name = "list_projects",
+    description = "List all available projects with their task counts, open task counts and latest activity"
+)]
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ListProjectsTool {
     /// Optional name filter to narrow down results
@@ -26,8 +29,9 @@ mod tests {
     use super::*;
     use mm_core::Ports;
     use mm_core::operations::memory::PROJECT_LABEL;
-    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
-    use mockall::predicate::*;
+    use mm_memory::{
+        MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository, ProjectSummary,
+    };
     use std::collections::HashMap;
     use std::sync::Arc;
 
@@ -39,6 +43,7 @@ mod tests {
             observations: vec!["A project for managing memory".to_string()],
             properties: HashMap::new(),
             relationships: Vec::new(),
+            revision: 0,
         };
 
         let project2 = MemoryEntity {
@@ -47,16 +52,16 @@ mod tests {
             observations: vec!["A project for managing Nix flakes".to_string()],
             properties: HashMap::new(),
             relationships: Vec::new(),
+            revision: 0,
         };
 
         let mut mock = MockMemoryRepository::new();
-        mock.expect_find_entities_by_labels()
-            .with(
-                eq(vec![PROJECT_LABEL.to_string()]),
-                eq(mm_memory::LabelMatchMode::All),
-                always(),
-            )
-            .returning(move |_, _, _| Ok(vec![project1.clone(), project2.clone()]));
+        mock.expect_list_project_summaries().returning(move |_| {
+            Ok(vec![
+                ProjectSummary::from_tasks(project1.clone(), &[]),
+                ProjectSummary::from_tasks(project2.clone(), &[]),
+            ])
+        });
 
         let service = MemoryService::new(mock, MemoryConfig::default());
         let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
@@ -78,6 +83,7 @@ mod tests {
             observations: vec!["A project for managing memory".to_string()],
             properties: HashMap::new(),
             relationships: Vec::new(),
+            revision: 0,
         };
 
         let project2 = MemoryEntity {
@@ -86,16 +92,16 @@ mod tests {
             observations: vec!["A project for managing Nix flakes".to_string()],
             properties: HashMap::new(),
             relationships: Vec::new(),
+            revision: 0,
         };
 
         let mut mock = MockMemoryRepository::new();
-        mock.expect_find_entities_by_labels()
-            .with(
-                eq(vec![PROJECT_LABEL.to_string()]),
-                eq(mm_memory::LabelMatchMode::All),
-                always(),
-            )
-            .returning(move |_, _, _| Ok(vec![project1.clone(), project2.clone()]));
+        mock.expect_list_project_summaries().returning(move |_| {
+            Ok(vec![
+                ProjectSummary::from_tasks(project1.clone(), &[]),
+                ProjectSummary::from_tasks(project2.clone(), &[]),
+            ])
+        });
 
         let service = MemoryService::new(mock, MemoryConfig::default());
         let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
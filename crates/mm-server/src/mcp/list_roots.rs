@@ -0,0 +1,45 @@
+use mm_core::operations::roots::{ListRootsCommand, list_roots};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+/// MCP tool for listing the workspace roots known to the server
+#[mcp_tool(
+    name = "list_roots",
+    description = "List the normalized workspace roots provided by the client"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListRootsTool {}
+
+impl ListRootsTool {
+    generate_call_tool!(self, ListRootsCommand {}, list_roots);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::{Ports, Root, RootCollection};
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let collection = RootCollection::from_roots(vec![Root::new(
+            Some("repo".to_string()),
+            "file:///tmp/repo".to_string(),
+        )]);
+        let ports = Ports::noop().with(|p| p.roots = Arc::new(RwLock::new(collection)));
+
+        let result = ListRootsTool {}.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["roots"][0]["uri"], "file:///tmp/repo");
+        assert_eq!(value["roots"][0]["name"], "repo");
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<ListRootsTool>();
+    }
+}
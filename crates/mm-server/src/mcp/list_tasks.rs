@@ -1,4 +1,6 @@
-use mm_core::operations::memory::{ListTasksCommand, list_tasks};
+use chrono::NaiveDate;
+use mm_core::operations::memory::{ListTasksCommand, Priority, TaskStatus, list_tasks};
+use mm_memory::date::deserialize_optional_date;
 use mm_utils::IntoJsonSchema;
 use rust_mcp_sdk::macros::mcp_tool;
 use serde::{Deserialize, Serialize};
@@ -10,12 +12,43 @@ pub struct ListTasksTool {
     pub project_name: Option<String>,
     /// Labels to filter by
     pub labels: Vec<String>,
+    /// Only include tasks with this status
+    #[serde(default)]
+    pub status: Option<TaskStatus>,
+    /// Only include tasks with this priority
+    #[serde(default)]
+    pub priority: Option<Priority>,
+    /// Only include tasks due before this date, e.g. `2025-07-01`, `tomorrow`
+    /// or `next friday`
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
+    #[schemars(with = "Option<String>")]
+    pub due_before: Option<NaiveDate>,
+    /// Only include tasks due on or after this date
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
+    #[schemars(with = "Option<String>")]
+    pub due_after: Option<NaiveDate>,
 }
 
 impl ListTasksTool {
+    /// Whether the call lists every task of the project
+    pub fn is_unfiltered(&self) -> bool {
+        self.labels.is_empty()
+            && self.status.is_none()
+            && self.priority.is_none()
+            && self.due_before.is_none()
+            && self.due_after.is_none()
+    }
+
     generate_call_tool!(
         self,
-        ListTasksCommand { project_name => self.project_name.clone(), labels => self.labels.clone() },
+        ListTasksCommand {
+            project_name => self.project_name.clone(),
+            labels => self.labels.clone(),
+            status => self.status.clone(),
+            priority => self.priority.clone(),
+            due_before,
+            due_after
+        },
         list_tasks
     );
 }
@@ -26,7 +59,7 @@ mod tests {
     use mm_core::operations::memory::TASK_LABEL;
     use mm_core::{Ports, operations::memory::TaskProperties};
     use mm_memory::{
-        MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository, RelationshipDirection,
+        MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository, TaskFilter,
         labels::ACTIVE_LABEL, value::MemoryValue,
     };
     use mockall::predicate::*;
@@ -42,16 +75,12 @@ mod tests {
             observations: vec![],
             properties: props.clone(),
             relationships: vec![],
+            revision: 0,
         };
         let mut mock = MockMemoryRepository::new();
-        mock.expect_find_related_entities()
-            .with(
-                eq("proj"),
-                eq(Some("contains".to_string())),
-                eq(Some(RelationshipDirection::Outgoing)),
-                eq(1u32),
-            )
-            .returning(move |_, _, _, _| Ok(vec![task.clone()]));
+        mock.expect_find_tasks()
+            .with(eq(TaskFilter::for_project("proj")))
+            .returning(move |_| Ok(vec![task.clone()]));
 
         let service = MemoryService::new(
             mock,
@@ -65,6 +94,10 @@ mod tests {
         let tool = ListTasksTool {
             project_name: None,
             labels: vec![],
+            status: None,
+            priority: None,
+            due_before: None,
+            due_after: None,
         };
         let result = tool.call_tool(&ports).await.unwrap();
         let text = result.content[0].as_text_content().unwrap().text.clone();
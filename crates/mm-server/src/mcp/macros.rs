@@ -10,10 +10,11 @@
 ///   command fields; optionally override a field with an expression using
 ///   `field => expr`.
 /// * `$operation` - path to the async core operation to call.
-/// * Optional `$success_msg` - static message to return on success.
+/// * Optional `$success_msg` - static message to return on success, followed
+///   by one line per warning of the result (see [`mm_core::HasWarnings`]).
 ///
 /// ### Examples
-/// ```no_run
+/// ```ignore
 /// // Automatically serialize result to JSON
 /// impl ExampleTool {
 ///     generate_call_tool!(
@@ -95,11 +96,15 @@ macro_rules! generate_call_tool {
             let span = tracing::info_span!("call_tool");
             async move {
                 // Convert core errors into CallToolError using anyhow
-                $operation(ports, command).await
+                let result = $operation(ports, command).await
                     .map_err(crate::mcp::error::into_call_tool_error)?;
 
-                // Return the success message
-                Ok(rust_mcp_sdk::schema::CallToolResult::text_content($success_msg.to_string(), None))
+                // Return the success message along with any warnings
+                let text = crate::mcp::with_warnings(
+                    $success_msg,
+                    mm_core::HasWarnings::warnings(&result),
+                );
+                Ok(rust_mcp_sdk::schema::CallToolResult::text_content(text, None))
             }
             .instrument(span)
             .await
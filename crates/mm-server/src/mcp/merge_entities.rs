@@ -0,0 +1,68 @@
+use mm_core::operations::memory::{MergeEntitiesCommand, merge_entities};
+use mm_memory::MemoryValue;
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[mcp_tool(
+    name = "merge_entities",
+    description = "Merge duplicate entities into a target: observations, labels and missing properties are copied over, relationships are re-pointed at the target and the duplicates are deleted. Returns the merged entity"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct MergeEntitiesTool {
+    /// Entity to keep
+    pub target: String,
+    /// Duplicate entities to fold into the target
+    pub sources: Vec<String>,
+    /// Values to keep for properties the entities disagree on, by property;
+    /// the target's value is kept for the others
+    #[serde(default)]
+    pub resolutions: HashMap<String, MemoryValue>,
+}
+
+impl MergeEntitiesTool {
+    generate_call_tool!(
+        self,
+        MergeEntitiesCommand {
+            target,
+            sources,
+            resolutions
+        },
+        merge_entities
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|name| {
+            Ok(Some(MemoryEntity {
+                name: name.to_string(),
+                labels: vec![format!("{name}_label")],
+                ..Default::default()
+            }))
+        });
+        mock.expect_merge_entities()
+            .withf(|merged, sources| merged.name == "a" && sources == ["b"])
+            .returning(|_, _| Ok(()));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let tool = MergeEntitiesTool {
+            target: "a".into(),
+            sources: vec!["b".into()],
+            resolutions: HashMap::new(),
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        let merged: MemoryEntity = serde_json::from_str(&text).unwrap();
+        assert_eq!(merged.labels, ["a_label", "b_label"]);
+    }
+}
@@ -1,56 +1,166 @@
 #[macro_use]
 mod macros;
+pub mod add_observations;
+pub mod attach_file_reference;
+pub mod compression;
+pub mod correlation;
 pub mod create_entities;
+pub mod create_from_template;
 pub mod create_relationships;
 pub mod create_tasks;
 pub mod delete_entities;
 pub mod delete_relationships;
 pub mod delete_task;
 pub mod error;
+pub mod estimate_calibration;
+pub mod find_by_source;
+pub mod find_contradictions;
+pub mod find_duplicates;
 pub mod find_entities_by_labels;
+pub mod find_entity_fuzzy;
+pub mod find_path;
 pub mod find_related_entities;
 pub mod find_relationships;
+pub mod get_audit_log;
 pub mod get_entity;
+pub mod get_entity_history;
 pub mod get_git_status;
 pub mod get_graph_meta;
+pub mod get_neighborhood;
+pub mod get_observations;
 pub mod get_project_context;
 pub mod get_task;
 pub mod list_projects;
+pub mod list_roots;
 pub mod list_tasks;
+pub mod merge_entities;
+pub mod propose_forget;
+pub mod purge_deleted_entities;
+pub mod remember;
+pub mod rename_entity;
+pub mod replace_in_observations;
+pub mod restore_entities;
+pub mod scan_secrets;
+pub mod server_info;
+pub mod slugify_name;
+pub mod spill;
+pub mod standup_summary;
+pub mod stream_project_context;
+pub mod task_report;
 #[cfg(test)]
 pub mod tests;
+pub mod transfer_ownership;
+pub mod update_entities;
 pub mod update_entity;
 pub mod update_relationship;
 pub mod update_task;
+pub mod validate_only;
+pub mod verify_attachments;
 
+use mm_core::Warning;
+use mm_memory::MemoryConfig;
 use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::schema::Tool;
 use rust_mcp_sdk::tool_box;
 use serde_json::{Map, Value};
 
+/// Every tool, with the property types declared in `relationship_rules`
+/// appended to the descriptions of the tools writing relationships and the
+/// configured templates to that of `create_from_template`
+pub fn configured_tools(config: &MemoryConfig) -> Vec<Tool> {
+    let mut declared: Vec<String> = config
+        .relationship_rules
+        .iter()
+        .filter(|(_, rule)| !rule.properties.is_empty())
+        .map(|(name, rule)| format!("{name} ({})", rule.describe_properties()))
+        .collect();
+    declared.sort();
+    let mut templates: Vec<&str> = config.templates.keys().map(String::as_str).collect();
+    templates.sort();
+
+    let mut tools = MMTools::tools();
+    for tool in &mut tools {
+        let note = match tool.name.as_str() {
+            "create_relationships" | "update_relationship" | "validate_only"
+                if !declared.is_empty() =>
+            {
+                format!(" Relationship properties: {}", declared.join("; "))
+            }
+            "create_from_template" if !templates.is_empty() => {
+                format!(" Configured templates: {}", templates.join(", "))
+            }
+            _ => continue,
+        };
+        tool.description.get_or_insert_default().push_str(&note);
+    }
+    tools
+}
+
+/// `message` followed by one `Warning: ...` line per warning
+pub(crate) fn with_warnings(message: &str, warnings: &[Warning]) -> String {
+    let mut text = message.to_string();
+    for warning in warnings {
+        text.push_str(&format!("\nWarning: {warning}"));
+    }
+    text
+}
+
+pub use add_observations::AddObservationsTool;
+pub use attach_file_reference::AttachFileReferenceTool;
 pub use create_entities::CreateEntitiesTool;
+pub use create_from_template::CreateFromTemplateTool;
 pub use create_relationships::CreateRelationshipsTool;
 pub use create_tasks::CreateTasksTool;
 pub use delete_entities::DeleteEntitiesTool;
 pub use delete_relationships::DeleteRelationshipsTool;
 pub use delete_task::DeleteTaskTool;
+pub use estimate_calibration::EstimateCalibrationTool;
+pub use find_by_source::FindBySourceTool;
+pub use find_contradictions::FindContradictionsTool;
+pub use find_duplicates::FindDuplicatesTool;
 pub use find_entities_by_labels::FindEntitiesByLabelsTool;
+pub use find_entity_fuzzy::FindEntityFuzzyTool;
+pub use find_path::FindPathTool;
 pub use find_related_entities::FindRelatedEntitiesTool;
 pub use find_relationships::FindRelationshipsTool;
+pub use get_audit_log::GetAuditLogTool;
 pub use get_entity::GetEntityTool;
+pub use get_entity_history::GetEntityHistoryTool;
 pub use get_git_status::GetGitStatusTool;
 pub use get_graph_meta::GetGraphMetaTool;
+pub use get_neighborhood::GetNeighborhoodTool;
+pub use get_observations::GetObservationsTool;
 pub use get_project_context::GetProjectContextTool;
 pub use get_task::GetTaskTool;
 pub use list_projects::ListProjectsTool;
+pub use list_roots::ListRootsTool;
 pub use list_tasks::ListTasksTool;
+pub use merge_entities::MergeEntitiesTool;
+pub use propose_forget::ProposeForgetTool;
+pub use purge_deleted_entities::PurgeDeletedEntitiesTool;
+pub use remember::RememberTool;
+pub use rename_entity::RenameEntityTool;
+pub use replace_in_observations::ReplaceInObservationsTool;
+pub use restore_entities::RestoreEntitiesTool;
+pub use scan_secrets::ScanSecretsTool;
+pub use server_info::ServerInfoTool;
+pub use slugify_name::SlugifyNameTool;
+pub use standup_summary::StandupSummaryTool;
+pub use stream_project_context::StreamProjectContextTool;
+pub use task_report::TaskReportTool;
+pub use transfer_ownership::TransferOwnershipTool;
+pub use update_entities::UpdateEntitiesTool;
 pub use update_entity::UpdateEntityTool;
 pub use update_relationship::UpdateRelationshipTool;
 pub use update_task::UpdateTaskTool;
+pub use validate_only::ValidateOnlyTool;
+pub use verify_attachments::VerifyAttachmentsTool;
 
 // Generate an enum with all tools
 tool_box!(
     MMTools,
     [
+        AddObservationsTool,
         CreateEntitiesTool,
         CreateRelationshipsTool,
         DeleteEntitiesTool,
@@ -68,8 +178,38 @@ tool_box!(
         GetGraphMetaTool,
         GetProjectContextTool,
         ListProjectsTool,
+        ListRootsTool,
         UpdateEntityTool,
-        UpdateRelationshipTool
+        UpdateRelationshipTool,
+        AttachFileReferenceTool,
+        VerifyAttachmentsTool,
+        RememberTool,
+        ProposeForgetTool,
+        FindBySourceTool,
+        FindContradictionsTool,
+        StreamProjectContextTool,
+        TaskReportTool,
+        StandupSummaryTool,
+        EstimateCalibrationTool,
+        ServerInfoTool,
+        FindEntityFuzzyTool,
+        RenameEntityTool,
+        MergeEntitiesTool,
+        RestoreEntitiesTool,
+        PurgeDeletedEntitiesTool,
+        TransferOwnershipTool,
+        GetEntityHistoryTool,
+        ReplaceInObservationsTool,
+        GetAuditLogTool,
+        ScanSecretsTool,
+        UpdateEntitiesTool,
+        FindPathTool,
+        GetNeighborhoodTool,
+        SlugifyNameTool,
+        ValidateOnlyTool,
+        GetObservationsTool,
+        CreateFromTemplateTool,
+        FindDuplicatesTool
     ]
 );
 
@@ -89,6 +229,7 @@ impl MMTools {
         G::Error: std::error::Error + Send + Sync + 'static,
     {
         match self {
+            MMTools::AddObservationsTool(tool) => tool.call_tool(ports).await,
             MMTools::CreateEntitiesTool(tool) => tool.call_tool(ports).await,
             MMTools::CreateRelationshipsTool(tool) => tool.call_tool(ports).await,
             MMTools::DeleteEntitiesTool(tool) => tool.call_tool(ports).await,
@@ -106,14 +247,82 @@ impl MMTools {
             MMTools::GetGraphMetaTool(tool) => tool.call_tool(ports).await,
             MMTools::GetProjectContextTool(tool) => tool.call_tool(ports).await,
             MMTools::ListProjectsTool(tool) => tool.call_tool(ports).await,
+            MMTools::ListRootsTool(tool) => tool.call_tool(ports).await,
             MMTools::UpdateEntityTool(tool) => tool.call_tool(ports).await,
             MMTools::UpdateRelationshipTool(tool) => tool.call_tool(ports).await,
+            MMTools::AttachFileReferenceTool(tool) => tool.call_tool(ports).await,
+            MMTools::VerifyAttachmentsTool(tool) => tool.call_tool(ports).await,
+            MMTools::RememberTool(tool) => tool.call_tool(ports).await,
+            MMTools::ProposeForgetTool(tool) => tool.call_tool(ports).await,
+            MMTools::FindBySourceTool(tool) => tool.call_tool(ports).await,
+            MMTools::FindContradictionsTool(tool) => tool.call_tool(ports).await,
+            MMTools::StreamProjectContextTool(tool) => tool.call_tool(ports).await,
+            MMTools::TaskReportTool(tool) => tool.call_tool(ports).await,
+            MMTools::EstimateCalibrationTool(tool) => tool.call_tool(ports).await,
+            MMTools::StandupSummaryTool(tool) => tool.call_tool(ports).await,
+            MMTools::ServerInfoTool(tool) => tool.call_tool(ports).await,
+            MMTools::FindEntityFuzzyTool(tool) => tool.call_tool(ports).await,
+            MMTools::RenameEntityTool(tool) => tool.call_tool(ports).await,
+            MMTools::MergeEntitiesTool(tool) => tool.call_tool(ports).await,
+            MMTools::RestoreEntitiesTool(tool) => tool.call_tool(ports).await,
+            MMTools::PurgeDeletedEntitiesTool(tool) => tool.call_tool(ports).await,
+            MMTools::TransferOwnershipTool(tool) => tool.call_tool(ports).await,
+            MMTools::GetEntityHistoryTool(tool) => tool.call_tool(ports).await,
+            MMTools::ReplaceInObservationsTool(tool) => tool.call_tool(ports).await,
+            MMTools::GetAuditLogTool(tool) => tool.call_tool(ports).await,
+            MMTools::ScanSecretsTool(tool) => tool.call_tool(ports).await,
+            MMTools::UpdateEntitiesTool(tool) => tool.call_tool(ports).await,
+            MMTools::FindPathTool(tool) => tool.call_tool(ports).await,
+            MMTools::GetNeighborhoodTool(tool) => tool.call_tool(ports).await,
+            MMTools::SlugifyNameTool(tool) => tool.call_tool(ports).await,
+            MMTools::ValidateOnlyTool(tool) => tool.call_tool(ports).await,
+            MMTools::GetObservationsTool(tool) => tool.call_tool(ports).await,
+            MMTools::CreateFromTemplateTool(tool) => tool.call_tool(ports).await,
+            MMTools::FindDuplicatesTool(tool) => tool.call_tool(ports).await,
         }
     }
 
+    /// Whether the contained tool only reads, so that results cached from
+    /// the graph stay valid across it
+    ///
+    /// Tools missing here are treated as writes.
+    pub fn is_query(&self) -> bool {
+        matches!(
+            self,
+            MMTools::FindEntitiesByLabelsTool(_)
+                | MMTools::FindRelationshipsTool(_)
+                | MMTools::FindRelatedEntitiesTool(_)
+                | MMTools::ListTasksTool(_)
+                | MMTools::GetTaskTool(_)
+                | MMTools::GetEntityTool(_)
+                | MMTools::GetGitStatusTool(_)
+                | MMTools::GetGraphMetaTool(_)
+                | MMTools::GetProjectContextTool(_)
+                | MMTools::ListProjectsTool(_)
+                | MMTools::ListRootsTool(_)
+                | MMTools::VerifyAttachmentsTool(_)
+                | MMTools::FindBySourceTool(_)
+                | MMTools::FindContradictionsTool(_)
+                | MMTools::StreamProjectContextTool(_)
+                | MMTools::TaskReportTool(_)
+                | MMTools::StandupSummaryTool(_)
+                | MMTools::EstimateCalibrationTool(_)
+                | MMTools::ServerInfoTool(_)
+                | MMTools::FindEntityFuzzyTool(_)
+                | MMTools::GetEntityHistoryTool(_)
+                | MMTools::GetAuditLogTool(_)
+                | MMTools::ScanSecretsTool(_)
+                | MMTools::FindPathTool(_)
+                | MMTools::GetNeighborhoodTool(_)
+                | MMTools::SlugifyNameTool(_)
+                | MMTools::ValidateOnlyTool(_)
+        )
+    }
+
     /// Return the JSON schema for the contained tool.
     pub fn schema(&self) -> Map<String, Value> {
         match self {
+            MMTools::AddObservationsTool(_) => AddObservationsTool::json_schema(),
             MMTools::CreateEntitiesTool(_) => CreateEntitiesTool::json_schema(),
             MMTools::CreateRelationshipsTool(_) => CreateRelationshipsTool::json_schema(),
             MMTools::DeleteEntitiesTool(_) => DeleteEntitiesTool::json_schema(),
@@ -131,8 +340,38 @@ impl MMTools {
             MMTools::GetGraphMetaTool(_) => GetGraphMetaTool::json_schema(),
             MMTools::GetProjectContextTool(_) => GetProjectContextTool::json_schema(),
             MMTools::ListProjectsTool(_) => ListProjectsTool::json_schema(),
+            MMTools::ListRootsTool(_) => ListRootsTool::json_schema(),
             MMTools::UpdateEntityTool(_) => UpdateEntityTool::json_schema(),
             MMTools::UpdateRelationshipTool(_) => UpdateRelationshipTool::json_schema(),
+            MMTools::AttachFileReferenceTool(_) => AttachFileReferenceTool::json_schema(),
+            MMTools::VerifyAttachmentsTool(_) => VerifyAttachmentsTool::json_schema(),
+            MMTools::RememberTool(_) => RememberTool::json_schema(),
+            MMTools::ProposeForgetTool(_) => ProposeForgetTool::json_schema(),
+            MMTools::FindBySourceTool(_) => FindBySourceTool::json_schema(),
+            MMTools::FindContradictionsTool(_) => FindContradictionsTool::json_schema(),
+            MMTools::StreamProjectContextTool(_) => StreamProjectContextTool::json_schema(),
+            MMTools::TaskReportTool(_) => TaskReportTool::json_schema(),
+            MMTools::EstimateCalibrationTool(_) => EstimateCalibrationTool::json_schema(),
+            MMTools::StandupSummaryTool(_) => StandupSummaryTool::json_schema(),
+            MMTools::ServerInfoTool(_) => ServerInfoTool::json_schema(),
+            MMTools::FindEntityFuzzyTool(_) => FindEntityFuzzyTool::json_schema(),
+            MMTools::RenameEntityTool(_) => RenameEntityTool::json_schema(),
+            MMTools::MergeEntitiesTool(_) => MergeEntitiesTool::json_schema(),
+            MMTools::RestoreEntitiesTool(_) => RestoreEntitiesTool::json_schema(),
+            MMTools::PurgeDeletedEntitiesTool(_) => PurgeDeletedEntitiesTool::json_schema(),
+            MMTools::TransferOwnershipTool(_) => TransferOwnershipTool::json_schema(),
+            MMTools::GetEntityHistoryTool(_) => GetEntityHistoryTool::json_schema(),
+            MMTools::ReplaceInObservationsTool(_) => ReplaceInObservationsTool::json_schema(),
+            MMTools::GetAuditLogTool(_) => GetAuditLogTool::json_schema(),
+            MMTools::ScanSecretsTool(_) => ScanSecretsTool::json_schema(),
+            MMTools::UpdateEntitiesTool(_) => UpdateEntitiesTool::json_schema(),
+            MMTools::FindPathTool(_) => FindPathTool::json_schema(),
+            MMTools::GetNeighborhoodTool(_) => GetNeighborhoodTool::json_schema(),
+            MMTools::SlugifyNameTool(_) => SlugifyNameTool::json_schema(),
+            MMTools::ValidateOnlyTool(_) => ValidateOnlyTool::json_schema(),
+            MMTools::GetObservationsTool(_) => GetObservationsTool::json_schema(),
+            MMTools::CreateFromTemplateTool(_) => CreateFromTemplateTool::json_schema(),
+            MMTools::FindDuplicatesTool(_) => FindDuplicatesTool::json_schema(),
         }
     }
 }
@@ -0,0 +1,79 @@
+use mm_core::operations::memory::{ProposeForgetCommand, propose_forget};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+/// MCP tool for proposing that memory be forgotten
+#[mcp_tool(
+    name = "propose_forget",
+    description = "Propose deleting an entity or some of its observations. Nothing is removed until a human approves the proposal with `mm review`"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ProposeForgetTool {
+    /// Entity to forget or to remove observations from
+    pub entity_name: String,
+    /// Observations to remove; leave empty to propose deleting the whole entity
+    #[serde(default)]
+    pub observations: Vec<String>,
+    /// Why the memory should be forgotten
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl ProposeForgetTool {
+    generate_call_tool!(
+        self,
+        ProposeForgetCommand {
+            entity_name => self.entity_name.clone(),
+            observations => self.observations.clone(),
+            reason => self.reason.clone()
+        },
+        propose_forget
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use mockall::predicate::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name()
+            .with(eq("tech:tool:old"))
+            .returning(|name| {
+                Ok(Some(MemoryEntity {
+                    name: name.to_string(),
+                    ..Default::default()
+                }))
+            });
+        mock.expect_find_entity_by_name()
+            .with(eq("review:forget:tech:tool:old"))
+            .returning(|_| Ok(None));
+        mock.expect_create_entities().returning(|_| Ok(()));
+        mock.expect_delete_entities().never();
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let tool = ProposeForgetTool {
+            entity_name: "tech:tool:old".to_string(),
+            observations: vec!["stale".to_string()],
+            reason: None,
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["name"], "review:forget:tech:tool:old");
+        assert_eq!(value["observations"][0], "stale");
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<ProposeForgetTool>();
+    }
+}
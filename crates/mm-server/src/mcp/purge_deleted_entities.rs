@@ -0,0 +1,39 @@
+use mm_core::operations::memory::{PurgeDeletedCommand, purge_deleted};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+#[mcp_tool(
+    name = "purge_deleted_entities",
+    description = "Permanently delete soft-deleted entities, either the named ones or the whole trash when no names are given. Returns the purged names"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct PurgeDeletedEntitiesTool {
+    /// Entities to purge; the whole trash when empty
+    #[serde(default)]
+    pub names: Vec<String>,
+}
+
+impl PurgeDeletedEntitiesTool {
+    generate_call_tool!(
+        self,
+        PurgeDeletedCommand { names => self.names.clone() },
+        purge_deleted
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_requires_soft_delete() {
+        let service = MemoryService::new(MockMemoryRepository::new(), MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let tool = PurgeDeletedEntitiesTool { names: vec![] };
+        assert!(tool.call_tool(&ports).await.is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use mm_core::operations::memory::{RememberCommand, remember};
+use mm_memory::ObservationSource;
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+/// MCP tool for quickly capturing knowledge
+#[mcp_tool(
+    name = "remember",
+    description = "Remember a piece of text, adding it to an existing entity or creating a new one named by convention"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RememberTool {
+    /// Free text to remember
+    pub text: String,
+    /// Labels for the entity; new entities default to `Note`
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Entity the text is about; a name following `memory.name_template` is derived when omitted
+    #[serde(default)]
+    pub entity_name: Option<String>,
+    /// Confidence in the text between 0 and 1; omit for established facts
+    #[serde(default)]
+    pub confidence: Option<f64>,
+    /// Where the text was learned: `url`, `file` and/or `conversation_id`
+    #[serde(default)]
+    pub source: Option<ObservationSource>,
+}
+
+impl RememberTool {
+    generate_call_tool!(
+        self,
+        RememberCommand {
+            text => self.text.clone(),
+            labels => self.labels.clone(),
+            entity_name => self.entity_name.clone(),
+            confidence => self.confidence,
+            source => self.source.clone()
+        },
+        remember
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|_| Ok(None));
+        mock.expect_create_entities().returning(|_| Ok(()));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let tool = RememberTool {
+            text: "Prefer small commits".to_string(),
+            labels: vec![],
+            entity_name: None,
+            confidence: None,
+            source: None,
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["entity_name"], "note:prefer_small_commits");
+        assert_eq!(value["action"], "created");
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<RememberTool>();
+    }
+}
@@ -0,0 +1,56 @@
+use mm_core::operations::memory::{RenameEntityCommand, rename_entity};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+#[mcp_tool(
+    name = "rename_entity",
+    description = "Rename an entity, keeping its observations, properties and relationships. Fails if the new name is already in use"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RenameEntityTool {
+    /// Current entity name
+    pub name: String,
+    /// New entity name
+    pub new_name: String,
+}
+
+impl RenameEntityTool {
+    generate_call_tool!(
+        self,
+        RenameEntityCommand { name, new_name },
+        rename_entity,
+        "Entity renamed"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|name| {
+            Ok((name == "old").then(|| MemoryEntity {
+                name: name.to_string(),
+                ..Default::default()
+            }))
+        });
+        mock.expect_rename_entity()
+            .withf(|old, new| old == "old" && new == "new")
+            .returning(|_, _| Ok(()));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let tool = RenameEntityTool {
+            name: "old".into(),
+            new_name: "new".into(),
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        assert_eq!(text, "Entity renamed");
+    }
+}
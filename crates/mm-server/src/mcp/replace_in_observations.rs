@@ -0,0 +1,60 @@
+use mm_core::operations::memory::{ReplaceInObservationsCommand, replace_in_observations};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+fn default_true() -> bool {
+    true
+}
+
+#[mcp_tool(
+    name = "replace_in_observations",
+    description = "Find and replace text in observations across the graph, optionally limited to labels or a project. Runs as a dry run listing every change unless `dry_run` is false"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ReplaceInObservationsTool {
+    /// Text to look for
+    pub pattern: String,
+    /// Replacement text; `$1` refers to a capture group when `regex` is set
+    pub replacement: String,
+    /// Treat `pattern` as a regular expression
+    #[serde(default)]
+    pub regex: bool,
+    /// Only change entities with any of these labels
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Only change this project and the entities it contains
+    #[serde(default)]
+    pub project: Option<String>,
+    /// List the changes without applying them (default true)
+    #[serde(default = "default_true")]
+    pub dry_run: bool,
+}
+
+impl ReplaceInObservationsTool {
+    generate_call_tool!(
+        self,
+        ReplaceInObservationsCommand {
+            pattern,
+            replacement,
+            regex => self.regex,
+            labels,
+            project,
+            dry_run => self.dry_run
+        },
+        replace_in_observations
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dry_run_by_default() {
+        let tool: ReplaceInObservationsTool =
+            serde_json::from_str(r#"{"pattern": "a", "replacement": "b"}"#).unwrap();
+        assert!(tool.dry_run);
+        assert!(!tool.regex);
+    }
+}
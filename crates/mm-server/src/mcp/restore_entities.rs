@@ -0,0 +1,22 @@
+use mm_core::operations::memory::{RestoreEntitiesCommand, restore_entities};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+#[mcp_tool(
+    name = "restore_entities",
+    description = "Restore soft-deleted entities from the trash. Requires `soft_delete` to be enabled"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RestoreEntitiesTool {
+    pub names: Vec<String>,
+}
+
+impl RestoreEntitiesTool {
+    generate_call_tool!(
+        self,
+        RestoreEntitiesCommand { names => self.names.clone() },
+        restore_entities,
+        "Entities restored"
+    );
+}
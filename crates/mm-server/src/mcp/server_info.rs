@@ -0,0 +1,110 @@
+use mm_memory::BackendInfo;
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use rust_mcp_sdk::schema::CallToolResult;
+use rust_mcp_sdk::schema::schema_utils::CallToolError;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+use std::time::Instant;
+
+/// Moment the server started, used to report uptime
+static STARTED: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+/// Record the server start time; later calls have no effect
+pub fn mark_started() {
+    LazyLock::force(&STARTED);
+}
+
+/// Build version of the server, taken from Cargo metadata
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Commit the server was built from, if it was built from a git checkout
+pub const GIT_COMMIT: Option<&str> = option_env!("MM_GIT_COMMIT");
+
+/// Cargo features enabled at build time
+pub fn features() -> Vec<String> {
+    env!("MM_FEATURES")
+        .split(',')
+        .filter(|feature| !feature.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Build and runtime details of the running server
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ServerInfo {
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    pub features: Vec<String>,
+    pub backend: BackendInfo,
+    /// Whether the memory graph rejects writes
+    pub read_only: bool,
+    pub uptime_secs: u64,
+}
+
+/// MCP tool reporting version, build and backend details of the server
+#[mcp_tool(
+    name = "server_info",
+    description = "Return the server version, git commit, enabled features, backend and uptime"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ServerInfoTool {}
+
+impl ServerInfoTool {
+    pub async fn call_tool<M, G>(
+        &self,
+        ports: &mm_core::Ports<M, G>,
+    ) -> Result<CallToolResult, CallToolError>
+    where
+        M: mm_memory::MemoryRepository + Send + Sync,
+        G: mm_git::GitRepository + Send + Sync,
+        M::Error: std::error::Error + Send + Sync + 'static,
+        G::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let info = ServerInfo {
+            version: VERSION.to_string(),
+            git_commit: GIT_COMMIT.map(str::to_string),
+            features: features(),
+            backend: ports.memory_service.backend_info(),
+            read_only: ports.memory_service.memory_config().read_only,
+            uptime_secs: STARTED.elapsed().as_secs(),
+        };
+        let json = serde_json::to_string(&info).map_err(crate::mcp::error::into_call_tool_error)?;
+        Ok(CallToolResult::text_content(json, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_backend_info().returning(|| BackendInfo {
+            backend: "neo4j".to_string(),
+            database: Some("neo4j://localhost:7688".to_string()),
+        });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let result = ServerInfoTool {}.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["version"], VERSION);
+        assert_eq!(value["backend"]["backend"], "neo4j");
+        assert_eq!(value["backend"]["database"], "neo4j://localhost:7688");
+        assert_eq!(value["read_only"], false);
+        assert!(value["uptime_secs"].is_u64());
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<ServerInfoTool>();
+    }
+}
@@ -0,0 +1,49 @@
+use mm_core::operations::memory::{SlugifyNameCommand, slugify_name};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+#[mcp_tool(
+    name = "slugify_name",
+    description = "Turn a human-readable title into an entity name that follows the configured naming convention, e.g. 'Rust Language' with label Technology becomes 'technology:rust_language'. Use it before creating entities instead of inventing names. Does not check whether the name is taken"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SlugifyNameTool {
+    /// Human-readable title of the entity
+    pub title: String,
+    /// Label the entity will have
+    pub label: String,
+    /// Project the entity belongs to; the default project when omitted
+    #[serde(default)]
+    pub project_name: Option<String>,
+}
+
+impl SlugifyNameTool {
+    generate_call_tool!(
+        self,
+        SlugifyNameCommand {
+            title,
+            label,
+            project_name
+        },
+        slugify_name
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+
+    #[tokio::test]
+    async fn test_call_tool_default_template() {
+        let tool = SlugifyNameTool {
+            title: "Rust Language".to_string(),
+            label: "Technology".to_string(),
+            project_name: None,
+        };
+        let result = tool.call_tool(&Ports::noop()).await.unwrap();
+        let text = &result.content[0].as_text_content().unwrap().text;
+        assert_eq!(text, r#"{"name":"technology:rust_language"}"#);
+    }
+}
@@ -0,0 +1,119 @@
+use rust_mcp_sdk::schema::{CallToolResult, CallToolResultContentItem};
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Prefix of the URIs of spilled tool results
+pub const RESULTS_URI_PREFIX: &str = "mm://results/";
+
+/// Tool results too large to return, kept for a while as resources
+#[derive(Debug)]
+pub struct ResultStore {
+    ttl: Duration,
+    results: Mutex<HashMap<String, (Instant, String)>>,
+}
+
+impl ResultStore {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            results: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Keep `text` and return its URI, dropping expired results
+    pub fn put(&self, text: String) -> String {
+        let id = format!("{:016x}", fastrand::u64(..));
+        let mut results = self.results.lock().unwrap();
+        let now = Instant::now();
+        results.retain(|_, (stored, _)| now.duration_since(*stored) < self.ttl);
+        results.insert(id.clone(), (now, text));
+        format!("{RESULTS_URI_PREFIX}{id}")
+    }
+
+    /// The result stored under `uri` unless it expired
+    pub fn get(&self, uri: &str) -> Option<String> {
+        let id = uri.strip_prefix(RESULTS_URI_PREFIX)?;
+        let results = self.results.lock().unwrap();
+        results
+            .get(id)
+            .filter(|(stored, _)| stored.elapsed() < self.ttl)
+            .map(|(_, text)| text.clone())
+    }
+
+    /// Store the text of `result` when it is longer than `threshold` bytes
+    /// and return a pointer to it with a summary instead
+    ///
+    /// The summary counts the items of every top-level array, or of the
+    /// result itself when it is an array. Results with anything but a single
+    /// text block are returned as they are.
+    pub fn spill(&self, result: CallToolResult, threshold: usize) -> CallToolResult {
+        let [CallToolResultContentItem::TextContent(text)] = result.content.as_slice() else {
+            return result;
+        };
+        if text.text.len() <= threshold {
+            return result;
+        }
+        let bytes = text.text.len();
+        let summary = summarize(&text.text);
+        let uri = self.put(text.text.clone());
+        let pointer = json!({
+            "resource": uri,
+            "bytes": bytes,
+            "expires_in_secs": self.ttl.as_secs(),
+            "summary": summary,
+        });
+        let mut spilled = CallToolResult::text_content(pointer.to_string(), None);
+        spilled.is_error = result.is_error;
+        spilled.meta = result.meta;
+        spilled
+    }
+}
+
+/// Item counts of the arrays in a JSON result
+fn summarize(text: &str) -> Value {
+    match serde_json::from_str::<Value>(text) {
+        Ok(Value::Array(items)) => json!({ "items": items.len() }),
+        Ok(Value::Object(fields)) => Value::Object(
+            fields
+                .into_iter()
+                .filter_map(|(key, value)| match value {
+                    Value::Array(items) => Some((key, Value::from(items.len()))),
+                    _ => None,
+                })
+                .collect::<Map<_, _>>(),
+        ),
+        _ => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spill_keeps_large_results() {
+        let store = ResultStore::new(Duration::from_secs(60));
+        let json = r#"{"entities":[1,2,3],"relationships":[],"complete":true}"#;
+        let result = store.spill(CallToolResult::text_content(json.to_string(), None), 10);
+        let text = &result.content[0].as_text_content().unwrap().text;
+        let pointer: Value = serde_json::from_str(text).unwrap();
+        assert_eq!(
+            pointer["summary"],
+            json!({ "entities": 3, "relationships": 0 })
+        );
+        let uri = pointer["resource"].as_str().unwrap();
+        assert_eq!(store.get(uri).as_deref(), Some(json));
+
+        let small = store.spill(CallToolResult::text_content("[]".to_string(), None), 10);
+        assert_eq!(small.content[0].as_text_content().unwrap().text, "[]");
+    }
+
+    #[test]
+    fn test_expired_results_are_dropped() {
+        let store = ResultStore::new(Duration::ZERO);
+        let uri = store.put("x".to_string());
+        assert!(store.get(&uri).is_none());
+    }
+}
@@ -0,0 +1,92 @@
+use chrono::{Duration, Utc};
+use mm_core::operations::memory::{StandupSummaryCommand, standup_summary};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use rust_mcp_sdk::schema::CallToolResult;
+use rust_mcp_sdk::schema::schema_utils::CallToolError;
+use serde::{Deserialize, Serialize};
+
+/// Window covered when `hours` is omitted
+const DEFAULT_STANDUP_HOURS: u32 = 24;
+
+/// MCP tool for composing a standup update from task status
+#[mcp_tool(
+    name = "standup_summary",
+    description = "Compose a markdown standup update for a project: tasks done within the last hours, tasks in progress and blocked tasks"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct StandupSummaryTool {
+    /// Project to summarize; the default project when omitted
+    pub project_name: Option<String>,
+    /// How many hours back finished tasks are included (default 24)
+    pub hours: Option<u32>,
+}
+
+impl StandupSummaryTool {
+    pub async fn call_tool<M, G>(
+        &self,
+        ports: &mm_core::Ports<M, G>,
+    ) -> Result<CallToolResult, CallToolError>
+    where
+        M: mm_memory::MemoryRepository + Send + Sync,
+        G: mm_git::GitRepository + Send + Sync,
+        M::Error: std::error::Error + Send + Sync + 'static,
+        G::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let hours = self.hours.unwrap_or(DEFAULT_STANDUP_HOURS);
+        let summary = standup_summary(
+            ports,
+            StandupSummaryCommand {
+                project_name: self.project_name.clone(),
+                since: Utc::now() - Duration::hours(i64::from(hours)),
+            },
+        )
+        .await
+        .map_err(crate::mcp::error::into_call_tool_error)?;
+
+        Ok(CallToolResult::text_content(summary.to_markdown(), None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_core::operations::memory::{TASK_LABEL, TaskProperties, TaskStatus};
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_returns_markdown() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_tasks().returning(|_| {
+            Ok(vec![MemoryEntity {
+                name: "task:1".to_string(),
+                labels: vec![TASK_LABEL.to_string()],
+                properties: TaskProperties {
+                    status: TaskStatus::Blocked,
+                    ..Default::default()
+                }
+                .into(),
+                ..Default::default()
+            }])
+        });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let tool = StandupSummaryTool {
+            project_name: Some("project:x".to_string()),
+            hours: None,
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        assert!(text.starts_with("## Standup: project:x\n"));
+        assert!(text.contains("**Blocked**\n- task:1\n"));
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<StandupSummaryTool>();
+    }
+}
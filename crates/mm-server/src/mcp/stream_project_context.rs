@@ -0,0 +1,159 @@
+use mm_core::operations::memory::{
+    ProjectFilter, StreamProjectContextCommand, stream_project_context,
+};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use rust_mcp_sdk::schema::schema_utils::CallToolError;
+use rust_mcp_sdk::schema::{CallToolResult, CallToolResultContentItem};
+use serde::{Deserialize, Serialize};
+
+/// MCP tool for retrieving project context in prioritized chunks
+#[mcp_tool(
+    name = "stream_project_context",
+    description = "Get project context as several content blocks ordered by importance: project core, tasks, technologies, then everything else. Stop reading once you have enough"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct StreamProjectContextTool {
+    /// Project name to look up (e.g., "andoriyu:project:middle_manager")
+    pub project_name: Option<String>,
+
+    /// Repository name to look up (e.g., "andoriyu/middle-manager")
+    pub repository_name: Option<String>,
+
+    /// Leave out related entities carrying any of these labels, e.g. `["Session"]`
+    #[serde(default)]
+    pub exclude_labels: Vec<String>,
+}
+
+impl StreamProjectContextTool {
+    pub async fn call_tool<M, G>(
+        &self,
+        ports: &mm_core::Ports<M, G>,
+    ) -> Result<CallToolResult, CallToolError>
+    where
+        M: mm_memory::MemoryRepository + Send + Sync,
+        G: mm_git::GitRepository + Send + Sync,
+        M::Error: std::error::Error + Send + Sync + 'static,
+        G::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let filter = match (self.project_name.clone(), self.repository_name.clone()) {
+            (Some(name), _) => ProjectFilter::Name(name),
+            (None, Some(repo)) => ProjectFilter::Repository(repo),
+            (None, None) => {
+                return Err(CallToolError(
+                    crate::mcp::error::error_with_source(
+                        "Either project_name or repository_name must be provided",
+                        std::io::Error::new(
+                            std::io::ErrorKind::InvalidInput,
+                            "Missing required parameter",
+                        ),
+                    )
+                    .into_boxed_dyn_error(),
+                ));
+            }
+        };
+
+        let result = stream_project_context(
+            ports,
+            StreamProjectContextCommand {
+                filter,
+                exclude_labels: self.exclude_labels.clone(),
+            },
+        )
+        .await
+        .map_err(crate::mcp::error::into_call_tool_error)?;
+
+        let content = result
+            .chunks
+            .iter()
+            .map(|chunk| {
+                serde_json::to_string(chunk)
+                    .map(|text| CallToolResultContentItem::text_content(text, None))
+                    .map_err(crate::mcp::error::into_call_tool_error)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(CallToolResult {
+            content,
+            is_error: None,
+            meta: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_core::operations::memory::{PROJECT_LABEL, TASK_LABEL, TECHNOLOGY_LABEL};
+    use mm_memory::{
+        EntitySnapshot, MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository,
+    };
+    use std::sync::Arc;
+
+    fn entity(name: &str, label: &str) -> MemoryEntity {
+        MemoryEntity {
+            name: name.to_string(),
+            labels: vec![label.to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_call_tool_returns_chunks_in_order() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_snapshot()
+            .returning(|name, queries| {
+                Ok(Some(EntitySnapshot {
+                    entity: entity(name, PROJECT_LABEL),
+                    related: vec![
+                        vec![
+                            entity("task:1", TASK_LABEL),
+                            entity("tech:rust", TECHNOLOGY_LABEL),
+                        ];
+                        queries.len()
+                    ],
+                }))
+            });
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let tool = StreamProjectContextTool {
+            project_name: Some("project:x".to_string()),
+            repository_name: None,
+            exclude_labels: Vec::new(),
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let sections: Vec<String> = result
+            .content
+            .iter()
+            .map(|c| {
+                let text = &c.as_text_content().unwrap().text;
+                serde_json::from_str::<serde_json::Value>(text).unwrap()["section"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(sections, ["project", "tasks", "technologies"]);
+    }
+
+    #[tokio::test]
+    async fn test_call_tool_missing_parameters() {
+        let service = MemoryService::new(MockMemoryRepository::new(), MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let tool = StreamProjectContextTool {
+            project_name: None,
+            repository_name: None,
+            exclude_labels: Vec::new(),
+        };
+        assert!(tool.call_tool(&ports).await.is_err());
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<StreamProjectContextTool>();
+    }
+}
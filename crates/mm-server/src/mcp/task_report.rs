@@ -0,0 +1,48 @@
+use mm_core::operations::memory::{TaskReportCommand, task_report};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+/// MCP tool for summarizing tasks and WIP limit violations
+#[mcp_tool(
+    name = "task_report",
+    description = "Count a project's tasks by status and list projects or assignees with more tasks in progress than their WIP limit"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TaskReportTool {
+    /// Project to report on; the default project when omitted
+    pub project_name: Option<String>,
+}
+
+impl TaskReportTool {
+    generate_call_tool!(self, TaskReportCommand { project_name }, task_report);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_tasks().returning(|_| Ok(vec![]));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let tool = TaskReportTool {
+            project_name: Some("project:x".to_string()),
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        assert!(text.contains("violations"));
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<TaskReportTool>();
+    }
+}
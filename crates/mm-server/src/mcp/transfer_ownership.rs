@@ -0,0 +1,68 @@
+use mm_core::operations::memory::{TransferOwnershipCommand, transfer_ownership};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+#[mcp_tool(
+    name = "transfer_ownership",
+    description = "Hand entities over from one agent to another: `created_by` is reassigned and names in the old agent's namespace (`agent:...`) are renamed into the new one. Returns the changes"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TransferOwnershipTool {
+    /// Agent giving up the entities
+    pub from: String,
+    /// Agent taking them over
+    pub to: String,
+    /// Entities to transfer; every entity owned by `from` when empty
+    #[serde(default)]
+    pub names: Vec<String>,
+    /// Only report what would change
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl TransferOwnershipTool {
+    generate_call_tool!(
+        self,
+        TransferOwnershipCommand {
+            from,
+            to,
+            names,
+            dry_run => self.dry_run
+        },
+        transfer_ownership
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_core::operations::memory::OwnershipChange;
+    use mm_memory::{MemoryConfig, MemoryEntity, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_dry_run() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name().returning(|name| {
+            Ok((name == "old:task:one").then(|| MemoryEntity {
+                name: name.to_string(),
+                ..Default::default()
+            }))
+        });
+        mock.expect_rename_entity().never();
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let tool = TransferOwnershipTool {
+            from: "old".into(),
+            to: "new".into(),
+            names: vec!["old:task:one".into()],
+            dry_run: true,
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        let changes: Vec<OwnershipChange> = serde_json::from_str(&text).unwrap();
+        assert_eq!(changes[0].new_name.as_deref(), Some("new:task:one"));
+    }
+}
@@ -0,0 +1,55 @@
+use mm_core::operations::memory::{UpdateEntitiesCommand, update_entities};
+use mm_memory::NamedEntityUpdate;
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+#[mcp_tool(
+    name = "update_entities",
+    description = "Update several entities in one call. Each item takes the same `update` as update_entity; invalid items are reported by index and the valid ones are applied together, failing as a whole on a stale `expected_revision`"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct UpdateEntitiesTool {
+    /// Entities to update with their changes
+    pub updates: Vec<NamedEntityUpdate>,
+}
+
+impl UpdateEntitiesTool {
+    generate_call_tool!(
+        self,
+        UpdateEntitiesCommand { updates },
+        update_entities,
+        "Entities updated"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{EntityUpdate, MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_update_entities()
+            .withf(|updates| updates.len() == 2)
+            .times(1)
+            .returning(|_| Ok(()));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+        let tool = UpdateEntitiesTool {
+            updates: ["a", "b"]
+                .into_iter()
+                .map(|name| NamedEntityUpdate {
+                    name: name.to_string(),
+                    update: EntityUpdate::default(),
+                })
+                .collect(),
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        assert_eq!(text, "Entities updated");
+    }
+}
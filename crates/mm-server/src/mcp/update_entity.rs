@@ -1,22 +1,36 @@
 use mm_core::operations::memory::{UpdateEntityCommand, update_entity};
-use mm_memory::EntityUpdate;
+use mm_memory::{EntityUpdate, ObservationSource};
 use mm_utils::IntoJsonSchema;
 use rust_mcp_sdk::macros::mcp_tool;
 use serde::{Deserialize, Serialize};
 
-#[mcp_tool(name = "update_entity", description = "Update fields of an entity")]
+#[mcp_tool(
+    name = "update_entity",
+    description = "Update fields of an entity. Pass the entity's `revision` as `update.expected_revision` to get a conflict error instead of overwriting a change made since you read it"
+)]
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct UpdateEntityTool {
     /// Entity name to update
     pub name: String,
     /// Changes to apply
     pub update: EntityUpdate,
+    /// Confidence between 0 and 1 for the observations the update adds or sets
+    #[serde(default)]
+    pub confidence: Option<f64>,
+    /// Where the observations the update adds or sets were learned: `url`, `file` and/or `conversation_id`
+    #[serde(default)]
+    pub source: Option<ObservationSource>,
 }
 
 impl UpdateEntityTool {
     generate_call_tool!(
         self,
-        UpdateEntityCommand { name, update },
+        UpdateEntityCommand {
+            name,
+            update,
+            confidence,
+            source
+        },
         update_entity,
         "Entity updated"
     );
@@ -40,6 +54,8 @@ mod tests {
         let tool = UpdateEntityTool {
             name: "e".into(),
             update: EntityUpdate::default(),
+            confidence: None,
+            source: None,
         };
         let result = tool.call_tool(&ports).await.unwrap();
         let text = result.content[0].as_text_content().unwrap().text.clone();
@@ -2,6 +2,8 @@ use mm_core::operations::memory::{TaskProperties, UpdateTaskCommand, update_task
 use mm_memory::{EntityUpdate, ObservationsUpdate, PropertiesUpdate};
 use mm_utils::IntoJsonSchema;
 use rust_mcp_sdk::macros::mcp_tool;
+use rust_mcp_sdk::schema::CallToolResult;
+use rust_mcp_sdk::schema::schema_utils::CallToolError;
 use serde::{Deserialize, Serialize};
 
 #[mcp_tool(
@@ -23,24 +25,46 @@ pub struct UpdateTaskTool {
 }
 
 impl UpdateTaskTool {
-    generate_call_tool!(
-        self,
-        UpdateTaskCommand {
-            name => self.task_name.clone(),
-            update => {
-                let mut update = EntityUpdate::default();
-                if let Some(obs) = self.observations.clone() {
-                    update.observations = Some(ObservationsUpdate { add: None, remove: None, set: Some(obs) });
-                }
-                if let Some(props) = self.properties.clone() {
-                    update.properties = Some(PropertiesUpdate { add: None, remove: None, set: Some(props.into()) });
-                }
-                update
-            }
-        },
-        update_task,
-        "Task updated"
-    );
+    pub async fn call_tool<M, G>(
+        &self,
+        ports: &mm_core::Ports<M, G>,
+    ) -> Result<CallToolResult, CallToolError>
+    where
+        M: mm_memory::MemoryRepository + Send + Sync,
+        G: mm_git::GitRepository + Send + Sync,
+        M::Error: std::error::Error + Send + Sync + 'static,
+        G::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let mut update = EntityUpdate::default();
+        if let Some(obs) = self.observations.clone() {
+            update.observations = Some(ObservationsUpdate {
+                add: None,
+                remove: None,
+                set: Some(obs),
+            });
+        }
+        if let Some(props) = self.properties.clone() {
+            update.properties = Some(PropertiesUpdate {
+                add: None,
+                remove: None,
+                set: Some(props.into()),
+            });
+        }
+
+        let outcome = update_task(
+            ports,
+            UpdateTaskCommand {
+                name: self.task_name.clone(),
+                update,
+            },
+        )
+        .await
+        .map_err(crate::mcp::error::into_call_tool_error)?;
+
+        // Over-limit transitions still succeed; tell the caller about them
+        let text = crate::mcp::with_warnings("Task updated", &outcome.warnings);
+        Ok(CallToolResult::text_content(text, None))
+    }
 }
 
 #[cfg(test)]
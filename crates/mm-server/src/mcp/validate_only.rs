@@ -0,0 +1,58 @@
+use mm_core::operations::memory::{TaskInput, ValidateOnlyCommand, validate_only};
+use mm_memory::{MemoryEntity, MemoryRelationship};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+#[mcp_tool(
+    name = "validate_only",
+    description = "Check a would-be create_entities, create_relationships and/or create_tasks payload without writing anything. Returns every validation error with the location of the offending input, and the valid items as they would be stored with defaults applied. Use it to fix inputs before creating them"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ValidateOnlyTool {
+    /// Project whose vocabulary applies; the default project when omitted
+    #[serde(default)]
+    pub project_name: Option<String>,
+    /// Entities as passed to create_entities
+    #[serde(default)]
+    pub entities: Vec<MemoryEntity>,
+    /// Relationships as passed to create_relationships, checked as if
+    /// created together with the entities
+    #[serde(default)]
+    pub relationships: Vec<MemoryRelationship>,
+    /// Tasks as passed to create_tasks
+    #[serde(default)]
+    pub tasks: Vec<TaskInput>,
+}
+
+impl ValidateOnlyTool {
+    generate_call_tool!(
+        self,
+        ValidateOnlyCommand {
+            project_name,
+            entities => self.entities.clone(),
+            relationships => self.relationships.clone(),
+            tasks => self.tasks.clone()
+        },
+        validate_only
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+
+    #[tokio::test]
+    async fn test_call_tool_reports_errors() {
+        let tool: ValidateOnlyTool = serde_json::from_str(
+            r#"{"entities": [{"name": "", "labels": ["Memory"], "observations": []}]}"#,
+        )
+        .unwrap();
+        let result = tool.call_tool(&Ports::noop()).await.unwrap();
+        let text = &result.content[0].as_text_content().unwrap().text;
+        let value: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert_eq!(value["valid"], false);
+        assert_eq!(value["errors"].as_array().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,57 @@
+use mm_core::operations::memory::{VerifyAttachmentsCommand, verify_attachments};
+use mm_utils::IntoJsonSchema;
+use rust_mcp_sdk::macros::mcp_tool;
+use serde::{Deserialize, Serialize};
+
+/// MCP tool for checking whether attached files changed
+#[mcp_tool(
+    name = "verify_attachments",
+    description = "Re-hash the files attached to an entity and flag those that changed or are missing"
+)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct VerifyAttachmentsTool {
+    /// Name of the entity whose attachments should be verified
+    pub entity_name: String,
+}
+
+impl VerifyAttachmentsTool {
+    generate_call_tool!(
+        self,
+        VerifyAttachmentsCommand {
+            entity_name => self.entity_name.clone()
+        },
+        verify_attachments
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::Ports;
+    use mm_memory::{MemoryConfig, MemoryService, MockMemoryRepository};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_call_tool_success() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_relationships()
+            .returning(|_, _, _| Ok(vec![]));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| p.memory_service = Arc::new(service));
+
+        let tool = VerifyAttachmentsTool {
+            entity_name: "decision:1".to_string(),
+        };
+        let result = tool.call_tool(&ports).await.unwrap();
+        let text = result.content[0].as_text_content().unwrap().text.clone();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert!(value["attachments"].as_array().unwrap().is_empty());
+        assert_eq!(value["stale"], false);
+    }
+
+    #[test]
+    fn test_schema_has_no_defs() {
+        use crate::mcp::tests::assert_no_defs;
+        assert_no_defs::<VerifyAttachmentsTool>();
+    }
+}
@@ -0,0 +1,195 @@
+//! Fetching the default project's context before the client asks for it.
+//!
+//! With `preload_context` enabled the server loads the context and the task
+//! list of `memory.default_project` in the background once the session is
+//! initialized. The first `get_project_context` call resolving to that
+//! project, by name or by repository, and the first unfiltered `list_tasks`
+//! call for it are answered from the preload, waiting for it if it is still
+//! loading. Any tool that may change the graph discards it.
+
+use rust_mcp_sdk::schema::CallToolResult;
+use tokio::sync::watch;
+
+/// Results loaded ahead of time for one project
+#[derive(Debug, Clone, Default)]
+pub struct Preloaded {
+    /// Name of the project
+    pub project: String,
+    /// Repository containing the project, as `get_project_context` takes it
+    pub repository: Option<String>,
+    /// Result of `get_project_context` for the project
+    pub context: Option<CallToolResult>,
+    /// Result of an unfiltered `list_tasks` for the project
+    pub tasks: Option<CallToolResult>,
+}
+
+#[derive(Debug, Default)]
+enum State {
+    #[default]
+    Idle,
+    Loading,
+    Ready(Box<Preloaded>),
+    Discarded,
+}
+
+/// Project context and tasks loaded ahead of the first calls asking for them
+#[derive(Debug)]
+pub struct ContextPreload {
+    state: watch::Sender<State>,
+}
+
+impl Default for ContextPreload {
+    fn default() -> Self {
+        Self {
+            state: watch::Sender::new(State::Idle),
+        }
+    }
+}
+
+impl ContextPreload {
+    /// Mark the preload as started
+    pub fn start(&self) {
+        self.state.send_replace(State::Loading);
+    }
+
+    /// Store the loaded results unless they were discarded meanwhile
+    pub fn finish(&self, preloaded: Preloaded) {
+        self.state.send_if_modified(|state| {
+            let loading = matches!(state, State::Loading);
+            if loading {
+                *state = State::Ready(Box::new(preloaded));
+            }
+            loading
+        });
+    }
+
+    /// Give up on a preload that failed, releasing anyone waiting for it
+    pub fn fail(&self) {
+        self.state.send_if_modified(|state| {
+            let loading = matches!(state, State::Loading);
+            if loading {
+                *state = State::Discarded;
+            }
+            loading
+        });
+    }
+
+    /// The preloaded context of the project named `project`, or of the one
+    /// in `repository` when no name is given, at most once
+    pub async fn take_context(
+        &self,
+        project: Option<&str>,
+        repository: Option<&str>,
+    ) -> Option<CallToolResult> {
+        self.take(|preloaded| {
+            let matches = match project {
+                Some(name) => name == preloaded.project,
+                None => repository.is_some() && repository == preloaded.repository.as_deref(),
+            };
+            matches.then(|| preloaded.context.take()).flatten()
+        })
+        .await
+    }
+
+    /// The preloaded unfiltered task list of `project`, at most once
+    pub async fn take_tasks(&self, project: &str) -> Option<CallToolResult> {
+        self.take(|preloaded| {
+            (project == preloaded.project)
+                .then(|| preloaded.tasks.take())
+                .flatten()
+        })
+        .await
+    }
+
+    /// Drop the preloaded results, including ones still loading
+    pub fn discard(&self) {
+        self.state.send_replace(State::Discarded);
+    }
+
+    /// Wait for a running preload, then let `pick` take one of its results
+    async fn take(
+        &self,
+        pick: impl FnOnce(&mut Preloaded) -> Option<CallToolResult>,
+    ) -> Option<CallToolResult> {
+        let mut receiver = self.state.subscribe();
+        // The sender lives in `self`, so waiting cannot fail
+        let _ = receiver
+            .wait_for(|state| !matches!(state, State::Loading))
+            .await;
+        let mut result = None;
+        self.state.send_if_modified(|state| {
+            if let State::Ready(preloaded) = state {
+                result = pick(preloaded);
+            }
+            false
+        });
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn result(text: &str) -> CallToolResult {
+        CallToolResult::text_content(text.to_string(), None)
+    }
+
+    fn preloaded() -> Preloaded {
+        Preloaded {
+            project: "p".to_string(),
+            repository: Some("owner/repo".to_string()),
+            context: Some(result("ctx")),
+            tasks: Some(result("tasks")),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_take_once_for_resolved_project() {
+        let preload = ContextPreload::default();
+        preload.start();
+        preload.finish(preloaded());
+        assert!(preload.take_context(Some("other"), None).await.is_none());
+        assert!(
+            preload
+                .take_context(None, Some("owner/repo"))
+                .await
+                .is_some()
+        );
+        assert!(preload.take_context(Some("p"), None).await.is_none());
+        assert!(preload.take_tasks("p").await.is_some());
+        assert!(preload.take_tasks("p").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_discard_while_loading() {
+        let preload = ContextPreload::default();
+        preload.start();
+        preload.discard();
+        preload.finish(preloaded());
+        assert!(preload.take_context(Some("p"), None).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_take_waits_for_running_preload() {
+        let preload = Arc::new(ContextPreload::default());
+        preload.start();
+        let waiting = tokio::spawn({
+            let preload = preload.clone();
+            async move { preload.take_context(Some("p"), None).await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiting.is_finished());
+        preload.finish(preloaded());
+        assert!(waiting.await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_failed_preload_releases_waiters() {
+        let preload = ContextPreload::default();
+        preload.start();
+        preload.fail();
+        assert!(preload.take_context(Some("p"), None).await.is_none());
+    }
+}
@@ -1,37 +1,93 @@
+use crate::mcp::spill::RESULTS_URI_PREFIX;
+use chrono::{Duration, Utc};
 use mm_core::Ports;
-use mm_core::operations::memory::{GetEntityCommand, get_entity};
+use mm_core::operations::memory::{
+    GetEntityCommand, ProjectContext, ProjectDigestCommand, get_entity, project_digest,
+};
 use mm_git::GitRepository;
 use mm_memory::MemoryRepository;
+use mm_utils::IntoJsonSchema;
 use rust_mcp_sdk::schema::{
     ListResourceTemplatesResult, ListResourcesResult, ReadResourceResult,
-    ReadResourceResultContentsItem, ResourceTemplate, RpcError, TextResourceContents,
+    ReadResourceResultContentsItem, Resource, ResourceTemplate, RpcError, TextResourceContents,
 };
 
+/// URI of the JSON schema describing `get_project_context` results
+pub const PROJECT_CONTEXT_SCHEMA_URI: &str = "schema://project_context";
+
+/// Prefix and suffix around the project name in weekly digest URIs
+const PROJECT_URI_PREFIX: &str = "mm://project/";
+const WEEKLY_DIGEST_SUFFIX: &str = "/digest/weekly";
+
 /// Return the list of resource templates supported by the server.
 pub fn list_resource_templates() -> ListResourceTemplatesResult {
     ListResourceTemplatesResult {
         meta: None,
         next_cursor: None,
-        resource_templates: vec![ResourceTemplate {
-            annotations: None,
-            description: Some("Retrieve a memory entity by name".to_string()),
-            mime_type: Some("application/json".to_string()),
-            name: "Memory Entity".to_string(),
-            uri_template: "memory://{name}".to_string(),
-        }],
+        resource_templates: vec![
+            ResourceTemplate {
+                annotations: None,
+                description: Some("Retrieve a memory entity by name".to_string()),
+                mime_type: Some("application/json".to_string()),
+                name: "Memory Entity".to_string(),
+                uri_template: "memory://{name}".to_string(),
+            },
+            ResourceTemplate {
+                annotations: None,
+                description: Some(
+                    "Completed tasks, new decisions and other changes of a project over the past week"
+                        .to_string(),
+                ),
+                mime_type: Some("application/json".to_string()),
+                name: "Weekly Project Digest".to_string(),
+                uri_template: format!("{PROJECT_URI_PREFIX}{{name}}{WEEKLY_DIGEST_SUFFIX}"),
+            },
+            ResourceTemplate {
+                annotations: None,
+                description: Some(
+                    "Full result of a tool call too large to return, kept for a limited time"
+                        .to_string(),
+                ),
+                mime_type: Some("application/json".to_string()),
+                name: "Spilled Tool Result".to_string(),
+                uri_template: format!("{RESULTS_URI_PREFIX}{{id}}"),
+            },
+        ],
     }
 }
 
-/// Return the list of resources. Dynamic memory resources are not enumerated, so this is empty.
+/// Return the list of resources. Dynamic memory resources are not enumerated, so only
+/// the static schemas are listed.
 pub fn list_resources() -> ListResourcesResult {
     ListResourcesResult {
         meta: None,
         next_cursor: None,
-        resources: vec![],
+        resources: vec![Resource {
+            annotations: None,
+            description: Some("JSON schema of the project context".to_string()),
+            mime_type: Some("application/schema+json".to_string()),
+            name: "Project Context Schema".to_string(),
+            size: None,
+            uri: PROJECT_CONTEXT_SCHEMA_URI.to_string(),
+        }],
     }
 }
 
-/// Read a memory entity from the given URI.
+pub(crate) fn text_resource(uri: &str, mime_type: &str, text: String) -> ReadResourceResult {
+    ReadResourceResult {
+        contents: vec![ReadResourceResultContentsItem::TextResourceContents(
+            TextResourceContents {
+                mime_type: Some(mime_type.to_string()),
+                text,
+                uri: uri.to_string(),
+            },
+        )],
+        meta: None,
+    }
+}
+
+/// Read a memory entity, a project digest or a published schema from the
+/// given URI.
 #[tracing::instrument(skip(ports), fields(uri))]
 pub async fn read_resource<M, G>(
     ports: &Ports<M, G>,
@@ -43,6 +99,33 @@ where
     M::Error: std::error::Error + Send + Sync + 'static,
     G::Error: std::error::Error + Send + Sync + 'static,
 {
+    if uri == PROJECT_CONTEXT_SCHEMA_URI {
+        let schema = serde_json::Value::Object(<ProjectContext as IntoJsonSchema>::json_schema());
+        return Ok(text_resource(
+            uri,
+            "application/schema+json",
+            schema.to_string(),
+        ));
+    }
+
+    if let Some(project_name) = uri
+        .strip_prefix(PROJECT_URI_PREFIX)
+        .and_then(|rest| rest.strip_suffix(WEEKLY_DIGEST_SUFFIX))
+    {
+        let digest = project_digest(
+            ports,
+            ProjectDigestCommand {
+                project_name: project_name.to_string(),
+                since: Utc::now() - Duration::weeks(1),
+            },
+        )
+        .await
+        .map_err(|e| RpcError::internal_error().with_message(e.to_string()))?;
+        let text = serde_json::to_string(&digest)
+            .map_err(|e| RpcError::internal_error().with_message(e.to_string()))?;
+        return Ok(text_resource(uri, "application/json", text));
+    }
+
     let Some(name) = uri.strip_prefix("memory://") else {
         return Err(RpcError::invalid_params().with_message("Unsupported URI".to_string()));
     };
@@ -65,16 +148,7 @@ where
     let text = serde_json::to_string(&entity)
         .map_err(|e| RpcError::internal_error().with_message(e.to_string()))?;
 
-    Ok(ReadResourceResult {
-        contents: vec![ReadResourceResultContentsItem::TextResourceContents(
-            TextResourceContents {
-                mime_type: Some("application/json".to_string()),
-                text,
-                uri: uri.to_string(),
-            },
-        )],
-        meta: None,
-    })
+    Ok(text_resource(uri, "application/json", text))
 }
 
 #[cfg(test)]
@@ -125,6 +199,62 @@ mod tests {
         assert_eq!(err.message, "Entity 'missing' not found");
     }
 
+    #[tokio::test]
+    async fn test_read_project_context_schema() {
+        let service = MemoryService::new(MockMemoryRepository::new(), MemoryConfig::default());
+        let ports = Ports::noop().with(|p| {
+            p.memory_service = Arc::new(service);
+        });
+        assert_eq!(
+            list_resources().resources[0].uri,
+            PROJECT_CONTEXT_SCHEMA_URI
+        );
+
+        let result = read_resource(&ports, PROJECT_CONTEXT_SCHEMA_URI)
+            .await
+            .unwrap();
+        let ReadResourceResultContentsItem::TextResourceContents(contents) = &result.contents[0]
+        else {
+            panic!("unexpected contents variant");
+        };
+        let schema: serde_json::Value = serde_json::from_str(&contents.text).unwrap();
+        assert!(schema["properties"]["schema_version"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_read_weekly_digest() {
+        let mut mock = MockMemoryRepository::new();
+        mock.expect_find_entity_by_name()
+            .with(eq("project:x"))
+            .returning(|name| {
+                Ok(Some(MemoryEntity {
+                    name: name.to_string(),
+                    ..Default::default()
+                }))
+            });
+        mock.expect_find_related_entities()
+            .returning(|_, _, _, _| Ok(vec![]));
+        let service = MemoryService::new(mock, MemoryConfig::default());
+        let ports = Ports::noop().with(|p| {
+            p.memory_service = Arc::new(service);
+        });
+        assert_eq!(
+            list_resource_templates().resource_templates[1].uri_template,
+            "mm://project/{name}/digest/weekly"
+        );
+
+        let result = read_resource(&ports, "mm://project/project:x/digest/weekly")
+            .await
+            .unwrap();
+        let ReadResourceResultContentsItem::TextResourceContents(contents) = &result.contents[0]
+        else {
+            panic!("unexpected contents variant");
+        };
+        let digest: serde_json::Value = serde_json::from_str(&contents.text).unwrap();
+        assert_eq!(digest["project_name"], "project:x");
+        assert!(digest["completed_tasks"].is_array());
+    }
+
     #[tokio::test]
     async fn test_read_resource_invalid_uri() {
         let mock = MockMemoryRepository::new();
@@ -171,7 +301,7 @@ mod prop_tests {
     impl<'a> Arbitrary<'a> for InvalidUri {
         fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
             let mut s: String = Arbitrary::arbitrary(u)?;
-            if s.starts_with("memory://") {
+            if s.starts_with("memory://") || s.starts_with(PROJECT_URI_PREFIX) {
                 s.insert(0, 'x');
             }
             Ok(InvalidUri(s))
@@ -1,9 +1,91 @@
 use mm_core::Root;
+use mm_utils::normalize_path;
+use tracing::warn;
+use url::Url;
 
 /// Convert an MCP SDK [`Root`] into a domain [`Root`].
-pub fn from_sdk_root(root: rust_mcp_sdk::schema::Root) -> Root {
-    Root {
+///
+/// Only `file://` roots are supported. The URI is parsed, its path is
+/// normalized lexically (`.` and `..` segments, trailing slashes) and
+/// re-encoded so equivalent URIs compare equal. Invalid and non-file roots
+/// are logged and dropped.
+pub fn from_sdk_root(root: rust_mcp_sdk::schema::Root) -> Option<Root> {
+    let url = match Url::parse(&root.uri) {
+        Ok(url) => url,
+        Err(err) => {
+            warn!(uri = %root.uri, "Ignoring root with invalid URI: {err}");
+            return None;
+        }
+    };
+    if url.scheme() != "file" {
+        warn!(uri = %root.uri, "Ignoring non-file root");
+        return None;
+    }
+    let Ok(path) = url.to_file_path() else {
+        warn!(uri = %root.uri, "Ignoring root that is not a local path");
+        return None;
+    };
+    let uri = Url::from_file_path(normalize_path(&path)).ok()?;
+    Some(Root {
         name: root.name,
-        uri: root.uri,
+        uri: uri.to_string(),
+    })
+}
+
+/// Convert SDK roots into a deduplicated list of normalized domain roots.
+///
+/// The first occurrence of each root wins, so the client's ordering and
+/// naming are preserved.
+pub fn normalize_roots(roots: Vec<rust_mcp_sdk::schema::Root>) -> Vec<Root> {
+    let mut normalized: Vec<Root> = Vec::with_capacity(roots.len());
+    for root in roots.into_iter().filter_map(from_sdk_root) {
+        if !normalized.iter().any(|r| r.uri == root.uri) {
+            normalized.push(root);
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sdk_root(uri: &str) -> rust_mcp_sdk::schema::Root {
+        rust_mcp_sdk::schema::Root {
+            name: None,
+            uri: uri.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_from_sdk_root_normalizes_path() {
+        let root = from_sdk_root(sdk_root("file:///tmp/./repo/sub/../")).unwrap();
+        assert_eq!(root.uri, "file:///tmp/repo");
+    }
+
+    #[test]
+    fn test_from_sdk_root_rejects_non_file() {
+        assert!(from_sdk_root(sdk_root("https://example.com/repo")).is_none());
+        assert!(from_sdk_root(sdk_root("not a uri")).is_none());
+    }
+
+    #[test]
+    fn test_normalize_roots_deduplicates() {
+        let roots = normalize_roots(vec![
+            rust_mcp_sdk::schema::Root {
+                name: Some("repo".to_string()),
+                uri: "file:///tmp/repo/".to_string(),
+            },
+            sdk_root("file:///tmp/repo"),
+            sdk_root("https://example.com/repo"),
+            sdk_root("file:///tmp/other"),
+        ]);
+        assert_eq!(
+            roots,
+            vec![
+                Root::new(Some("repo".to_string()), "file:///tmp/repo".to_string()),
+                Root::new(None, "file:///tmp/other".to_string()),
+            ]
+        );
     }
 }
@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use mm_git::{GitError, GitRepository, GitResult, GitStatus};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::path::{Path, PathBuf};
+
+/// Git repository returning preconfigured statuses
+///
+/// Paths without a configured status are reported as not being a Git
+/// repository, mirroring what the `git2` adapter does for plain directories.
+#[derive(Debug, Clone, Default)]
+pub struct FakeGitRepository {
+    statuses: HashMap<PathBuf, GitStatus>,
+}
+
+impl FakeGitRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report `status` for the repository at `path`
+    pub fn with_status(mut self, path: impl Into<PathBuf>, status: GitStatus) -> Self {
+        self.statuses.insert(path.into(), status);
+        self
+    }
+
+    /// Status of a clean checkout of `branch` that is in sync with its upstream
+    pub fn clean_status(branch: impl Into<String>) -> GitStatus {
+        GitStatus {
+            branch: branch.into(),
+            is_dirty: false,
+            ahead_by: 0,
+            behind_by: 0,
+            changed_files: vec![],
+        }
+    }
+}
+
+#[async_trait]
+impl GitRepository for FakeGitRepository {
+    type Error = Infallible;
+
+    async fn get_status(&self, path: &Path) -> GitResult<GitStatus, Self::Error> {
+        self.statuses.get(path).cloned().ok_or_else(|| {
+            GitError::repository_error(format!("Not a git repository: {}", path.display()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn returns_configured_status() {
+        let repo = FakeGitRepository::new()
+            .with_status("/tmp/repo", FakeGitRepository::clean_status("main"));
+
+        let status = repo.get_status(Path::new("/tmp/repo")).await.unwrap();
+        assert_eq!(status.branch, "main");
+        assert!(!status.is_dirty);
+        assert!(repo.get_status(Path::new("/tmp/other")).await.is_err());
+    }
+}
@@ -0,0 +1,24 @@
+//! Test doubles for code built on top of Middle Manager.
+//!
+//! The workspace tests wire operations against [`Ports::noop`] and mockall
+//! expectations. This crate packages that setup together with in-memory fakes
+//! so integrations can exercise real behaviour without a Neo4j instance or a
+//! Git checkout:
+//!
+//! - [`FakeMemoryRepository`] keeps entities and relationships in memory. It
+//!   is the `InMemoryRepository` from `mm-memory-inmem`.
+//! - [`FakeGitRepository`] returns canned [`GitStatus`] values per path.
+//! - [`TestPorts`] builds [`Ports`] backed by both fakes.
+//! - [`noop_ports`] returns the mock-backed ports used by the workspace tests.
+#![warn(clippy::all)]
+mod git;
+mod ports;
+
+pub use git::FakeGitRepository;
+pub use mm_memory_inmem::InMemoryRepository as FakeMemoryRepository;
+pub use ports::{FakePorts, TestPorts, noop_ports};
+
+pub use mm_core::{Ports, Root, RootCollection};
+pub use mm_git::GitStatus;
+pub use mm_git::repository::MockGitRepository;
+pub use mm_memory::MockMemoryRepository;
@@ -0,0 +1,133 @@
+use mm_core::{Ports, Root, RootCollection};
+use mm_git::repository::MockGitRepository;
+use mm_git::{GitService, GitStatus};
+use mm_memory::{
+    MemoryConfig, MemoryEntity, MemoryRelationship, MemoryService, MockMemoryRepository,
+};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::FakeMemoryRepository;
+use crate::git::FakeGitRepository;
+
+/// Ports backed by the in-memory fakes
+pub type FakePorts = Ports<FakeMemoryRepository, FakeGitRepository>;
+
+/// Create ports backed by mocks without any expectations
+///
+/// Same as [`Ports::noop`]; configure individual services with
+/// [`Ports::with`].
+pub fn noop_ports() -> Ports<MockMemoryRepository, MockGitRepository> {
+    Ports::noop()
+}
+
+/// Builder for [`FakePorts`]
+///
+/// ```
+/// use mm_test_support::{FakeGitRepository, TestPorts};
+/// use mm_memory::MemoryEntity;
+///
+/// let ports = TestPorts::new()
+///     .entity(MemoryEntity {
+///         name: "project:demo".to_string(),
+///         labels: vec!["Project".to_string()],
+///         ..Default::default()
+///     })
+///     .git_status("/tmp/demo", FakeGitRepository::clean_status("main"))
+///     .build();
+/// # let _ = ports;
+/// ```
+#[derive(Debug, Default)]
+pub struct TestPorts {
+    config: MemoryConfig,
+    entities: Vec<MemoryEntity>,
+    relationships: Vec<MemoryRelationship>,
+    git: FakeGitRepository,
+    roots: Vec<Root>,
+}
+
+impl TestPorts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `config` for the memory service instead of [`MemoryConfig::default`]
+    pub fn memory_config(mut self, config: MemoryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Seed the memory repository with an entity
+    pub fn entity(mut self, entity: MemoryEntity) -> Self {
+        self.entities.push(entity);
+        self
+    }
+
+    /// Seed the memory repository with a relationship
+    pub fn relationship(mut self, relationship: MemoryRelationship) -> Self {
+        self.relationships.push(relationship);
+        self
+    }
+
+    /// Report `status` for the Git repository at `path`
+    pub fn git_status(mut self, path: impl Into<PathBuf>, status: GitStatus) -> Self {
+        self.git = self.git.with_status(path, status);
+        self
+    }
+
+    /// Register a client root
+    pub fn root(mut self, root: Root) -> Self {
+        self.roots.push(root);
+        self
+    }
+
+    pub fn build(self) -> FakePorts {
+        let repository = FakeMemoryRepository::new()
+            .with_entities(self.entities)
+            .with_relationships(self.relationships);
+        Ports::with_all(
+            Arc::new(MemoryService::new(repository, self.config)),
+            Arc::new(GitService::new(self.git)),
+            Arc::new(RwLock::new(RootCollection::from_roots(self.roots))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mm_core::operations::memory::{GetEntityCommand, get_entity};
+    use std::path::Path;
+
+    #[tokio::test]
+    async fn built_ports_serve_seeded_state() {
+        let ports = TestPorts::new()
+            .entity(MemoryEntity {
+                name: "test:entity".to_string(),
+                labels: vec!["Memory".to_string()],
+                ..Default::default()
+            })
+            .git_status("/tmp/repo", FakeGitRepository::clean_status("main"))
+            .root(Root::new(None, "file:///tmp/repo".to_string()))
+            .build();
+
+        let result = get_entity(
+            &ports,
+            GetEntityCommand {
+                name: "test:entity".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.unwrap().name, "test:entity");
+
+        let status = ports
+            .git_service
+            .get_status(Path::new("/tmp/repo"))
+            .await
+            .unwrap();
+        assert_eq!(status.branch, "main");
+        assert_eq!(ports.roots.read().await.roots().len(), 1);
+    }
+}
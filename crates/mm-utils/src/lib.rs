@@ -3,8 +3,13 @@
 #![warn(clippy::all)]
 pub mod json_schema;
 pub mod prop;
+pub mod similarity;
+pub mod template;
 
 pub use json_schema::IntoJsonSchema;
+pub use template::TemplateVars;
+
+use std::path::{Component, Path, PathBuf};
 
 /// Check if a string is in snake_case format.
 ///
@@ -26,9 +31,74 @@ pub fn is_snake_case(s: &str) -> bool {
     s.chars().all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_'))
 }
 
+/// Normalize `.` and `..` segments of a path without touching the filesystem.
+///
+/// `..` never climbs above the filesystem root, and trailing slashes are
+/// dropped. Symlinks are not resolved.
+///
+/// # Examples
+///
+/// ```
+/// use mm_utils::normalize_path;
+/// use std::path::Path;
+///
+/// assert_eq!(normalize_path(Path::new("/a/./b/../c/")), Path::new("/a/c"));
+/// assert_eq!(normalize_path(Path::new("/../a")), Path::new("/a"));
+/// ```
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Convert free text into a snake_case identifier.
+///
+/// ASCII letters are lowercased and digits kept; every run of other
+/// characters becomes a single underscore, with none at either end. An
+/// uppercase letter following a lowercase one starts a new word, so
+/// CamelCase labels split as expected.
+///
+/// # Examples
+///
+/// ```
+/// use mm_utils::slugify;
+///
+/// assert_eq!(slugify("Rust Language"), "rust_language");
+/// assert_eq!(slugify("NamespaceRegistry"), "namespace_registry");
+/// assert_eq!(slugify("  C++ / FFI (v2) "), "c_ffi_v2");
+/// assert_eq!(slugify("!!!"), "");
+/// ```
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    let mut previous_lower = false;
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && previous_lower {
+                slug.push('_');
+            }
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('_') {
+            slug.push('_');
+        }
+        previous_lower = c.is_ascii_lowercase();
+    }
+    if slug.ends_with('_') {
+        slug.pop();
+    }
+    slug
+}
+
 #[cfg(test)]
 mod tests {
-    use super::is_snake_case;
+    use super::{is_snake_case, slugify};
     use arbitrary::{Arbitrary, Unstructured};
     use arbtest::arbtest;
     use std::ops::ControlFlow;
@@ -107,6 +177,19 @@ mod tests {
         assert!(!is_snake_case("hello１23"));
     }
 
+    #[test]
+    fn slugs_are_snake_case() {
+        assert_eq!(slugify("Hello, World!"), "hello_world");
+        assert_eq!(slugify("Café 2024"), "caf_2024");
+        arbtest(|u| {
+            let text: String = u.arbitrary()?;
+            let slug = slugify(&text);
+            assert!(is_snake_case(&slug), "{}", slug);
+            assert!(!slug.starts_with('_') && !slug.ends_with('_'), "{}", slug);
+            Ok(())
+        });
+    }
+
     #[test]
     fn arbitrary_valid_strings() {
         arbtest(|u| {
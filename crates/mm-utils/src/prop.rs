@@ -1,7 +1,7 @@
 //! Additional property helpers for generating memory data live in
 //! `mm_memory::test_helpers`.
 //!
-//! ```
+//! ```ignore
 //! use arbitrary::Unstructured;
 //! use mm_memory::test_helpers::{prop_random_entity, prop_random_relationship};
 //!
@@ -0,0 +1,83 @@
+//! Case-insensitive string similarity for fuzzy name lookups.
+
+use std::collections::HashSet;
+
+/// Number of single-character insertions, deletions and substitutions that
+/// turn `a` into `b`
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+fn trigrams(s: &str) -> HashSet<[char; 3]> {
+    // Pad so short strings and word boundaries still produce trigrams
+    let chars: Vec<char> = "  ".chars().chain(s.chars()).chain([' ']).collect();
+    chars.windows(3).map(|w| [w[0], w[1], w[2]]).collect()
+}
+
+/// Similarity of `a` and `b` between 0 and 1, ignoring case
+///
+/// The better of the normalized Levenshtein distance, which catches typos,
+/// and trigram overlap, which catches reordered or missing segments.
+///
+/// # Examples
+///
+/// ```
+/// use mm_utils::similarity::similarity;
+///
+/// assert_eq!(similarity("Middle", "middle"), 1.0);
+/// assert!(similarity("project:midle_manager", "project:middle_manager") > 0.9);
+/// assert!(similarity("abc", "xyz") < 0.1);
+/// ```
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let a = a.to_lowercase();
+    let b = b.to_lowercase();
+    if a == b {
+        return 1.0;
+    }
+    let len = a.chars().count().max(b.chars().count());
+    let edit = 1.0 - levenshtein(&a, &b) as f64 / len as f64;
+
+    let (ta, tb) = (trigrams(&a), trigrams(&b));
+    let union = ta.union(&tb).count();
+    let trigram = if union == 0 {
+        0.0
+    } else {
+        ta.intersection(&tb).count() as f64 / union as f64
+    };
+    edit.max(trigram)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein_distances() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("middle", "midle"), 1);
+    }
+
+    #[test]
+    fn similarity_ranks_typos_above_unrelated_names() {
+        let target = "andoriyu:project:middle_manager";
+        let typo = similarity("andoriyu:project:midle_manager", target);
+        let reordered = similarity("middle_manager", target);
+        let unrelated = similarity("someone:task:write_docs", target);
+        assert!(typo > reordered);
+        assert!(reordered > unrelated);
+        assert_eq!(similarity("", ""), 1.0);
+    }
+}
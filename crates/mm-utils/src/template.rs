@@ -0,0 +1,111 @@
+//! Variable substitution for config-defined text such as prompts and
+//! templates.
+//!
+//! Variables are written as `{{name}}`, optionally with whitespace inside the
+//! braces. Unknown variables and unterminated placeholders are left as-is so
+//! a typo shows up in the rendered text instead of silently disappearing.
+
+/// Values available to templates
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateVars {
+    /// Replaces `{{project}}`
+    pub project: Option<String>,
+    /// Replaces `{{agent}}`
+    pub agent: Option<String>,
+    /// Replaces `{{date}}`
+    pub date: Option<String>,
+}
+
+impl TemplateVars {
+    /// Value of the variable called `name`
+    pub fn get(&self, name: &str) -> Option<&str> {
+        match name {
+            "project" => self.project.as_deref(),
+            "agent" => self.agent.as_deref(),
+            "date" => self.date.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Substitute these variables into `template`
+    pub fn render(&self, template: &str) -> String {
+        render(template, |name| self.get(name))
+    }
+}
+
+/// Substitute `{{name}}` placeholders in `template` using `lookup`
+///
+/// # Examples
+///
+/// ```
+/// use mm_utils::template::render;
+///
+/// let out = render("Hi {{ agent }}, {{missing}}", |name| {
+///     (name == "agent").then_some("bot")
+/// });
+/// assert_eq!(out, "Hi bot, {{missing}}");
+/// ```
+pub fn render<'a, F>(template: &str, lookup: F) -> String
+where
+    F: Fn(&str) -> Option<&'a str>,
+{
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        match lookup(after[..end].trim()) {
+            Some(value) => out.push_str(value),
+            None => out.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> TemplateVars {
+        TemplateVars {
+            project: Some("mm".to_string()),
+            agent: Some("claude".to_string()),
+            date: Some("2024-05-01".to_string()),
+        }
+    }
+
+    #[test]
+    fn substitutes_known_variables() {
+        assert_eq!(
+            vars().render("{{agent}} on {{ project }} at {{date}}"),
+            "claude on mm at 2024-05-01"
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_and_unset_variables() {
+        let vars = TemplateVars {
+            date: None,
+            ..vars()
+        };
+        assert_eq!(vars.render("{{date}} {{other}}"), "{{date}} {{other}}");
+    }
+
+    #[test]
+    fn keeps_unterminated_placeholder() {
+        assert_eq!(vars().render("a {{agent}} {{agent"), "a claude {{agent");
+    }
+
+    #[test]
+    fn text_without_placeholders_is_unchanged() {
+        assert_eq!(vars().render("plain { text }"), "plain { text }");
+    }
+}